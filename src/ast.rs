@@ -5,22 +5,81 @@
 
 use crate::span::Span;
 use crate::syntax;
+use crate::types::IntTy;
 
 pub type Ast = Vec<Item>;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Item {
     Function(Function),
     Struct(Struct),
     Enum(EnumDecl),
     Import(ImportDecl),
     Error(ErrorDecl),
+    Impl(ImplDecl),
+    Interface(InterfaceDecl),
+    Const(ConstDecl),
+    Global(GlobalDecl),
+}
+
+impl Item {
+    /// The functions this item declares — itself, or an impl's methods
+    /// (ADR 0040) — so passes that walk functions see methods too.
+    pub fn functions(&self) -> &[Function] {
+        match self {
+            Item::Function(f) => std::slice::from_ref(f),
+            Item::Impl(i) => &i.methods,
+            _ => &[],
+        }
+    }
+}
+
+/// `interface Ordered { fun less(self, other: Self): bool; }` — a set
+/// of method signatures a type satisfies by having them (ADR 0041).
+/// `self` is annotated `Self`, which stands for the satisfying type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceDecl {
+    pub exported: bool,
+    pub name: String,
+    pub methods: Vec<MethodSig>,
+    pub span: Span,
+}
+
+/// One required method of an interface: a body-less header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSig {
+    pub name: String,
+    pub params: Vec<Param>,
+    pub return_type: Option<TypeAnn>,
+    pub span: Span,
+}
+
+/// `T: Ordered` in a function's type-parameter list (ADR 0041): the
+/// argument bound to `param` must satisfy `interface`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bound {
+    pub param: String,
+    pub interface: String,
+    pub span: Span,
+}
+
+/// `impl Tree { fun sum(self): int { … } }` — methods on a struct,
+/// refstruct, or enum declared in the same file (ADR 0040). Each method
+/// is an ordinary `Function` named `Tree.sum` whose first parameter,
+/// `self`, is annotated with the impl's type; a generic impl
+/// (`impl Pair<T, U>`) prepends its parameters to every method's.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplDecl {
+    pub name: String,
+    pub type_params: Vec<(String, Span)>,
+    pub methods: Vec<Function>,
+    pub span: Span,
 }
 
 /// `enum Shape { Circle(float), Ready }` — a payload enum (ADR 0036):
 /// variants carry zero or more positional payload types. Generic like
 /// structs (ADR 0035).
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumDecl {
     pub exported: bool,
     pub name: String,
@@ -29,26 +88,51 @@ pub struct EnumDecl {
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Variant {
     pub name: String,
     pub payloads: Vec<TypeAnn>,
     pub span: Span,
 }
 
-/// `error NotFound, Timeout;` — module-scoped error codes (ADR 0034).
-/// Each name keeps its span so duplicate/resolution diagnostics can
-/// point at the exact identifier.
-#[derive(Debug, PartialEq)]
+/// `error NotFound, ParseFailed(int, string);` — module-scoped error
+/// codes (ADR 0034). Each code is shaped like an enum variant: a name
+/// with its span, for duplicate/resolution diagnostics, and positional
+/// payload types (ADR 0062).
+#[derive(Debug, Clone, PartialEq)]
 pub struct ErrorDecl {
     pub exported: bool,
-    pub names: Vec<(String, Span)>,
+    pub codes: Vec<Variant>,
+    pub span: Span,
+}
+
+/// `const LIMIT: int = 1024;` at module level (ADR 0047) — a named
+/// value whose initializer runs once, at compile time. The annotation
+/// is required.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstDecl {
+    pub exported: bool,
+    pub name: String,
+    pub ty: TypeAnn,
+    pub value: Expr,
+    pub span: Span,
+}
+
+/// `var hits: int = 0;` at module level (ADR 0048) — mutable state
+/// initialized at run time, before `main`, in module dependency order.
+/// The annotation is required.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalDecl {
+    pub exported: bool,
+    pub name: String,
+    pub ty: TypeAnn,
+    pub value: Expr,
     pub span: Span,
 }
 
 /// `import { a, b } from "./path";` — each name keeps its own span so
 /// resolution errors can point at the exact identifier.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ImportDecl {
     pub names: Vec<(String, Span)>,
     pub path: String,
@@ -56,7 +140,7 @@ pub struct ImportDecl {
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Function {
     pub exported: bool,
     pub name: String,
@@ -64,19 +148,22 @@ pub struct Function {
     /// keeps its span so shadowing/duplicate diagnostics point at it.
     /// Empty for ordinary functions.
     pub type_params: Vec<(String, Span)>,
+    /// `<T: Ordered>` — interface bounds on those parameters (ADR 0041),
+    /// checked at each instantiation.
+    pub bounds: Vec<Bound>,
     pub params: Vec<Param>,
     pub return_type: Option<TypeAnn>,
     pub body: Vec<Stmt>,
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Param {
     pub name: String,
     pub ty: TypeAnn,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Struct {
     pub exported: bool,
     /// True for `refstruct` — reference semantics (shared, aliased) instead
@@ -89,7 +176,7 @@ pub struct Struct {
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Field {
     pub name: String,
     pub ty: TypeAnn,
@@ -102,6 +189,8 @@ pub enum TypeAnn {
     Bool,
     Str,
     File,
+    /// `u8`, `i32`, … (ADR 0052).
+    Sized(IntTy),
     Named(String),
     /// `Pair<int, string>` — a generic type applied to arguments
     /// (ADR 0035).
@@ -118,9 +207,18 @@ pub enum TypeAnn {
     Optional(Box<TypeAnn>),
     /// `T[]` — a growable array of T, reference semantics like refstruct.
     Array(Box<TypeAnn>),
+    /// `T[4]` — a fixed-size array of T (ADR 0056): the elements
+    /// inline, value semantics like a struct.
+    Fixed(Box<TypeAnn>, usize),
+    /// `fun(int, string): int` — a function type (ADR 0038); `None`
+    /// is a unit return, as in declarations.
+    Fn(Vec<TypeAnn>, Option<Box<TypeAnn>>),
+    /// `(int, string)` — an anonymous value tuple (ADR 0043); always
+    /// two or more elements.
+    Tuple(Vec<TypeAnn>),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
     /// `var`/`const` binding. `mutable` is true for `var`. The annotation is
     /// optional (`var x: Node? = null;`) — required only when the
@@ -132,23 +230,74 @@ pub enum Stmt {
         value: Expr,
         span: Span,
     },
+    /// `const (a, b) = f();` — binds each element of a tuple (ADR
+    /// 0043). The names take the element types, the way `for` names
+    /// take the element type; `_` skips an element.
+    LetTuple {
+        mutable: bool,
+        names: Vec<String>,
+        value: Expr,
+        span: Span,
+    },
+    /// `const { x, y: py } = p;` — binds fields of a struct by name
+    /// (ADR 0057); `y: py` binds field `y` as `py`. Each entry is the
+    /// field, the bound name, and the field's span.
+    LetStruct {
+        mutable: bool,
+        fields: Vec<(String, String, Span)>,
+        value: Expr,
+        span: Span,
+    },
+    /// `const Circle(r) = shape else { return 0; };` — binds a variant
+    /// pattern's names in the enclosing scope, or runs `else`, which
+    /// must leave it (ADR 0057). Without `else` the pattern must match
+    /// every value.
+    LetElse {
+        mutable: bool,
+        pattern: Pattern,
+        value: Expr,
+        else_body: Option<Vec<Stmt>>,
+        span: Span,
+    },
     /// `target = value;` — target is a place: a variable or a field chain
     /// rooted at one (`x`, `p.x`, `o.i.v`). The parser rejects anything else.
+    /// `op` is set for a compound assignment `target op= value` (ADR 0050);
+    /// `x++;` and `x--;` parse as `+= 1` and `-= 1`, with `step` set so
+    /// diagnostics can name the operator the source wrote.
     Assign {
         target: Expr,
+        op: Option<BinOp>,
         value: Expr,
+        step: bool,
         span: Span,
     },
     Return {
         value: Option<Expr>,
         span: Span,
     },
-    /// `break;` — exits the innermost enclosing loop (ADR 0019).
+    /// `break;` — exits the innermost enclosing loop (ADR 0019), or
+    /// the loop named by `break outer;` (ADR 0059).
     Break {
+        label: Option<String>,
         span: Span,
     },
-    /// `continue;` — skips to the innermost loop's next iteration.
+    /// `continue;` — skips to the innermost loop's next iteration, or
+    /// the named loop's.
     Continue {
+        label: Option<String>,
+        span: Span,
+    },
+    /// `defer close(f);` / `defer { … }` — the body runs when the
+    /// enclosing block exits, by any edge, after the statements that
+    /// follow it; a block's defers run last-registered first (ADR 0060).
+    Defer {
+        body: Vec<Stmt>,
+        span: Span,
+    },
+    /// `arena { … }` — the block's allocations bind to a region that
+    /// frees wholesale when the block exits, by any edge (ADR 0061).
+    Arena {
+        body: Vec<Stmt>,
         span: Span,
     },
     If {
@@ -158,11 +307,33 @@ pub enum Stmt {
         else_body: Option<Vec<Stmt>>,
         span: Span,
     },
+    /// `outer: while … { … }` names the loop for `break outer;` and
+    /// `continue outer;` (ADR 0059); so does the label of a `for`.
     While {
+        label: Option<String>,
         cond: Expr,
         body: Vec<Stmt>,
         span: Span,
     },
+    /// `if const v = e { … }` — runs `then_body` with `v` bound to the
+    /// payload of a present `T?` or a successful `T!`, or with a variant
+    /// pattern's names when `e` matches it (ADR 0058).
+    IfConst {
+        pattern: Pattern,
+        value: Expr,
+        then_body: Vec<Stmt>,
+        else_body: Option<Vec<Stmt>>,
+        span: Span,
+    },
+    /// `while const line = readLine() { … }` — `value` runs before each
+    /// pass; the loop ends at the first that does not bind (ADR 0058).
+    WhileConst {
+        label: Option<String>,
+        pattern: Pattern,
+        value: Expr,
+        body: Vec<Stmt>,
+        span: Span,
+    },
     /// `match s { Circle(r) { … } else { … } }` — variant dispatch
     /// (ADR 0036). Arms are blocks; payload bindings are consts scoped
     /// to their arm; `else` covers the rest.
@@ -174,8 +345,9 @@ pub enum Stmt {
     },
     /// `for x in xs { … }` — iterates an array; `x` is a const binding of
     /// the element type, fresh each iteration. `for [i, x] in xs` also
-    /// binds the const int index.
+    /// binds the const int index. `for i in a..b` counts (ADR 0051).
     For {
+        label: Option<String>,
         index: Option<String>,
         name: String,
         iterable: Expr,
@@ -191,6 +363,8 @@ pub enum Conv {
     Int,
     Float,
     Str,
+    /// `u8(x)`, `i32(x)`, … — truncating to the width (ADR 0052).
+    Sized(IntTy),
 }
 
 impl Conv {
@@ -200,31 +374,126 @@ impl Conv {
             Conv::Int => syntax::KW_INT,
             Conv::Float => syntax::KW_FLOAT,
             Conv::Str => syntax::KW_STRING,
+            Conv::Sized(t) => t.name(),
         }
     }
 }
 
-/// One `Variant(a, _, b) { … }` arm (ADR 0036). A `_` binding skips
-/// its payload; counts must match the variant.
-#[derive(Debug, PartialEq)]
-pub struct MatchArm {
-    pub variant: String,
-    pub variant_span: Span,
-    pub bindings: Vec<(String, Span)>,
-    pub body: Vec<Stmt>,
+/// One `Variant(a, _, b) { … }` arm (ADR 0036). The head is a
+/// pattern, optionally guarded: `Circle(r) if r > 1.0 { … }` (ADR
+/// 0045). The match expression's `Variant(a) => e` arms carry an
+/// expression body (ADR 0044).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm<B = Vec<Stmt>> {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: B,
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A match pattern (ADR 0045). An arm head is a `Variant` — a bare
+/// name there names the variant — or, on an int or string scrutinee,
+/// a `Lit`, or on an error one an `Error` (ADR 0046). Inside a payload
+/// list a bare name binds, so a nested nullary variant takes
+/// parentheses: `Some(Dot())`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// `name` binds the payload as a const; `_` skips it.
+    Bind(String, Span),
+    /// An int, string, or bool literal; `-1` folds into the literal.
+    Lit(Expr),
+    /// `Circle(r)`: the variant, then one pattern per payload.
+    Variant {
+        name: String,
+        name_span: Span,
+        args: Vec<Pattern>,
+        span: Span,
+    },
+    /// `error.Name`, or `error.ParseFailed(line, _)` with one pattern
+    /// per payload (ADR 0062). `name_span` covers `error.Name`.
+    Error {
+        name: String,
+        name_span: Span,
+        args: Vec<Pattern>,
+        span: Span,
+    },
+}
+
+impl Pattern {
+    pub fn span(&self) -> Span {
+        match self {
+            Pattern::Bind(_, span)
+            | Pattern::Variant { span, .. }
+            | Pattern::Error { span, .. } => *span,
+            Pattern::Lit(e) => e.span(),
+        }
+    }
+
+    /// Nested variants parenthesize, `(Circle r)`; an arm head spreads
+    /// its payloads into the arm's own list instead.
+    #[cfg(test)]
+    pub fn sexpr(&self) -> String {
+        match self {
+            Pattern::Bind(name, _) => name.clone(),
+            Pattern::Lit(e) => e.sexpr(),
+            Pattern::Error { name, args, .. } if args.is_empty() => format!("error.{name}"),
+            Pattern::Variant { .. } | Pattern::Error { .. } => format!("({})", self.head_sexpr()),
+        }
+    }
+
+    #[cfg(test)]
+    fn head_sexpr(&self) -> String {
+        match self {
+            Pattern::Variant { name, args, .. } => {
+                let mut head = vec![name.clone()];
+                head.extend(args.iter().map(Pattern::sexpr));
+                head.join(" ")
+            }
+            Pattern::Error { name, args, .. } if !args.is_empty() => {
+                let mut head = vec![format!("error.{name}")];
+                head.extend(args.iter().map(Pattern::sexpr));
+                head.join(" ")
+            }
+            other => other.sexpr(),
+        }
+    }
+}
+
+impl<B> MatchArm<B> {
+    /// The arm's head for sexprs: pattern, then any guard.
+    #[cfg(test)]
+    pub fn head_sexpr(&self) -> String {
+        match &self.guard {
+            Some(g) => format!("{} if {}", self.pattern.head_sexpr(), g.sexpr()),
+            None => self.pattern.head_sexpr(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
     Int(i64, Span),
     Float(f64, Span),
     Bool(bool, Span),
     Str(String, Span),
     Ident(String, Span),
+    /// `identity<int>` — a generic function instance named as a value
+    /// (ADR 0038). Plain function names stay `Ident`s; the checker
+    /// tells values from variables.
+    FnRef {
+        name: String,
+        type_args: Vec<TypeAnn>,
+        span: Span,
+    },
     Null(Span),
-    /// `error.Name` — an error-code literal (ADR 0034).
-    ErrorLit(String, Span),
+    /// `error.Name` — an error-code literal (ADR 0034) — or
+    /// `error.ParseFailed(12, "eof")`, a code with its payloads
+    /// (ADR 0062).
+    ErrorLit {
+        name: String,
+        args: Vec<Expr>,
+        span: Span,
+    },
     /// Bare `error` — the state marker, legal only as an `==`/`!=`
     /// operand against an error union (ADR 0034).
     ErrorKind(Span),
@@ -292,11 +561,68 @@ pub enum Expr {
         elements: Vec<Expr>,
         span: Span,
     },
+    /// `[0; 16]` — a fixed array of `count` copies of one value
+    /// (ADR 0056); the value runs once.
+    ArrayRepeat {
+        value: Box<Expr>,
+        count: usize,
+        span: Span,
+    },
+    /// `(1, "a")` — a tuple literal (ADR 0043); `(x)` stays a
+    /// grouping, so there is no one-element tuple.
+    TupleLit {
+        elements: Vec<Expr>,
+        span: Span,
+    },
+    /// `{ "a": 1, "b": 2 }` — a map literal (ADR 0042): entries in
+    /// source order, which is also the map's iteration order; `{}` is
+    /// the empty map, typed by its declared slot like `[]`.
+    MapLit {
+        entries: Vec<(Expr, Expr)>,
+        span: Span,
+    },
     Index {
         base: Box<Expr>,
         index: Box<Expr>,
         span: Span,
     },
+    /// `a..b`, `a..=b`, `a..b step s` — an int range (ADR 0051). The
+    /// parser builds it only as a `for` iterable or as slice bounds in
+    /// index brackets; only slice bounds may leave an end open.
+    Range {
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+        inclusive: bool,
+        step: Option<Box<Expr>>,
+        span: Span,
+    },
+    /// `t.sum(1)` — a method call (ADR 0040) on any receiver but a bare
+    /// name: `ident.ident(…)` stays `EnumLit`, and the checker decides
+    /// whether the name is an enum or a receiver variable.
+    MethodCall {
+        receiver: Box<Expr>,
+        method: String,
+        args: Vec<Expr>,
+        span: Span,
+    },
+    /// `match s { Circle(r) => r * r, else => 0.0 }` — the expression
+    /// form of variant dispatch (ADR 0044): every arm yields a value,
+    /// and the arms (or `else`) must cover the enum.
+    Match {
+        scrutinee: Box<Expr>,
+        arms: Vec<MatchArm<Expr>>,
+        else_value: Option<Box<Expr>>,
+        span: Span,
+    },
+    /// `fun (x: int): int { … }` — a function literal (ADR 0039). Free
+    /// names are captured when it evaluates: values copied, refstructs
+    /// and arrays by handle. Every parameter is annotated.
+    Lambda {
+        params: Vec<Param>,
+        return_type: Option<TypeAnn>,
+        body: Vec<Stmt>,
+        span: Span,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -316,12 +642,23 @@ pub enum BinOp {
     Ge,
     /// `??` — left side unless it's null, then the (lazily evaluated) right.
     Coalesce,
+    /// Bitwise `&`, `|`, `^` on `int` (ADR 0049).
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// `<<`, `>>` (sign-filling), `>>>` (zero-filling). A count outside
+    /// `0..=63` is a runtime error in both engines (ADR 0049).
+    Shl,
+    Shr,
+    UShr,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum UnOp {
     Neg,
     Not,
+    /// `~` — bitwise complement of an `int` (ADR 0049).
+    BitNot,
 }
 
 impl BinOp {
@@ -341,6 +678,12 @@ impl BinOp {
             BinOp::Gt => ">",
             BinOp::Ge => ">=",
             BinOp::Coalesce => "??",
+            BinOp::BitAnd => "&",
+            BinOp::BitOr => "|",
+            BinOp::BitXor => "^",
+            BinOp::Shl => "<<",
+            BinOp::Shr => ">>",
+            BinOp::UShr => ">>>",
         }
     }
 }
@@ -351,6 +694,7 @@ impl UnOp {
         match self {
             UnOp::Neg => "-",
             UnOp::Not => "!",
+            UnOp::BitNot => "~",
         }
     }
 }
@@ -359,12 +703,19 @@ impl Stmt {
     pub fn span(&self) -> Span {
         match self {
             Stmt::Let { span, .. }
+            | Stmt::LetTuple { span, .. }
+            | Stmt::LetStruct { span, .. }
+            | Stmt::LetElse { span, .. }
             | Stmt::Assign { span, .. }
             | Stmt::Return { span, .. }
-            | Stmt::Break { span }
-            | Stmt::Continue { span }
+            | Stmt::Break { span, .. }
+            | Stmt::Continue { span, .. }
+            | Stmt::Defer { span, .. }
+            | Stmt::Arena { span, .. }
             | Stmt::If { span, .. }
             | Stmt::While { span, .. }
+            | Stmt::IfConst { span, .. }
+            | Stmt::WhileConst { span, .. }
             | Stmt::Match { span, .. }
             | Stmt::For { span, .. } => *span,
             Stmt::Expr(e) => e.span(),
@@ -402,6 +753,16 @@ impl Expr {
         }
     }
 
+    /// The receiver of an `ident.ident(…)` method call (ADR 0040): the
+    /// base name as a plain identifier spanning just the name — where
+    /// the checker records the receiver's (narrowed) type.
+    pub fn enum_lit_receiver(name: &str, span: Span) -> Expr {
+        Expr::Ident(
+            name.to_string(),
+            Span::new(span.start, span.start + name.len()),
+        )
+    }
+
     pub fn span(&self) -> Span {
         match self {
             Expr::Int(_, s)
@@ -410,9 +771,10 @@ impl Expr {
             | Expr::Str(_, s)
             | Expr::Ident(_, s)
             | Expr::Null(s)
-            | Expr::ErrorLit(_, s)
             | Expr::ErrorKind(s) => *s,
-            Expr::Try { span, .. } => *span,
+            Expr::Try { span, .. } | Expr::FnRef { span, .. } | Expr::ErrorLit { span, .. } => {
+                *span
+            }
             Expr::Unary { span, .. }
             | Expr::Convert { span, .. }
             | Expr::Binary { span, .. }
@@ -421,7 +783,14 @@ impl Expr {
             | Expr::StructLit { span, .. }
             | Expr::EnumLit { span, .. }
             | Expr::ArrayLit { span, .. }
-            | Expr::Index { span, .. } => *span,
+            | Expr::ArrayRepeat { span, .. }
+            | Expr::TupleLit { span, .. }
+            | Expr::MapLit { span, .. }
+            | Expr::Index { span, .. }
+            | Expr::Range { span, .. }
+            | Expr::MethodCall { span, .. }
+            | Expr::Match { span, .. }
+            | Expr::Lambda { span, .. } => *span,
         }
     }
 
@@ -434,8 +803,15 @@ impl Expr {
             Expr::Bool(b, _) => b.to_string(),
             Expr::Str(s, _) => format!("{s:?}"),
             Expr::Ident(name, _) => name.clone(),
+            Expr::FnRef {
+                name, type_args, ..
+            } => format!("{name}{}", show_type_args(type_args)),
             Expr::Null(_) => "null".to_string(),
-            Expr::ErrorLit(n, _) => format!("error.{n}"),
+            Expr::ErrorLit { name, args, .. } if args.is_empty() => format!("error.{name}"),
+            Expr::ErrorLit { name, args, .. } => {
+                let args: Vec<String> = args.iter().map(Expr::sexpr).collect();
+                format!("(error.{name} {})", args.join(" "))
+            }
             Expr::ErrorKind(_) => "error".to_string(),
             Expr::Try { expr, .. } => format!("(try {})", expr.sexpr()),
             Expr::Unary { op, rhs, .. } => format!("({} {})", op.symbol(), rhs.sexpr()),
@@ -505,9 +881,75 @@ impl Expr {
                 let es: Vec<String> = elements.iter().map(Expr::sexpr).collect();
                 format!("[{}]", es.join(" "))
             }
+            Expr::ArrayRepeat { value, count, .. } => format!("[{}; {count}]", value.sexpr()),
+            Expr::Match {
+                scrutinee,
+                arms,
+                else_value,
+                ..
+            } => {
+                let mut parts = vec![scrutinee.sexpr()];
+                for arm in arms {
+                    parts.push(format!("({} => {})", arm.head_sexpr(), arm.body.sexpr()));
+                }
+                if let Some(e) = else_value {
+                    parts.push(format!("(else => {})", e.sexpr()));
+                }
+                format!("(match {})", parts.join(" "))
+            }
+            Expr::TupleLit { elements, .. } => {
+                let es: Vec<String> = elements.iter().map(Expr::sexpr).collect();
+                format!("(tuple {})", es.join(" "))
+            }
+            Expr::MapLit { entries, .. } => {
+                let es: Vec<String> = entries
+                    .iter()
+                    .map(|(k, v)| format!(" {}={}", k.sexpr(), v.sexpr()))
+                    .collect();
+                format!("(map{})", es.concat())
+            }
             Expr::Index { base, index, .. } => {
                 format!("(idx {} {})", base.sexpr(), index.sexpr())
             }
+            Expr::Range {
+                start,
+                end,
+                inclusive,
+                step,
+                ..
+            } => {
+                let bound =
+                    |b: &Option<Box<Expr>>| b.as_ref().map_or("_".to_string(), |e| e.sexpr());
+                let op = if *inclusive { "..=" } else { ".." };
+                let step = step
+                    .as_ref()
+                    .map_or(String::new(), |s| format!(" step {}", s.sexpr()));
+                format!("({op} {} {}{step})", bound(start), bound(end))
+            }
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+                ..
+            } => {
+                let args: Vec<String> = args.iter().map(Expr::sexpr).collect();
+                format!("(method {} {method} {})", receiver.sexpr(), args.join(" "))
+            }
+            Expr::Lambda {
+                params,
+                return_type,
+                body,
+                ..
+            } => {
+                let ps: Vec<String> = params
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name, p.ty.show()))
+                    .collect();
+                let ret = return_type
+                    .as_ref()
+                    .map_or(String::new(), |t| format!(": {}", t.show()));
+                format!("(fun ({}){ret} {{{} stmts}})", ps.join(", "), body.len())
+            }
         }
     }
 }
@@ -533,6 +975,7 @@ impl TypeAnn {
             TypeAnn::Bool => "bool".to_string(),
             TypeAnn::Str => "string".to_string(),
             TypeAnn::File => "file".to_string(),
+            TypeAnn::Sized(t) => t.name().to_string(),
             TypeAnn::Named(n) => n.clone(),
             TypeAnn::Applied(n, args) => format!("{n}{}", show_type_args(args)),
             TypeAnn::Resolved(t) => t.name(),
@@ -540,6 +983,18 @@ impl TypeAnn {
             TypeAnn::ErrUnion(inner) => format!("{}!", inner.show()),
             TypeAnn::Optional(inner) => format!("{}?", inner.show()),
             TypeAnn::Array(inner) => format!("{}[]", inner.show()),
+            TypeAnn::Fixed(inner, n) => format!("{}[{n}]", inner.show()),
+            TypeAnn::Fn(params, ret) => {
+                let parts: Vec<String> = params.iter().map(TypeAnn::show).collect();
+                match ret {
+                    Some(r) => format!("(fun({}): {})", parts.join(", "), r.show()),
+                    None => format!("(fun({}))", parts.join(", ")),
+                }
+            }
+            TypeAnn::Tuple(elems) => {
+                let parts: Vec<String> = elems.iter().map(TypeAnn::show).collect();
+                format!("({})", parts.join(", "))
+            }
         }
     }
 }
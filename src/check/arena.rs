@@ -0,0 +1,787 @@
+//! Arena blocks (ADR 0061): the escape analysis. Everything an `arena`
+//! block allocates frees when the block exits, so no handle made inside
+//! may outlive it. The check is syntactic and conservative. A value is
+//! *region-born* unless it is a literal, `null`, or read from a place
+//! rooted at a binding declared outside the innermost arena; a
+//! region-born value that holds a handle may not be assigned under an
+//! outer root, pushed or inserted into an outer container, or returned.
+//! A binding given outer memory anywhere in the arena — bound from an
+//! outer place, assigned one, or stored one into — aliases it, so
+//! writes through it count as outer writes too; they are judged when
+//! the block ends, loop back edges included. What a call
+//! or a function literal does with the handles it reaches takes the
+//! whole program, so those uses are recorded here and judged once every
+//! body is checked (`escape.rs`).
+
+use super::*;
+
+/// `holds_handle`'s recursion cap, as `EQ_FUEL` is `reaches_err_union`'s.
+pub(super) const HANDLE_FUEL: usize = 64;
+
+/// An arena use only a whole-program summary can judge, recorded while
+/// its body checks.
+pub(super) enum ArenaUse {
+    /// A call to a user function or a function value.
+    Call { span: Span, call: CallFacts },
+    /// A function literal made in the arena; its captures stand after
+    /// its parameters, like extra arguments.
+    Lambda {
+        span: Span,
+        params: usize,
+        captures: Vec<ArgFacts>,
+    },
+    /// `try operand`: is any error it raises carrying a handle?
+    Try { span: Span, operand: Expr },
+    /// A region-born value written through `target` — `'name'`, an
+    /// inner binding that holds a call's result, or `this container`
+    /// for a computed one: an escape if the result is outer memory.
+    Through {
+        span: Span,
+        target: String,
+        assigned: bool,
+        via: Vec<CallFacts>,
+    },
+}
+
+/// What a call passes, as the arena sees it.
+#[derive(Clone)]
+pub(super) struct CallFacts {
+    pub(super) callee: Callee,
+    /// `'name'`, or `this call` for a computed callee.
+    pub(super) label: String,
+    /// The receiver first for a method call.
+    pub(super) args: Vec<ArgFacts>,
+}
+
+#[derive(Clone)]
+pub(super) enum Callee {
+    Direct((usize, String)),
+    /// A function value: any body of this type may run.
+    Indirect(Type),
+}
+
+#[derive(Clone)]
+pub(super) struct ArgFacts {
+    pub(super) span: Span,
+    /// Region-born and holding a handle.
+    pub(super) escapes: bool,
+    pub(super) reach: Reach,
+}
+
+/// What a value may point into.
+#[derive(Clone, Default)]
+pub(super) struct Reach {
+    /// Memory outside the innermost arena.
+    pub(super) outer: bool,
+    /// Whatever these calls' results point into.
+    pub(super) via: Vec<CallFacts>,
+    /// Whatever these inner bindings point into by the time the arena
+    /// ends; settled then (`ArenaFrame::settle`), empty after.
+    pub(super) bindings: Vec<usize>,
+}
+
+impl Reach {
+    fn join(mut self, other: Reach) -> Reach {
+        self.outer |= other.outer;
+        self.via.extend(other.via);
+        self.bindings.extend(other.bindings);
+        self
+    }
+}
+
+/// One open `arena` block. What an inner binding reaches is only
+/// known once the whole block is checked — a later assignment, or one
+/// on a loop's next pass, may make it an alias — so writes through
+/// inner bindings wait in `pending` until the block ends.
+pub(super) struct ArenaFrame {
+    /// `scopes.len()` when the block opened: bindings below it are
+    /// outer.
+    base: usize,
+    /// `arena_uses.len()` when the block opened: the uses recorded
+    /// since name its inner bindings.
+    uses: usize,
+    /// Each inner binding by scope depth and name, numbered as `bind`
+    /// makes it: a shadowing or a later loop's binding of the same
+    /// name is another binding.
+    ids: HashMap<(usize, String), usize>,
+    /// Bindings made so far: the next one's id.
+    bound: usize,
+    /// Bindings given outer memory somewhere in the block — bound,
+    /// assigned, or stored into from an outer place. Written through,
+    /// they reach outer memory.
+    aliases: HashSet<usize>,
+    /// Bindings given a call's result: outer memory if the call
+    /// returns some.
+    via: HashMap<usize, Vec<CallFacts>>,
+    /// Bindings given another inner binding's value: they reach
+    /// whatever it does.
+    from: HashMap<usize, HashSet<usize>>,
+    /// Region-born values written through inner places.
+    pending: Vec<Pending>,
+    /// What the binding head being checked reaches: every name `bind`
+    /// makes meanwhile reaches it too.
+    reading: Reach,
+}
+
+/// A region-born value written through an inner place, judged when
+/// the arena ends.
+struct Pending {
+    span: Span,
+    /// What the place written through reaches.
+    place: Reach,
+    /// `'name'` for a binding, or `this place` / `this container`.
+    label: String,
+    /// The builtin for a `push`/`insert`; `None` for an assignment.
+    store: Option<String>,
+}
+
+impl ArenaFrame {
+    /// Binding `id` now holds a value that reaches `reach`.
+    fn note(&mut self, id: usize, reach: Reach) {
+        if reach.outer {
+            self.aliases.insert(id);
+        }
+        if !reach.via.is_empty() {
+            self.via.entry(id).or_default().extend(reach.via);
+        }
+        if !reach.bindings.is_empty() {
+            self.from.entry(id).or_default().extend(reach.bindings);
+        }
+    }
+
+    /// The block has ended: resolves the inner bindings in the uses it
+    /// recorded, and judges its pending writes.
+    fn settle(self, uses: &mut [ArenaUse], diags: &mut Vec<Diagnostic>) -> Vec<ArenaUse> {
+        let mut settle = Settle {
+            frame: &self,
+            done: HashMap::new(),
+            busy: HashSet::new(),
+        };
+        for used in uses.iter_mut() {
+            match used {
+                ArenaUse::Call { call, .. } => settle.call(call),
+                ArenaUse::Lambda { captures, .. } => {
+                    for capture in captures {
+                        settle.reach(&mut capture.reach);
+                    }
+                }
+                ArenaUse::Through { via, .. } => via.iter_mut().for_each(|c| settle.call(c)),
+                ArenaUse::Try { .. } => {}
+            }
+        }
+        let mut through = Vec::new();
+        for p in &self.pending {
+            let mut place = p.place.clone();
+            settle.reach(&mut place);
+            if place.outer {
+                let diag = match &p.store {
+                    Some(name) => Diagnostic::error(
+                        format!(
+                            "'{name}' stores a value that dies with this arena into one that outlives it"
+                        ),
+                        p.span,
+                    )
+                    .with_help(
+                        "build the value before the arena, or keep the container inside it"
+                            .to_string(),
+                    ),
+                    None => Diagnostic::error(
+                        format!(
+                            "{} reaches memory outside this arena, which the value assigned here does not outlive",
+                            p.label
+                        ),
+                        p.span,
+                    )
+                    .with_help(
+                        "declare the variable inside the arena, or build the value before it"
+                            .to_string(),
+                    ),
+                };
+                diags.push(diag);
+            } else if !place.via.is_empty() {
+                through.push(ArenaUse::Through {
+                    span: p.span,
+                    target: p.label.clone(),
+                    assigned: p.store.is_none(),
+                    via: place.via,
+                });
+            }
+        }
+        through
+    }
+}
+
+/// `ArenaFrame::settle`'s walk: each inner binding resolved once. A
+/// binding met again while its own calls resolve — `x = f(x)` in a
+/// loop — keeps its outer flag but not those calls a second time.
+struct Settle<'f> {
+    frame: &'f ArenaFrame,
+    done: HashMap<usize, Reach>,
+    busy: HashSet<usize>,
+}
+
+impl Settle<'_> {
+    fn reach(&mut self, reach: &mut Reach) {
+        for id in std::mem::take(&mut reach.bindings) {
+            let resolved = self.binding(id);
+            reach.outer |= resolved.outer;
+            reach.via.extend(resolved.via);
+        }
+        reach.via.iter_mut().for_each(|c| self.call(c));
+    }
+
+    fn call(&mut self, call: &mut CallFacts) {
+        for arg in &mut call.args {
+            self.reach(&mut arg.reach);
+        }
+    }
+
+    /// What binding `id` reaches: the union over every binding it was
+    /// given the value of, itself included.
+    fn binding(&mut self, id: usize) -> Reach {
+        if let Some(done) = self.done.get(&id) {
+            return done.clone();
+        }
+        let mut sources = vec![id];
+        let mut seen = HashSet::from([id]);
+        while let Some(next) = sources.pop() {
+            for &source in self.frame.from.get(&next).into_iter().flatten() {
+                if seen.insert(source) {
+                    sources.push(source);
+                }
+            }
+        }
+        let outer = seen.iter().any(|n| self.frame.aliases.contains(n));
+        if !self.busy.insert(id) {
+            return Reach {
+                outer,
+                ..Reach::default()
+            };
+        }
+        let mut via: Vec<CallFacts> = seen
+            .iter()
+            .flat_map(|n| self.frame.via.get(n).into_iter().flatten())
+            .cloned()
+            .collect();
+        via.iter_mut().for_each(|c| self.call(c));
+        self.busy.remove(&id);
+        let resolved = Reach {
+            outer,
+            via,
+            bindings: Vec::new(),
+        };
+        self.done.insert(id, resolved.clone());
+        resolved
+    }
+}
+
+impl Checker<'_, '_> {
+    /// Checks the body like a plain block, inside a new region. Its
+    /// facts survive: the body always runs, start to end.
+    pub(super) fn check_arena(&mut self, body: &[Stmt]) {
+        self.arenas.push(ArenaFrame {
+            base: self.scopes.len(),
+            uses: self.out.arena_uses.len(),
+            ids: HashMap::new(),
+            bound: 0,
+            aliases: HashSet::new(),
+            via: HashMap::new(),
+            from: HashMap::new(),
+            pending: Vec::new(),
+            reading: Reach::default(),
+        });
+        let survivors = self.check_block_narrowed(body, HashMap::new());
+        if let Some(frame) = self.arenas.pop() {
+            let uses = &mut self.out.arena_uses[frame.uses..];
+            let through = frame.settle(uses, self.diagnostics);
+            self.out.arena_uses.extend(through);
+        }
+        self.add_facts(survivors);
+    }
+
+    /// Brackets a binding head that reads `value`: the names bound
+    /// until `end_binding_from` alias outer memory if `value` does.
+    pub(super) fn begin_binding_from(&mut self, value: &Expr) {
+        if self.arenas.is_empty() {
+            return;
+        }
+        let reach = self.reach(value);
+        if let Some(frame) = self.arenas.last_mut() {
+            frame.reading = reach;
+        }
+    }
+
+    pub(super) fn end_binding_from(&mut self) {
+        if let Some(frame) = self.arenas.last_mut() {
+            frame.reading = Reach::default();
+        }
+    }
+
+    /// Records `name` as an alias if it is bound while an outer place
+    /// is being read (called from `bind`).
+    pub(super) fn note_binding(&mut self, name: &str) {
+        let depth = self.scopes.len() - 1;
+        if let Some(frame) = self.arenas.last_mut() {
+            let id = frame.bound;
+            frame.bound += 1;
+            frame.ids.insert((depth, name.to_string()), id);
+            let reach = frame.reading.clone();
+            frame.note(id, reach);
+        }
+    }
+
+    /// `target = value` or `target op= value`: rejected when the result
+    /// is region-born and the place outlives the arena. A string `+=`
+    /// always builds a fresh string. A write through an inner binding
+    /// waits for the arena's end, when what the binding reaches is
+    /// known; one that stores outer memory into it makes it an alias.
+    pub(super) fn check_arena_assign(
+        &mut self,
+        target: &Expr,
+        value: &Expr,
+        compound: bool,
+        span: Span,
+    ) {
+        if self.arenas.is_empty() {
+            return;
+        }
+        let target_ty = self.out.expr_types.get(&target.span()).cloned();
+        let escaping = compound && target_ty == Some(Type::Str) || self.escapes(value);
+        let (place, label) = match root_ident(target) {
+            Some((root, _)) if !self.declared_inside(root) => {
+                if escaping {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            format!(
+                                "'{root}' outlives this arena, but the value assigned here does not"
+                            ),
+                            span,
+                        )
+                        .with_help(
+                            "declare the variable inside the arena, or build the value before it"
+                                .to_string(),
+                        ),
+                    );
+                }
+                return;
+            }
+            Some((root, _)) => {
+                let reach = self.stored_reach(value);
+                let place = self.name_reach(root);
+                if let Some(frame) = self.arenas.last_mut() {
+                    for &id in &place.bindings {
+                        frame.note(id, reach.clone());
+                    }
+                }
+                // Rebinding an inner variable frees nothing it held.
+                if matches!(target, Expr::Ident(..)) {
+                    return;
+                }
+                (place, format!("'{root}'"))
+            }
+            // Not a plain place: judged by whatever its parts reach.
+            None => (self.reach(target), "this place".to_string()),
+        };
+        if escaping {
+            self.pend(span, place, label, None);
+        }
+    }
+
+    /// `push(xs, v)` / `insert(xs, i, v)` / `insert(m, k, v)`: the
+    /// stored arguments may not be region-born when the container
+    /// outlives the arena. Storing outer memory into an inner container
+    /// makes it an alias, like an assignment.
+    pub(super) fn check_arena_store(&mut self, name: &str, container: &Expr, stored: &[Expr]) {
+        if self.arenas.is_empty() {
+            return;
+        }
+        let value = stored.iter().find(|v| self.escapes(v)).map(Expr::span);
+        let (place, label) = match read_root(container) {
+            Some(root) if !self.declared_inside(root) => {
+                if let Some(span) = value {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            format!(
+                                "'{name}' stores a value that dies with this arena into one that outlives it"
+                            ),
+                            span,
+                        )
+                        .with_help(
+                            "build the value before the arena, or keep the container inside it"
+                                .to_string(),
+                        ),
+                    );
+                }
+                return;
+            }
+            Some(root) => (self.name_reach(root), format!("'{root}'")),
+            // A computed container — a call's result, `??`, a `match` —
+            // reaches whatever its parts do.
+            None => (self.reach(container), "this container".to_string()),
+        };
+        let reach = stored
+            .iter()
+            .fold(Reach::default(), |r, v| r.join(self.stored_reach(v)));
+        if let Some(frame) = self.arenas.last_mut() {
+            for &id in &place.bindings {
+                frame.note(id, reach.clone());
+            }
+        }
+        if let Some(span) = value {
+            self.pend(span, place, label, Some(name.to_string()));
+        }
+    }
+
+    /// `return value;` inside an arena: the result must not be
+    /// region-born.
+    pub(super) fn check_arena_return(&mut self, value: &Expr, span: Span) {
+        if !self.arenas.is_empty() && self.escapes(value) {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    "cannot return a value allocated inside an arena".to_string(),
+                    span,
+                )
+                .with_help(
+                    "it is freed when the arena ends — build it before the arena".to_string(),
+                ),
+            );
+        }
+    }
+
+    /// `try` inside an arena: the error it propagates may carry a
+    /// payload built in the region (ADR 0062). Which errors `operand`
+    /// can raise takes its callees' bodies, so it is judged later —
+    /// and only when some error's payloads hold a handle at all.
+    pub(super) fn check_arena_try(&mut self, operand: &Expr, span: Span) {
+        if !self.arenas.is_empty() && self.holds_handle(&Type::ErrCode, HANDLE_FUEL) {
+            self.out.arena_uses.push(ArenaUse::Try {
+                span,
+                operand: operand.clone(),
+            });
+        }
+    }
+
+    /// A call checked inside an arena: recorded with what each argument
+    /// passes, unless it is a builtin (`push` and `insert` check their
+    /// stores themselves).
+    pub(super) fn note_arena_call(&mut self, call: &Expr) {
+        if self.arenas.is_empty() {
+            return;
+        }
+        if let Some(facts) = self.call_facts(call) {
+            self.out.arena_uses.push(ArenaUse::Call {
+                span: call.span(),
+                call: facts,
+            });
+        }
+    }
+
+    /// A function literal made inside an arena: it may run any time
+    /// before the arena ends, storing what it captured.
+    pub(super) fn note_arena_lambda(&mut self, params: usize, span: Span) {
+        if self.arenas.is_empty() {
+            return;
+        }
+        let captures = self.out.captures[&span]
+            .iter()
+            .map(|(name, ty)| ArgFacts {
+                span,
+                escapes: self.holds_handle(ty, HANDLE_FUEL) && self.declared_inside(name),
+                reach: self.name_reach(name),
+            })
+            .collect();
+        self.out.arena_uses.push(ArenaUse::Lambda {
+            span,
+            params,
+            captures,
+        });
+    }
+
+    /// A region-born value written through `place`, judged when the
+    /// innermost arena ends.
+    fn pend(&mut self, span: Span, place: Reach, label: String, store: Option<String>) {
+        if let Some(frame) = self.arenas.last_mut() {
+            frame.pending.push(Pending {
+                span,
+                place,
+                label,
+                store,
+            });
+        }
+    }
+
+    /// The callee and argument facts of a call to a user function or a
+    /// function value; `None` for anything else.
+    fn call_facts(&self, call: &Expr) -> Option<CallFacts> {
+        let (label, callee, receiver, args) = match call {
+            Expr::Call {
+                callee, args, span, ..
+            } => {
+                let target = self.out.call_targets.get(span);
+                let callee_ty = self.out.expr_types.get(&callee.span());
+                let label = match &**callee {
+                    Expr::Ident(name, _) => format!("'{name}'"),
+                    _ => "this call".to_string(),
+                };
+                let callee = match (target, callee_ty) {
+                    (Some(key), _) => Callee::Direct(key.clone()),
+                    (None, Some(t @ Type::Fn(..))) => Callee::Indirect(t.clone()),
+                    _ => return None,
+                };
+                (label, callee, None, args)
+            }
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+                span,
+            } => {
+                let key = self.out.call_targets.get(span)?;
+                let receiver = (**receiver).clone();
+                (
+                    format!("'{method}'"),
+                    Callee::Direct(key.clone()),
+                    Some(receiver),
+                    args,
+                )
+            }
+            Expr::EnumLit {
+                name,
+                variant,
+                args,
+                span,
+                ..
+            } => {
+                let key = self.out.call_targets.get(span)?;
+                let receiver = Expr::enum_lit_receiver(name, *span);
+                (
+                    format!("'{variant}'"),
+                    Callee::Direct(key.clone()),
+                    Some(receiver),
+                    args,
+                )
+            }
+            _ => return None,
+        };
+        let args = receiver
+            .iter()
+            .chain(args)
+            .map(|a| ArgFacts {
+                span: a.span(),
+                escapes: self.escapes(a),
+                reach: self.reach(a),
+            })
+            .collect();
+        Some(CallFacts {
+            callee,
+            label,
+            args,
+        })
+    }
+
+    /// What `value` may point into: outer memory for a read of an outer
+    /// place, a call's result, or an aggregate built from those. A
+    /// string is never written through, so it reaches nothing.
+    fn reach(&self, value: &Expr) -> Reach {
+        let parts: Vec<&Expr> = match value {
+            _ if read_root(value).is_some() => {
+                return read_root(value).map_or_else(Reach::default, |r| self.name_reach(r));
+            }
+            Expr::Call { callee, args, .. } => {
+                if let Some(call) = self.call_facts(value) {
+                    return Reach {
+                        via: vec![call],
+                        ..Reach::default()
+                    };
+                }
+                // `pop`, `removeAt`, and `slice` hand back what their
+                // array holds; every other builtin builds its result.
+                match &**callee {
+                    Expr::Ident(name, _)
+                        if [
+                            syntax::BUILTIN_POP,
+                            syntax::BUILTIN_REMOVE_AT,
+                            syntax::BUILTIN_SLICE,
+                        ]
+                        .contains(&name.as_str()) =>
+                    {
+                        args.iter().take(1).collect()
+                    }
+                    _ => Vec::new(),
+                }
+            }
+            Expr::MethodCall { .. } | Expr::EnumLit { .. } => match self.call_facts(value) {
+                Some(call) => {
+                    return Reach {
+                        via: vec![call],
+                        ..Reach::default()
+                    };
+                }
+                None => match value {
+                    Expr::EnumLit { args, .. } => args.iter().collect(),
+                    _ => Vec::new(),
+                },
+            },
+            Expr::Try { expr, .. } => vec![expr],
+            // A slice copies the array but shares the handles in it.
+            Expr::Index { base, .. } | Expr::Field { base, .. } => vec![base],
+            Expr::Binary {
+                op: BinOp::Coalesce,
+                lhs,
+                rhs,
+                ..
+            } => vec![lhs, rhs],
+            Expr::Match {
+                arms, else_value, ..
+            } => arms
+                .iter()
+                .map(|a| &a.body)
+                .chain(else_value.as_deref())
+                .collect(),
+            Expr::StructLit { fields, .. } => fields.iter().map(|(_, e)| e).collect(),
+            Expr::ArrayLit { elements, .. } | Expr::TupleLit { elements, .. } => {
+                elements.iter().collect()
+            }
+            Expr::ArrayRepeat { value, .. } => vec![value],
+            Expr::MapLit { entries, .. } => entries.iter().flat_map(|(k, v)| [k, v]).collect(),
+            Expr::ErrorLit { args, .. } => args.iter().collect(),
+            _ => Vec::new(),
+        };
+        parts
+            .into_iter()
+            .filter(|e| {
+                !matches!(
+                    self.out.expr_types.get(&e.span()),
+                    Some(Type::Str | Type::File | Type::Fn(..))
+                )
+            })
+            .fold(Reach::default(), |r, e| r.join(self.reach(e)))
+    }
+
+    /// What the binding `name` may point into: outer memory if it is
+    /// outer, else whatever it reaches when the arena ends.
+    fn name_reach(&self, name: &str) -> Reach {
+        match self.inner_binding(name) {
+            Some(id) => Reach {
+                bindings: vec![id],
+                ..Reach::default()
+            },
+            None => Reach {
+                outer: true,
+                ..Reach::default()
+            },
+        }
+    }
+
+    /// What storing `value` into a place gives the place: nothing for
+    /// a value without handles, or a string, a file, or a function
+    /// value, which are never written through.
+    fn stored_reach(&self, value: &Expr) -> Reach {
+        match self.out.expr_types.get(&value.span()) {
+            Some(Type::Str | Type::File | Type::Fn(..)) => Reach::default(),
+            Some(t) if !self.holds_handle(t, HANDLE_FUEL) => Reach::default(),
+            _ => self.reach(value),
+        }
+    }
+
+    /// Region-born and holding a handle: freed with the arena.
+    fn escapes(&self, value: &Expr) -> bool {
+        let holds = self
+            .out
+            .expr_types
+            .get(&value.span())
+            .is_some_and(|t| self.holds_handle(t, HANDLE_FUEL));
+        holds && self.region_born(value)
+    }
+
+    /// Could `value` be allocated in the innermost arena? Reads of a
+    /// place rooted at a binding declared outside it could not; nor
+    /// can literals that never allocate.
+    fn region_born(&self, value: &Expr) -> bool {
+        match value {
+            // A function named as a value is its code address.
+            Expr::Str(..) | Expr::Null(..) | Expr::FnRef { .. } => false,
+            // An error is born where its payloads are (ADR 0062).
+            Expr::ErrorLit { args, .. } => args.iter().any(|a| self.escapes(a)),
+            _ => match read_root(value) {
+                Some(root) => self.declared_inside(root),
+                None => true,
+            },
+        }
+    }
+
+    /// Is `name` a binding the innermost arena declared? Module vars
+    /// and consts never are.
+    fn declared_inside(&self, name: &str) -> bool {
+        self.inner_binding(name).is_some()
+    }
+
+    /// The id of the binding `name` reads, if the innermost arena
+    /// declared it.
+    fn inner_binding(&self, name: &str) -> Option<usize> {
+        let frame = self.arenas.last()?;
+        let depth = self
+            .scopes
+            .iter()
+            .rposition(|scope| scope.contains_key(name))
+            .filter(|&depth| depth >= frame.base)?;
+        frame.ids.get(&(depth, name.to_string())).copied()
+    }
+
+    fn holds_handle(&self, t: &Type, fuel: usize) -> bool {
+        holds_handle(
+            t,
+            &self.mono.structs,
+            &self.mono.enums,
+            self.error_payloads,
+            fuel,
+        )
+    }
+}
+
+/// Does a value of type `t` carry a heap handle — a string, array, map,
+/// refstruct, file, or function value — by value, error payloads
+/// included? Cut at the fuel floor like `reaches_err_union`.
+pub(super) fn holds_handle(
+    t: &Type,
+    structs: &HashMap<(usize, String), StructType>,
+    enums: &HashMap<(usize, String), EnumType>,
+    errors: &[Vec<Type>],
+    fuel: usize,
+) -> bool {
+    let Some(next) = fuel.checked_sub(1) else {
+        return false;
+    };
+    let holds = |t: &Type| holds_handle(t, structs, enums, errors, next);
+    match t {
+        Type::Str | Type::File | Type::Array(_) | Type::Map(..) | Type::Fn(..) => true,
+        Type::Optional(inner) | Type::Fixed(inner, _) => holds(inner),
+        Type::ErrUnion(inner) => holds(inner) || holds(&Type::ErrCode),
+        // Any error may arrive, so any code's payloads count.
+        Type::ErrCode => errors.iter().flatten().any(holds),
+        Type::Struct(m, n) => {
+            let def = &structs[&(*m, n.clone())];
+            def.by_ref || def.fields.iter().any(|(_, ft)| holds(ft))
+        }
+        Type::Enum(m, n) => enums[&(*m, n.clone())]
+            .variants
+            .iter()
+            .flat_map(|(_, payloads)| payloads)
+            .any(holds),
+        Type::Tuple(elems) => elems.iter().any(holds),
+        _ => false,
+    }
+}
+
+/// The binding a read goes through: the root of a field/index chain.
+/// A slice is a fresh copy, not a read of its base (ADR 0051).
+pub(super) fn read_root(e: &Expr) -> Option<&str> {
+    match e {
+        Expr::Ident(name, _) => Some(name),
+        Expr::Field { base, .. } => read_root(base),
+        Expr::Index { base, index, .. } if !matches!(**index, Expr::Range { .. }) => {
+            read_root(base)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,196 @@
+//! Match coverage over nested patterns (ADR 0045): the pattern-matrix
+//! usefulness walk. A row is one arm's pattern; an arm is reachable
+//! when its pattern is useful against the unguarded rows above it,
+//! and a match is exhaustive when a wildcard is not. `missing` turns
+//! the same walk into a witness — a value no row matches — for the
+//! diagnostic. Guarded rows never enter the matrix: a guard may fail.
+
+use std::collections::HashMap;
+
+use crate::types::{EnumType, Type};
+
+/// A checked pattern, stripped to what coverage needs: bindings are
+/// wildcards and variants are tags.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Pat {
+    Wild,
+    Ctor(Ctor, Vec<Pat>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Ctor {
+    Variant(usize),
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    /// An error code (ADR 0046), by its interned number, with its
+    /// payload types (ADR 0062).
+    Err(u32, Vec<Type>),
+}
+
+type Enums = HashMap<(usize, String), EnumType>;
+
+/// Every constructor of `ty` with its payload types, or `None` when
+/// only a wildcard can cover it (ints, strings, error codes,
+/// everything else).
+fn ctors_of(ty: &Type, enums: &Enums) -> Option<Vec<(Ctor, Vec<Type>)>> {
+    match ty {
+        Type::Enum(m, n) => Some(
+            enums[&(*m, n.clone())]
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(i, (_, payloads))| (Ctor::Variant(i), payloads.clone()))
+                .collect(),
+        ),
+        Type::Bool => Some(vec![
+            (Ctor::Bool(false), Vec::new()),
+            (Ctor::Bool(true), Vec::new()),
+        ]),
+        _ => None,
+    }
+}
+
+/// The payload types a constructor of `ty` opens into the matrix.
+fn sub_types(c: &Ctor, ty: &Type, enums: &Enums) -> Vec<Type> {
+    match (c, ty) {
+        (Ctor::Variant(i), Type::Enum(m, n)) => enums[&(*m, n.clone())].variants[*i].1.clone(),
+        (Ctor::Err(_, payloads), _) => payloads.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// The rows that can match constructor `c` in the first column, with
+/// that column replaced by its `arity` payload columns.
+fn specialize(rows: &[Vec<Pat>], c: &Ctor, arity: usize) -> Vec<Vec<Pat>> {
+    rows.iter()
+        .filter_map(|row| {
+            let mut out = match &row[0] {
+                Pat::Ctor(rc, args) if rc == c => args.clone(),
+                Pat::Ctor(..) => return None,
+                Pat::Wild => vec![Pat::Wild; arity],
+            };
+            out.extend_from_slice(&row[1..]);
+            Some(out)
+        })
+        .collect()
+}
+
+/// The rows whose first column is a wildcard, without that column.
+fn default_rows(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+    rows.iter()
+        .filter(|row| row[0] == Pat::Wild)
+        .map(|row| row[1..].to_vec())
+        .collect()
+}
+
+/// The distinct constructors heading the first column, in order.
+fn heads(rows: &[Vec<Pat>]) -> Vec<Ctor> {
+    let mut seen: Vec<Ctor> = Vec::new();
+    for row in rows {
+        if let Pat::Ctor(c, _) = &row[0]
+            && !seen.contains(c)
+        {
+            seen.push(c.clone());
+        }
+    }
+    seen
+}
+
+/// Does `q` match some value that no row matches?
+pub(super) fn useful(rows: &[Vec<Pat>], q: &[Pat], tys: &[Type], enums: &Enums) -> bool {
+    let Some((head, rest)) = q.split_first() else {
+        return rows.is_empty();
+    };
+    match head {
+        Pat::Ctor(c, args) => {
+            let mut sub = sub_types(c, &tys[0], enums);
+            let rows = specialize(rows, c, sub.len());
+            let mut q = args.clone();
+            q.extend_from_slice(rest);
+            sub.extend_from_slice(&tys[1..]);
+            useful(&rows, &q, &sub, enums)
+        }
+        Pat::Wild => {
+            let present = heads(rows);
+            match ctors_of(&tys[0], enums) {
+                Some(all) if all.iter().all(|(c, _)| present.contains(c)) => {
+                    all.into_iter().any(|(c, mut sub)| {
+                        let rows = specialize(rows, &c, sub.len());
+                        let mut q = vec![Pat::Wild; sub.len()];
+                        q.extend_from_slice(rest);
+                        sub.extend_from_slice(&tys[1..]);
+                        useful(&rows, &q, &sub, enums)
+                    })
+                }
+                _ => useful(&default_rows(rows), rest, &tys[1..], enums),
+            }
+        }
+    }
+}
+
+/// A value no row matches, one pattern per column, or `None` when the
+/// rows are exhaustive.
+pub(super) fn missing(rows: &[Vec<Pat>], tys: &[Type], enums: &Enums) -> Option<Vec<Pat>> {
+    if tys.is_empty() {
+        return rows.is_empty().then(Vec::new);
+    }
+    let present = heads(rows);
+    let all = ctors_of(&tys[0], enums);
+    if let Some(all) = &all
+        && all.iter().all(|(c, _)| present.contains(c))
+    {
+        for (c, sub) in all {
+            let arity = sub.len();
+            let mut sub = sub.clone();
+            sub.extend_from_slice(&tys[1..]);
+            if let Some(mut w) = missing(&specialize(rows, c, arity), &sub, enums) {
+                let rest = w.split_off(arity);
+                let mut out = vec![Pat::Ctor(c.clone(), w)];
+                out.extend(rest);
+                return Some(out);
+            }
+        }
+        return None;
+    }
+    let rest = missing(&default_rows(rows), &tys[1..], enums)?;
+    // Name a constructor the column lacks when there is one to name.
+    let head = all
+        .and_then(|all| all.into_iter().find(|(c, _)| !present.contains(c)))
+        .filter(|_| !present.is_empty())
+        .map_or(Pat::Wild, |(c, sub)| {
+            Pat::Ctor(c, vec![Pat::Wild; sub.len()])
+        });
+    let mut out = vec![head];
+    out.extend(rest);
+    Some(out)
+}
+
+/// A pattern in source syntax, for diagnostics: `Some(Circle(_))`.
+/// Only an arm head spells a nullary variant without parentheses.
+pub(super) fn show(p: &Pat, ty: &Type, head: bool, enums: &Enums) -> String {
+    match p {
+        Pat::Wild => "_".to_string(),
+        Pat::Ctor(Ctor::Bool(b), _) => b.to_string(),
+        Pat::Ctor(Ctor::Int(n), _) => n.to_string(),
+        Pat::Ctor(Ctor::Str(s), _) => format!("{s:?}"),
+        // Witnesses never name a code: the codes are an open set.
+        Pat::Ctor(Ctor::Err(..), _) => "error".to_string(),
+        Pat::Ctor(c @ Ctor::Variant(i), args) => {
+            let Type::Enum(m, n) = ty else {
+                unreachable!("variant patterns check against enums")
+            };
+            let name = &enums[&(*m, n.clone())].variants[*i].0;
+            if args.is_empty() && head {
+                return name.clone();
+            }
+            let subs = sub_types(c, ty, enums);
+            let args: Vec<String> = args
+                .iter()
+                .zip(&subs)
+                .map(|(a, t)| show(a, t, false, enums))
+                .collect();
+            format!("{name}({})", args.join(", "))
+        }
+    }
+}
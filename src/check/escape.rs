@@ -0,0 +1,958 @@
+//! Arena escapes through calls (ADR 0061). An arena body's own stores
+//! are checked where they stand; what a call or a function literal does
+//! with the handles it reaches takes the bodies it may run. Every body —
+//! function, instance, or function literal — gets a summary of what its
+//! caller can see it do, computed to a fixpoint over the program, and
+//! each use the checker recorded inside an arena is judged against its
+//! callee's summary. Summaries are flow-insensitive and go by name: a
+//! name carries every source any of its bindings could give it.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::Resolutions;
+use super::arena::{ArenaUse, ArgFacts, CallFacts, Callee, HANDLE_FUEL, Reach, holds_handle};
+use crate::ast::{Expr, Function, Item, Pattern, Stmt};
+use crate::diagnostic::Diagnostic;
+use crate::modules::ModuleGraph;
+use crate::span::Span;
+use crate::syntax;
+use crate::types::Type;
+
+type Key = (usize, String);
+
+/// Where a handle may come from, or be stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Src {
+    /// Allocated while the body runs.
+    Fresh,
+    /// Reachable from the body's `i`th parameter; a literal's captures
+    /// number after its parameters.
+    Param(usize),
+    /// Reachable from a module var.
+    Global,
+}
+
+type Srcs = BTreeSet<Src>;
+
+/// What a body may do that its caller can see.
+#[derive(Clone, Default, PartialEq)]
+struct Summary {
+    /// `(from, into)`: a handle from `from` may be stored in memory
+    /// reachable from `into` — a parameter or a module var.
+    stores: BTreeSet<(Src, Src)>,
+    /// What the result may point into.
+    returns: Srcs,
+    /// May it raise an error whose payloads hold a handle?
+    raises: bool,
+}
+
+impl Summary {
+    /// A body seen from outside: its captures are module-var-like, as
+    /// far as any caller can tell.
+    fn seen_by_caller(&self, arity: usize) -> Summary {
+        let outside = |s: Src| match s {
+            Src::Param(i) if i >= arity => Src::Global,
+            s => s,
+        };
+        Summary {
+            stores: self
+                .stores
+                .iter()
+                .map(|&(from, into)| (outside(from), outside(into)))
+                .collect(),
+            returns: self.returns.iter().map(|&s| outside(s)).collect(),
+            raises: self.raises,
+        }
+    }
+
+    fn absorb(&mut self, other: Summary) {
+        self.stores.extend(other.stores);
+        self.returns.extend(other.returns);
+        self.raises |= other.raises;
+    }
+}
+
+/// One body a call may run.
+struct Body<'a> {
+    /// Parameter names, then a function literal's captures.
+    params: Vec<&'a str>,
+    /// How many of `params` a caller passes.
+    arity: usize,
+    stmts: &'a [Stmt],
+    /// The type a function value running this body has, if any does.
+    ty: Option<Type>,
+}
+
+struct Program<'a> {
+    res: &'a Resolutions,
+    bodies: Vec<Body<'a>>,
+    fns: HashMap<&'a Key, usize>,
+    lambdas: HashMap<Span, usize>,
+    summaries: Vec<Summary>,
+}
+
+pub(super) fn check_arena_uses(
+    graph: &ModuleGraph,
+    res: &Resolutions,
+    uses: &[ArenaUse],
+    diags: &mut Vec<Diagnostic>,
+) {
+    let prog = Program::new(graph, res);
+    for used in uses {
+        match used {
+            ArenaUse::Call { span, call } => {
+                let summary = prog.callee(&call.callee, call.args.len());
+                prog.judge_stores(&summary, &call.args, &call.label, *span, diags);
+            }
+            ArenaUse::Lambda {
+                span,
+                params,
+                captures,
+            } => {
+                let Some(&i) = prog.lambdas.get(span) else {
+                    continue;
+                };
+                let passed = ArgFacts {
+                    span: *span,
+                    escapes: false,
+                    reach: Reach::default(),
+                };
+                let args: Vec<ArgFacts> = std::iter::repeat_n(passed, *params)
+                    .chain(captures.iter().cloned())
+                    .collect();
+                let label = "this function literal";
+                prog.judge_stores(&prog.summaries[i], &args, label, *span, diags);
+            }
+            ArenaUse::Try { span, operand } => {
+                if prog.raises(operand) {
+                    diags.push(
+                        Diagnostic::error(
+                            "'try' cannot propagate an error whose payloads hold handles out of an arena"
+                                .to_string(),
+                            *span,
+                        )
+                        .with_help("match the error inside the arena instead".to_string()),
+                    );
+                }
+            }
+            ArenaUse::Through {
+                span,
+                target,
+                assigned,
+                via,
+            } => {
+                let Some(call) = via.iter().find(|c| prog.returns_outer(c)) else {
+                    continue;
+                };
+                let verb = if *assigned { "assigned" } else { "stored" };
+                diags.push(
+                    Diagnostic::error(
+                        format!(
+                            "{target} may reach memory outside this arena through {}, which the value {verb} here does not outlive",
+                            call.label
+                        ),
+                        *span,
+                    )
+                    .with_help(
+                        "declare the variable inside the arena, or build the value before it"
+                            .to_string(),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+impl<'a> Program<'a> {
+    /// Every body, summarized to a fixpoint: a summary only grows, and
+    /// each grows with its callees'.
+    fn new(graph: &'a ModuleGraph, res: &'a Resolutions) -> Program<'a> {
+        let mut prog = Program {
+            res,
+            bodies: Vec::new(),
+            fns: HashMap::new(),
+            lambdas: HashMap::new(),
+            summaries: Vec::new(),
+        };
+        let values: BTreeSet<&Key> = res.fn_values.values().collect();
+        let mut fns: Vec<(&'a Key, &'a Function)> = Vec::new();
+        for (mi, module) in graph.modules.iter().enumerate() {
+            for item in &module.ast {
+                for f in item.functions().iter().filter(|f| f.type_params.is_empty()) {
+                    if let Some((key, _)) = res.sigs.get_key_value(&(mi, f.name.clone())) {
+                        fns.push((key, f));
+                    }
+                }
+            }
+        }
+        fns.extend(&res.instances);
+        for (key, f) in fns {
+            let ty = values.contains(key).then(|| {
+                let sig = &res.sigs[key];
+                Type::Fn(sig.params.clone(), Box::new(sig.ret.clone()))
+            });
+            prog.fns.insert(key, prog.bodies.len());
+            prog.bodies.push(Body {
+                params: f.params.iter().map(|p| p.name.as_str()).collect(),
+                arity: f.params.len(),
+                stmts: &f.body,
+                ty,
+            });
+        }
+        // Function literals live inside the bodies above and in module
+        // initializers.
+        let mut lambdas = Vec::new();
+        for body in &prog.bodies {
+            each_lambda(body.stmts, &mut lambdas);
+        }
+        for module in &graph.modules {
+            for item in &module.ast {
+                match item {
+                    Item::Const(c) => lambdas_in(&c.value, &mut lambdas),
+                    Item::Global(g) => lambdas_in(&g.value, &mut lambdas),
+                    _ => {}
+                }
+            }
+        }
+        for e in lambdas {
+            let Expr::Lambda {
+                params, body, span, ..
+            } = e
+            else {
+                continue;
+            };
+            let captures = res.captures.get(span).map_or(&[][..], Vec::as_slice);
+            prog.lambdas.insert(*span, prog.bodies.len());
+            prog.bodies.push(Body {
+                params: params
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .chain(captures.iter().map(|(n, _)| n.as_str()))
+                    .collect(),
+                arity: params.len(),
+                stmts: body,
+                ty: res.expr_types.get(span).cloned(),
+            });
+        }
+        prog.summaries = vec![Summary::default(); prog.bodies.len()];
+        loop {
+            let mut changed = false;
+            for i in 0..prog.bodies.len() {
+                let summary = Walk::summarize(&prog, &prog.bodies[i]);
+                if summary != prog.summaries[i] {
+                    prog.summaries[i] = summary;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return prog;
+            }
+        }
+    }
+
+    fn holds(&self, t: &Type) -> bool {
+        let res = self.res;
+        holds_handle(
+            t,
+            &res.structs,
+            &res.enums,
+            &res.error_payloads,
+            HANDLE_FUEL,
+        )
+    }
+
+    fn type_of(&self, e: &Expr) -> Option<&'a Type> {
+        self.res.expr_types.get(&e.span())
+    }
+
+    /// What a call to `callee` may do. An unknown function may do
+    /// anything.
+    fn callee(&self, callee: &Callee, arity: usize) -> Summary {
+        match callee {
+            Callee::Direct(key) => match self.fns.get(key) {
+                Some(&i) => self.summaries[i].clone(),
+                None => {
+                    let every: Srcs = (0..arity)
+                        .map(Src::Param)
+                        .chain([Src::Fresh, Src::Global])
+                        .collect();
+                    let into = every.iter().filter(|&&s| s != Src::Fresh);
+                    Summary {
+                        stores: into
+                            .flat_map(|&i| every.iter().map(move |&f| (f, i)))
+                            .collect(),
+                        returns: every.clone(),
+                        raises: true,
+                    }
+                }
+            },
+            // Any body of the value's type may run.
+            Callee::Indirect(ty) => {
+                let mut all = Summary::default();
+                for (body, summary) in self.bodies.iter().zip(&self.summaries) {
+                    if body.ty.as_ref() == Some(ty) {
+                        all.absorb(summary.seen_by_caller(body.arity));
+                    }
+                }
+                all
+            }
+        }
+    }
+
+    /// The callee a call expression runs; `None` for a builtin.
+    fn call_target(&self, e: &Expr) -> Option<Callee> {
+        match e {
+            Expr::Call { callee, span, .. } => match self.res.call_targets.get(span) {
+                Some(key) => Some(Callee::Direct(key.clone())),
+                None => match self.type_of(callee) {
+                    Some(t @ Type::Fn(..)) => Some(Callee::Indirect(t.clone())),
+                    _ => None,
+                },
+            },
+            Expr::MethodCall { span, .. } | Expr::EnumLit { span, .. } => self
+                .res
+                .call_targets
+                .get(span)
+                .map(|key| Callee::Direct(key.clone())),
+            _ => None,
+        }
+    }
+
+    /// May `e` raise an error whose payloads hold a handle? Only a
+    /// literal or a call's summary can say no.
+    fn raises(&self, e: &Expr) -> bool {
+        if !matches!(self.type_of(e), Some(Type::ErrCode | Type::ErrUnion(_))) {
+            return false;
+        }
+        if let Expr::ErrorLit { span, .. } = e
+            && let Some(code) = self.res.error_lits.get(span)
+        {
+            return self.res.error_payloads[*code as usize - 2]
+                .iter()
+                .any(|t| self.holds(t));
+        }
+        match e {
+            // A builtin raises no declared error.
+            Expr::Call { .. } | Expr::MethodCall { .. } | Expr::EnumLit { .. } => self
+                .call_target(e)
+                .is_some_and(|callee| self.callee(&callee, 0).raises),
+            _ => self.holds(&Type::ErrCode),
+        }
+    }
+
+    /// Does `reach` include memory outside the arena?
+    fn outer(&self, reach: &Reach) -> bool {
+        reach.outer || reach.via.iter().any(|c| self.returns_outer(c))
+    }
+
+    /// May the call's result point outside the arena?
+    fn returns_outer(&self, call: &CallFacts) -> bool {
+        let summary = self.callee(&call.callee, call.args.len());
+        summary.returns.iter().any(|s| match s {
+            Src::Fresh => false,
+            Src::Param(k) => call.args.get(*k).is_some_and(|a| self.outer(&a.reach)),
+            Src::Global => true,
+        })
+    }
+
+    /// Reports the first store `summary` makes of a region-born handle
+    /// into memory outside the arena, given what `args` pass.
+    fn judge_stores(
+        &self,
+        summary: &Summary,
+        args: &[ArgFacts],
+        label: &str,
+        span: Span,
+        diags: &mut Vec<Diagnostic>,
+    ) {
+        let outside = |into: &Src| match into {
+            Src::Param(k) => args.get(*k).is_some_and(|a| self.outer(&a.reach)),
+            Src::Global => true,
+            Src::Fresh => false,
+        };
+        let found = summary
+            .stores
+            .iter()
+            .filter(|(_, into)| outside(into))
+            .find_map(|(from, _)| match from {
+                Src::Fresh => Some((
+                    format!("{label} may store a value allocated in this arena where it outlives the arena"),
+                    span,
+                )),
+                Src::Param(j) => args.get(*j).filter(|a| a.escapes).map(|a| {
+                    (
+                        format!("{label} may store a value that dies with this arena where it outlives the arena"),
+                        a.span,
+                    )
+                }),
+                Src::Global => None,
+            });
+        if let Some((message, at)) = found {
+            diags.push(
+                Diagnostic::error(message, at).with_help(
+                    "build the value before the arena, or keep what the call stores inside it"
+                        .to_string(),
+                ),
+            );
+        }
+    }
+}
+
+/// One pass over a body, growing what each name may hold until nothing
+/// changes.
+struct Walk<'p, 'a> {
+    prog: &'p Program<'a>,
+    env: HashMap<&'a str, Srcs>,
+    summary: Summary,
+    grew: bool,
+}
+
+/// A call argument: what it may point into, and the local it names.
+struct Arg<'a> {
+    srcs: Srcs,
+    root: Option<&'a str>,
+}
+
+impl<'p, 'a> Walk<'p, 'a> {
+    fn summarize(prog: &'p Program<'a>, body: &Body<'a>) -> Summary {
+        let mut walk = Walk {
+            prog,
+            env: body
+                .params
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| (p, Srcs::from([Src::Param(i)])))
+                .collect(),
+            summary: Summary::default(),
+            grew: true,
+        };
+        while walk.grew {
+            walk.grew = false;
+            walk.block(body.stmts);
+        }
+        walk.summary
+    }
+
+    fn add(&mut self, name: &'a str, srcs: &Srcs) {
+        let held = self.env.entry(name).or_default();
+        for s in srcs {
+            self.grew |= held.insert(*s);
+        }
+    }
+
+    fn store(&mut self, from: &Srcs, into: Src) {
+        for f in from {
+            self.grew |= self.summary.stores.insert((*f, into));
+        }
+    }
+
+    fn is_global(&self, span: Span) -> bool {
+        self.prog.res.global_refs.contains_key(&span)
+    }
+
+    fn block(&mut self, stmts: &'a [Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Let { name, value, .. } => {
+                self.expr(value);
+                let srcs = self.srcs(value);
+                self.add(name, &srcs);
+            }
+            Stmt::LetTuple { names, value, .. } => {
+                self.expr(value);
+                let srcs = self.srcs(value);
+                for name in names {
+                    self.add(name, &srcs);
+                }
+            }
+            Stmt::LetStruct { fields, value, .. } => {
+                self.expr(value);
+                let srcs = self.srcs(value);
+                for (_, name, _) in fields {
+                    self.add(name, &srcs);
+                }
+            }
+            Stmt::LetElse {
+                pattern,
+                value,
+                else_body,
+                ..
+            } => {
+                self.bind_pattern(pattern, value);
+                if let Some(body) = else_body {
+                    self.block(body);
+                }
+            }
+            Stmt::Assign {
+                target, op, value, ..
+            } => {
+                self.expr(target);
+                self.expr(value);
+                let mut srcs = self.srcs(value);
+                if op.is_some()
+                    && self
+                        .prog
+                        .type_of(target)
+                        .is_some_and(|t| self.prog.holds(t))
+                {
+                    srcs.insert(Src::Fresh);
+                }
+                self.write(target, &srcs);
+            }
+            Stmt::Return { value, .. } => {
+                if let Some(value) = value {
+                    self.expr(value);
+                    let srcs = self.srcs(value);
+                    for s in srcs {
+                        self.grew |= self.summary.returns.insert(s);
+                    }
+                    self.raise_if(value);
+                }
+            }
+            Stmt::Break { .. } | Stmt::Continue { .. } => {}
+            Stmt::Defer { body, .. } | Stmt::Arena { body, .. } => self.block(body),
+            Stmt::If {
+                cond,
+                then_body,
+                else_body,
+                ..
+            } => {
+                self.expr(cond);
+                self.block(then_body);
+                if let Some(body) = else_body {
+                    self.block(body);
+                }
+            }
+            Stmt::While { cond, body, .. } => {
+                self.expr(cond);
+                self.block(body);
+            }
+            Stmt::IfConst {
+                pattern,
+                value,
+                then_body,
+                else_body,
+                ..
+            } => {
+                self.bind_pattern(pattern, value);
+                self.block(then_body);
+                if let Some(body) = else_body {
+                    self.block(body);
+                }
+            }
+            Stmt::WhileConst {
+                pattern,
+                value,
+                body,
+                ..
+            } => {
+                self.bind_pattern(pattern, value);
+                self.block(body);
+            }
+            Stmt::Match {
+                scrutinee,
+                arms,
+                else_body,
+                ..
+            } => {
+                self.expr(scrutinee);
+                let srcs = self.srcs(scrutinee);
+                for arm in arms {
+                    self.bind_names(&arm.pattern, &srcs);
+                    if let Some(guard) = &arm.guard {
+                        self.expr(guard);
+                    }
+                    self.block(&arm.body);
+                }
+                if let Some(body) = else_body {
+                    self.block(body);
+                }
+            }
+            Stmt::For {
+                name,
+                iterable,
+                body,
+                ..
+            } => {
+                self.expr(iterable);
+                let srcs = self.srcs(iterable);
+                self.add(name, &srcs);
+                self.block(body);
+            }
+            Stmt::Expr(e) => self.expr(e),
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &'a Pattern, value: &'a Expr) {
+        self.expr(value);
+        let srcs = self.srcs(value);
+        self.bind_names(pattern, &srcs);
+    }
+
+    fn bind_names(&mut self, pattern: &'a Pattern, srcs: &Srcs) {
+        match pattern {
+            Pattern::Bind(name, _) => self.add(name, srcs),
+            Pattern::Lit(_) => {}
+            Pattern::Variant { args, .. } | Pattern::Error { args, .. } => {
+                for arg in args {
+                    self.bind_names(arg, srcs);
+                }
+            }
+        }
+    }
+
+    fn raise_if(&mut self, e: &Expr) {
+        if !self.summary.raises && self.prog.raises(e) {
+            self.summary.raises = true;
+            self.grew = true;
+        }
+    }
+
+    /// Visits `e` for what it does: the calls it makes, the errors it
+    /// raises, the names its match arms bind. A function literal's body
+    /// is its own.
+    fn expr(&mut self, e: &'a Expr) {
+        if let Expr::Match {
+            scrutinee, arms, ..
+        } = e
+        {
+            let srcs = self.srcs(scrutinee);
+            for arm in arms {
+                self.bind_names(&arm.pattern, &srcs);
+            }
+        }
+        for child in children(e) {
+            self.expr(child);
+        }
+        match e {
+            Expr::Try { expr, .. } => self.raise_if(expr),
+            Expr::Call { .. } | Expr::MethodCall { .. } | Expr::EnumLit { .. } => self.call(e),
+            Expr::Lambda { span, .. } => self.lambda(*span),
+            _ => {}
+        }
+    }
+
+    /// A function literal may run any time its captures live, so what
+    /// it stores through them counts as its maker's own. What it does
+    /// with its parameters waits for the call.
+    fn lambda(&mut self, span: Span) {
+        let Some(&i) = self.prog.lambdas.get(&span) else {
+            return;
+        };
+        let body = &self.prog.bodies[i];
+        let args: Vec<Arg<'a>> = body
+            .params
+            .iter()
+            .enumerate()
+            .map(|(j, &name)| match j < body.arity {
+                true => Arg {
+                    srcs: Srcs::new(),
+                    root: None,
+                },
+                false => Arg {
+                    srcs: self.env.get(name).cloned().unwrap_or_default(),
+                    root: Some(name),
+                },
+            })
+            .collect();
+        self.apply(&self.prog.summaries[i].clone(), &args);
+    }
+
+    /// Applies a call's stores to what its arguments point into; `push`
+    /// and `insert` store their last arguments into the first.
+    fn call(&mut self, e: &'a Expr) {
+        let Some(callee) = self.prog.call_target(e) else {
+            if let Expr::Call { callee, args, .. } = e
+                && let Expr::Ident(name, _) = &**callee
+                && (name == syntax::BUILTIN_PUSH || name == syntax::BUILTIN_INSERT)
+                && let Some((container, stored)) = args.split_first()
+            {
+                let srcs = stored.iter().flat_map(|a| self.srcs(a)).collect();
+                self.write_into(container, &srcs);
+            }
+            return;
+        };
+        let args = self.args(e);
+        let summary = self.prog.callee(&callee, args.len());
+        self.apply(&summary, &args);
+    }
+
+    /// Applies a callee's stores to what its arguments point into.
+    fn apply(&mut self, summary: &Summary, args: &[Arg<'a>]) {
+        for &(from, into) in &summary.stores {
+            let from = match from {
+                Src::Param(j) => args.get(j).map(|a| a.srcs.clone()).unwrap_or_default(),
+                s => Srcs::from([s]),
+            };
+            match into {
+                Src::Param(k) => {
+                    let Some(arg) = args.get(k) else { continue };
+                    for &into in arg.srcs.iter().filter(|s| **s != Src::Fresh) {
+                        self.store(&from, into);
+                    }
+                    if let Some(root) = arg.root {
+                        self.add(root, &from);
+                    }
+                }
+                into => self.store(&from, into),
+            }
+        }
+    }
+
+    /// A call's arguments, the receiver first.
+    fn args(&self, e: &'a Expr) -> Vec<Arg<'a>> {
+        let arg = |a: &'a Expr| Arg {
+            srcs: self.srcs(a),
+            root: self.local_root(a),
+        };
+        match e {
+            Expr::Call { args, .. } => args.iter().map(arg).collect(),
+            Expr::MethodCall { receiver, args, .. } => {
+                std::iter::once(&**receiver).chain(args).map(arg).collect()
+            }
+            Expr::EnumLit {
+                name, args, span, ..
+            } => {
+                let receiver = Expr::enum_lit_receiver(name, *span);
+                let local = !self.is_global(receiver.span());
+                let srcs = if local {
+                    self.env.get(name.as_str()).cloned().unwrap_or_default()
+                } else {
+                    Srcs::from([Src::Global])
+                };
+                let receiver = Arg {
+                    srcs,
+                    root: local.then_some(name.as_str()),
+                };
+                std::iter::once(receiver)
+                    .chain(args.iter().map(arg))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The local a place is rooted at, if it is not a module var.
+    fn local_root(&self, e: &'a Expr) -> Option<&'a str> {
+        match e {
+            Expr::Ident(name, span) => (!self.is_global(*span)).then_some(name.as_str()),
+            Expr::Field { base, .. } => self.local_root(base),
+            Expr::Index { base, index, .. } if !matches!(**index, Expr::Range { .. }) => {
+                self.local_root(base)
+            }
+            _ => None,
+        }
+    }
+
+    /// `target = ` a value from `srcs`.
+    fn write(&mut self, target: &'a Expr, srcs: &Srcs) {
+        match target {
+            Expr::Ident(_, span) if self.is_global(*span) => self.store(srcs, Src::Global),
+            Expr::Ident(name, _) => self.add(name, srcs),
+            Expr::Field { base, .. } | Expr::Index { base, .. } => self.write_into(base, srcs),
+            _ => {}
+        }
+    }
+
+    /// Stores a value from `srcs` into the memory `place` points into.
+    fn write_into(&mut self, place: &'a Expr, srcs: &Srcs) {
+        if srcs.is_empty() {
+            return;
+        }
+        for into in self.srcs(place) {
+            if into != Src::Fresh {
+                self.store(srcs, into);
+            }
+        }
+        if let Some(root) = self.local_root(place) {
+            self.add(root, srcs);
+        }
+    }
+
+    /// What the value of `e` may point into. A value without handles
+    /// points nowhere; a literal is static.
+    fn srcs(&self, e: &'a Expr) -> Srcs {
+        if !self.prog.type_of(e).is_some_and(|t| self.prog.holds(t)) {
+            return Srcs::new();
+        }
+        match e {
+            Expr::Str(..) | Expr::Null(..) | Expr::FnRef { .. } => Srcs::new(),
+            Expr::Ident(name, span) => {
+                if self.is_global(*span) {
+                    Srcs::from([Src::Global])
+                } else {
+                    self.env.get(name.as_str()).cloned().unwrap_or_default()
+                }
+            }
+            Expr::Field { base, .. } => self.srcs(base),
+            Expr::Index { base, index, .. } => {
+                let mut srcs = self.srcs(base);
+                if matches!(**index, Expr::Range { .. }) {
+                    srcs.insert(Src::Fresh);
+                }
+                srcs
+            }
+            Expr::Try { expr, .. } => self.srcs(expr),
+            Expr::Lambda { span, .. } => {
+                let captures = self.prog.res.captures.get(span);
+                let mut srcs = Srcs::from([Src::Fresh]);
+                for (name, _) in captures.into_iter().flatten() {
+                    srcs.extend(self.env.get(name.as_str()).into_iter().flatten());
+                }
+                srcs
+            }
+            _ => match self.prog.call_target(e) {
+                Some(callee) => {
+                    let args = self.args(e);
+                    let summary = self.prog.callee(&callee, args.len());
+                    let mut srcs = Srcs::from([Src::Fresh]);
+                    for s in summary.returns {
+                        match s {
+                            Src::Param(k) => {
+                                srcs.extend(args.get(k).into_iter().flat_map(|a| &a.srcs));
+                            }
+                            s => {
+                                srcs.insert(s);
+                            }
+                        }
+                    }
+                    srcs
+                }
+                None => {
+                    let mut srcs = Srcs::from([Src::Fresh]);
+                    for child in children(e) {
+                        srcs.extend(self.srcs(child));
+                    }
+                    srcs
+                }
+            },
+        }
+    }
+}
+
+/// An expression's operands, a function literal's body excepted.
+fn children(e: &Expr) -> Vec<&Expr> {
+    match e {
+        Expr::Int(..)
+        | Expr::Float(..)
+        | Expr::Bool(..)
+        | Expr::Str(..)
+        | Expr::Ident(..)
+        | Expr::FnRef { .. }
+        | Expr::Null(..)
+        | Expr::ErrorKind(..)
+        | Expr::Lambda { .. } => Vec::new(),
+        Expr::ErrorLit { args, .. } | Expr::EnumLit { args, .. } => args.iter().collect(),
+        Expr::Try { expr, .. } => vec![expr],
+        Expr::Unary { rhs, .. } => vec![rhs],
+        Expr::Convert { arg, .. } => vec![arg],
+        Expr::Binary { lhs, rhs, .. } => vec![lhs, rhs],
+        Expr::Call { callee, args, .. } => std::iter::once(&**callee).chain(args).collect(),
+        Expr::Field { base, .. } => vec![base],
+        Expr::StructLit { fields, .. } => fields.iter().map(|(_, e)| e).collect(),
+        Expr::ArrayLit { elements, .. } | Expr::TupleLit { elements, .. } => {
+            elements.iter().collect()
+        }
+        Expr::ArrayRepeat { value, .. } => vec![value],
+        Expr::MapLit { entries, .. } => entries.iter().flat_map(|(k, v)| [k, v]).collect(),
+        Expr::Index { base, index, .. } => vec![base, index],
+        Expr::Range {
+            start, end, step, ..
+        } => [start, end, step]
+            .into_iter()
+            .flatten()
+            .map(|e| &**e)
+            .collect(),
+        Expr::MethodCall { receiver, args, .. } => {
+            std::iter::once(&**receiver).chain(args).collect()
+        }
+        Expr::Match {
+            scrutinee,
+            arms,
+            else_value,
+            ..
+        } => std::iter::once(&**scrutinee)
+            .chain(arms.iter().flat_map(|a| a.guard.iter().chain([&a.body])))
+            .chain(else_value.as_deref())
+            .collect(),
+    }
+}
+
+/// Collects every function literal in `stmts`, nested ones included.
+fn each_lambda<'a>(stmts: &'a [Stmt], out: &mut Vec<&'a Expr>) {
+    for stmt in stmts {
+        let (exprs, blocks): (Vec<&Expr>, Vec<&[Stmt]>) = match stmt {
+            Stmt::Let { value, .. }
+            | Stmt::LetTuple { value, .. }
+            | Stmt::LetStruct { value, .. } => (vec![value], Vec::new()),
+            Stmt::LetElse {
+                value, else_body, ..
+            } => (vec![value], else_body.iter().map(Vec::as_slice).collect()),
+            Stmt::Assign { target, value, .. } => (vec![target, value], Vec::new()),
+            Stmt::Return { value, .. } => (value.iter().collect(), Vec::new()),
+            Stmt::Break { .. } | Stmt::Continue { .. } => (Vec::new(), Vec::new()),
+            Stmt::Defer { body, .. } | Stmt::Arena { body, .. } => (Vec::new(), vec![body]),
+            Stmt::If {
+                cond,
+                then_body,
+                else_body,
+                ..
+            } => (
+                vec![cond],
+                std::iter::once(then_body.as_slice())
+                    .chain(else_body.iter().map(Vec::as_slice))
+                    .collect(),
+            ),
+            Stmt::IfConst {
+                value,
+                then_body,
+                else_body,
+                ..
+            } => (
+                vec![value],
+                std::iter::once(then_body.as_slice())
+                    .chain(else_body.iter().map(Vec::as_slice))
+                    .collect(),
+            ),
+            Stmt::While { cond, body, .. } => (vec![cond], vec![body]),
+            Stmt::WhileConst { value, body, .. } => (vec![value], vec![body]),
+            Stmt::For { iterable, body, .. } => (vec![iterable], vec![body]),
+            Stmt::Match {
+                scrutinee,
+                arms,
+                else_body,
+                ..
+            } => (
+                std::iter::once(scrutinee)
+                    .chain(arms.iter().filter_map(|a| a.guard.as_ref()))
+                    .collect(),
+                arms.iter()
+                    .map(|a| a.body.as_slice())
+                    .chain(else_body.iter().map(Vec::as_slice))
+                    .collect(),
+            ),
+            Stmt::Expr(e) => (vec![e], Vec::new()),
+        };
+        for e in exprs {
+            lambdas_in(e, out);
+        }
+        for block in blocks {
+            each_lambda(block, out);
+        }
+    }
+}
+
+fn lambdas_in<'a>(e: &'a Expr, out: &mut Vec<&'a Expr>) {
+    if let Expr::Lambda { body, .. } = e {
+        out.push(e);
+        each_lambda(body, out);
+    }
+    for child in children(e) {
+        lambdas_in(child, out);
+    }
+}
@@ -6,11 +6,6 @@
 
 use super::*;
 
-/// Recursion cap for the `reaches_err_union` walk (ADR 0037) — the
-/// same order as the layout walk's fuel; only uninstantiable
-/// self-embedding value types can spend it.
-const EQ_FUEL: usize = 64;
-
 impl Checker<'_, '_> {
     // Recursion here (and in every later pass) is stack-safe because the
     // parser bounds AST height at construction (`MAX_FN_OPS`), and the
@@ -42,8 +37,55 @@ impl Checker<'_, '_> {
             Expr::Float(_, _) => Type::Float,
             Expr::Bool(_, _) => Type::Bool,
             Expr::Str(_, _) => Type::Str,
+            // A name no local binds but a module const declares reads
+            // the const (ADR 0047); locals shadow consts.
+            Expr::Ident(name, span)
+                if self.find_var(name).is_none() && self.const_alias.contains_key(name) =>
+            {
+                let key = self.const_alias[name].clone();
+                let ty = self.const_types[&key].clone();
+                self.out.const_refs.insert(*span, key);
+                ty
+            }
+            // Likewise a module var (ADR 0048). Its reads never narrow:
+            // any call may reassign it.
+            Expr::Ident(name, span)
+                if self.find_var(name).is_none() && self.global_alias.contains_key(name) =>
+            {
+                let key = self.global_alias[name].clone();
+                let ty = self.global_types[&key].clone();
+                self.out.global_refs.insert(*span, key);
+                ty
+            }
+            // A name no local binds but a function declares is that
+            // function as a value (ADR 0038); locals shadow functions.
+            Expr::Ident(name, span)
+                if self.find_var(name).is_none() && self.fn_alias.contains_key(name) =>
+            {
+                self.fn_value(name, &[], *span)
+            }
             Expr::Ident(name, span) => self.lookup(name, *span),
+            Expr::FnRef {
+                name,
+                type_args,
+                span,
+            } => self.fn_value(name, type_args, *span),
             Expr::Null(_) => Type::Null,
+            // The parser builds ranges only where `for` and slicing
+            // consume them; neither types one as a value.
+            Expr::Range { span, .. } => {
+                self.error(
+                    "a range is only valid as a 'for' iterable or slice bounds".to_string(),
+                    *span,
+                );
+                Type::Error
+            }
+            Expr::Lambda {
+                params,
+                return_type,
+                body,
+                span,
+            } => self.check_lambda(params, return_type.as_ref(), body, *span),
             // `error.Name` resolves through the module's error view; the
             // interned code lands span-keyed so the engines never resolve
             // a name themselves (ADR 0034).
@@ -70,7 +112,13 @@ impl Checker<'_, '_> {
                     );
                     return Type::Error;
                 };
-                if !matches!(self.ret, Type::ErrUnion(_)) {
+                self.check_arena_try(expr, *span);
+                if self.deferring {
+                    self.error(
+                        "a 'defer' body cannot propagate with 'try'".to_string(),
+                        *span,
+                    );
+                } else if !matches!(self.ret, Type::ErrUnion(_)) {
                     self.error(
                         "'try' propagates an error, so the enclosing function must return 'T!'"
                             .to_string(),
@@ -79,19 +127,7 @@ impl Checker<'_, '_> {
                 }
                 (*inner).clone()
             }
-            Expr::ErrorLit(name, span) => match self.err_alias.get(name) {
-                Some(key) => {
-                    self.out.error_lits.insert(*span, self.error_codes[key]);
-                    Type::ErrCode
-                }
-                None => {
-                    self.diagnostics.push(
-                        Diagnostic::error(format!("unknown error '{name}'"), *span)
-                            .suggest(name, self.err_alias.keys().map(String::as_str)),
-                    );
-                    Type::Error
-                }
-            },
+            Expr::ErrorLit { name, args, span } => self.check_error_lit(name, args, *span),
             // Conversions cross-convert only (ADR 0028): identity
             // conversions are rejected — a no-op spelled as a conversion
             // is noise, not explicitness. `string(x)` (ADR 0029) renders
@@ -131,12 +167,38 @@ impl Checker<'_, '_> {
                     }
                     return Type::Str;
                 }
+                // `u8(x)` & co. truncate any other integer type; floats
+                // go through `int()` first (ADR 0052).
+                if let Conv::Sized(t) = to {
+                    if is_integer(&ty) && ty != Type::Sized(*t) {
+                        return Type::Sized(*t);
+                    }
+                    let mut diag = Diagnostic::error(
+                        format!(
+                            "{}() expects an integer, found {}",
+                            to.keyword(),
+                            self.type_name(&ty)
+                        ),
+                        *span,
+                    );
+                    if ty == Type::Sized(*t) {
+                        diag = diag.with_help(format!("the value is already {}", t.name()));
+                    } else if ty == Type::Float {
+                        diag = diag.with_help(format!(
+                            "convert with 'int()' first: '{}(int(x))'",
+                            t.name()
+                        ));
+                    }
+                    self.diagnostics.push(diag);
+                    return Type::Error;
+                }
                 let (want, result) = if *to == Conv::Float {
                     (Type::Int, Type::Float)
                 } else {
                     (Type::Float, Type::Int)
                 };
-                if ty != want {
+                // A sized integer widens to either, explicitly.
+                if ty != want && !matches!(ty, Type::Sized(_)) {
                     let mut diag = Diagnostic::error(
                         format!(
                             "{}() expects {}, found {}",
@@ -172,6 +234,14 @@ impl Checker<'_, '_> {
                         );
                         Type::Error
                     }
+                    UnOp::BitNot if is_integer(&ty) => ty,
+                    UnOp::BitNot => {
+                        self.error(
+                            format!("cannot apply '~' to {}", self.type_name(&ty)),
+                            *span,
+                        );
+                        Type::Error
+                    }
                 }
             }
             Expr::Binary { op, lhs, rhs, span } => {
@@ -201,7 +271,41 @@ impl Checker<'_, '_> {
                     }
                     return Type::Bool;
                 }
+                // `t == (1, "x")` — a tuple literal takes the other
+                // side's element types, as at a declared slot (ADR 0043);
+                // so does an array literal against a fixed array
+                // (ADR 0056).
+                if matches!(op, BinOp::Eq | BinOp::Ne) {
+                    let value_lit = |e: &Expr| {
+                        matches!(
+                            e,
+                            Expr::TupleLit { .. }
+                                | Expr::ArrayLit { .. }
+                                | Expr::ArrayRepeat { .. }
+                        )
+                    };
+                    let lit_rhs = value_lit(rhs);
+                    if lit_rhs || value_lit(lhs) {
+                        let (lit, other) = if lit_rhs { (rhs, lhs) } else { (lhs, rhs) };
+                        let ot = self.type_of_expr(other);
+                        let lit_ty = if matches!(ot, Type::Tuple(_) | Type::Fixed(..))
+                            && self.check_literal_against(lit, &ot)
+                        {
+                            ot.clone()
+                        } else {
+                            self.type_of_expr(lit)
+                        };
+                        let (lt, rt) = if lit_rhs { (ot, lit_ty) } else { (lit_ty, ot) };
+                        return self.check_binary(*op, lt, rt, *span);
+                    }
+                }
+                // `e == error.ParseFailed` tests the code alone (ADR
+                // 0062): a bare name stands for every payload.
+                let bare = |e: &Expr| matches!(e, Expr::ErrorLit { args, .. } if args.is_empty());
+                let eq = matches!(op, BinOp::Eq | BinOp::Ne);
+                self.code_ok = eq && bare(lhs);
                 let lt = self.type_of_expr(lhs);
+                self.code_ok = eq && bare(rhs);
                 // `x != null && …` — the null check guards the right side.
                 let rt = if *op == BinOp::And {
                     let (if_true, _) = condition_facts(lhs);
@@ -212,6 +316,20 @@ impl Checker<'_, '_> {
                 } else {
                     self.type_of_expr(rhs)
                 };
+                // An int literal operand takes the other side's sized
+                // type (ADR 0052): `b + 1` on a u8.
+                let mut rt = rt;
+                if rt == Type::Int
+                    && let Some(t) = self.sized_literal(rhs, &lt)
+                {
+                    rt = t;
+                }
+                let mut lt = lt;
+                if lt == Type::Int
+                    && let Some(t) = self.sized_literal(lhs, &rt)
+                {
+                    lt = t;
+                }
                 self.check_binary(*op, lt, rt, *span)
             }
             Expr::Call {
@@ -224,6 +342,7 @@ impl Checker<'_, '_> {
                 // The callee can mutate any shared refstruct it can reach,
                 // so field-path narrowing doesn't survive a call.
                 self.unnarrow_field_paths();
+                self.note_arena_call(expr);
                 ty
             }
             Expr::Field {
@@ -244,7 +363,56 @@ impl Checker<'_, '_> {
                 variant,
                 args,
                 span,
-            } => self.check_enum_lit(name, type_args, variant, args, *span),
+            } => {
+                // A variable base makes it a method call (ADR 0040);
+                // locals shadow types, as they shadow functions.
+                if type_args.is_empty() && self.is_variable(name) {
+                    let receiver = Expr::enum_lit_receiver(name, *span);
+                    let ty = self.check_method_call(&receiver, variant, args, *span);
+                    self.unnarrow_field_paths();
+                    self.note_arena_call(expr);
+                    return ty;
+                }
+                self.check_enum_lit(name, type_args, variant, args, *span)
+            }
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+                span,
+            } => {
+                let ty = self.check_method_call(receiver, method, args, *span);
+                self.unnarrow_field_paths();
+                self.note_arena_call(expr);
+                ty
+            }
+            Expr::MapLit { entries, .. } => self.check_map_lit(entries),
+            Expr::Match {
+                scrutinee,
+                arms,
+                else_value,
+                span,
+            } => self.check_match_expr(scrutinee, arms, else_value.as_deref(), *span, None),
+            // With no declared slot, a tuple is its elements' types as
+            // they stand (ADR 0043); `null` alone names no type.
+            Expr::TupleLit { elements, .. } => Type::Tuple(
+                elements
+                    .iter()
+                    .map(|e| match self.type_of_expr(e) {
+                        Type::Null => {
+                            self.diagnostics.push(
+                                Diagnostic::error(
+                                    "tuple element type cannot be inferred from 'null'".to_string(),
+                                    e.span(),
+                                )
+                                .with_help("declare the tuple's type".to_string()),
+                            );
+                            Type::Error
+                        }
+                        ty => ty,
+                    })
+                    .collect(),
+            ),
             Expr::ArrayLit { elements, .. } => {
                 // The first element names the type; a later `null` widens
                 // it to optional; `[]` is unconstrained and fits any array
@@ -291,8 +459,39 @@ impl Checker<'_, '_> {
                 }
                 Type::Array(Box::new(elem_ty))
             }
+            // `[v; N]` (ADR 0056): the value names the element type,
+            // as an array literal's first element does.
+            Expr::ArrayRepeat { value, count, .. } => {
+                let elem = match self.type_of_expr(value) {
+                    Type::Null => {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                "array element type cannot be inferred from 'null'".to_string(),
+                                value.span(),
+                            )
+                            .with_help(format!("declare the array's type, e.g. 'int?[{count}]'")),
+                        );
+                        Type::Error
+                    }
+                    ty => ty,
+                };
+                let ty = Type::Fixed(Box::new(elem), *count);
+                if let Some(diag) = fixed_size_error(&ty, self.mono, expr.span()) {
+                    self.diagnostics.push(diag);
+                    return Type::Error;
+                }
+                ty
+            }
+            Expr::Index { base, index, span } if matches!(**index, Expr::Range { .. }) => {
+                self.slice(base, index, *span)
+            }
             Expr::Index { base, index, span } => {
                 let base_ty = self.type_of_expr(base);
+                // `m[k]` (ADR 0042): a lookup that may miss, so `V?`.
+                if let Type::Map(k, v) = base_ty {
+                    self.check_key(index, &k);
+                    return or_null(*v);
+                }
                 let index_ty = self.type_of_expr(index);
                 if !fits(&index_ty, &Type::Int) {
                     self.error(
@@ -301,7 +500,9 @@ impl Checker<'_, '_> {
                     );
                 }
                 match base_ty {
-                    Type::Array(elem) => *elem,
+                    Type::Array(elem) | Type::Fixed(elem, _) => *elem,
+                    // A string indexes its bytes (ADR 0053).
+                    Type::Str => Type::Sized(IntTy::U8),
                     ref t if poisoned(t) => Type::Error,
                     Type::Optional(_) => {
                         self.diagnostics.push(
@@ -328,7 +529,83 @@ impl Checker<'_, '_> {
         }
     }
 
+    /// `xs[a..b]` (ADR 0051): a new array of the same type; `s[a..b]` a
+    /// string view of the byte range (ADR 0053). Either bound may be
+    /// open; a step has no meaning here.
+    fn slice(&mut self, base: &Expr, range: &Expr, span: Span) -> Type {
+        let base_ty = self.type_of_expr(base);
+        self.check_range(range, true);
+        match base_ty {
+            Type::Array(_) | Type::Str => base_ty,
+            ref t if poisoned(t) => Type::Error,
+            Type::Optional(_) => {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        format!(
+                            "{} may be null — it can't be sliced directly",
+                            self.type_name(&base_ty)
+                        ),
+                        span,
+                    )
+                    .with_help("check '!= null' first".to_string()),
+                );
+                Type::Error
+            }
+            other => {
+                self.error(format!("cannot slice {}", self.type_name(&other)), span);
+                Type::Error
+            }
+        }
+    }
+
+    /// A range's bounds and step are ints (ADR 0051). A literal zero
+    /// step is caught here; a computed one traps at run time.
+    pub(super) fn check_range(&mut self, range: &Expr, slice: bool) {
+        let Expr::Range {
+            start, end, step, ..
+        } = range
+        else {
+            return;
+        };
+        for bound in [start, end].into_iter().flatten() {
+            let ty = self.type_of_expr(bound);
+            if !fits(&ty, &Type::Int) {
+                self.error(
+                    format!("range bound must be int, found {}", self.type_name(&ty)),
+                    bound.span(),
+                );
+            }
+        }
+        let Some(step) = step else { return };
+        if slice {
+            self.error("a slice takes no step".to_string(), step.span());
+            return;
+        }
+        let ty = self.type_of_expr(step);
+        if !fits(&ty, &Type::Int) {
+            self.error(
+                format!("range step must be int, found {}", self.type_name(&ty)),
+                step.span(),
+            );
+        } else if let Expr::Int(0, span) = **step {
+            self.error("range step must not be zero".to_string(), span);
+        }
+    }
+
     fn check_binary(&mut self, op: BinOp, lt: Type, rt: Type, span: Span) -> Type {
+        self.check_operator(op, op.symbol(), lt, rt, span)
+    }
+
+    /// Types `lt op rt`, naming the operator as written — `op.symbol()`,
+    /// or its assigning form for `place op= value` (ADR 0050).
+    pub(super) fn check_operator(
+        &mut self,
+        op: BinOp,
+        written: &str,
+        lt: Type,
+        rt: Type,
+        span: Span,
+    ) -> Type {
         use BinOp::*;
         // A poisoned operand already produced its diagnostic — stay silent.
         if poisoned(&lt) || poisoned(&rt) {
@@ -368,6 +645,9 @@ impl Checker<'_, '_> {
             }
             // Logic on bools.
             And | Or => (lt == Type::Bool && rt == Type::Bool, Type::Bool),
+            // Bit operations and shifts on matching integers (ADR 0049,
+            // 0052).
+            BitAnd | BitOr | BitXor | Shl | Shr | UShr => (lt == rt && is_integer(&lt), lt.clone()),
             // `a ?? b`: a must be optional; b re-fills it (`T` unwraps,
             // `T?`/null keep it optional).
             Coalesce => match &lt {
@@ -386,7 +666,7 @@ impl Checker<'_, '_> {
             self.error(
                 format!(
                     "cannot apply '{}' to {} and {}",
-                    op.symbol(),
+                    written,
                     self.type_name(&lt),
                     self.type_name(&rt)
                 ),
@@ -404,12 +684,12 @@ impl Checker<'_, '_> {
         args: &[Expr],
         span: Span,
     ) -> Type {
+        // A variable shadows functions and builtins alike; any other
+        // callee shape is an expression producing a function value
+        // (ADR 0038).
         let name = match callee {
-            Expr::Ident(n, _) => n.clone(),
-            _ => {
-                self.error("only named functions can be called".to_string(), span);
-                return Type::Error;
-            }
+            Expr::Ident(n, _) if !self.is_variable(n) => n.clone(),
+            _ => return self.check_indirect_call(callee, type_args, args, span),
         };
         // Copy the map references out of `self` so the signature borrow is
         // independent of the `&mut self` calls below — no clone needed.
@@ -437,9 +717,16 @@ impl Checker<'_, '_> {
                 }
                 for arg in args {
                     let ty = self.type_of_expr(arg);
-                    if !matches!(ty, Type::Array(_)) && !poisoned(&ty) {
+                    if !matches!(
+                        ty,
+                        Type::Array(_) | Type::Fixed(..) | Type::Map(..) | Type::Str
+                    ) && !poisoned(&ty)
+                    {
                         self.error(
-                            format!("'len' expects an array, found {}", self.type_name(&ty)),
+                            format!(
+                                "'len' expects an array, a map, or a string, found {}",
+                                self.type_name(&ty)
+                            ),
                             arg.span(),
                         );
                     }
@@ -471,6 +758,14 @@ impl Checker<'_, '_> {
                 self.expect_builtin_args(&name, args, &[Type::File], span);
                 return Type::Bool;
             }
+            if name == syntax::BUILTIN_CHARS {
+                self.expect_builtin_args(&name, args, &[Type::Str], span);
+                return Type::Array(Box::new(Type::Sized(IntTy::U32)));
+            }
+            if let Some((want, result)) = string_builtin(&name) {
+                self.expect_builtin_args(&name, args, &want, span);
+                return result;
+            }
             if name == syntax::BUILTIN_PUSH {
                 if args.len() != 2 {
                     self.error(
@@ -482,30 +777,41 @@ impl Checker<'_, '_> {
                     }
                     return Type::Unit;
                 }
-                let array_ty = self.type_of_expr(&args[0]);
-                let value_ty = self.type_of_expr(&args[1]);
-                match array_ty {
+                match self.type_of_expr(&args[0]) {
                     Type::Array(elem) => {
-                        if !fits(&value_ty, &elem) {
-                            self.error(
-                                format!(
-                                    "'push' into {}[]: expected {}, found {}",
-                                    self.type_name(&elem),
-                                    self.type_name(&elem),
-                                    self.type_name(&value_ty)
-                                ),
-                                args[1].span(),
-                            );
-                        }
+                        let what = format!("'push' into {}[]", self.type_name(&elem));
+                        self.check_slot(&args[1], &elem, &what, "element");
+                        self.check_arena_store(&name, &args[0], &args[1..]);
+                    }
+                    ref t if poisoned(t) => {
+                        self.type_of_expr(&args[1]);
+                    }
+                    other => {
+                        self.type_of_expr(&args[1]);
+                        self.error(
+                            format!("'push' expects an array, found {}", self.type_name(&other)),
+                            args[0].span(),
+                        );
                     }
-                    ref t if poisoned(t) => {}
-                    other => self.error(
-                        format!("'push' expects an array, found {}", self.type_name(&other)),
-                        args[0].span(),
-                    ),
                 }
                 return Type::Unit;
             }
+            let on_array = is_array_builtin(&name);
+            if on_array
+                || name == syntax::BUILTIN_INSERT
+                || name == syntax::BUILTIN_REMOVE
+                || name == syntax::BUILTIN_HAS
+            {
+                // `insert` serves both: the first argument's type picks
+                // an array shift (ADR 0055) or a map upsert (ADR 0042).
+                let target = args.first().map(|a| self.type_of_expr(a));
+                if on_array
+                    || (name == syntax::BUILTIN_INSERT && matches!(target, Some(Type::Array(_))))
+                {
+                    return self.check_array_builtin(&name, args, target, span);
+                }
+                return self.check_map_builtin(&name, args, target, span);
+            }
             self.diagnostics.push(
                 Diagnostic::error(format!("undefined function '{name}'"), span)
                     .suggest(&name, self.fn_alias.keys().map(String::as_str)),
@@ -519,7 +825,7 @@ impl Checker<'_, '_> {
         };
         if self.mono.fn_templates.contains_key(target) {
             let target = target.clone();
-            return self.check_generic_call(&name, target, type_args, args, span);
+            return self.check_generic_call(&name, target, type_args, None, args, span);
         }
         if !type_args.is_empty() {
             self.error(format!("'{name}' takes no type arguments"), span);
@@ -537,139 +843,551 @@ impl Checker<'_, '_> {
                 span,
             );
         }
-        for (arg, expected) in args.iter().zip(&sig.params) {
-            if self.check_literal_against(arg, expected) {
-                continue;
+        self.check_args(args, &sig.params);
+        sig.ret.clone()
+    }
+
+    /// `insert(m, k, v)`, `remove(m, k)`, `has(m, k)` (ADR 0042): the
+    /// map names the key and value slots, which the other arguments
+    /// check against like literal entries. `insert` upserts (unit);
+    /// `remove` and `has` report whether the key was there. `target` is
+    /// the first argument's type, already checked by the dispatch.
+    fn check_map_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        target: Option<Type>,
+        span: Span,
+    ) -> Type {
+        let want = if name == syntax::BUILTIN_INSERT { 3 } else { 2 };
+        let ret = if want == 3 { Type::Unit } else { Type::Bool };
+        if args.len() != want {
+            self.error(
+                format!("'{name}' expects {want} arguments, found {}", args.len()),
+                span,
+            );
+            for arg in args.iter().skip(1) {
+                self.type_of_expr(arg);
             }
-            let got = self.type_of_expr(arg);
-            if !fits(&got, expected) {
+            return ret;
+        }
+        let map_ty = target.unwrap_or(Type::Error);
+        let Type::Map(k, v) = map_ty else {
+            if !poisoned(&map_ty) {
+                let want = if name == syntax::BUILTIN_INSERT {
+                    "an array or a map"
+                } else {
+                    "a map"
+                };
                 self.error(
-                    format!(
-                        "expected argument of type {}, found {}",
-                        self.type_name(expected),
-                        self.type_name(&got)
-                    ),
-                    arg.span(),
+                    format!("'{name}' expects {want}, found {}", self.type_name(&map_ty)),
+                    args[0].span(),
                 );
             }
+            for arg in &args[1..] {
+                self.type_of_expr(arg);
+            }
+            return ret;
+        };
+        self.check_key(&args[1], &k);
+        if let Some(value) = args.get(2) {
+            self.check_slot(value, &v, "map value", "value");
+            self.check_arena_store(name, &args[0], &args[1..]);
         }
-        sig.ret.clone()
+        ret
     }
 
-    /// Qualified enum construction — `Shape.Circle(1.5)` (ADR 0036).
-    /// The parser builds this node for every `ident.ident(…)` shape;
-    /// a base that isn't an enum type falls back to the pre-enum
-    /// diagnostics. Generic enums pin their parameters explicitly or
-    /// by unifying the variant's payload annotations against the
-    /// argument types (ADR 0035 machinery).
-    fn check_enum_lit(
+    /// The array library (ADR 0055): the array names the element slot,
+    /// which inserted and searched-for values check against like literal
+    /// elements; indices are `int`. `pop` reads like a map lookup — an
+    /// empty array gives `null`. `sort` without a key orders `int`,
+    /// `float`, and `string` elements; with one, any element type, by a
+    /// key of one of those three.
+    fn check_array_builtin(
         &mut self,
         name: &str,
-        type_args: &[TypeAnn],
-        variant: &str,
         args: &[Expr],
+        target: Option<Type>,
         span: Span,
     ) -> Type {
-        let arg_tys: Vec<Type> = args.iter().map(|a| self.type_of_expr(a)).collect();
-        let Some(key) = self.ty_alias.get(name).cloned() else {
-            if self.find_var(name).is_some() {
-                // `p.x(…)` on a variable — the pre-enum call shape.
-                self.error("only named functions can be called".to_string(), span);
-            } else {
-                self.diagnostics.push(
-                    Diagnostic::error(format!("unknown enum '{name}'"), span)
-                        .suggest(name, self.ty_alias.keys().map(String::as_str)),
-                );
-            }
-            return Type::Error;
+        let want = match name {
+            syntax::BUILTIN_POP | syntax::BUILTIN_CLEAR | syntax::BUILTIN_REVERSE => 1,
+            syntax::BUILTIN_INSERT | syntax::BUILTIN_SLICE => 3,
+            syntax::BUILTIN_SORT => args.len().clamp(1, 2),
+            _ => 2,
         };
-        // Pin the instance: monomorphic key, or template + arguments.
-        let ikey = if let Some(tmpl) = self.mono.enum_templates.get(&key).copied() {
-            // For inference, the named variant's payload annotations
-            // unify against the argument types.
-            let pairs: Vec<(&TypeAnn, &Type)> = if type_args.is_empty() {
-                let Some(vdecl) = tmpl.variants.iter().find(|v| v.name == variant) else {
-                    self.unknown_variant(name, variant, span);
-                    return Type::Error;
-                };
-                vdecl.payloads.iter().zip(&arg_tys).collect()
+        if args.len() != want {
+            let want = if name == syntax::BUILTIN_SORT {
+                "1 or 2 arguments".to_string()
             } else {
-                Vec::new()
-            };
-            let Some(bind) = self.bind_type_params(
-                name,
-                &tmpl.type_params,
-                type_args,
-                &pairs,
-                format!("write the type arguments: '{name}<…>.{variant}(…)'"),
-                span,
-            ) else {
-                return Type::Error;
+                format!("{want} argument{}", if want == 1 { "" } else { "s" })
             };
-            let ordered: Vec<Type> = tmpl
-                .type_params
-                .iter()
-                .map(|(n, _)| bind[n].clone())
-                .collect();
-            match instantiate_enum(&key, ordered, &mut self.cx_in(self.module), span) {
-                Type::Enum(m, n) => (m, n),
-                _ => return Type::Error,
-            }
-        } else if self.mono.enums.contains_key(&key) {
-            if !type_args.is_empty() {
-                self.error(format!("'{name}' takes no type arguments"), span);
-            }
-            key
-        } else {
-            self.error(format!("'{name}' is not an enum"), span);
-            return Type::Error;
-        };
-        // The variant against the (instance's) resolved payloads.
-        let def = &self.mono.enums[&ikey];
-        let Some(tag) = def.variants.iter().position(|(n, _)| n == variant) else {
-            self.unknown_variant(name, variant, span);
-            return Type::Error;
-        };
-        let payloads = def.variants[tag].1.clone();
-        if args.len() != payloads.len() {
             self.error(
-                format!(
-                    "variant '{variant}' expects {} payload(s), found {}",
-                    payloads.len(),
-                    args.len()
-                ),
+                format!("'{name}' expects {want}, found {}", args.len()),
                 span,
             );
+            for arg in args.iter().skip(1) {
+                self.type_of_expr(arg);
+            }
             return Type::Error;
         }
-        for ((arg, at), expected) in args.iter().zip(&arg_tys).zip(&payloads) {
-            if poisoned(at) {
-                continue;
-            }
-            if matches!(arg, Expr::ArrayLit { .. }) && self.check_literal_against(arg, expected) {
-                continue;
-            }
-            if !fits(at, expected) {
+        let array_ty = target.unwrap_or(Type::Error);
+        let Type::Array(elem) = array_ty else {
+            if !poisoned(&array_ty) {
                 self.error(
                     format!(
-                        "payload of type {}, found {}",
-                        self.type_name(expected),
-                        self.type_name(at)
+                        "'{name}' expects an array, found {}",
+                        self.type_name(&array_ty)
                     ),
-                    arg.span(),
+                    args[0].span(),
                 );
             }
-        }
-        self.out.variant_tags.insert(span, tag as u32);
-        Type::Enum(ikey.0, ikey.1)
-    }
-
-    fn unknown_variant(&mut self, name: &str, variant: &str, span: Span) {
-        self.error(format!("enum '{name}' has no variant '{variant}'"), span);
-    }
-
-    /// Pins a template's type parameters (ADR 0035): explicit
-    /// arguments resolve in the caller's view — they were written
+            for arg in &args[1..] {
+                self.type_of_expr(arg);
+            }
+            return Type::Error;
+        };
+        let rest = &args[1..];
+        match name {
+            syntax::BUILTIN_POP => or_null(*elem),
+            syntax::BUILTIN_CLEAR | syntax::BUILTIN_REVERSE => Type::Unit,
+            syntax::BUILTIN_INSERT => {
+                self.check_index_arg(name, &rest[0]);
+                let what = format!("'{name}' into {}[]", self.type_name(&elem));
+                self.check_slot(&rest[1], &elem, &what, "element");
+                self.check_arena_store(name, &args[0], &rest[1..]);
+                Type::Unit
+            }
+            syntax::BUILTIN_REMOVE_AT => {
+                self.check_index_arg(name, &rest[0]);
+                *elem
+            }
+            syntax::BUILTIN_SLICE => {
+                self.check_index_arg(name, &rest[0]);
+                self.check_index_arg(name, &rest[1]);
+                Type::Array(elem)
+            }
+            syntax::BUILTIN_SORT => {
+                let Some(key) = rest.first() else {
+                    if !sortable(&elem) && !poisoned(&elem) {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                format!(
+                                    "'sort' orders integer, float, and string elements, found {}",
+                                    self.type_name(&elem)
+                                ),
+                                span,
+                            )
+                            .with_help(format!(
+                                "pass a key function: 'sort(xs, fun (x: {}): int {{ … }})'",
+                                self.type_name(&elem)
+                            )),
+                        );
+                    }
+                    return Type::Unit;
+                };
+                let key_ty = self.type_of_expr(key);
+                let ok = match &key_ty {
+                    Type::Fn(params, ret) => {
+                        params.as_slice() == [(*elem).clone()] && sortable(ret)
+                    }
+                    t => poisoned(t),
+                };
+                if !ok {
+                    self.error(
+                        format!(
+                            "'sort' key must be fun({}) returning an integer, float, or string, found {}",
+                            self.type_name(&elem),
+                            self.type_name(&key_ty)
+                        ),
+                        key.span(),
+                    );
+                }
+                Type::Unit
+            }
+            // indexOf / contains: a search by `==`.
+            _ => {
+                let what = format!("'{name}' in {}[]", self.type_name(&elem));
+                self.check_slot(&rest[0], &elem, &what, "element");
+                if self.reaches_err_union(&elem, EQ_FUEL) {
+                    self.error(
+                        format!(
+                            "cannot search {}[] — its elements contain an error union",
+                            self.type_name(&elem)
+                        ),
+                        args[0].span(),
+                    );
+                }
+                if name == syntax::BUILTIN_INDEX_OF {
+                    Type::Optional(Box::new(Type::Int))
+                } else {
+                    Type::Bool
+                }
+            }
+        }
+    }
+
+    /// An array library position (ADR 0055): any `int` expression.
+    fn check_index_arg(&mut self, name: &str, index: &Expr) {
+        let ty = self.type_of_expr(index);
+        if ty != Type::Int && !poisoned(&ty) {
+            self.error(
+                format!("'{name}' index must be int, found {}", self.type_name(&ty)),
+                index.span(),
+            );
+        }
+    }
+
+    /// A map literal with no declared slot (ADR 0042): the first entry
+    /// names the key and value types, the way an array literal's first
+    /// element does — a later `null` value widens the value type.
+    fn check_map_lit(&mut self, entries: &[(Expr, Expr)]) -> Type {
+        let mut rest = entries.iter();
+        let Some((k0, v0)) = rest.next() else {
+            return Type::Map(Box::new(Type::Unknown), Box::new(Type::Unknown));
+        };
+        let mut key_ty = self.type_of_expr(k0);
+        if self.reaches_err_union(&key_ty, EQ_FUEL) {
+            self.error(
+                format!(
+                    "{} cannot be a map key — it contains an error union",
+                    self.type_name(&key_ty)
+                ),
+                k0.span(),
+            );
+            key_ty = Type::Error;
+        }
+        let mut val_ty = self.type_of_expr(v0);
+        for (e, ty, what) in [(k0, &mut key_ty, "key"), (v0, &mut val_ty, "value")] {
+            if *ty == Type::Null {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        format!("map {what} type cannot be inferred from 'null'"),
+                        e.span(),
+                    )
+                    .with_help(
+                        "start with a non-null entry, or declare the map's type".to_string(),
+                    ),
+                );
+                *ty = Type::Error;
+            }
+        }
+        for (key, value) in rest {
+            let kt = self.type_of_expr(key);
+            if unconstrained(&key_ty) && !unconstrained(&kt) {
+                key_ty = kt;
+            } else if !fits(&kt, &key_ty) {
+                self.error(
+                    format!(
+                        "map keys must share one type: expected {}, found {}",
+                        self.type_name(&key_ty),
+                        self.type_name(&kt)
+                    ),
+                    key.span(),
+                );
+            }
+            let vt = self.type_of_expr(value);
+            if vt == Type::Null && !matches!(val_ty, Type::Optional(_)) {
+                val_ty = Type::Optional(Box::new(val_ty));
+            } else if unconstrained(&val_ty) && !unconstrained(&vt) {
+                val_ty = vt;
+            } else if !fits(&vt, &val_ty) {
+                self.error(
+                    format!(
+                        "map values must share one type: expected {}, found {}",
+                        self.type_name(&val_ty),
+                        self.type_name(&vt)
+                    ),
+                    value.span(),
+                );
+            }
+        }
+        Type::Map(Box::new(key_ty), Box::new(val_ty))
+    }
+
+    /// Arguments against a callee's parameter types — the direct-call
+    /// rule: literals re-record at their declared position, everything
+    /// else must fit.
+    fn check_args(&mut self, args: &[Expr], params: &[Type]) {
+        for (arg, expected) in args.iter().zip(params) {
+            if self.check_literal_against(arg, expected) {
+                continue;
+            }
+            let got = self.type_of_expr(arg);
+            if !fits(&got, expected) {
+                self.error(
+                    format!(
+                        "expected argument of type {}, found {}",
+                        self.type_name(expected),
+                        self.type_name(&got)
+                    ),
+                    arg.span(),
+                );
+            }
+        }
+    }
+
+    /// A method call (ADR 0040): the receiver's struct or enum type
+    /// names the impl — `Tree.sum` in the type's own module — and the
+    /// call checks like a direct one with the receiver as the first
+    /// argument, so a value-struct `self` is a copy and a refstruct
+    /// `self` aliases. A generic impl's parameters are inferred from
+    /// the receiver like any template argument.
+    fn check_method_call(
+        &mut self,
+        receiver: &Expr,
+        method: &str,
+        args: &[Expr],
+        span: Span,
+    ) -> Type {
+        let rt = self.type_of_expr(receiver);
+        let owner = match self.method_owner(&rt) {
+            Some(owner) => owner,
+            None => {
+                let t = &rt;
+                if matches!(t, Type::Optional(_)) {
+                    self.error(
+                        format!(
+                            "cannot call '{method}' on {} — it may be null; narrow it first",
+                            self.type_name(t)
+                        ),
+                        receiver.span(),
+                    );
+                } else if !poisoned(t) {
+                    self.error(
+                        format!("type {} has no methods", self.type_name(t)),
+                        receiver.span(),
+                    );
+                }
+                for arg in args {
+                    self.type_of_expr(arg);
+                }
+                return Type::Error;
+            }
+        };
+        let key = (owner.0, format!("{}.{method}", owner.1));
+        if self.mono.fn_templates.contains_key(&key) {
+            let name = key.1.clone();
+            return self.check_generic_call(&name, key, &[], Some((receiver, rt)), args, span);
+        }
+        let sigs = self.sigs;
+        let Some(sig) = sigs.get(&key) else {
+            let mut diag = Diagnostic::error(
+                format!("type {} has no method '{method}'", self.type_name(&rt)),
+                span,
+            );
+            if let Type::Struct(m, n) = &rt
+                && let Some(StructType { fields, .. }) = self.mono.structs.get(&(*m, n.clone()))
+                && fields
+                    .iter()
+                    .any(|(f, t)| f == method && matches!(t, Type::Fn(..)))
+            {
+                let base = match receiver {
+                    Expr::Ident(n, _) => n.clone(),
+                    _ => "…".to_string(),
+                };
+                diag = diag.with_help(format!(
+                    "to call a function-valued field, group it: '({base}.{method})(…)'"
+                ));
+            }
+            self.diagnostics.push(diag);
+            for arg in args {
+                self.type_of_expr(arg);
+            }
+            return Type::Error;
+        };
+        self.out.call_targets.insert(span, key.clone());
+        if args.len() + 1 != sig.params.len() {
+            self.error(
+                format!(
+                    "method '{method}' expects {} argument(s), found {}",
+                    sig.params.len() - 1,
+                    args.len()
+                ),
+                span,
+            );
+        }
+        self.check_args(args, &sig.params[1..]);
+        sig.ret.clone()
+    }
+
+    /// Qualified enum construction — `Shape.Circle(1.5)` (ADR 0036).
+    /// The parser builds this node for every `ident.ident(…)` shape;
+    /// a base that isn't an enum type falls back to the pre-enum
+    /// diagnostics. Generic enums pin their parameters explicitly or
+    /// by unifying the variant's payload annotations against the
+    /// argument types (ADR 0035 machinery).
+    fn check_enum_lit(
+        &mut self,
+        name: &str,
+        type_args: &[TypeAnn],
+        variant: &str,
+        args: &[Expr],
+        span: Span,
+    ) -> Type {
+        let arg_tys: Vec<Type> = args.iter().map(|a| self.type_of_expr(a)).collect();
+        let Some(key) = self.ty_alias.get(name).cloned() else {
+            self.diagnostics.push(
+                Diagnostic::error(format!("unknown enum '{name}'"), span)
+                    .suggest(name, self.ty_alias.keys().map(String::as_str)),
+            );
+            return Type::Error;
+        };
+        // Pin the instance: monomorphic key, or template + arguments.
+        let ikey = if let Some(tmpl) = self.mono.enum_templates.get(&key).copied() {
+            // For inference, the named variant's payload annotations
+            // unify against the argument types.
+            let pairs: Vec<(&TypeAnn, &Type)> = if type_args.is_empty() {
+                let Some(vdecl) = tmpl.variants.iter().find(|v| v.name == variant) else {
+                    self.unknown_variant(name, variant, span);
+                    return Type::Error;
+                };
+                vdecl.payloads.iter().zip(&arg_tys).collect()
+            } else {
+                Vec::new()
+            };
+            let Some(bind) = self.bind_type_params(
+                name,
+                &tmpl.type_params,
+                type_args,
+                &pairs,
+                format!("write the type arguments: '{name}<…>.{variant}(…)'"),
+                span,
+            ) else {
+                return Type::Error;
+            };
+            let ordered: Vec<Type> = tmpl
+                .type_params
+                .iter()
+                .map(|(n, _)| bind[n].clone())
+                .collect();
+            match instantiate_enum(&key, ordered, &mut self.cx_in(self.module), span) {
+                Type::Enum(m, n) => (m, n),
+                _ => return Type::Error,
+            }
+        } else if self.mono.enums.contains_key(&key) {
+            if !type_args.is_empty() {
+                self.error(format!("'{name}' takes no type arguments"), span);
+            }
+            key
+        } else {
+            self.error(format!("'{name}' is not an enum"), span);
+            return Type::Error;
+        };
+        // The variant against the (instance's) resolved payloads.
+        let def = &self.mono.enums[&ikey];
+        let Some(tag) = def.variants.iter().position(|(n, _)| n == variant) else {
+            self.unknown_variant(name, variant, span);
+            return Type::Error;
+        };
+        let payloads = def.variants[tag].1.clone();
+        if args.len() != payloads.len() {
+            self.error(
+                format!(
+                    "variant '{variant}' expects {} payload(s), found {}",
+                    payloads.len(),
+                    args.len()
+                ),
+                span,
+            );
+            return Type::Error;
+        }
+        for ((arg, at), expected) in args.iter().zip(&arg_tys).zip(&payloads) {
+            if poisoned(at) {
+                continue;
+            }
+            if matches!(
+                arg,
+                Expr::ArrayLit { .. }
+                    | Expr::ArrayRepeat { .. }
+                    | Expr::MapLit { .. }
+                    | Expr::TupleLit { .. }
+                    | Expr::Int(..)
+                    | Expr::Unary { .. }
+            ) && self.check_literal_against(arg, expected)
+            {
+                continue;
+            }
+            if !fits(at, expected) {
+                self.error(
+                    format!(
+                        "payload of type {}, found {}",
+                        self.type_name(expected),
+                        self.type_name(at)
+                    ),
+                    arg.span(),
+                );
+            }
+        }
+        self.out.variant_tags.insert(span, tag as u32);
+        Type::Enum(ikey.0, ikey.1)
+    }
+
+    /// `error.Name` / `error.Name(args)` (ADR 0034, ADR 0062): the
+    /// arguments check against the declared payloads like a variant's.
+    /// A payload-carrying code may appear bare only as an operand of
+    /// `==`/`!=`, where it compares the code alone.
+    fn check_error_lit(&mut self, name: &str, args: &[Expr], span: Span) -> Type {
+        let code_ok = std::mem::take(&mut self.code_ok);
+        let arg_tys: Vec<Type> = args.iter().map(|a| self.type_of_expr(a)).collect();
+        let Some(key) = self.err_alias.get(name) else {
+            self.diagnostics.push(
+                Diagnostic::error(format!("unknown error '{name}'"), span)
+                    .suggest(name, self.err_alias.keys().map(String::as_str)),
+            );
+            return Type::Error;
+        };
+        let code = self.error_codes[key];
+        self.out.error_lits.insert(span, code);
+        let all = self.error_payloads;
+        let payloads = &all[code as usize - 2];
+        if args.len() != payloads.len() && !(code_ok && args.is_empty()) {
+            self.error(
+                format!(
+                    "error '{name}' expects {} payload(s), found {}",
+                    payloads.len(),
+                    args.len()
+                ),
+                span,
+            );
+            return Type::Error;
+        }
+        for ((arg, at), expected) in args.iter().zip(&arg_tys).zip(payloads) {
+            if poisoned(at) {
+                continue;
+            }
+            if matches!(
+                arg,
+                Expr::ArrayLit { .. }
+                    | Expr::ArrayRepeat { .. }
+                    | Expr::MapLit { .. }
+                    | Expr::TupleLit { .. }
+                    | Expr::Int(..)
+                    | Expr::Unary { .. }
+            ) && self.check_literal_against(arg, expected)
+            {
+                continue;
+            }
+            if !fits(at, expected) {
+                self.error(
+                    format!(
+                        "payload of type {}, found {}",
+                        self.type_name(expected),
+                        self.type_name(at)
+                    ),
+                    arg.span(),
+                );
+            }
+        }
+        Type::ErrCode
+    }
+
+    fn unknown_variant(&mut self, name: &str, variant: &str, span: Span) {
+        self.error(format!("enum '{name}' has no variant '{variant}'"), span);
+    }
+
+    /// Pins a template's type parameters (ADR 0035): explicit
+    /// arguments resolve in the caller's view — they were written
     /// here — and must match the parameter count; otherwise `pairs`
     /// (template annotation, argument type) drive unification. `help`
     /// names the explicit syntax for the cannot-infer diagnostic.
@@ -743,24 +1461,38 @@ impl Checker<'_, '_> {
     /// — explicitly, or by unifying parameter annotations against the
     /// argument types — then check arguments against the substituted
     /// signature, record the call target, and enqueue the instance.
+    /// A method call passes its already-typed receiver as the first
+    /// argument (ADR 0040).
     fn check_generic_call(
         &mut self,
         name: &str,
         tkey: (usize, String),
         type_args: &[TypeAnn],
+        receiver: Option<(&Expr, Type)>,
         args: &[Expr],
         span: Span,
     ) -> Type {
         let tmpl = self.mono.fn_templates[&tkey];
+        let skip = receiver.is_some() as usize;
+        let mut all: Vec<&Expr> = Vec::with_capacity(args.len() + skip);
+        let mut arg_tys: Vec<Type> = Vec::with_capacity(args.len() + skip);
+        if let Some((e, t)) = receiver {
+            all.push(e);
+            arg_tys.push(t);
+        }
         // Arguments type first — inference needs them, and their own
         // errors must not vanish behind inference noise.
-        let arg_tys: Vec<Type> = args.iter().map(|a| self.type_of_expr(a)).collect();
-        if args.len() != tmpl.params.len() {
+        for arg in args {
+            all.push(arg);
+            arg_tys.push(self.type_of_expr(arg));
+        }
+        if all.len() != tmpl.params.len() {
+            let what = if skip == 1 { "method" } else { "function" };
             self.error(
-                format!(
-                    "function '{}' expects {} argument(s), found {}",
-                    name,
-                    tmpl.params.len(),
+                format!(
+                    "{what} '{}' expects {} argument(s), found {}",
+                    crate::types::pretty(name),
+                    tmpl.params.len() - skip,
                     args.len()
                 ),
                 span,
@@ -785,11 +1517,20 @@ impl Checker<'_, '_> {
             instance_signature(tmpl, &bind, &mut self.cx_in(tkey.0), tmpl.span);
         // Argument fit against the substituted parameters; literal
         // re-recording keeps the type table concrete for the engines.
-        for ((arg, at), expected) in args.iter().zip(&arg_tys).zip(&params) {
+        for ((arg, at), expected) in all.into_iter().zip(&arg_tys).zip(&params) {
             if poisoned(at) {
                 continue;
             }
-            if matches!(arg, Expr::ArrayLit { .. }) && self.check_literal_against(arg, expected) {
+            if matches!(
+                arg,
+                Expr::ArrayLit { .. }
+                    | Expr::ArrayRepeat { .. }
+                    | Expr::MapLit { .. }
+                    | Expr::TupleLit { .. }
+                    | Expr::Int(..)
+                    | Expr::Unary { .. }
+            ) && self.check_literal_against(arg, expected)
+            {
                 continue;
             }
             if !fits(at, expected) {
@@ -804,14 +1545,146 @@ impl Checker<'_, '_> {
             }
         }
         // Register the instance and resolve this call site to it.
+        let ikey = self.request_instance(name, &tkey, &bind, span);
+        self.out.call_targets.insert(span, ikey);
+        ret
+    }
+
+    /// The declaration whose impl blocks hold `ty`'s methods (ADR
+    /// 0040): its own key, or its template's for a generic instance.
+    /// Only structs and enums have methods.
+    fn method_owner(&self, ty: &Type) -> Option<(usize, String)> {
+        let (Type::Struct(m, n) | Type::Enum(m, n)) = ty else {
+            return None;
+        };
+        Some(match self.mono.instance_args.get(&(*m, n.clone())) {
+            Some((tkey, _)) => tkey.clone(),
+            None => (*m, n.clone()),
+        })
+    }
+
+    /// Whether `ty` satisfies interface `iface` (ADR 0041): for every
+    /// requirement it has a method of that name whose signature, past
+    /// `self`, equals the requirement's with `Self` = `ty`. The error
+    /// says what is missing.
+    fn satisfies(&mut self, ty: &Type, iface: &(usize, String)) -> Result<(), String> {
+        let decl = self.mono.interfaces[iface];
+        let Some(owner) = self.method_owner(ty) else {
+            return Err(format!("type {} has no methods", self.type_name(ty)));
+        };
+        // A generic owner's methods bind its parameters from the
+        // instance; a method's own parameters could never be inferred
+        // from a requirement.
+        let owner_args = match ty {
+            Type::Struct(m, n) | Type::Enum(m, n) => self
+                .mono
+                .instance_args
+                .get(&(*m, n.clone()))
+                .map(|(_, args)| args.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        for req in &decl.methods {
+            let want = required_signature(req, ty, &mut self.cx_in(iface.0));
+            let key = (owner.0, format!("{}.{}", owner.1, req.name));
+            let have = if let Some(tmpl) = self.mono.fn_templates.get(&key).copied() {
+                if tmpl.type_params.len() > owner_args.len() {
+                    return Err(format!(
+                        "method '{}' is generic, so it cannot satisfy a requirement",
+                        req.name
+                    ));
+                }
+                let bind = bind_params(&tmpl.type_params, owner_args.clone());
+                instance_signature(tmpl, &bind, &mut self.cx_in(key.0), tmpl.span)
+            } else if let Some(sig) = self.sigs.get(&key) {
+                sig.clone()
+            } else {
+                return Err(format!(
+                    "type {} has no method '{}'",
+                    self.type_name(ty),
+                    req.name
+                ));
+            };
+            if have.params.get(1..) != want.params.get(1..) || have.ret != want.ret {
+                let shape = |sig: FnSig| Type::Fn(sig.params[1..].to_vec(), Box::new(sig.ret));
+                return Err(format!(
+                    "its '{}' is {}, but '{}' requires {}",
+                    req.name,
+                    self.type_name(&shape(have)),
+                    iface.1,
+                    self.type_name(&shape(want))
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a template's `T: I` bounds against `bind` at the call
+    /// site (ADR 0041), before any instance exists — so an unsuitable
+    /// argument is reported here, not deep inside the template body.
+    fn bounds_hold(
+        &mut self,
+        name: &str,
+        tkey: &(usize, String),
+        bind: &HashMap<String, Type>,
+        span: Span,
+    ) -> bool {
+        let tmpl = self.mono.fn_templates[tkey];
+        let mut ok = true;
+        for b in &tmpl.bounds {
+            // An unresolvable bound was diagnosed with the declaration.
+            let Some(iface) = self.ty_aliases[tkey.0]
+                .get(&b.interface)
+                .filter(|k| self.mono.interfaces.contains_key(*k))
+                .cloned()
+            else {
+                continue;
+            };
+            let ty = &bind[&b.param];
+            if poisoned(ty) {
+                continue;
+            }
+            if let Err(reason) = self.satisfies(ty, &iface) {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        format!(
+                            "type {} does not satisfy '{}: {}' required by '{}'",
+                            self.type_name(ty),
+                            b.param,
+                            b.interface,
+                            crate::types::pretty(name)
+                        ),
+                        span,
+                    )
+                    .with_help(reason),
+                );
+                ok = false;
+            }
+        }
+        ok
+    }
+
+    /// Enqueues the instance of template `tkey` at `bind` (once per
+    /// program, depth-capped) and returns its key (ADR 0035).
+    /// Unsatisfied bounds (ADR 0041) leave it unrequested.
+    fn request_instance(
+        &mut self,
+        name: &str,
+        tkey: &(usize, String),
+        bind: &HashMap<String, Type>,
+        span: Span,
+    ) -> (usize, String) {
+        let tmpl = self.mono.fn_templates[tkey];
         let ordered: Vec<Type> = tmpl
             .type_params
             .iter()
             .map(|(n, _)| bind[n].clone())
             .collect();
-        let ikey = super::generics::fn_instance_key(&tkey, &ordered);
-        self.out.call_targets.insert(span, ikey.clone());
-        if self.mono.requested.insert(ikey) {
+        let ikey = super::generics::fn_instance_key(tkey, &ordered);
+        if !self.bounds_hold(name, tkey, bind, span) {
+            return ikey;
+        }
+        if self.mono.requested.insert(ikey.clone()) {
             if self.inst_depth >= super::generics::DEPTH_CAP {
                 self.error(
                     format!(
@@ -823,12 +1696,120 @@ impl Checker<'_, '_> {
             } else {
                 let depth = self.inst_depth + 1;
                 self.mono.work.push(super::generics::FnWork {
-                    template: tkey,
+                    template: tkey.clone(),
                     args: ordered,
                     depth,
                 });
             }
         }
+        ikey
+    }
+
+    /// A function named as a value (ADR 0038): a monomorphic function,
+    /// or a generic one pinned by explicit type arguments — there are
+    /// no arguments to infer from. The site lands in `fn_values`.
+    fn fn_value(&mut self, name: &str, type_args: &[TypeAnn], span: Span) -> Type {
+        let Some(target) = self.fn_alias.get(name).cloned() else {
+            self.diagnostics.push(
+                Diagnostic::error(format!("undefined function '{name}'"), span)
+                    .suggest(name, self.fn_alias.keys().map(String::as_str)),
+            );
+            return Type::Error;
+        };
+        if target == (0, syntax::ENTRY_FN.to_string()) {
+            self.error(
+                format!("'{}' cannot be used as a value", syntax::ENTRY_FN),
+                span,
+            );
+            return Type::Error;
+        }
+        let (key, FnSig { params, ret }) =
+            if let Some(tmpl) = self.mono.fn_templates.get(&target).copied() {
+                if type_args.is_empty() {
+                    self.error(
+                        format!("'{name}' is generic — write '{name}<…>' to name an instance"),
+                        span,
+                    );
+                    return Type::Error;
+                }
+                let Some(bind) = self.bind_type_params(
+                    name,
+                    &tmpl.type_params,
+                    type_args,
+                    &[],
+                    format!("write the type arguments: '{name}<…>'"),
+                    span,
+                ) else {
+                    return Type::Error;
+                };
+                let sig = instance_signature(tmpl, &bind, &mut self.cx_in(target.0), tmpl.span);
+                (self.request_instance(name, &target, &bind, span), sig)
+            } else {
+                if !type_args.is_empty() {
+                    self.error(format!("'{name}' takes no type arguments"), span);
+                }
+                let sig = self.sigs[&target].clone();
+                (target, sig)
+            };
+        self.out.fn_values.insert(span, key);
+        Type::Fn(params, Box::new(ret))
+    }
+
+    /// A call through a function value (ADR 0038): the callee is any
+    /// expression of function type; the arguments check against its
+    /// parameter types exactly like a direct call's. The engines see
+    /// the callee's recorded `Type::Fn` and call indirectly.
+    fn check_indirect_call(
+        &mut self,
+        callee: &Expr,
+        type_args: &[TypeAnn],
+        args: &[Expr],
+        span: Span,
+    ) -> Type {
+        if !type_args.is_empty() {
+            self.error("a function value takes no type arguments".to_string(), span);
+        }
+        let (params, ret) = match self.type_of_expr(callee) {
+            Type::Fn(params, ret) => (params, *ret),
+            t => {
+                if !poisoned(&t) {
+                    self.error(
+                        format!("cannot call a value of type {}", self.type_name(&t)),
+                        callee.span(),
+                    );
+                }
+                for arg in args {
+                    self.type_of_expr(arg);
+                }
+                return Type::Error;
+            }
+        };
+        if args.len() != params.len() {
+            self.error(
+                format!(
+                    "function value expects {} argument(s), found {}",
+                    params.len(),
+                    args.len()
+                ),
+                span,
+            );
+        }
+        for (arg, expected) in args.iter().zip(&params) {
+            if self.check_literal_against(arg, expected) {
+                continue;
+            }
+            let got = self.type_of_expr(arg);
+            if !fits(&got, expected) {
+                self.error(
+                    format!(
+                        "expected argument of type {}, found {}",
+                        self.type_name(expected),
+                        self.type_name(&got)
+                    ),
+                    arg.span(),
+                );
+            }
+        }
         ret
     }
 
@@ -838,40 +1819,244 @@ impl Checker<'_, '_> {
     /// returns true when it handled the pair — callers fall back to the
     /// ordinary `fits` path otherwise.
     pub(super) fn check_literal_against(&mut self, value: &Expr, expected: &Type) -> bool {
+        // A match expression checks each arm against the slot (ADR 0044).
+        if let Expr::Match {
+            scrutinee,
+            arms,
+            else_value,
+            span,
+        } = value
+        {
+            let ty = self.check_match_expr(
+                scrutinee,
+                arms,
+                else_value.as_deref(),
+                *span,
+                Some(expected),
+            );
+            self.out.expr_types.insert(*span, ty);
+            return true;
+        }
         let mut target = expected;
         while let Type::Optional(inner) = target {
             target = inner;
         }
-        let (Type::Array(elem), Expr::ArrayLit { elements, .. }) = (target, value) else {
-            return false;
+        if self.sized_literal(value, target).is_some() {
+            return true;
+        }
+        match (target, value) {
+            (Type::Array(elem), Expr::ArrayLit { elements, .. }) => {
+                // This path bypasses type_of_expr, but the per-expression
+                // type table must stay total: the literal's type IS the
+                // declared one.
+                self.out.expr_types.insert(value.span(), expected.clone());
+                for element in elements {
+                    self.check_slot(element, elem, "array element", "element");
+                }
+                true
+            }
+            // Map literals follow the same rule per entry (ADR 0042).
+            (Type::Map(k, v), Expr::MapLit { entries, .. }) => {
+                self.out.expr_types.insert(value.span(), expected.clone());
+                for (key, val) in entries {
+                    self.check_key(key, k);
+                    self.check_slot(val, v, "map value", "value");
+                }
+                true
+            }
+            // A fixed array's literal checks every element against its
+            // one element type and must have exactly its length
+            // (ADR 0056). Recorded bare, like a tuple's.
+            (Type::Fixed(elem, n), Expr::ArrayLit { elements, .. }) => {
+                self.out.expr_types.insert(value.span(), target.clone());
+                if elements.len() != *n {
+                    self.error(
+                        format!(
+                            "expected {n} elements for {}, found {}",
+                            self.type_name(target),
+                            elements.len()
+                        ),
+                        value.span(),
+                    );
+                }
+                for element in elements {
+                    self.check_slot(element, elem, "array element", "element");
+                }
+                true
+            }
+            (
+                Type::Fixed(elem, n),
+                Expr::ArrayRepeat {
+                    value: item, count, ..
+                },
+            ) if count == n => {
+                self.out.expr_types.insert(value.span(), target.clone());
+                self.check_slot(item, elem, "array element", "element");
+                true
+            }
+            // Tuple literals check position by position (ADR 0043); an
+            // arity mismatch falls through to the plain `fits` error.
+            (Type::Tuple(elems), Expr::TupleLit { elements, .. })
+                if elems.len() == elements.len() =>
+            {
+                // The bare tuple, not `expected`: a value tuple under `?`
+                // is tag-wrapped at the slot, so the literal itself must
+                // not read as optional-shaped.
+                self.out.expr_types.insert(value.span(), target.clone());
+                for (element, et) in elements.iter().zip(elems) {
+                    self.check_slot(element, et, "tuple element", "element");
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// An int literal, or a negated one, meeting a sized integer slot
+    /// takes the slot's type (ADR 0052) — `let b: u8 = 200`, `x + 1` on
+    /// a u16 — and must be one of its values. Returns the adopted type;
+    /// `None` when `value` is no literal or `want` no sized type.
+    pub(super) fn sized_literal(&mut self, value: &Expr, want: &Type) -> Option<Type> {
+        let mut want = want;
+        while let Type::Optional(inner) = want {
+            want = inner;
+        }
+        let Type::Sized(t) = want else {
+            return None;
         };
-        // This path bypasses type_of_expr, but the per-expression type
-        // table must stay total: the literal's type IS the declared one.
-        self.out.expr_types.insert(value.span(), expected.clone());
-        for element in elements {
-            if self.check_literal_against(element, elem) {
-                continue;
+        let (n, inner) = match value {
+            Expr::Int(n, _) => (*n, None),
+            Expr::Unary {
+                op: UnOp::Neg, rhs, ..
+            } => match rhs.as_ref() {
+                Expr::Int(n, span) => (n.wrapping_neg(), Some(*span)),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !t.holds(n) {
+            self.error(
+                format!("literal {n} does not fit {}", t.name()),
+                value.span(),
+            );
+        }
+        self.out.expr_types.insert(value.span(), want.clone());
+        if let Some(span) = inner {
+            self.out.expr_types.insert(span, want.clone());
+        }
+        Some(want.clone())
+    }
+
+    /// The match expression (ADR 0044): arm heads and coverage as in
+    /// the statement form, each arm's value typed in its own scope.
+    /// With a declared slot every arm checks against it; without one
+    /// the first arm names the type, like an array literal's first
+    /// element, and a `null` arm widens it to optional.
+    fn check_match_expr(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm<Expr>],
+        else_value: Option<&Expr>,
+        span: Span,
+        expected: Option<&Type>,
+    ) -> Type {
+        let mut matrix = self.match_scrutinee(scrutinee);
+        let mut result: Option<Type> = None;
+        let mut saw_null = false;
+        let values = arms
+            .iter()
+            .map(|a| (Some(a), &a.body))
+            .chain(else_value.map(|e| (None, e)));
+        for (arm, value) in values {
+            self.nonnull.push(NarrowFrame::new(HashMap::new()));
+            self.scopes.push(HashMap::new());
+            if let Some(arm) = arm {
+                self.check_arm_head(&mut matrix, arm);
             }
-            let got = self.type_of_expr(element);
-            if !fits(&got, elem) {
-                let mut diag = Diagnostic::error(
-                    format!(
-                        "array element: expected {}, found {}",
-                        self.type_name(elem),
-                        self.type_name(&got)
+            if let Some(want) = expected {
+                self.check_slot(value, want, "match arm", "arm");
+            } else {
+                let ty = self.type_of_expr(value);
+                match &result {
+                    _ if ty == Type::Null => saw_null = true,
+                    None => result = Some(ty),
+                    Some(r) if unconstrained(r) && !unconstrained(&ty) => result = Some(ty),
+                    Some(r) if !fits(&ty, r) => self.error(
+                        format!(
+                            "match arms must share one type: expected {}, found {}",
+                            self.type_name(r),
+                            self.type_name(&ty)
+                        ),
+                        value.span(),
                     ),
-                    element.span(),
-                );
-                if got == Type::Null {
-                    diag = diag.with_help(format!(
-                        "declare the element type optional: '{}?'",
-                        self.type_name(elem)
-                    ));
+                    Some(_) => {}
                 }
-                self.diagnostics.push(diag);
             }
+            self.scopes.pop();
+            self.nonnull.pop();
+        }
+        self.match_coverage(&matrix, else_value.is_some(), span);
+        if let Some(want) = expected {
+            return want.clone();
+        }
+        match (result, saw_null) {
+            (Some(t @ Type::Optional(_)), _) | (Some(t), false) => t,
+            (Some(t), true) => Type::Optional(Box::new(t)),
+            (None, true) => {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        "match type cannot be inferred from 'null'".to_string(),
+                        span,
+                    )
+                    .with_help("declare the result's type".to_string()),
+                );
+                Type::Error
+            }
+            // No arms at all: the coverage check already spoke.
+            (None, false) => Type::Error,
+        }
+    }
+
+    /// A map key against the key type. Hashing walks the key like `==`
+    /// does (ADR 0042), so an error union anywhere inside is rejected
+    /// here, the way `==` rejects it (ADR 0037).
+    fn check_key(&mut self, key: &Expr, k: &Type) {
+        self.check_slot(key, k, "map key", "key");
+        if self.reaches_err_union(k, EQ_FUEL) {
+            self.error(
+                format!(
+                    "{} cannot be a map key — it contains an error union",
+                    self.type_name(k)
+                ),
+                key.span(),
+            );
+        }
+    }
+
+    /// One literal entry against its declared slot type: nested
+    /// literals recurse, everything else must fit.
+    fn check_slot(&mut self, e: &Expr, want: &Type, what: &str, noun: &str) {
+        if self.check_literal_against(e, want) {
+            return;
+        }
+        let got = self.type_of_expr(e);
+        if !fits(&got, want) {
+            let mut diag = Diagnostic::error(
+                format!(
+                    "{what}: expected {}, found {}",
+                    self.type_name(want),
+                    self.type_name(&got)
+                ),
+                e.span(),
+            );
+            if got == Type::Null {
+                diag = diag.with_help(format!(
+                    "declare the {noun} type optional: '{}?'",
+                    self.type_name(want)
+                ));
+            }
+            self.diagnostics.push(diag);
         }
-        true
     }
 
     fn check_field(&mut self, base: &Expr, field: &str, optional: bool, span: Span) -> Type {
@@ -907,64 +2092,75 @@ impl Checker<'_, '_> {
             }
             (ty, false) => ty,
         };
-        // Stage 2: the field lookup, shared by both forms.
-        let Type::Struct(sm, struct_name) = inner else {
-            self.error(
-                format!("type {} has no fields", self.type_name(&base_ty)),
-                span,
-            );
-            return Type::Error;
-        };
-        let field_ty = self
-            .mono
-            .structs
-            .get(&(*sm, struct_name.clone()))
-            .and_then(|st| {
-                st.fields
-                    .iter()
-                    .position(|(fname, _)| fname == field)
-                    .map(|i| (i, st.fields[i].1.clone()))
-            })
-            .map(|(i, ty)| {
-                // Codegen's layout table (field offsets are computed from
-                // the base def, since fields may be multi-word).
-                self.out.field_slots.insert(
+        // Stage 2: the field lookup, shared by both forms. A tuple's
+        // fields are its positions (ADR 0043).
+        let (index, field_ty) = match inner {
+            Type::Struct(sm, struct_name) => {
+                let found = self
+                    .mono
+                    .structs
+                    .get(&(*sm, struct_name.clone()))
+                    .and_then(|st| {
+                        st.fields
+                            .iter()
+                            .position(|(fname, _)| fname == field)
+                            .map(|i| (i, st.fields[i].1.clone()))
+                    });
+                let Some(found) = found else {
+                    self.error(
+                        format!("struct '{struct_name}' has no field '{field}'"),
+                        span,
+                    );
+                    return Type::Error;
+                };
+                found
+            }
+            Type::Tuple(elems) => {
+                let found = field
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| Some((i, elems.get(i)?.clone())));
+                let Some(found) = found else {
+                    self.error(
+                        format!("tuple {} has no element '{field}'", self.type_name(inner)),
+                        span,
+                    );
+                    return Type::Error;
+                };
+                found
+            }
+            _ => {
+                self.error(
+                    format!("type {} has no fields", self.type_name(&base_ty)),
                     span,
-                    FieldSlot {
-                        base: (*sm, struct_name.clone()),
-                        index: i,
-                        ty: ty.clone(),
-                    },
                 );
-                ty
-            });
+                return Type::Error;
+            }
+        };
+        // Codegen's layout table (field offsets are computed from the
+        // base type, since fields may be multi-word).
+        self.out.field_slots.insert(
+            span,
+            FieldSlot {
+                base: inner.clone(),
+                index,
+                ty: field_ty.clone(),
+            },
+        );
         match field_ty {
-            Some(ty) if optional => match ty {
+            ty if optional => match ty {
                 // `a?.b` is optional; an already-optional field stays flat.
                 Type::Optional(_) => ty,
                 other => Type::Optional(Box::new(other)),
             },
             // A narrowed field path reads as its inner type, same as
             // narrowed variables in `lookup`.
-            Some(Type::Optional(inner)) if self.field_fact(base, field) == Some(Fact::NonNull) => {
-                *inner
-            }
+            Type::Optional(inner) if self.field_fact(base, field) == Some(Fact::NonNull) => *inner,
             // The `T!` mirror (ADR 0037): a proven-value field reads as
             // its payload type, a proven-error field as the code.
-            Some(Type::ErrUnion(inner)) if self.field_fact(base, field) == Some(Fact::NoErr) => {
-                *inner
-            }
-            Some(Type::ErrUnion(_)) if self.field_fact(base, field) == Some(Fact::IsErr) => {
-                Type::ErrCode
-            }
-            Some(ty) => ty,
-            None => {
-                self.error(
-                    format!("struct '{struct_name}' has no field '{field}'"),
-                    span,
-                );
-                Type::Error
-            }
+            Type::ErrUnion(inner) if self.field_fact(base, field) == Some(Fact::NoErr) => *inner,
+            Type::ErrUnion(_) if self.field_fact(base, field) == Some(Fact::IsErr) => Type::ErrCode,
+            ty => ty,
         }
     }
 
@@ -1068,8 +2264,12 @@ impl Checker<'_, '_> {
                 let base_ty = self.type_of_expr(base);
                 self.is_by_ref(&base_ty) || self.crosses_ref(base)
             }
-            // Arrays are references — element writes never touch the binding.
-            Expr::Index { .. } => true,
+            // Arrays are references — element writes never touch the
+            // binding — but a fixed array is a value hop (ADR 0056).
+            Expr::Index { base, .. } => {
+                let base_ty = self.type_of_expr(base);
+                !matches!(base_ty, Type::Fixed(..)) || self.crosses_ref(base)
+            }
             _ => false,
         }
     }
@@ -1093,7 +2293,7 @@ impl Checker<'_, '_> {
         };
         match t {
             Type::ErrUnion(_) => true,
-            Type::Optional(inner) => self.reaches_err_union(inner, next),
+            Type::Optional(inner) | Type::Fixed(inner, _) => self.reaches_err_union(inner, next),
             Type::Struct(m, n) => {
                 let def = &self.mono.structs[&(*m, n.clone())];
                 !def.by_ref
@@ -1107,6 +2307,7 @@ impl Checker<'_, '_> {
                 .iter()
                 .flat_map(|(_, payloads)| payloads)
                 .any(|pt| self.reaches_err_union(pt, next)),
+            Type::Tuple(elems) => elems.iter().any(|et| self.reaches_err_union(et, next)),
             _ => false,
         }
     }
@@ -1132,6 +2333,7 @@ impl Checker<'_, '_> {
             .last_mut()
             .unwrap()
             .insert(name.to_string(), VarInfo { ty, mutable });
+        self.note_binding(name);
         if let Some(frame) = self.nonnull.last_mut() {
             frame.facts.retain(|f, _| !covers(name, f));
             frame.shadowed.insert(name.to_string());
@@ -1143,6 +2345,12 @@ impl Checker<'_, '_> {
         self.scopes.iter().rev().find_map(|scope| scope.get(name))
     }
 
+    /// Does `name` read a variable — a local, or a module var
+    /// (ADR 0048)? Variables shadow functions, builtins, and types.
+    fn is_variable(&self, name: &str) -> bool {
+        self.find_var(name).is_some() || self.global_alias.contains_key(name)
+    }
+
     /// Arity and per-argument typing for a fixed-signature builtin;
     /// arguments are always typed (even on arity errors) for recovery.
     fn expect_builtin_args(&mut self, name: &str, args: &[Expr], want: &[Type], span: Span) {
@@ -1184,7 +2392,89 @@ impl Checker<'_, '_> {
         self.type_of_expr(e)
     }
 
+    /// Records `name` as a capture of every function literal it is
+    /// free in (ADR 0039); true when the innermost literal captures
+    /// it. A capture keeps the binding's declared type — narrowing
+    /// never crosses into a literal.
+    pub(super) fn capture(&mut self, name: &str) -> bool {
+        if self.closures.is_empty() {
+            return false;
+        }
+        let Some(depth) = self.scopes.iter().rposition(|s| s.contains_key(name)) else {
+            return false;
+        };
+        let ty = self.scopes[depth][name].ty.clone();
+        let mut captured = false;
+        for frame in self.closures.iter_mut().filter(|f| f.base > depth) {
+            if !frame.captures.iter().any(|(n, _)| n == name) {
+                frame.captures.push((name.to_string(), ty.clone()));
+            }
+            captured = true;
+        }
+        captured
+    }
+
+    /// A function literal (ADR 0039): the body checks like a
+    /// function's — its own return type, no enclosing loop, a fresh
+    /// narrowing stack — with the enclosing scopes still visible;
+    /// names resolved through them become captures.
+    fn check_lambda(
+        &mut self,
+        params: &[Param],
+        return_type: Option<&TypeAnn>,
+        body: &[Stmt],
+        span: Span,
+    ) -> Type {
+        let param_tys: Vec<Type> = params.iter().map(|p| self.resolve(&p.ty, span)).collect();
+        let ret = match return_type {
+            Some(t) => self.resolve(t, span),
+            None => Type::Unit,
+        };
+        let outer_ret = std::mem::replace(&mut self.ret, ret.clone());
+        let outer_loops = std::mem::take(&mut self.loops);
+        let outer_deferring = std::mem::replace(&mut self.deferring, false);
+        let outer_arenas = std::mem::take(&mut self.arenas);
+        let outer_facts =
+            std::mem::replace(&mut self.nonnull, vec![NarrowFrame::new(HashMap::new())]);
+        self.closures.push(ClosureFrame {
+            base: self.scopes.len(),
+            captures: Vec::new(),
+        });
+        let scope = params
+            .iter()
+            .zip(&param_tys)
+            .map(|(p, ty)| {
+                let info = VarInfo {
+                    ty: ty.clone(),
+                    mutable: false,
+                };
+                (p.name.clone(), info)
+            })
+            .collect();
+        self.scopes.push(scope);
+        for stmt in body {
+            self.check_stmt(stmt);
+        }
+        self.scopes.pop();
+        let frame = self.closures.pop().expect("frame pushed above");
+        self.nonnull = outer_facts;
+        self.loops = outer_loops;
+        self.deferring = outer_deferring;
+        self.arenas = outer_arenas;
+        self.ret = outer_ret;
+        if ret != Type::Unit && !poisoned(&ret) && !always_returns(body) {
+            self.error(
+                "not all paths in this function literal return a value".to_string(),
+                span,
+            );
+        }
+        self.out.captures.insert(span, frame.captures);
+        self.note_arena_lambda(params.len(), span);
+        Type::Fn(param_tys, Box::new(ret))
+    }
+
     pub(super) fn lookup(&mut self, name: &str, span: Span) -> Type {
+        self.capture(name);
         if let Some(info) = self.find_var(name) {
             // A narrowed optional reads as its inner type; a narrowed
             // error union as its value type or as `error` (ADR 0034).
@@ -1206,3 +2496,54 @@ impl Checker<'_, '_> {
         Type::Error
     }
 }
+
+/// The type of a read that can miss — `m[k]` (ADR 0042), `pop(xs)`
+/// (ADR 0055): the value or `null` — an already-optional value type
+/// stays as it is (`T??` is just `T?`).
+fn or_null(v: Type) -> Type {
+    match v {
+        Type::Optional(_) => v,
+        other => Type::Optional(Box::new(other)),
+    }
+}
+
+/// The string library's signatures (ADR 0054): parameters, then the
+/// result. A miss is a value — `find` and the parsers produce optionals.
+fn string_builtin(name: &str) -> Option<(Vec<Type>, Type)> {
+    let strs = |n: usize| vec![Type::Str; n];
+    Some(match name {
+        syntax::BUILTIN_FIND => (strs(2), Type::Optional(Box::new(Type::Int))),
+        syntax::BUILTIN_STARTS_WITH | syntax::BUILTIN_ENDS_WITH => (strs(2), Type::Bool),
+        syntax::BUILTIN_SPLIT => (strs(2), Type::Array(Box::new(Type::Str))),
+        syntax::BUILTIN_TRIM | syntax::BUILTIN_TO_UPPER | syntax::BUILTIN_TO_LOWER => {
+            (strs(1), Type::Str)
+        }
+        syntax::BUILTIN_REPLACE => (strs(3), Type::Str),
+        syntax::BUILTIN_JOIN => (vec![Type::Array(Box::new(Type::Str)), Type::Str], Type::Str),
+        syntax::BUILTIN_PARSE_INT => (strs(1), Type::Optional(Box::new(Type::Int))),
+        syntax::BUILTIN_PARSE_FLOAT => (strs(1), Type::Optional(Box::new(Type::Float))),
+        _ => return None,
+    })
+}
+
+/// The array library's names (ADR 0055); `insert` is shared with maps
+/// and dispatches on its first argument.
+fn is_array_builtin(name: &str) -> bool {
+    [
+        syntax::BUILTIN_POP,
+        syntax::BUILTIN_REMOVE_AT,
+        syntax::BUILTIN_SLICE,
+        syntax::BUILTIN_CLEAR,
+        syntax::BUILTIN_SORT,
+        syntax::BUILTIN_REVERSE,
+        syntax::BUILTIN_INDEX_OF,
+        syntax::BUILTIN_CONTAINS,
+    ]
+    .contains(&name)
+}
+
+/// The orders `sort` knows (ADR 0055): numeric, IEEE total for floats,
+/// bytewise for strings.
+fn sortable(t: &Type) -> bool {
+    matches!(t, Type::Int | Type::Sized(_) | Type::Float | Type::Str)
+}
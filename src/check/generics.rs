@@ -6,7 +6,9 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::ast::{EnumDecl, Expr, Function, MatchArm, Param, Stmt, Struct, TypeAnn};
+use crate::ast::{
+    EnumDecl, Expr, Function, InterfaceDecl, MatchArm, Param, Pattern, Stmt, Struct, TypeAnn,
+};
 use crate::span::Span;
 use crate::types::{EnumType, StructType, Type, instance_name};
 
@@ -38,6 +40,8 @@ pub(super) struct Mono<'g> {
     pub fn_templates: HashMap<ItemKey, &'g Function>,
     pub struct_templates: HashMap<ItemKey, &'g Struct>,
     pub enum_templates: HashMap<ItemKey, &'g EnumDecl>,
+    /// Interfaces by key (ADR 0041) — bounds resolve against them.
+    pub interfaces: HashMap<ItemKey, &'g InterfaceDecl>,
     /// Every struct layout — monomorphic declarations and instances
     /// alike. Moves into `Resolutions` when checking completes.
     pub structs: HashMap<ItemKey, StructType>,
@@ -57,6 +61,7 @@ impl Mono<'_> {
             fn_templates: HashMap::new(),
             struct_templates: HashMap::new(),
             enum_templates: HashMap::new(),
+            interfaces: HashMap::new(),
             structs: HashMap::new(),
             enums: HashMap::new(),
             instance_args: HashMap::new(),
@@ -113,8 +118,55 @@ pub(super) fn unify(
             Type::Array(a) => unify(inner, a, tparams, bind, instance_args, ty_alias),
             _ => Ok(()),
         },
+        // So are fixed arrays, at their own length (ADR 0056).
+        TypeAnn::Fixed(inner, n) => match actual {
+            Type::Fixed(a, an) if an == n => {
+                unify(inner, a, tparams, bind, instance_args, ty_alias)
+            }
+            _ => Ok(()),
+        },
+        // `fun(T): U` against a function value: parameters and return
+        // are exact positions (function types are invariant, ADR 0038).
+        TypeAnn::Fn(anns, ret) => {
+            let Type::Fn(params, aret) = actual else {
+                return Ok(());
+            };
+            if anns.len() != params.len() {
+                return Ok(());
+            }
+            for (ann, p) in anns.iter().zip(params) {
+                unify(ann, p, tparams, bind, instance_args, ty_alias)?;
+            }
+            match ret {
+                Some(r) => unify(r, aret, tparams, bind, instance_args, ty_alias),
+                None => Ok(()),
+            }
+        }
+        // `(T, U)` against a tuple of the same arity: element-wise,
+        // each position exact (ADR 0043).
+        TypeAnn::Tuple(anns) => {
+            let Type::Tuple(elems) = actual else {
+                return Ok(());
+            };
+            if anns.len() != elems.len() {
+                return Ok(());
+            }
+            for (ann, e) in anns.iter().zip(elems) {
+                unify(ann, e, tparams, bind, instance_args, ty_alias)?;
+            }
+            Ok(())
+        }
         // `Pair<T, U>` against an instantiated struct or enum:
         // decompose when the argument instantiates the same template.
+        // `map<K, V>` against a map (ADR 0042): both positions are
+        // invariant, like an array's element.
+        TypeAnn::Applied(n, anns) if n == crate::syntax::TYPE_MAP && !ty_alias.contains_key(n) => {
+            let (Type::Map(k, v), [ka, va]) = (actual, anns.as_slice()) else {
+                return Ok(());
+            };
+            unify(ka, k, tparams, bind, instance_args, ty_alias)?;
+            unify(va, v, tparams, bind, instance_args, ty_alias)
+        }
         TypeAnn::Applied(n, anns) => {
             let (am, an) = match actual {
                 Type::Struct(am, an) | Type::Enum(am, an) => (am, an),
@@ -154,7 +206,15 @@ pub(super) fn substitute_ann(ann: &TypeAnn, bind: &HashMap<String, Type>) -> Typ
         ),
         TypeAnn::Optional(inner) => TypeAnn::Optional(Box::new(substitute_ann(inner, bind))),
         TypeAnn::Array(inner) => TypeAnn::Array(Box::new(substitute_ann(inner, bind))),
+        TypeAnn::Fixed(inner, n) => TypeAnn::Fixed(Box::new(substitute_ann(inner, bind)), *n),
         TypeAnn::ErrUnion(inner) => TypeAnn::ErrUnion(Box::new(substitute_ann(inner, bind))),
+        TypeAnn::Fn(params, ret) => TypeAnn::Fn(
+            params.iter().map(|p| substitute_ann(p, bind)).collect(),
+            ret.as_ref().map(|r| Box::new(substitute_ann(r, bind))),
+        ),
+        TypeAnn::Tuple(elems) => {
+            TypeAnn::Tuple(elems.iter().map(|e| substitute_ann(e, bind)).collect())
+        }
         other => other.clone(),
     }
 }
@@ -188,6 +248,7 @@ pub(super) fn instantiate_fn(
         exported: f.exported,
         name,
         type_params: Vec::new(),
+        bounds: Vec::new(),
         params: f
             .params
             .iter()
@@ -221,23 +282,77 @@ fn clone_stmt(stmt: &Stmt, bind: &HashMap<String, Type>, delta: usize) -> Stmt {
             value: clone_expr(value, bind, delta),
             span: shift(*span, delta),
         },
+        Stmt::LetTuple {
+            mutable,
+            names,
+            value,
+            span,
+        } => Stmt::LetTuple {
+            mutable: *mutable,
+            names: names.clone(),
+            value: clone_expr(value, bind, delta),
+            span: shift(*span, delta),
+        },
+        Stmt::LetStruct {
+            mutable,
+            fields,
+            value,
+            span,
+        } => Stmt::LetStruct {
+            mutable: *mutable,
+            fields: fields
+                .iter()
+                .map(|(field, name, s)| (field.clone(), name.clone(), shift(*s, delta)))
+                .collect(),
+            value: clone_expr(value, bind, delta),
+            span: shift(*span, delta),
+        },
+        Stmt::LetElse {
+            mutable,
+            pattern,
+            value,
+            else_body,
+            span,
+        } => Stmt::LetElse {
+            mutable: *mutable,
+            pattern: clone_pattern(pattern, bind, delta),
+            value: clone_expr(value, bind, delta),
+            else_body: else_body
+                .as_ref()
+                .map(|b| b.iter().map(|s| clone_stmt(s, bind, delta)).collect()),
+            span: shift(*span, delta),
+        },
         Stmt::Assign {
             target,
+            op,
             value,
+            step,
             span,
         } => Stmt::Assign {
             target: clone_expr(target, bind, delta),
+            op: *op,
             value: clone_expr(value, bind, delta),
+            step: *step,
             span: shift(*span, delta),
         },
         Stmt::Return { value, span } => Stmt::Return {
             value: value.as_ref().map(|v| clone_expr(v, bind, delta)),
             span: shift(*span, delta),
         },
-        Stmt::Break { span } => Stmt::Break {
+        Stmt::Break { label, span } => Stmt::Break {
+            label: label.clone(),
             span: shift(*span, delta),
         },
-        Stmt::Continue { span } => Stmt::Continue {
+        Stmt::Continue { label, span } => Stmt::Continue {
+            label: label.clone(),
+            span: shift(*span, delta),
+        },
+        Stmt::Defer { body, span } => Stmt::Defer {
+            body: body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
+        Stmt::Arena { body, span } => Stmt::Arena {
+            body: body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
             span: shift(*span, delta),
         },
         Stmt::If {
@@ -256,18 +371,57 @@ fn clone_stmt(stmt: &Stmt, bind: &HashMap<String, Type>, delta: usize) -> Stmt {
                 .map(|b| b.iter().map(|s| clone_stmt(s, bind, delta)).collect()),
             span: shift(*span, delta),
         },
-        Stmt::While { cond, body, span } => Stmt::While {
+        Stmt::While {
+            label,
+            cond,
+            body,
+            span,
+        } => Stmt::While {
+            label: label.clone(),
             cond: clone_expr(cond, bind, delta),
             body: body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
             span: shift(*span, delta),
         },
+        Stmt::IfConst {
+            pattern,
+            value,
+            then_body,
+            else_body,
+            span,
+        } => Stmt::IfConst {
+            pattern: clone_pattern(pattern, bind, delta),
+            value: clone_expr(value, bind, delta),
+            then_body: then_body
+                .iter()
+                .map(|s| clone_stmt(s, bind, delta))
+                .collect(),
+            else_body: else_body
+                .as_ref()
+                .map(|b| b.iter().map(|s| clone_stmt(s, bind, delta)).collect()),
+            span: shift(*span, delta),
+        },
+        Stmt::WhileConst {
+            label,
+            pattern,
+            value,
+            body,
+            span,
+        } => Stmt::WhileConst {
+            label: label.clone(),
+            pattern: clone_pattern(pattern, bind, delta),
+            value: clone_expr(value, bind, delta),
+            body: body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
         Stmt::For {
+            label,
             index,
             name,
             iterable,
             body,
             span,
         } => Stmt::For {
+            label: label.clone(),
             index: index.clone(),
             name: name.clone(),
             iterable: clone_expr(iterable, bind, delta),
@@ -283,16 +437,9 @@ fn clone_stmt(stmt: &Stmt, bind: &HashMap<String, Type>, delta: usize) -> Stmt {
             scrutinee: clone_expr(scrutinee, bind, delta),
             arms: arms
                 .iter()
-                .map(|a| MatchArm {
-                    variant: a.variant.clone(),
-                    variant_span: shift(a.variant_span, delta),
-                    bindings: a
-                        .bindings
-                        .iter()
-                        .map(|(n, s)| (n.clone(), shift(*s, delta)))
-                        .collect(),
-                    body: a.body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
-                    span: shift(a.span, delta),
+                .map(|a| {
+                    let body = a.body.iter().map(|s| clone_stmt(s, bind, delta)).collect();
+                    clone_arm(a, body, bind, delta)
                 })
                 .collect(),
             else_body: else_body
@@ -304,6 +451,51 @@ fn clone_stmt(stmt: &Stmt, bind: &HashMap<String, Type>, delta: usize) -> Stmt {
     }
 }
 
+/// An arm head shifted into the instance, around an already-cloned
+/// body — shared by both match forms (ADR 0044).
+fn clone_arm<B, C>(
+    a: &MatchArm<B>,
+    body: C,
+    bind: &HashMap<String, Type>,
+    delta: usize,
+) -> MatchArm<C> {
+    MatchArm {
+        pattern: clone_pattern(&a.pattern, bind, delta),
+        guard: a.guard.as_ref().map(|g| clone_expr(g, bind, delta)),
+        body,
+        span: shift(a.span, delta),
+    }
+}
+
+fn clone_pattern(p: &Pattern, bind: &HashMap<String, Type>, delta: usize) -> Pattern {
+    match p {
+        Pattern::Bind(name, span) => Pattern::Bind(name.clone(), shift(*span, delta)),
+        Pattern::Lit(e) => Pattern::Lit(clone_expr(e, bind, delta)),
+        Pattern::Variant {
+            name,
+            name_span,
+            args,
+            span,
+        } => Pattern::Variant {
+            name: name.clone(),
+            name_span: shift(*name_span, delta),
+            args: args.iter().map(|a| clone_pattern(a, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
+        Pattern::Error {
+            name,
+            name_span,
+            args,
+            span,
+        } => Pattern::Error {
+            name: name.clone(),
+            name_span: shift(*name_span, delta),
+            args: args.iter().map(|a| clone_pattern(a, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
+    }
+}
+
 fn clone_expr(expr: &Expr, bind: &HashMap<String, Type>, delta: usize) -> Expr {
     let sub = |e: &Expr| Box::new(clone_expr(e, bind, delta));
     match expr {
@@ -312,8 +504,21 @@ fn clone_expr(expr: &Expr, bind: &HashMap<String, Type>, delta: usize) -> Expr {
         Expr::Bool(b, s) => Expr::Bool(*b, shift(*s, delta)),
         Expr::Str(t, s) => Expr::Str(t.clone(), shift(*s, delta)),
         Expr::Ident(n, s) => Expr::Ident(n.clone(), shift(*s, delta)),
+        Expr::FnRef {
+            name,
+            type_args,
+            span,
+        } => Expr::FnRef {
+            name: name.clone(),
+            type_args: type_args.iter().map(|t| substitute_ann(t, bind)).collect(),
+            span: shift(*span, delta),
+        },
         Expr::Null(s) => Expr::Null(shift(*s, delta)),
-        Expr::ErrorLit(n, s) => Expr::ErrorLit(n.clone(), shift(*s, delta)),
+        Expr::ErrorLit { name, args, span } => Expr::ErrorLit {
+            name: name.clone(),
+            args: args.iter().map(|a| clone_expr(a, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
         Expr::ErrorKind(s) => Expr::ErrorKind(shift(*s, delta)),
         Expr::Try { expr, span } => Expr::Try {
             expr: sub(expr),
@@ -390,6 +595,17 @@ fn clone_expr(expr: &Expr, bind: &HashMap<String, Type>, delta: usize) -> Expr {
             args: args.iter().map(|a| clone_expr(a, bind, delta)).collect(),
             span: shift(*span, delta),
         },
+        Expr::MethodCall {
+            receiver,
+            method,
+            args,
+            span,
+        } => Expr::MethodCall {
+            receiver: Box::new(clone_expr(receiver, bind, delta)),
+            method: method.clone(),
+            args: args.iter().map(|a| clone_expr(a, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
         Expr::ArrayLit { elements, span } => Expr::ArrayLit {
             elements: elements
                 .iter()
@@ -397,10 +613,75 @@ fn clone_expr(expr: &Expr, bind: &HashMap<String, Type>, delta: usize) -> Expr {
                 .collect(),
             span: shift(*span, delta),
         },
+        Expr::ArrayRepeat { value, count, span } => Expr::ArrayRepeat {
+            value: Box::new(clone_expr(value, bind, delta)),
+            count: *count,
+            span: shift(*span, delta),
+        },
+        Expr::Match {
+            scrutinee,
+            arms,
+            else_value,
+            span,
+        } => Expr::Match {
+            scrutinee: Box::new(clone_expr(scrutinee, bind, delta)),
+            arms: arms
+                .iter()
+                .map(|a| clone_arm(a, clone_expr(&a.body, bind, delta), bind, delta))
+                .collect(),
+            else_value: else_value
+                .as_ref()
+                .map(|e| Box::new(clone_expr(e, bind, delta))),
+            span: shift(*span, delta),
+        },
+        Expr::TupleLit { elements, span } => Expr::TupleLit {
+            elements: elements
+                .iter()
+                .map(|e| clone_expr(e, bind, delta))
+                .collect(),
+            span: shift(*span, delta),
+        },
+        Expr::MapLit { entries, span } => Expr::MapLit {
+            entries: entries
+                .iter()
+                .map(|(k, v)| (clone_expr(k, bind, delta), clone_expr(v, bind, delta)))
+                .collect(),
+            span: shift(*span, delta),
+        },
         Expr::Index { base, index, span } => Expr::Index {
             base: sub(base),
             index: sub(index),
             span: shift(*span, delta),
         },
+        Expr::Range {
+            start,
+            end,
+            inclusive,
+            step,
+            span,
+        } => Expr::Range {
+            start: start.as_deref().map(sub),
+            end: end.as_deref().map(sub),
+            inclusive: *inclusive,
+            step: step.as_deref().map(sub),
+            span: shift(*span, delta),
+        },
+        Expr::Lambda {
+            params,
+            return_type,
+            body,
+            span,
+        } => Expr::Lambda {
+            params: params
+                .iter()
+                .map(|p| Param {
+                    name: p.name.clone(),
+                    ty: substitute_ann(&p.ty, bind),
+                })
+                .collect(),
+            return_type: return_type.as_ref().map(|t| substitute_ann(t, bind)),
+            body: body.iter().map(|s| clone_stmt(s, bind, delta)).collect(),
+            span: shift(*span, delta),
+        },
     }
 }
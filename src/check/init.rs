@@ -0,0 +1,120 @@
+//! Module var initialization order (ADR 0048). Vars initialize before
+//! `main`: module by module in the graph's dependency order, and in
+//! declaration order within a module. An initializer may call anything,
+//! so the check is a reachability walk over every body it can run —
+//! its own expression, then each function it calls or names as a
+//! value, transitively. Reaching a var that is not set yet (a later
+//! one, or the var itself) is an error at the use. Naming a function
+//! counts as calling it: the walk cannot tell whether the value is
+//! invoked before `main`.
+
+use std::collections::{HashMap, HashSet};
+
+use super::Resolutions;
+use crate::ast::Item;
+use crate::diagnostic::Diagnostic;
+use crate::modules::ModuleGraph;
+use crate::span::Span;
+
+type Key = (usize, String);
+
+/// What one body reaches, in source order: functions it calls or names,
+/// and vars it reads or writes, each with the span of the use.
+#[derive(Default)]
+struct Uses {
+    fns: Vec<(Span, Key)>,
+    vars: Vec<(Span, Key)>,
+}
+
+pub(super) fn check_init_order(
+    graph: &ModuleGraph,
+    res: &Resolutions,
+    diags: &mut Vec<Diagnostic>,
+) {
+    // Every body that can run, keyed like its callers name it. A var's
+    // initializer takes the var's key — the value namespace is shared,
+    // so no function has it.
+    let mut bodies: Vec<(Span, Key)> = Vec::new();
+    for (mi, module) in graph.modules.iter().enumerate() {
+        for item in &module.ast {
+            for f in item.functions().iter().filter(|f| f.type_params.is_empty()) {
+                bodies.push((f.span, (mi, f.name.clone())));
+            }
+        }
+    }
+    for (key, f) in &res.instances {
+        bodies.push((f.span, key.clone()));
+    }
+    let mut order: Vec<Key> = Vec::new();
+    for &mi in &graph.init_order {
+        for item in &graph.modules[mi].ast {
+            if let Item::Global(g) = item {
+                bodies.push((g.value.span(), (mi, g.name.clone())));
+                order.push((mi, g.name.clone()));
+            }
+        }
+    }
+    let position: HashMap<&Key, usize> = order.iter().enumerate().map(|(i, k)| (k, i)).collect();
+
+    // Bodies never nest (function literals belong to their enclosing
+    // body), so each use sits in at most one: the last that starts at
+    // or before it.
+    bodies.sort_by_key(|(span, _)| span.start);
+    let owner = |at: Span| {
+        let i = bodies.partition_point(|(span, _)| span.start <= at.start);
+        let (span, key) = bodies.get(i.checked_sub(1)?)?;
+        (at.end <= span.end).then_some(key)
+    };
+    let mut uses: HashMap<&Key, Uses> = HashMap::new();
+    for (span, target) in res.call_targets.iter().chain(&res.fn_values) {
+        if let Some(key) = owner(*span) {
+            uses.entry(key)
+                .or_default()
+                .fns
+                .push((*span, target.clone()));
+        }
+    }
+    for (span, var) in &res.global_refs {
+        if let Some(key) = owner(*span) {
+            uses.entry(key).or_default().vars.push((*span, var.clone()));
+        }
+    }
+    for u in uses.values_mut() {
+        u.fns.sort_by_key(|(span, _)| span.start);
+        u.vars.sort_by_key(|(span, _)| span.start);
+    }
+
+    let mut reported = HashSet::new();
+    for (i, var) in order.iter().enumerate() {
+        let mut seen = HashSet::new();
+        let mut stack = vec![var];
+        while let Some(body) = stack.pop() {
+            if !seen.insert(body) {
+                continue;
+            }
+            let Some(u) = uses.get(body) else { continue };
+            let early = u.vars.iter().find(|(_, used)| position[used] >= i);
+            if let Some((span, used)) = early {
+                if reported.insert(*span) {
+                    let help = if used == var {
+                        format!("the initializer of '{}' reaches this use of itself", var.1)
+                    } else {
+                        format!(
+                            "the initializer of '{}' reaches this use, but '{}' is initialized after it",
+                            var.1, used.1
+                        )
+                    };
+                    diags.push(
+                        Diagnostic::error(
+                            format!("module var '{}' is used before it is initialized", used.1),
+                            *span,
+                        )
+                        .with_help(help),
+                    );
+                }
+                break;
+            }
+            stack.extend(u.fns.iter().rev().map(|(_, f)| f));
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::ast::{BinOp, Conv, Expr, Function, Item, Stmt, TypeAnn, UnOp};
+use crate::ast::{
+    BinOp, Conv, Expr, Function, ImplDecl, Item, MatchArm, MethodSig, Param, Stmt, TypeAnn, UnOp,
+};
 use crate::diagnostic::Diagnostic;
 use crate::modules::ModuleGraph;
 use crate::narrow::{Fact, NarrowFrame, body_effects, condition_facts, covers, diverges};
@@ -8,12 +10,24 @@ use crate::source::SourceMap;
 use crate::span::Span;
 use crate::syntax;
 use crate::types::{
-    EnumType, FnSig, StructType, Type, eq_comparable, fits, instance_name, is_numeric, poisoned,
-    pretty, unconstrained,
+    EnumType, FnSig, IntTy, StructType, Type, eq_comparable, fits, instance_name, is_integer,
+    is_numeric, poisoned, pretty, unconstrained,
 };
 
 use generics::{DEPTH_CAP, FnWork, Mono, bind_params, instantiate_fn, substitute_ann};
 
+/// Recursion cap for the `reaches_err_union` walk (ADR 0037) and the
+/// error-payload walk (ADR 0062) — the same order as the layout walk's
+/// fuel; only uninstantiable self-embedding value types can spend it.
+const EQ_FUEL: usize = 64;
+
+/// The most words a fixed array's value may span, elements times
+/// element words (ADR 0056). It lives in frame slots and every copy
+/// point takes one, so this keeps frames well inside the default
+/// stack; the parser's per-length cap alone lets `int[4096][512]`
+/// through.
+const MAX_FIXED_WORDS: usize = 4096;
+
 /// A per-module view: visible name → the (module, name) that defines it.
 pub type Alias = HashMap<String, (usize, String)>;
 
@@ -29,14 +43,23 @@ pub struct Resolutions {
     /// Every enum definition, same keying and instance story
     /// (ADR 0036).
     pub enums: HashMap<(usize, String), EnumType>,
-    /// The variant tag behind every enum construction and every match
-    /// arm, keyed by the construction's span / the arm's variant span
-    /// (ADR 0036) — engines never resolve a variant name.
+    /// The variant tag behind every enum construction and every variant
+    /// pattern, keyed by the construction's span / the pattern's name
+    /// span (ADR 0036/0045) — engines never resolve a variant name.
     pub variant_tags: HashMap<Span, u32>,
     /// Every resolved user-function call, keyed by the call's span —
     /// total, like the type table (ADR 0035). Both engines resolve
     /// calls through it; an absent span means a builtin.
     pub call_targets: HashMap<Span, (usize, String)>,
+    /// Every function named as a value — a plain name or an explicit
+    /// instance (`identity<int>`) — keyed by the expression's span
+    /// (ADR 0038). Indirect calls need no table: their callee's
+    /// recorded type is a `Type::Fn`.
+    pub fn_values: HashMap<Span, (usize, String)>,
+    /// Every function literal's captures — (name, declared type) in
+    /// first-use order — keyed by the literal's span (ADR 0039). The
+    /// literal's own signature is its recorded `Type::Fn`.
+    pub captures: HashMap<Span, Vec<(String, Type)>>,
     /// Monomorphized function bodies by instance key (ADR 0035):
     /// substituted, respanned clones the engines run/lower after the
     /// module ASTs. Templates themselves are never executable.
@@ -59,14 +82,43 @@ pub struct Resolutions {
     /// value, 1 = reserved, ADR 0034). Both engines render names
     /// through this table; codes are never observable.
     pub error_names: Vec<String>,
+    /// Each declared error's payload types, parallel to `error_names`
+    /// (ADR 0062) — empty for a bare code. The backend sizes `error`
+    /// by the widest.
+    pub error_payloads: Vec<Vec<Type>>,
     /// Each `error.Name` literal's interned code, keyed by its span —
-    /// the engines never resolve an error name themselves.
+    /// the engines never resolve an error name themselves. Error
+    /// patterns key by their `error.Name` span.
     pub error_lits: HashMap<Span, u32>,
+    /// Every read of a module const, keyed by the name's span, to the
+    /// (defining module, name) it reads (ADR 0047).
+    pub const_refs: HashMap<Span, (usize, String)>,
+    /// Every module const's value, computed at compile time by the
+    /// interpreter (ADR 0047). Both engines read consts from here.
+    pub consts: HashMap<(usize, String), ConstValue>,
+    /// Every read or write of a module var, keyed by the name's span,
+    /// to the (defining module, name) it names (ADR 0048).
+    pub global_refs: HashMap<Span, (usize, String)>,
+    /// Every module var's declared type — the backend sizes its storage
+    /// from it (ADR 0048).
+    pub globals: HashMap<(usize, String), Type>,
+}
+
+/// A module const's compile-time value (ADR 0047): what the
+/// initializer evaluated to, in a form the backend can put in rodata.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(Vec<u8>),
 }
 
 /// A resolved field access (see `Resolutions::field_slots`).
 pub struct FieldSlot {
-    pub base: (usize, String),
+    /// The value aggregate the access reads through: a struct, or a
+    /// tuple whose fields are its positions (ADR 0043).
+    pub base: Type,
     pub index: usize,
     pub ty: Type,
 }
@@ -81,17 +133,27 @@ struct OutTables {
     let_types: HashMap<Span, Type>,
     error_lits: HashMap<Span, u32>,
     call_targets: HashMap<Span, (usize, String)>,
+    fn_values: HashMap<Span, (usize, String)>,
+    captures: HashMap<Span, Vec<(String, Type)>>,
     variant_tags: HashMap<Span, u32>,
+    const_refs: HashMap<Span, (usize, String)>,
+    global_refs: HashMap<Span, (usize, String)>,
+    /// Arena uses judged once every body is checked (ADR 0061) — the
+    /// checker's own, never handed to the engines.
+    arena_uses: Vec<arena::ArenaUse>,
 }
 
 /// One module's declared names with their export flags. `structs` is
 /// the shared TYPE namespace — enums live in it too (ADR 0036), so a
 /// struct and an enum can't share a name and imports resolve through
-/// one bucket.
+/// one bucket. `consts` and `globals` share the value namespace with
+/// `fns` (ADR 0047/0048): a bare name reads exactly one of them.
 struct ModuleNames {
     fns: HashMap<String, bool>,
     structs: HashMap<String, bool>,
     errs: HashMap<String, bool>,
+    consts: HashMap<String, bool>,
+    globals: HashMap<String, bool>,
 }
 
 /// Static type checking over the whole module graph. Empty diagnostics =
@@ -113,6 +175,8 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
     let mut fn_aliases: Vec<Alias> = Vec::new();
     let mut ty_aliases: Vec<Alias> = Vec::new();
     let mut err_aliases: Vec<Alias> = Vec::new();
+    let mut const_aliases: Vec<Alias> = Vec::new();
+    let mut global_aliases: Vec<Alias> = Vec::new();
     for (mi, module) in graph.modules.iter().enumerate() {
         let mut fn_alias: Alias = names[mi]
             .fns
@@ -129,18 +193,37 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
             .keys()
             .map(|n| (n.clone(), (mi, n.clone())))
             .collect();
+        let mut const_alias: Alias = names[mi]
+            .consts
+            .keys()
+            .map(|n| (n.clone(), (mi, n.clone())))
+            .collect();
+        let mut global_alias: Alias = names[mi]
+            .globals
+            .keys()
+            .map(|n| (n.clone(), (mi, n.clone())))
+            .collect();
         for binding in &module.imports {
             let target = &names[binding.target];
             let target_path = &graph.modules[binding.target].path;
             let fn_export = target.fns.get(&binding.name).copied();
             let ty_export = target.structs.get(&binding.name).copied();
             let err_export = target.errs.get(&binding.name).copied();
-            if fn_export.is_none() && ty_export.is_none() && err_export.is_none() {
+            let const_export = target.consts.get(&binding.name).copied();
+            let global_export = target.globals.get(&binding.name).copied();
+            if fn_export.is_none()
+                && ty_export.is_none()
+                && err_export.is_none()
+                && const_export.is_none()
+                && global_export.is_none()
+            {
                 let exported_names = target
                     .fns
                     .iter()
                     .chain(target.structs.iter())
                     .chain(target.errs.iter())
+                    .chain(target.consts.iter())
+                    .chain(target.globals.iter())
                     .filter(|&(_, &exported)| exported)
                     .map(|(n, _)| n.as_str());
                 diags.push(
@@ -152,7 +235,12 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
                 );
                 continue;
             }
-            if fn_export != Some(true) && ty_export != Some(true) && err_export != Some(true) {
+            if fn_export != Some(true)
+                && ty_export != Some(true)
+                && err_export != Some(true)
+                && const_export != Some(true)
+                && global_export != Some(true)
+            {
                 diags.push(
                     Diagnostic::error(
                         format!(
@@ -171,6 +259,8 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
             if fn_alias.contains_key(&binding.name)
                 || ty_alias.contains_key(&binding.name)
                 || err_alias.contains_key(&binding.name)
+                || const_alias.contains_key(&binding.name)
+                || global_alias.contains_key(&binding.name)
             {
                 diags.push(Diagnostic::error(
                     format!("'{}' is already defined in this file", binding.name),
@@ -187,10 +277,18 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
             if err_export == Some(true) {
                 err_alias.insert(binding.name.clone(), (binding.target, binding.name.clone()));
             }
+            if const_export == Some(true) {
+                const_alias.insert(binding.name.clone(), (binding.target, binding.name.clone()));
+            }
+            if global_export == Some(true) {
+                global_alias.insert(binding.name.clone(), (binding.target, binding.name.clone()));
+            }
         }
         fn_aliases.push(fn_alias);
         ty_aliases.push(ty_alias);
         err_aliases.push(err_alias);
+        const_aliases.push(const_alias);
+        global_aliases.push(global_alias);
     }
 
     // Pass C: resolve signatures and struct layouts through the alias
@@ -203,6 +301,10 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
     let mut mono = Mono::new();
     let mut error_codes: HashMap<(usize, String), u32> = HashMap::new();
     let mut error_names: Vec<String> = Vec::new();
+    let mut error_payloads: Vec<Vec<Type>> = Vec::new();
+    let mut error_spans: Vec<Span> = Vec::new();
+    let mut const_types: HashMap<(usize, String), Type> = HashMap::new();
+    let mut global_types: HashMap<(usize, String), Type> = HashMap::new();
     // Templates first: a monomorphic signature may apply a generic
     // struct or enum declared anywhere in the graph. Monomorphic enums
     // leave a shell so `Named` resolution can classify enum-vs-struct
@@ -232,12 +334,29 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
                     }
                     mono.fn_templates.insert((mi, f.name.clone()), f);
                 }
+                Item::Interface(i) => {
+                    // A duplicate was diagnosed; the first one stands.
+                    mono.interfaces.entry((mi, i.name.clone())).or_insert(i);
+                }
+                // A generic impl's methods are templates over its
+                // parameters plus their own (ADR 0040).
+                Item::Impl(i) => {
+                    check_type_params(&i.type_params, &ty_aliases[mi], &mut diags);
+                    for f in i.methods.iter().filter(|f| !f.type_params.is_empty()) {
+                        let own = &f.type_params[i.type_params.len()..];
+                        check_type_params(own, &ty_aliases[mi], &mut diags);
+                        mono.fn_templates.insert((mi, f.name.clone()), f);
+                    }
+                }
                 _ => {}
             }
         }
     }
     for (mi, module) in graph.modules.iter().enumerate() {
         for item in &module.ast {
+            for f in item.functions() {
+                check_bounds(f, &ty_aliases[mi], &mono, &mut diags);
+            }
             match item {
                 Item::Struct(s) if s.type_params.is_empty() => {
                     let mut cx = TypeCx {
@@ -294,16 +413,95 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
                     let sig = instance_signature(f, &HashMap::new(), &mut cx, f.span);
                     sigs.insert((mi, f.name.clone()), sig);
                 }
+                Item::Impl(i) => {
+                    // A rejected impl adds no methods — its `self` type
+                    // would only repeat the diagnostic.
+                    if !check_impl(i, &graph.modules[mi].ast, &mut diags) {
+                        continue;
+                    }
+                    for f in i.methods.iter().filter(|f| f.type_params.is_empty()) {
+                        let mut cx = TypeCx {
+                            module: mi,
+                            ty_aliases: &ty_aliases,
+                            mono: &mut mono,
+                            diags: &mut diags,
+                        };
+                        let sig = instance_signature(f, &HashMap::new(), &mut cx, f.span);
+                        sigs.insert((mi, f.name.clone()), sig);
+                    }
+                }
+                // Requirements resolve per satisfying type; checking
+                // them once with `Self` poisoned reports the rest.
+                Item::Interface(i) => {
+                    let mut cx = TypeCx {
+                        module: mi,
+                        ty_aliases: &ty_aliases,
+                        mono: &mut mono,
+                        diags: &mut diags,
+                    };
+                    for m in &i.methods {
+                        required_signature(m, &Type::Error, &mut cx);
+                    }
+                }
+                // A module const holds a value the backend can put in
+                // rodata (ADR 0047); anything else poisons its readers.
+                Item::Const(c) => {
+                    let mut cx = TypeCx {
+                        module: mi,
+                        ty_aliases: &ty_aliases,
+                        mono: &mut mono,
+                        diags: &mut diags,
+                    };
+                    let mut ty = resolve_type(&c.ty, &mut cx, c.span);
+                    if !matches!(ty, Type::Int | Type::Float | Type::Bool | Type::Str)
+                        && !poisoned(&ty)
+                    {
+                        diags.push(Diagnostic::error(
+                            format!(
+                                "module const '{}' must be int, float, bool, or string, found {}",
+                                c.name,
+                                ty.name()
+                            ),
+                            c.span,
+                        ));
+                        ty = Type::Error;
+                    }
+                    const_types.entry((mi, c.name.clone())).or_insert(ty);
+                }
+                // A module var may hold any value (ADR 0048).
+                Item::Global(g) => {
+                    let mut cx = TypeCx {
+                        module: mi,
+                        ty_aliases: &ty_aliases,
+                        mono: &mut mono,
+                        diags: &mut diags,
+                    };
+                    let ty = resolve_type(&g.ty, &mut cx, g.span);
+                    global_types.entry((mi, g.name.clone())).or_insert(ty);
+                }
                 Item::Struct(_) | Item::Function(_) | Item::Enum(_) | Item::Import(_) => {}
                 Item::Error(e) => {
-                    for (name, _) in &e.names {
+                    for code in &e.codes {
                         // Duplicates were diagnosed in collect_names; the
                         // entry guard keeps their codes stable anyway.
                         if let std::collections::hash_map::Entry::Vacant(slot) =
-                            error_codes.entry((mi, name.clone()))
+                            error_codes.entry((mi, code.name.clone()))
                         {
                             slot.insert((error_names.len() + 2) as u32);
-                            error_names.push(name.clone());
+                            error_names.push(code.name.clone());
+                            let mut cx = TypeCx {
+                                module: mi,
+                                ty_aliases: &ty_aliases,
+                                mono: &mut mono,
+                                diags: &mut diags,
+                            };
+                            let payloads = code
+                                .payloads
+                                .iter()
+                                .map(|ann| resolve_type(ann, &mut cx, code.span))
+                                .collect();
+                            error_payloads.push(payloads);
+                            error_spans.push(code.span);
                         }
                     }
                 }
@@ -311,15 +509,88 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
         }
     }
 
+    // `error` is as wide as its widest payload (ADR 0062), so no
+    // payload may hold an error by value: it would hold itself.
+    for (i, payloads) in error_payloads.iter().enumerate() {
+        for pt in payloads {
+            if holds_error(pt, &mono, EQ_FUEL) {
+                diags.push(Diagnostic::error(
+                    format!(
+                        "error '{}' cannot carry {}: a payload may not hold an error",
+                        error_names[i],
+                        pt.name()
+                    ),
+                    error_spans[i],
+                ));
+            }
+        }
+    }
+
+    // A fixed array of a struct declared later was sized while that
+    // struct had no layout; now that every one has, declarations are
+    // sized again (ADR 0056).
+    for (mi, module) in graph.modules.iter().enumerate() {
+        for item in &module.ast {
+            for f in item.functions() {
+                let Some(sig) = sigs.get(&(mi, f.name.clone())) else {
+                    continue;
+                };
+                if let Some(diag) = sig
+                    .params
+                    .iter()
+                    .chain([&sig.ret])
+                    .find_map(|t| oversized_fixed(t, &mono, f.span, EQ_FUEL))
+                {
+                    diags.push(diag);
+                }
+            }
+            let (types, span): (Vec<&Type>, Span) = match item {
+                Item::Struct(s) if s.type_params.is_empty() => (
+                    mono.structs[&(mi, s.name.clone())]
+                        .fields
+                        .iter()
+                        .map(|(_, ft)| ft)
+                        .collect(),
+                    s.span,
+                ),
+                Item::Enum(e) if e.type_params.is_empty() => (
+                    mono.enums[&(mi, e.name.clone())]
+                        .variants
+                        .iter()
+                        .flat_map(|(_, payloads)| payloads)
+                        .collect(),
+                    e.span,
+                ),
+                Item::Global(g) => (
+                    global_types
+                        .get(&(mi, g.name.clone()))
+                        .into_iter()
+                        .collect(),
+                    g.span,
+                ),
+                _ => continue,
+            };
+            if let Some(diag) = types
+                .into_iter()
+                .find_map(|t| oversized_fixed(t, &mono, span, EQ_FUEL))
+            {
+                diags.push(diag);
+            }
+        }
+    }
+
     // Pass D: check every monomorphic function body against its
     // module's view. Generic bodies are checked per instance (pass E).
     let paths: Vec<&str> = graph.modules.iter().map(|m| m.path.as_str()).collect();
     let mut out = OutTables::default();
     for (mi, module) in graph.modules.iter().enumerate() {
         for item in &module.ast {
-            if let Item::Function(f) = item
-                && f.type_params.is_empty()
-            {
+            // A rejected impl registered no signatures; its bodies are
+            // skipped rather than checked against a missing `self`.
+            let checkable = |f: &&Function| {
+                f.type_params.is_empty() && sigs.contains_key(&(mi, f.name.clone()))
+            };
+            for f in item.functions().iter().filter(checkable) {
                 let mut checker = Checker {
                     module: mi,
                     paths: &paths,
@@ -327,20 +598,66 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
                     ty_alias: &ty_aliases[mi],
                     ty_aliases: &ty_aliases,
                     err_alias: &err_aliases[mi],
+                    const_alias: &const_aliases[mi],
+                    global_alias: &global_aliases[mi],
                     error_codes: &error_codes,
+                    error_payloads: &error_payloads,
                     sigs: &sigs,
+                    const_types: &const_types,
+                    global_types: &global_types,
                     mono: &mut mono,
                     diagnostics: &mut diags,
                     scopes: Vec::new(),
                     nonnull: Vec::new(),
-                    loop_depth: 0,
+                    closures: Vec::new(),
+                    loops: Vec::new(),
+                    deferring: false,
+                    arenas: Vec::new(),
                     ret: Type::Unit,
                     try_ok: false,
+                    code_ok: false,
                     inst_depth: 0,
                     out: &mut out,
                 };
                 checker.check_function(f);
             }
+            let init = match item {
+                Item::Const(c) => Some((&c.name, &c.value, c.span, &const_types)),
+                Item::Global(g) => Some((&g.name, &g.value, g.span, &global_types)),
+                _ => None,
+            };
+            if let Some((name, value, span, types)) = init {
+                let mut checker = Checker {
+                    module: mi,
+                    paths: &paths,
+                    fn_alias: &fn_aliases[mi],
+                    ty_alias: &ty_aliases[mi],
+                    ty_aliases: &ty_aliases,
+                    err_alias: &err_aliases[mi],
+                    const_alias: &const_aliases[mi],
+                    global_alias: &global_aliases[mi],
+                    error_codes: &error_codes,
+                    error_payloads: &error_payloads,
+                    sigs: &sigs,
+                    const_types: &const_types,
+                    global_types: &global_types,
+                    mono: &mut mono,
+                    diagnostics: &mut diags,
+                    scopes: Vec::new(),
+                    nonnull: Vec::new(),
+                    closures: Vec::new(),
+                    loops: Vec::new(),
+                    deferring: false,
+                    arenas: Vec::new(),
+                    ret: Type::Unit,
+                    try_ok: false,
+                    code_ok: false,
+                    inst_depth: 0,
+                    out: &mut out,
+                };
+                let declared = types[&(mi, name.clone())].clone();
+                checker.check_initializer(name, value, &declared, span);
+            }
         }
     }
 
@@ -392,15 +709,24 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
             ty_alias: &ty_aliases[mi],
             ty_aliases: &ty_aliases,
             err_alias: &err_aliases[mi],
+            const_alias: &const_aliases[mi],
+            global_alias: &global_aliases[mi],
             error_codes: &error_codes,
+            error_payloads: &error_payloads,
             sigs: &sigs,
+            const_types: &const_types,
+            global_types: &global_types,
             mono: &mut mono,
             diagnostics: &mut diags,
             scopes: Vec::new(),
             nonnull: Vec::new(),
-            loop_depth: 0,
+            closures: Vec::new(),
+            loops: Vec::new(),
+            deferring: false,
+            arenas: Vec::new(),
             ret: Type::Unit,
             try_ok: false,
+            code_ok: false,
             inst_depth: depth,
             out: &mut out,
         };
@@ -436,22 +762,47 @@ pub fn check(graph: &ModuleGraph, map: &mut SourceMap) -> (Resolutions, Vec<Diag
         }
     }
 
-    (
-        Resolutions {
-            structs: mono.structs,
-            enums: mono.enums,
-            variant_tags: out.variant_tags,
-            call_targets: out.call_targets,
-            instances,
-            field_slots: out.field_slots,
-            sigs,
-            expr_types: out.expr_types,
-            let_types: out.let_types,
-            error_names,
-            error_lits: out.error_lits,
-        },
-        diags,
-    )
+    let mut res = Resolutions {
+        structs: mono.structs,
+        enums: mono.enums,
+        variant_tags: out.variant_tags,
+        call_targets: out.call_targets,
+        fn_values: out.fn_values,
+        captures: out.captures,
+        instances,
+        field_slots: out.field_slots,
+        sigs,
+        expr_types: out.expr_types,
+        let_types: out.let_types,
+        error_names,
+        error_payloads,
+        error_lits: out.error_lits,
+        const_refs: out.const_refs,
+        consts: HashMap::new(),
+        global_refs: out.global_refs,
+        globals: global_types,
+    };
+    // Arena uses a body alone cannot judge: what calls and function
+    // literals store, and which errors a `try` can raise (ADR 0061).
+    if diags.is_empty() && !out.arena_uses.is_empty() {
+        escape::check_arena_uses(graph, &res, &out.arena_uses, &mut diags);
+    }
+    // Pass F: module vars initialize before `main`, in dependency order
+    // (ADR 0048) — no initializer may reach a var that isn't set yet.
+    if diags.is_empty() && !res.globals.is_empty() {
+        init::check_init_order(graph, &res, &mut diags);
+    }
+    // Pass G: comptime (ADR 0047). A well-typed program's consts run
+    // through the interpreter once, here — so a failing initializer
+    // is a compile error in both engines, and neither engine evaluates
+    // a const initializer at run time.
+    if diags.is_empty() && !const_types.is_empty() {
+        match crate::interpreter::eval_consts(graph, &res) {
+            Ok(values) => res.consts = values,
+            Err(d) => diags.push(d),
+        }
+    }
+    (res, diags)
 }
 
 /// A signature with `bind` substituted into the annotations, resolved
@@ -475,6 +826,48 @@ fn instance_signature(
     FnSig { params, ret }
 }
 
+/// An interface requirement's signature with `Self` bound to `self_ty`
+/// (ADR 0041), resolved in the interface's module — `self` included.
+fn required_signature(m: &MethodSig, self_ty: &Type, cx: &mut TypeCx) -> FnSig {
+    let bind = HashMap::from([(syntax::SELF_TYPE.to_string(), self_ty.clone())]);
+    let params = m
+        .params
+        .iter()
+        .map(|p| resolve_type(&substitute_ann(&p.ty, &bind), cx, m.span))
+        .collect();
+    let ret = match &m.return_type {
+        Some(t) => resolve_type(&substitute_ann(t, &bind), cx, m.span),
+        None => Type::Unit,
+    };
+    FnSig { params, ret }
+}
+
+/// Every `T: I` bound names an interface visible in the function's
+/// module (ADR 0041). Satisfaction is checked per instantiation.
+fn check_bounds(f: &Function, ty_alias: &Alias, mono: &Mono, diags: &mut Vec<Diagnostic>) {
+    for b in &f.bounds {
+        match ty_alias.get(&b.interface) {
+            Some(key) if mono.interfaces.contains_key(key) => {}
+            Some(_) => diags.push(Diagnostic::error(
+                format!(
+                    "'{}' is not an interface — bounds name interfaces",
+                    b.interface
+                ),
+                b.span,
+            )),
+            None => diags.push(
+                Diagnostic::error(format!("unknown interface '{}'", b.interface), b.span).suggest(
+                    &b.interface,
+                    ty_alias
+                        .iter()
+                        .filter(|(_, k)| mono.interfaces.contains_key(*k))
+                        .map(|(n, _)| n.as_str()),
+                ),
+            ),
+        }
+    }
+}
+
 /// Duplicate and shadowing rules for `<T, U>` lists (ADR 0035): names
 /// must be distinct and must not shadow a visible type.
 fn check_type_params(params: &[(String, Span)], ty_alias: &Alias, diags: &mut Vec<Diagnostic>) {
@@ -494,22 +887,75 @@ fn check_type_params(params: &[(String, Span)], ty_alias: &Alias, diags: &mut Ve
     }
 }
 
+/// An impl block's shape (ADR 0040): its type is a struct, refstruct,
+/// or enum declared in the same file — methods resolve through the
+/// type's own module — with exactly the declaration's type parameters.
+fn check_impl(i: &ImplDecl, ast: &[Item], diags: &mut Vec<Diagnostic>) -> bool {
+    let declared = ast.iter().find_map(|item| match item {
+        Item::Struct(s) if s.name == i.name => Some(s.type_params.len()),
+        Item::Enum(e) if e.name == i.name => Some(e.type_params.len()),
+        _ => None,
+    });
+    let message = match declared {
+        None => format!(
+            "cannot implement '{}' — methods attach to a struct or enum declared in this file",
+            i.name
+        ),
+        Some(n) if n != i.type_params.len() => format!(
+            "'{}' has {n} type parameter(s), but the impl names {}",
+            i.name,
+            i.type_params.len()
+        ),
+        Some(_) => return true,
+    };
+    diags.push(Diagnostic::error(message, i.span));
+    false
+}
+
 fn collect_names(ast: &[Item], diags: &mut Vec<Diagnostic>) -> ModuleNames {
     let mut names = ModuleNames {
         fns: HashMap::new(),
         structs: HashMap::new(),
         errs: HashMap::new(),
+        consts: HashMap::new(),
+        globals: HashMap::new(),
     };
+    let mut methods = HashSet::new();
     for item in ast {
         match item {
             Item::Function(f) => {
-                if names.fns.insert(f.name.clone(), f.exported).is_some() {
+                if names.fns.insert(f.name.clone(), f.exported).is_some()
+                    || names.consts.contains_key(&f.name)
+                    || names.globals.contains_key(&f.name)
+                {
                     diags.push(Diagnostic::error(
                         format!("function '{}' is already defined", f.name),
                         f.span,
                     ));
                 }
             }
+            Item::Const(c) => {
+                if names.consts.insert(c.name.clone(), c.exported).is_some()
+                    || names.fns.contains_key(&c.name)
+                    || names.globals.contains_key(&c.name)
+                {
+                    diags.push(Diagnostic::error(
+                        format!("const '{}' is already defined", c.name),
+                        c.span,
+                    ));
+                }
+            }
+            Item::Global(g) => {
+                if names.globals.insert(g.name.clone(), g.exported).is_some()
+                    || names.fns.contains_key(&g.name)
+                    || names.consts.contains_key(&g.name)
+                {
+                    diags.push(Diagnostic::error(
+                        format!("var '{}' is already defined", g.name),
+                        g.span,
+                    ));
+                }
+            }
             Item::Struct(s) => {
                 if names.structs.insert(s.name.clone(), s.exported).is_some() {
                     diags.push(Diagnostic::error(
@@ -526,13 +972,44 @@ fn collect_names(ast: &[Item], diags: &mut Vec<Diagnostic>) -> ModuleNames {
                     ));
                 }
             }
+            // Interfaces share the type namespace (ADR 0041): they
+            // import like types and can't be declared twice.
+            Item::Interface(i) => {
+                if names.structs.insert(i.name.clone(), i.exported).is_some() {
+                    diags.push(Diagnostic::error(
+                        format!("interface '{}' is already defined", i.name),
+                        i.span,
+                    ));
+                }
+                for (k, m) in i.methods.iter().enumerate() {
+                    if i.methods[..k].iter().any(|o| o.name == m.name) {
+                        diags.push(Diagnostic::error(
+                            format!("'{}' already requires method '{}'", i.name, m.name),
+                            m.span,
+                        ));
+                    }
+                }
+            }
             Item::Import(_) => {}
+            // Methods live in their type's namespace (ADR 0040), one
+            // name per type across all of its impl blocks.
+            Item::Impl(i) => {
+                for f in &i.methods {
+                    if !methods.insert(f.name.as_str()) {
+                        let method = &f.name[i.name.len() + 1..];
+                        diags.push(Diagnostic::error(
+                            format!("method '{method}' is already defined on '{}'", i.name),
+                            f.span,
+                        ));
+                    }
+                }
+            }
             Item::Error(e) => {
-                for (name, span) in &e.names {
-                    if names.errs.insert(name.clone(), e.exported).is_some() {
+                for code in &e.codes {
+                    if names.errs.insert(code.name.clone(), e.exported).is_some() {
                         diags.push(Diagnostic::error(
-                            format!("error '{name}' is already declared"),
-                            *span,
+                            format!("error '{}' is already declared", code.name),
+                            code.span,
                         ));
                     }
                 }
@@ -559,6 +1036,7 @@ fn resolve_type(ann: &TypeAnn, cx: &mut TypeCx, span: Span) -> Type {
         TypeAnn::Bool => Type::Bool,
         TypeAnn::Str => Type::Str,
         TypeAnn::File => Type::File,
+        TypeAnn::Sized(t) => Type::Sized(*t),
         TypeAnn::ErrCode => Type::ErrCode,
         TypeAnn::ErrUnion(inner) => {
             let inner = resolve_type(inner, cx, span);
@@ -573,8 +1051,46 @@ fn resolve_type(ann: &TypeAnn, cx: &mut TypeCx, span: Span) -> Type {
         }
         TypeAnn::Optional(inner) => Type::Optional(Box::new(resolve_type(inner, cx, span))),
         TypeAnn::Array(inner) => Type::Array(Box::new(resolve_type(inner, cx, span))),
+        TypeAnn::Fixed(inner, n) => {
+            let ty = Type::Fixed(Box::new(resolve_type(inner, cx, span)), *n);
+            if let Some(diag) = fixed_size_error(&ty, cx.mono, span) {
+                cx.diags.push(diag);
+                return Type::Error;
+            }
+            ty
+        }
+        TypeAnn::Fn(params, ret) => Type::Fn(
+            params.iter().map(|p| resolve_type(p, cx, span)).collect(),
+            Box::new(match ret {
+                Some(r) => resolve_type(r, cx, span),
+                None => Type::Unit,
+            }),
+        ),
+        TypeAnn::Tuple(elems) => {
+            Type::Tuple(elems.iter().map(|e| resolve_type(e, cx, span)).collect())
+        }
         // The monomorphizer's substitution carrier (ADR 0035).
         TypeAnn::Resolved(t) => t.clone(),
+        // `map<K, V>` (ADR 0042) — the builtin resolves last, so a
+        // user type named `map` shadows it.
+        TypeAnn::Applied(name, args)
+            if name == syntax::TYPE_MAP && !cx.ty_aliases[cx.module].contains_key(name) =>
+        {
+            let [k, v] = args.as_slice() else {
+                cx.diags.push(Diagnostic::error(
+                    format!("'map' takes 2 type arguments, found {}", args.len()),
+                    span,
+                ));
+                return Type::Error;
+            };
+            let k = resolve_type(k, cx, span);
+            let v = resolve_type(v, cx, span);
+            if let Some(diag) = map_key_error(&k, span) {
+                cx.diags.push(diag);
+                return Type::Error;
+            }
+            Type::Map(Box::new(k), Box::new(v))
+        }
         // `Pair<int, string>` — instantiate the template (ADR
         // 0035/0036); struct and enum templates share the dispatch.
         TypeAnn::Applied(name, args) => {
@@ -599,6 +1115,14 @@ fn resolve_type(ann: &TypeAnn, cx: &mut TypeCx, span: Span) -> Type {
                 ));
                 Type::Error
             }
+            Some(key) if cx.mono.interfaces.contains_key(key) => {
+                cx.diags.push(
+                    Diagnostic::error(format!("interface '{name}' is not a type"), span).with_help(
+                        format!("take a bounded type parameter instead: '<T: {name}>'"),
+                    ),
+                );
+                Type::Error
+            }
             Some(key) if cx.mono.enums.contains_key(key) => Type::Enum(key.0, key.1.clone()),
             Some((m, n)) => Type::Struct(*m, n.clone()),
             None => unknown_type(name, cx, span),
@@ -606,6 +1130,139 @@ fn resolve_type(ann: &TypeAnn, cx: &mut TypeCx, span: Span) -> Type {
     }
 }
 
+/// Map keys hash and compare with `==` (ADR 0042), so a key type must
+/// be comparable with itself — error unions are not (ADR 0034); the
+/// checker catches unions nested in value types at each key use.
+fn map_key_error(k: &Type, span: Span) -> Option<Diagnostic> {
+    if poisoned(k) || eq_comparable(k, k) {
+        return None;
+    }
+    Some(Diagnostic::error(
+        format!(
+            "{} cannot be a map key — it contains an error union",
+            k.name()
+        ),
+        span,
+    ))
+}
+
+/// Whether `t` holds an `error` by value — through optionals, fixed
+/// arrays, tuples, value structs, and enum payloads. Handles (arrays,
+/// maps, refstructs) cut the walk: they are one word whatever they
+/// point at.
+fn holds_error(t: &Type, mono: &Mono, fuel: usize) -> bool {
+    let Some(next) = fuel.checked_sub(1) else {
+        return false;
+    };
+    match t {
+        Type::ErrCode | Type::ErrUnion(_) => true,
+        Type::Optional(inner) | Type::Fixed(inner, _) => holds_error(inner, mono, next),
+        Type::Struct(m, n) => mono.structs.get(&(*m, n.clone())).is_some_and(|def| {
+            !def.by_ref && def.fields.iter().any(|(_, ft)| holds_error(ft, mono, next))
+        }),
+        Type::Enum(m, n) => mono.enums.get(&(*m, n.clone())).is_some_and(|def| {
+            def.variants
+                .iter()
+                .flat_map(|(_, payloads)| payloads)
+                .any(|pt| holds_error(pt, mono, next))
+        }),
+        Type::Tuple(elems) => elems.iter().any(|et| holds_error(et, mono, next)),
+        _ => false,
+    }
+}
+
+/// About how many words a value of `t` spans inline: the backend's
+/// layout without error payload widening (ADR 0062). Handles are one
+/// word whatever they point at, and so is a struct not laid out yet.
+fn inline_words(t: &Type, mono: &Mono, fuel: usize) -> usize {
+    let Some(next) = fuel.checked_sub(1) else {
+        return 1;
+    };
+    match t {
+        Type::Str => 2,
+        Type::Optional(inner) if is_handle(inner, mono) => 1,
+        Type::Optional(inner) | Type::ErrUnion(inner) => 1 + inline_words(inner, mono, next),
+        Type::Fixed(inner, n) => n.saturating_mul(inline_words(inner, mono, next)),
+        Type::Tuple(elems) => elems.iter().map(|et| inline_words(et, mono, next)).sum(),
+        Type::Struct(m, n) => match mono.structs.get(&(*m, n.clone())) {
+            Some(def) if !def.by_ref => def
+                .fields
+                .iter()
+                .map(|(_, ft)| inline_words(ft, mono, next))
+                .sum(),
+            _ => 1,
+        },
+        Type::Enum(m, n) => mono.enums.get(&(*m, n.clone())).map_or(1, |def| {
+            let widest = def.variants.iter().map(|(_, payloads)| {
+                payloads
+                    .iter()
+                    .map(|pt| inline_words(pt, mono, next))
+                    .sum::<usize>()
+            });
+            1 + widest.max().unwrap_or(0)
+        }),
+        _ => 1,
+    }
+}
+
+/// A handle: one word where 0 means `null` (ADR 0009).
+fn is_handle(t: &Type, mono: &Mono) -> bool {
+    match t {
+        Type::Array(_) | Type::Map(..) | Type::File | Type::Fn(..) => true,
+        Type::Struct(m, n) => mono
+            .structs
+            .get(&(*m, n.clone()))
+            .is_some_and(|def| def.by_ref),
+        _ => false,
+    }
+}
+
+/// The diagnostic for a fixed array spanning more than
+/// `MAX_FIXED_WORDS`, if `t` is one.
+fn fixed_size_error(t: &Type, mono: &Mono, span: Span) -> Option<Diagnostic> {
+    if !matches!(t, Type::Fixed(..)) {
+        return None;
+    }
+    let words = inline_words(t, mono, EQ_FUEL);
+    (words > MAX_FIXED_WORDS).then(|| {
+        Diagnostic::error(
+            format!(
+                "{} is too large for a fixed array: {words} words, the limit is {MAX_FIXED_WORDS}",
+                t.name()
+            ),
+            span,
+        )
+        .with_help("keep large data in a growable array ('T[]')".to_string())
+    })
+}
+
+/// The first fixed array inside `t` that spans more than
+/// `MAX_FIXED_WORDS`. Structs and enums are not entered: their own
+/// declarations are checked.
+fn oversized_fixed(t: &Type, mono: &Mono, span: Span, fuel: usize) -> Option<Diagnostic> {
+    let next = fuel.checked_sub(1)?;
+    if let Some(diag) = fixed_size_error(t, mono, span) {
+        return Some(diag);
+    }
+    match t {
+        Type::Optional(inner)
+        | Type::Array(inner)
+        | Type::Fixed(inner, _)
+        | Type::ErrUnion(inner) => oversized_fixed(inner, mono, span, next),
+        Type::Map(k, v) => {
+            oversized_fixed(k, mono, span, next).or_else(|| oversized_fixed(v, mono, span, next))
+        }
+        Type::Tuple(elems) => elems
+            .iter()
+            .find_map(|et| oversized_fixed(et, mono, span, next)),
+        Type::Fn(params, ret) => params
+            .iter()
+            .chain([&**ret])
+            .find_map(|pt| oversized_fixed(pt, mono, span, next)),
+        _ => None,
+    }
+}
+
 fn unknown_type(name: &str, cx: &mut TypeCx, span: Span) -> Type {
     cx.diags.push(
         Diagnostic::error(format!("unknown type '{name}'"), span)
@@ -763,6 +1420,14 @@ struct VarInfo {
     mutable: bool,
 }
 
+/// A function literal whose body is being checked (ADR 0039): scopes
+/// below `base` belong to the enclosing bodies, so a name resolved
+/// there is a capture.
+struct ClosureFrame {
+    base: usize,
+    captures: Vec<(String, Type)>,
+}
+
 struct Checker<'a, 'g> {
     module: usize,
     paths: &'a [&'a str],
@@ -772,8 +1437,18 @@ struct Checker<'a, 'g> {
     /// generic structs in their defining module (ADR 0035).
     ty_aliases: &'a [Alias],
     err_alias: &'a Alias,
+    /// Module consts visible here, and every const's declared type
+    /// (ADR 0047).
+    const_alias: &'a Alias,
+    /// Module vars visible here, and every var's declared type
+    /// (ADR 0048).
+    global_alias: &'a Alias,
     error_codes: &'a HashMap<(usize, String), u32>,
+    /// Payload types by code − 2 (see `Resolutions::error_payloads`).
+    error_payloads: &'a [Vec<Type>],
     sigs: &'a HashMap<(usize, String), FnSig>,
+    const_types: &'a HashMap<(usize, String), Type>,
+    global_types: &'a HashMap<(usize, String), Type>,
     /// Generic templates, instantiation state, and the struct-layout
     /// table (grow-only, ADR 0035).
     mono: &'a mut Mono<'g>,
@@ -785,15 +1460,29 @@ struct Checker<'a, 'g> {
     /// it while the shadow's frame lives; field paths are also dropped on
     /// any call or field write, since aliases can reach them.
     nonnull: Vec<NarrowFrame>,
-    /// How many loops enclose the statement being checked — `break`/
-    /// `continue` are rejected at depth 0 (ADR 0019).
-    loop_depth: usize,
+    /// Enclosing function literals, innermost last (ADR 0039).
+    closures: Vec<ClosureFrame>,
+    /// The loops enclosing the statement being checked, innermost
+    /// last, with their labels — `break`/`continue` are rejected
+    /// outside any (ADR 0019) and must name one that exists (ADR 0059).
+    loops: Vec<Option<String>>,
+    /// True inside a `defer` body (ADR 0060): it runs while its block
+    /// is already exiting, so it may not `return` or `try` out.
+    deferring: bool,
+    /// The open `arena` blocks, innermost last (ADR 0061): what may
+    /// not escape each.
+    arenas: Vec<arena::ArenaFrame>,
     ret: Type,
     /// True exactly while typing a statement's direct right-hand side —
     /// the only positions where `try` is supported (ADR 0034). Set
     /// ONLY by `type_of_rhs`; taken (and reset) at every
     /// `type_of_expr` entry, so operands never inherit it.
     try_ok: bool,
+    /// True exactly while typing a bare `error.Name` operand of
+    /// `==`/`!=`: codes compare by identity (ADR 0034), so one that
+    /// carries payloads may be named without them there (ADR 0062).
+    /// Set by the equality check, taken by the literal.
+    code_ok: bool,
     /// Instantiation-chain depth of the body being checked — 0 for
     /// source functions; instances carry their chain depth so
     /// transitive requests can hit the cap (ADR 0035).
@@ -803,8 +1492,13 @@ struct Checker<'a, 'g> {
     out: &'a mut OutTables,
 }
 
+mod arena;
+mod coverage;
+mod escape;
 mod exprs;
 mod generics;
+mod init;
+mod patterns;
 mod stmts;
 #[cfg(test)]
 mod tests;
@@ -840,9 +1534,22 @@ impl<'g> Checker<'_, 'g> {
             Type::Struct(m, n) if *m != self.module => {
                 format!("{n} (from {})", self.paths[*m])
             }
+            // Function types parenthesize under suffixes (`name()`).
+            Type::Optional(inner) | Type::Array(inner) | Type::Fixed(inner, _)
+                if matches!(**inner, Type::Fn(..)) =>
+            {
+                t.name()
+            }
             Type::Optional(inner) => format!("{}?", self.type_name(inner)),
             Type::Array(inner) if unconstrained(inner) => "[]".to_string(),
             Type::Array(inner) => format!("{}[]", self.type_name(inner)),
+            Type::Fixed(inner, n) => format!("{}[{n}]", self.type_name(inner)),
+            Type::Map(k, v) if unconstrained(k) && unconstrained(v) => "{}".to_string(),
+            Type::Map(k, v) => format!("map<{}, {}>", self.type_name(k), self.type_name(v)),
+            Type::Tuple(elems) => {
+                let parts: Vec<String> = elems.iter().map(|e| self.type_name(e)).collect();
+                format!("({})", parts.join(", "))
+            }
             _ => t.name(),
         }
     }
@@ -867,14 +1574,21 @@ fn always_returns(stmts: &[Stmt]) -> bool {
             then_body,
             else_body: Some(else_body),
             ..
-        } => always_returns(then_body) && always_returns(else_body),
-        // Syntax-only, so only an `else`-carrying match counts — the
-        // analysis can't prove exhaustiveness (ADR 0036).
-        Stmt::Match {
-            arms,
+        }
+        | Stmt::IfConst {
+            then_body,
             else_body: Some(else_body),
             ..
-        } => arms.iter().all(|a| always_returns(&a.body)) && always_returns(else_body),
+        } => always_returns(then_body) && always_returns(else_body),
+        // `check_match` rejects a match that misses a variant, so an
+        // `else`-less match that type-checks is exhaustive (ADR 0044).
+        Stmt::Match {
+            arms, else_body, ..
+        } => {
+            arms.iter().all(|a| always_returns(&a.body))
+                && else_body.as_deref().is_none_or(always_returns)
+        }
+        Stmt::Arena { body, .. } => always_returns(body),
         _ => false,
     })
 }
@@ -0,0 +1,485 @@
+//! Match arms for both match forms: the scrutinee, arm patterns and
+//! their bindings (ADR 0036/0045), `if` guards, and the coverage
+//! matrix that proves exhaustiveness and finds unreachable arms
+//! (ADR 0044/0045). Int, string, and error scrutinees match literal
+//! arms and always need `else` (ADR 0046). A let-else is a one-arm
+//! match over the same machinery (ADR 0057), and so is the head of an
+//! `if const` or `while const` (ADR 0058).
+
+use super::coverage::{Ctor, Pat, missing, show, useful};
+use super::*;
+use crate::ast::Pattern;
+
+/// One match's coverage state: the scrutinee type and the patterns of
+/// the unguarded arms so far. `poisoned` after any head error — the
+/// matrix would only echo it.
+pub(super) struct ArmMatrix {
+    ty: Type,
+    rows: Vec<Vec<Pat>>,
+    guarded: bool,
+    poisoned: bool,
+}
+
+impl Checker<'_, '_> {
+    /// The scrutinee's type: an enum, or an int, string, or error code
+    /// matched against literal arms.
+    pub(super) fn match_scrutinee(&mut self, scrutinee: &Expr) -> ArmMatrix {
+        let ty = self.type_of_expr(scrutinee);
+        self.unnarrow_field_paths(); // the scrutinee may call
+        let poisoned = match &ty {
+            Type::Enum(..) | Type::Int | Type::Str | Type::ErrCode => false,
+            t if poisoned(t) => true,
+            other => {
+                self.error(
+                    format!(
+                        "match needs an enum, int, string, or error, found {}",
+                        self.type_name(other)
+                    ),
+                    scrutinee.span(),
+                );
+                true
+            }
+        };
+        ArmMatrix {
+            ty,
+            rows: Vec::new(),
+            guarded: false,
+            poisoned,
+        }
+    }
+
+    /// One arm head, in the arm's own scope and narrowing frame: binds
+    /// the pattern's names, checks the guard and lends its if-true
+    /// facts to the arm, and adds the pattern to the matrix — unless
+    /// the arms above already match everything it does.
+    pub(super) fn check_arm_head<B>(&mut self, m: &mut ArmMatrix, arm: &MatchArm<B>) {
+        let ty = if m.poisoned {
+            Type::Error
+        } else {
+            m.ty.clone()
+        };
+        let pat = self.check_pattern(&arm.pattern, &ty, false, &mut HashSet::new());
+        if let Some(guard) = &arm.guard {
+            self.check_condition("match guard", guard);
+            self.add_facts(condition_facts(guard).0);
+            m.guarded = true;
+        }
+        let Some(pat) = pat else {
+            m.poisoned = true;
+            return;
+        };
+        if m.poisoned {
+            return;
+        }
+        let row = vec![pat];
+        if !useful(&m.rows, &row, std::slice::from_ref(&m.ty), &self.mono.enums) {
+            // A whole variant or a literal named twice keeps its own
+            // message.
+            let whole = matches!(&row[0], Pat::Ctor(Ctor::Variant(_) | Ctor::Err(..), args)
+                if args.iter().all(|a| *a == Pat::Wild));
+            let repeated = m.rows.contains(&row);
+            let message = match &arm.pattern {
+                Pattern::Variant { name, .. } if whole && repeated => {
+                    format!("duplicate arm for variant '{name}' — this arm is unreachable")
+                }
+                Pattern::Error { name, .. } if whole && repeated => {
+                    format!("duplicate arm for 'error.{name}' — this arm is unreachable")
+                }
+                Pattern::Lit(e) if repeated => {
+                    format!(
+                        "duplicate arm for '{}' — this arm is unreachable",
+                        literal(e)
+                    )
+                }
+                _ => {
+                    "this arm is unreachable — the arms above match every value it does".to_string()
+                }
+            };
+            self.error(message, arm.pattern.span());
+        }
+        if arm.guard.is_none() {
+            m.rows.push(row);
+        }
+    }
+
+    /// A let-else pattern (ADR 0057): a one-arm match whose names bind
+    /// in the enclosing scope. Without `else` the pattern must match
+    /// every value; with one, some value must be left for it.
+    pub(super) fn check_let_pattern(
+        &mut self,
+        pattern: &Pattern,
+        ty: &Type,
+        mutable: bool,
+        has_else: bool,
+        span: Span,
+    ) {
+        let pat = self.check_pattern(pattern, ty, mutable, &mut HashSet::new());
+        let Some(pat) = pat.filter(|_| !poisoned(ty)) else {
+            return;
+        };
+        let rows = vec![vec![pat]];
+        let tys = std::slice::from_ref(ty);
+        if has_else {
+            if !useful(&rows, &[Pat::Wild], tys, &self.mono.enums) {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        "'else' is unreachable — the pattern matches every value".to_string(),
+                        span,
+                    )
+                    .with_help("remove the 'else' block".to_string()),
+                );
+            }
+        } else if let Some(witness) = missing(&rows, tys, &self.mono.enums) {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    format!(
+                        "pattern does not cover '{}'",
+                        show(&witness[0], ty, true, &self.mono.enums)
+                    ),
+                    pattern.span(),
+                )
+                .with_help("add 'else { ... }' to leave when it does not match".to_string()),
+            );
+        }
+    }
+
+    /// An `if const` / `while const` head (ADR 0058): a bare name
+    /// unwraps a `T?` or `T!` value, a variant pattern tests an enum.
+    /// Names bind as consts in the current scope, which the caller
+    /// opens for the body.
+    pub(super) fn check_const_head(&mut self, pattern: &Pattern, ty: &Type, keyword: &str) {
+        match pattern {
+            Pattern::Bind(..) => {
+                let inner = match ty {
+                    Type::Optional(inner) | Type::ErrUnion(inner) => (**inner).clone(),
+                    t if poisoned(t) => Type::Error,
+                    other => {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                format!(
+                                    "'{keyword} const' needs an optional or an error union, found {}",
+                                    self.type_name(other)
+                                ),
+                                pattern.span(),
+                            )
+                            .with_help(
+                                "an enum matches a variant pattern: 'Name(x)'".to_string(),
+                            ),
+                        );
+                        Type::Error
+                    }
+                };
+                self.check_pattern(pattern, &inner, false, &mut HashSet::new());
+            }
+            Pattern::Variant { .. } => {
+                let pat = self.check_pattern(pattern, ty, false, &mut HashSet::new());
+                let Some(pat) = pat else {
+                    return;
+                };
+                let tys = std::slice::from_ref(ty);
+                if !useful(&[vec![pat]], &[Pat::Wild], tys, &self.mono.enums) {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            format!("this '{keyword} const' pattern matches every value"),
+                            pattern.span(),
+                        )
+                        .with_help("bind it with 'const … = …;' instead".to_string()),
+                    );
+                }
+            }
+            Pattern::Lit(_) | Pattern::Error { .. } => {
+                unreachable!("the parser rejects literal heads")
+            }
+        }
+    }
+
+    /// One pattern against the type it matches: names bind as consts
+    /// (as vars under a `var` let-else) in the current scope, each at
+    /// most once per arm. `None` after an error.
+    fn check_pattern(
+        &mut self,
+        p: &Pattern,
+        ty: &Type,
+        mutable: bool,
+        names: &mut HashSet<String>,
+    ) -> Option<Pat> {
+        match p {
+            Pattern::Bind(name, span) => {
+                if name == "_" {
+                    return Some(Pat::Wild);
+                }
+                if !names.insert(name.clone()) {
+                    self.error(format!("'{name}' is bound twice"), *span);
+                }
+                self.bind(name, ty.clone(), mutable);
+                // `Dot` inside a payload list binds; say so when it
+                // reads like the variant of the same name.
+                if let Type::Enum(m, n) = ty
+                    && self.mono.enums[&(*m, n.clone())]
+                        .variants
+                        .iter()
+                        .any(|(v, _)| v == name)
+                {
+                    self.diagnostics.push(
+                        Diagnostic::error(
+                            format!("'{name}' binds this payload instead of matching the variant"),
+                            *span,
+                        )
+                        .with_help(format!("write '{name}()' to match the variant")),
+                    );
+                    return None;
+                }
+                Some(Pat::Wild)
+            }
+            Pattern::Lit(e) => {
+                let got = self.type_of_expr(e);
+                if poisoned(ty) || poisoned(&got) {
+                    return None;
+                }
+                if !matches!(ty, Type::Int | Type::Str | Type::Bool | Type::ErrCode) {
+                    self.error(
+                        format!(
+                            "literal patterns match int, string, bool, or error, found {}",
+                            self.type_name(ty)
+                        ),
+                        e.span(),
+                    );
+                    return None;
+                }
+                if got != *ty {
+                    self.error(
+                        format!(
+                            "pattern: expected {}, found {}",
+                            self.type_name(ty),
+                            self.type_name(&got)
+                        ),
+                        e.span(),
+                    );
+                    return None;
+                }
+                let c = match e {
+                    Expr::Int(n, _) => Ctor::Int(*n),
+                    Expr::Str(s, _) => Ctor::Str(s.clone()),
+                    Expr::Bool(b, _) => Ctor::Bool(*b),
+                    _ => unreachable!("the parser builds literal patterns from literals"),
+                };
+                Some(Pat::Ctor(c, Vec::new()))
+            }
+            Pattern::Variant {
+                name,
+                name_span,
+                args,
+                ..
+            } => {
+                let def = match ty {
+                    Type::Enum(m, n) => Some(self.mono.enums[&(*m, n.clone())].clone()),
+                    t if poisoned(t) => None,
+                    other => {
+                        self.error(
+                            format!(
+                                "variant pattern '{name}' needs an enum, found {}",
+                                self.type_name(other)
+                            ),
+                            *name_span,
+                        );
+                        None
+                    }
+                };
+                let tag = def.as_ref().and_then(|def| {
+                    let tag = def.variants.iter().position(|(n, _)| n == name);
+                    if tag.is_none() {
+                        let names = def.variants.iter().map(|(n, _)| n.as_str());
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                format!("this enum has no variant '{name}'"),
+                                *name_span,
+                            )
+                            .suggest(name, names),
+                        );
+                    }
+                    tag
+                });
+                let payloads = match (&def, tag) {
+                    (Some(def), Some(tag)) => def.variants[tag].1.clone(),
+                    _ => Vec::new(),
+                };
+                let mut ok = tag.is_some();
+                if ok && args.len() != payloads.len() {
+                    self.error(
+                        format!(
+                            "variant '{name}' has {} payload(s), found {} pattern(s)",
+                            payloads.len(),
+                            args.len()
+                        ),
+                        *name_span,
+                    );
+                    ok = false;
+                }
+                // Check every sub-pattern, even after an error, so its
+                // names still bind (as poison) for the arm body.
+                let mut subs = Vec::new();
+                for (i, arg) in args.iter().enumerate() {
+                    let pt = if ok { payloads[i].clone() } else { Type::Error };
+                    match self.check_pattern(arg, &pt, mutable, names) {
+                        Some(s) => subs.push(s),
+                        None => ok = false,
+                    }
+                }
+                let tag = tag.filter(|_| ok)?;
+                self.out.variant_tags.insert(*name_span, tag as u32);
+                Some(Pat::Ctor(Ctor::Variant(tag), subs))
+            }
+            Pattern::Error {
+                name,
+                name_span,
+                args,
+                ..
+            } => self.check_error_pattern(name, *name_span, args, ty, mutable, names),
+        }
+    }
+
+    /// `error.Name(p, …)` against an `error` scrutinee (ADR 0046, ADR
+    /// 0062): the sub-patterns match the code's payloads. Without a
+    /// list it matches the code whatever its payloads carry.
+    fn check_error_pattern(
+        &mut self,
+        name: &str,
+        name_span: Span,
+        args: &[Pattern],
+        ty: &Type,
+        mutable: bool,
+        names: &mut HashSet<String>,
+    ) -> Option<Pat> {
+        let code = match self.err_alias.get(name) {
+            Some(key) => Some(self.error_codes[key]),
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::error(format!("unknown error '{name}'"), name_span)
+                        .suggest(name, self.err_alias.keys().map(String::as_str)),
+                );
+                None
+            }
+        };
+        let mut ok = code.is_some();
+        if ok && *ty != Type::ErrCode {
+            if !poisoned(ty) {
+                self.error(
+                    format!(
+                        "error pattern 'error.{name}' needs an error, found {}",
+                        self.type_name(ty)
+                    ),
+                    name_span,
+                );
+            }
+            ok = false;
+        }
+        let all = self.error_payloads;
+        let payloads = code.map_or(&[][..], |c| &all[c as usize - 2][..]);
+        if ok && !args.is_empty() && args.len() != payloads.len() {
+            self.error(
+                format!(
+                    "error '{name}' has {} payload(s), found {} pattern(s)",
+                    payloads.len(),
+                    args.len()
+                ),
+                name_span,
+            );
+            ok = false;
+        }
+        // As for variants: every sub-pattern checks, so its names bind.
+        let mut subs = Vec::new();
+        for (i, arg) in args.iter().enumerate() {
+            let pt = if ok { payloads[i].clone() } else { Type::Error };
+            match self.check_pattern(arg, &pt, mutable, names) {
+                Some(s) => subs.push(s),
+                None => ok = false,
+            }
+        }
+        let code = code.filter(|_| ok)?;
+        if args.is_empty() {
+            subs = vec![Pat::Wild; payloads.len()];
+        }
+        self.out.error_lits.insert(name_span, code);
+        Some(Pat::Ctor(Ctor::Err(code, payloads.to_vec()), subs))
+    }
+
+    /// Exhaustiveness (ADR 0044/0045): without `else` the unguarded
+    /// arms must match every value; with one, some value must be left
+    /// for it to reach. Literal arms never cover an int, string, or
+    /// error scrutinee, so those need `else` (ADR 0046).
+    pub(super) fn match_coverage(&mut self, m: &ArmMatrix, has_else: bool, span: Span) {
+        if m.poisoned {
+            return;
+        }
+        let tys = std::slice::from_ref(&m.ty);
+        if has_else {
+            if !useful(&m.rows, &[Pat::Wild], tys, &self.mono.enums) {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        "'else' is unreachable — the arms already match every value".to_string(),
+                        span,
+                    )
+                    .with_help("remove the 'else' arm".to_string()),
+                );
+            }
+            return;
+        }
+        let Some(witness) = missing(&m.rows, tys, &self.mono.enums) else {
+            return;
+        };
+        let Type::Enum(mk, n) = &m.ty else {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    format!(
+                        "match on {} needs 'else' — literal arms cannot cover every value",
+                        self.type_name(&m.ty)
+                    ),
+                    span,
+                )
+                .with_help("add 'else' for the values no arm names".to_string()),
+            );
+            return;
+        };
+        // Variants no unguarded arm reaches at all are listed by name;
+        // otherwise the witness shows which values slip through.
+        let def = &self.mono.enums[&(*mk, n.clone())];
+        let untouched: Vec<String> = def
+            .variants
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                !m.rows.iter().any(|r| match &r[0] {
+                    Pat::Ctor(Ctor::Variant(t), _) => t == i,
+                    _ => true,
+                })
+            })
+            .map(|(_, (name, _))| format!("'{name}'"))
+            .collect();
+        let message = if untouched.is_empty() {
+            format!(
+                "match does not cover '{}' — add an arm or 'else'",
+                show(&witness[0], &m.ty, true, &self.mono.enums)
+            )
+        } else {
+            format!(
+                "match does not cover variant(s) {} — add arms or 'else'",
+                untouched.join(", ")
+            )
+        };
+        let mut diag = Diagnostic::error(message, span);
+        if m.guarded {
+            diag = diag.with_help("guarded arms do not count toward coverage".to_string());
+        }
+        self.diagnostics.push(diag);
+    }
+}
+
+/// A literal pattern as written, for duplicate-arm diagnostics.
+fn literal(e: &Expr) -> String {
+    match e {
+        Expr::Int(n, _) => n.to_string(),
+        Expr::Str(s, _) => format!("{s:?}"),
+        Expr::Bool(b, _) => b.to_string(),
+        _ => unreachable!("the parser builds literal patterns from literals"),
+    }
+}
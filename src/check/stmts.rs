@@ -37,12 +37,42 @@ impl Checker<'_, '_> {
         }
     }
 
+    /// A module const's or var's initializer (ADR 0047/0048): an
+    /// expression in an empty scope, against the declared type. A const's
+    /// value is computed later, by the interpreter, once the whole
+    /// program checks; a var's runs before `main`.
+    pub(super) fn check_initializer(
+        &mut self,
+        name: &str,
+        value: &Expr,
+        declared: &Type,
+        span: Span,
+    ) {
+        self.scopes.push(HashMap::new());
+        self.nonnull.push(NarrowFrame::new(HashMap::new()));
+        if !self.check_literal_against(value, declared) {
+            let init_ty = self.type_of_expr(value);
+            if !fits(&init_ty, declared) && !poisoned(declared) {
+                self.error(
+                    format!(
+                        "'{name}' is declared as {} but initialized with {}",
+                        self.type_name(declared),
+                        self.type_name(&init_ty)
+                    ),
+                    span,
+                );
+            }
+        }
+        self.nonnull.pop();
+        self.scopes.pop();
+    }
+
     /// Type-checks a nested block in its own scope (bindings made inside die
     /// at the closing brace), with a set of place paths proven non-null for
     /// its duration. Returns the facts still standing at the block's end —
     /// the survivors a divergence-aware join may carry past an `if`
     /// (writes inside the block already subtracted themselves, ADR 0020).
-    fn check_block_narrowed(
+    pub(super) fn check_block_narrowed(
         &mut self,
         stmts: &[Stmt],
         facts: HashMap<String, Fact>,
@@ -53,7 +83,8 @@ impl Checker<'_, '_> {
             self.check_stmt(stmt);
         }
         self.scopes.pop();
-        self.nonnull.pop().expect("frame pushed above").facts
+        let facts = self.nonnull.pop().expect("frame pushed above").facts;
+        deferred_survivors(stmts, facts)
     }
 
     /// Snapshot of the fact stack, taken before a diverging branch: a
@@ -73,7 +104,7 @@ impl Checker<'_, '_> {
     /// Grants the code after a divergence-aware join its proven facts;
     /// they live in the innermost frame and expire with the enclosing
     /// block. A frame always exists (`check_function` pushes the base).
-    fn add_facts(&mut self, facts: HashMap<String, Fact>) {
+    pub(super) fn add_facts(&mut self, facts: HashMap<String, Fact>) {
         if let Some(frame) = self.nonnull.last_mut() {
             frame.facts.extend(facts);
         }
@@ -110,6 +141,56 @@ impl Checker<'_, '_> {
         }
     }
 
+    /// A `defer` body (ADR 0060) runs at the block's exit, not here:
+    /// it checks with no enclosing loop, and keeps only the facts on
+    /// const bindings — nothing between here and the exit can undo
+    /// those. What it writes or calls is subtracted from the standing
+    /// facts at once.
+    fn check_defer(&mut self, body: &[Stmt]) {
+        let stable: HashMap<String, Fact> = self
+            .nonnull
+            .iter()
+            .flat_map(|frame| frame.facts.keys())
+            .filter(|path| !path.contains('.'))
+            .filter(|path| self.find_var(path).is_some_and(|v| !v.mutable))
+            .filter_map(|path| Some((path.clone(), self.fact_of(path)?)))
+            .collect();
+        let outer_loops = std::mem::take(&mut self.loops);
+        let outer_deferring = std::mem::replace(&mut self.deferring, true);
+        let outer_facts = std::mem::replace(&mut self.nonnull, vec![NarrowFrame::new(stable)]);
+        self.scopes.push(HashMap::new());
+        for stmt in body {
+            self.check_stmt(stmt);
+        }
+        self.scopes.pop();
+        self.nonnull = outer_facts;
+        self.deferring = outer_deferring;
+        self.loops = outer_loops;
+        let mut assigned = HashSet::new();
+        let mut kills_fields = false;
+        body_effects(body, &mut assigned, &mut kills_fields);
+        for path in &assigned {
+            self.unnarrow(path);
+        }
+        if kills_fields {
+            self.unnarrow_field_paths();
+        }
+    }
+
+    /// Opens a loop for `break`/`continue`. A label must differ from
+    /// every enclosing loop's, so each names one loop (ADR 0059).
+    fn enter_loop(&mut self, label: &Option<String>, span: Span) {
+        if let Some(name) = label
+            && self.loops.iter().any(|l| l.as_ref() == Some(name))
+        {
+            self.error(
+                format!("label '{name}' is already used by an enclosing loop"),
+                span,
+            );
+        }
+        self.loops.push(label.clone());
+    }
+
     /// Facts from outside a loop go stale on iteration 2 if the body can
     /// invalidate them (only the loop's own condition is re-checked each
     /// pass) — drop everything the body can touch before checking it. The
@@ -138,83 +219,28 @@ impl Checker<'_, '_> {
         }
     }
 
-    /// `match` (ADR 0036): the scrutinee must be an enum; every arm
-    /// names a distinct variant and binds its payloads (`_` skips);
-    /// coverage is all variants or an `else`. Arms mirror `if`
-    /// branches for narrowing — own frame and scope, divergence-aware
-    /// rollback.
+    /// `match` (ADR 0036): the scrutinee must be an enum; arm heads
+    /// and coverage as in `patterns`. Arms mirror `if` branches for
+    /// narrowing — own frame and scope, divergence-aware rollback.
     fn check_match(
         &mut self,
         scrutinee: &Expr,
-        arms: &[crate::ast::MatchArm],
+        arms: &[MatchArm],
         else_body: Option<&[Stmt]>,
         span: Span,
     ) {
-        let s_ty = self.type_of_expr(scrutinee);
-        self.unnarrow_field_paths(); // the scrutinee may call
-        let def = match &s_ty {
-            Type::Enum(m, n) => Some(self.mono.enums[&(*m, n.clone())].clone()),
-            t if poisoned(t) => None,
-            other => {
-                self.error(
-                    format!("match needs an enum, found {}", self.type_name(other)),
-                    scrutinee.span(),
-                );
-                None
-            }
-        };
-        let mut seen: HashSet<String> = HashSet::new();
+        let mut matrix = self.match_scrutinee(scrutinee);
         for arm in arms {
-            let payloads = match &def {
-                Some(def) => match def.variants.iter().position(|(n, _)| n == &arm.variant) {
-                    Some(tag) => {
-                        if !seen.insert(arm.variant.clone()) {
-                            self.error(
-                                format!("duplicate arm for variant '{}'", arm.variant),
-                                arm.variant_span,
-                            );
-                        }
-                        self.out.variant_tags.insert(arm.variant_span, tag as u32);
-                        let payloads = def.variants[tag].1.clone();
-                        if arm.bindings.len() != payloads.len() {
-                            self.error(
-                                format!(
-                                    "variant '{}' has {} payload(s), found {} binding(s)",
-                                    arm.variant,
-                                    payloads.len(),
-                                    arm.bindings.len()
-                                ),
-                                arm.variant_span,
-                            );
-                        }
-                        payloads
-                    }
-                    None => {
-                        let names = def.variants.iter().map(|(n, _)| n.as_str());
-                        self.diagnostics.push(
-                            Diagnostic::error(
-                                format!("this enum has no variant '{}'", arm.variant),
-                                arm.variant_span,
-                            )
-                            .suggest(&arm.variant, names),
-                        );
-                        Vec::new()
-                    }
-                },
-                None => Vec::new(),
-            };
-            // The arm body: its own frame and scope with the payload
-            // bindings; a diverging arm's narrowing side effects roll
-            // back, like an if branch (ADR 0020).
+            // The arm body: its own frame and scope with the pattern's
+            // bindings and the guard's facts; a diverging arm's
+            // narrowing side effects roll back, like an if branch
+            // (ADR 0020).
             let saved = self.checkpoint(diverges(&arm.body));
             self.nonnull.push(NarrowFrame::new(HashMap::new()));
             self.scopes.push(HashMap::new());
-            for (i, (bname, _)) in arm.bindings.iter().enumerate() {
-                if bname != "_" {
-                    let ty = payloads.get(i).cloned().unwrap_or(Type::Error);
-                    self.bind(bname, ty, false);
-                }
-            }
+            self.begin_binding_from(scrutinee);
+            self.check_arm_head(&mut matrix, arm);
+            self.end_binding_from();
             for stmt in &arm.body {
                 self.check_stmt(stmt);
             }
@@ -226,27 +252,8 @@ impl Checker<'_, '_> {
             let saved = self.checkpoint(diverges(else_body));
             self.check_block_narrowed(else_body, HashMap::new());
             self.rollback(saved);
-        } else if let Some(def) = &def {
-            let missing: Vec<&str> = def
-                .variants
-                .iter()
-                .map(|(n, _)| n.as_str())
-                .filter(|n| !seen.contains(*n))
-                .collect();
-            if !missing.is_empty() {
-                self.error(
-                    format!(
-                        "match does not cover variant(s) {} — add arms or 'else'",
-                        missing
-                            .iter()
-                            .map(|n| format!("'{n}'"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ),
-                    span,
-                );
-            }
         }
+        self.match_coverage(&matrix, else_body.is_some(), span);
     }
 
     pub(super) fn check_condition(&mut self, keyword: &str, cond: &Expr) {
@@ -262,7 +269,7 @@ impl Checker<'_, '_> {
         }
     }
 
-    fn check_stmt(&mut self, stmt: &Stmt) {
+    pub(super) fn check_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Let {
                 mutable,
@@ -313,19 +320,193 @@ impl Checker<'_, '_> {
                         }
                     }
                 };
+                self.begin_binding_from(value);
                 self.bind(name, ty, *mutable);
+                self.end_binding_from();
+            }
+            // The names take their types from the tuple, the way loop
+            // variables take theirs from the iterable (ADR 0043); `_`
+            // skips a position.
+            Stmt::LetTuple {
+                mutable,
+                names,
+                value,
+                span,
+            } => {
+                let ty = self.type_of_rhs(value);
+                let elems = match ty {
+                    Type::Tuple(elems) if elems.len() == names.len() => elems,
+                    t => {
+                        if !poisoned(&t) {
+                            self.error(
+                                format!(
+                                    "cannot destructure {}: expected a tuple of {} elements",
+                                    self.type_name(&t),
+                                    names.len()
+                                ),
+                                value.span(),
+                            );
+                        }
+                        vec![Type::Error; names.len()]
+                    }
+                };
+                let mut seen = HashSet::new();
+                for (name, ety) in names.iter().zip(elems) {
+                    if name == "_" {
+                        continue;
+                    }
+                    if !seen.insert(name) {
+                        self.error(format!("'{name}' is bound twice"), *span);
+                    }
+                    // Nothing to annotate: a leftover `[]`/`{}` element
+                    // can't be bound.
+                    let ety = if unconstrained(&ety) {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                format!(
+                                    "cannot infer a type for '{name}' from {}",
+                                    self.type_name(&ety)
+                                ),
+                                *span,
+                            )
+                            .with_help("destructure a declared tuple instead".to_string()),
+                        );
+                        Type::Error
+                    } else {
+                        ety
+                    };
+                    self.begin_binding_from(value);
+                    self.bind(name, ety, *mutable);
+                    self.end_binding_from();
+                }
+            }
+            // Fields bind by name with their declared types; each one
+            // records its slot for lowering like a field read (ADR 0057).
+            Stmt::LetStruct {
+                mutable,
+                fields,
+                value,
+                span: _,
+            } => {
+                let ty = self.type_of_rhs(value);
+                let def = match &ty {
+                    Type::Struct(m, n) => Some(self.mono.structs[&(*m, n.clone())].clone()),
+                    t => {
+                        if !poisoned(t) {
+                            self.error(
+                                format!(
+                                    "cannot destructure {}: expected a struct",
+                                    self.type_name(t)
+                                ),
+                                value.span(),
+                            );
+                        }
+                        None
+                    }
+                };
+                let mut seen = HashSet::new();
+                for (field, name, fspan) in fields {
+                    let fty = match &def {
+                        Some(def) => match def.fields.iter().position(|(f, _)| f == field) {
+                            Some(index) => {
+                                let fty = def.fields[index].1.clone();
+                                self.out.field_slots.insert(
+                                    *fspan,
+                                    FieldSlot {
+                                        base: ty.clone(),
+                                        index,
+                                        ty: fty.clone(),
+                                    },
+                                );
+                                fty
+                            }
+                            None => {
+                                let names = def.fields.iter().map(|(f, _)| f.as_str());
+                                self.diagnostics.push(
+                                    Diagnostic::error(
+                                        format!(
+                                            "struct '{}' has no field '{field}'",
+                                            self.type_name(&ty)
+                                        ),
+                                        *fspan,
+                                    )
+                                    .suggest(field, names),
+                                );
+                                Type::Error
+                            }
+                        },
+                        None => Type::Error,
+                    };
+                    if name == "_" {
+                        continue;
+                    }
+                    if !seen.insert(name) {
+                        self.error(format!("'{name}' is bound twice"), *fspan);
+                    }
+                    self.begin_binding_from(value);
+                    self.bind(name, fty, *mutable);
+                    self.end_binding_from();
+                }
+            }
+            // A one-arm match whose names outlive it (ADR 0057). The
+            // `else` runs before anything binds and must leave, so the
+            // code after it only runs when the pattern matched.
+            Stmt::LetElse {
+                mutable,
+                pattern,
+                value,
+                else_body,
+                span,
+            } => {
+                let ty = self.type_of_rhs(value);
+                self.unnarrow_field_paths(); // the value may call
+                let ty = match ty {
+                    Type::Enum(..) => ty,
+                    t if poisoned(&t) => Type::Error,
+                    other => {
+                        self.error(
+                            format!("let-else needs an enum, found {}", self.type_name(&other)),
+                            value.span(),
+                        );
+                        Type::Error
+                    }
+                };
+                if let Some(else_body) = else_body {
+                    if !diverges(else_body) {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                "the 'else' of a let-else must not fall through".to_string(),
+                                *span,
+                            )
+                            .with_help("end it with 'return', 'break', or 'continue'".to_string()),
+                        );
+                    }
+                    let saved = self.checkpoint(true);
+                    self.check_block_narrowed(else_body, HashMap::new());
+                    self.rollback(saved);
+                }
+                self.begin_binding_from(value);
+                self.check_let_pattern(pattern, &ty, *mutable, else_body.is_some(), *span);
+                self.end_binding_from();
             }
             Stmt::Return { value, span } => {
+                if self.deferring {
+                    self.error("a 'defer' body cannot return".to_string(), *span);
+                }
                 let ret = self.ret.clone();
                 if let Some(e) = value
                     && self.check_literal_against(e, &ret)
                 {
+                    self.check_arena_return(e, *span);
                     return;
                 }
                 let ty = match value {
                     Some(e) => self.type_of_rhs(e),
                     None => Type::Unit,
                 };
+                if let Some(e) = value {
+                    self.check_arena_return(e, *span);
+                }
                 if !fits(&ty, &self.ret) {
                     self.error(
                         format!(
@@ -370,15 +551,81 @@ impl Checker<'_, '_> {
                     None => {}
                 }
             }
-            Stmt::While { cond, body, .. } => {
+            // The head binds in the then-branch's own frame and scope;
+            // the branches join as an `if`'s do (ADR 0058).
+            Stmt::IfConst {
+                pattern,
+                value,
+                then_body,
+                else_body,
+                ..
+            } => {
+                let ty = self.type_of_expr(value);
+                self.unnarrow_field_paths(); // the value may call
+                let then_diverges = diverges(then_body);
+                let saved = self.checkpoint(then_diverges);
+                self.nonnull.push(NarrowFrame::new(HashMap::new()));
+                self.scopes.push(HashMap::new());
+                self.begin_binding_from(value);
+                self.check_const_head(pattern, &ty, "if");
+                self.end_binding_from();
+                for stmt in then_body {
+                    self.check_stmt(stmt);
+                }
+                self.scopes.pop();
+                let then_survivors = self.nonnull.pop().expect("frame pushed above").facts;
+                let then_survivors = deferred_survivors(then_body, then_survivors);
+                self.rollback(saved);
+                if let Some(else_body) = else_body {
+                    let else_diverges = diverges(else_body);
+                    let saved = self.checkpoint(else_diverges);
+                    let else_survivors = self.check_block_narrowed(else_body, HashMap::new());
+                    self.rollback(saved);
+                    match (then_diverges, else_diverges) {
+                        (true, false) => self.add_facts(else_survivors),
+                        (false, true) => self.add_facts(then_survivors),
+                        _ => {}
+                    }
+                }
+            }
+            Stmt::WhileConst {
+                label,
+                pattern,
+                value,
+                body,
+                span,
+            } => {
+                let ty = self.type_of_expr(value);
+                self.unnarrow_field_paths(); // the value may call
+                self.drop_loop_invalidated_facts(body);
+                self.enter_loop(label, *span);
+                self.nonnull.push(NarrowFrame::new(HashMap::new()));
+                self.scopes.push(HashMap::new());
+                self.begin_binding_from(value);
+                self.check_const_head(pattern, &ty, "while");
+                self.end_binding_from();
+                for stmt in body {
+                    self.check_stmt(stmt);
+                }
+                self.scopes.pop();
+                self.nonnull.pop();
+                self.loops.pop();
+            }
+            Stmt::While {
+                label,
+                cond,
+                body,
+                span,
+            } => {
                 self.check_condition("while", cond);
                 let (if_true, _) = condition_facts(cond);
                 self.drop_loop_invalidated_facts(body);
-                self.loop_depth += 1;
+                self.enter_loop(label, *span);
                 self.check_block_narrowed(body, if_true);
-                self.loop_depth -= 1;
+                self.loops.pop();
             }
             Stmt::For {
+                label,
                 index,
                 name,
                 iterable,
@@ -391,12 +638,55 @@ impl Checker<'_, '_> {
                         *span,
                     );
                 }
-                let iter_ty = self.type_of_expr(iterable);
+                // A range binds its int counter alone, as an int array
+                // binds its element (ADR 0051).
+                let iter_ty = if let Expr::Range { .. } = iterable {
+                    self.check_range(iterable, false);
+                    if index.is_some() {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                "a range binds only its counter".to_string(),
+                                iterable.span(),
+                            )
+                            .with_help(format!("write 'for {name} in …'")),
+                        );
+                    }
+                    Type::Array(Box::new(Type::Int))
+                } else {
+                    self.type_of_expr(iterable)
+                };
+                // A map binds its key where an array binds the index
+                // (ADR 0042): `for [k, v] in m`, insertion order.
+                let mut index_ty = Type::Int;
                 let elem = match iter_ty {
+                    Type::Map(k, v) if unconstrained(&k) || unconstrained(&v) => {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                format!("cannot infer a type for '{name}' from this iterable"),
+                                iterable.span(),
+                            )
+                            .with_help("bind the map with an annotated type first".to_string()),
+                        );
+                        index_ty = Type::Error;
+                        Type::Error
+                    }
+                    Type::Map(k, v) => {
+                        if index.is_none() {
+                            self.diagnostics.push(
+                                Diagnostic::error(
+                                    "iterating a map binds a key and a value".to_string(),
+                                    iterable.span(),
+                                )
+                                .with_help(format!("write 'for [key, {name}] in …'")),
+                            );
+                        }
+                        index_ty = *k;
+                        *v
+                    }
                     // An unconstrained element (`for x in [[]]`) would bind
                     // x at a type that fits everything — reject like an
                     // un-annotated `[]` binding.
-                    Type::Array(elem) if unconstrained(&elem) => {
+                    Type::Array(elem) | Type::Fixed(elem, _) if unconstrained(&elem) => {
                         self.diagnostics.push(
                             Diagnostic::error(
                                 format!("cannot infer a type for '{name}' from this iterable"),
@@ -406,12 +696,14 @@ impl Checker<'_, '_> {
                         );
                         Type::Error
                     }
-                    Type::Array(elem) => *elem,
+                    Type::Array(elem) | Type::Fixed(elem, _) => *elem,
+                    // A string yields its bytes (ADR 0053).
+                    Type::Str => Type::Sized(IntTy::U8),
                     ref t if poisoned(t) => Type::Error,
                     other => {
                         self.error(
                             format!(
-                                "can only iterate over arrays, found {}",
+                                "can only iterate over arrays, maps, and strings, found {}",
                                 self.type_name(&other)
                             ),
                             iterable.span(),
@@ -421,18 +713,20 @@ impl Checker<'_, '_> {
                 };
                 self.drop_loop_invalidated_facts(body);
                 // Body scope with the loop bindings: const element (and
-                // optional const int index).
+                // optional const int index, or the map key).
                 self.nonnull.push(NarrowFrame::new(HashMap::new()));
                 self.scopes.push(HashMap::new());
+                self.begin_binding_from(iterable);
                 self.bind(name, elem, false);
                 if let Some(index) = index {
-                    self.bind(index, Type::Int, false);
+                    self.bind(index, index_ty, false);
                 }
-                self.loop_depth += 1;
+                self.end_binding_from();
+                self.enter_loop(label, *span);
                 for stmt in body {
                     self.check_stmt(stmt);
                 }
-                self.loop_depth -= 1;
+                self.loops.pop();
                 self.scopes.pop();
                 self.nonnull.pop();
             }
@@ -445,25 +739,44 @@ impl Checker<'_, '_> {
             Stmt::Expr(e) => {
                 self.type_of_rhs(e);
             }
-            Stmt::Break { span } | Stmt::Continue { span } => {
-                if self.loop_depth == 0 {
-                    let kw = if matches!(stmt, Stmt::Break { .. }) {
-                        "break"
-                    } else {
-                        "continue"
-                    };
+            Stmt::Defer { body, .. } => self.check_defer(body),
+            Stmt::Arena { body, .. } => self.check_arena(body),
+            Stmt::Break { label, span } | Stmt::Continue { label, span } => {
+                let kw = if matches!(stmt, Stmt::Break { .. }) {
+                    "break"
+                } else {
+                    "continue"
+                };
+                if self.loops.is_empty() {
                     self.error(format!("'{kw}' outside of a loop"), *span);
+                } else if let Some(label) = label
+                    && !self.loops.iter().any(|l| l.as_ref() == Some(label))
+                {
+                    let names = self.loops.iter().flatten().map(String::as_str);
+                    self.diagnostics.push(
+                        Diagnostic::error(format!("'{kw} {label}' names no enclosing loop"), *span)
+                            .suggest(label, names),
+                    );
                 }
             }
             Stmt::Assign {
                 target,
+                op,
                 value,
+                step,
                 span,
             } => {
                 // Array-literal values are checked against the target's
                 // declared type once it's known (ADR 0010); everything else
                 // is typed now, while narrowing facts are still intact.
-                let value_ty = if matches!(value, Expr::ArrayLit { .. }) {
+                let value_ty = if op.is_none()
+                    && matches!(
+                        value,
+                        Expr::ArrayLit { .. }
+                            | Expr::ArrayRepeat { .. }
+                            | Expr::MapLit { .. }
+                            | Expr::TupleLit { .. }
+                    ) {
                     None
                 } else {
                     Some(self.type_of_rhs(value))
@@ -472,15 +785,34 @@ impl Checker<'_, '_> {
                 let Some((root, root_span)) = root_ident(target) else {
                     return;
                 };
-                let Some(mutable) = self.find_var(root).map(|info| info.mutable) else {
-                    self.lookup(root, root_span); // emits undefined + suggestion
-                    return;
+                // A module var is always writable and never captured
+                // (ADR 0048): every function shares the one storage.
+                let (mutable, captured) = match self.find_var(root).map(|info| info.mutable) {
+                    // A function literal holds copies of its captures
+                    // (ADR 0039) — rebinding one would be invisible outside.
+                    Some(mutable) => (mutable, self.capture(root)),
+                    None if self.global_alias.contains_key(root) => (true, false),
+                    None => {
+                        if self.const_alias.contains_key(root) {
+                            self.error(
+                                format!("cannot assign to '{root}': module consts are immutable"),
+                                root_span,
+                            );
+                        } else {
+                            self.lookup(root, root_span); // emits undefined + suggestion
+                        }
+                        return;
+                    }
                 };
                 // Rebinding a place invalidates its narrowing — the new
                 // value may be null again. (The value above was typed while
                 // still narrowed, so `cur = cur.next` checks out. Prefixes
                 // stay narrowed, so a guarded `cur.left.v = 1` still types.)
-                if self.has_facts()
+                // A compound assignment reads the place first and writes
+                // back the operator's result, never null (ADR 0050): the
+                // target keeps its narrowing and is typed under it.
+                if op.is_none()
+                    && self.has_facts()
                     && let Some(path) = target.place_path()
                 {
                     self.unnarrow(&path);
@@ -496,7 +828,7 @@ impl Checker<'_, '_> {
                 // not the binding). Decided while narrowing facts are still
                 // intact, so crosses_ref's re-typing sees exactly what the
                 // typing pass above saw and emits nothing new.
-                let allowed = !clean || mutable || self.crosses_ref(target);
+                let allowed = !clean || (mutable && !captured) || self.crosses_ref(target);
                 // A write through a field or index may reach aliased state —
                 // field facts don't survive it. Dropped only now, after
                 // every check that re-reads the place.
@@ -506,15 +838,97 @@ impl Checker<'_, '_> {
                 if !clean {
                     return;
                 }
+                // `m[k]` reads a `V?` (ADR 0042); writes are `insert`.
+                if let Expr::Index { base, .. } = target
+                    && matches!(self.out.expr_types.get(&base.span()), Some(Type::Map(..)))
+                {
+                    self.diagnostics.push(
+                        Diagnostic::error("cannot assign to a map lookup".to_string(), *span)
+                            .with_help("store entries with 'insert(m, k, v)'".to_string()),
+                    );
+                    return;
+                }
+                // Strings are immutable (ADR 0013): their bytes read only.
+                if let Expr::Index { base, .. } = target
+                    && matches!(self.out.expr_types.get(&base.span()), Some(Type::Str))
+                {
+                    self.diagnostics.push(
+                        Diagnostic::error("cannot assign into a string".to_string(), *span)
+                            .with_help(
+                                "strings are immutable — build a new one with '+'".to_string(),
+                            ),
+                    );
+                    return;
+                }
+                // A slice is a fresh copy (ADR 0051): a write into one
+                // would land nowhere the program can see.
+                let mut place = target;
+                while let Expr::Field { base, .. } | Expr::Index { base, .. } = place {
+                    if let Expr::Index { index, .. } = place
+                        && let Expr::Range { span, .. } = &**index
+                    {
+                        self.diagnostics.push(
+                            Diagnostic::error("cannot assign into a slice".to_string(), *span)
+                                .with_help(
+                                    "a slice is a copy — index the array itself".to_string(),
+                                ),
+                        );
+                        return;
+                    }
+                    place = base;
+                }
                 if !allowed {
-                    self.error(format!("cannot assign to const '{root}'"), *span);
+                    let message = if captured {
+                        format!(
+                            "cannot assign to captured '{root}' — a function literal holds a copy"
+                        )
+                    } else {
+                        format!("cannot assign to const '{root}'")
+                    };
+                    let mut diag = Diagnostic::error(message, *span);
+                    // A value-struct receiver is copied in (ADR 0040).
+                    if root == syntax::SELF_PARAM && !captured {
+                        diag = diag.with_help(
+                            "'self' is a copy of the receiver — update a 'var' copy and return it"
+                                .to_string(),
+                        );
+                    }
+                    self.diagnostics.push(diag);
                     return;
                 }
+                // An int literal takes the place's sized type (ADR 0052).
                 let value_ty = match value_ty {
+                    Some(Type::Int) => self.sized_literal(value, &target_ty).unwrap_or(Type::Int),
                     Some(ty) => ty,
-                    None if self.check_literal_against(value, &target_ty) => return,
+                    None if self.check_literal_against(value, &target_ty) => {
+                        self.check_arena_assign(target, value, false, *span);
+                        return;
+                    }
                     None => self.type_of_expr(value),
                 };
+                self.check_arena_assign(target, value, op.is_some(), *span);
+                // `place op= value` types as `place op value`; the result
+                // has the place's own type (or, for `??=`, one that fits it).
+                if let Some(op) = op {
+                    // `x++` steps the place itself: only an integer
+                    // steps, and the error names the operator written.
+                    if *step {
+                        if !is_integer(&target_ty) && !poisoned(&target_ty) {
+                            let written = if *op == BinOp::Add { "++" } else { "--" };
+                            self.error(
+                                format!(
+                                    "cannot apply '{written}' to {}",
+                                    self.type_name(&target_ty)
+                                ),
+                                *span,
+                            );
+                        }
+                        return;
+                    }
+                    let written = format!("{}=", op.symbol());
+                    self.check_operator(*op, &written, target_ty, value_ty, *span);
+                    return;
+                }
                 if !fits(&value_ty, &target_ty) {
                     let message = match target {
                         Expr::Field { name, .. } => format!(
@@ -534,3 +948,14 @@ impl Checker<'_, '_> {
         }
     }
 }
+
+/// The facts a block hands to the join after it. A block with a
+/// `defer` hands on none: the deferred body runs after the last
+/// statement, where a fact proven later in the block may not hold.
+fn deferred_survivors(body: &[Stmt], facts: HashMap<String, Fact>) -> HashMap<String, Fact> {
+    if body.iter().any(|s| matches!(s, Stmt::Defer { .. })) {
+        HashMap::new()
+    } else {
+        facts
+    }
+}
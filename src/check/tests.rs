@@ -16,6 +16,7 @@ fn graph_of(src: &str) -> (ModuleGraph, SourceMap) {
             ast,
             imports: Vec::new(),
         }],
+        init_order: vec![0],
     };
     (graph, map)
 }
@@ -2105,13 +2106,22 @@ fn match_coverage_rules() {
             .any(|e| e.message.contains("does not cover variant(s) 'B'")),
         "{d:?}"
     );
-    // else closes the gap; a redundant else on an exhaustive match is
-    // legal (the divergence analyses are syntax-only).
+    // else closes the gap; on an already exhaustive match it is
+    // unreachable (ADR 0044).
     let d = diags(&format!(
         "{base}fun main(): int {{ const s: S = S.B(); \
-         match s {{ A(_) {{ }} B {{ }} else {{ }} }} return 0; }}"
+         match s {{ A(_) {{ }} else {{ }} }} return 0; }}"
     ));
     assert!(d.is_empty(), "{d:?}");
+    let d = diags(&format!(
+        "{base}fun main(): int {{ const s: S = S.B(); \
+         match s {{ A(_) {{ }} B {{ }} else {{ }} }} return 0; }}"
+    ));
+    assert!(
+        d.iter()
+            .any(|e| e.message.contains("'else' is unreachable")),
+        "{d:?}"
+    );
     let d = diags(&format!(
         "{base}fun main(): int {{ const s: S = S.B(); \
          match s {{ A(v) {{ print(v); }} A(v) {{ print(v); }} B {{ }} }} return 0; }}"
@@ -2135,13 +2145,14 @@ fn match_coverage_rules() {
     assert!(
         d.iter().any(|e| e
             .message
-            .contains("'A' has 1 payload(s), found 2 binding(s)")),
+            .contains("'A' has 1 payload(s), found 2 pattern(s)")),
         "{d:?}"
     );
-    let d = diags("fun main(): int { match 5 { A { } } return 0; }");
+    let d = diags("fun main(): int { match 1.5 { A { } } return 0; }");
     assert!(
-        d.iter()
-            .any(|e| e.message.contains("match needs an enum, found int")),
+        d.iter().any(|e| e
+            .message
+            .contains("match needs an enum, int, string, or error, found float")),
         "{d:?}"
     );
 }
@@ -2154,12 +2165,19 @@ fn match_with_else_satisfies_definite_return() {
          fun main(): int { return f(S.B()); }",
     );
     assert!(d.is_empty(), "{d:?}");
-    // Without else the analysis stays conservative.
+    // Coverage is checked, so an exhaustive match needs no else
+    // (ADR 0044) — but every arm must still return.
     let d = diags(
         "enum S { A(int), B }\n\
          fun f(s: S): int { match s { A(v) { return v; } B { return 0; } } }\n\
          fun main(): int { return f(S.B()); }",
     );
+    assert!(d.is_empty(), "{d:?}");
+    let d = diags(
+        "enum S { A(int), B }\n\
+         fun f(s: S): int { match s { A(v) { return v; } B { print(0); } } }\n\
+         fun main(): int { return f(S.B()); }",
+    );
     assert!(
         d.iter().any(|e| e.message.contains("not all paths")),
         "{d:?}"
@@ -2210,14 +2228,14 @@ fn enum_construction_rules() {
             .any(|e| e.message.contains("cannot infer 'T' from the arguments")),
         "{d:?}"
     );
-    // The old non-enum call shape keeps its message.
+    // On a variable the same shape is a method call (ADR 0040).
     let d = diags(
         "struct P { x: int }\n\
          fun main(): int { const p: P = P { x: 1 }; p.x(1); return 0; }",
     );
     assert!(
         d.iter()
-            .any(|e| e.message.contains("only named functions can be called")),
+            .any(|e| e.message.contains("type P has no method 'x'")),
         "{d:?}"
     );
 }
@@ -2241,3 +2259,1963 @@ fn enums_share_the_type_namespace() {
     assert!(d.is_empty(), "{d:?}");
     assert!(res.enums.contains_key(&(1, "Color".to_string())));
 }
+
+#[test]
+fn functions_are_values_of_function_type() {
+    let (res, d) = checked(
+        "fun double(x: int): int { return x * 2; }\n\
+         fun id<T>(x: T): T { return x; }\n\
+         fun main(): int {\n\
+             const f: fun(int): int = double;\n\
+             const g: fun(int): int = id<int>;\n\
+             const fs: (fun(int): int)[] = [f, g];\n\
+             return fs[0](1) + g(2);\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    // Both names land in the value table; the instance is requested.
+    let mut targets: Vec<&(usize, String)> = res.fn_values.values().collect();
+    targets.sort();
+    assert_eq!(
+        targets,
+        [&(0, "double".to_string()), &(0, "id<int>".to_string())]
+    );
+    assert!(res.instances.contains_key(&(0, "id<int>".to_string())));
+    let names: Vec<String> = res
+        .expr_types
+        .values()
+        .filter(|t| matches!(t, Type::Fn(..)))
+        .map(Type::name)
+        .collect();
+    assert!(names.iter().all(|n| n == "fun(int): int"), "{names:?}");
+}
+
+#[test]
+fn function_values_infer_generic_parameters() {
+    let d = diags(
+        "fun map<T, U>(xs: T[], f: fun(T): U): U[] {\n\
+             var out: U[] = [];\n\
+             for x in xs { push(out, f(x)); }\n\
+             return out;\n\
+         }\n\
+         fun len1(s: string): int { return 1; }\n\
+         fun main(): int { const n: int[] = map([\"a\"], len1); return n[0]; }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn function_value_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "fun main(): int { const x: int = 1; return x(2); }",
+            "cannot call a value of type int",
+        ),
+        (
+            "fun f(x: int): int { return x; }\n\
+             fun main(): int { const g: fun(int): int = f; return g(); }",
+            "function value expects 1 argument(s), found 0",
+        ),
+        (
+            "fun f(x: int): int { return x; }\n\
+             fun main(): int { const g: fun(string): int = f; return 0; }",
+            "declared as fun(string): int but initialized with fun(int): int",
+        ),
+        (
+            "fun id<T>(x: T): T { return x; }\n\
+             fun main(): int { const g: fun(int): int = id; return 0; }",
+            "'id' is generic — write 'id<…>'",
+        ),
+        (
+            "fun helper(): int { const m: fun(): int = main; return 0; }\n\
+             fun main(): int { return 0; }",
+            "'main' cannot be used as a value",
+        ),
+        (
+            "fun f() {}\nfun main(): int { const g: fun(): int = f; return 0; }",
+            "declared as fun(): int but initialized with fun()",
+        ),
+    ];
+    for (src, want) in cases {
+        let d = diags(src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{want}: {d:?}");
+    }
+}
+
+#[test]
+fn locals_shadow_functions_in_calls() {
+    // A function-typed parameter named like a function calls the
+    // parameter; the old direct-call target is never recorded.
+    let (res, d) = checked(
+        "fun step(x: int): int { return x + 1; }\n\
+         fun twice(step: fun(int): int, x: int): int { return step(step(x)); }\n\
+         fun main(): int { return twice(step, 1); }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    assert_eq!(res.call_targets.len(), 1, "only main's call is direct");
+}
+
+#[test]
+fn function_literals_record_captures_in_first_use_order() {
+    let (res, d) = checked(
+        "fun main(): int {\n\
+             const k: int = 2;\n\
+             const name: string = \"n\";\n\
+             const f: fun(int): int = fun (x: int): int {\n\
+                 const g: fun(): string = fun (): string { return name; };\n\
+                 return x * k;\n\
+             };\n\
+             return f(1);\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    let mut lists: Vec<Vec<String>> = res
+        .captures
+        .values()
+        .map(|c| {
+            c.iter()
+                .map(|(n, t)| format!("{n}: {}", t.name()))
+                .collect()
+        })
+        .collect();
+    lists.sort();
+    // The inner literal's capture threads through the outer one.
+    assert_eq!(
+        lists,
+        [
+            vec!["name: string".to_string()],
+            vec!["name: string".to_string(), "k: int".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn function_literal_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "fun main(): int { var n: int = 0;\n\
+             const f: fun() = fun () { n = 1; }; return n; }",
+            "cannot assign to captured 'n' — a function literal holds a copy",
+        ),
+        (
+            "fun main(): int { const f: fun(int): int = fun (x: int): int {\n\
+             if x > 0 { return 1; } }; return 0; }",
+            "not all paths in this function literal return a value",
+        ),
+        (
+            "fun main(): int { const f: fun(int): int = fun (x: string): int {\n\
+             return 1; }; return 0; }",
+            "declared as fun(int): int but initialized with fun(string): int",
+        ),
+        (
+            "fun main(): int { while true {\n\
+             const f: fun() = fun () { break; }; } return 0; }",
+            "'break' outside of a loop",
+        ),
+    ];
+    for (src, want) in cases {
+        let d = diags(src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{want}: {d:?}");
+    }
+}
+
+#[test]
+fn captured_refstructs_stay_writable_through_their_handle() {
+    let d = diags(
+        "refstruct Counter { n: int }\n\
+         fun main(): int {\n\
+             const c: Counter = Counter { n: 0 };\n\
+             const bump: fun() = fun () { c.n = c.n + 1; };\n\
+             bump();\n\
+             return c.n;\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+// --- Methods (ADR 0040) ---
+
+#[test]
+fn method_calls_resolve_to_static_targets_and_instances() {
+    let (res, d) = checked(
+        "struct P { x: int }\n\
+         struct Pair<T, U> { a: T, b: U }\n\
+         impl P { fun get(self): int { return self.x; } }\n\
+         impl Pair<T, U> { fun first(self): T { return self.a; } }\n\
+         fun main(): int {\n\
+             const p: P = P { x: 1 };\n\
+             const q: Pair<string, int> = Pair<string, int> { a: \"s\", b: 2 };\n\
+             print(q.first());\n\
+             return p.get();\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    let mut targets: Vec<String> = res
+        .call_targets
+        .values()
+        .map(|(_, n)| crate::types::pretty(n))
+        .collect();
+    targets.sort();
+    assert_eq!(targets, ["P.get", "Pair.first<string, int>"]);
+    assert_eq!(res.instances.len(), 1);
+}
+
+#[test]
+fn value_self_is_a_copy_and_refstruct_self_aliases() {
+    let d = diags(
+        "refstruct C { n: int }\n\
+         impl C { fun bump(self) { self.n = self.n + 1; } }\n\
+         fun main(): int { const c: C = C { n: 0 }; c.bump(); return c.n; }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    let d = diags(
+        "struct P { x: int }\n\
+         impl P { fun set(self, v: int) { self.x = v; } }\n\
+         fun main(): int { return 0; }",
+    );
+    let e = d
+        .iter()
+        .find(|e| e.message.contains("cannot assign to const 'self'"))
+        .unwrap_or_else(|| panic!("{d:?}"));
+    assert!(
+        e.help
+            .as_deref()
+            .is_some_and(|h| h.contains("copy of the receiver"))
+    );
+}
+
+#[test]
+fn method_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "struct P { x: int }\n\
+             fun main(): int { const p: P = P { x: 1 }; return p.get(); }",
+            "type P has no method 'get'",
+        ),
+        (
+            "struct P { x: int }\n\
+             impl P { fun get(self): int { return 1; } }\n\
+             fun main(): int { const p: P = P { x: 1 }; return p.get(2); }",
+            "method 'get' expects 0 argument(s), found 1",
+        ),
+        (
+            "struct P { x: int }\n\
+             impl P { fun get(self): int { return 1; } }\n\
+             fun main(): int { const p: P? = null; return p.get(); }",
+            "cannot call 'get' on P? — it may be null",
+        ),
+        (
+            "fun main(): int { const n: int = 1; return n.get(); }",
+            "type int has no methods",
+        ),
+        (
+            "impl Q { fun get(self): int { return 1; } }",
+            "cannot implement 'Q'",
+        ),
+        (
+            "struct P { x: int }\n\
+             impl P<T> { fun get(self): int { return 1; } }",
+            "'P' has 0 type parameter(s), but the impl names 1",
+        ),
+        (
+            "struct P { x: int }\n\
+             impl P { fun get(self): int { return 1; } }\n\
+             impl P { fun get(self): int { return 2; } }",
+            "method 'get' is already defined on 'P'",
+        ),
+    ];
+    for (src, want) in cases {
+        let d = diags(src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{want}: {d:?}");
+    }
+}
+
+#[test]
+fn function_valued_fields_still_need_grouping() {
+    let d = diags(
+        "struct P { run: fun(int): int }\n\
+         fun main(): int {\n\
+             const p: P = P { run: fun (x: int): int { return x; } };\n\
+             return p.run(1);\n\
+         }",
+    );
+    let e = d
+        .iter()
+        .find(|e| e.message.contains("type P has no method 'run'"))
+        .unwrap_or_else(|| panic!("{d:?}"));
+    assert!(e.help.as_deref().is_some_and(|h| h.contains("(p.run)(…)")));
+}
+
+// --- Interfaces and bounds (ADR 0041) ---
+
+#[test]
+fn bounded_calls_instantiate_when_the_argument_satisfies() {
+    let (res, d) = checked(
+        "interface Ordered { fun less(self, other: Self): bool; }\n\
+         struct M { c: int }\n\
+         struct Box<T> { v: T }\n\
+         impl M { fun less(self, o: M): bool { return self.c < o.c; } }\n\
+         impl Box<T> { fun less(self, o: Box<T>): bool { return false; } }\n\
+         fun max<T: Ordered>(a: T, b: T): T { if a.less(b) { return b; } return a; }\n\
+         fun main(): int {\n\
+             const m: M = max(M { c: 1 }, M { c: 2 });\n\
+             const b: Box<int> = max((Box<int> { v: 1 }), (Box<int> { v: 2 }));\n\
+             return m.c;\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    let mut names: Vec<String> = res
+        .instances
+        .keys()
+        .map(|(_, n)| crate::types::pretty(n))
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Box.less<int>", "max<Box<int>>", "max<M>"]);
+}
+
+#[test]
+fn unsatisfied_bounds_are_reported_at_the_call_site() {
+    let cases = [
+        (
+            "fun max<T: Ordered>(a: T, b: T): T { return a; }\n\
+             fun main(): int { return max(1, 2); }",
+            "type int does not satisfy 'T: Ordered' required by 'max'",
+            "type int has no methods",
+        ),
+        (
+            "struct B { n: int }\n\
+             impl B { fun less(self, o: int): bool { return true; } }\n\
+             fun max<T: Ordered>(a: T, b: T): T { return a; }\n\
+             fun main(): int { const b: B = max(B { n: 1 }, B { n: 2 }); return 0; }",
+            "type B does not satisfy 'T: Ordered'",
+            "its 'less' is fun(int): bool, but 'Ordered' requires fun(B): bool",
+        ),
+        (
+            "struct B { n: int }\n\
+             fun max<T: Ordered>(a: T, b: T): T { return a; }\n\
+             fun main(): int { const f: fun(B, B): B = max<B>; return 0; }",
+            "type B does not satisfy 'T: Ordered'",
+            "type B has no method 'less'",
+        ),
+    ];
+    for (body, want, help) in cases {
+        let src = format!("interface Ordered {{ fun less(self, other: Self): bool; }}\n{body}");
+        let d = diags(&src);
+        let e = d
+            .iter()
+            .find(|e| e.message.contains(want))
+            .unwrap_or_else(|| panic!("{want}: {d:?}"));
+        assert!(e.help.as_deref().is_some_and(|h| h.contains(help)), "{e:?}");
+        // The instance is never checked, so nothing leaks from inside it.
+        assert_eq!(d.len(), 1, "{d:?}");
+    }
+}
+
+#[test]
+fn interface_declarations_and_bound_names_are_checked() {
+    let cases = [
+        (
+            "interface I { fun f(self); }\n\
+             interface I { fun g(self); }",
+            "interface 'I' is already defined",
+        ),
+        (
+            "interface I { fun f(self); fun f(self): int; }",
+            "'I' already requires method 'f'",
+        ),
+        (
+            "interface I { fun f(self, x: Missing); }",
+            "unknown type 'Missing'",
+        ),
+        ("fun f<T: Nope>(a: T) {}", "unknown interface 'Nope'"),
+        (
+            "struct P { x: int }\n\
+             fun f<T: P>(a: T) {}",
+            "'P' is not an interface",
+        ),
+        (
+            "interface I { fun f(self); }\n\
+             fun g(x: I) {}",
+            "interface 'I' is not a type",
+        ),
+    ];
+    for (src, want) in cases {
+        let d = diags(src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{want}: {d:?}");
+    }
+}
+
+#[test]
+fn interfaces_import_like_types_and_bind_self_per_caller() {
+    let (_, d) = multi(&[
+        (
+            "main.ys",
+            "import { Ordered, max } from \"./lib\";\n\
+             struct M { c: int }\n\
+             impl M { fun less(self, o: M): bool { return self.c < o.c; } }\n\
+             fun pick<T: Ordered>(a: T, b: T): T { return max(a, b); }\n\
+             fun main(): int { return pick(M { c: 1 }, M { c: 2 }).c; }",
+        ),
+        (
+            "lib.ys",
+            "export interface Ordered { fun less(self, other: Self): bool; }\n\
+             export fun max<T: Ordered>(a: T, b: T): T { if a.less(b) { return b; } return a; }",
+        ),
+    ]);
+    assert!(d.is_empty(), "{d:?}");
+}
+
+// --- Maps (ADR 0042) ---
+
+#[test]
+fn map_literals_infer_and_lookups_read_optional() {
+    let d = diags(
+        "fun main(): int {\n\
+             const m: map<string, int> = { \"a\": 1, \"b\": 2 };\n\
+             const n: map<int, string?> = { 1: null };\n\
+             const e: map<int, int> = {};\n\
+             const hit: int? = m[\"a\"];\n\
+             const s: string? = n[1];\n\
+             insert(e, 1, len(m));\n\
+             const gone: bool = remove(m, \"a\") && has(e, 1);\n\
+             for [k, v] in m { print(k); print(v); }\n\
+             return hit ?? 0;\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn map_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "print({ 1: 1, \"b\": 2 });",
+            "map keys must share one type: expected int, found string",
+        ),
+        (
+            "const m: map<int, int> = { 1: \"x\" };",
+            "map value: expected int, found string",
+        ),
+        (
+            "const m: map<int, int> = { null: 1 };",
+            "map key: expected int, found null",
+        ),
+        (
+            "const m: map<int!, int> = {};",
+            "int! cannot be a map key — it contains an error union",
+        ),
+        (
+            "const m: map<int> = {};",
+            "'map' takes 2 type arguments, found 1",
+        ),
+        (
+            "const m: map<int, int> = {}; const v: int = m[1];",
+            "'v' is declared as int but initialized with int?",
+        ),
+        (
+            "const m: map<int, int> = {}; insert(m, 1);",
+            "'insert' expects 3 arguments, found 2",
+        ),
+        (
+            "insert(true, 1, 1);",
+            "'insert' expects an array or a map, found bool",
+        ),
+        (
+            "const m: map<int, int> = {}; for v in m { }",
+            "iterating a map binds a key and a value",
+        ),
+        ("for [k, v] in {} { }", "cannot infer a type for"),
+    ];
+    for (body, want) in cases {
+        let src = format!("fun main(): int {{ {body} return 0; }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{body}: {d:?}");
+    }
+}
+
+#[test]
+fn map_lookups_are_not_assignable() {
+    let d = diags(
+        "fun main(): int {\n\
+             const m: map<string, int> = {};\n\
+             m[\"a\"] = 1;\n\
+             return 0;\n\
+         }",
+    );
+    let e = d
+        .iter()
+        .find(|e| e.message == "cannot assign to a map lookup")
+        .unwrap_or_else(|| panic!("{d:?}"));
+    assert!(
+        e.help
+            .as_deref()
+            .is_some_and(|h| h.contains("insert(m, k, v)"))
+    );
+}
+
+// --- Tuples (ADR 0043) ---
+
+#[test]
+fn tuples_type_positionally_and_destructure_by_arity() {
+    let d = diags(
+        "fun divmod(a: int, b: int): (int, int) { return (a / b, a % b); }\n\
+         fun main(): int {\n\
+             const (q, _) = divmod(7, 2);\n\
+             var t: (int, string?) = (q, null);\n\
+             t.1 = \"x\";\n\
+             const s: string? = t.1;\n\
+             const u: (int, string?)? = null;\n\
+             print(t == (1, \"x\"));\n\
+             print(s);\n\
+             print(u);\n\
+             return t.0;\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn tuple_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "const t: (int, string) = (1, 2);",
+            "tuple element: expected string, found int",
+        ),
+        (
+            "const t: (int, int) = (1, 2, 3);",
+            "'t' is declared as (int, int) but initialized with (int, int, int)",
+        ),
+        (
+            "const t: (int, int) = (1, 2); print(t.2);",
+            "tuple (int, int) has no element '2'",
+        ),
+        (
+            "const (a, b) = 5;",
+            "cannot destructure int: expected a tuple of 2 elements",
+        ),
+        ("const (a, a) = (1, 2);", "'a' is bound twice"),
+        ("const (a, b) = ([], 1);", "cannot infer a type for 'a'"),
+        (
+            "print((1, null));",
+            "tuple element type cannot be inferred from 'null'",
+        ),
+        (
+            "const t: (int, int) = (1, 2); t.0 = 3;",
+            "cannot assign to const 't'",
+        ),
+        (
+            "const (a, b) = (1, 2); a = 3;",
+            "cannot assign to const 'a'",
+        ),
+    ];
+    for (body, want) in cases {
+        let src = format!("fun main(): int {{ {body} return 0; }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{body}: {d:?}");
+    }
+}
+
+// --- Match expressions (ADR 0044) ---
+
+#[test]
+fn match_expressions_type_their_arms() {
+    let d = diags(
+        "enum S { A(int), B(float), C }\n\
+         fun main(): int {\n\
+             const s: S = S.C();\n\
+             const f: float = match s { A(n) => float(n), B(x) => x, C => 0.0 };\n\
+             const o: string? = match s { C => null, else => \"s\" };\n\
+             const t: (int, string?) = match s { A(n) => (n, null), else => (0, \"x\") };\n\
+             print(f);\n\
+             print(o);\n\
+             print(t);\n\
+             print(match s { C => null, else => 1 });\n\
+             return match s { A(n) => n, else => 0 } + 1;\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn match_expression_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "const v: int = match s { A(n) => n };",
+            "match does not cover variant(s) 'B'",
+        ),
+        (
+            "const v: int = match s { A(n) => n, B => 0, else => 1 };",
+            "'else' is unreachable",
+        ),
+        (
+            "const v: int = match s { A(n) => n, A(n) => 1, else => 0 };",
+            "duplicate arm for variant 'A' — this arm is unreachable",
+        ),
+        (
+            "const v: int = match s { A(n) => n, B => \"b\" };",
+            "match arm: expected int, found string",
+        ),
+        (
+            "print(match s { A(n) => n, B => \"b\" });",
+            "match arms must share one type: expected int, found string",
+        ),
+        (
+            "print(match s { A(_) => null, B => null });",
+            "match type cannot be inferred from 'null'",
+        ),
+        (
+            "const v: int = match s { A(n) => n, B => n };",
+            "undefined variable 'n'",
+        ),
+    ];
+    for (body, want) in cases {
+        let src = format!(
+            "enum S {{ A(int), B }}\nfun main(): int {{ const s: S = S.B(); {body} return 0; }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{body}: {d:?}");
+    }
+}
+
+// --- Nested, literal, and guarded patterns (ADR 0045) ---
+
+#[test]
+fn nested_patterns_and_guards_check() {
+    let d = diags(
+        "enum Shape { Circle(float), Dot }\n\
+         enum Opt<T> { Some(T), None }\n\
+         enum Box { V(int?), S(string), B(bool) }\n\
+         fun f(o: Opt<Shape>): float {\n\
+             return match o {\n\
+                 Some(Circle(r)) if r > 1.0 => r,\n\
+                 Some(Circle(_)) => 1.0,\n\
+                 Some(Dot()) => 0.0,\n\
+                 None => -1.0,\n\
+             };\n\
+         }\n\
+         fun g(b: Box): int {\n\
+             match b {\n\
+                 V(x) if x != null { return x + 1; }\n\
+                 V(_) { return 0; }\n\
+                 S(\"one\") { return 1; }\n\
+                 S(_) { return 2; }\n\
+                 B(true) { return 3; }\n\
+                 B(false) { return 4; }\n\
+             }\n\
+         }\n\
+         fun main(): int { print(f(Opt<Shape>.None())); return g(Box.V(1)); }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn pattern_coverage_and_misuse_are_diagnosed() {
+    let cases = [
+        (
+            "match t { Num(0) { } Word(_) { } Pair(_, _) { } }",
+            "match does not cover variant(s) 'Flag'",
+        ),
+        (
+            "match t { Num(_) { } Word(_) { } Flag(true) { } Pair(_, _) { } }",
+            "match does not cover 'Flag(false)'",
+        ),
+        (
+            "match t { Num(_) { } Word(_) { } Flag(_) { } \
+             Pair(Circle(_), _) { } Pair(Square(_), _) { } }",
+            "match does not cover 'Pair(Dot(), _)'",
+        ),
+        (
+            "match t { Num(n) if n > 0 { } Word(_) { } Flag(_) { } Pair(_, _) { } }",
+            "match does not cover variant(s) 'Num'",
+        ),
+        (
+            "match t { Num(_) { } Num(1) { } else { } }",
+            "this arm is unreachable",
+        ),
+        (
+            "match t { Num(_) { } Word(_) { } Flag(true) { } Flag(false) { } \
+             Pair(_, _) { } else { } }",
+            "'else' is unreachable",
+        ),
+        (
+            "match t { Num(\"a\") { } else { } }",
+            "pattern: expected int, found string",
+        ),
+        (
+            "match t { Pair(Dot, _) { } else { } }",
+            "'Dot' binds this payload instead of matching the variant",
+        ),
+        ("match t { Pair(x, x) { } else { } }", "'x' is bound twice"),
+        (
+            "match t { Flag(b) if 1 { } else { } }",
+            "match guard condition must be bool, found int",
+        ),
+        (
+            "match t { Num(Circle(r)) { } else { } }",
+            "variant pattern 'Circle' needs an enum, found int",
+        ),
+        (
+            "match t { Pair(1, _) { } else { } }",
+            "literal patterns match int, string, bool, or error, found Shape",
+        ),
+    ];
+    for (body, want) in cases {
+        let src = format!(
+            "enum Shape {{ Circle(float), Square(float), Dot }}\n\
+             enum Tok {{ Num(int), Word(string), Flag(bool), Pair(Shape, int) }}\n\
+             fun main(): int {{ const t: Tok = Tok.Num(1); {body} return 0; }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{body}: {d:?}");
+    }
+}
+
+// --- Int, string, and error-code match (ADR 0046) ---
+
+#[test]
+fn literal_scrutinees_check() {
+    let d = diags(
+        "error NotFound;\n\
+         error Timeout;\n\
+         fun f(n: int, s: string, e: error): int {\n\
+             match n { 0 { return 1; } -1 { return 2; } else { } }\n\
+             const k: int = match s { \"a\" => 1, \"b\" => 2, else => 3 };\n\
+             return match e { error.NotFound => k, error.Timeout => 408, else => 500 };\n\
+         }\n\
+         fun main(): int { return f(0, \"a\", error.NotFound); }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn literal_scrutinee_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "match n { 1 { } 2 { } 1 { } else { } }",
+            "duplicate arm for '1' — this arm is unreachable",
+        ),
+        (
+            "match s { \"a\" { } \"a\" { } else { } }",
+            "duplicate arm for '\"a\"' — this arm is unreachable",
+        ),
+        (
+            "match e { error.NotFound { } error.NotFound { } else { } }",
+            "duplicate arm for 'error.NotFound' — this arm is unreachable",
+        ),
+        (
+            "match n { 1 { } 2 { } }",
+            "match on int needs 'else' — literal arms cannot cover every value",
+        ),
+        (
+            "const x: int = match s { \"a\" => 1 };",
+            "match on string needs 'else'",
+        ),
+        (
+            "match n { \"a\" { } else { } }",
+            "pattern: expected int, found string",
+        ),
+        (
+            "match e { 1 { } else { } }",
+            "pattern: expected error, found int",
+        ),
+        (
+            "match n { A { } else { } }",
+            "variant pattern 'A' needs an enum, found int",
+        ),
+        (
+            "match e { error.Nope { } else { } }",
+            "unknown error 'Nope'",
+        ),
+    ];
+    for (body, want) in cases {
+        let src = format!(
+            "error NotFound;\n\
+             fun f(n: int, s: string, e: error) {{ {body} }}\n\
+             fun main(): int {{ return 0; }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message.contains(want)), "{body}: {d:?}");
+        assert_eq!(d.len(), 1, "{body}: {d:?}");
+    }
+}
+
+// --- Module consts (ADR 0047) ---
+
+#[test]
+fn module_consts_are_computed_at_compile_time() {
+    let (res, d) = checked(
+        "const LIMIT: int = KB * 4;\n\
+         const KB: int = square(32);\n\
+         const NAME: string = \"ys\";\n\
+         fun square(n: int): int { return n * n; }\n\
+         fun main(): int { return LIMIT; }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    assert_eq!(
+        res.consts.get(&(0, "LIMIT".to_string())),
+        Some(&ConstValue::Int(4096))
+    );
+    assert_eq!(
+        res.consts.get(&(0, "NAME".to_string())),
+        Some(&ConstValue::Str(b"ys".to_vec()))
+    );
+}
+
+#[test]
+fn module_const_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "const A: int[] = [1];",
+            "module const 'A' must be int, float, bool, or string, found int[]",
+        ),
+        (
+            "const A: int = \"x\";",
+            "'A' is declared as int but initialized with string",
+        ),
+        (
+            "const A: int = 1; fun g() { A = 2; }",
+            "cannot assign to 'A': module consts are immutable",
+        ),
+        (
+            "const A: int = 1; fun A(): int { return 1; }",
+            "function 'A' is already defined",
+        ),
+        (
+            "const A: int = B; const B: int = A + 1;",
+            "const 'A' depends on its own value",
+        ),
+        ("const A: int = 1 / 0;", "division by zero"),
+        (
+            "fun mk(): int { const a: int[] = [1]; return 1; } const A: int = mk();",
+            "a const initializer cannot allocate",
+        ),
+        (
+            "fun p(): int { print(1); return 1; } const A: int = p();",
+            "a const initializer cannot do I/O ('print')",
+        ),
+    ];
+    for (items, want) in cases {
+        let d = diags(&format!("{items}\nfun main(): int {{ return 0; }}"));
+        assert!(d.iter().any(|e| e.message.contains(want)), "{items}: {d:?}");
+        assert_eq!(d.len(), 1, "{items}: {d:?}");
+    }
+}
+
+#[test]
+fn runaway_const_initializer_is_diagnosed() {
+    // The step budget names the const whose initializer ran out — here
+    // B, read from A's.
+    let d = diags(
+        "fun spin(): int { while true {} return 0; }\n\
+         const A: int = B + 1;\n\
+         const B: int = spin();\n\
+         fun main(): int { return A; }",
+    );
+    assert_eq!(d.len(), 1, "{d:?}");
+    assert!(
+        d[0].message
+            .starts_with("const 'B' did not finish within 10000000 steps"),
+        "{d:?}"
+    );
+    assert_eq!(
+        d[0].help.as_deref(),
+        Some("a const initializer must terminate — loop in 'main' instead")
+    );
+}
+
+#[test]
+fn module_consts_import_like_functions() {
+    let (res, d) = multi(&[
+        (
+            "main.ys",
+            "import { SCALE } from \"./lib\";\n\
+             const TWICE: float = SCALE * 2.0;\n\
+             fun main(): int { return int(TWICE); }",
+        ),
+        (
+            "lib.ys",
+            "export const SCALE: float = 2.5;\nconst HIDDEN: int = 1;",
+        ),
+    ]);
+    assert!(d.is_empty(), "{d:?}");
+    assert_eq!(
+        res.consts.get(&(0, "TWICE".to_string())),
+        Some(&ConstValue::Float(5.0))
+    );
+
+    let (_, d) = multi(&[
+        (
+            "main.ys",
+            "import { HIDDEN } from \"./lib\"; fun main(): int { return 1; }",
+        ),
+        ("lib.ys", "const HIDDEN: int = 1;"),
+    ]);
+    assert!(
+        d.iter().any(|e| e
+            .message
+            .contains("'HIDDEN' exists in 'lib.ys' but is not exported")),
+        "{d:?}"
+    );
+}
+
+// --- Module vars (ADR 0048) ---
+
+#[test]
+fn module_vars_are_shared_mutable_state() {
+    let (res, d) = checked(
+        "struct P { x: int }\n\
+         var hits: int = 0;\n\
+         var origin: P = P { x: 1 };\n\
+         var log: string[] = [];\n\
+         var step: fun(int): int = bump;\n\
+         fun bump(n: int): int { hits = hits + n; origin.x = hits; return hits; }\n\
+         fun main(): int { push(log, \"go\"); return step(2) + origin.x + len(log); }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    assert_eq!(res.globals.get(&(0, "hits".to_string())), Some(&Type::Int));
+}
+
+#[test]
+fn module_var_misuse_is_diagnosed() {
+    let cases = [
+        (
+            "var a: int = \"x\";",
+            "'a' is declared as int but initialized with string",
+        ),
+        (
+            "var a: int = 1; var a: int = 2;",
+            "var 'a' is already defined",
+        ),
+        (
+            "var a: int = 1; fun a(): int { return 1; }",
+            "function 'a' is already defined",
+        ),
+        (
+            "var a: int = 1; const B: int = a;",
+            "a const initializer cannot use module var 'a'",
+        ),
+    ];
+    for (items, want) in cases {
+        let d = diags(&format!("{items}\nfun main(): int {{ return 0; }}"));
+        assert!(d.iter().any(|e| e.message.contains(want)), "{items}: {d:?}");
+        assert_eq!(d.len(), 1, "{items}: {d:?}");
+    }
+}
+
+#[test]
+fn module_vars_cannot_be_read_before_they_are_initialized() {
+    let cases = [
+        (
+            "var a: int = b + 1; var b: int = 2;",
+            "b",
+            "but 'b' is initialized after it",
+        ),
+        ("var a: int = a + 1;", "a", "reaches this use of itself"),
+        // Through calls, and through a function named as a value.
+        (
+            "fun get(): int { return b; } var a: int = get(); var b: int = 2;",
+            "b",
+            "the initializer of 'a' reaches this use",
+        ),
+        (
+            "fun get(): int { return b; } var a: fun(): int = get; var b: int = 2;",
+            "b",
+            "the initializer of 'a' reaches this use",
+        ),
+    ];
+    for (items, var, help) in cases {
+        let d = diags(&format!("{items}\nfun main(): int {{ return 0; }}"));
+        let want = format!("module var '{var}' is used before it is initialized");
+        assert!(d.iter().any(|e| e.message == want), "{items}: {d:?}");
+        assert!(
+            d[0].help.as_deref().is_some_and(|h| h.contains(help)),
+            "{items}: {d:?}"
+        );
+        assert_eq!(d.len(), 1, "{items}: {d:?}");
+    }
+    // Reads in declaration order, and reads from functions only `main`
+    // reaches, are fine.
+    let d = diags(
+        "var b: int = 2;\n\
+         fun get(): int { return b + late; }\n\
+         var a: int = b * 2;\n\
+         var late: int = 1;\n\
+         fun main(): int { return get() + a; }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+}
+
+#[test]
+fn module_vars_initialize_in_dependency_order() {
+    // `lib` initializes before `main`, so `main`'s vars may read its
+    // vars; nothing in `lib` can name `main`'s.
+    let (_, d) = multi(&[
+        (
+            "main.ys",
+            "import { base, next } from \"./lib\";\n\
+             var mine: int = base + next();\n\
+             fun main(): int { base = 5; return mine; }",
+        ),
+        (
+            "lib.ys",
+            "export var base: int = 40;\n\
+             export fun next(): int { base = base + 1; return base; }",
+        ),
+    ]);
+    assert!(d.is_empty(), "{d:?}");
+}
+
+// --- Bitwise operators (ADR 0049) ---
+
+#[test]
+fn bitwise_operators_take_ints_only() {
+    let d = diags("fun f(a: int, b: int): int { return (a & b) | (a ^ ~b) << 2 >> 1 >>> a; }");
+    assert!(d.is_empty(), "{d:?}");
+    for (src, message) in [
+        (
+            "fun f(a: bool): bool { return a & a; }",
+            "cannot apply '&' to bool and bool",
+        ),
+        (
+            "fun f(a: float): float { return a << 1.0; }",
+            "cannot apply '<<' to float and float",
+        ),
+        (
+            "fun f(a: int): bool { return ~(a > 0); }",
+            "cannot apply '~' to bool",
+        ),
+        // C precedence: `&` binds looser than `==`.
+        (
+            "fun f(a: int): bool { return a & 1 == 1; }",
+            "cannot apply '&' to int and bool",
+        ),
+    ] {
+        let d = diags(src);
+        assert!(
+            d.iter().any(|e| e.message.contains(message)),
+            "{src}: {d:?}"
+        );
+    }
+}
+
+// --- Compound assignment (ADR 0050) ---
+
+#[test]
+fn compound_assignment_types_as_its_operator() {
+    let d = diags(
+        "struct P { n: int, s: string, o: int? }
+fun f(ps: P[], c: int?): int {
+    var i: int = 1;
+    i += 2; i -= 1; i *= 3; i /= 2; i %= 5; i <<= 1; i >>= 1; i >>>= 1; i &= 7; i |= 8; i ^= 1;
+    i++; i--;
+    var x: float = 1.0;
+    x *= 2.5;
+    ps[i].n += 1;
+    ps[0].s += \"!\";
+    ps[0].o ??= 4;
+    var d: int? = c;
+    d ??= null;
+    if d != null { d += 1; return d; }
+    return i;
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "var s: string = \"a\"; s += 1;",
+            "cannot apply '+=' to string and int",
+        ),
+        ("var x: float = 1.0; x++;", "cannot apply '++' to float"),
+        ("var s: string = \"a\"; s--;", "cannot apply '--' to string"),
+        (
+            "var b: bool = true; b &= false;",
+            "cannot apply '&=' to bool and bool",
+        ),
+        (
+            "var n: int = 0; n ??= 1;",
+            "cannot apply '??=' to int and int",
+        ),
+        (
+            "var c: int? = null; c += 1;",
+            "cannot apply '+=' to int? and int",
+        ),
+        ("const k: int = 0; k += 1;", "cannot assign to const 'k'"),
+        (
+            "var m: map<string, int> = {}; m[\"a\"] += 1;",
+            "cannot assign to a map lookup",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+#[test]
+fn compound_assignment_keeps_the_target_narrowed() {
+    // The write is the operator's result, never null — unlike `=`.
+    let src =
+        "fun f(v: int?): int { var c: int? = v; if c != null { c STEP; return c * 2; } return 0; }";
+    let d = diags(&src.replace("STEP", "+= 1"));
+    assert!(d.is_empty(), "{d:?}");
+    let d = diags(&src.replace("STEP", "= null"));
+    assert!(!d.is_empty());
+}
+
+// --- Ranges and slices (ADR 0051) ---
+
+#[test]
+fn ranges_count_with_int_bounds() {
+    let d = diags(
+        "fun f(xs: string[], n: int, s: int): string[] {
+    var t: int = 0;
+    for i in 0..n step s { t += i; }
+    for i in n..=0 step -1 { t += i; }
+    const ys: string[] = xs[1..n];
+    return ys[..t];
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "for i in 0..1.5 { }",
+            "range bound must be int, found float",
+        ),
+        (
+            "for i in 0..3 step true { }",
+            "range step must be int, found bool",
+        ),
+        ("for i in 0..3 step 0 { }", "range step must not be zero"),
+        ("for [k, i] in 0..3 { }", "a range binds only its counter"),
+        ("for i in 0..3 { i = 2; }", "cannot assign to const 'i'"),
+        (
+            "const xs: int[] = [1]; const ys: int[] = xs[0..1 step 2];",
+            "a slice takes no step",
+        ),
+        ("const b: bool = true; print(b[0..1]);", "cannot slice bool"),
+        (
+            "var m: map<string, int> = {}; print(m[\"a\"..]);",
+            "cannot slice map<string, int>",
+        ),
+        (
+            "var xs: int[] = [1, 2]; xs[0..1][0] = 5;",
+            "cannot assign into a slice",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- Sized integers (ADR 0052) ---
+
+#[test]
+fn sized_integers_never_widen_implicitly() {
+    let d = diags(
+        "fun f(a: u8, b: i32): u64 {\n\
+         var xs: u8[] = [1, 255, a + 1];\n\
+         push(xs, 7);\n\
+         const c: i8 = -128;\n\
+         const n: u32? = 4000000000;\n\
+         return u64(int(a) + int(b) + int(c)) & u64(-1);\n\
+         }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (src, message) in [
+        ("fun f(a: u8): int { return a; }", "found u8"),
+        (
+            "fun f(a: u8, b: u16): int { return int(a + b); }",
+            "cannot apply '+' to u8 and u16",
+        ),
+        ("fun f(): u8 { return 256; }", "literal 256 does not fit u8"),
+        (
+            "fun f(): i8 { return -129; }",
+            "literal -129 does not fit i8",
+        ),
+        (
+            "fun f(): int { var xs: u16[] = [1, 70000]; return 0; }",
+            "literal 70000 does not fit u16",
+        ),
+        (
+            "fun f(x: float): u32 { return u32(x); }",
+            "u32() expects an integer, found float",
+        ),
+        (
+            "fun f(x: i16): i16 { return i16(x); }",
+            "i16() expects an integer, found i16",
+        ),
+    ] {
+        let d = diags(src);
+        assert!(
+            d.iter().any(|e| e.message.contains(message)),
+            "{src}: {d:?}"
+        );
+    }
+}
+
+// --- Strings as bytes (ADR 0053) ---
+
+#[test]
+fn strings_read_as_bytes() {
+    let d = diags(
+        "fun f(s: string, i: int): u32 {
+    const b: byte = s[i];
+    const head: string = s[..i] + s[i..=i];
+    var n: u8 = 0;
+    for [k, c] in s { if c == b && k > 0 { n += 1; } }
+    const points: u32[] = chars(head);
+    return points[len(s) - 1] + u32(n);
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "var s: string = \"ab\"; s[0] = 1;",
+            "cannot assign into a string",
+        ),
+        (
+            "const s: string = \"ab\"; const n: int = s[0];",
+            "'n' is declared as int but initialized with u8",
+        ),
+        (
+            "const s: string = \"ab\"; print(s[1.5]);",
+            "index must be int, found float",
+        ),
+        ("print(chars(5));", "'chars' expects string, found int"),
+        (
+            "print(len(true));",
+            "'len' expects an array, a map, or a string, found bool",
+        ),
+        (
+            "for x in 5 { }",
+            "can only iterate over arrays, maps, and strings, found int",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- String library (ADR 0054) ---
+
+#[test]
+fn string_library_signatures() {
+    let d = diags(
+        "fun f(s: string): float {
+    const at: int? = find(s, \",\");
+    const parts: string[] = split(trim(s), \",\");
+    const back: string = join(parts, \";\") + replace(s, \"a\", \"b\");
+    const loud: bool = startsWith(toUpper(s), \"A\") || endsWith(toLower(s), \"z\");
+    const n: int? = parseInt(back);
+    if loud && at != null && n != null { return 0.0; }
+    return parseFloat(s) ?? 1.5;
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        ("print(find(\"a\"));", "'find' expects 2 arguments, found 1"),
+        ("print(trim(5));", "'trim' expects string, found int"),
+        (
+            "const n: int = parseInt(\"1\");",
+            "'n' is declared as int but initialized with int?",
+        ),
+        (
+            "print(join([1, 2], \",\"));",
+            "'join' expects string[], found int[]",
+        ),
+        (
+            "const f: float? = parseInt(\"1\");",
+            "'f' is declared as float? but initialized with int?",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- Array library (ADR 0055) ---
+
+#[test]
+fn array_library_signatures() {
+    let d = diags(
+        "struct P { name: string, age: int }
+fun f(xs: int[], ps: P[], m: map<string, int>, bs: u8[], qs: u64[]): int {
+    const last: int? = pop(xs);
+    insert(xs, 0, 7);
+    insert(m, \"k\", 1);
+    const first: int = removeAt(xs, 0);
+    const mid: int[] = slice(xs, 1, 2);
+    sort(xs);
+    sort(bs);
+    sort(qs);
+    sort(ps, fun (p: P): i16 { return i16(p.age); });
+    sort(ps, fun (p: P): string { return p.name; });
+    reverse(ps);
+    clear(mid);
+    const at: int? = indexOf(ps, P { name: \"a\", age: 1 });
+    if contains(xs, 3) && at != null { return first; }
+    return last ?? len(mid);
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        ("print(pop(5));", "'pop' expects an array, found int"),
+        ("print(pop([1], 2));", "'pop' expects 1 argument, found 2"),
+        (
+            "sort([1], 2, 3);",
+            "'sort' expects 1 or 2 arguments, found 3",
+        ),
+        (
+            "insert([1], 0, true);",
+            "'insert' into int[]: expected int, found bool",
+        ),
+        (
+            "print(removeAt([1], 0.5));",
+            "'removeAt' index must be int, found float",
+        ),
+        (
+            "sort([true]);",
+            "'sort' orders integer, float, and string elements, found bool",
+        ),
+        (
+            "sort([1], fun (x: int): bool { return x > 0; });",
+            "'sort' key must be fun(int) returning an integer, float, or string, found fun(int): bool",
+        ),
+        (
+            "const a: int = pop([1]);",
+            "'a' is declared as int but initialized with int?",
+        ),
+        (
+            "print(indexOf([1], \"a\"));",
+            "'indexOf' in int[]: expected int, found string",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- Fixed arrays (ADR 0056) ---
+
+#[test]
+fn fixed_array_signatures() {
+    let d = diags(
+        "struct G { cells: int[4], names: string?[2] }
+fun f(m: int[3][2], g: G): int[3] {
+    var row: int[3] = m[1];
+    row[0] = len(m) + len(row);
+    var h: G = g;
+    h.cells[3] += 1;
+    h.names[0] ??= \"x\";
+    const z: float[8] = [0.5; 8];
+    const o: int?[3] = [null; 3];
+    var keys: map<int[2], string> = {};
+    insert(keys, [1, 2], \"a\");
+    var rows: int[3][] = [];
+    push(rows, [1, 2, 3]);
+    for x in z { print(x); }
+    if row == [1, 2, 3] || h.cells != [0; 4] { return [int(z[0]); 3]; }
+    return row;
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "const a: int[3] = [1, 2];",
+            "expected 3 elements for int[3], found 2",
+        ),
+        (
+            "const a: int[3] = [1, 2, 3]; a[0] = 4;",
+            "cannot assign to const 'a'",
+        ),
+        (
+            "var a: int[3] = [0; 3]; push(a, 4);",
+            "'push' expects an array, found int[3]",
+        ),
+        (
+            "const a: int[3] = [0; 3]; const b: int[2] = a;",
+            "'b' is declared as int[2] but initialized with int[3]",
+        ),
+        (
+            "const a: int[3] = [0; 3]; const b: int[] = a;",
+            "'b' is declared as int[] but initialized with int[3]",
+        ),
+        (
+            "print([null; 3]);",
+            "array element type cannot be inferred from 'null'",
+        ),
+        (
+            "const a: int[3] = [0; 3]; print(a[true]);",
+            "index must be int, found bool",
+        ),
+    ] {
+        let src = format!("fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+#[test]
+fn fixed_arrays_are_bounded_by_total_size() {
+    // Elements times element words, through rows, structs, and tuples —
+    // each length alone is within the parser's cap.
+    let d = diags(
+        "struct P { x: int, y: int }
+refstruct R { v: int }
+fun f(): int {
+    const a: int[64][64] = [[0; 64]; 64];
+    const b: P[2048] = [P { x: 0, y: 0 }; 2048];
+    const c: R?[4096] = [null; 4096];
+    return len(a) + len(b) + len(c);
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (src, message) in [
+        (
+            "fun f() { var big: int[4096][512] = [[0; 4096]; 512]; }",
+            "int[4096][512] is too large for a fixed array: 2097152 words, the limit is 4096",
+        ),
+        (
+            "fun f() { print([[1; 4096]; 2]); }",
+            "int[4096][2] is too large for a fixed array: 8192 words, the limit is 4096",
+        ),
+        (
+            "fun f(ps: (int, string)[2048]) { }",
+            "(int, string)[2048] is too large for a fixed array: 6144 words, the limit is 4096",
+        ),
+        // The element's struct is declared after its use.
+        (
+            "struct Grid { cells: Cell[4096] }\nstruct Cell { v: int, w: int }",
+            "Cell[4096] is too large for a fixed array: 8192 words, the limit is 4096",
+        ),
+        (
+            "fun f(o: int?[4096]) { }",
+            "int?[4096] is too large for a fixed array: 8192 words, the limit is 4096",
+        ),
+    ] {
+        let d = diags(src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- Destructuring (ADR 0057) ---
+
+#[test]
+fn destructuring_and_let_else() {
+    let d = diags(
+        "struct P { x: int, y: int? }
+refstruct N { name: string }
+enum Shape { Circle(float), Rect(int, int) }
+enum One { Only(P) }
+fun f(p: P, n: N, s: Shape, o: One): int {
+    var { x, y: py } = p;
+    x += 1;
+    const { name } = n;
+    const Only(inner) = o;
+    const iy: int? = inner.y;
+    if iy != null {
+        const Rect(w, h) = s else { return iy; };
+        return w * h + x + len(name);
+    }
+    for i in 0..3 {
+        const Circle(r) = s else { continue; };
+        print(r);
+    }
+    return py ?? 0;
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        ("const { x, z } = p;", "struct 'P' has no field 'z'"),
+        ("const { x: a, y: a } = p;", "'a' is bound twice"),
+        (
+            "const { q } = 5;",
+            "cannot destructure int: expected a struct",
+        ),
+        ("const { x } = p; x = 2;", "cannot assign to const 'x'"),
+        (
+            "const Circle(r) = s;",
+            "pattern does not cover 'Rect(_, _)'",
+        ),
+        (
+            "const Rect(w, h) = s else { print(1); };",
+            "the 'else' of a let-else must not fall through",
+        ),
+        (
+            "const Only(k) = o else { return; };",
+            "'else' is unreachable — the pattern matches every value",
+        ),
+        (
+            "const Circle(c) = 3 else { return; };",
+            "let-else needs an enum, found int",
+        ),
+        (
+            "const Rect(w, h) = s else { print(w); return; };",
+            "undefined variable 'w'",
+        ),
+    ] {
+        let src = format!(
+            "struct P {{ x: int, y: int }}
+enum Shape {{ Circle(float), Rect(int, int) }}
+enum One {{ Only(int) }}
+fun f(p: P, s: Shape, o: One) {{ {body} }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+}
+
+// --- Binding conditions (ADR 0058) ---
+
+#[test]
+fn if_const_and_while_const_bind_payloads() {
+    let d = diags(
+        "error Missing;
+struct P { x: int }
+enum Shape { Circle(float), Rect(int, int) }
+fun find(xs: int[], v: int): int! {
+    for [i, x] in xs { if x == v { return i; } }
+    return error.Missing;
+}
+fun f(xs: int?[], ps: P?[], s: Shape): int {
+    var sum: int = 0;
+    for i in 0..len(xs) {
+        if const v = xs[i] { sum += v; }
+    }
+    if const p = ps[0] { sum += p.x; }
+    if const at = find([1, 2], 2) { sum += at; } else if const Rect(w, h) = s { sum += w * h; }
+    while const line = readLine() { sum += len(line); }
+    if const Circle(r) = s { return int(r); } else { return sum; }
+}",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "if const v = n { }",
+            "'if const' needs an optional or an error union, found int",
+        ),
+        (
+            "while const Circle(r) = m { }",
+            "variant pattern 'Circle' needs an enum, found int?",
+        ),
+        (
+            "if const Only(k) = o { }",
+            "this 'if const' pattern matches every value",
+        ),
+        ("if const v = m { } print(v);", "undefined variable 'v'"),
+        ("if const v = m { v = 2; }", "cannot assign to const 'v'"),
+    ] {
+        let src = format!(
+            "enum Shape {{ Circle(float), Rect(int, int) }}
+enum One {{ Only(int) }}
+fun f(n: int, m: int?, o: One) {{ {body} }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // Both branches returning is a definite return.
+    assert!(
+        diags("fun f(m: int?): int { if const v = m { return v; } else { return 0; } }").is_empty()
+    );
+}
+
+// --- Loop labels (ADR 0059) ---
+
+#[test]
+fn loop_labels_must_name_an_enclosing_loop_once() {
+    let d = diags(
+        "fun f(xs: int[]) { outer: for x in xs { inner: while true { \
+         if x > 1 { break outer; } continue inner; } } }",
+    );
+    assert!(d.is_empty(), "{d:?}");
+    for (body, message) in [
+        (
+            "outer: while true { break outr; }",
+            "'break outr' names no enclosing loop",
+        ),
+        (
+            "a: while true { } while true { continue a; }",
+            "'continue a' names no enclosing loop",
+        ),
+        (
+            "a: while true { a: for x in xs { } }",
+            "label 'a' is already used by an enclosing loop",
+        ),
+        ("break a;", "'break' outside of a loop"),
+        (
+            "a: while true { const g = fun() { break a; }; }",
+            "'break' outside of a loop",
+        ),
+    ] {
+        let src = format!("fun f(xs: int[]) {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    let d = diags("fun f() { outer: while true { break outr; } }");
+    assert_eq!(d[0].help.as_deref(), Some("did you mean 'outer'?"), "{d:?}");
+    // Sibling loops may reuse a label.
+    assert!(diags("fun f() { a: while true { break a; } a: while true { break a; } }").is_empty());
+}
+
+// --- Defer (ADR 0060) ---
+
+#[test]
+fn defer_bodies_cannot_leave_their_block() {
+    for (body, message) in [
+        ("defer return 1;", "a 'defer' body cannot return"),
+        (
+            "defer { const v: int = try g(); }",
+            "a 'defer' body cannot propagate with 'try'",
+        ),
+        ("while true { defer break; }", "'break' outside of a loop"),
+    ] {
+        let src = format!("fun g(): int! {{ return 1; }}\nfun f(): int! {{ {body} return 0; }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // Inside the body, loops and function literals are its own.
+    assert!(
+        diags(
+            "fun f() { defer { while true { break; } \
+             const g: fun(): int = fun (): int { return 1; }; } }"
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn defer_bodies_keep_only_const_facts() {
+    // A const's guard holds at the block's exit: `close` gets a file.
+    assert!(
+        diags(
+            "fun f(): int { const r: file? = open(\"a\", \"r\"); \
+             if r == null { return 0; } defer close(r); return 1; }"
+        )
+        .is_empty()
+    );
+    // A var may be reassigned before the exit.
+    let d = diags(
+        "refstruct N { v: int }\n\
+         fun f(p: N?) { var q: N? = p; if q == null { return; } \
+         defer print(q.v); q = null; }",
+    );
+    assert!(d.iter().any(|e| e.message.contains("may be null")), "{d:?}");
+    // The body's write lands after everything in the block, so facts
+    // proven later in it do not survive the block either.
+    let d = diags(
+        "refstruct N { v: int }\n\
+         fun f(b: bool): int { var q: N? = null; \
+         if b { defer q = null; q = N { v: 1 }; } else { return 0; } \
+         return q.v; }",
+    );
+    assert!(d.iter().any(|e| e.message.contains("may be null")), "{d:?}");
+}
+
+// --- Arena blocks (ADR 0061) ---
+
+#[test]
+fn arena_values_cannot_escape() {
+    let prelude = "refstruct N { v: int, next: N? }\nvar G: string = \"\";\n";
+    for (body, message) in [
+        (
+            "var s: string = \"\"; arena { s = `${1}`; }",
+            "'s' outlives this arena, but the value assigned here does not",
+        ),
+        (
+            "var s: string = \"\"; arena { s += \"x\"; }",
+            "'s' outlives this arena, but the value assigned here does not",
+        ),
+        (
+            "arena { G = `${2}`; }",
+            "'G' outlives this arena, but the value assigned here does not",
+        ),
+        (
+            "const r: N = N { v: 0, next: null }; arena { r.next = N { v: 1, next: null }; }",
+            "'r' outlives this arena, but the value assigned here does not",
+        ),
+        (
+            "const xs: int[][] = []; arena { const ys: int[] = [1]; push(xs, ys); }",
+            "'push' stores a value that dies with this arena into one that outlives it",
+        ),
+        (
+            "const m: map<string, int> = {}; arena { insert(m, `${3}`, 1); }",
+            "'insert' stores a value that dies with this arena into one that outlives it",
+        ),
+        (
+            "const r: N = N { v: 0, next: null }; arena { const a: N = r; \
+             a.next = N { v: 1, next: null }; }",
+            "'a' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+    ] {
+        let src = format!("{prelude}fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    let d = diags("fun f(): int[] { arena { return [1]; } }");
+    assert_eq!(
+        d[0].message,
+        "cannot return a value allocated inside an arena"
+    );
+}
+
+#[test]
+fn arena_calls_and_literals_cannot_smuggle_handles() {
+    // What a callee or a function literal stores is judged from its
+    // body, transitively.
+    let prelude = "refstruct B { xs: int[] }\nvar kept: int[] = [];\n\
+                   fun stash(x: int[]) { kept = x; }\n\
+                   fun keep(b: B, x: int[]) { b.xs = x; }\n\
+                   fun relay(b: B, x: int[]) { keep(b, x); }\n\
+                   fun fill(b: B) { b.xs = [1]; }\n\
+                   fun first(bs: B[]): B { return bs[0]; }\n";
+    for (body, message) in [
+        (
+            "arena { const a: int[] = [7]; stash(a); }",
+            "'stash' may store a value that dies with this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; arena { const a: int[] = [7]; relay(b, a); }",
+            "'relay' may store a value that dies with this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; arena { const a: int[] = [7]; b.keep_in(a); }",
+            "'keep_in' may store a value that dies with this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; arena { fill(b); }",
+            "'fill' may store a value allocated in this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; arena { const a: int[] = [7]; \
+             const h: fun() = fun () { b.xs = a; }; h(); }",
+            "this function literal may store a value that dies with this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; arena { const a: int[] = [7]; \
+             const mk: fun(): fun() = fun (): fun() { return fun () { b.xs = a; }; }; mk()(); }",
+            "this function literal may store a value that dies with this arena where it outlives the arena",
+        ),
+        (
+            "const b: B = B { xs: [] }; const h: fun() = fun () { b.xs = [2]; }; arena { h(); }",
+            "'h' may store a value allocated in this arena where it outlives the arena",
+        ),
+        (
+            "const bs: B[] = [B { xs: [] }]; arena { const q: B = first(bs); q.xs = [4]; }",
+            "'q' may reach memory outside this arena through 'first', which the value assigned here does not outlive",
+        ),
+    ] {
+        let src = format!(
+            "{prelude}impl B {{ fun keep_in(self, x: int[]) {{ self.xs = x; }} }}\n\
+             fun f() {{ {body} }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // Calls that only read, or store into inner memory, are free.
+    assert!(
+        diags(&format!(
+            "{prelude}fun total(xs: int[]): int {{ return len(xs); }}\n\
+             fun f(b: B): int {{ var n: int = 0; arena {{ const a: int[] = [1]; \
+             n = n + total(a); const c: B = B {{ xs: [] }}; keep(c, a); fill(c); \
+             keep(b, b.xs); const q: B = first([c]); q.xs = [2]; \
+             const g: fun(): int = fun (): int {{ return len(a) + len(c.xs); }}; \
+             n = n + g(); }} return n; }}"
+        ))
+        .is_empty()
+    );
+}
+
+#[test]
+fn arena_computed_containers_cannot_smuggle_handles() {
+    // A container that is not a plain place is judged by what it
+    // reaches: outer memory directly, or through a call's result.
+    let prelude = "refstruct N { v: int }\nrefstruct H { xs: N[] }\n\
+                   impl H { fun items(self): N[] { return self.xs; } }\n\
+                   fun id(x: N[]): N[] { return x; }\n";
+    let stored = "'push' stores a value that dies with this arena into one that outlives it";
+    for (body, message) in [
+        (
+            "arena { push(id(out), N { v: 5 }); }",
+            "this container may reach memory outside this arena through 'id', which the value stored here does not outlive",
+        ),
+        ("arena { push(o ?? out, N { v: 5 }); }", stored),
+        (
+            "arena { push(match k { 0 => out, else => out }, N { v: 5 }); }",
+            stored,
+        ),
+        (
+            "arena { push(h.items(), N { v: 5 }); }",
+            "this container may reach memory outside this arena through 'items', which the value stored here does not outlive",
+        ),
+        (
+            "arena { insert(mo ?? m, 1, N { v: 5 }); }",
+            "'insert' stores a value that dies with this arena into one that outlives it",
+        ),
+    ] {
+        let src = format!(
+            "{prelude}fun f(out: N[], o: N[]?, k: int, h: H, m: map<int, N>, mo: map<int, N>?) \
+             {{ {body} }}"
+        );
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // A computed container of inner memory is free.
+    assert!(
+        diags(&format!(
+            "{prelude}fun f(k: int) {{ arena {{ const a: N[] = []; push(id(a), N {{ v: 1 }}); \
+             push(match k {{ 0 => a, else => a }}, N {{ v: 2 }}); }} }}"
+        ))
+        .is_empty()
+    );
+}
+
+#[test]
+fn arena_aliases_hold_for_the_whole_block() {
+    // An inner binding given outer memory anywhere in the arena — by a
+    // store into it, a field write, or a later pass of a loop — is an
+    // alias at every write through it.
+    let prelude = "refstruct Node { v: int, next: Node? }\nrefstruct Box { n: Node? }\n\
+                   refstruct H { b: Box? }\n";
+    for (body, message) in [
+        (
+            "arena { const xs: Box[] = []; push(xs, b); \
+             for y in xs { y.n = Node { v: 7, next: null }; } }",
+            "'y' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+        (
+            "arena { const m: map<int, Box> = {}; insert(m, 1, b); \
+             if const y = m[1] { y.n = Node { v: 7, next: null }; } }",
+            "'y' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+        (
+            "arena { const h: H = H { b: null }; h.b = b; \
+             if const bb = h.b { bb.n = Node { v: 7, next: null }; } }",
+            "'bb' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+        (
+            "arena { var x: Box = Box { n: null }; var i: int = 0; \
+             while i < 2 { x.n = Node { v: 7, next: null }; x = b; i = i + 1; } }",
+            "'x' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+        (
+            "arena { var x: Box = Box { n: null }; var i: int = 0; \
+             while i < 2 { const y: Box = x; y.n = Node { v: 7, next: null }; x = b; i = i + 1; } }",
+            "'y' reaches memory outside this arena, which the value assigned here does not outlive",
+        ),
+    ] {
+        let src = format!("{prelude}fun f(b: Box) {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // The same shapes over inner memory only are free.
+    assert!(
+        diags(&format!(
+            "{prelude}fun f(b: Box, n: Node) {{ arena {{ const xs: Box[] = []; \
+             push(xs, Box {{ n: null }}); for y in xs {{ y.n = Node {{ v: 7, next: null }}; }} \
+             const h: H = H {{ b: Box {{ n: null }} }}; \
+             if const bb = h.b {{ bb.n = Node {{ v: 7, next: null }}; }} \
+             const os: Box[] = []; push(os, b); for y in os {{ y.n = n; }} \
+             var x: Box = Box {{ n: null }}; var i: int = 0; \
+             while i < 2 {{ x.n = n; x = b; i = i + 1; }} }} }}"
+        ))
+        .is_empty()
+    );
+}
+
+#[test]
+fn arena_allows_what_outlives_nothing() {
+    // Scalars, literals, outer reads, and inner containers are free;
+    // so is a function literal that stores nothing.
+    assert!(
+        diags(
+            "refstruct N { v: int, next: N? }\n\
+             fun f(xs: int[], r: N): int { var n: int = 0; var s: string = \"\"; \
+             arena { const ys: int[] = [1, 2]; push(xs, len(ys)); n = n + len(ys); \
+             s = \"lit\"; r.next = r; const zs: int[][] = []; push(zs, ys); \
+             const g: fun(): int[] = fun (): int[] { return [n]; }; \
+             return len(xs); } }"
+        )
+        .is_empty()
+    );
+}
+
+// --- Error payloads (ADR 0062) ---
+
+#[test]
+fn error_payloads_check_like_variant_payloads() {
+    let prelude = "error NotFound, ParseFailed(int, string);\n";
+    for (body, message) in [
+        (
+            "const e: error = error.ParseFailed(1);",
+            "error 'ParseFailed' expects 2 payload(s), found 1",
+        ),
+        (
+            "const e: error = error.ParseFailed;",
+            "error 'ParseFailed' expects 2 payload(s), found 0",
+        ),
+        (
+            "const e: error = error.ParseFailed(1, 2);",
+            "payload of type string, found int",
+        ),
+        (
+            "const e: error = error.NotFound; match e { error.ParseFailed(n) { } else { } }",
+            "error 'ParseFailed' has 2 payload(s), found 1 pattern(s)",
+        ),
+        (
+            "const n: int = 1; match n { error.NotFound { } else { } }",
+            "error pattern 'error.NotFound' needs an error, found int",
+        ),
+        (
+            "const e: error = error.NotFound; \
+             match e { error.ParseFailed { } error.ParseFailed { } else { } }",
+            "duplicate arm for 'error.ParseFailed' — this arm is unreachable",
+        ),
+    ] {
+        let src = format!("{prelude}fun f() {{ {body} }}");
+        let d = diags(&src);
+        assert!(d.iter().any(|e| e.message == message), "{src}: {d:?}");
+    }
+    // A bare name compares or matches the code alone; arms bind.
+    assert!(
+        diags(&format!(
+            "{prelude}fun f(e: error): int {{ if e == error.ParseFailed {{ return 1; }} \
+             return match e {{ error.ParseFailed(line, _) => line, error.NotFound => 0, \
+             else => -1 }}; }}"
+        ))
+        .is_empty()
+    );
+}
+
+#[test]
+fn error_payloads_may_not_hold_errors_or_escape_arenas() {
+    let d = diags("struct S { e: error }\nerror Wrap(S);");
+    assert_eq!(
+        d[0].message,
+        "error 'Wrap' cannot carry S: a payload may not hold an error"
+    );
+    // Only the errors the operand can raise count, through every
+    // function it reaches.
+    let src = "error Bad(string), Bare;\n\
+               fun g(n: int): int! { if n > 1 { return error.Bad(`${n}`); } return 1; }\n\
+               fun h(n: int): int! { const v: int = try g(n); return v; }\n\
+               fun f(): int! { arena { const n: int = try h(2); print(n); } return 0; }";
+    assert_eq!(
+        diags(src)[0].message,
+        "'try' cannot propagate an error whose payloads hold handles out of an arena"
+    );
+    let src = "error Bad(string), Bare;\n\
+               fun g(): int! { return error.Bare; }\n\
+               fun f(): int! { arena { const n: int = try g(); print(n); } return 0; }";
+    assert!(diags(src).is_empty());
+    let src = "error Bad(string);\n\
+               fun f(n: int): int! { arena { const s: string = `${n}`; \
+               return error.Bad(s); } }";
+    assert_eq!(
+        diags(src)[0].message,
+        "cannot return a value allocated inside an arena"
+    );
+}
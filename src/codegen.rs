@@ -6,8 +6,8 @@
 //! by default, so data symbols are RIP-relative and descriptors needing
 //! load-time relocations live in .data.rel.ro.
 
-use crate::ast::{Function, Item, TypeAnn};
-use crate::check::Resolutions;
+use crate::ast::{Function, TypeAnn};
+use crate::check::{ConstValue, Resolutions};
 use crate::diagnostic::Diagnostic;
 use crate::modules::ModuleGraph;
 use crate::source::SourceMap;
@@ -26,10 +26,39 @@ use std::fmt::Write;
 /// from a pointer (multi-word elements, ADR 0023).
 pub(crate) const RT_PUSH: &str = "ys_push";
 pub(crate) const RT_PUSH_N: &str = "ys_push_n";
+/// The array library (ADR 0055); `array_runtime` documents the shapes.
+pub(crate) const RT_ARR_INSERT: &str = "ys_insert";
+pub(crate) const RT_ARR_REMOVE: &str = "ys_remove_at";
+pub(crate) const RT_ARR_REVERSE: &str = "ys_reverse";
+pub(crate) const RT_ARR_SORT: &str = "ys_sort";
+/// `ys_sort`'s key orders; anything else orders as `int`.
+pub(crate) const SORT_BY_FLOAT: i64 = 1;
+pub(crate) const SORT_BY_STR: i64 = 2;
+/// `xs[a..b]` (ADR 0051): checks the bounds and copies them out into a
+/// fresh array.
+pub(crate) const RT_SLICE: &str = "ys_slice";
+/// Strings as bytes (ADR 0053): `ys_str_slice` checks a byte range and
+/// writes the view's descriptor; `ys_chars` decodes UTF-8 into a fresh
+/// `u32[]`.
+pub(crate) const RT_STR_SLICE: &str = "ys_str_slice";
+pub(crate) const RT_CHARS: &str = "ys_chars";
+/// The string library (ADR 0054); `text_runtime` documents the shapes.
+pub(crate) const RT_STR_FIND: &str = "ys_str_find";
+pub(crate) const RT_STR_STARTS: &str = "ys_str_starts";
+pub(crate) const RT_STR_ENDS: &str = "ys_str_ends";
+pub(crate) const RT_STR_TRIM: &str = "ys_str_trim";
+pub(crate) const RT_STR_CASE: &str = "ys_str_case";
+pub(crate) const RT_STR_SPLIT: &str = "ys_str_split";
+pub(crate) const RT_STR_REPLACE: &str = "ys_str_replace";
+pub(crate) const RT_STR_JOIN: &str = "ys_str_join";
+pub(crate) const RT_PARSE_INT: &str = "ys_parse_int";
+pub(crate) const RT_PARSE_FLOAT: &str = "ys_parse_float";
 /// The shared text builder (ADR 0029): `ys_sb_append` grows the static
-/// byte buffer and copies bytes in; `ys_sb_int` renders one i64 into it.
+/// byte buffer and copies bytes in; `ys_sb_int` renders one i64 into it,
+/// `ys_sb_u64` one u64 (ADR 0052).
 pub(crate) const RT_SB_APPEND: &str = "ys_sb_append";
 pub(crate) const RT_SB_INT: &str = "ys_sb_int";
+pub(crate) const RT_SB_U64: &str = "ys_sb_u64";
 /// The world interface (ADR 0031): argv materialization, file handles
 /// (heap boxes `{FILE*, closed}`), and line input.
 pub(crate) const RT_ARGS: &str = "ys_args";
@@ -38,14 +67,34 @@ pub(crate) const RT_READ: &str = "ys_read";
 pub(crate) const RT_READLINE: &str = "ys_readline";
 pub(crate) const RT_WRITE: &str = "ys_write";
 pub(crate) const RT_CLOSE: &str = "ys_close";
+/// Maps (ADR 0042): FNV-1a hashing of words and byte runs, the table
+/// header's constructor, and the probe/append/delete mechanics. Key
+/// equality stays in lowered code — the runtime only matches hashes.
+pub(crate) const RT_HASH_WORD: &str = "ys_hash_word";
+pub(crate) const RT_HASH_BYTES: &str = "ys_hash_bytes";
+pub(crate) const RT_MAP_NEW: &str = "ys_map_new";
+pub(crate) const RT_MAP_PROBE: &str = "ys_map_probe";
+pub(crate) const RT_MAP_ADD: &str = "ys_map_add";
+pub(crate) const RT_MAP_DEL: &str = "ys_map_del";
 /// The builder's `{len, cap, ptr}` header: lowered code stores len = 0
 /// to reset and reads `{len, ptr}` to consume the bytes.
 pub(crate) const SB_HDR: &str = ".Lys_sb";
+/// The region allocator (ADR 0061): every runtime and lowered
+/// allocation goes through `ys_alloc`/`ys_realloc`, which serve the
+/// innermost open `arena` block's region or, outside any, libc's heap.
+/// `region_runtime` documents the shapes.
+pub(crate) const RT_ALLOC: &str = "ys_alloc";
+pub(crate) const RT_REALLOC: &str = "ys_realloc";
+pub(crate) const RT_ARENA_ENTER: &str = "ys_arena_enter";
+pub(crate) const RT_ARENA_LEAVE: &str = "ys_arena_leave";
 pub(crate) const RT_PRINTF: &str = "printf@PLT";
-pub(crate) const RT_MALLOC: &str = "malloc@PLT";
-pub(crate) const RT_REALLOC: &str = "realloc@PLT";
+pub(crate) const RT_LIBC_MALLOC: &str = "malloc@PLT";
+pub(crate) const RT_LIBC_REALLOC: &str = "realloc@PLT";
+pub(crate) const RT_FREE: &str = "free@PLT";
 pub(crate) const RT_MEMCPY: &str = "memcpy@PLT";
 pub(crate) const RT_MEMCMP: &str = "memcmp@PLT";
+pub(crate) const RT_MEMMOVE: &str = "memmove@PLT";
+pub(crate) const RT_MEMMEM: &str = "memmem@PLT";
 pub(crate) const RT_FMOD: &str = "fmod@PLT";
 pub(crate) const RT_DPRINTF: &str = "dprintf@PLT";
 /// The float formatter (ADR 0027) and the libc pieces only it uses.
@@ -66,9 +115,13 @@ pub(crate) const RT_EXIT: &str = "exit@PLT";
 /// builder's bytes as `error: …` on stderr and exits 1. CALL-entered
 /// like every stub — never `jmp` (stack alignment).
 pub(crate) const RT_ERR_EXIT: &str = "ys_err_exit";
-/// The `main(): int!` implementation label: the dot keeps it out of
+/// The entry `main`'s own label: the C-visible `main` is a stub that
+/// calls it (ADR 0034 decision 8, ADR 0048). The dot keeps it out of
 /// user-identifier space (the show-routine convention).
 pub(crate) const ENTRY_IMPL: &str = "ys.main";
+/// The module-var initializer the C entry calls first (ADR 0048) —
+/// dotted like `ENTRY_IMPL`.
+pub(crate) const GLOBALS_INIT: &str = "ys.init";
 
 /// Trap stubs (ADR 0022): print a runtime diagnostic and exit 1.
 pub(crate) const TRAP_DIV0: &str = "ys_trap_div0";
@@ -77,11 +130,18 @@ pub(crate) const TRAP_OOB: &str = "ys_trap_oob";
 pub(crate) const TRAP_F2I: &str = "ys_trap_f2i";
 pub(crate) const TRAP_CLOSED: &str = "ys_trap_closed";
 pub(crate) const TRAP_READSIZE: &str = "ys_trap_readsize";
+pub(crate) const TRAP_SHIFT: &str = "ys_trap_shift";
+pub(crate) const TRAP_SLICE: &str = "ys_trap_slice";
+pub(crate) const TRAP_STEP0: &str = "ys_trap_step0";
+pub(crate) const TRAP_SPLIT0: &str = "ys_trap_split0";
+pub(crate) const TRAP_REPLACE0: &str = "ys_trap_replace0";
 
 /// printf formats and fixed strings for `print`. `FMT_INT_RAW` carries
-/// no newline — it is `ys_sb_int`'s snprintf format (ADR 0029).
+/// no newline — it is `ys_sb_int`'s snprintf format (ADR 0029), as
+/// `FMT_U64_RAW` is `ys_sb_u64`'s.
 pub(crate) const FMT_INT: &str = ".Lfmt_int";
 pub(crate) const FMT_INT_RAW: &str = ".Lfmt_int_raw";
+pub(crate) const FMT_U64_RAW: &str = ".Lfmt_u64_raw";
 pub(crate) const FMT_CSTR: &str = ".Lfmt_cstr";
 pub(crate) const FMT_STR: &str = ".Lfmt_str";
 pub(crate) const FMT_ERR_EXIT: &str = ".Lfmt_err_exit";
@@ -90,21 +150,39 @@ pub(crate) const FALSE_S: &str = ".Lfalse_s";
 pub(crate) const NULL_S: &str = ".Lnull_s";
 pub(crate) const FMT_TRAP: &str = ".Lfmt_trap";
 pub(crate) const FMT_TRAP_OOB: &str = ".Lfmt_trap_oob";
+pub(crate) const FMT_TRAP_SHIFT: &str = ".Lfmt_trap_shift";
+pub(crate) const FMT_TRAP_SLICE: &str = ".Lfmt_trap_slice";
 pub(crate) const MSG_DIV0: &str = ".Lmsg_div0";
 pub(crate) const MSG_OVERFLOW: &str = ".Lmsg_overflow";
 pub(crate) const MSG_F2I: &str = ".Lmsg_f2i";
 pub(crate) const MSG_CLOSED: &str = ".Lmsg_closed";
 pub(crate) const MSG_READSIZE: &str = ".Lmsg_readsize";
+pub(crate) const MSG_STEP0: &str = ".Lmsg_step0";
+pub(crate) const MSG_SPLIT0: &str = ".Lmsg_split0";
+pub(crate) const MSG_REPLACE0: &str = ".Lmsg_replace0";
 
-/// The assembly symbol for a function: the entry `main` keeps its name
-/// (the C runtime calls it); everything else is suffixed with its module
-/// index, which decodes uniquely (the suffix after the last underscore).
+/// The assembly symbol for a function: its name suffixed with its
+/// module index, which decodes uniquely (the suffix after the last
+/// underscore). The entry `main` is `ENTRY_IMPL`, so a call to it —
+/// recursion included — never re-enters the C entry stub.
 pub(crate) fn label_of(module: usize, name: &str) -> String {
-    if module == 0 && name == syntax::ENTRY_FN {
-        name.to_string()
+    let name = if module == 0 && name == syntax::ENTRY_FN {
+        ENTRY_IMPL
     } else {
-        format!("{}_{module}", sanitize(name))
-    }
+        name
+    };
+    format!("{}_{module}", sanitize(name))
+}
+
+/// The rodata symbol holding a scalar module const's value (ADR 0047);
+/// string consts are interned descriptors instead.
+pub(crate) fn const_label(module: usize, name: &str) -> String {
+    format!(".Lkc_{}_{module}", sanitize(name))
+}
+
+/// The .bss symbol holding a module var's storage (ADR 0048).
+pub(crate) fn global_label(module: usize, name: &str) -> String {
+    format!(".Lkg_{}_{module}", sanitize(name))
 }
 
 /// Assembler-safe instance names (ADR 0035): the canonical mangle's
@@ -126,6 +204,10 @@ pub(crate) fn sanitize(name: &str) -> String {
             '!' => out.push_str("$e"),
             '[' => out.push_str("$b"),
             ']' => out.push_str("$d"),
+            // Function types in instance names (ADR 0038).
+            '(' => out.push_str("$o"),
+            ')' => out.push_str("$p"),
+            ':' => out.push_str("$k"),
             ' ' => {}
             other => out.push(other),
         }
@@ -141,19 +223,21 @@ pub(crate) fn sanitize(name: &str) -> String {
 pub(crate) struct Strings {
     bytes: String,
     descriptors: String,
-    ids: HashMap<String, usize>,
+    ids: HashMap<Vec<u8>, usize>,
     locs: HashMap<String, usize>,
+    fns: HashMap<String, usize>,
 }
 
 impl Strings {
     /// Returns the descriptor's symbol — the label format lives only here.
-    pub(crate) fn intern(&mut self, text: &str) -> String {
+    pub(crate) fn intern(&mut self, text: impl AsRef<[u8]>) -> String {
+        let text = text.as_ref();
         if let Some(&id) = self.ids.get(text) {
             return format!(".Lsd{id}");
         }
         let id = self.ids.len();
         let _ = writeln!(self.bytes, ".Lsb{id}:");
-        for chunk in text.as_bytes().chunks(16) {
+        for chunk in text.chunks(16) {
             let bytes: Vec<String> = chunk.iter().map(|b| b.to_string()).collect();
             let _ = writeln!(self.bytes, "\t.byte {}", bytes.join(","));
         }
@@ -162,10 +246,24 @@ impl Strings {
             "\t.balign 8\n.Lsd{id}:\n\t.quad .Lsb{id}\n\t.quad {}",
             text.len()
         );
-        self.ids.insert(text.to_string(), id);
+        self.ids.insert(text.to_vec(), id);
         format!(".Lsd{id}")
     }
 
+    /// Interns a named function's static function object (ADR 0038):
+    /// one word, the code address — what a function value points at.
+    /// Deduplicated per program, so identity equality holds however
+    /// the function was named.
+    pub(crate) fn intern_fn(&mut self, label: &str) -> String {
+        if let Some(&id) = self.fns.get(label) {
+            return format!(".Lfd{id}");
+        }
+        let id = self.fns.len();
+        let _ = writeln!(self.descriptors, "\t.balign 8\n.Lfd{id}:\n\t.quad {label}");
+        self.fns.insert(label.to_string(), id);
+        format!(".Lfd{id}")
+    }
+
     /// Interns a NUL-terminated C string — trap locations (ADR 0022)
     /// and print fragments (ADR 0025); returns its label. Deduplicated
     /// per program.
@@ -222,23 +320,7 @@ pub fn compile(
     for (mi, module) in graph.modules.iter().enumerate() {
         for item in &module.ast {
             // Generic templates compile per instance, below (ADR 0035).
-            if let Item::Function(f) = item
-                && f.type_params.is_empty()
-            {
-                // The int!-returning entry moves aside (sret convention);
-                // the C `main` below adapts (ADR 0034 decision 8).
-                if entry_errs && mi == 0 && f.name == syntax::ENTRY_FN {
-                    asm.push_str(&crate::ir::function_as(
-                        f,
-                        mi,
-                        res,
-                        &mut strings,
-                        &mut printers,
-                        map,
-                        ENTRY_IMPL,
-                    )?);
-                    continue;
-                }
+            for f in item.functions().iter().filter(|f| f.type_params.is_empty()) {
                 asm.push_str(&crate::ir::function(
                     f,
                     mi,
@@ -265,24 +347,71 @@ pub fn compile(
             map,
         )?);
     }
+    // Module vars (ADR 0048): the initializer the C entry calls first.
+    if !res.globals.is_empty() {
+        asm.push_str(&crate::ir::globals_init(
+            graph,
+            res,
+            &mut strings,
+            &mut printers,
+            map,
+        )?);
+    }
+    // The C entry: runs the module-var initializer once, argc/argv kept
+    // across it, then the program's `main` — so a `main` that recurses
+    // never re-initializes (ADR 0048).
+    let init = if res.globals.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\tpushq %rdi\n\tpushq %rsi\n\tcall {}\n\tpopq %rsi\n\tpopq %rdi\n",
+            label_of(0, GLOBALS_INIT)
+        )
+    };
+    let impl_label = label_of(0, ENTRY_IMPL);
     if entry_errs {
         // The wrapper: forward argc/argv behind the sret pointer, then
-        // exit with the payload — or render the code via its show
+        // exit with the payload — or render the error via its show
         // routine (builder reset first, consumer discipline) and take
-        // the trap-shaped exit.
-        let show = printers.request(&crate::types::Type::ErrCode, res);
-        let impl_label = label_of(0, ENTRY_IMPL);
+        // the trap-shaped exit. Errors carrying payloads (ADR 0062)
+        // widen the `int!` and pass the routine a pointer to the code.
+        use crate::ir::layout::{FUEL, Kind, kind_of};
+        use crate::types::Type;
+        let unsupported = || {
+            Diagnostic::error(
+                "not yet compilable: error payloads of this type".to_string(),
+                main_fn.span,
+            )
+        };
+        let words = kind_of(&Type::ErrUnion(Box::new(Type::Int)), res, FUEL)
+            .ok_or_else(unsupported)?
+            .words();
+        let wide = kind_of(&Type::ErrCode, res, FUEL).ok_or_else(unsupported)? != Kind::Word;
+        let frame = (8 * words).next_multiple_of(16);
+        let err_arg = if wide {
+            format!("leaq -{frame}(%rbp), %rdi")
+        } else {
+            format!("movq -{frame}(%rbp), %rdi")
+        };
+        let show = printers.request(&Type::ErrCode, res);
         let show_label = label_of(0, &show);
         let _ = write!(
             asm,
             "\t.globl main\nmain:\n\
-             \tpushq %rbp\n\tmovq %rsp, %rbp\n\tsubq $16, %rsp\n\
-             \tmovq %rsi, %rdx\n\tmovq %rdi, %rsi\n\tleaq -16(%rbp), %rdi\n\
+             \tpushq %rbp\n\tmovq %rsp, %rbp\n\tsubq ${frame}, %rsp\n{init}\
+             \tmovq %rsi, %rdx\n\tmovq %rdi, %rsi\n\tleaq -{frame}(%rbp), %rdi\n\
              \tcall {impl_label}\n\
-             \tmovq -16(%rbp), %rdi\n\tcmpq $2, %rdi\n\tjl .Lys_main_ok\n\
-             \tmovq $0, {SB_HDR}(%rip)\n\tmovq $8, %rsi\n\tcall {show_label}\n\
+             \tcmpq $2, -{frame}(%rbp)\n\tjl .Lys_main_ok\n\
+             \tmovq $0, {SB_HDR}(%rip)\n\t{err_arg}\n\tmovq $8, %rsi\n\tcall {show_label}\n\
              \tcall {RT_ERR_EXIT}\n\
-             .Lys_main_ok:\n\tmovq -8(%rbp), %rax\n\tleave\n\tret\n"
+             .Lys_main_ok:\n\tmovq -{payload}(%rbp), %rax\n\tleave\n\tret\n",
+            payload = frame - 8
+        );
+    } else {
+        let _ = write!(
+            asm,
+            "\t.globl main\nmain:\n\tpushq %rbp\n\tmovq %rsp, %rbp\n{init}\
+             \tcall {impl_label}\n\tleave\n\tret\n"
         );
     }
     // The show routines requested by print sites (ADR 0025).
@@ -291,12 +420,13 @@ pub fn compile(
     }
     asm.push_str(&runtime());
     asm.push_str(&fmt_f64_runtime());
-    asm.push_str(&rodata());
+    asm.push_str(&rodata(res));
     asm.push_str(&strings.bytes);
     if !strings.descriptors.is_empty() {
         asm.push_str("\t.section .data.rel.ro\n");
         asm.push_str(&strings.descriptors);
     }
+    asm.push_str(&bss(res));
     Ok(asm)
 }
 
@@ -314,14 +444,13 @@ pub fn dump_ir(
     let mut printers = crate::ir::show::Printers::default();
     for (mi, module) in graph.modules.iter().enumerate() {
         for item in &module.ast {
-            if let Item::Function(f) = item
-                && f.type_params.is_empty()
-            {
-                let ir = crate::ir::lower_function(f, mi, res, &mut strings, &mut printers, map)?;
-                if !output.is_empty() {
-                    output.push('\n');
+            for f in item.functions().iter().filter(|f| f.type_params.is_empty()) {
+                for ir in crate::ir::lower_function(f, mi, res, &mut strings, &mut printers, map)? {
+                    if !output.is_empty() {
+                        output.push('\n');
+                    }
+                    let _ = writeln!(output, "{ir}");
                 }
-                let _ = writeln!(output, "{ir}");
             }
         }
     }
@@ -329,11 +458,18 @@ pub fn dump_ir(
     instance_keys.sort();
     for key in instance_keys {
         let f = &res.instances[key];
-        let ir = crate::ir::lower_function(f, key.0, res, &mut strings, &mut printers, map)?;
-        if !output.is_empty() {
+        for ir in crate::ir::lower_function(f, key.0, res, &mut strings, &mut printers, map)? {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            let _ = writeln!(output, "{ir}");
+        }
+    }
+    if !res.globals.is_empty() {
+        for ir in crate::ir::lower_globals(graph, res, &mut strings, &mut printers, map)? {
             output.push('\n');
+            let _ = writeln!(output, "{ir}");
         }
-        let _ = writeln!(output, "{ir}");
     }
     for ir in printers.build(res, &mut strings) {
         if !output.is_empty() {
@@ -346,10 +482,19 @@ pub fn dump_ir(
 
 /// The in-assembly runtime, appended to every program. Arrays follow ADR
 /// 0014: a handle points at a `{len, cap, data*}` header, elements are
-/// inline 8-byte values, buffers come from libc malloc/realloc and are
-/// never freed (the arena/leak story of ADR 0009/0015). Both push
+/// inline 8-byte values, buffers come from the region allocator — freed
+/// with their `arena` block, else never (the leak story of ADR
+/// 0009/0015; ADR 0061). Both push
 /// routines grow by doubling (min 4); `ys_push_n` takes
 /// `(hdr, src*, stride_bytes)` and memcpys the element in (ADR 0023).
+/// `ys_slice` takes `(hdr, lo, hi, stride_bytes, loc)`: one unsigned
+/// compare per bound also rejects a negative one, and the copy gets
+/// `cap == len`, so its first push grows it (ADR 0051).
+/// `ys_str_slice` takes `(out, desc, lo, hi, loc)` and shares the slice
+/// trap; the view points into the same bytes, no copy (ADR 0053).
+/// `ys_chars` takes a descriptor and sizes the buffer for one code point
+/// per byte; a byte that starts no well-formed sequence decodes as
+/// U+FFFD and the walk resumes at the next byte — the oracle's rules.
 /// The labels can't collide with user code — every user symbol except
 /// the entry `main` carries a `_<module>` suffix.
 fn runtime() -> String {
@@ -425,6 +570,175 @@ fn runtime() -> String {
 \tmovq %rax, 0(%rdi)
 \tpopq %rbp
 \tret
+{RT_SLICE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %r12
+\tpushq %r13
+\tpushq %r14
+\tpushq %r15
+\tsubq $8, %rsp
+\tcmpq 0(%rdi), %rdx
+\tja .Lys_slice_oob
+\tcmpq %rdx, %rsi
+\tja .Lys_slice_oob
+\tmovq %rdi, %rbx
+\tmovq %rsi, %r12
+\tmovq %rdx, %r13
+\tsubq %rsi, %r13
+\tmovq %rcx, %r14
+\tmovl $24, %edi
+\tcall {RT_ALLOC}
+\tmovq %rax, %r15
+\tmovq %r13, 0(%rax)
+\tmovq %r13, 8(%rax)
+\tmovq %r13, %rdi
+\timulq %r14, %rdi
+\tmovl $8, %eax
+\tcmpq %rax, %rdi
+\tcmovbq %rax, %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, 16(%r15)
+\tmovq %rax, %rdi
+\tmovq %r12, %rsi
+\timulq %r14, %rsi
+\taddq 16(%rbx), %rsi
+\tmovq %r13, %rdx
+\timulq %r14, %rdx
+\tcall {RT_MEMCPY}
+\tmovq %r15, %rax
+\taddq $8, %rsp
+\tpopq %r15
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+.Lys_slice_oob:
+\tmovq %r8, %rcx
+\tmovq 0(%rdi), %r8
+\tmovq %rsi, %rdi
+\tmovq %rdx, %rsi
+\tmovq %r8, %rdx
+\tcall {TRAP_SLICE}
+{RT_STR_SLICE}:
+\tcmpq 8(%rsi), %rcx
+\tja .Lys_str_slice_oob
+\tcmpq %rcx, %rdx
+\tja .Lys_str_slice_oob
+\tmovq 0(%rsi), %rax
+\taddq %rdx, %rax
+\tmovq %rax, 0(%rdi)
+\tsubq %rdx, %rcx
+\tmovq %rcx, 8(%rdi)
+\tret
+.Lys_str_slice_oob:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tmovq %rdx, %rdi
+\tmovq 8(%rsi), %rdx
+\tmovq %rcx, %rsi
+\tmovq %r8, %rcx
+\tcall {TRAP_SLICE}
+{RT_CHARS}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %r12
+\tpushq %r13
+\tpushq %r14
+\tmovq 0(%rdi), %rbx
+\tmovq 8(%rdi), %r12
+\tmovl $24, %edi
+\tcall {RT_ALLOC}
+\tmovq %rax, %r13
+\tleaq 0(,%r12,4), %rdi
+\tmovl $8, %eax
+\tcmpq %rax, %rdi
+\tcmovbq %rax, %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, 16(%r13)
+\tmovq %rax, %rdi
+\txorl %esi, %esi
+\txorl %r14d, %r14d
+.Lys_chars_next:
+\tcmpq %r12, %rsi
+\tjae .Lys_chars_done
+\tmovzbl (%rbx,%rsi), %eax
+\tcmpl $0x80, %eax
+\tjb .Lys_chars_put
+\tcmpl $0xc2, %eax
+\tjb .Lys_chars_bad
+\tcmpl $0xe0, %eax
+\tjb .Lys_chars_2
+\tcmpl $0xf0, %eax
+\tjb .Lys_chars_3
+\tcmpl $0xf5, %eax
+\tjae .Lys_chars_bad
+\tandl $0x07, %eax
+\tmovl $3, %ecx
+\tmovl $0x10000, %r8d
+\tjmp .Lys_chars_tail
+.Lys_chars_2:
+\tandl $0x1f, %eax
+\tmovl $1, %ecx
+\tmovl $0x80, %r8d
+\tjmp .Lys_chars_tail
+.Lys_chars_3:
+\tandl $0x0f, %eax
+\tmovl $2, %ecx
+\tmovl $0x800, %r8d
+.Lys_chars_tail:
+\tleaq (%rsi,%rcx), %r9
+\tcmpq %r12, %r9
+\tjae .Lys_chars_bad
+\tmovq %rsi, %rdx
+.Lys_chars_cont:
+\tincq %rdx
+\tmovzbl (%rbx,%rdx), %r10d
+\tmovl %r10d, %r11d
+\tandl $0xc0, %r11d
+\tcmpl $0x80, %r11d
+\tjne .Lys_chars_bad
+\tshll $6, %eax
+\tandl $0x3f, %r10d
+\torl %r10d, %eax
+\tcmpq %r9, %rdx
+\tjb .Lys_chars_cont
+\tcmpl %r8d, %eax
+\tjb .Lys_chars_bad
+\tcmpl $0x10ffff, %eax
+\tja .Lys_chars_bad
+\tmovl %eax, %r10d
+\tandl $0xfffff800, %r10d
+\tcmpl $0xd800, %r10d
+\tje .Lys_chars_bad
+\tmovl %eax, (%rdi,%r14,4)
+\tincq %r14
+\tleaq 1(%r9), %rsi
+\tjmp .Lys_chars_next
+.Lys_chars_put:
+\tmovl %eax, (%rdi,%r14,4)
+\tincq %r14
+\tincq %rsi
+\tjmp .Lys_chars_next
+.Lys_chars_bad:
+\tmovl $0xfffd, (%rdi,%r14,4)
+\tincq %r14
+\tincq %rsi
+\tjmp .Lys_chars_next
+.Lys_chars_done:
+\tmovq %r14, 0(%r13)
+\tmovq %r14, 8(%r13)
+\tmovq %r13, %rax
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
 {TRAP_OOB}:
 \tpushq %rbp
 \tmovq %rsp, %rbp
@@ -437,14 +751,38 @@ fn runtime() -> String {
 \tcall {RT_DPRINTF}
 \tmovl $1, %edi
 \tcall {RT_EXIT}
+{TRAP_SHIFT}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tmovq %rsi, %rcx
+\tmovq %rdi, %rdx
+\tleaq {FMT_TRAP_SHIFT}(%rip), %rsi
+\tmovl $2, %edi
+\txorl %eax, %eax
+\tcall {RT_DPRINTF}
+\tmovl $1, %edi
+\tcall {RT_EXIT}
+{TRAP_SLICE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tmovq %rcx, %r9
+\tmovq %rdx, %r8
+\tmovq %rsi, %rcx
+\tmovq %rdi, %rdx
+\tleaq {FMT_TRAP_SLICE}(%rip), %rsi
+\tmovl $2, %edi
+\txorl %eax, %eax
+\tcall {RT_DPRINTF}
+\tmovl $1, %edi
+\tcall {RT_EXIT}
 "
     ) + &one_message_traps()
 }
 
 /// The one-message trap stubs (ADR 0022/0028): identical shape, the
 /// message register aside — location arrives in %rdi, dprintf reports
-/// on stderr, exit 1. The OOB trap stays bespoke (it also carries the
-/// index and length).
+/// on stderr, exit 1. The OOB, shift, and slice traps stay bespoke
+/// (they also carry the index and length, the count, or the bounds).
 fn one_message_traps() -> String {
     [
         (TRAP_DIV0, MSG_DIV0),
@@ -452,6 +790,9 @@ fn one_message_traps() -> String {
         (TRAP_F2I, MSG_F2I),
         (TRAP_CLOSED, MSG_CLOSED),
         (TRAP_READSIZE, MSG_READSIZE),
+        (TRAP_STEP0, MSG_STEP0),
+        (TRAP_SPLIT0, MSG_SPLIT0),
+        (TRAP_REPLACE0, MSG_REPLACE0),
     ]
     .into_iter()
     .map(|(stub, msg)| {
@@ -472,8 +813,799 @@ fn one_message_traps() -> String {
         )
     })
     .collect::<String>()
+        + &array_runtime()
         + &sb_runtime()
+        + &text_runtime()
         + &io_runtime()
+        + &map_runtime()
+        + &region_runtime()
+}
+
+/// The array library (ADR 0055), over the `{len, cap, data*}` header at
+/// a caller-given stride, like `ys_push_n`. `ys_insert(hdr, i, src*,
+/// stride, loc)` appends through `ys_push_n` — growth and all — then
+/// shifts the tail up one and copies the element into place; `i` may
+/// be the length. `ys_remove_at(hdr, i, stride)` closes the gap behind
+/// an already bounds-checked element. `ys_reverse(hdr, stride)` swaps
+/// elements end for end a byte at a time. `ys_sort(hdr, keys, order,
+/// stride)` is a stable bottom-up merge sort of element positions by a
+/// `keys` array — the array itself, or one a key function built — read
+/// as ints, floats (IEEE total order, as the oracle's `total_cmp`), or
+/// string descriptors; the elements are then copied back from a
+/// scratch copy in that order. The buffer stays the array's own, so it
+/// stays in the region it was born in (ADR 0061 decision 2).
+fn array_runtime() -> String {
+    format!(
+        "\
+{RT_ARR_INSERT}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # hdr
+\tpushq %r12                 # i
+\tpushq %r13                 # src
+\tpushq %r14                 # stride
+\tcmpq 0(%rdi), %rsi         # unsigned: a negative i is out too
+\tjbe .Lys_insert_go
+\tmovq 0(%rdi), %rax
+\tmovq %rsi, %rdi
+\tmovq %rax, %rsi
+\tmovq %r8, %rdx
+\tcall {TRAP_OOB}
+.Lys_insert_go:
+\tmovq %rdi, %rbx
+\tmovq %rsi, %r12
+\tmovq %rdx, %r13
+\tmovq %rcx, %r14
+\tmovq %rdx, %rsi
+\tmovq %rcx, %rdx
+\tcall {RT_PUSH_N}
+\tmovq 16(%rbx), %rsi
+\tmovq %r12, %rax
+\timulq %r14, %rax
+\taddq %rax, %rsi            # data + i * stride
+\tleaq (%rsi,%r14), %rdi
+\tmovq 0(%rbx), %rdx
+\tdecq %rdx
+\tsubq %r12, %rdx
+\timulq %r14, %rdx
+\tcall {RT_MEMMOVE}
+\tmovq 16(%rbx), %rdi
+\tmovq %r12, %rax
+\timulq %r14, %rax
+\taddq %rax, %rdi
+\tmovq %r13, %rsi
+\tmovq %r14, %rdx
+\tcall {RT_MEMCPY}
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_ARR_REMOVE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tdecq 0(%rdi)
+\tmovq 0(%rdi), %rax
+\tsubq %rsi, %rax            # elements behind i
+\timulq %rdx, %rax
+\timulq %rdx, %rsi
+\tmovq 16(%rdi), %rdi
+\taddq %rsi, %rdi
+\tleaq (%rdi,%rdx), %rsi
+\tmovq %rax, %rdx
+\tcall {RT_MEMMOVE}
+\tpopq %rbp
+\tret
+{RT_ARR_REVERSE}:
+\tmovq 0(%rdi), %rax
+\ttestq %rax, %rax
+\tje .Lys_reverse_ret
+\tmovq 16(%rdi), %rcx        # front element
+\tdecq %rax
+\timulq %rsi, %rax
+\tleaq (%rcx,%rax), %rdx     # back element
+.Lys_reverse_pair:
+\tcmpq %rdx, %rcx
+\tjae .Lys_reverse_ret
+\txorl %r8d, %r8d
+.Lys_reverse_byte:
+\tmovzbl (%rcx,%r8), %r9d
+\tmovzbl (%rdx,%r8), %r10d
+\tmovb %r10b, (%rcx,%r8)
+\tmovb %r9b, (%rdx,%r8)
+\tincq %r8
+\tcmpq %rsi, %r8
+\tjb .Lys_reverse_byte
+\taddq %rsi, %rcx
+\tsubq %rsi, %rdx
+\tjmp .Lys_reverse_pair
+.Lys_reverse_ret:
+\tret
+{RT_ARR_SORT}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # the keys' buffer
+\tpushq %r12                 # the key order
+\tpushq %r13                 # n
+\tpushq %r14                 # positions, sorted run by run
+\tpushq %r15                 # the merge target
+\tsubq $72, %rsp
+\tmovq %rdi, -48(%rbp)       # hdr
+\tmovq %rcx, -56(%rbp)       # stride
+\tmovq %rdx, %r12
+\tmovq 16(%rsi), %rbx
+\tmovq 0(%rsi), %r13
+\tcmpq 0(%rdi), %r13         # a key function may have shrunk the array
+\tcmovaq 0(%rdi), %r13
+\tcmpq $2, %r13
+\tjb .Lys_sort_ret
+\tleaq 0(,%r13,8), %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, %r14
+\tleaq 0(,%r13,8), %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, %r15
+\txorl %ecx, %ecx
+.Lys_sort_iota:
+\tmovq %rcx, (%r14,%rcx,8)
+\tincq %rcx
+\tcmpq %r13, %rcx
+\tjb .Lys_sort_iota
+\tmovq $1, -64(%rbp)         # run width
+.Lys_sort_pass:
+\tcmpq %r13, -64(%rbp)
+\tjae .Lys_sort_place
+\tmovq $0, -72(%rbp)         # lo
+.Lys_sort_run:
+\tmovq -72(%rbp), %rax
+\tcmpq %r13, %rax
+\tjae .Lys_sort_swap
+\tmovq %rax, %rcx
+\taddq -64(%rbp), %rcx
+\tcmpq %r13, %rcx
+\tcmovaq %r13, %rcx
+\tmovq %rcx, -80(%rbp)       # mid = min(lo + width, n)
+\taddq -64(%rbp), %rcx
+\tcmpq %r13, %rcx
+\tcmovaq %r13, %rcx
+\tmovq %rcx, -88(%rbp)       # hi = min(mid + width, n)
+\tmovq %rax, -96(%rbp)       # left cursor
+\tmovq -80(%rbp), %rcx
+\tmovq %rcx, -104(%rbp)      # right cursor
+\tmovq %rax, -112(%rbp)      # out cursor
+.Lys_sort_merge:
+\tmovq -112(%rbp), %rax
+\tcmpq -88(%rbp), %rax
+\tjae .Lys_sort_next
+\tmovq -96(%rbp), %rax
+\tcmpq -80(%rbp), %rax
+\tjae .Lys_sort_right
+\tmovq -104(%rbp), %rcx
+\tcmpq -88(%rbp), %rcx
+\tjae .Lys_sort_left
+\tmovq (%r14,%rax,8), %rdi
+\tmovq (%r14,%rcx,8), %rsi
+\tcall .Lys_sort_gt
+\ttestl %eax, %eax           # the right only when strictly less: stable
+\tjne .Lys_sort_right
+.Lys_sort_left:
+\tmovq -96(%rbp), %rax
+\tmovq (%r14,%rax,8), %rdx
+\tincq -96(%rbp)
+\tjmp .Lys_sort_put
+.Lys_sort_right:
+\tmovq -104(%rbp), %rax
+\tmovq (%r14,%rax,8), %rdx
+\tincq -104(%rbp)
+.Lys_sort_put:
+\tmovq -112(%rbp), %rax
+\tmovq %rdx, (%r15,%rax,8)
+\tincq -112(%rbp)
+\tjmp .Lys_sort_merge
+.Lys_sort_next:
+\tmovq -88(%rbp), %rax
+\tmovq %rax, -72(%rbp)
+\tjmp .Lys_sort_run
+.Lys_sort_swap:
+\txchgq %r14, %r15
+\tshlq $1, -64(%rbp)
+\tjmp .Lys_sort_pass
+.Lys_sort_place:
+\tmovq %r13, %rdi            # a scratch copy of the sorted part; the keys
+\timulq -56(%rbp), %rdi      # are done with
+\tcall {RT_ALLOC}
+\tmovq %rax, %rbx
+\tmovq %rax, %rdi
+\tmovq -48(%rbp), %rcx
+\tmovq 16(%rcx), %r15        # the array's own buffer, written in place
+\tmovq %r15, %rsi
+\tmovq %r13, %rdx
+\timulq -56(%rbp), %rdx
+\tcall {RT_MEMCPY}
+\txorl %r12d, %r12d
+.Lys_sort_copy:
+\tmovq %r12, %rdi
+\timulq -56(%rbp), %rdi
+\taddq %r15, %rdi
+\tmovq (%r14,%r12,8), %rsi
+\timulq -56(%rbp), %rsi
+\taddq %rbx, %rsi
+\tmovq -56(%rbp), %rdx
+\tcall {RT_MEMCPY}
+\tincq %r12
+\tcmpq %r13, %r12
+\tjb .Lys_sort_copy
+.Lys_sort_ret:
+\taddq $72, %rsp
+\tpopq %r15
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+# -- is key a greater than key b? positions in %rdi, %rsi; keys at %rbx
+.Lys_sort_gt:
+\tcmpq ${SORT_BY_STR}, %r12
+\tje .Lys_sort_gt_str
+\tmovq (%rbx,%rdi,8), %rax
+\tmovq (%rbx,%rsi,8), %rcx
+\tcmpq ${SORT_BY_FLOAT}, %r12
+\tjne .Lys_sort_gt_int
+\tmovq %rax, %rdx            # negative floats flip their magnitude bits:
+\tsarq $63, %rdx             # the bits then order as signed ints
+\tshrq $1, %rdx
+\txorq %rdx, %rax
+\tmovq %rcx, %rdx
+\tsarq $63, %rdx
+\tshrq $1, %rdx
+\txorq %rdx, %rcx
+.Lys_sort_gt_int:
+\tcmpq %rcx, %rax
+\tsetg %al
+\tmovzbl %al, %eax
+\tret
+.Lys_sort_gt_str:
+\tshlq $4, %rdi
+\taddq %rbx, %rdi
+\tshlq $4, %rsi
+\taddq %rbx, %rsi
+\tmovq 8(%rdi), %rax
+\tmovq 8(%rsi), %rcx
+\tpushq %rax
+\tpushq %rcx
+\tsubq $8, %rsp
+\tmovq %rax, %rdx
+\tcmpq %rcx, %rdx
+\tcmovaq %rcx, %rdx          # the common prefix first ...
+\tmovq 0(%rdi), %rdi
+\tmovq 0(%rsi), %rsi
+\tcall {RT_MEMCMP}
+\taddq $8, %rsp
+\tpopq %rcx
+\tpopq %rdx
+\ttestl %eax, %eax
+\tjne .Lys_sort_gt_byte
+\tcmpq %rcx, %rdx            # ... then the longer is greater
+\tseta %al
+\tmovzbl %al, %eax
+\tret
+.Lys_sort_gt_byte:
+\tsetg %al
+\tmovzbl %al, %eax
+\tret
+"
+    )
+}
+
+/// The string library (ADR 0054), over `{ptr, len}` descriptors and
+/// never assuming UTF-8. Searching is libc `memmem`; an empty needle
+/// is settled before it is called. `split` pieces and `trim` are views
+/// into the source; `replace`, `join`, and the case mappings allocate
+/// exactly the bytes they produce. Results that can miss fill a
+/// caller-provided `{tag, value}` optional, zeroed when null.
+fn text_runtime() -> String {
+    format!(
+        "\
+{RT_STR_FIND}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # dst optional {{tag, value}}
+\tpushq %r12                 # haystack start
+\tmovq %rdi, %rbx
+\tmovq 0(%rsi), %r12
+\tmovq $0, 0(%rbx)
+\tmovq $0, 8(%rbx)
+\tmovq 8(%rdx), %rcx
+\ttestq %rcx, %rcx           # an empty needle matches at 0
+\tje .Lys_find_hit
+\tmovq 0(%rdx), %rdx
+\tmovq 0(%rsi), %rdi
+\tmovq 8(%rsi), %rsi
+\tcall {RT_MEMMEM}
+\ttestq %rax, %rax
+\tje .Lys_find_ret
+\tsubq %r12, %rax
+\tmovq %rax, 8(%rbx)
+.Lys_find_hit:
+\tmovq $1, 0(%rbx)
+.Lys_find_ret:
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_STR_STARTS}:
+\tmovq 8(%rsi), %rdx
+\tcmpq 8(%rdi), %rdx
+\tja .Lys_affix_no
+\tmovq 0(%rdi), %rdi
+\tmovq 0(%rsi), %rsi
+\tjmp .Lys_affix_cmp
+{RT_STR_ENDS}:
+\tmovq 8(%rsi), %rdx
+\tmovq 8(%rdi), %rax
+\tcmpq %rax, %rdx
+\tja .Lys_affix_no
+\tsubq %rdx, %rax            # the suffix starts len - plen in
+\tmovq 0(%rdi), %rdi
+\taddq %rax, %rdi
+\tmovq 0(%rsi), %rsi
+.Lys_affix_cmp:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tcall {RT_MEMCMP}
+\tpopq %rbp
+\ttestl %eax, %eax
+\tsete %al
+\tmovzbl %al, %eax
+\tret
+.Lys_affix_no:
+\txorl %eax, %eax
+\tret
+{RT_STR_TRIM}:
+\tmovq 0(%rsi), %rax         # first byte kept
+\tmovq 8(%rsi), %rcx
+\taddq %rax, %rcx            # one past the last byte kept
+.Lys_trim_front:
+\tcmpq %rcx, %rax
+\tjae .Lys_trim_done
+\tmovzbl (%rax), %edx
+\tcmpl $32, %edx
+\tje .Lys_trim_front_next
+\tsubl $9, %edx              # \\t \\n \\v \\f \\r: C's isspace
+\tcmpl $4, %edx
+\tja .Lys_trim_back
+.Lys_trim_front_next:
+\tincq %rax
+\tjmp .Lys_trim_front
+.Lys_trim_back:
+\tmovzbl -1(%rcx), %edx
+\tcmpl $32, %edx
+\tje .Lys_trim_back_next
+\tsubl $9, %edx
+\tcmpl $4, %edx
+\tja .Lys_trim_done
+.Lys_trim_back_next:
+\tdecq %rcx
+\tjmp .Lys_trim_back
+.Lys_trim_done:
+\tmovq %rax, 0(%rdi)
+\tsubq %rax, %rcx
+\tmovq %rcx, 8(%rdi)
+\tret
+{RT_STR_CASE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # dst descriptor
+\tpushq %r12                 # source descriptor
+\tpushq %r13                 # upper?
+\tpushq %r14
+\tmovq %rdi, %rbx
+\tmovq %rsi, %r12
+\tmovq %rdx, %r13
+\tmovq 8(%rsi), %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, 0(%rbx)
+\tmovq 8(%r12), %rcx
+\tmovq %rcx, 8(%rbx)
+\tmovq 0(%r12), %rsi
+\tmovl $65, %r8d             # 'A'..'Z' gain 32 ...
+\tmovl $32, %r9d
+\ttestq %r13, %r13
+\tje .Lys_case_start
+\tmovl $97, %r8d             # ... or 'a'..'z' lose it
+\tmovl $-32, %r9d
+.Lys_case_start:
+\txorl %edx, %edx
+.Lys_case_loop:
+\tcmpq %rcx, %rdx
+\tjae .Lys_case_done
+\tmovzbl (%rsi,%rdx), %r10d
+\tmovl %r10d, %r11d
+\tsubl %r8d, %r11d
+\tcmpl $25, %r11d
+\tja .Lys_case_put
+\taddl %r9d, %r10d
+.Lys_case_put:
+\tmovb %r10b, (%rax,%rdx)
+\tincq %rdx
+\tjmp .Lys_case_loop
+.Lys_case_done:
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_STR_SPLIT}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # cursor in the source
+\tpushq %r12                 # one past its end
+\tpushq %r13                 # separator descriptor
+\tpushq %r14                 # the result's header
+\tpushq %r15                 # piece count, then the write cursor
+\tsubq $8, %rsp
+\tcmpq $0, 8(%rsi)
+\tjne .Lys_split_go
+\tmovq %rdx, %rdi
+\tcall {TRAP_SPLIT0}
+.Lys_split_go:
+\tmovq %rsi, %r13
+\tmovq 0(%rdi), %rbx
+\tmovq 8(%rdi), %r12
+\taddq %rbx, %r12
+\tmovl $1, %r15d             # n separators cut n + 1 pieces
+\tmovq %rbx, %r14
+.Lys_split_count:
+\tmovq %r14, %rdi
+\tmovq %r12, %rsi
+\tsubq %r14, %rsi
+\tmovq 0(%r13), %rdx
+\tmovq 8(%r13), %rcx
+\tcall {RT_MEMMEM}
+\ttestq %rax, %rax
+\tje .Lys_split_alloc
+\tincq %r15
+\tmovq %rax, %r14
+\taddq 8(%r13), %r14
+\tjmp .Lys_split_count
+.Lys_split_alloc:
+\tmovl $24, %edi
+\tcall {RT_ALLOC}
+\tmovq %rax, %r14
+\tmovq %r15, 0(%r14)
+\tmovq %r15, 8(%r14)
+\tmovq %r15, %rdi
+\tshlq $4, %rdi              # string descriptors are 16 bytes
+\tcall {RT_ALLOC}
+\tmovq %rax, 16(%r14)
+\tmovq %rax, %r15
+.Lys_split_fill:
+\tmovq %rbx, %rdi
+\tmovq %r12, %rsi
+\tsubq %rbx, %rsi
+\tmovq 0(%r13), %rdx
+\tmovq 8(%r13), %rcx
+\tcall {RT_MEMMEM}
+\ttestq %rax, %rax
+\tje .Lys_split_last
+\tmovq %rbx, 0(%r15)         # the piece is a view: {{ptr, len}}
+\tmovq %rax, %rcx
+\tsubq %rbx, %rcx
+\tmovq %rcx, 8(%r15)
+\taddq $16, %r15
+\tmovq %rax, %rbx
+\taddq 8(%r13), %rbx
+\tjmp .Lys_split_fill
+.Lys_split_last:
+\tmovq %rbx, 0(%r15)
+\tmovq %r12, %rcx
+\tsubq %rbx, %rcx
+\tmovq %rcx, 8(%r15)
+\tmovq %r14, %rax
+\taddq $8, %rsp
+\tpopq %r15
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_STR_REPLACE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # cursor in the source
+\tpushq %r12                 # one past its end
+\tpushq %r13                 # pattern descriptor
+\tpushq %r14                 # replacement descriptor
+\tpushq %r15                 # match count, then the write cursor
+\tpushq %rdi                 # -48: dst descriptor
+\tpushq 0(%rsi)              # -56: the source's start
+\tsubq $8, %rsp              # -64: the current match
+\tcmpq $0, 8(%rdx)
+\tjne .Lys_replace_go
+\tmovq %r8, %rdi
+\tcall {TRAP_REPLACE0}
+.Lys_replace_go:
+\tmovq %rdx, %r13
+\tmovq %rcx, %r14
+\tmovq 0(%rsi), %rbx
+\tmovq 8(%rsi), %r12
+\taddq %rbx, %r12
+\txorl %r15d, %r15d
+.Lys_replace_count:
+\tmovq %rbx, %rdi
+\tmovq %r12, %rsi
+\tsubq %rbx, %rsi
+\tmovq 0(%r13), %rdx
+\tmovq 8(%r13), %rcx
+\tcall {RT_MEMMEM}
+\ttestq %rax, %rax
+\tje .Lys_replace_alloc
+\tincq %r15
+\tmovq %rax, %rbx
+\taddq 8(%r13), %rbx
+\tjmp .Lys_replace_count
+.Lys_replace_alloc:
+\tmovq 8(%r14), %rax         # len + count * (to.len - from.len)
+\tsubq 8(%r13), %rax
+\timulq %r15, %rax
+\taddq %r12, %rax
+\tsubq -56(%rbp), %rax
+\tmovq -48(%rbp), %rcx
+\tmovq %rax, 8(%rcx)
+\tmovq %rax, %rdi
+\tcall {RT_ALLOC}
+\tmovq -48(%rbp), %rcx
+\tmovq %rax, 0(%rcx)
+\tmovq %rax, %r15
+\tmovq -56(%rbp), %rbx
+.Lys_replace_fill:
+\tmovq %rbx, %rdi
+\tmovq %r12, %rsi
+\tsubq %rbx, %rsi
+\tmovq 0(%r13), %rdx
+\tmovq 8(%r13), %rcx
+\tcall {RT_MEMMEM}
+\ttestq %rax, %rax
+\tje .Lys_replace_tail
+\tmovq %rax, -64(%rbp)
+\tmovq %r15, %rdi            # the bytes before the match ...
+\tmovq %rbx, %rsi
+\tmovq %rax, %rdx
+\tsubq %rbx, %rdx
+\taddq %rdx, %r15
+\tcall {RT_MEMCPY}
+\tmovq %r15, %rdi            # ... then the replacement
+\tmovq 0(%r14), %rsi
+\tmovq 8(%r14), %rdx
+\taddq %rdx, %r15
+\tcall {RT_MEMCPY}
+\tmovq -64(%rbp), %rbx
+\taddq 8(%r13), %rbx
+\tjmp .Lys_replace_fill
+.Lys_replace_tail:
+\tmovq %r15, %rdi
+\tmovq %rbx, %rsi
+\tmovq %r12, %rdx
+\tsubq %rbx, %rdx
+\tcall {RT_MEMCPY}
+\tleaq -40(%rbp), %rsp
+\tpopq %r15
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_STR_JOIN}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # the next part's descriptor
+\tpushq %r12                 # parts left
+\tpushq %r13                 # separator descriptor
+\tpushq %r14                 # dst descriptor
+\tpushq %r15                 # write cursor
+\tsubq $8, %rsp
+\tmovq %rdi, %r14
+\tmovq %rdx, %r13
+\tmovq 0(%rsi), %r12
+\tmovq 16(%rsi), %rbx
+\txorl %eax, %eax            # total: every part, n - 1 separators
+\ttestq %r12, %r12
+\tje .Lys_join_sized
+\tmovq %r12, %rax
+\tdecq %rax
+\timulq 8(%r13), %rax
+\txorl %ecx, %ecx
+.Lys_join_sum:
+\tmovq %rcx, %rdx
+\tshlq $4, %rdx
+\taddq 8(%rbx,%rdx), %rax
+\tincq %rcx
+\tcmpq %r12, %rcx
+\tjb .Lys_join_sum
+.Lys_join_sized:
+\tmovq %rax, 8(%r14)
+\tmovq %rax, %rdi
+\tcall {RT_ALLOC}
+\tmovq %rax, 0(%r14)
+\tmovq %rax, %r15
+\ttestq %r12, %r12
+\tje .Lys_join_done
+.Lys_join_loop:
+\tmovq %r15, %rdi
+\tmovq 0(%rbx), %rsi
+\tmovq 8(%rbx), %rdx
+\taddq %rdx, %r15
+\tcall {RT_MEMCPY}
+\taddq $16, %rbx
+\tdecq %r12
+\tje .Lys_join_done
+\tmovq %r15, %rdi
+\tmovq 0(%r13), %rsi
+\tmovq 8(%r13), %rdx
+\taddq %rdx, %r15
+\tcall {RT_MEMCPY}
+\tjmp .Lys_join_loop
+.Lys_join_done:
+\taddq $8, %rsp
+\tpopq %r15
+\tpopq %r14
+\tpopq %r13
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_PARSE_INT}:
+\tmovq $0, 0(%rdi)
+\tmovq $0, 8(%rdi)
+\tmovq 0(%rsi), %r8
+\tmovq 8(%rsi), %r9
+\ttestq %r9, %r9
+\tje .Lys_pint_ret
+\txorl %ecx, %ecx
+\txorl %r10d, %r10d          # negative?
+\tmovzbl (%r8), %edx
+\tcmpl $45, %edx             # '-'
+\tjne .Lys_pint_plus
+\tmovl $1, %r10d
+\tincq %rcx
+\tjmp .Lys_pint_digits
+.Lys_pint_plus:
+\tcmpl $43, %edx             # '+'
+\tjne .Lys_pint_digits
+\tincq %rcx
+.Lys_pint_digits:
+\tcmpq %r9, %rcx             # a sign alone is no number
+\tjae .Lys_pint_ret
+\txorl %eax, %eax            # accumulated negated: MIN fits, MAX+1 not
+.Lys_pint_loop:
+\tmovzbl (%r8,%rcx), %edx
+\tsubl $48, %edx
+\tcmpl $9, %edx
+\tja .Lys_pint_ret
+\timulq $10, %rax, %rax
+\tjo .Lys_pint_ret
+\tsubq %rdx, %rax
+\tjo .Lys_pint_ret
+\tincq %rcx
+\tcmpq %r9, %rcx
+\tjb .Lys_pint_loop
+\ttestq %r10, %r10
+\tjne .Lys_pint_store
+\tnegq %rax
+\tjo .Lys_pint_ret
+.Lys_pint_store:
+\tmovq %rax, 8(%rdi)
+\tmovq $1, 0(%rdi)
+.Lys_pint_ret:
+\tret
+{RT_PARSE_FLOAT}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # dst optional {{tag, value}}
+\tpushq %r12                 # source descriptor
+\tmovq %rdi, %rbx
+\tmovq %rsi, %r12
+\tmovq $0, 0(%rbx)
+\tmovq $0, 8(%rbx)
+# -- validate [+-]? (d+ (. d*)? | . d+) ([eE] [+-]? d+)? first: strtod
+#    alone would also take whitespace, inf, nan, and hex
+\tmovq 0(%rsi), %r8
+\tmovq 8(%rsi), %r9
+\txorl %ecx, %ecx
+\txorl %r10d, %r10d          # mantissa digits seen
+\ttestq %r9, %r9
+\tje .Lys_pflt_ret
+\tmovzbl (%r8), %edx
+\tcmpl $43, %edx
+\tje .Lys_pflt_sign
+\tcmpl $45, %edx
+\tjne .Lys_pflt_whole
+.Lys_pflt_sign:
+\tincq %rcx
+.Lys_pflt_whole:
+\tcmpq %r9, %rcx
+\tjae .Lys_pflt_mant
+\tmovzbl (%r8,%rcx), %edx
+\tsubl $48, %edx
+\tcmpl $9, %edx
+\tja .Lys_pflt_dot
+\tincq %r10
+\tincq %rcx
+\tjmp .Lys_pflt_whole
+.Lys_pflt_dot:
+\tcmpl $-2, %edx             # '.'
+\tjne .Lys_pflt_mant
+\tincq %rcx
+.Lys_pflt_frac:
+\tcmpq %r9, %rcx
+\tjae .Lys_pflt_mant
+\tmovzbl (%r8,%rcx), %edx
+\tsubl $48, %edx
+\tcmpl $9, %edx
+\tja .Lys_pflt_mant
+\tincq %r10
+\tincq %rcx
+\tjmp .Lys_pflt_frac
+.Lys_pflt_mant:
+\ttestq %r10, %r10
+\tje .Lys_pflt_ret
+\tcmpq %r9, %rcx
+\tjae .Lys_pflt_convert
+\tmovzbl (%r8,%rcx), %edx
+\torl $32, %edx              # 'E' folds onto 'e'
+\tcmpl $101, %edx
+\tjne .Lys_pflt_ret
+\tincq %rcx
+\tcmpq %r9, %rcx
+\tjae .Lys_pflt_ret
+\tmovzbl (%r8,%rcx), %edx
+\tcmpl $43, %edx
+\tje .Lys_pflt_esign
+\tcmpl $45, %edx
+\tjne .Lys_pflt_exp
+.Lys_pflt_esign:
+\tincq %rcx
+.Lys_pflt_exp:
+\txorl %r10d, %r10d          # exponent digits seen
+.Lys_pflt_exp_loop:
+\tcmpq %r9, %rcx
+\tjae .Lys_pflt_exp_end
+\tmovzbl (%r8,%rcx), %edx
+\tsubl $48, %edx
+\tcmpl $9, %edx
+\tja .Lys_pflt_ret
+\tincq %r10
+\tincq %rcx
+\tjmp .Lys_pflt_exp_loop
+.Lys_pflt_exp_end:
+\ttestq %r10, %r10
+\tje .Lys_pflt_ret
+.Lys_pflt_convert:
+\tleaq 1(%r9), %rdi          # strtod needs NUL termination
+\tcall {RT_ALLOC}
+\tmovq %rax, %rdi
+\tmovq 0(%r12), %rsi
+\tmovq 8(%r12), %rdx
+\tcall {RT_MEMCPY}
+\tmovq 8(%r12), %rcx
+\tmovb $0, (%rax,%rcx)
+\tmovq %rax, %rdi
+\txorl %esi, %esi
+\tcall {RT_STRTOD}
+\tmovq %xmm0, 8(%rbx)
+\tmovq $1, 0(%rbx)
+.Lys_pflt_ret:
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+"
+    )
 }
 
 /// The world interface (ADR 0031). A file handle is a heap box
@@ -502,14 +1634,14 @@ fn io_runtime() -> String {
 \tmovq $1, %r12
 .Lys_args_hdr:
 \tmovq $24, %rdi             # array header {{len, cap, data*}}
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %rax, %r14
 \tleaq -1(%r12), %rax        # n = argc - 1: argv[0] is not an arg
 \tmovq %rax, 0(%r14)
 \tmovq %rax, 8(%r14)
 \tshlq $4, %rax              # string descriptors are 16 bytes
 \tmovq %rax, %rdi
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %rax, 16(%r14)
 \tmovq %rax, %r15
 .Lys_args_loop:
@@ -560,7 +1692,7 @@ fn io_runtime() -> String {
 .Lys_open_path:
 \tmovq 8(%rbx), %rdi         # fopen needs NUL termination; a path
 \tincq %rdi                  # with an embedded NUL can't name a file
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %rax, %r13
 \tmovq %rax, %rdi
 \tmovq 0(%rbx), %rsi
@@ -579,7 +1711,7 @@ fn io_runtime() -> String {
 \tje .Lys_open_fail
 \tmovq %rax, %r14
 \tmovq $16, %rdi             # the handle box {{FILE*, closed}}
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %r14, 0(%rax)
 \tmovq $0, 8(%rax)
 \tjmp .Lys_open_ret
@@ -613,7 +1745,7 @@ fn io_runtime() -> String {
 \tcall {TRAP_READSIZE}
 .Lys_read_sized:
 \tmovq %r12, %rdi
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %rax, %r14
 \tmovq %rax, %rdi            # fread(buf, 1, max, f): loops short
 \tmovl $1, %esi              # reads internally — the oracle mirrors
@@ -677,7 +1809,7 @@ fn io_runtime() -> String {
 \tdecq %r13                  # an empty line is a 0-length string
 .Lys_rl_copy:
 \tmovq %r13, %rdi
-\tcall {RT_MALLOC}
+\tcall {RT_ALLOC}
 \tmovq %rax, %r14
 \tmovq %rax, %rdi
 \tleaq .Lys_rl_buf(%rip), %rax
@@ -769,6 +1901,212 @@ fn io_runtime() -> String {
     )
 }
 
+/// Maps (ADR 0042). A handle points at a `{len, cap, entries*, icap,
+/// index*, stride}` header: entries `[hash, key…, value…]` sit in
+/// insertion order in a doubling buffer, and a power-of-two index of
+/// entry positions (-1 = empty) is probed linearly from `hash & mask`.
+/// The load factor stays ≤ 1/2, so every probe reaches an empty slot.
+/// `ys_map_probe` yields one hash match per call and advances the
+/// caller's cursor word — lowered code confirms each candidate with
+/// the type's own equality and re-probes on a collision. Removal
+/// shifts later entries down and rebuilds the index: order is
+/// preserved, and iteration stays positional like the array loop's.
+fn map_runtime() -> String {
+    format!(
+        "\
+{RT_HASH_WORD}:
+\tmovq %rdi, %rax
+\tmovabsq $0x100000001b3, %r8 # FNV prime
+\tmovl $8, %ecx
+.Lys_hash_word_loop:
+\tmovzbl %sil, %edx
+\txorq %rdx, %rax
+\timulq %r8, %rax
+\tshrq $8, %rsi
+\tdecl %ecx
+\tjne .Lys_hash_word_loop
+\tret
+{RT_HASH_BYTES}:
+\tmovq %rdi, %rax
+\tmovabsq $0x100000001b3, %r8
+\ttestq %rdx, %rdx
+\tje .Lys_hash_bytes_done
+.Lys_hash_bytes_loop:
+\tmovzbl (%rsi), %ecx
+\txorq %rcx, %rax
+\timulq %r8, %rax
+\tincq %rsi
+\tdecq %rdx
+\tjne .Lys_hash_bytes_loop
+.Lys_hash_bytes_done:
+\tret
+{RT_MAP_NEW}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %rdi
+\tmovl $48, %edi
+\tcall {RT_ALLOC}
+\tmovq %rax, %rbx
+\tpopq %rdi
+\tmovq %rdi, 40(%rbx)
+\tmovq $0, 0(%rbx)
+\tmovq $0, 8(%rbx)
+\tmovq $0, 24(%rbx)          # icap 0: the index allocates on first add
+\tpushq %rbx                 # keep the calls aligned
+\txorl %edi, %edi            # empty buffers in the map's own region,
+\tcall {RT_ALLOC}            # so they grow there (ADR 0061)
+\tmovq %rax, 16(%rbx)
+\txorl %edi, %edi
+\tcall {RT_ALLOC}
+\tmovq %rax, 32(%rbx)
+\tmovq %rbx, %rax
+\taddq $8, %rsp
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_MAP_PROBE}:
+\tmovq 24(%rdi), %r8
+\ttestq %r8, %r8
+\tje .Lys_map_probe_miss
+\tdecq %r8                   # mask
+\tmovq (%rdx), %rcx          # cursor
+.Lys_map_probe_loop:
+\tmovq %rcx, %rax
+\tandq %r8, %rax
+\tincq %rcx
+\tmovq 32(%rdi), %r9
+\tmovq (%r9,%rax,8), %rax
+\tcmpq $-1, %rax
+\tje .Lys_map_probe_miss
+\timulq 40(%rdi), %rax
+\taddq 16(%rdi), %rax
+\tcmpq %rsi, 0(%rax)
+\tjne .Lys_map_probe_loop
+\tmovq %rcx, (%rdx)          # resume past this candidate
+\tret
+.Lys_map_probe_miss:
+\txorl %eax, %eax
+\tret
+{RT_MAP_ADD}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %r12
+\tmovq %rdi, %rbx
+\tmovq %rsi, %r12
+\tmovq 0(%rbx), %rax
+\tcmpq 8(%rbx), %rax
+\tjb .Lys_map_add_store
+\tmovq 8(%rbx), %rcx
+\ttestq %rcx, %rcx
+\tjne .Lys_map_add_double
+\tmovq $2, %rcx
+.Lys_map_add_double:
+\taddq %rcx, %rcx
+\tmovq %rcx, 8(%rbx)
+\tmovq %rcx, %rsi
+\timulq 40(%rbx), %rsi
+\tmovq 16(%rbx), %rdi
+\tcall {RT_REALLOC}
+\tmovq %rax, 16(%rbx)
+.Lys_map_add_store:
+\tmovq 0(%rbx), %rax
+\timulq 40(%rbx), %rax
+\taddq 16(%rbx), %rax
+\tmovq %r12, 0(%rax)         # the entry's hash word
+\tincq 0(%rbx)
+\tmovq 0(%rbx), %rcx
+\taddq %rcx, %rcx
+\tcmpq 24(%rbx), %rcx
+\tjbe .Lys_map_add_place
+\tmovq 24(%rbx), %rcx        # over half full: double (min 8), rebuild
+\taddq %rcx, %rcx
+\tcmpq $8, %rcx
+\tjae .Lys_map_add_grow
+\tmovq $8, %rcx
+.Lys_map_add_grow:
+\tmovq %rcx, 24(%rbx)
+\tleaq 0(,%rcx,8), %rsi
+\tmovq 32(%rbx), %rdi
+\tcall {RT_REALLOC}
+\tmovq %rax, 32(%rbx)
+\tmovq %rbx, %rdi
+\tcall .Lys_map_rebuild
+\tjmp .Lys_map_add_done
+.Lys_map_add_place:
+\tmovq %rbx, %rdi
+\tmovq 0(%rbx), %rsi
+\tdecq %rsi
+\tcall .Lys_map_place
+.Lys_map_add_done:
+\tmovq 0(%rbx), %rax
+\tdecq %rax
+\timulq 40(%rbx), %rax
+\taddq 16(%rbx), %rax
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_MAP_DEL}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rdi
+\tpushq %rsi
+\tmovq 0(%rdi), %rdx
+\timulq 40(%rdi), %rdx
+\taddq 16(%rdi), %rdx        # end of the live entries
+\tmovq %rsi, %rcx
+\taddq 40(%rdi), %rcx        # the next entry
+\tsubq %rcx, %rdx
+\tmovq %rsi, %rdi            # memmove(entry, next, end - next)
+\tmovq %rcx, %rsi
+\tcall {RT_MEMMOVE}
+\tpopq %rsi
+\tpopq %rdi
+\tdecq 0(%rdi)
+\tcall .Lys_map_rebuild
+\tpopq %rbp
+\tret
+.Lys_map_place:                # (hdr, pos): claim the first empty slot
+\tmovq %rsi, %rax
+\timulq 40(%rdi), %rax
+\taddq 16(%rdi), %rax
+\tmovq 0(%rax), %rcx
+\tmovq 24(%rdi), %r8
+\tdecq %r8
+\tmovq 32(%rdi), %rdx
+.Lys_map_place_loop:
+\tandq %r8, %rcx
+\tcmpq $-1, (%rdx,%rcx,8)
+\tje .Lys_map_place_store
+\tincq %rcx
+\tjmp .Lys_map_place_loop
+.Lys_map_place_store:
+\tmovq %rsi, (%rdx,%rcx,8)
+\tret
+.Lys_map_rebuild:              # (hdr): clear the index, re-place all
+\tmovq 32(%rdi), %rdx
+\tmovq 24(%rdi), %rcx
+.Lys_map_rebuild_clear:
+\tdecq %rcx
+\tmovq $-1, (%rdx,%rcx,8)
+\ttestq %rcx, %rcx
+\tjne .Lys_map_rebuild_clear
+\txorl %r9d, %r9d
+.Lys_map_rebuild_loop:
+\tcmpq 0(%rdi), %r9
+\tjae .Lys_map_rebuild_done
+\tmovq %r9, %rsi
+\tcall .Lys_map_place
+\tincq %r9
+\tjmp .Lys_map_rebuild_loop
+.Lys_map_rebuild_done:
+\tret
+"
+    )
+}
+
 /// The shared text builder (ADR 0029): one static `{len, cap, ptr}`
 /// byte buffer that the show routines, `ys_fmt_f64`, and `ys_sb_int`
 /// append into; `print` and `string()` reset it, run producers, then
@@ -776,6 +2114,168 @@ fn io_runtime() -> String {
 /// single-threaded (the ADR 0027 scratch precedent) — and growth
 /// mirrors the array runtime: doubling, realloc from NULL, never freed
 /// (ADR 0015).
+/// The region allocator (ADR 0061). Every block carries a 16-byte
+/// header `{size, region*}` in front of the pointer handed out, so
+/// `ys_realloc` grows a buffer in the region it was born in — an outer
+/// array pushed to inside an arena stays outer. Region 0 is libc's
+/// heap: `ys_alloc` mallocs there outside any arena, and `ys_realloc`
+/// of a NULL buffer (the text builder's first growth) lands there too.
+/// An arena's region is a malloc'd record `{prev, chunks, cursor,
+/// end}` on a stack whose top is `.Lys_region`: blocks bump-allocate
+/// from 64 KiB chunks (a larger block gets a chunk of its own), a
+/// region realloc copies into a fresh block unless the old one already
+/// fits, and `ys_arena_leave` frees the chunks and the record at once
+/// — no per-block free anywhere.
+fn region_runtime() -> String {
+    format!(
+        "\
+{RT_ALLOC}:
+\tmovq .Lys_region(%rip), %rsi
+\ttestq %rsi, %rsi
+\tjne .Lys_region_alloc         # tail call: (size, region)
+.Lys_alloc_heap:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rdi
+\tpushq %rdi                 # keep the call aligned
+\taddq $16, %rdi
+\tcall {RT_LIBC_MALLOC}
+\tpopq %rdi
+\tpopq %rdi
+\tmovq %rdi, 0(%rax)         # header {{size, region 0}}
+\tmovq $0, 8(%rax)
+\taddq $16, %rax
+\tpopq %rbp
+\tret
+.Lys_region_alloc:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx                 # region record
+\tpushq %r12                 # block bytes: header + size rounded to 16
+\tmovq %rsi, %rbx
+\tleaq 31(%rdi), %r12
+\tandq $-16, %r12
+\tmovq 16(%rbx), %rax        # cursor
+\tleaq (%rax,%r12), %rcx
+\tcmpq 24(%rbx), %rcx
+\tjbe .Lys_region_bump       # a fresh record's 0/0 never fits
+\tleaq 16(%r12), %rdi        # chunk = link + block, at least 64 KiB
+\tcmpq $65536, %rdi
+\tjae .Lys_region_chunk
+\tmovq $65536, %rdi
+.Lys_region_chunk:
+\tpushq %rdi
+\tpushq %rdi
+\tcall {RT_LIBC_MALLOC}
+\tpopq %rdi
+\tpopq %rdi
+\tmovq 8(%rbx), %rcx         # link it into the chunk list
+\tmovq %rcx, 0(%rax)
+\tmovq %rax, 8(%rbx)
+\tleaq (%rax,%rdi), %rcx
+\tmovq %rcx, 24(%rbx)
+\taddq $16, %rax
+.Lys_region_bump:
+\tleaq (%rax,%r12), %rcx
+\tmovq %rcx, 16(%rbx)
+\tleaq -16(%r12), %rcx
+\tmovq %rcx, 0(%rax)         # header {{capacity, region}}
+\tmovq %rbx, 8(%rax)
+\taddq $16, %rax
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_REALLOC}:
+\ttestq %rdi, %rdi
+\tjne .Lys_realloc_block
+\tmovq %rsi, %rdi
+\tjmp .Lys_alloc_heap
+.Lys_realloc_block:
+\tmovq -8(%rdi), %rax
+\ttestq %rax, %rax
+\tjne .Lys_realloc_region
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rsi
+\tpushq %rsi
+\tsubq $16, %rdi             # libc's block starts at the header
+\taddq $16, %rsi
+\tcall {RT_LIBC_REALLOC}
+\tpopq %rsi
+\tpopq %rsi
+\tmovq %rsi, 0(%rax)
+\taddq $16, %rax
+\tpopq %rbp
+\tret
+.Lys_realloc_region:
+\tcmpq -16(%rdi), %rsi
+\tja .Lys_realloc_move
+\tmovq %rdi, %rax            # the block already fits
+\tret
+.Lys_realloc_move:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %r12
+\tmovq %rdi, %rbx
+\tmovq %rsi, %rdi
+\tmovq %rax, %rsi
+\tcall .Lys_region_alloc
+\tmovq %rax, %r12
+\tmovq %rax, %rdi
+\tmovq %rbx, %rsi
+\tmovq -16(%rbx), %rdx
+\tcall {RT_MEMCPY}
+\tmovq %r12, %rax
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+{RT_ARENA_ENTER}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tmovl $32, %edi
+\tcall {RT_LIBC_MALLOC}
+\tmovq .Lys_region(%rip), %rcx
+\tmovq %rcx, 0(%rax)         # {{prev, chunks, cursor, end}}
+\tmovq $0, 8(%rax)
+\tmovq $0, 16(%rax)
+\tmovq $0, 24(%rax)
+\tmovq %rax, .Lys_region(%rip)
+\tpopq %rbp
+\tret
+{RT_ARENA_LEAVE}:
+\tpushq %rbp
+\tmovq %rsp, %rbp
+\tpushq %rbx
+\tpushq %r12
+\tmovq .Lys_region(%rip), %rbx
+\tmovq 0(%rbx), %rax
+\tmovq %rax, .Lys_region(%rip)
+\tmovq 8(%rbx), %r12
+.Lys_arena_free:
+\ttestq %r12, %r12
+\tje .Lys_arena_gone
+\tmovq %r12, %rdi
+\tmovq 0(%r12), %r12
+\tcall {RT_FREE}
+\tjmp .Lys_arena_free
+.Lys_arena_gone:
+\tmovq %rbx, %rdi
+\tcall {RT_FREE}
+\tpopq %r12
+\tpopq %rbx
+\tpopq %rbp
+\tret
+\t.section .bss
+.Lys_region:
+\t.skip 8                    # the innermost open region; 0 outside any
+\t.text
+"
+    )
+}
+
 fn sb_runtime() -> String {
     format!(
         "\
@@ -827,16 +2327,20 @@ fn sb_runtime() -> String {
 \trep movsb
 \tpopq %rbp
 \tret
+{RT_SB_U64}:
+\tleaq {FMT_U64_RAW}(%rip), %rdx
+\tjmp .Lys_sb_num
 {RT_SB_INT}:
+\tleaq {FMT_INT_RAW}(%rip), %rdx
+.Lys_sb_num:
 \tpushq %rbp
 \tmovq %rsp, %rbp
-\tmovq %rdi, %rcx            # snprintf(scratch, 24, \"%ld\", value)
-\tleaq {FMT_INT_RAW}(%rip), %rdx
+\tmovq %rdi, %rcx            # snprintf(scratch, 24, \"%ld\" or \"%lu\", value)
 \tmovl $24, %esi
 \tleaq .Lys_sb_scratch(%rip), %rdi
 \txorl %eax, %eax
 \tcall {RT_SNPRINTF}
-\tmovl %eax, %esi            # the count — an i64 is at most 20 chars
+\tmovl %eax, %esi            # the count — a 64-bit value is at most 20 chars
 \tleaq .Lys_sb_scratch(%rip), %rdi
 \tpopq %rbp
 \tjmp {RT_SB_APPEND}
@@ -1098,9 +2602,47 @@ fn fmt_f64_runtime() -> String {
     )
 }
 
+/// Zeroed storage for every module var (ADR 0048), sized by its kind;
+/// the initializer fills it before `main` runs.
+fn bss(res: &Resolutions) -> String {
+    let mut globals: Vec<_> = res.globals.iter().collect();
+    globals.sort_by(|a, b| a.0.cmp(b.0));
+    let mut out = String::new();
+    for ((module, name), ty) in globals {
+        let words = crate::ir::storage_words(ty, res).unwrap_or(1);
+        let _ = writeln!(
+            out,
+            "\t.balign 8\n{}:\n\t.zero {}",
+            global_label(*module, name),
+            8 * words
+        );
+    }
+    if out.is_empty() {
+        return out;
+    }
+    format!("\t.section .bss\n{out}")
+}
+
 /// Static formats for `print` (printf needs NUL-terminated formats; ys
-/// strings are length-carried, hence `%.*s`).
-fn rodata() -> String {
+/// strings are length-carried, hence `%.*s`), then the scalar module
+/// consts computed at compile time (ADR 0047) as aligned words.
+fn rodata(res: &Resolutions) -> String {
+    let mut consts: Vec<_> = res.consts.iter().collect();
+    consts.sort_by(|a, b| a.0.cmp(b.0));
+    let mut words = String::new();
+    for ((module, name), value) in consts {
+        let bits = match value {
+            ConstValue::Int(n) => *n,
+            ConstValue::Float(f) => f.to_bits() as i64,
+            ConstValue::Bool(b) => i64::from(*b),
+            ConstValue::Str(_) => continue,
+        };
+        let _ = writeln!(
+            words,
+            "\t.balign 8\n{}:\n\t.quad {bits}",
+            const_label(*module, name)
+        );
+    }
     format!(
         "\
 \t.section .rodata
@@ -1112,6 +2654,8 @@ fn rodata() -> String {
 \t.string \"%.*s\\n\"
 {FMT_INT_RAW}:
 \t.string \"%ld\"
+{FMT_U64_RAW}:
+\t.string \"%lu\"
 {TRUE_S}:
 \t.string \"true\"
 {FALSE_S}:
@@ -1124,6 +2668,10 @@ fn rodata() -> String {
 \t.string \"error: %.*s\\n\"
 {FMT_TRAP_OOB}:
 \t.string \"error: index %ld out of bounds (length %ld)\\n --> %s\\n\"
+{FMT_TRAP_SHIFT}:
+\t.string \"error: shift count %ld out of range\\n --> %s\\n\"
+{FMT_TRAP_SLICE}:
+\t.string \"error: slice %ld..%ld out of bounds (length %ld)\\n --> %s\\n\"
 {MSG_DIV0}:
 \t.string \"division by zero\"
 {MSG_OVERFLOW}:
@@ -1134,6 +2682,12 @@ fn rodata() -> String {
 \t.string \"operation on closed file\"
 {MSG_READSIZE}:
 \t.string \"read size must be positive\"
-"
+{MSG_STEP0}:
+\t.string \"range step must not be zero\"
+{MSG_SPLIT0}:
+\t.string \"'split' separator must not be empty\"
+{MSG_REPLACE0}:
+\t.string \"'replace' pattern must not be empty\"
+{words}"
     )
 }
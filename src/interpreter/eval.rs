@@ -12,30 +12,17 @@ pub(super) fn run_program(
     resolutions: &Resolutions,
     args: &[Vec<u8>],
 ) -> Result<(Value, Heap), Diagnostic> {
-    let mut functions: HashMap<(usize, &str), &Function> = HashMap::new();
-    for (mi, module) in graph.modules.iter().enumerate() {
-        for item in &module.ast {
-            // Generic templates are never executable — their instances
-            // are (ADR 0035).
-            if let Item::Function(f) = item
-                && f.type_params.is_empty()
-            {
-                functions.insert((mi, f.name.as_str()), f);
+    let mut interp = Interp::new(graph, resolutions, false);
+    // Module vars first, dependencies before their importers
+    // (ADR 0048); the checker proved no initializer reads ahead.
+    for &mi in &graph.init_order {
+        for item in &graph.modules[mi].ast {
+            if let Item::Global(g) = item {
+                let value = interp.eval_in(mi, &g.value)?;
+                interp.globals.insert((mi, g.name.clone()), value);
             }
         }
     }
-    for ((mi, name), f) in &resolutions.instances {
-        functions.insert((*mi, name.as_str()), f);
-    }
-    let mut interp = Interp {
-        functions,
-        resolutions,
-        module: 0,
-        scopes: Vec::new(),
-        depth: 0,
-        heap: Heap::default(),
-    };
-    interp.heap.error_names = resolutions.error_names.clone();
     let value = match interp.functions.get(&(0, syntax::ENTRY_FN)).copied() {
         // `main(args: string[])` (ADR 0031): materialize argv once.
         Some(main) => {
@@ -43,8 +30,7 @@ pub(super) fn run_program(
                 Vec::new()
             } else {
                 let items = args.iter().map(|a| Value::Str(a.clone())).collect();
-                interp.heap.arrays.push(items);
-                vec![Value::Array(interp.heap.arrays.len() - 1)]
+                vec![Value::Array(interp.heap.arrays.alloc(items, 0))]
             };
             interp.call(main, 0, argv, Span::new(0, 0))?
         }
@@ -53,6 +39,43 @@ pub(super) fn run_program(
     Ok((value, interp.heap))
 }
 
+/// Comptime (ADR 0047): every module const in module and declaration
+/// order. A const read by another initializer is computed on first
+/// read, so declaration order never matters.
+pub(super) fn run_consts(
+    graph: &ModuleGraph,
+    resolutions: &Resolutions,
+) -> Result<HashMap<(usize, String), ConstValue>, Diagnostic> {
+    let mut interp = Interp::new(graph, resolutions, true);
+    let mut out = HashMap::new();
+    for (mi, module) in graph.modules.iter().enumerate() {
+        for item in &module.ast {
+            let Item::Const(c) = item else { continue };
+            let key = (mi, c.name.clone());
+            // Each const gets the whole step budget.
+            interp.steps = 0;
+            let value = interp.const_value(&key, c.span).map_err(|d| {
+                if d.help.is_some() {
+                    return d;
+                }
+                d.with_help(format!(
+                    "while computing const '{}' at compile time",
+                    c.name
+                ))
+            })?;
+            let value = match value {
+                Value::Int(n) => ConstValue::Int(n),
+                Value::Float(f) => ConstValue::Float(f),
+                Value::Bool(b) => ConstValue::Bool(b),
+                Value::Str(s) => ConstValue::Str(s),
+                _ => unreachable!("the checker admits only int, float, bool, and string consts"),
+            };
+            out.insert(key, value);
+        }
+    }
+    Ok(out)
+}
+
 /// What a statement's right-hand side produced: a value, or `try`'s
 /// propagation (ADR 0034) — the error returning from the enclosing
 /// function.
@@ -64,14 +87,78 @@ enum Rhs {
 enum Flow {
     Normal,
     Return(Value),
-    /// `break` / `continue` unwinding toward the innermost loop, which
-    /// consumes it; the checker proves one exists (ADR 0019).
-    Break,
-    Continue,
+    /// `break` / `continue` unwinding toward the innermost loop, or the
+    /// one the label names (ADR 0059), which consumes it; the checker
+    /// proves one exists (ADR 0019).
+    Break(Option<String>),
+    Continue(Option<String>),
+}
+
+impl Flow {
+    /// What the loop labelled `label` makes of one pass's flow: `None`
+    /// runs the next pass, `Some` ends the loop with that flow —
+    /// `Normal` for a `break` aimed here, anything else unwinds on.
+    fn after_pass(self, label: Option<&str>) -> Option<Flow> {
+        let ours = |target: &Option<String>| target.is_none() || target.as_deref() == label;
+        match self {
+            Flow::Normal => None,
+            Flow::Continue(target) if ours(&target) => None,
+            Flow::Break(target) if ours(&target) => Some(Flow::Normal),
+            flow => Some(flow),
+        }
+    }
+}
+
+/// An assignment target with its subexpressions already run (ADR
+/// 0050): reading and writing it evaluates no user code, so a compound
+/// assignment runs the place once.
+struct Place<'a> {
+    root: Root<'a>,
+    /// Hops below the root, through values only — a refstruct hop
+    /// re-roots at the shared object.
+    path: Vec<(Hop<'a>, Span)>,
+}
+
+#[derive(Clone, Copy)]
+enum Hop<'a> {
+    /// A struct field, or a tuple element by its `.N` name.
+    Field(&'a str),
+    /// A fixed array's element (ADR 0056), bounds-checked when the
+    /// place resolved — the length is the type's.
+    Slot(usize),
+}
+
+enum Root<'a> {
+    Var(&'a str, Span),
+    /// An array slot: the buffer, the index, and the index expression's
+    /// span for the bounds error.
+    Elem(usize, usize, Span),
+    /// A refstruct's heap object; the first hop names its field.
+    Obj(usize),
 }
 
+/// A match arm chosen by `select_arm`, with the scope binding its
+/// payloads.
+type Selected<'a, B> = (&'a MatchArm<B>, HashMap<String, Value>);
+
 struct Interp<'a> {
     functions: HashMap<(usize, &'a str), &'a Function>,
+    /// Module const initializers and the values computed so far
+    /// (ADR 0047). A run starts with every value; comptime starts
+    /// empty and computes each const on first read.
+    const_inits: HashMap<(usize, String), &'a Expr>,
+    consts: HashMap<(usize, String), Value>,
+    /// Consts whose initializers are running — reading one is a cycle.
+    pending: Vec<(usize, String)>,
+    /// True while computing consts at compile time: allocation and
+    /// I/O are errors there, since nothing survives into the program.
+    comptime: bool,
+    /// Evaluation steps taken at comptime, bounded by
+    /// `MAX_COMPTIME_STEPS`.
+    steps: u64,
+    /// Every function literal evaluated so far, by span (ADR 0039) —
+    /// a closure can only be called after its literal ran.
+    lambdas: HashMap<Span, (&'a [Param], &'a [Stmt])>,
     resolutions: &'a Resolutions,
     /// The module whose alias map resolves calls in the currently executing
     /// function — saved/restored around every call.
@@ -80,54 +167,218 @@ struct Interp<'a> {
     /// Current language-call depth — bounded as language policy in `call`.
     depth: usize,
     heap: Heap,
+    /// Module vars (ADR 0048), set in initialization order before
+    /// `main` runs.
+    globals: HashMap<(usize, String), Value>,
 }
 
 impl<'a> Interp<'a> {
+    fn new(graph: &'a ModuleGraph, resolutions: &'a Resolutions, comptime: bool) -> Self {
+        let mut functions: HashMap<(usize, &str), &Function> = HashMap::new();
+        let mut const_inits = HashMap::new();
+        for (mi, module) in graph.modules.iter().enumerate() {
+            for item in &module.ast {
+                // Generic templates are never executable — their instances
+                // are (ADR 0035).
+                for f in item.functions().iter().filter(|f| f.type_params.is_empty()) {
+                    functions.insert((mi, f.name.as_str()), f);
+                }
+                if let Item::Const(c) = item {
+                    const_inits.insert((mi, c.name.clone()), &c.value);
+                }
+            }
+        }
+        for ((mi, name), f) in &resolutions.instances {
+            functions.insert((*mi, name.as_str()), f);
+        }
+        let consts = resolutions
+            .consts
+            .iter()
+            .map(|(key, c)| {
+                let v = match c {
+                    ConstValue::Int(n) => Value::Int(*n),
+                    ConstValue::Float(f) => Value::Float(*f),
+                    ConstValue::Bool(b) => Value::Bool(*b),
+                    ConstValue::Str(s) => Value::Str(s.clone()),
+                };
+                (key.clone(), v)
+            })
+            .collect();
+        let mut interp = Interp {
+            functions,
+            const_inits,
+            consts,
+            pending: Vec::new(),
+            comptime,
+            steps: 0,
+            lambdas: HashMap::new(),
+            resolutions,
+            module: 0,
+            scopes: Vec::new(),
+            depth: 0,
+            heap: Heap::default(),
+            globals: HashMap::new(),
+        };
+        interp.heap.error_names = resolutions.error_names.clone();
+        interp.heap.error_payloads = resolutions.error_payloads.clone();
+        interp
+    }
+
+    /// A module const's value (ADR 0047), computing it first at
+    /// comptime.
+    fn const_value(&mut self, key: &(usize, String), span: Span) -> Result<Value, Diagnostic> {
+        if let Some(v) = self.consts.get(key) {
+            return Ok(v.clone());
+        }
+        if self.pending.contains(key) {
+            return Err(Diagnostic::error(
+                format!("const '{}' depends on its own value", key.1),
+                span,
+            ));
+        }
+        self.pending.push(key.clone());
+        let value = self.eval_in(key.0, self.const_inits[key]);
+        self.pending.pop();
+        let value = value?;
+        self.consts.insert(key.clone(), value.clone());
+        Ok(value)
+    }
+
+    /// Evaluates a module-level initializer in its own module, with no
+    /// locals in scope.
+    fn eval_in(&mut self, module: usize, init: &'a Expr) -> Result<Value, Diagnostic> {
+        let module = std::mem::replace(&mut self.module, module);
+        let scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+        let value = self.eval(init);
+        self.module = module;
+        self.scopes = scopes;
+        value
+    }
+
     fn call(
         &mut self,
         func: &'a Function,
         module: usize,
         args: Vec<Value>,
         span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let mut scope = HashMap::new();
+        for (param, value) in func.params.iter().zip(args) {
+            scope.insert(param.name.clone(), value);
+        }
+        self.run_body(&func.name, scope, module, &func.body, span)
+    }
+
+    /// Calls the method the checker resolved at `span` (ADR 0040): the
+    /// receiver binds `self` like any first argument — a value struct
+    /// is copied in, a refstruct handle aliases the caller's object.
+    fn call_method(
+        &mut self,
+        receiver: Value,
+        args: &'a [Expr],
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let (module, name) = self.resolutions.call_targets[&span].clone();
+        let func = self.functions[&(module, name.as_str())];
+        let mut argv = Vec::with_capacity(args.len() + 1);
+        argv.push(receiver);
+        for arg in args {
+            argv.push(self.eval(arg)?);
+        }
+        self.call(func, module, argv, span)
+    }
+
+    /// Calls a closure (ADR 0039): its captures, then the parameters,
+    /// bind in the callee's frame — parameters shadow captures.
+    fn call_closure(
+        &mut self,
+        id: usize,
+        args: Vec<Value>,
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let obj = &self.heap.closures[id];
+        let (params, body) = self.lambdas[&obj.literal];
+        let module = obj.module;
+        let mut scope: HashMap<String, Value> = obj.captures.iter().cloned().collect();
+        for (param, value) in params.iter().zip(args) {
+            scope.insert(param.name.clone(), value);
+        }
+        self.run_body("function literal", scope, module, body, span)
+    }
+
+    /// Calls a function value (ADR 0038): a named function or a closure.
+    fn call_value(&mut self, f: Value, args: Vec<Value>, span: Span) -> Result<Value, Diagnostic> {
+        match f {
+            Value::Func(m, name) => {
+                let func = self.functions[&(m, name.as_str())];
+                self.call(func, m, args, span)
+            }
+            Value::Closure(id) => self.call_closure(id, args, span),
+            _ => Err(Diagnostic::error("only functions can be called", span)),
+        }
+    }
+
+    /// Runs a callee's body in a fresh frame seeded with `scope`.
+    fn run_body(
+        &mut self,
+        name: &str,
+        scope: HashMap<String, Value>,
+        module: usize,
+        body: &'a [Stmt],
+        span: Span,
     ) -> Result<Value, Diagnostic> {
         // Calls, statements, and expressions all charge the one depth
         // budget (see the policy block up top) — the diagnostic here just
         // gets to name the function.
         if self.depth >= MAX_EVAL_DEPTH {
             return Err(Diagnostic::error(
-                format!("evaluation depth limit exceeded in '{}'", func.name),
+                format!("evaluation depth limit exceeded in '{name}'"),
                 span,
             ));
         }
         self.depth += 1;
-        let mut scope = HashMap::new();
-        for (param, value) in func.params.iter().zip(args) {
-            scope.insert(param.name.clone(), value);
-        }
         let prev_module = self.module;
         self.module = module;
         self.scopes.push(scope);
-        let result = self.exec_block(&func.body);
+        let result = self.exec_block(body);
         self.scopes.pop();
         self.module = prev_module;
         self.depth -= 1;
         Ok(match result? {
             Flow::Return(v) => v,
             Flow::Normal => Value::Unit,
-            Flow::Break | Flow::Continue => {
+            Flow::Break(_) | Flow::Continue(_) => {
                 unreachable!("checker rejects break/continue outside loops")
             }
         })
     }
 
+    /// Runs a block's statements, then its `defer` bodies, last first,
+    /// on whatever flow leaves it (ADR 0060). Each body runs in the
+    /// scopes of its registration: the rest of the block gets a fresh
+    /// scope, so a later binding cannot shadow a name it reads. A
+    /// runtime error skips them, like a trap in native code.
     fn exec_block(&mut self, body: &'a [Stmt]) -> Result<Flow, Diagnostic> {
+        // An empty loop body still takes a step per pass.
+        self.step()?;
+        let mut deferred = Vec::new();
+        let mut flow = Flow::Normal;
         for stmt in body {
-            match self.exec_stmt(stmt)? {
-                Flow::Normal => {}
-                other => return Ok(other),
+            if let Stmt::Defer { body, .. } = stmt {
+                deferred.push((self.scopes.len(), body));
+                self.scopes.push(HashMap::new());
+                continue;
+            }
+            flow = self.exec_stmt(stmt)?;
+            if !matches!(flow, Flow::Normal) {
+                break;
             }
         }
-        Ok(Flow::Normal)
+        for (depth, body) in deferred.into_iter().rev() {
+            self.scopes.truncate(depth);
+            self.exec_block_scoped(body)?;
+        }
+        Ok(flow)
     }
 
     fn exec_stmt(&mut self, stmt: &'a Stmt) -> Result<Flow, Diagnostic> {
@@ -147,6 +398,67 @@ impl<'a> Interp<'a> {
                 self.scopes.last_mut().unwrap().insert(name.clone(), v);
                 Ok(Flow::Normal)
             }
+            // Each position binds as its own copy; `_` skips one
+            // (ADR 0043).
+            Stmt::LetTuple { names, value, .. } => {
+                let v = match self.eval_rhs(value)? {
+                    Rhs::Value(v) => v,
+                    Rhs::Propagate(flow) => return Ok(flow),
+                };
+                let Value::Tuple(items) = v else {
+                    return Err(Diagnostic::error(
+                        format!("cannot destructure {}", v.type_name()),
+                        value.span(),
+                    ));
+                };
+                let scope = self.scopes.last_mut().unwrap();
+                for (name, item) in names.iter().zip(items) {
+                    if name != "_" {
+                        scope.insert(name.clone(), item);
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            // Fields read out by name, each its own copy (ADR 0057).
+            Stmt::LetStruct { fields, value, .. } => {
+                let v = match self.eval_rhs(value)? {
+                    Rhs::Value(v) => v,
+                    Rhs::Propagate(flow) => return Ok(flow),
+                };
+                for (field, name, span) in fields {
+                    let item = self.get_field(&v, field, *span)?;
+                    if name != "_" {
+                        self.scopes.last_mut().unwrap().insert(name.clone(), item);
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            // A one-arm match: the names land in the enclosing scope, or
+            // the `else` runs — the checker proved it leaves (ADR 0057).
+            Stmt::LetElse {
+                pattern,
+                value,
+                else_body,
+                span,
+                ..
+            } => {
+                let v = match self.eval_rhs(value)? {
+                    Rhs::Value(v) => v,
+                    Rhs::Propagate(flow) => return Ok(flow),
+                };
+                let mut scope = HashMap::new();
+                if pattern_binds(pattern, &v, &self.resolutions.error_lits, &mut scope) {
+                    self.scopes.last_mut().unwrap().extend(scope);
+                    return Ok(Flow::Normal);
+                }
+                match else_body {
+                    Some(else_body) => self.exec_block_scoped(else_body),
+                    None => Err(Diagnostic::error(
+                        format!("no pattern matched {}", v.type_name()),
+                        *span,
+                    )),
+                }
+            }
             Stmt::Return { value, .. } => {
                 let v = match value {
                     Some(e) => match self.eval_rhs(e)? {
@@ -157,8 +469,17 @@ impl<'a> Interp<'a> {
                 };
                 Ok(Flow::Return(v))
             }
-            Stmt::Break { .. } => Ok(Flow::Break),
-            Stmt::Continue { .. } => Ok(Flow::Continue),
+            Stmt::Break { label, .. } => Ok(Flow::Break(label.clone())),
+            Stmt::Continue { label, .. } => Ok(Flow::Continue(label.clone())),
+            Stmt::Defer { .. } => unreachable!("exec_block registers defers"),
+            // The region frees on every way out, the body's defers
+            // already run (ADR 0061).
+            Stmt::Arena { body, .. } => {
+                self.heap.enter_region();
+                let flow = self.exec_block_scoped(body);
+                self.heap.leave_region();
+                flow
+            }
             Stmt::If {
                 cond,
                 then_body,
@@ -173,28 +494,78 @@ impl<'a> Interp<'a> {
                     Ok(Flow::Normal)
                 }
             }
-            Stmt::While { cond, body, .. } => {
+            Stmt::While {
+                label, cond, body, ..
+            } => {
                 while self.eval_condition(cond)? {
-                    match self.exec_block_scoped(body)? {
-                        Flow::Return(v) => return Ok(Flow::Return(v)),
-                        Flow::Break => break,
-                        Flow::Continue | Flow::Normal => {}
+                    if let Some(flow) = self.exec_block_scoped(body)?.after_pass(label.as_deref()) {
+                        return Ok(flow);
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Stmt::IfConst {
+                pattern,
+                value,
+                then_body,
+                else_body,
+                ..
+            } => {
+                if let Some(scope) = self.const_head(pattern, value)? {
+                    self.scopes.push(scope);
+                    let flow = self.exec_block(then_body);
+                    self.scopes.pop();
+                    flow
+                } else if let Some(else_body) = else_body {
+                    self.exec_block_scoped(else_body)
+                } else {
+                    Ok(Flow::Normal)
+                }
+            }
+            Stmt::WhileConst {
+                label,
+                pattern,
+                value,
+                body,
+                ..
+            } => {
+                while let Some(scope) = self.const_head(pattern, value)? {
+                    self.scopes.push(scope);
+                    let flow = self.exec_block(body);
+                    self.scopes.pop();
+                    if let Some(flow) = flow?.after_pass(label.as_deref()) {
+                        return Ok(flow);
                     }
                 }
                 Ok(Flow::Normal)
             }
             Stmt::For {
+                label,
                 index,
                 name,
                 iterable,
                 body,
                 span,
             } => {
+                if let Expr::Range { .. } = iterable {
+                    return self.exec_range_for(label, name, iterable, body);
+                }
                 let id = match self.eval(iterable)? {
                     Value::Array(id) => id,
+                    Value::Map(id) => return self.exec_map_for(id, label, index, name, body),
+                    Value::Str(bytes) => {
+                        let items = bytes.iter().map(|&b| Value::Sized(IntTy::U8, b as i64));
+                        return self.exec_items_for(items, label, index, name, body);
+                    }
+                    Value::Fixed(items) => {
+                        return self.exec_items_for(items, label, index, name, body);
+                    }
                     other => {
                         return Err(Diagnostic::error(
-                            format!("can only iterate over arrays, found {}", other.type_name()),
+                            format!(
+                                "can only iterate over arrays, maps, and strings, found {}",
+                                other.type_name()
+                            ),
                             *span,
                         ));
                     }
@@ -213,11 +584,9 @@ impl<'a> Interp<'a> {
                     self.scopes.push(scope);
                     let flow = self.exec_block(body);
                     self.scopes.pop();
-                    match flow? {
-                        Flow::Return(v) => return Ok(Flow::Return(v)),
-                        Flow::Break => break,
-                        // `continue` still advances — the increment is here.
-                        Flow::Continue | Flow::Normal => {}
+                    // `continue` still advances — the increment is below.
+                    if let Some(flow) = flow?.after_pass(label.as_deref()) {
+                        return Ok(flow);
                     }
                     i += 1;
                 }
@@ -238,44 +607,51 @@ impl<'a> Interp<'a> {
                 }
                 Ok(Flow::Normal)
             }
-            Stmt::Assign { target, value, .. } => {
+            Stmt::Assign {
+                target,
+                op,
+                value,
+                span,
+                ..
+            } => {
+                // `??=` reads first and runs its value only on null.
+                if *op == Some(BinOp::Coalesce) {
+                    let place = self.place(target)?;
+                    if self.read_place(&place)? == Value::Null {
+                        let v = match self.eval_rhs(value)? {
+                            Rhs::Value(v) => v,
+                            Rhs::Propagate(flow) => return Ok(flow),
+                        };
+                        self.write_place(&place, v)?;
+                    }
+                    return Ok(Flow::Normal);
+                }
                 let v = match self.eval_rhs(value)? {
                     Rhs::Value(v) => v,
                     Rhs::Propagate(flow) => return Ok(flow),
                 };
-                self.assign_place(target, v)?;
+                // The value runs first, then the place — once (ADR 0050).
+                let place = self.place(target)?;
+                let v = match op {
+                    Some(op) => eval_binary(*op, self.read_place(&place)?, v, *span)?,
+                    None => v,
+                };
+                self.write_place(&place, v)?;
                 Ok(Flow::Normal)
             }
         }
     }
 
     /// Variant dispatch (ADR 0036): find the live variant's arm, bind
-    /// its payloads (cloned out — value semantics), run the block;
-    /// `else` takes the rest.
+    /// its payloads, run the block; `else` takes the rest.
     fn exec_match(
         &mut self,
         scrutinee: &'a Expr,
-        arms: &'a [crate::ast::MatchArm],
+        arms: &'a [MatchArm],
         else_body: Option<&'a [Stmt]>,
         span: Span,
     ) -> Result<Flow, Diagnostic> {
-        let v = self.eval(scrutinee)?;
-        let Value::Enum {
-            variant, payloads, ..
-        } = v
-        else {
-            return Err(Diagnostic::error(
-                format!("match needs an enum, found {}", v.type_name()),
-                span,
-            ));
-        };
-        if let Some(arm) = arms.iter().find(|a| a.variant == variant) {
-            let mut scope = HashMap::new();
-            for ((bname, _), value) in arm.bindings.iter().zip(payloads) {
-                if bname != "_" {
-                    scope.insert(bname.clone(), value);
-                }
-            }
+        if let Some((arm, scope)) = self.select_arm(scrutinee, arms, span)? {
             self.scopes.push(scope);
             let flow = self.exec_block(&arm.body);
             self.scopes.pop();
@@ -287,6 +663,69 @@ impl<'a> Interp<'a> {
         unreachable!("checker proves match coverage")
     }
 
+    /// The match expression (ADR 0044): the same dispatch, yielding the
+    /// chosen arm's value.
+    fn eval_match(
+        &mut self,
+        scrutinee: &'a Expr,
+        arms: &'a [MatchArm<Expr>],
+        else_value: Option<&'a Expr>,
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        if let Some((arm, scope)) = self.select_arm(scrutinee, arms, span)? {
+            self.scopes.push(scope);
+            let v = self.eval(&arm.body);
+            self.scopes.pop();
+            return v;
+        }
+        match else_value {
+            Some(e) => self.eval(e),
+            None => unreachable!("checker proves match coverage"),
+        }
+    }
+
+    /// Evaluates the scrutinee and finds the first arm whose pattern
+    /// matches and whose guard holds (ADR 0045), with a scope binding
+    /// the pattern's names (cloned out — value semantics). A guard
+    /// sees those bindings. `None` sends control to `else`.
+    fn select_arm<B>(
+        &mut self,
+        scrutinee: &'a Expr,
+        arms: &'a [MatchArm<B>],
+        span: Span,
+    ) -> Result<Option<Selected<'a, B>>, Diagnostic> {
+        let v = self.eval(scrutinee)?;
+        if !matches!(
+            v,
+            Value::Enum { .. } | Value::Int(_) | Value::Str(_) | Value::Err(..)
+        ) {
+            return Err(Diagnostic::error(
+                format!(
+                    "match needs an enum, int, string, or error, found {}",
+                    v.type_name()
+                ),
+                span,
+            ));
+        }
+        let codes = &self.resolutions.error_lits;
+        for arm in arms {
+            let mut scope = HashMap::new();
+            if !pattern_binds(&arm.pattern, &v, codes, &mut scope) {
+                continue;
+            }
+            if let Some(guard) = &arm.guard {
+                self.scopes.push(scope);
+                let holds = self.eval_condition(guard);
+                scope = self.scopes.pop().expect("guard scope");
+                if !holds? {
+                    continue;
+                }
+            }
+            return Ok(Some((arm, scope)));
+        }
+        Ok(None)
+    }
+
     /// Evaluates a statement's right-hand side, honoring `try`
     /// (ADR 0034): an error value becomes the enclosing function's
     /// return, a payload continues the normal path. The checker
@@ -294,7 +733,7 @@ impl<'a> Interp<'a> {
     fn eval_rhs(&mut self, e: &'a Expr) -> Result<Rhs, Diagnostic> {
         if let Expr::Try { expr, .. } = e {
             let v = self.eval(expr)?;
-            if matches!(v, Value::Err(_)) {
+            if matches!(v, Value::Err(..)) {
                 return Ok(Rhs::Propagate(Flow::Return(v)));
             }
             return Ok(Rhs::Value(v));
@@ -311,6 +750,25 @@ impl<'a> Interp<'a> {
         flow
     }
 
+    /// Evaluates an `if const` / `while const` value (ADR 0058): the
+    /// head's bindings when it binds, `None` when it does not. A bare
+    /// name takes a present optional or a successful error union.
+    fn const_head(
+        &mut self,
+        pattern: &Pattern,
+        value: &'a Expr,
+    ) -> Result<Option<HashMap<String, Value>>, Diagnostic> {
+        let v = self.eval(value)?;
+        let absent = match (pattern, self.resolutions.expr_types.get(&value.span())) {
+            (Pattern::Bind(..), Some(Type::ErrUnion(_))) => matches!(v, Value::Err(..)),
+            (Pattern::Bind(..), _) => v == Value::Null,
+            _ => false,
+        };
+        let mut scope = HashMap::new();
+        let binds = !absent && pattern_binds(pattern, &v, &self.resolutions.error_lits, &mut scope);
+        Ok(binds.then_some(scope))
+    }
+
     fn eval_condition(&mut self, cond: &'a Expr) -> Result<bool, Diagnostic> {
         match self.eval(cond)? {
             Value::Bool(b) => Ok(b),
@@ -351,6 +809,16 @@ impl<'a> Interp<'a> {
         self.charge(expr.span())?;
         let value = self.eval_inner(expr);
         self.depth -= 1;
+        // Every read of a handle passes here, so a dead one is caught
+        // before anything reaches through it (ADR 0061).
+        if let Ok(v) = &value
+            && let Some(kind) = self.heap.dead(v)
+        {
+            return Err(Diagnostic::error(
+                format!("this {kind} was freed when its arena ended"),
+                expr.span(),
+            ));
+        }
         value
     }
 
@@ -363,10 +831,33 @@ impl<'a> Interp<'a> {
                 span,
             ));
         }
+        self.step()?;
         self.depth += 1;
         Ok(())
     }
 
+    /// Counts one comptime step. An initializer that runs out is
+    /// reported at its own expression, naming its const — the one
+    /// innermost in `pending`.
+    fn step(&mut self) -> Result<(), Diagnostic> {
+        if !self.comptime {
+            return Ok(());
+        }
+        self.steps += 1;
+        if self.steps <= MAX_COMPTIME_STEPS {
+            return Ok(());
+        }
+        let key = self.pending.last().expect("comptime runs inside a const");
+        Err(Diagnostic::error(
+            format!(
+                "const '{}' did not finish within {MAX_COMPTIME_STEPS} steps at compile time",
+                key.1
+            ),
+            self.const_inits[key].span(),
+        )
+        .with_help("a const initializer must terminate — loop in 'main' instead".to_string()))
+    }
+
     /// `open(path, mode)` (ADR 0031): the mode set is pinned to r/w/a —
     /// both engines validate it themselves, so fopen's extended modes
     /// can't diverge. Any environmental failure (including a NUL byte
@@ -385,24 +876,47 @@ impl<'a> Interp<'a> {
             _ => return Value::Null,
         };
         match entry {
-            Ok(e) => {
-                self.heap.files.push(e);
-                Value::File(self.heap.files.len() - 1)
-            }
+            Ok(e) => Value::File(self.heap.files.alloc(e, self.heap.region())),
             Err(_) => Value::Null,
         }
     }
 
     fn eval_inner(&mut self, expr: &'a Expr) -> Result<Value, Diagnostic> {
         match expr {
-            Expr::Int(n, _) => Ok(Value::Int(*n)),
+            // A literal at a sized slot carries the slot's type (ADR 0052).
+            Expr::Int(n, span) => Ok(match self.resolutions.expr_types.get(span) {
+                Some(Type::Sized(t)) => Value::Sized(*t, t.wrap(*n)),
+                _ => Value::Int(*n),
+            }),
             Expr::Float(f, _) => Ok(Value::Float(*f)),
             Expr::Bool(b, _) => Ok(Value::Bool(*b)),
             Expr::Str(s, _) => Ok(Value::Str(s.as_bytes().to_vec())),
+            // The checker decided which names are function values
+            // (ADR 0038); everything else is a variable.
+            Expr::Ident(_, span) | Expr::FnRef { span, .. }
+                if self.resolutions.fn_values.contains_key(span) =>
+            {
+                let (m, name) = self.resolutions.fn_values[span].clone();
+                Ok(Value::Func(m, name))
+            }
+            Expr::Ident(_, span) if self.resolutions.const_refs.contains_key(span) => {
+                let key = self.resolutions.const_refs[span].clone();
+                self.const_value(&key, *span)
+            }
             Expr::Ident(name, span) => self.lookup(name, *span),
+            Expr::FnRef { .. } => unreachable!("checker records every instance value"),
             Expr::Null(_) => Ok(Value::Null),
             // The checker interned the code (or rejected the program).
-            Expr::ErrorLit(_, span) => Ok(Value::Err(self.resolutions.error_lits[span])),
+            Expr::ErrorLit { args, span, .. } => {
+                let payloads = args
+                    .iter()
+                    .map(|a| self.eval(a))
+                    .collect::<Result<_, _>>()?;
+                Ok(Value::Err(
+                    self.resolutions.error_lits[span],
+                    ErrPayload(payloads),
+                ))
+            }
             Expr::ErrorKind(_) => unreachable!("checker rejects bare 'error' outside tests"),
             Expr::Try { .. } => unreachable!("checker restricts 'try' to statement positions"),
             Expr::Unary { op, rhs, span } => {
@@ -423,6 +937,15 @@ impl<'a> Interp<'a> {
                     }
                 }
                 (v, Conv::Str) => Ok(Value::Str(v.display(&self.heap))),
+                // ADR 0052: widening is exact (u64 reads its bits as
+                // unsigned for float, as two's complement for int);
+                // narrowing keeps the low bits.
+                (Value::Sized(IntTy::U64, n), Conv::Float) => Ok(Value::Float(n as u64 as f64)),
+                (Value::Sized(_, n), Conv::Float) => Ok(Value::Float(n as f64)),
+                (Value::Sized(_, n), Conv::Int) => Ok(Value::Int(n)),
+                (Value::Int(n) | Value::Sized(_, n), Conv::Sized(t)) => {
+                    Ok(Value::Sized(t, t.wrap(n)))
+                }
                 _ => unreachable!("checker enforced the operand type"),
             },
             Expr::Binary { op, lhs, rhs, span } => match op {
@@ -437,7 +960,7 @@ impl<'a> Interp<'a> {
                     } else {
                         lhs
                     };
-                    let is_err = matches!(self.eval(place)?, Value::Err(_));
+                    let is_err = matches!(self.eval(place)?, Value::Err(..));
                     Ok(Value::Bool(is_err == (*op == BinOp::Eq)))
                 }
                 BinOp::And | BinOp::Or => self.eval_logical(*op, lhs, rhs),
@@ -452,6 +975,22 @@ impl<'a> Interp<'a> {
                     eval_binary(*op, l, r, *span)
                 }
             },
+            // A callee typed as a function value calls indirectly
+            // (ADR 0038): callee first, then the arguments, in order.
+            Expr::Call {
+                callee, args, span, ..
+            } if matches!(
+                self.resolutions.expr_types.get(&callee.span()),
+                Some(Type::Fn(..))
+            ) =>
+            {
+                let f = self.eval(callee)?;
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(self.eval(arg)?);
+                }
+                self.call_value(f, values, *span)
+            }
             Expr::Call {
                 callee, args, span, ..
             } => {
@@ -473,6 +1012,14 @@ impl<'a> Interp<'a> {
                     // Builtins run only when no user definition shadows them
                     // — mirrors the checker's resolution order. Shape errors
                     // are defensive; the checker validated arities and types.
+                    if self.comptime && is_io_builtin(&name) {
+                        return Err(Diagnostic::error(
+                            format!(
+                                "a const initializer cannot do I/O ('{name}') — consts are computed at compile time"
+                            ),
+                            *span,
+                        ));
+                    }
                     if name == syntax::BUILTIN_PRINT && args.len() == 1 {
                         let v = self.eval(&args[0])?;
                         use std::io::Write;
@@ -615,12 +1162,57 @@ impl<'a> Interp<'a> {
                     if name == syntax::BUILTIN_LEN && args.len() == 1 {
                         return match self.eval(&args[0])? {
                             Value::Array(id) => Ok(Value::Int(self.heap.arrays[id].len() as i64)),
+                            Value::Map(id) => Ok(Value::Int(self.heap.maps[id].len() as i64)),
+                            Value::Str(bytes) => Ok(Value::Int(bytes.len() as i64)),
+                            Value::Fixed(items) => Ok(Value::Int(items.len() as i64)),
                             other => Err(Diagnostic::error(
-                                format!("'len' expects an array, found {}", other.type_name()),
+                                format!(
+                                    "'len' expects an array, a map, or a string, found {}",
+                                    other.type_name()
+                                ),
                                 *span,
                             )),
                         };
                     }
+                    // `insert` into an array shifts (ADR 0055); the checker's
+                    // type for the first argument picks, as it did there.
+                    if is_array_builtin(&name)
+                        || (name == syntax::BUILTIN_INSERT
+                            && args.first().is_some_and(|a| {
+                                matches!(
+                                    self.resolutions.expr_types.get(&a.span()),
+                                    Some(Type::Array(_))
+                                )
+                            }))
+                    {
+                        return self.array_builtin(&name, args, *span);
+                    }
+                    if name == syntax::BUILTIN_INSERT
+                        || name == syntax::BUILTIN_REMOVE
+                        || name == syntax::BUILTIN_HAS
+                    {
+                        return self.map_builtin(&name, args, *span);
+                    }
+                    if name == syntax::BUILTIN_CHARS && args.len() == 1 {
+                        return match self.eval(&args[0])? {
+                            Value::Str(bytes) => {
+                                let points = code_points(&bytes)
+                                    .into_iter()
+                                    .map(|c| Value::Sized(IntTy::U32, c as i64))
+                                    .collect();
+                                self.check_heap(*span)?;
+                                let region = self.heap.region();
+                                Ok(Value::Array(self.heap.arrays.alloc(points, region)))
+                            }
+                            other => Err(Diagnostic::error(
+                                format!("'chars' expects a string, found {}", other.type_name()),
+                                *span,
+                            )),
+                        };
+                    }
+                    if is_string_builtin(&name) {
+                        return self.string_builtin(&name, args, *span);
+                    }
                     if name == syntax::BUILTIN_PUSH && args.len() == 2 {
                         let array = self.eval(&args[0])?;
                         let value = self.eval(&args[1])?;
@@ -682,13 +1274,12 @@ impl<'a> Interp<'a> {
                 // everyone who copies the handle aliases it.
                 if by_ref {
                     self.check_heap(*span)?;
-                    Ok(Value::Ref({
-                        self.heap.structs.push(StructObj {
-                            name: display,
-                            fields: vals,
-                        });
-                        self.heap.structs.len() - 1
-                    }))
+                    let obj = StructObj {
+                        name: display,
+                        fields: vals,
+                    };
+                    let region = self.heap.region();
+                    Ok(Value::Ref(self.heap.structs.alloc(obj, region)))
                 } else {
                     Ok(Value::Struct {
                         name: display,
@@ -707,6 +1298,27 @@ impl<'a> Interp<'a> {
                 Value::Null if *optional => Ok(Value::Null),
                 v => self.get_field(&v, name, *span),
             },
+            // `x.m(…)` where `x` is a variable is a method call the
+            // checker resolved (ADR 0040) — the receiver is that variable.
+            Expr::EnumLit {
+                name,
+                variant: _,
+                args,
+                span,
+                ..
+            } if self.resolutions.call_targets.contains_key(span) => {
+                let receiver = self.lookup(name, Expr::enum_lit_receiver(name, *span).span())?;
+                self.call_method(receiver, args, *span)
+            }
+            Expr::MethodCall {
+                receiver,
+                args,
+                span,
+                ..
+            } => {
+                let receiver = self.eval(receiver)?;
+                self.call_method(receiver, args, *span)
+            }
             // Qualified construction (ADR 0036) — own frame, like
             // exec_match.
             Expr::EnumLit {
@@ -720,16 +1332,102 @@ impl<'a> Interp<'a> {
                 for element in elements {
                     items.push(self.eval(element)?);
                 }
+                // At a fixed array's slot the literal is the value itself
+                // (ADR 0056) — nothing on the heap.
+                if let Some(Type::Fixed(..)) = self.resolutions.expr_types.get(span) {
+                    return Ok(Value::Fixed(items));
+                }
                 self.check_heap(*span)?;
-                self.heap.arrays.push(items);
-                Ok(Value::Array(self.heap.arrays.len() - 1))
+                let region = self.heap.region();
+                Ok(Value::Array(self.heap.arrays.alloc(items, region)))
+            }
+            Expr::ArrayRepeat { value, count, .. } => {
+                Ok(Value::Fixed(vec![self.eval(value)?; *count]))
+            }
+            Expr::Match {
+                scrutinee,
+                arms,
+                else_value,
+                span,
+            } => self.eval_match(scrutinee, arms, else_value.as_deref(), *span),
+            Expr::TupleLit { elements, .. } => {
+                let mut items = Vec::with_capacity(elements.len());
+                for element in elements {
+                    items.push(self.eval(element)?);
+                }
+                Ok(Value::Tuple(items))
+            }
+            // Insertion semantics per entry: a repeated key keeps its
+            // first position and its last value (ADR 0042).
+            Expr::MapLit { entries, span } => {
+                let mut items: Vec<(Value, Value)> = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    let k = self.eval(key)?;
+                    let v = self.eval(value)?;
+                    match items.iter().position(|(have, _)| *have == k) {
+                        Some(i) => items[i].1 = v,
+                        None => items.push((k, v)),
+                    }
+                }
+                self.check_heap(*span)?;
+                let region = self.heap.region();
+                Ok(Value::Map(self.heap.maps.alloc(items, region)))
+            }
+            Expr::Index { base, index, span } if matches!(**index, Expr::Range { .. }) => {
+                self.eval_slice(base, index, *span)
             }
             Expr::Index { base, index, span } => {
                 let array = self.eval(base)?;
                 let index = self.eval(index)?;
+                // A map lookup misses with null (ADR 0042).
+                if let Value::Map(id) = array {
+                    return Ok(match self.map_find(id, &index) {
+                        Some(i) => self.heap.maps[id][i].1.clone(),
+                        None => Value::Null,
+                    });
+                }
+                // A string reads its bytes (ADR 0053).
+                if let (Value::Str(bytes), Value::Int(i)) = (&array, &index) {
+                    return match usize::try_from(*i).ok().and_then(|i| bytes.get(i)) {
+                        Some(&b) => Ok(Value::Sized(IntTy::U8, b as i64)),
+                        None => Err(Diagnostic::error(
+                            format!("index {i} out of bounds (length {})", bytes.len()),
+                            *span,
+                        )),
+                    };
+                }
+                // A fixed array holds its elements inline (ADR 0056).
+                if let Value::Fixed(mut items) = array {
+                    let i = fixed_index(items.len(), index, *span)?;
+                    return Ok(items.swap_remove(i));
+                }
                 let (id, i) = self.index_array(array, index, *span)?;
                 Ok(self.heap.arrays[id][i].clone())
             }
+            // Only `for` and slicing consume a range (ADR 0051).
+            Expr::Range { span, .. } => Err(Diagnostic::error(
+                "a range is only valid as a 'for' iterable or slice bounds",
+                *span,
+            )),
+            // A function literal captures now (ADR 0039): the checker
+            // listed the free names; each is read once, at creation.
+            Expr::Lambda {
+                params, body, span, ..
+            } => {
+                self.lambdas.insert(*span, (params, body));
+                let mut captures = Vec::new();
+                for (name, _) in &self.resolutions.captures[span] {
+                    captures.push((name.clone(), self.lookup(name, *span)?));
+                }
+                self.check_heap(*span)?;
+                let closure = ClosureObj {
+                    literal: *span,
+                    module: self.module,
+                    captures,
+                };
+                let region = self.heap.region();
+                Ok(Value::Closure(self.heap.closures.alloc(closure, region)))
+            }
         }
     }
 
@@ -758,54 +1456,46 @@ impl<'a> Interp<'a> {
         })
     }
 
-    /// Writes `v` into the storage a place expression names. Field chains
-    /// are read-modify-write: a value-struct hop clones the struct, sets the
-    /// field in the copy, and writes the copy back into its own place; a
-    /// refstruct hop mutates the shared object directly (that's the aliasing
-    /// semantics — and it also ends the write-back, since the handle itself
-    /// is unchanged). Error arms are defensive; the checker has already
-    /// validated the place.
-    // ponytail: value hops clone the intermediate structs — fine for the
-    // interpreter (the oracle); codegen will write in place.
-    fn assign_place(&mut self, target: &'a Expr, v: Value) -> Result<(), Diagnostic> {
+    /// Resolves an assignment target to its storage, running each index
+    /// and base expression exactly once. Error arms are defensive; the
+    /// checker has already validated the place.
+    fn place(&mut self, target: &'a Expr) -> Result<Place<'a>, Diagnostic> {
         match target {
-            Expr::Ident(name, span) => {
-                *self.slot_mut(name, *span)? = v;
-                Ok(())
-            }
-            Expr::Field {
-                base, name, span, ..
-            } => match self.eval(base)? {
-                // A refstruct hop mutates the shared heap object directly —
-                // the aliasing semantics, and the end of the write-back
-                // chain (the handle itself is unchanged).
-                Value::Ref(id) => set_in_fields(&mut self.heap.structs[id].fields, name, v, *span),
-                // A value hop: set the field in the copy, write the copy
-                // back into its own place.
-                Value::Struct {
-                    name: struct_name,
-                    mut fields,
-                } => {
-                    set_in_fields(&mut fields, name, v, *span)?;
-                    self.assign_place(
-                        base,
-                        Value::Struct {
-                            name: struct_name,
-                            fields,
-                        },
-                    )
-                }
-                other => Err(Diagnostic::error(
-                    format!("type {} has no fields", other.type_name()),
-                    *span,
-                )),
-            },
+            Expr::Ident(name, span) => Ok(Place {
+                root: Root::Var(name, *span),
+                path: Vec::new(),
+            }),
             Expr::Index { base, index, span } => {
+                // A fixed array's element is one more value hop inside
+                // the array's own place (ADR 0056).
+                if let Some(Type::Fixed(_, n)) = self.resolutions.expr_types.get(&base.span()) {
+                    let mut place = self.place(base)?;
+                    let i = fixed_index(*n, self.eval(index)?, *span)?;
+                    place.path.push((Hop::Slot(i), *span));
+                    return Ok(place);
+                }
                 let array = self.eval(base)?;
                 let index = self.eval(index)?;
                 let (id, i) = self.index_array(array, index, *span)?;
-                self.heap.arrays[id][i] = v;
-                Ok(())
+                Ok(Place {
+                    root: Root::Elem(id, i, *span),
+                    path: Vec::new(),
+                })
+            }
+            Expr::Field {
+                base, name, span, ..
+            } => {
+                let mut place = self.place(base)?;
+                // Past a refstruct handle the write mutates the shared
+                // object directly — that's the aliasing semantics.
+                if let Value::Ref(id) = self.read_place(&place)? {
+                    place = Place {
+                        root: Root::Obj(id),
+                        path: Vec::new(),
+                    };
+                }
+                place.path.push((Hop::Field(name), *span));
+                Ok(place)
             }
             _ => Err(Diagnostic::error(
                 "invalid assignment target",
@@ -814,9 +1504,64 @@ impl<'a> Interp<'a> {
         }
     }
 
-    /// The arena never frees, so runaway allocation must become a
+    fn read_place(&mut self, place: &Place) -> Result<Value, Diagnostic> {
+        let mut v = match place.root {
+            Root::Var(name, span) => self.lookup(name, span)?,
+            Root::Elem(id, i, span) => self.element(id, i, span)?.clone(),
+            Root::Obj(id) => Value::Ref(id),
+        };
+        for (hop, span) in &place.path {
+            v = match hop {
+                Hop::Field(field) => self.get_field(&v, field, *span)?,
+                Hop::Slot(i) => match v {
+                    Value::Fixed(mut items) => items.swap_remove(*i),
+                    other => return Err(not_fixed(&other, *span)),
+                },
+            };
+        }
+        Ok(v)
+    }
+
+    /// Writes `v` into a resolved place: value hops are set in place
+    /// inside the root's value, a refstruct root sets its field on the
+    /// shared object.
+    // ponytail: reads clone the intermediate structs — fine for the
+    // interpreter (the oracle); codegen reads and writes in place.
+    fn write_place(&mut self, place: &Place, v: Value) -> Result<(), Diagnostic> {
+        let (slot, path) = match place.root {
+            Root::Var(name, span) => (self.slot_mut(name, span)?, &place.path[..]),
+            Root::Elem(id, i, span) => (self.element(id, i, span)?, &place.path[..]),
+            Root::Obj(id) => {
+                let (Hop::Field(field), span) = place.path[0] else {
+                    unreachable!("a refstruct root's first hop names its field")
+                };
+                let slot = field_mut(&mut self.heap.structs[id].fields, field, span)?;
+                (slot, &place.path[1..])
+            }
+        };
+        set_path(slot, path, v)
+    }
+
+    /// An array slot a place resolved to, bounds-checked on each access
+    /// like the compiled `IndexSet` — a `??=` value runs between the read
+    /// and the write.
+    fn element(&mut self, id: usize, i: usize, span: Span) -> Result<&mut Value, Diagnostic> {
+        let len = self.heap.arrays[id].len();
+        self.heap.arrays[id].get_mut(i).ok_or_else(|| {
+            Diagnostic::error(format!("index {i} out of bounds (length {len})"), span)
+        })
+    }
+
+    /// The program's region never frees, so runaway allocation must become a
     /// sanctioned diagnostic (like the depth limit) instead of an OOM kill.
     fn check_heap(&self, span: Span) -> Result<(), Diagnostic> {
+        // Comptime objects would die with the compiler (ADR 0047).
+        if self.comptime {
+            return Err(Diagnostic::error(
+                "a const initializer cannot allocate — consts are computed at compile time",
+                span,
+            ));
+        }
         if self.heap.cell_count() >= MAX_HEAP_CELLS {
             return Err(Diagnostic::error(
                 format!("heap limit ({MAX_HEAP_CELLS} objects) exceeded"),
@@ -833,6 +1578,7 @@ impl<'a> Interp<'a> {
         match container {
             Value::Struct { fields, .. } => get_in_fields(fields, field, span),
             Value::Ref(id) => get_in_fields(&self.heap.structs[*id].fields, field, span),
+            Value::Tuple(items) => Ok(items[tuple_index(items, field, span)?].clone()),
             other => Err(Diagnostic::error(
                 format!("type {} has no fields", other.type_name()),
                 span,
@@ -840,6 +1586,416 @@ impl<'a> Interp<'a> {
         }
     }
 
+    /// `for [k, v] in m` (ADR 0042): live positional iteration, like
+    /// arrays — an insert during the loop is visited, a remove shifts
+    /// the later entries down one place. Key and value clone out
+    /// before the body runs.
+    fn exec_map_for(
+        &mut self,
+        id: usize,
+        label: &'a Option<String>,
+        index: &'a Option<String>,
+        name: &'a str,
+        body: &'a [Stmt],
+    ) -> Result<Flow, Diagnostic> {
+        let mut i = 0;
+        while let Some((key, value)) = self.heap.maps[id].get(i).cloned() {
+            let mut scope = HashMap::from([(name.to_string(), value)]);
+            if let Some(index) = index {
+                scope.insert(index.clone(), key);
+            }
+            self.scopes.push(scope);
+            let flow = self.exec_block(body);
+            self.scopes.pop();
+            if let Some(flow) = flow?.after_pass(label.as_deref()) {
+                return Ok(flow);
+            }
+            i += 1;
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// `for x in v` over elements evaluated once: a string's bytes as
+    /// `u8` (ADR 0053) — strings are immutable — or the copy a fixed
+    /// array evaluated to (ADR 0056), which the body cannot reach.
+    fn exec_items_for(
+        &mut self,
+        items: impl IntoIterator<Item = Value>,
+        label: &'a Option<String>,
+        index: &'a Option<String>,
+        name: &'a str,
+        body: &'a [Stmt],
+    ) -> Result<Flow, Diagnostic> {
+        for (i, item) in items.into_iter().enumerate() {
+            let mut scope = HashMap::from([(name.to_string(), item)]);
+            if let Some(index) = index {
+                scope.insert(index.clone(), Value::Int(i as i64));
+            }
+            self.scopes.push(scope);
+            let flow = self.exec_block(body);
+            self.scopes.pop();
+            if let Some(flow) = flow?.after_pass(label.as_deref()) {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// `for i in a..b step s` (ADR 0051): bounds and step evaluate once,
+    /// in that order. The counter stops at the end, or where the next
+    /// step would leave the int range.
+    fn exec_range_for(
+        &mut self,
+        label: &'a Option<String>,
+        name: &'a str,
+        range: &'a Expr,
+        body: &'a [Stmt],
+    ) -> Result<Flow, Diagnostic> {
+        let Expr::Range {
+            start: Some(start),
+            end: Some(end),
+            inclusive,
+            step,
+            span,
+        } = range
+        else {
+            return Err(Diagnostic::error(
+                "a 'for' range needs both bounds",
+                range.span(),
+            ));
+        };
+        let mut i = self.eval_int(start)?;
+        let end = self.eval_int(end)?;
+        let step = match step {
+            Some(step) => self.eval_int(step)?,
+            None => 1,
+        };
+        if step == 0 {
+            return Err(Diagnostic::error("range step must not be zero", *span));
+        }
+        loop {
+            let more = match (step > 0, inclusive) {
+                (true, false) => i < end,
+                (true, true) => i <= end,
+                (false, false) => i > end,
+                (false, true) => i >= end,
+            };
+            if !more {
+                break;
+            }
+            self.scopes
+                .push(HashMap::from([(name.to_string(), Value::Int(i))]));
+            let flow = self.exec_block(body);
+            self.scopes.pop();
+            if let Some(flow) = flow?.after_pass(label.as_deref()) {
+                return Ok(flow);
+            }
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// `xs[a..b]` (ADR 0051): a new array copying the elements in
+    /// `a..b`; `s[a..b]` the bytes in `a..b` (ADR 0053). An open start
+    /// is 0, an open end the length.
+    fn eval_slice(
+        &mut self,
+        base: &'a Expr,
+        range: &'a Expr,
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let Expr::Range {
+            start,
+            end,
+            inclusive,
+            ..
+        } = range
+        else {
+            return Err(Diagnostic::error(
+                "slice bounds must be a range",
+                range.span(),
+            ));
+        };
+        let base = self.eval(base)?;
+        let len = match &base {
+            Value::Array(id) => self.heap.arrays[*id].len(),
+            Value::Str(bytes) => bytes.len(),
+            _ => {
+                return Err(Diagnostic::error(
+                    "only arrays and strings can be sliced",
+                    span,
+                ));
+            }
+        };
+        let lo = match start {
+            Some(start) => self.eval_int(start)?,
+            None => 0,
+        };
+        let hi = match end {
+            Some(end) if *inclusive => self.eval_int(end)?.wrapping_add(1),
+            Some(end) => self.eval_int(end)?,
+            None => len as i64,
+        };
+        self.slice_of(base, lo, hi, span)
+    }
+
+    /// The bounds check and cut behind `xs[a..b]` and `slice(xs, a, b)`
+    /// (ADR 0055): against the length now, a fresh array or a string's
+    /// bytes.
+    fn slice_of(&mut self, base: Value, lo: i64, hi: i64, span: Span) -> Result<Value, Diagnostic> {
+        let len = match &base {
+            Value::Array(id) => self.heap.arrays[*id].len(),
+            Value::Str(bytes) => bytes.len(),
+            _ => {
+                return Err(Diagnostic::error(
+                    "only arrays and strings can be sliced",
+                    span,
+                ));
+            }
+        };
+        let bounds = usize::try_from(lo)
+            .ok()
+            .zip(usize::try_from(hi).ok())
+            .filter(|&(lo, hi)| lo <= hi && hi <= len);
+        let Some((lo, hi)) = bounds else {
+            return Err(Diagnostic::error(
+                format!("slice {lo}..{hi} out of bounds (length {len})"),
+                span,
+            ));
+        };
+        let id = match base {
+            Value::Str(bytes) => return Ok(Value::Str(bytes[lo..hi].to_vec())),
+            Value::Array(id) => id,
+            _ => unreachable!("the length match admitted only arrays and strings"),
+        };
+        let items = self.heap.arrays[id][lo..hi].to_vec();
+        self.check_heap(span)?;
+        let region = self.heap.region();
+        Ok(Value::Array(self.heap.arrays.alloc(items, region)))
+    }
+
+    fn eval_int(&mut self, e: &'a Expr) -> Result<i64, Diagnostic> {
+        match self.eval(e)? {
+            Value::Int(n) => Ok(n),
+            other => Err(Diagnostic::error(
+                format!("expected int, found {}", other.type_name()),
+                e.span(),
+            )),
+        }
+    }
+
+    /// The entry position holding `key` — `==` on values, which is the
+    /// compiled hash-and-compare's equality (ADR 0042).
+    fn map_find(&self, id: usize, key: &Value) -> Option<usize> {
+        self.heap.maps[id].iter().position(|(k, _)| k == key)
+    }
+
+    /// The string library (ADR 0054): arguments evaluate left to right,
+    /// then one byte-level helper runs. An empty `split` separator or
+    /// `replace` pattern is a runtime error — it names no place to cut.
+    fn string_builtin(
+        &mut self,
+        name: &str,
+        args: &'a [Expr],
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let mut strs = Vec::with_capacity(args.len());
+        let mut parts = None;
+        for arg in args {
+            match self.eval(arg)? {
+                Value::Str(bytes) => strs.push(bytes),
+                Value::Array(id) if name == syntax::BUILTIN_JOIN => parts = Some(id),
+                other => {
+                    return Err(Diagnostic::error(
+                        format!("'{name}' expects a string, found {}", other.type_name()),
+                        span,
+                    ));
+                }
+            }
+        }
+        let int = |n: Option<usize>| n.map_or(Value::Null, |n| Value::Int(n as i64));
+        let strings = |pieces: Vec<&[u8]>| -> Vec<Value> {
+            pieces.into_iter().map(|p| Value::Str(p.to_vec())).collect()
+        };
+        Ok(match (name, strs.as_slice()) {
+            (syntax::BUILTIN_FIND, [s, sub]) => int(text::find(s, sub)),
+            (syntax::BUILTIN_STARTS_WITH, [s, p]) => Value::Bool(s.starts_with(p)),
+            (syntax::BUILTIN_ENDS_WITH, [s, p]) => Value::Bool(s.ends_with(p)),
+            (syntax::BUILTIN_SPLIT | syntax::BUILTIN_REPLACE, [_, sep, ..]) if sep.is_empty() => {
+                let what = if name == syntax::BUILTIN_SPLIT {
+                    "separator"
+                } else {
+                    "pattern"
+                };
+                return Err(Diagnostic::error(
+                    format!("'{name}' {what} must not be empty"),
+                    span,
+                ));
+            }
+            (syntax::BUILTIN_SPLIT, [s, sep]) => {
+                let pieces = strings(text::split(s, sep));
+                self.check_heap(span)?;
+                let region = self.heap.region();
+                Value::Array(self.heap.arrays.alloc(pieces, region))
+            }
+            (syntax::BUILTIN_REPLACE, [s, from, to]) => Value::Str(text::replace(s, from, to)),
+            (syntax::BUILTIN_TRIM, [s]) => Value::Str(text::trim(s).to_vec()),
+            (syntax::BUILTIN_TO_UPPER, [s]) => Value::Str(s.to_ascii_uppercase()),
+            (syntax::BUILTIN_TO_LOWER, [s]) => Value::Str(s.to_ascii_lowercase()),
+            (syntax::BUILTIN_JOIN, [sep]) => {
+                let id = parts.expect("join's first argument is the array");
+                let mut out = Vec::new();
+                for (i, part) in self.heap.arrays[id].iter().enumerate() {
+                    let Value::Str(bytes) = part else {
+                        return Err(Diagnostic::error("'join' expects a string[]", span));
+                    };
+                    if i > 0 {
+                        out.extend_from_slice(sep);
+                    }
+                    out.extend_from_slice(bytes);
+                }
+                Value::Str(out)
+            }
+            (syntax::BUILTIN_PARSE_INT, [s]) => text::parse_int(s).map_or(Value::Null, Value::Int),
+            (syntax::BUILTIN_PARSE_FLOAT, [s]) => {
+                text::parse_float(s).map_or(Value::Null, Value::Float)
+            }
+            _ => {
+                return Err(Diagnostic::error(
+                    format!("'{name}' called with the wrong arguments"),
+                    span,
+                ));
+            }
+        })
+    }
+
+    /// The array library (ADR 0055): arguments evaluate left to right,
+    /// then the array changes in place. Positions check like indexing —
+    /// `insert` also admits the end. `sort` computes every key first,
+    /// in element order, then reorders stably; the order is numeric,
+    /// IEEE total for floats, bytewise for strings.
+    fn array_builtin(
+        &mut self,
+        name: &str,
+        args: &'a [Expr],
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let mut argv = Vec::with_capacity(args.len());
+        for arg in args {
+            argv.push(self.eval(arg)?);
+        }
+        let Some(&Value::Array(id)) = argv.first() else {
+            return Err(Diagnostic::error(
+                format!("'{name}' expects an array"),
+                span,
+            ));
+        };
+        let len = self.heap.arrays[id].len();
+        Ok(match (name, &argv[1..]) {
+            (syntax::BUILTIN_POP, []) => self.heap.arrays[id].pop().unwrap_or(Value::Null),
+            (syntax::BUILTIN_CLEAR, []) => {
+                self.heap.arrays[id].clear();
+                Value::Unit
+            }
+            (syntax::BUILTIN_REVERSE, []) => {
+                self.heap.arrays[id].reverse();
+                Value::Unit
+            }
+            (syntax::BUILTIN_INSERT, [Value::Int(i), value]) => {
+                let Some(at) = usize::try_from(*i).ok().filter(|&at| at <= len) else {
+                    return Err(Diagnostic::error(
+                        format!("index {i} out of bounds (length {len})"),
+                        span,
+                    ));
+                };
+                self.heap.arrays[id].insert(at, value.clone());
+                Value::Unit
+            }
+            (syntax::BUILTIN_REMOVE_AT, [index]) => {
+                let (_, at) = self.index_array(Value::Array(id), index.clone(), span)?;
+                self.heap.arrays[id].remove(at)
+            }
+            (syntax::BUILTIN_SLICE, [Value::Int(lo), Value::Int(hi)]) => {
+                self.slice_of(Value::Array(id), *lo, *hi, span)?
+            }
+            (syntax::BUILTIN_SORT, rest) => {
+                let keys = match rest {
+                    [key] => {
+                        let mut keys = Vec::with_capacity(len);
+                        for i in 0..len {
+                            let (_, i) =
+                                self.index_array(Value::Array(id), Value::Int(i as i64), span)?;
+                            let x = self.heap.arrays[id][i].clone();
+                            keys.push(self.call_value(key.clone(), vec![x], span)?);
+                        }
+                        keys
+                    }
+                    _ => self.heap.arrays[id].clone(),
+                };
+                let n = keys.len().min(self.heap.arrays[id].len());
+                let mut order: Vec<usize> = (0..n).collect();
+                order.sort_by(|&a, &b| sort_order(&keys[a], &keys[b]));
+                let items = &mut self.heap.arrays[id];
+                let sorted: Vec<Value> = order.iter().map(|&i| items[i].clone()).collect();
+                items.splice(..n, sorted);
+                Value::Unit
+            }
+            (syntax::BUILTIN_INDEX_OF | syntax::BUILTIN_CONTAINS, [value]) => {
+                let at = self.heap.arrays[id].iter().position(|x| x == value);
+                if name == syntax::BUILTIN_CONTAINS {
+                    Value::Bool(at.is_some())
+                } else {
+                    at.map_or(Value::Null, |i| Value::Int(i as i64))
+                }
+            }
+            _ => {
+                return Err(Diagnostic::error(
+                    format!("'{name}' called with the wrong arguments"),
+                    span,
+                ));
+            }
+        })
+    }
+
+    /// `insert`/`remove`/`has` (ADR 0042): arguments evaluate left to
+    /// right, then the table changes — an insert of a new key appends,
+    /// of a present key updates in place; a remove closes the gap.
+    fn map_builtin(
+        &mut self,
+        name: &str,
+        args: &'a [Expr],
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let mut argv = Vec::with_capacity(args.len());
+        for arg in args {
+            argv.push(self.eval(arg)?);
+        }
+        let (id, key) = match argv.as_slice() {
+            [Value::Map(id), key, ..] => (*id, key.clone()),
+            _ => {
+                return Err(Diagnostic::error(format!("'{name}' expects a map"), span));
+            }
+        };
+        let found = self.map_find(id, &key);
+        if name == syntax::BUILTIN_INSERT {
+            let value = argv.pop().expect("checked arity");
+            match found {
+                Some(i) => self.heap.maps[id][i].1 = value,
+                None => self.heap.maps[id].push((key, value)),
+            }
+            return Ok(Value::Unit);
+        }
+        if name == syntax::BUILTIN_REMOVE
+            && let Some(i) = found
+        {
+            self.heap.maps[id].remove(i);
+        }
+        Ok(Value::Bool(found.is_some()))
+    }
+
     /// Bounds-checked (cell, index) extraction shared by element reads and
     /// writes. The value shapes are checker-guaranteed; the bounds aren't.
     fn index_array(
@@ -870,8 +2026,25 @@ impl<'a> Interp<'a> {
         self.slot_mut(name, span).map(|v| v.clone())
     }
 
-    /// The scope slot holding `name`, innermost first.
+    /// The scope slot holding `name`, innermost first — or the module
+    /// var the checker resolved it to (ADR 0048).
     fn slot_mut(&mut self, name: &str, span: Span) -> Result<&mut Value, Diagnostic> {
+        if let Some(key) = self.resolutions.global_refs.get(&span) {
+            if self.comptime {
+                return Err(Diagnostic::error(
+                    format!(
+                        "a const initializer cannot use module var '{name}' — it has no value until the program runs"
+                    ),
+                    span,
+                ));
+            }
+            return self.globals.get_mut(key).ok_or_else(|| {
+                Diagnostic::error(
+                    format!("module var '{name}' is used before it is initialized"),
+                    span,
+                )
+            });
+        }
         self.scopes
             .iter_mut()
             .rev()
@@ -880,6 +2053,106 @@ impl<'a> Interp<'a> {
     }
 }
 
+/// The world-interface builtins (ADR 0031) and `print` — everything
+/// comptime refuses as I/O (ADR 0047).
+fn is_io_builtin(name: &str) -> bool {
+    [
+        syntax::BUILTIN_PRINT,
+        syntax::BUILTIN_OPEN,
+        syntax::BUILTIN_READ,
+        syntax::BUILTIN_READLINE,
+        syntax::BUILTIN_WRITE,
+        syntax::BUILTIN_CLOSE,
+    ]
+    .contains(&name)
+}
+
+/// The array library's names (ADR 0055); `insert` is shared with maps
+/// and dispatched by type at the call.
+fn is_array_builtin(name: &str) -> bool {
+    [
+        syntax::BUILTIN_POP,
+        syntax::BUILTIN_REMOVE_AT,
+        syntax::BUILTIN_SLICE,
+        syntax::BUILTIN_CLEAR,
+        syntax::BUILTIN_SORT,
+        syntax::BUILTIN_REVERSE,
+        syntax::BUILTIN_INDEX_OF,
+        syntax::BUILTIN_CONTAINS,
+    ]
+    .contains(&name)
+}
+
+/// `sort`'s key order (ADR 0055): ints numerically, floats by IEEE
+/// total order (-0.0 before 0.0, NaNs at the ends), strings bytewise.
+fn sort_order(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a.cmp(b),
+        // The canonical word orders every sized type but `u64` (ADR 0052).
+        (Value::Sized(IntTy::U64, a), Value::Sized(_, b)) => (*a as u64).cmp(&(*b as u64)),
+        (Value::Sized(_, a), Value::Sized(_, b)) => a.cmp(b),
+        (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+        (Value::Str(a), Value::Str(b)) => a.cmp(b),
+        _ => std::cmp::Ordering::Equal,
+    }
+}
+
+/// The string library's names (ADR 0054).
+fn is_string_builtin(name: &str) -> bool {
+    [
+        syntax::BUILTIN_FIND,
+        syntax::BUILTIN_STARTS_WITH,
+        syntax::BUILTIN_ENDS_WITH,
+        syntax::BUILTIN_SPLIT,
+        syntax::BUILTIN_TRIM,
+        syntax::BUILTIN_REPLACE,
+        syntax::BUILTIN_JOIN,
+        syntax::BUILTIN_TO_UPPER,
+        syntax::BUILTIN_TO_LOWER,
+        syntax::BUILTIN_PARSE_INT,
+        syntax::BUILTIN_PARSE_FLOAT,
+    ]
+    .contains(&name)
+}
+
+/// `chars(s)` (ADR 0053): UTF-8 decoding where every byte that does
+/// not start a well-formed sequence — a stray continuation, a
+/// truncated or overlong form, a surrogate, past U+10FFFF — yields
+/// U+FFFD and decoding resumes at the next byte. The compiled
+/// `ys_chars` routine walks the same rules.
+fn code_points(bytes: &[u8]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i] as u32;
+        let (extra, bits, min) = match lead {
+            0x00..=0x7f => (0, lead, 0),
+            0xc2..=0xdf => (1, lead & 0x1f, 0x80),
+            0xe0..=0xef => (2, lead & 0x0f, 0x800),
+            0xf0..=0xf4 => (3, lead & 0x07, 0x1_0000),
+            _ => (0, 0xfffd, 0),
+        };
+        let tail = bytes.get(i + 1..i + 1 + extra).unwrap_or(&[0]);
+        let point = tail
+            .iter()
+            .try_fold(bits, |c, &b| {
+                (b & 0xc0 == 0x80).then_some(c << 6 | (b & 0x3f) as u32)
+            })
+            .filter(|&c| c >= min && c <= 0x10_ffff && !(0xd800..=0xdfff).contains(&c));
+        match point {
+            Some(c) => {
+                out.push(c);
+                i += 1 + extra;
+            }
+            None => {
+                out.push(0xfffd);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
 /// Field lookup/update on a sorted fields vec — shared by inline structs
 /// and heap objects. Error arms are defensive; the checker validated fields.
 fn get_in_fields(fields: &[(String, Value)], field: &str, span: Span) -> Result<Value, Diagnostic> {
@@ -890,26 +2163,82 @@ fn get_in_fields(fields: &[(String, Value)], field: &str, span: Span) -> Result<
         .ok_or_else(|| Diagnostic::error(format!("no field '{field}'"), span))
 }
 
-fn set_in_fields(
-    fields: &mut [(String, Value)],
+fn field_mut<'v>(
+    fields: &'v mut [(String, Value)],
     field: &str,
-    v: Value,
     span: Span,
-) -> Result<(), Diagnostic> {
-    match fields.iter_mut().find(|(fname, _)| fname == field) {
-        Some((_, slot)) => {
-            *slot = v;
-            Ok(())
+) -> Result<&'v mut Value, Diagnostic> {
+    fields
+        .iter_mut()
+        .find(|(fname, _)| fname == field)
+        .map(|(_, slot)| slot)
+        .ok_or_else(|| Diagnostic::error(format!("no field '{field}'"), span))
+}
+
+/// Sets the value `path` names inside `slot` — struct fields by name,
+/// tuple elements by position (ADR 0043), fixed array elements by
+/// index (ADR 0056).
+fn set_path(slot: &mut Value, path: &[(Hop, Span)], v: Value) -> Result<(), Diagnostic> {
+    let Some(&(hop, span)) = path.first() else {
+        *slot = v;
+        return Ok(());
+    };
+    let next = match (hop, slot) {
+        (Hop::Field(field), Value::Struct { fields, .. }) => field_mut(fields, field, span)?,
+        (Hop::Field(field), Value::Tuple(items)) => {
+            let i = tuple_index(items, field, span)?;
+            &mut items[i]
+        }
+        (Hop::Slot(i), Value::Fixed(items)) => &mut items[i],
+        (Hop::Slot(_), other) => return Err(not_fixed(other, span)),
+        (Hop::Field(_), other) => {
+            return Err(Diagnostic::error(
+                format!("type {} has no fields", other.type_name()),
+                span,
+            ));
         }
-        None => Err(Diagnostic::error(format!("no field '{field}'"), span)),
+    };
+    set_path(next, &path[1..], v)
+}
+
+/// The position `index` names in a fixed array of `len` elements
+/// (ADR 0056), checked like an array index.
+fn fixed_index(len: usize, index: Value, span: Span) -> Result<usize, Diagnostic> {
+    match index {
+        Value::Int(i) => usize::try_from(i).ok().filter(|&i| i < len).ok_or_else(|| {
+            Diagnostic::error(format!("index {i} out of bounds (length {len})"), span)
+        }),
+        other => Err(Diagnostic::error(
+            format!("cannot index fixed array with {}", other.type_name()),
+            span,
+        )),
     }
 }
 
+fn not_fixed(v: &Value, span: Span) -> Diagnostic {
+    Diagnostic::error(
+        format!("cannot index {} as a fixed array", v.type_name()),
+        span,
+    )
+}
+
+/// The position a tuple field names (`.0`, `.1`, …), bounds-checked.
+fn tuple_index(items: &[Value], field: &str, span: Span) -> Result<usize, Diagnostic> {
+    field
+        .parse::<usize>()
+        .ok()
+        .filter(|i| *i < items.len())
+        .ok_or_else(|| Diagnostic::error(format!("no element '{field}'"), span))
+}
+
 fn eval_unary(op: UnOp, v: Value, span: Span) -> Result<Value, Diagnostic> {
     match (op, v) {
         (UnOp::Neg, Value::Int(n)) => Ok(Value::Int(-n)),
         (UnOp::Neg, Value::Float(f)) => Ok(Value::Float(-f)),
         (UnOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+        (UnOp::BitNot, Value::Int(n)) => Ok(Value::Int(!n)),
+        (UnOp::Neg, Value::Sized(t, n)) => Ok(Value::Sized(t, t.wrap(n.wrapping_neg()))),
+        (UnOp::BitNot, Value::Sized(t, n)) => Ok(Value::Sized(t, t.wrap(!n))),
         (UnOp::Neg, other) => Err(Diagnostic::error(
             format!("cannot negate {}", other.type_name()),
             span,
@@ -918,6 +2247,10 @@ fn eval_unary(op: UnOp, v: Value, span: Span) -> Result<Value, Diagnostic> {
             format!("cannot apply '!' to {}", other.type_name()),
             span,
         )),
+        (UnOp::BitNot, other) => Err(Diagnostic::error(
+            format!("cannot apply '~' to {}", other.type_name()),
+            span,
+        )),
     }
 }
 
@@ -926,6 +2259,7 @@ fn eval_unary(op: UnOp, v: Value, span: Span) -> Result<Value, Diagnostic> {
 fn eval_binary(op: BinOp, l: Value, r: Value, span: Span) -> Result<Value, Diagnostic> {
     match (l, r) {
         (Value::Int(a), Value::Int(b)) => int_op(op, a, b, span),
+        (Value::Sized(t, a), Value::Sized(_, b)) => sized_op(op, t, a, b, span),
         (Value::Float(a), Value::Float(b)) => float_op(op, a, b, span),
         (Value::Str(a), Value::Str(b)) => str_op(op, a, b, span),
         (Value::Bool(a), Value::Bool(b)) => bool_op(op, a, b, span),
@@ -969,6 +2303,71 @@ fn int_op(op: BinOp, a: i64, b: i64, span: Span) -> Result<Value, Diagnostic> {
         Le => Value::Bool(a <= b),
         Gt => Value::Bool(a > b),
         Ge => Value::Bool(a >= b),
+        BitAnd => Value::Int(a & b),
+        BitOr => Value::Int(a | b),
+        BitXor => Value::Int(a ^ b),
+        // The count must name a bit: 0..=63, in every shift. Hardware
+        // masks it to six bits instead, so a larger or negative count
+        // would silently mean something else (ADR 0049).
+        Shl | Shr | UShr if !(0..64).contains(&b) => {
+            return Err(Diagnostic::error(
+                format!("shift count {b} out of range"),
+                span,
+            ));
+        }
+        // Bits shifted past either end are dropped; `>>` copies the
+        // sign bit in, `>>>` zeros.
+        Shl => Value::Int(a << b),
+        Shr => Value::Int(a >> b),
+        UShr => Value::Int(((a as u64) >> b) as i64),
+        And | Or | Coalesce => {
+            unreachable!("short-circuiting operators are handled lazily in eval")
+        }
+    };
+    Ok(v)
+}
+
+/// Sized integer operators (ADR 0052): `int_op`'s rules computed on
+/// the canonical words, then wrapped to the width — so `i8` MIN / -1
+/// wraps instead of erroring. u64 compares, divides, and `>>`-shifts
+/// as unsigned; `>>>` on a signed type shifts its own-width pattern.
+fn sized_op(op: BinOp, t: IntTy, a: i64, b: i64, span: Span) -> Result<Value, Diagnostic> {
+    use BinOp::*;
+    let u64 = t == IntTy::U64;
+    let (ua, ub) = (a as u64, b as u64);
+    let wrap = |n: i64| Value::Sized(t, t.wrap(n));
+    let v = match op {
+        Add => wrap(a.wrapping_add(b)),
+        Sub => wrap(a.wrapping_sub(b)),
+        Mul => wrap(a.wrapping_mul(b)),
+        Div | Rem if b == 0 => return Err(Diagnostic::error("division by zero", span)),
+        Div if u64 => wrap((ua / ub) as i64),
+        Rem if u64 => wrap((ua % ub) as i64),
+        // Canonical operands are at most 32 bits wide: no i64 overflow.
+        Div => wrap(a / b),
+        Rem => wrap(a % b),
+        Eq => Value::Bool(a == b),
+        Ne => Value::Bool(a != b),
+        Lt if u64 => Value::Bool(ua < ub),
+        Le if u64 => Value::Bool(ua <= ub),
+        Gt if u64 => Value::Bool(ua > ub),
+        Ge if u64 => Value::Bool(ua >= ub),
+        Lt => Value::Bool(a < b),
+        Le => Value::Bool(a <= b),
+        Gt => Value::Bool(a > b),
+        Ge => Value::Bool(a >= b),
+        BitAnd => wrap(a & b),
+        BitOr => wrap(a | b),
+        BitXor => wrap(a ^ b),
+        Shl | Shr | UShr if !(0..64).contains(&b) => {
+            return Err(Diagnostic::error(
+                format!("shift count {b} out of range"),
+                span,
+            ));
+        }
+        Shl => wrap(a << b),
+        Shr if t.signed() => wrap(a >> b),
+        Shr | UShr => wrap((t.unsigned().wrap(a) as u64 >> b) as i64),
         And | Or | Coalesce => {
             unreachable!("short-circuiting operators are handled lazily in eval")
         }
@@ -993,6 +2392,9 @@ fn float_op(op: BinOp, a: f64, b: f64, _span: Span) -> Result<Value, Diagnostic>
         Le => Value::Bool(a <= b),
         Gt => Value::Bool(a > b),
         Ge => Value::Bool(a >= b),
+        BitAnd | BitOr | BitXor | Shl | Shr | UShr => {
+            unreachable!("the checker admits bit operations on ints only")
+        }
         And | Or | Coalesce => {
             unreachable!("short-circuiting operators are handled lazily in eval")
         }
@@ -1031,3 +2433,54 @@ fn bool_op(op: BinOp, a: bool, b: bool, span: Span) -> Result<Value, Diagnostic>
         )),
     }
 }
+
+/// Does `v` match `p`? Binds the pattern's names into `scope` along
+/// the way; a failed match leaves partial bindings the caller drops.
+/// `codes` resolves `error.Name` literals (ADR 0046).
+fn pattern_binds(
+    p: &Pattern,
+    v: &Value,
+    codes: &HashMap<Span, u32>,
+    scope: &mut HashMap<String, Value>,
+) -> bool {
+    match p {
+        Pattern::Bind(name, _) => {
+            if name != "_" {
+                scope.insert(name.clone(), v.clone());
+            }
+            true
+        }
+        Pattern::Lit(e) => match (e, v) {
+            (Expr::Int(n, _), Value::Int(m)) => n == m,
+            (Expr::Str(s, _), Value::Str(t)) => s.as_bytes() == t.as_slice(),
+            (Expr::Bool(b, _), Value::Bool(c)) => b == c,
+            _ => false,
+        },
+        Pattern::Variant { name, args, .. } => {
+            let Value::Enum {
+                variant, payloads, ..
+            } = v
+            else {
+                return false;
+            };
+            variant == name
+                && args
+                    .iter()
+                    .zip(payloads)
+                    .all(|(a, pv)| pattern_binds(a, pv, codes, scope))
+        }
+        // Without a list the code alone decides (ADR 0062).
+        Pattern::Error {
+            name_span, args, ..
+        } => {
+            let Value::Err(code, ErrPayload(payloads)) = v else {
+                return false;
+            };
+            codes[name_span] == *code
+                && args
+                    .iter()
+                    .zip(payloads)
+                    .all(|(a, pv)| pattern_binds(a, pv, codes, scope))
+        }
+    }
+}
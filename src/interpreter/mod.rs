@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use crate::ast::{BinOp, Conv, Expr, Function, Item, Stmt, UnOp};
-use crate::check::Resolutions;
+use crate::ast::{BinOp, Conv, Expr, Function, Item, MatchArm, Param, Pattern, Stmt, UnOp};
+use crate::check::{ConstValue, Resolutions};
 use crate::diagnostic::Diagnostic;
 use crate::modules::ModuleGraph;
 use crate::span::Span;
 use crate::syntax;
+use crate::types::{IntTy, Type};
 
 // ---- Interpreter policy ----------------------------------------------
 // One unit of evaluation depth (a call, statement, or expression level)
@@ -14,25 +15,105 @@ use crate::syntax;
 // stack is sized so the depth budget always binds first, with headroom
 // for frame growth: 65_536 units x 32KB = 2GB = INTERP_STACK_BYTES — a
 // virtual reservation, faulted only as used. The heap cap turns
-// runaway allocation into a diagnostic instead of an OOM kill.
+// runaway allocation into a diagnostic instead of an OOM kill. The
+// step cap does the same for a const initializer that never finishes:
+// `build` and `run` must not hang at compile time (ADR 0047).
 const MAX_EVAL_DEPTH: usize = 65_536;
 const MAX_HEAP_CELLS: usize = 1 << 20;
+const MAX_COMPTIME_STEPS: u64 = 10_000_000;
 const INTERP_STACK_BYTES: usize = 1 << 31;
 
-/// The interpreter's arena: every refstruct object and array buffer lives
-/// here, addressed by handle into its own typed table — a `Value::Ref` can
-/// only name a struct object and a `Value::Array` only a buffer, so no
-/// mismatch arm exists anywhere. Nothing is freed mid-run; the arena drops
-/// wholesale when execution ends (ADR 0009's collector-free story), which
-/// also makes reference cycles harmless.
+/// The interpreter's arena: every refstruct object, array buffer, and map
+/// lives here, addressed by handle into its own typed table — a
+/// `Value::Ref` can only name a struct object and a `Value::Array` only a
+/// buffer, so no
+/// mismatch arm exists anywhere. Cells bind to a region (ADR 0061): the
+/// program's own drops wholesale when execution ends (ADR 0009's
+/// collector-free story), which also makes reference cycles harmless;
+/// an `arena` block's frees when the block exits, and a handle into it
+/// is dead from then on.
 #[derive(Debug, Default)]
 pub struct Heap {
-    structs: Vec<StructObj>,
-    arrays: Vec<Vec<Value>>,
-    files: Vec<FileEntry>,
+    structs: Cells<StructObj>,
+    arrays: Cells<Vec<Value>>,
+    /// Maps (ADR 0042): entries in insertion order, found by a linear
+    /// `==` scan — the oracle keeps the contract, not the hashing.
+    maps: Cells<Vec<(Value, Value)>>,
+    files: Cells<FileEntry>,
+    closures: Cells<ClosureObj>,
     /// Declared error names, code = index + 2 (ADR 0034) — rendering
     /// context for `Value::Err`, copied from Resolutions at startup.
     error_names: Vec<String>,
+    /// Each code's payload types, by the same index (ADR 0062): their
+    /// static types decide which payloads render quoted.
+    error_payloads: Vec<Vec<Type>>,
+    /// The open `arena` regions, innermost last: each one's id and the
+    /// table lengths when it opened. Every cell past those marks is its
+    /// own or an already-freed inner region's.
+    open: Vec<(usize, [usize; 5])>,
+    /// Per region ever opened (id - 1): freed yet? Region 0 — the
+    /// program's own — never is.
+    freed: Vec<bool>,
+    /// Cells freed with their region: the heap cap counts live ones.
+    released: usize,
+}
+
+/// One heap table, each cell tagged with the region it was allocated
+/// in (ADR 0061). Indexing reaches the cell whatever its region;
+/// `Heap::alive` is the check.
+#[derive(Debug)]
+struct Cells<T> {
+    items: Vec<T>,
+    regions: Vec<usize>,
+}
+
+impl<T> Default for Cells<T> {
+    fn default() -> Self {
+        Cells {
+            items: Vec::new(),
+            regions: Vec::new(),
+        }
+    }
+}
+
+impl<T> Cells<T> {
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Stores a new cell in `region` and returns its handle.
+    fn alloc(&mut self, item: T, region: usize) -> usize {
+        self.items.push(item);
+        self.regions.push(region);
+        self.items.len() - 1
+    }
+
+    /// Empties every cell of `region` from `start` on; returns how many.
+    fn release(&mut self, start: usize, region: usize, empty: impl Fn(&mut T)) -> usize {
+        let mut count = 0;
+        for (item, _) in self.items[start..]
+            .iter_mut()
+            .zip(&self.regions[start..])
+            .filter(|(_, r)| **r == region)
+        {
+            empty(item);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T> std::ops::Index<usize> for Cells<T> {
+    type Output = T;
+    fn index(&self, id: usize) -> &T {
+        &self.items[id]
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for Cells<T> {
+    fn index_mut(&mut self, id: usize) -> &mut T {
+        &mut self.items[id]
+    }
 }
 
 /// An open file's engine state (ADR 0031): the mode decides which
@@ -54,9 +135,69 @@ struct StructObj {
     fields: Vec<(String, Value)>,
 }
 
+/// A closure (ADR 0039): the function literal it was created from
+/// (by span), the module its body resolves names in, and its captured
+/// values — copies of value types, handles of refstructs and arrays.
+#[derive(Debug)]
+struct ClosureObj {
+    literal: Span,
+    module: usize,
+    captures: Vec<(String, Value)>,
+}
+
 impl Heap {
     fn cell_count(&self) -> usize {
-        self.structs.len() + self.arrays.len()
+        self.structs.len() + self.arrays.len() + self.maps.len() + self.closures.len()
+            - self.released
+    }
+
+    /// The region new cells bind to: the innermost open arena's, else
+    /// the program's own (0).
+    fn region(&self) -> usize {
+        self.open.last().map_or(0, |(id, _)| *id)
+    }
+
+    /// Opens an `arena` block's region (ADR 0061).
+    fn enter_region(&mut self) {
+        self.freed.push(false);
+        let marks = [
+            self.structs.len(),
+            self.arrays.len(),
+            self.maps.len(),
+            self.files.len(),
+            self.closures.len(),
+        ];
+        self.open.push((self.freed.len(), marks));
+    }
+
+    /// Frees the innermost region: its cells empty out, and every
+    /// handle into them is dead. An open file is closed with its cell.
+    fn leave_region(&mut self) {
+        let (id, marks) = self.open.pop().expect("paired with enter_region");
+        self.freed[id - 1] = true;
+        let mut released = self
+            .structs
+            .release(marks[0], id, |o| o.fields = Vec::new());
+        released += self.arrays.release(marks[1], id, |a| *a = Vec::new());
+        released += self.maps.release(marks[2], id, |m| *m = Vec::new());
+        self.files.release(marks[3], id, |f| *f = FileEntry::Closed);
+        released += self
+            .closures
+            .release(marks[4], id, |c| c.captures = Vec::new());
+        self.released += released;
+    }
+
+    /// The kind of cell `v` is a dead handle to, if it is one.
+    fn dead(&self, v: &Value) -> Option<&'static str> {
+        let (region, kind) = match v {
+            Value::Ref(id) => (self.structs.regions[*id], "refstruct"),
+            Value::Array(id) => (self.arrays.regions[*id], "array"),
+            Value::Map(id) => (self.maps.regions[*id], "map"),
+            Value::File(id) => (self.files.regions[*id], "file"),
+            Value::Closure(id) => (self.closures.regions[*id], "closure"),
+            _ => return None,
+        };
+        (region > 0 && self.freed[region - 1]).then_some(kind)
     }
 }
 
@@ -66,6 +207,9 @@ impl Heap {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Int(i64),
+    /// A sized integer (ADR 0052) in the canonical word form
+    /// `IntTy::wrap` keeps — so derived `PartialEq` is value equality.
+    Sized(IntTy, i64),
     Float(f64),
     Bool(bool),
     /// Raw length-carried bytes — the ADR 0013 representation. Source
@@ -93,26 +237,55 @@ pub enum Value {
         variant: String,
         payloads: Vec<Value>,
     },
+    /// A tuple (ADR 0043): its elements in order. Value semantics like
+    /// a struct; derived `PartialEq` is the element-wise equality.
+    Tuple(Vec<Value>),
+    /// A fixed array (ADR 0056): its elements inline. Value semantics
+    /// like a tuple, so a copy is a clone of the vector.
+    Fixed(Vec<Value>),
     /// A `refstruct` instance: a handle to one shared heap object, aliased
     /// by every copy of the handle.
     Ref(usize),
     /// An array: a handle to one shared, growable heap buffer.
     Array(usize),
+    /// A map (ADR 0042): a handle to one shared entry table.
+    Map(usize),
     /// An open file (ADR 0031): a handle into the files table, identity
     /// equality like every handle.
     File(usize),
+    /// A function value (ADR 0038): the callee's (module, name) —
+    /// instances by canonical name. Identity equality: the same
+    /// function compares equal however it was named.
+    Func(usize, String),
+    /// A closure (ADR 0039): a handle to its heap object — identity
+    /// equality, like every handle. Never equal to a `Func`.
+    Closure(usize),
     /// A declared error code (ADR 0034) — identity equality by code;
-    /// the name renders through `Heap::error_names`.
-    Err(u32),
+    /// the name renders through `Heap::error_names`. The payloads
+    /// (ADR 0062) ride along but never take part in equality.
+    Err(u32, ErrPayload),
     /// The `null` literal — the empty state of a `T?` slot.
     Null,
     Unit,
 }
 
+/// An error's payloads (ADR 0062). Errors compare by code alone, so
+/// every two payload lists are equal: `Value::Err`'s derived
+/// `PartialEq` stays the code identity.
+#[derive(Debug, Clone, Default)]
+pub struct ErrPayload(pub Vec<Value>);
+
+impl PartialEq for ErrPayload {
+    fn eq(&self, _: &ErrPayload) -> bool {
+        true
+    }
+}
+
 mod eval;
 mod render;
 #[cfg(test)]
 mod tests;
+mod text;
 
 /// Runs `main()` from the entry module (graph index 0), resolving every call
 /// through its module's alias map. Returns `Unit` (and the heap, for
@@ -124,11 +297,29 @@ pub fn interpret(
     resolutions: &Resolutions,
     args: &[Vec<u8>],
 ) -> Result<(Value, Heap), Diagnostic> {
+    on_interpreter_stack(|| eval::run_program(graph, resolutions, args))
+}
+
+/// Computes every module const (ADR 0047) — the interpreter as the
+/// comptime engine. Initializers may call any function, but one that
+/// allocates or does I/O is a diagnostic, as is any run-time error.
+pub fn eval_consts(
+    graph: &ModuleGraph,
+    resolutions: &Resolutions,
+) -> Result<HashMap<(usize, String), ConstValue>, Diagnostic> {
+    on_interpreter_stack(|| eval::run_consts(graph, resolutions))
+}
+
+/// Runs `work` on the interpreter's own thread, sized by
+/// `INTERP_STACK_BYTES`.
+fn on_interpreter_stack<T: Send>(
+    work: impl FnOnce() -> Result<T, Diagnostic> + Send,
+) -> Result<T, Diagnostic> {
     std::thread::scope(|scope| {
         let worker = std::thread::Builder::new()
             .name("interpreter".to_string())
             .stack_size(INTERP_STACK_BYTES)
-            .spawn_scoped(scope, || eval::run_program(graph, resolutions, args));
+            .spawn_scoped(scope, work);
         match worker {
             Ok(handle) => handle
                 .join()
@@ -25,6 +25,8 @@ impl Value {
         }
         match self {
             Value::Int(n) => n.to_string().into_bytes(),
+            Value::Sized(IntTy::U64, n) => (*n as u64).to_string().into_bytes(),
+            Value::Sized(_, n) => n.to_string().into_bytes(),
             Value::Float(f) => f.to_string().into_bytes(),
             Value::Bool(b) => b.to_string().into_bytes(),
             Value::Str(s) => s.clone(),
@@ -33,9 +35,11 @@ impl Value {
             // Opaque handle (ADR 0031): constant text — an address or
             // table index could never match across engines.
             Value::File(_) => b"file".to_vec(),
+            // Same rule for function values (ADR 0038).
+            Value::Func(..) | Value::Closure(_) => b"fun".to_vec(),
             // Codes are never observable — only names render (ADR 0034).
-            Value::Err(code) => {
-                format!("error.{}", heap.error_names[(*code - 2) as usize]).into_bytes()
+            Value::Err(code, ErrPayload(payloads)) => {
+                display_error(heap, *code, payloads, |v| v.display_depth(heap, depth - 1))
             }
             // The hop consumes a level, the object's children another.
             Value::Ref(id) if depth == 1 => {
@@ -49,9 +53,18 @@ impl Value {
             Value::Array(id) => {
                 display_items(&heap.arrays[*id], |v| v.display_depth(heap, depth - 1))
             }
+            // `{a: 1, b: 2}` in insertion order (ADR 0042); keys and
+            // values both sit one level down, like array elements.
+            Value::Map(id) => {
+                display_entries(&heap.maps[*id], |v| v.display_depth(heap, depth - 1))
+            }
             Value::Struct { name, fields } => {
                 display_struct(name, fields, |v| v.display_depth(heap, depth - 1))
             }
+            // `(1, a)` — elements one level down (ADR 0043).
+            Value::Tuple(items) => display_tuple(items, |v| v.display_depth(heap, depth - 1)),
+            // `[1, 2]`, like a growable array (ADR 0056).
+            Value::Fixed(items) => display_items(items, |v| v.display_depth(heap, depth - 1)),
             // `Circle(1.5)` / `Ready` — payloads at one level deeper,
             // like struct fields (ADR 0036).
             Value::Enum {
@@ -85,9 +98,20 @@ impl Value {
             Value::Array(id) => {
                 render_items(&heap.arrays[*id], |v| v.render_depth(heap, depth - 1))
             }
+            Value::Map(id) => {
+                let shown = display_entries(&heap.maps[*id], |v| {
+                    v.render_depth(heap, depth - 1).into_bytes()
+                });
+                String::from_utf8_lossy(&shown).into_owned()
+            }
             Value::Struct { name, fields } => {
                 render_struct(name, fields, |v| v.render_depth(heap, depth - 1))
             }
+            Value::Fixed(items) => render_items(items, |v| v.render_depth(heap, depth - 1)),
+            Value::Tuple(items) => {
+                let shown = display_tuple(items, |v| v.render_depth(heap, depth - 1).into_bytes());
+                String::from_utf8_lossy(&shown).into_owned()
+            }
             Value::Enum {
                 variant, payloads, ..
             } => {
@@ -98,8 +122,15 @@ impl Value {
             }
             Value::Str(s) => format!("Str({:?})", String::from_utf8_lossy(s)),
             // The code stays unobservable in the result line too.
-            Value::Err(code) => {
-                format!("Err(error.{})", heap.error_names[(*code - 2) as usize])
+            Value::Err(code, ErrPayload(payloads)) => {
+                let name = &heap.error_names[(*code - 2) as usize];
+                if payloads.is_empty() {
+                    return format!("Err(error.{name})");
+                }
+                let shown = display_variant(name, payloads, |v| {
+                    v.render_depth(heap, depth - 1).into_bytes()
+                });
+                format!("Err({})", String::from_utf8_lossy(&shown))
             }
             other => format!("{other:?}"),
         }
@@ -107,15 +138,20 @@ impl Value {
     pub(super) fn type_name(&self) -> &'static str {
         match self {
             Value::Int(_) => "int",
+            Value::Sized(t, _) => t.name(),
             Value::Float(_) => "float",
             Value::Bool(_) => "bool",
             Value::Str(_) => "string",
             Value::Struct { .. } => "struct",
             Value::Enum { .. } => "enum",
+            Value::Tuple(_) => "tuple",
+            Value::Fixed(_) => "fixed array",
             Value::Ref(_) => "refstruct",
             Value::Array(_) => "array",
+            Value::Map(_) => "map",
             Value::File(_) => "file",
-            Value::Err(_) => "error",
+            Value::Func(..) | Value::Closure(_) => "function",
+            Value::Err(..) => "error",
             Value::Null => "null",
             Value::Unit => "unit",
         }
@@ -140,6 +176,37 @@ fn display_struct(
     out
 }
 
+/// `error.Name` for a bare code; a code with payloads (ADR 0062)
+/// renders like a variant, `Name(12, "eof")`. A payload declared
+/// `string`, or `string?` holding a string, is quoted — decided by the
+/// declared type, as the native printer must.
+fn display_error(
+    heap: &Heap,
+    code: u32,
+    payloads: &[Value],
+    mut one: impl FnMut(&Value) -> Vec<u8>,
+) -> Vec<u8> {
+    let name = &heap.error_names[(code - 2) as usize];
+    if payloads.is_empty() {
+        return format!("error.{name}").into_bytes();
+    }
+    let types = &heap.error_payloads[(code - 2) as usize];
+    let mut index = 0;
+    display_variant(name, payloads, |v| {
+        let quoted = match &types[index] {
+            Type::Str => true,
+            Type::Optional(inner) => **inner == Type::Str && !matches!(v, Value::Null),
+            _ => false,
+        };
+        index += 1;
+        if quoted {
+            [&b"\""[..], &one(v), b"\""].concat()
+        } else {
+            one(v)
+        }
+    })
+}
+
 /// `Variant(p1, p2)`, or the bare name for nullary variants.
 fn display_variant(
     variant: &str,
@@ -161,6 +228,19 @@ fn display_variant(
     out
 }
 
+/// `(a, b)` — a tuple's elements in order.
+fn display_tuple(items: &[Value], mut one: impl FnMut(&Value) -> Vec<u8>) -> Vec<u8> {
+    let mut out = b"(".to_vec();
+    for (i, v) in items.iter().enumerate() {
+        if i > 0 {
+            out.extend_from_slice(b", ");
+        }
+        out.extend_from_slice(&one(v));
+    }
+    out.push(b')');
+    out
+}
+
 fn display_items(items: &[Value], mut one: impl FnMut(&Value) -> Vec<u8>) -> Vec<u8> {
     let mut out = b"[".to_vec();
     for (i, v) in items.iter().enumerate() {
@@ -173,6 +253,20 @@ fn display_items(items: &[Value], mut one: impl FnMut(&Value) -> Vec<u8>) -> Vec
     out
 }
 
+fn display_entries(entries: &[(Value, Value)], mut one: impl FnMut(&Value) -> Vec<u8>) -> Vec<u8> {
+    let mut out = b"{".to_vec();
+    for (i, (k, v)) in entries.iter().enumerate() {
+        if i > 0 {
+            out.extend_from_slice(b", ");
+        }
+        out.extend_from_slice(&one(k));
+        out.extend_from_slice(b": ");
+        out.extend_from_slice(&one(v));
+    }
+    out.extend_from_slice(b"}");
+    out
+}
+
 fn render_struct(
     name: &str,
     fields: &[(String, Value)],
@@ -21,6 +21,7 @@ fn run_full(src: &str) -> Result<(Value, Heap), Diagnostic> {
             ast,
             imports: Vec::new(),
         }],
+        init_order: vec![0],
     };
     let (res, cd) = check(&graph, &mut map);
     assert!(cd.is_empty(), "check: {cd:?}");
@@ -82,6 +83,50 @@ fn same_named_functions_resolve_within_their_own_module() {
     );
 }
 
+#[test]
+fn module_consts_read_in_their_own_module() {
+    // `lib`'s SIZE is computed with lib's BASE, not main's.
+    assert_eq!(
+        run_multi(&[
+            (
+                "main.ys",
+                "import { SIZE } from \"./lib\";\n\
+                     const BASE: int = 100;\n\
+                     fun main(): int { return BASE + SIZE; }"
+            ),
+            (
+                "lib.ys",
+                "const BASE: int = 2;\n\
+                     export const SIZE: int = BASE * BASE;"
+            ),
+        ]),
+        Ok(Value::Int(104))
+    );
+}
+
+#[test]
+fn module_vars_initialize_dependencies_first() {
+    // `lib`'s vars are set before `main`'s initializers run, and every
+    // function shares the one storage.
+    assert_eq!(
+        run_multi(&[
+            (
+                "main.ys",
+                "import { total, add } from \"./lib\";\n\
+                     var first: int = add(2);\n\
+                     var second: int = add(3);\n\
+                     fun main(): int { add(100); return total * 100 + first * 10 + second; }"
+            ),
+            (
+                "lib.ys",
+                "export var total: int = 1;\n\
+                     export fun add(n: int): int { total = total + n; return total; }"
+            ),
+        ]),
+        Ok(Value::Int(10636))
+    );
+}
+
 #[test]
 fn arithmetic_respects_precedence() {
     assert_eq!(
@@ -782,8 +827,10 @@ fn scalar_rendering_matches_debug() {
 #[test]
 fn display_shows_user_values_not_enum_internals() {
     let mut heap = Heap::default();
-    heap.arrays
-        .push(vec![Value::Int(1), Value::Str(b"x".to_vec()), Value::Null]);
+    heap.arrays.alloc(
+        vec![Value::Int(1), Value::Str(b"x".to_vec()), Value::Null],
+        0,
+    );
     let array = Value::Array(0);
     assert_eq!(array.display(&heap), b"[1, x, null]");
     let s = Value::Struct {
@@ -791,10 +838,13 @@ fn display_shows_user_values_not_enum_internals() {
         fields: vec![("x".into(), Value::Int(1))],
     };
     assert_eq!(s.display(&heap), b"P { x: 1 }");
-    heap.structs.push(StructObj {
-        name: "N".into(),
-        fields: vec![("v".into(), Value::Bool(true))],
-    });
+    heap.structs.alloc(
+        StructObj {
+            name: "N".into(),
+            fields: vec![("v".into(), Value::Bool(true))],
+        },
+        0,
+    );
     let r = Value::Ref(0);
     assert_eq!(r.display(&heap), b"N { v: true }");
 }
@@ -985,7 +1035,7 @@ fun main(): int! {
     return 0;
 }";
     let (value, heap) = run_full(src).unwrap();
-    assert!(matches!(value, Value::Err(_)));
+    assert!(matches!(value, Value::Err(..)));
     assert_eq!(value.display(&heap), b"error.Nope".to_vec());
 }
 
@@ -999,3 +1049,582 @@ fun fib(n: int): int {
 fun main(): int { return fib(10); }";
     assert_eq!(run(fib), Ok(Value::Int(55)));
 }
+
+#[test]
+fn function_values_call_through_tables_and_compare_by_identity() {
+    // ADR 0038: a function value is its callee's identity — however
+    // it was named (plain, instance, table slot), calls and `==`
+    // agree.
+    let src = "\
+fun double(x: int): int { return x * 2; }
+fun identity<T>(x: T): T { return x; }
+fun pick(n: int): fun(int): int {
+    if n == 0 { return double; }
+    return identity<int>;
+}
+fun main(): int {
+    const ops: (fun(int): int)[] = [double, identity<int>];
+    if ops[0] != pick(0) { return -1; }
+    if ops[1] != (identity<int>) { return -2; }
+    if ops[0] == ops[1] { return -3; }
+    return ops[0](20) + pick(1)(2);
+}";
+    assert_eq!(run(src), Ok(Value::Int(42)));
+}
+
+#[test]
+fn closures_copy_values_and_share_handles_at_creation() {
+    // ADR 0039: an int capture is a snapshot — the later write to
+    // `base` is invisible — while a refstruct capture aliases.
+    let src = "\
+refstruct Counter { n: int }
+fun adder(k: int): fun(int): int {
+    return fun (x: int): int { return x + k; };
+}
+fun main(): int {
+    var base: int = 10;
+    const add: fun(int): int = adder(base);
+    base = 1000;
+    const c: Counter = Counter { n: 0 };
+    const bump: fun(): int = fun (): int { c.n = c.n + 1; return c.n; };
+    bump();
+    bump();
+    if add == adder(10) { return -1; }
+    return add(c.n) + bump();
+}";
+    assert_eq!(run(src), Ok(Value::Int(15)));
+}
+
+#[test]
+fn methods_copy_value_receivers_and_alias_refstructs() {
+    // ADR 0040: a value-struct receiver is copied into `self`, so a
+    // method's local update never reaches the caller; a refstruct
+    // receiver is the caller's object.
+    let src = "\
+struct P { x: int }
+refstruct C { n: int }
+impl P {
+    fun moved(self, dx: int): P { var q: P = self; q.x = q.x + dx; return q; }
+}
+impl C {
+    fun bump(self): int { self.n = self.n + 1; return self.n; }
+}
+fun main(): int {
+    const p: P = P { x: 1 };
+    const q: P = p.moved(10).moved(100);
+    const c: C = C { n: 0 };
+    c.bump();
+    return p.x + q.x + c.bump() * 1000;
+}";
+    assert_eq!(run(src), Ok(Value::Int(2112)));
+}
+
+#[test]
+fn bounded_generics_dispatch_statically_per_instance() {
+    // ADR 0041: each instance calls its argument type's own method.
+    let src = "\
+interface Scored { fun score(self): int; }
+struct A { n: int }
+enum B { One, Two }
+impl A { fun score(self): int { return self.n; } }
+impl B {
+    fun score(self): int {
+        match self { One { return 100; } else { return 200; } }
+        return 0;
+    }
+}
+fun total<T: Scored>(xs: T[]): int {
+    var sum: int = 0;
+    for x in xs { sum = sum + x.score(); }
+    return sum;
+}
+fun main(): int {
+    return total([A { n: 1 }, A { n: 2 }]) + total([B.One(), B.Two()]);
+}";
+    assert_eq!(run(src), Ok(Value::Int(303)));
+}
+
+#[test]
+fn maps_keep_insertion_order_and_update_in_place() {
+    // ADR 0042: a re-insert keeps the key's position; removal closes
+    // the gap; lookups of missing keys read null.
+    let src = "\
+fun main(): int {
+    const m: map<string, int> = { \"b\": 1, \"a\": 2 };
+    insert(m, \"c\", 3);
+    insert(m, \"b\", 10);
+    remove(m, \"a\");
+    var out: int = 0;
+    for [k, v] in m { out = out * 100 + v; }
+    return out + (m[\"a\"] ?? 7000000);
+}";
+    assert_eq!(run(src), Ok(Value::Int(7001003)));
+}
+
+#[test]
+fn tuples_copy_like_structs_and_destructure() {
+    // ADR 0043: a tuple is a value — the copy in `u` doesn't see the
+    // write through `t`; `var (…)` bindings are independent copies.
+    let src = "\
+fun swap(p: (int, int)): (int, int) { return (p.1, p.0); }
+fun main(): int {
+    var t: (int, int) = (1, 2);
+    const u: (int, int) = t;
+    t.0 = 10;
+    var (a, b) = swap(t);
+    a = a * 100;
+    return a + b + u.0 * 1000 + t.0 * 10000;
+}";
+    assert_eq!(run(src), Ok(Value::Int(101210)));
+}
+
+#[test]
+fn match_expressions_yield_the_chosen_arm() {
+    // ADR 0044: only the live arm is evaluated; payload bindings are
+    // scoped to their arm.
+    let src = "\
+enum Op { Add(int, int), Neg(int), Zero }
+fun eval(o: Op): int {
+    return match o { Add(a, b) => a + b, Neg(a) => -a, Zero => 0 };
+}
+fun main(): int {
+    const ops: Op[] = [Op.Add(3, 4), Op.Neg(20), Op.Zero()];
+    var out: int = 0;
+    for o in ops { out = out * 100 + eval(o) + match o { Zero => 50, else => 0 }; }
+    return out;
+}";
+    assert_eq!(run(src), Ok(Value::Int(68050)));
+}
+
+#[test]
+fn patterns_pick_the_first_matching_arm_whose_guard_holds() {
+    // ADR 0045: arms try in order; a failed guard moves on to the
+    // next arm, and nested payloads bind like top-level ones.
+    let src = "\
+enum Shape { Circle(int), Dot }
+enum Opt { Some(Shape), None }
+fun score(o: Opt): int {
+    return match o {
+        Some(Circle(r)) if r > 5 => 1000,
+        Some(Circle(7)) => 999,
+        Some(Circle(r)) => r * 10,
+        Some(Dot()) => 3,
+        None => 0,
+    };
+}
+fun main(): int {
+    return score(Opt.Some(Shape.Circle(9))) + score(Opt.Some(Shape.Circle(2)))
+        + score(Opt.Some(Shape.Dot())) + score(Opt.None());
+}";
+    assert_eq!(run(src), Ok(Value::Int(1023)));
+}
+
+#[test]
+fn literal_scrutinees_pick_the_matching_arm() {
+    let src = "\
+error NotFound;
+error Timeout;
+fun digit(n: int): int {
+    match n {
+        0 { return 1; }
+        -3 { return 2; }
+        else { return 4; }
+    }
+}
+fun word(s: string): int {
+    return match s { \"yes\" => 8, \"no\" => 16, else => 32 };
+}
+fun code(e: error): int {
+    return match e { error.Timeout => 64, else => 128 };
+}
+fun main(): int {
+    return digit(0) + digit(-3) + digit(5) + word(\"yes\") + word(\"no\") + word(\"y\")
+        + code(error.Timeout) + code(error.NotFound);
+}";
+    assert_eq!(run(src), Ok(Value::Int(255)));
+}
+
+#[test]
+fn bitwise_operators_and_shift_counts() {
+    let src = "\
+fun main(): int {
+    var m: int = -16;
+    var n: int = 2;
+    if (m >> n) != -4 { return 1; }
+    if (m >>> 60) != 15 { return 2; }
+    if (1 << 63) != -9223372036854775807 - 1 { return 3; }
+    if (~m ^ 5) != 10 { return 4; }
+    return (12 & 10) | (1 << n) << 4;
+}";
+    assert_eq!(run(src), Ok(Value::Int(72)));
+    // The count must name a bit: 0..=63, never masked.
+    for count in ["64", "(0 - 1)"] {
+        let err = run(&format!(
+            "fun main(): int {{ var c: int = {count}; return 1 << c; }}"
+        ))
+        .unwrap_err();
+        assert!(err.message.contains("out of range"), "{err:?}");
+    }
+}
+
+#[test]
+fn compound_assignment_runs_the_place_once() {
+    let src = "\
+struct P { n: int, tag: string? }
+var calls: int = 0;
+fun at(i: int): int {
+    calls++;
+    return i;
+}
+fun main(): int {
+    var xs: int[] = [10, 20];
+    xs[at(1)] += 5;
+    var ps: P[] = [P { n: 1, tag: null }];
+    ps[at(0)].n <<= 3;
+    ps[at(0)].tag ??= \"a\";
+    ps[at(0)].tag ??= \"never\";
+    var s: string = ps[0].tag ?? \"\";
+    s += \"b\";
+    if s != \"ab\" { return -1; }
+    // The plain `=` resolves its place once too.
+    ps[at(0)].n = ps[0].n - 1;
+    return xs[1] * 100 + ps[0].n * 10 + calls;
+}";
+    assert_eq!(run(src), Ok(Value::Int(2575)));
+    let err =
+        run("fun main(): int { var x: int = 1; var z: int = 0; x %= z; return x; }").unwrap_err();
+    assert!(err.message.contains("division by zero"), "{err:?}");
+}
+
+#[test]
+fn range_loops_stop_at_the_int_edge() {
+    let src = "\
+fun main(): int {
+    var n: int = 0;
+    for i in 9223372036854775806..=9223372036854775807 step 2 {
+        n = n * 10 + 1;
+    }
+    var bound: int = 3;
+    for i in 0..bound {
+        bound = 100;
+        n = n * 10 + i;
+    }
+    for i in 4..=0 step -2 {
+        n = n * 10 + i;
+    }
+    const xs: int[] = [1, 2, 3, 4];
+    var ys: int[] = xs[1..=2];
+    push(ys, 9);
+    return n * 10 + len(xs) + ys[2];
+}";
+    assert_eq!(run(src), Ok(Value::Int(10124213)));
+    let err =
+        run("fun main(): int { var s: int = 0; for i in 0..1 step s { } return 0; }").unwrap_err();
+    assert_eq!(err.message, "range step must not be zero");
+    let err = run("fun main(): int { const xs: int[] = [1]; return len(xs[..2]); }").unwrap_err();
+    assert_eq!(err.message, "slice 0..2 out of bounds (length 1)");
+}
+
+#[test]
+fn sized_integers_wrap_at_their_width() {
+    let src = "\
+fun main(): int {
+    const a: u8 = 200;
+    const b: i8 = -128;
+    const m: u64 = u64(-1);
+    if int(a + a) != 144 { return 1; }
+    if int(-b) != -128 || int(b / -1) != -128 { return 2; }
+    if !(m > u64(1)) || int(m >> 60) != 15 { return 3; }
+    if int(m / 3) != 6148914691236517205 { return 4; }
+    if int(i8(255)) != -1 || int(u16(-1)) != 65535 { return 5; }
+    var xs: u8[] = [250, 5];
+    xs[0] += 10;
+    return int(xs[0]) + int(xs[1]);
+}";
+    assert_eq!(run(src), Ok(Value::Int(9)));
+    let err =
+        run("fun main(): int { const z: u64 = u64(0); return int(u64(1) / z); }").unwrap_err();
+    assert!(err.message.contains("division by zero"), "{err:?}");
+}
+
+#[test]
+fn strings_index_slice_and_decode() {
+    let src = "\
+fun main(): int {
+    const s: string = \"h\u{e9}llo\";
+    if len(s) != 6 || int(s[1]) != 195 { return 1; }
+    if s[3..] != \"llo\" || s[..=0] != \"h\" { return 2; }
+    var sum: int = 0;
+    for [i, b] in s[1..3] { sum += i * 1000 + int(b); }
+    if sum != 1000 + 195 + 169 { return 3; }
+    const points: u32[] = chars(s[2..] + s[1..2]);
+    return len(points) * 100000 + int(points[len(points) - 1]);
+}";
+    assert_eq!(run(src), Ok(Value::Int(565533)));
+    let err =
+        run("fun main(): int { const s: string = \"ab\"; return len(s[1..3]); }").unwrap_err();
+    assert!(
+        err.message.contains("slice 1..3 out of bounds (length 2)"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn string_library_searches_splits_and_parses() {
+    let src = "\
+fun main(): int {
+    const line: string = \"  k1=10, k2=-3 ,k3=x  \";
+    var total: int = 0;
+    for field in split(trim(line), \",\") {
+        const kv: string[] = split(trim(field), \"=\");
+        total += parseInt(kv[1]) ?? 100;
+    }
+    if total != 107 { return 1; }
+    if find(line, \"k2\") != 9 || find(line, \"k4\") != null || find(line, \"\") != 0 { return 2; }
+    if replace(\"a.b.c\", \".\", \"::\") != \"a::b::c\" || join([\"x\"], \"-\") != \"x\" { return 3; }
+    if toUpper(\"h\u{e9}!\") != \"H\u{e9}!\" || toLower(\"ABC\") != \"abc\" { return 4; }
+    if !startsWith(line, \"  k\") || endsWith(line, \"x\") { return 5; }
+    if parseFloat(\"2.5e1\") != 25.0 || parseFloat(\"inf\") != null { return 6; }
+    return len(split(\",,\", \",\"));
+}";
+    assert_eq!(run(src), Ok(Value::Int(3)));
+    let err = run("fun main(): int { return len(split(\"ab\", \"\")); }").unwrap_err();
+    assert!(
+        err.message.contains("'split' separator must not be empty"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn array_library_edits_sorts_and_searches() {
+    let src = "\
+struct P { name: string, age: int }
+fun main(): int {
+    var xs: int[] = [4, 1, 3];
+    insert(xs, 1, 9);
+    insert(xs, len(xs), 0);
+    if removeAt(xs, 0) != 4 || pop(xs) != 0 { return 1; }
+    sort(xs);
+    if len(xs) != 3 || xs[0] != 1 || xs[2] != 9 { return 2; }
+    reverse(xs);
+    if indexOf(xs, 3) != 1 || indexOf(xs, 5) != null || !contains(xs, 9) { return 3; }
+    var ps: P[] = [P { name: \"b\", age: 2 }, P { name: \"a\", age: 1 }, P { name: \"c\", age: 2 }];
+    sort(ps, fun (p: P): int { return p.age; });
+    if ps[0].name != \"a\" || ps[1].name != \"b\" || ps[2].name != \"c\" { return 4; }
+    const tail: P[] = slice(ps, 1, 3);
+    clear(ps);
+    if pop(ps) != null { return 5; }
+    return len(tail) * 10 + (indexOf(tail, P { name: \"c\", age: 2 }) ?? 0);
+}";
+    assert_eq!(run(src), Ok(Value::Int(21)));
+    let err =
+        run("fun main(): int { var xs: int[] = [1]; insert(xs, 2, 0); return 0; }").unwrap_err();
+    assert!(
+        err.message.contains("index 2 out of bounds (length 1)"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn fixed_arrays_copy_on_assignment() {
+    let src = "\
+struct G { cells: int[3] }
+fun fill(g: G, v: int): G {
+    var out: G = g;
+    out.cells[1] = v;
+    return out;
+}
+fun main(): int {
+    var a: int[3] = [1, 2, 3];
+    const b: int[3] = a;
+    a[0] = 10;
+    if b[0] != 1 || a == b { return 1; }
+    const g: G = G { cells: [0; 3] };
+    const h: G = fill(g, 7);
+    if g.cells[1] != 0 || h.cells != [0, 7, 0] { return 2; }
+    var sum: int = 0;
+    for x in a {
+        a[2] = 100;
+        sum += x;
+    }
+    return sum * 10 + len(a);
+}";
+    assert_eq!(run(src), Ok(Value::Int(153)));
+    let err =
+        run("fun main(): int { var a: int[2] = [0; 2]; var i: int = 2; a[i] = 1; return 0; }")
+            .unwrap_err();
+    assert!(
+        err.message.contains("index 2 out of bounds (length 2)"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn destructuring_binds_copies_and_let_else_leaves() {
+    let src = "\
+struct P { x: int, y: int }
+struct Box { p: P, tag: string }
+enum Shape { Circle(int), Rect(int, int) }
+fun area(s: Shape): int {
+    const Rect(w, h) = s else { return -1; };
+    return w * h;
+}
+fun main(): int {
+    const b: Box = Box { p: P { x: 1, y: 2 }, tag: \"t\" };
+    var { p: q, tag } = b;
+    q.x = 10;
+    if b.p.x != 1 || tag != \"t\" { return 1; }
+    const { x, y: _ } = q;
+    return x * 100 + area(Shape.Rect(2, 3)) * 10 - area(Shape.Circle(1));
+}";
+    assert_eq!(run(src), Ok(Value::Int(1061)));
+}
+
+#[test]
+fn if_const_and_while_const_unwrap() {
+    let src = "\
+error Missing;
+refstruct Node { val: int, next: Node? }
+fun find(xs: int[], v: int): int! {
+    for [i, x] in xs { if x == v { return i; } }
+    return error.Missing;
+}
+fun main(): int {
+    const xs: int?[] = [4, null, 6];
+    var sum: int = 0;
+    for i in 0..3 {
+        if const v = xs[i] { sum += v; } else { sum += 100; }
+    }
+    if const at = find([7, 8], 8) { sum += at * 1000; }
+    if const at = find([7, 8], 9) { return -1; }
+    var head: Node? = Node { val: 1, next: Node { val: 2, next: null } };
+    while const n = head {
+        sum += n.val * 10;
+        head = n.next;
+    }
+    return sum;
+}";
+    assert_eq!(run(src), Ok(Value::Int(1140)));
+}
+
+#[test]
+fn labeled_break_and_continue_leave_the_named_loop() {
+    let src = "\
+fun main(): int {
+    var hits: int = 0;
+    outer: for i in 0..4 {
+        var j: int = 0;
+        inner: while true {
+            j += 1;
+            if j > i { continue outer; }
+            if i == 3 { break outer; }
+            hits += 1;
+            if j == 1 { continue inner; }
+        }
+    }
+    return hits;
+}";
+    // i = 1 and 2 add 1 and 2 hits; i = 3 breaks out on its first pass.
+    assert_eq!(run(src), Ok(Value::Int(3)));
+}
+
+#[test]
+fn defers_run_last_first_on_every_exit() {
+    let src = "\
+error Bad;
+fun check(n: int): int! {
+    if n < 0 { return error.Bad; }
+    return n;
+}
+fun tally(log: int[], n: int): int! {
+    defer push(log, 1);
+    defer push(log, 2);
+    const v: int = try check(n);
+    for i in 0..3 {
+        defer push(log, 10 + i);
+        if i == 1 { continue; }
+        if i == 2 { break; }
+    }
+    return v;
+}
+fun main(): int {
+    var log: int[] = [];
+    var a: int = 0;
+    if const v = tally(log, 5) { a = v; }
+    if const v = tally(log, -1) { return -1; }
+    var digest: int = 0;
+    for x in log { digest = digest * 100 + x; }
+    return digest + a;
+}";
+    // 10 11 12 2 1, then 2 1 from the failed try.
+    assert_eq!(run(src), Ok(Value::Int(10111202010201 + 5)));
+}
+
+#[test]
+fn arenas_free_their_cells_and_catch_escapes() {
+    let src = "\
+refstruct Box { items: int[] }
+fun fill(b: Box) { b.items = [1, 2, 3]; }
+fun main(): int {
+    const b: Box = Box { items: [] };
+    var n: int = 0;
+    for i in 0..100 {
+        arena {
+            const xs: int[] = [i, i];
+            n = n + len(xs);
+        }
+    }
+    arena { fill(b); n = n + len(b.items); }
+    return n + len(b.items);
+}";
+    // The call smuggles an arena array out. The checker rejects it
+    // (ADR 0061); run anyway, and the heap still catches the next read.
+    let (tokens, _) = lex(src);
+    let (ast, _) = parse(&tokens);
+    let graph = ModuleGraph {
+        modules: vec![Module {
+            path: "test.ys".to_string(),
+            ast,
+            imports: Vec::new(),
+        }],
+        init_order: vec![0],
+    };
+    let mut map = SourceMap::new();
+    map.add("test.ys", src);
+    let (res, cd) = check(&graph, &mut map);
+    assert_eq!(
+        cd[0].message,
+        "'fill' may store a value allocated in this arena where it outlives the arena"
+    );
+    let err = interpret(&graph, &res, &[]).unwrap_err();
+    assert_eq!(err.message, "this array was freed when its arena ended");
+    let (value, heap) = run_full(
+        "fun main(): int { var n: int = 0; \
+         for i in 0..100 { arena { const xs: int[] = [i]; n = n + xs[0]; } } return n; }",
+    )
+    .unwrap();
+    assert_eq!(value, Value::Int(4950));
+    // Only the freed cells' tombstones remain.
+    assert_eq!(heap.cell_count(), 0);
+}
+
+#[test]
+fn error_payloads_ride_along_but_compare_by_code() {
+    // ADR 0062: payloads render and bind; equality is the code's.
+    let src = "\
+error ParseFailed(int, string);
+fun main(): int! {
+    const a: error = error.ParseFailed(1, \"x\");
+    const b: error = error.ParseFailed(2, \"y\");
+    if a != b { return 0; }
+    const line: int = match b { error.ParseFailed(n, _) => n, else => 0 };
+    if line == 2 { return error.ParseFailed(12, \"eof\"); }
+    return 1;
+}";
+    let (value, heap) = run_full(src).unwrap();
+    assert_eq!(value.display(&heap), br#"ParseFailed(12, "eof")"#.to_vec());
+    assert_eq!(
+        value.render(&heap),
+        "Err(ParseFailed(Int(12), Str(\"eof\")))"
+    );
+}
@@ -0,0 +1,85 @@
+//! The string library (ADR 0054) over raw bytes: nothing here assumes
+//! UTF-8, so non-UTF-8 input searches, splits, and cases exactly as the
+//! compiled runtime does. The empty-separator and empty-pattern traps
+//! are the caller's; these helpers assume a non-empty needle where it
+//! matters.
+
+/// The first occurrence of `needle` in `hay`; an empty needle matches
+/// at 0.
+pub(super) fn find(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    hay.windows(needle.len()).position(|w| w == needle)
+}
+
+/// The pieces between non-overlapping occurrences of `sep`, left to
+/// right: `n` occurrences make `n + 1` pieces, empty ones included.
+pub(super) fn split<'b>(s: &'b [u8], sep: &[u8]) -> Vec<&'b [u8]> {
+    let mut pieces = Vec::new();
+    let mut rest = s;
+    while let Some(at) = find(rest, sep) {
+        pieces.push(&rest[..at]);
+        rest = &rest[at + sep.len()..];
+    }
+    pieces.push(rest);
+    pieces
+}
+
+/// `split`'s pieces rejoined around `to`.
+pub(super) fn replace(s: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    split(s, from).join(to)
+}
+
+/// C's `isspace` in the C locale: space, `\t`, `\n`, `\v`, `\f`, `\r`.
+fn is_space(b: &u8) -> bool {
+    *b == b' ' || (9..=13).contains(b)
+}
+
+pub(super) fn trim(s: &[u8]) -> &[u8] {
+    let start = s.iter().position(|b| !is_space(b)).unwrap_or(s.len());
+    let end = s
+        .iter()
+        .rposition(|b| !is_space(b))
+        .map_or(start, |i| i + 1);
+    &s[start..end]
+}
+
+/// An optional sign, then one or more decimal digits, nothing else;
+/// out of range is a miss like any other.
+pub(super) fn parse_int(s: &[u8]) -> Option<i64> {
+    std::str::from_utf8(s).ok()?.parse().ok()
+}
+
+/// The decimal grammar both engines validate before converting:
+/// `[+-]? (d+ (. d*)? | . d+) ([eE] [+-]? d+)?`. No whitespace, no
+/// `inf`/`nan`, no hex — the spellings where the engines' parsers
+/// disagree are rejected before either one runs.
+pub(super) fn parse_float(s: &[u8]) -> Option<f64> {
+    let digits = |from: usize| s[from..].iter().take_while(|b| b.is_ascii_digit()).count();
+    let sign = |at: usize| usize::from(matches!(s.get(at), Some(b'+' | b'-')));
+    let mut i = sign(0);
+    let whole = digits(i);
+    i += whole;
+    let mut frac = 0;
+    if s.get(i) == Some(&b'.') {
+        frac = digits(i + 1);
+        i += 1 + frac;
+    }
+    if whole + frac == 0 {
+        return None;
+    }
+    if matches!(s.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        i += sign(i);
+        let exp = digits(i);
+        if exp == 0 {
+            return None;
+        }
+        i += exp;
+    }
+    if i != s.len() {
+        return None;
+    }
+    std::str::from_utf8(s).ok()?.parse().ok()
+}
@@ -5,11 +5,12 @@
 //! this backend never pushes operands, so %rsp stays aligned at every
 //! call site with no fix-ups.
 
+use super::layout::Elem;
 use super::regalloc::{ARG_REGS, CALLEE_SAVED, Loc, allocate, intervals};
 use super::{FunctionIr, Inst, V, cc};
 use crate::ast::BinOp;
-use crate::codegen::{RT_FMOD, TRAP_DIV0, TRAP_F2I, TRAP_OOB, TRAP_OVERFLOW, label_of};
-use crate::syntax;
+use crate::codegen::{RT_FMOD, TRAP_DIV0, TRAP_F2I, TRAP_OOB, TRAP_OVERFLOW, TRAP_SHIFT, label_of};
+use crate::types::IntTy;
 use std::collections::HashMap;
 use std::fmt::Write;
 
@@ -48,18 +49,87 @@ fn magic_i64(d: i64) -> (i64, u32) {
 }
 
 /// The bounds-check preamble shared by element reads and writes:
-/// an index below the length falls through to `target`; out-of-range
-/// reports and exits through the OOB trap (ADR 0022).
-fn bounds_check(arr: &str, idx: &str, loc: &str, target: &str) -> String {
+/// an index below the length at `len_off(arr)` — an array header's
+/// first word, a string's second (ADR 0053) — falls through to
+/// `target`; out-of-range reports and exits through the OOB trap
+/// (ADR 0022).
+fn bounds_check(arr: &str, len_off: i64, idx: &str, loc: &str, target: &str) -> String {
     format!(
-        "\tmovq {arr}, %rax\n\tmovq {idx}, %rcx\n\tcmpq 0(%rax), %rcx\n\
+        "\tmovq {arr}, %rax\n\tmovq {idx}, %rcx\n\tcmpq {len_off}(%rax), %rcx\n\
          \tjb {target}\n\
-         \tmovq %rcx, %rdi\n\tmovq 0(%rax), %rsi\n\tleaq {loc}(%rip), %rdx\n\
+         \tmovq %rcx, %rdi\n\tmovq {len_off}(%rax), %rsi\n\tleaq {loc}(%rip), %rdx\n\
          \tcall {TRAP_OOB}\n\
          {target}:\n"
     )
 }
 
+/// The two-operand word instruction for an arithmetic, bitwise, or
+/// shift operator (ADR 0049 for the last two groups).
+fn word_op(op: BinOp) -> &'static str {
+    match op {
+        BinOp::Add => "addq",
+        BinOp::Sub => "subq",
+        BinOp::Mul => "imulq",
+        BinOp::BitAnd => "andq",
+        BinOp::BitOr => "orq",
+        BinOp::BitXor => "xorq",
+        BinOp::Shl => "salq",
+        BinOp::Shr => "sarq",
+        BinOp::UShr => "shrq",
+        _ => unreachable!("'{}' is not a word operation", op.symbol()),
+    }
+}
+
+/// The condition code of an unsigned comparison (u64, ADR 0052).
+fn cc_unsigned(op: BinOp) -> &'static str {
+    match op {
+        BinOp::Eq => "e",
+        BinOp::Ne => "ne",
+        BinOp::Lt => "b",
+        BinOp::Le => "be",
+        BinOp::Gt => "a",
+        _ => "ae",
+    }
+}
+
+/// The extending move that takes a sized integer's low bytes at `src`
+/// — memory, or %rax's own sub-register — to its canonical word in
+/// %rax (ADR 0052): sign-extended when signed, zero-extended otherwise.
+fn extend(t: IntTy, src: &str) -> String {
+    let op = match (t.bits(), t.signed()) {
+        (8, true) => "movsbq",
+        (8, false) => "movzbq",
+        (16, true) => "movswq",
+        (16, false) => "movzwq",
+        (32, true) => "movslq",
+        // A 32-bit move zeroes the upper half by itself.
+        (32, false) => return format!("\tmovl {src}, %eax\n"),
+        _ => return format!("\tmovq {src}, %rax\n"),
+    };
+    format!("\t{op} {src}, %rax\n")
+}
+
+/// %rax's sub-register of a sized integer's width.
+fn rax_part(t: IntTy) -> &'static str {
+    match t.bits() {
+        8 => "%al",
+        16 => "%ax",
+        32 => "%eax",
+        _ => "%rax",
+    }
+}
+
+/// Stores %rdx's low bytes — a packed element's width — to `dst`.
+fn store_packed(t: IntTy, dst: &str) -> String {
+    let (op, reg) = match t.bits() {
+        8 => ("movb", "%dl"),
+        16 => ("movw", "%dx"),
+        32 => ("movl", "%edx"),
+        _ => ("movq", "%rdx"),
+    };
+    format!("\t{op} {reg}, {dst}\n")
+}
+
 fn operand(loc: Loc) -> String {
     match loc {
         Loc::Reg(r) => r.to_string(),
@@ -72,6 +142,7 @@ pub(super) fn emit(ir: FunctionIr) -> String {
         name,
         module,
         nparams,
+        env,
         vregs,
         floats,
         insts,
@@ -99,7 +170,7 @@ pub(super) fn emit(ir: FunctionIr) -> String {
     let outgoing = insts
         .iter()
         .map(|inst| match inst {
-            Inst::Call { args, sret, .. } => {
+            Inst::Call { args, sret, .. } | Inst::CallInd { args, sret, .. } => {
                 (args.len() + sret.is_some() as usize).saturating_sub(6)
             }
             _ => 0,
@@ -110,9 +181,6 @@ pub(super) fn emit(ir: FunctionIr) -> String {
 
     let mut a = String::new();
     let label = label_of(module, &name);
-    if label == syntax::ENTRY_FN {
-        let _ = writeln!(a, "\t.globl {label}");
-    }
     let _ = writeln!(a, "{label}:\n\tpushq %rbp\n\tmovq %rsp, %rbp");
     // One frame covers saves, spills, temps, and outgoing args, 16-byte
     // aligned; this backend never pushes operands, so %rsp stays aligned
@@ -129,6 +197,11 @@ pub(super) fn emit(ir: FunctionIr) -> String {
             let _ = writeln!(a, "\tmovq {}(%rbp), {r}", -8 * (i as i64 + 1));
         }
     };
+    // A function literal's environment arrives in %rax (ADR 0039);
+    // save it before stack params use %rax as scratch.
+    if let Some(l) = env.and_then(|v| loc.get(&v)) {
+        let _ = writeln!(a, "\tmovq %rax, {}", operand(*l));
+    }
     // Params land in vregs 0..nparams (sret pointer included) in
     // argument-register order; slots 6+ read from above the frame
     // (ADR 0024). Param intervals start at instruction 0 — no defining
@@ -192,6 +265,11 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 let _ = writeln!(a, "\tmovq {}, %rax\n\tmovq {off}(%rax), %rax", at(*base));
                 let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
             }
+            Inst::LoadPacked { dst, base, off, ty } => {
+                let _ = writeln!(a, "\tmovq {}, %rax", at(*base));
+                a.push_str(&extend(*ty, &format!("{off}(%rax)")));
+                let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
+            }
             Inst::StoreAt { base, off, val } => {
                 let _ = writeln!(
                     a,
@@ -237,6 +315,41 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                     at(*d)
                 );
             }
+            // A set top bit is out of cvtsi2sd's range: convert half the
+            // value (the dropped bit folded in, so rounding still sees
+            // it) and double it back.
+            Inst::UintToFloat(d, s) => {
+                traps += 1;
+                let high = format!(".LTB{module}_{name}_{traps}");
+                traps += 1;
+                let done = format!(".LTB{module}_{name}_{traps}");
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rax\n\ttestq %rax, %rax\n\tjs {high}\n\
+                     \tcvtsi2sdq %rax, %xmm0\n\tjmp {done}\n\
+                     {high}:\n\tmovq %rax, %rcx\n\tshrq $1, %rcx\n\tandl $1, %eax\n\
+                     \torq %rax, %rcx\n\tcvtsi2sdq %rcx, %xmm0\n\taddsd %xmm0, %xmm0\n\
+                     {done}:\n\tmovq %xmm0, {}",
+                    at(*s),
+                    at(*d)
+                );
+            }
+            Inst::Narrow { dst, src, ty } => {
+                let _ = writeln!(a, "\tmovq {}, %rax", at(*src));
+                a.push_str(&extend(*ty, rax_part(*ty)));
+                let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
+            }
+            Inst::CmpU { op, dst, lhs, rhs } => {
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rax\n\tcmpq {}, %rax\n\tset{} %al\n\tmovzbq %al, %rax\n\
+                     \tmovq %rax, {}",
+                    at(*lhs),
+                    at(*rhs),
+                    cc_unsigned(*op),
+                    at(*dst)
+                );
+            }
             // cvttsd2si truncates toward zero and yields the sentinel
             // 0x8000000000000000 exactly for NaN, out-of-range, and
             // -2^63 — whose bit pattern (0xC3E0000000000000) is the one
@@ -265,8 +378,24 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                     at(*d)
                 );
             }
+            Inst::BitNot(d, s) => {
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rax\n\tnotq %rax\n\tmovq %rax, {}",
+                    at(*s),
+                    at(*d)
+                );
+            }
             Inst::BinImm { op, dst, lhs, imm } => match op {
-                BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                BinOp::Add
+                | BinOp::Sub
+                | BinOp::Mul
+                | BinOp::BitAnd
+                | BinOp::BitOr
+                | BinOp::BitXor
+                | BinOp::Shl
+                | BinOp::Shr
+                | BinOp::UShr => {
                     if let Loc::Reg(d) = loc[dst] {
                         if matches!(op, BinOp::Mul) {
                             let _ = writeln!(a, "\timulq ${imm}, {}, {d}", at(*lhs));
@@ -274,21 +403,16 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                             if at(*lhs) != at(*dst) {
                                 let _ = writeln!(a, "\tmovq {}, {d}", at(*lhs));
                             }
-                            let mnem = if matches!(op, BinOp::Add) {
-                                "addq"
-                            } else {
-                                "subq"
-                            };
-                            let _ = writeln!(a, "\t{mnem} ${imm}, {d}");
+                            let _ = writeln!(a, "\t{} ${imm}, {d}", word_op(*op));
                         }
                     } else {
                         let _ = writeln!(a, "\tmovq {}, %rax", at(*lhs));
-                        let mnem = match op {
-                            BinOp::Add => "addq",
-                            BinOp::Sub => "subq",
-                            _ => "imulq",
-                        };
-                        let _ = writeln!(a, "\t{mnem} ${imm}, %rax\n\tmovq %rax, {}", at(*dst));
+                        let _ = writeln!(
+                            a,
+                            "\t{} ${imm}, %rax\n\tmovq %rax, {}",
+                            word_op(*op),
+                            at(*dst)
+                        );
                     }
                 }
                 _ => {
@@ -327,6 +451,7 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 lhs,
                 rhs,
                 rem,
+                unsigned,
                 loc,
             } => {
                 traps += 1;
@@ -339,6 +464,19 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                      \tleaq {loc}(%rip), %rdi\n\tcall {TRAP_DIV0}\n{ok0}:",
                     at(*rhs)
                 );
+                // Unsigned (u64, ADR 0052): no overflowing quotient exists.
+                if *unsigned {
+                    let _ = writeln!(
+                        a,
+                        "\tmovq {}, %rax\n\txorl %edx, %edx\n\tdivq %rcx",
+                        at(*lhs)
+                    );
+                    if *rem {
+                        a.push_str("\tmovq %rdx, %rax\n");
+                    }
+                    let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
+                    continue;
+                }
                 let _ = writeln!(
                     a,
                     "\tmovq {}, %rax\n\tcmpq $-1, %rcx\n\tjne {ok1}\n\
@@ -352,6 +490,31 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 }
                 let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
             }
+            // The count must name a bit (ADR 0049); one unsigned compare
+            // also sends a negative count to the trap.
+            Inst::ShiftChecked {
+                op,
+                dst,
+                lhs,
+                rhs,
+                loc,
+            } => {
+                traps += 1;
+                let ok = format!(".LTB{module}_{name}_{traps}");
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rcx\n\tcmpq $63, %rcx\n\tjbe {ok}\n\
+                     \tmovq %rcx, %rdi\n\tleaq {loc}(%rip), %rsi\n\tcall {TRAP_SHIFT}\n{ok}:",
+                    at(*rhs)
+                );
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rax\n\t{} %cl, %rax\n\tmovq %rax, {}",
+                    at(*lhs),
+                    word_op(*op),
+                    at(*dst)
+                );
+            }
             Inst::DivMagic { dst, src, d } | Inst::RemMagic { dst, src, d } => {
                 let (m, shift) = magic_i64(*d);
                 let _ = writeln!(
@@ -385,34 +548,40 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 lhs,
                 rhs,
             } => {
+                let word = matches!(
+                    op,
+                    BinOp::Add
+                        | BinOp::Sub
+                        | BinOp::Mul
+                        | BinOp::BitAnd
+                        | BinOp::BitOr
+                        | BinOp::BitXor
+                );
                 if let (Loc::Reg(d), true) = (loc[dst], at(*dst) != at(*rhs))
-                    && matches!(op, BinOp::Add | BinOp::Sub | BinOp::Mul)
+                    && word
                 {
                     if at(*lhs) != at(*dst) {
                         let _ = writeln!(a, "\tmovq {}, {d}", at(*lhs));
                     }
-                    let mnem = match op {
-                        BinOp::Add => "addq",
-                        BinOp::Sub => "subq",
-                        _ => "imulq",
-                    };
-                    let _ = writeln!(a, "\t{mnem} {}, {d}", at(*rhs));
+                    let _ = writeln!(a, "\t{} {}, {d}", word_op(*op), at(*rhs));
                     continue;
                 }
                 let _ = writeln!(a, "\tmovq {}, %rax", at(*lhs));
                 match op {
-                    BinOp::Add => {
-                        let _ = writeln!(a, "\taddq {}, %rax", at(*rhs));
-                    }
-                    BinOp::Sub => {
-                        let _ = writeln!(a, "\tsubq {}, %rax", at(*rhs));
-                    }
-                    BinOp::Mul => {
-                        let _ = writeln!(a, "\timulq {}, %rax", at(*rhs));
+                    BinOp::Add
+                    | BinOp::Sub
+                    | BinOp::Mul
+                    | BinOp::BitAnd
+                    | BinOp::BitOr
+                    | BinOp::BitXor => {
+                        let _ = writeln!(a, "\t{} {}, %rax", word_op(*op), at(*rhs));
                     }
                     BinOp::Div | BinOp::Rem => {
                         unreachable!("integer division lowers to DivChecked (ADR 0022)")
                     }
+                    BinOp::Shl | BinOp::Shr | BinOp::UShr => {
+                        unreachable!("shifts lower to ShiftChecked or an immediate (ADR 0049)")
+                    }
                     BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => {
                         let _ = writeln!(
                             a,
@@ -450,6 +619,12 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                     BinOp::And | BinOp::Or | BinOp::Coalesce => {
                         unreachable!("lowered to control flow")
                     }
+                    BinOp::BitAnd
+                    | BinOp::BitOr
+                    | BinOp::BitXor
+                    | BinOp::Shl
+                    | BinOp::Shr
+                    | BinOp::UShr => unreachable!("the checker admits bit operations on ints only"),
                 };
                 a.push_str(&code);
                 let cmp = matches!(
@@ -467,10 +642,10 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 }
             }
             Inst::Call {
-                dst,
-                label,
-                args,
-                sret,
+                dst, args, sret, ..
+            }
+            | Inst::CallInd {
+                dst, args, sret, ..
             } => {
                 let shift = sret.is_some() as usize;
                 if let Some(s) = sret {
@@ -490,7 +665,13 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                         }
                     }
                 }
-                let _ = writeln!(a, "\tcall {label}");
+                if let Inst::CallInd { callee, .. } = inst {
+                    // The object's first word is the code address
+                    // (ADR 0038); %rax is free once arguments are set.
+                    let _ = writeln!(a, "\tmovq {}, %rax\n\tcall *(%rax)", at(*callee));
+                } else if let Inst::Call { label, .. } = inst {
+                    let _ = writeln!(a, "\tcall {label}");
+                }
                 if let Some(l) = loc.get(dst) {
                     let _ = writeln!(a, "\tmovq %rax, {}", operand(*l));
                 }
@@ -521,14 +702,20 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                     at(*buf)
                 );
             }
-            Inst::BufSet { buf, slot, val } => {
-                let _ = writeln!(
-                    a,
-                    "\tmovq {}, %rax\n\tmovq {}, %rcx\n\tmovq %rcx, {}(%rax)",
-                    at(*buf),
-                    at(*val),
-                    8 * slot
-                );
+            Inst::BufSet {
+                buf,
+                slot,
+                val,
+                elem,
+            } => {
+                let off = elem.stride() * *slot as i64;
+                let _ = writeln!(a, "\tmovq {}, %rax\n\tmovq {}, %rdx", at(*buf), at(*val));
+                match elem {
+                    Elem::Packed(t) => a.push_str(&store_packed(*t, &format!("{off}(%rax)"))),
+                    _ => {
+                        let _ = writeln!(a, "\tmovq %rdx, {off}(%rax)");
+                    }
+                }
             }
             Inst::Len(d, arr) => {
                 let _ = writeln!(a, "\tmovq {}, %rax\n\tmovq 0(%rax), %rax", at(*arr));
@@ -539,52 +726,95 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                 arr,
                 idx,
                 loc,
-                agg,
+                elem,
             } => {
                 traps += 1;
                 let target = format!(".LTB{module}_{name}_{traps}");
-                a.push_str(&bounds_check(&at(*arr), &at(*idx), loc, &target));
-                match agg {
-                    None => {
+                a.push_str(&bounds_check(&at(*arr), 0, &at(*idx), loc, &target));
+                match elem {
+                    Elem::Word => {
                         a.push_str("\tmovq 16(%rax), %rax\n\tmovq (%rax,%rcx,8), %rax\n");
                     }
+                    Elem::Packed(t) => {
+                        a.push_str("\tmovq 16(%rax), %rax\n");
+                        a.push_str(&extend(*t, &format!("(%rax,%rcx,{})", elem.stride())));
+                    }
                     // Interior pointer: data + idx*stride (ADR 0023).
-                    Some(words) => {
+                    Elem::Agg(_) => {
                         let _ = writeln!(
                             a,
                             "\tmovq 16(%rax), %rax\n\timulq ${}, %rcx, %rcx\n\taddq %rcx, %rax",
-                            8 * *words
+                            elem.stride()
                         );
                     }
                 }
                 let _ = writeln!(a, "\tmovq %rax, {}", at(*dst));
             }
+            Inst::ByteAt { dst, s, idx, loc } => {
+                traps += 1;
+                let target = format!(".LTB{module}_{name}_{traps}");
+                a.push_str(&bounds_check(&at(*s), 8, &at(*idx), loc, &target));
+                let _ = writeln!(
+                    a,
+                    "\tmovq 0(%rax), %rax\n\tmovzbq (%rax,%rcx), %rax\n\tmovq %rax, {}",
+                    at(*dst)
+                );
+            }
+            // The length is the type's (ADR 0056): an immediate compare,
+            // then the interior pointer.
+            Inst::SlotAt {
+                dst,
+                base,
+                idx,
+                len,
+                stride,
+                loc,
+            } => {
+                traps += 1;
+                let target = format!(".LTB{module}_{name}_{traps}");
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rcx\n\tcmpq ${len}, %rcx\n\tjb {target}\n\
+                     \tmovq %rcx, %rdi\n\tmovq ${len}, %rsi\n\tleaq {loc}(%rip), %rdx\n\
+                     \tcall {TRAP_OOB}\n\
+                     {target}:\n\
+                     \timulq ${stride}, %rcx, %rcx\n\tmovq {}, %rax\n\taddq %rcx, %rax\n\
+                     \tmovq %rax, {}",
+                    at(*idx),
+                    at(*base),
+                    at(*dst)
+                );
+            }
             Inst::IndexSet {
                 arr,
                 idx,
                 val,
                 loc,
-                agg,
+                elem,
             } => {
                 traps += 1;
                 let target = format!(".LTB{module}_{name}_{traps}");
-                a.push_str(&bounds_check(&at(*arr), &at(*idx), loc, &target));
-                match agg {
-                    None => {
+                a.push_str(&bounds_check(&at(*arr), 0, &at(*idx), loc, &target));
+                match elem {
+                    Elem::Word => {
                         let _ = writeln!(
                             a,
                             "\tmovq 16(%rax), %rax\n\tmovq {}, %rdx\n\tmovq %rdx, (%rax,%rcx,8)",
                             at(*val)
                         );
                     }
+                    Elem::Packed(t) => {
+                        let _ = writeln!(a, "\tmovq 16(%rax), %rax\n\tmovq {}, %rdx", at(*val));
+                        a.push_str(&store_packed(*t, &format!("(%rax,%rcx,{})", elem.stride())));
+                    }
                     // rep movsq from the value pointer into the buffer
                     // slot — %rdi/%rsi/%rcx are reserved scratch (CopyW).
-                    Some(words) => {
+                    Elem::Agg(words) => {
                         let _ = writeln!(
                             a,
                             "\tmovq 16(%rax), %rdi\n\timulq ${}, %rcx, %rcx\n\taddq %rcx, %rdi\n\
                              \tmovq {}, %rsi\n\tmovq ${words}, %rcx\n\trep movsq",
-                            8 * *words,
+                            elem.stride(),
                             at(*val)
                         );
                     }
@@ -605,6 +835,32 @@ pub(super) fn emit(ir: FunctionIr) -> String {
                     let _ = writeln!(a, "\tcmpq $0, {}\n\tje .LT{module}_{name}_{l}", at(*v));
                 }
             }
+            // Jump table (ADR 0046): rebase, one unsigned compare for
+            // both bounds, then an indirect jump through 32-bit offsets
+            // relative to the table — position-independent, so .rodata
+            // needs no load-time relocation.
+            Inst::Switch {
+                value,
+                lo,
+                table,
+                default,
+            } => {
+                let jt = format!(".LJ{module}_{name}_{idx}");
+                let _ = writeln!(
+                    a,
+                    "\tmovq {}, %rax\n\tmovabsq ${lo}, %rcx\n\tsubq %rcx, %rax\n\
+                     \tcmpq ${}, %rax\n\tjae .LT{module}_{name}_{default}\n\
+                     \tleaq {jt}(%rip), %rcx\n\tmovslq (%rcx,%rax,4), %rax\n\
+                     \taddq %rcx, %rax\n\tjmp *%rax\n\
+                     \t.section .rodata\n\t.p2align 2\n{jt}:",
+                    at(*value),
+                    table.len()
+                );
+                for l in table {
+                    let _ = writeln!(a, "\t.long .LT{module}_{name}_{l} - {jt}");
+                }
+                a.push_str("\t.text\n");
+            }
             Inst::Label(l) => {
                 let _ = writeln!(a, ".LT{module}_{name}_{l}:");
             }
@@ -5,7 +5,7 @@
 //! this module only maps them onto the machine.
 
 use crate::check::Resolutions;
-use crate::types::{StructType, Type};
+use crate::types::{IntTy, StructType, Type};
 
 /// Recursion bound for layout walks — a recursive value struct has
 /// infinite size; its values can't exist, so hitting this is diagnostic.
@@ -47,11 +47,42 @@ impl Kind {
     }
 }
 
+/// How an array element sits in its buffer (ADR 0014): a word, a sized
+/// integer packed at its own width (ADR 0052), or an aggregate of that
+/// many words stored inline (ADR 0023), read as an interior pointer.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Elem {
+    Word,
+    Packed(IntTy),
+    Agg(usize),
+}
+
+impl Elem {
+    /// Bytes from one element to the next.
+    pub(crate) fn stride(self) -> i64 {
+        match self {
+            Elem::Word => 8,
+            Elem::Packed(t) => t.bits() as i64 / 8,
+            Elem::Agg(words) => 8 * words as i64,
+        }
+    }
+}
+
+/// The buffer layout of a `T[]`'s elements; `None` when `T` is not
+/// compilable. A u64 fills the word anyway, so it stays `Word`.
+pub(crate) fn elem_of(t: &Type, res: &Resolutions) -> Option<Elem> {
+    Some(match (t, kind_of(t, res, FUEL)?) {
+        (Type::Sized(it), _) if it.bits() < 64 => Elem::Packed(*it),
+        (_, Kind::Word) => Elem::Word,
+        (_, k) => Elem::Agg(k.words()),
+    })
+}
+
 /// A reference-shaped checker type: a handle where 0 means `null`, so a
 /// `T?` of it is a nullable pointer for free (ADR 0009).
 pub(crate) fn ref_shaped(t: &Type, res: &Resolutions) -> bool {
     match t {
-        Type::Array(_) | Type::File => true,
+        Type::Array(_) | Type::Map(..) | Type::File | Type::Fn(..) => true,
         Type::Struct(m, n) => res.structs[&(*m, n.clone())].by_ref,
         _ => false,
     }
@@ -61,7 +92,23 @@ pub(crate) fn ref_shaped(t: &Type, res: &Resolutions) -> bool {
 /// optionals, float printing aside) or infinite (recursive value struct).
 pub(crate) fn kind_of(t: &Type, res: &Resolutions, fuel: usize) -> Option<Kind> {
     match t {
-        Type::Int | Type::Bool | Type::Float | Type::File | Type::ErrCode => Some(Kind::Word),
+        Type::Int | Type::Bool | Type::Float | Type::File => Some(Kind::Word),
+        // The code word, then room for the widest payload list (ADR
+        // 0062) — a bare word while no error carries payloads. Slack
+        // is zeroed like an enum's, but codes compare alone, never by
+        // memcmp.
+        Type::ErrCode => match error_payload_words(res, fuel.checked_sub(1)?)? {
+            0 => Some(Kind::Word),
+            words => Some(Kind::Enum {
+                words: 1 + words,
+                no_memcmp: true,
+            }),
+        },
+        // Canonical in a full word wherever it travels (ADR 0052); only
+        // array buffers pack it (`elem_of`).
+        Type::Sized(_) => Some(Kind::Word),
+        // A pointer to a function object (ADR 0038).
+        Type::Fn(..) => Some(Kind::Word),
         // An empty literal's unconstrained element ([]): a handle word.
         Type::Unknown => Some(Kind::Word),
         // A nullable handle is a free word; a value payload gets the
@@ -70,9 +117,14 @@ pub(crate) fn kind_of(t: &Type, res: &Resolutions, fuel: usize) -> Option<Kind>
         // no-memcmp struct payloads are not.
         // `T!` is always tagged — even ref-shaped payloads: a handle
         // cannot encode which error (ADR 0034). Same words math as the
-        // value optional; tag 0 = value, ≥2 = the code, 1 reserved.
+        // value optional; tag 0 = value, ≥2 = the code, 1 reserved. An
+        // error's payloads share the words after the tag with the
+        // value (ADR 0062), so the tag word and what follows read as
+        // an `error`.
         Type::ErrUnion(inner) => {
-            let k = kind_of(inner, res, fuel.checked_sub(1)?)?;
+            let next = fuel.checked_sub(1)?;
+            let k = kind_of(inner, res, next)?;
+            let err_words = error_payload_words(res, next)?;
             let no_memcmp = matches!(inner.as_ref(), Type::Float)
                 || matches!(
                     k,
@@ -88,7 +140,7 @@ pub(crate) fn kind_of(t: &Type, res: &Resolutions, fuel: usize) -> Option<Kind>
                         | Kind::Opt { .. }
                 );
             Some(Kind::Opt {
-                words: 1 + k.words(),
+                words: 1 + k.words().max(err_words),
                 no_memcmp,
             })
         }
@@ -123,6 +175,14 @@ pub(crate) fn kind_of(t: &Type, res: &Resolutions, fuel: usize) -> Option<Kind>
             kind_of(inner, res, fuel.checked_sub(1)?)?;
             Some(Kind::Word)
         }
+        // A map is a handle too (ADR 0042); entries store key and
+        // value inline at the stride their kinds add up to.
+        Type::Map(k, v) => {
+            let next = fuel.checked_sub(1)?;
+            kind_of(k, res, next)?;
+            kind_of(v, res, next)?;
+            Some(Kind::Word)
+        }
         Type::Str => Some(Kind::Str),
         // Tag word + the widest variant (ADR 0036). `no_memcmp`
         // mirrors the struct rule over every payload of every variant.
@@ -170,40 +230,111 @@ pub(crate) fn kind_of(t: &Type, res: &Resolutions, fuel: usize) -> Option<Kind>
             if def.by_ref {
                 return Some(Kind::Word);
             }
-            let next = fuel.checked_sub(1)?;
-            let mut words = 0;
-            let mut no_memcmp = false;
-            for (_, ft) in &def.fields {
-                no_memcmp |= matches!(ft, Type::Float);
-                match kind_of(ft, res, next)? {
-                    Kind::Word => words += 1,
-                    Kind::Str => {
-                        words += 2;
-                        no_memcmp = true;
-                    }
-                    Kind::Struct {
-                        words: w,
-                        no_memcmp: n,
-                    }
-                    | Kind::Opt {
-                        words: w,
-                        no_memcmp: n,
-                    }
-                    | Kind::Enum {
-                        words: w,
-                        no_memcmp: n,
-                    } => {
-                        words += w;
-                        no_memcmp |= n;
-                    }
-                }
-            }
-            Some(Kind::Struct { words, no_memcmp })
+            let fields = def.fields.iter().map(|(_, ft)| ft);
+            aggregate_kind(fields, res, fuel.checked_sub(1)?)
+        }
+        // Laid out exactly like a value struct with fields `.0`, `.1`,
+        // … in order (ADR 0043).
+        Type::Tuple(elems) => aggregate_kind(elems.iter(), res, fuel.checked_sub(1)?),
+        // N elements inline at the element's stride (ADR 0056): a tuple
+        // of N equal legs, so it copies, compares, and hashes like one.
+        Type::Fixed(inner, n) => {
+            let Kind::Struct { words, no_memcmp } =
+                aggregate_kind(std::iter::once(&**inner), res, fuel.checked_sub(1)?)?
+            else {
+                unreachable!("aggregate_kind is a struct kind")
+            };
+            Some(Kind::Struct {
+                words: n * words,
+                no_memcmp,
+            })
         }
         _ => None,
     }
 }
 
+/// The words the widest error payload list takes (ADR 0062): what
+/// an `error` carries after its code word. `None` when a payload is
+/// not compilable.
+pub(crate) fn error_payload_words(res: &Resolutions, fuel: usize) -> Option<usize> {
+    let mut max_words = 0;
+    for payloads in &res.error_payloads {
+        let mut words = 0;
+        for pt in payloads {
+            words += kind_of(pt, res, fuel)?.words();
+        }
+        max_words = max_words.max(words);
+    }
+    Some(max_words)
+}
+
+/// A value aggregate's kind: its legs back to back, memcmp-comparable
+/// only when every leg is (float and str legs rule it out).
+fn aggregate_kind<'a>(
+    legs: impl Iterator<Item = &'a Type>,
+    res: &Resolutions,
+    fuel: usize,
+) -> Option<Kind> {
+    let mut words = 0;
+    let mut no_memcmp = false;
+    for ft in legs {
+        no_memcmp |= matches!(ft, Type::Float);
+        match kind_of(ft, res, fuel)? {
+            Kind::Word => words += 1,
+            Kind::Str => {
+                words += 2;
+                no_memcmp = true;
+            }
+            Kind::Struct {
+                words: w,
+                no_memcmp: n,
+            }
+            | Kind::Opt {
+                words: w,
+                no_memcmp: n,
+            }
+            | Kind::Enum {
+                words: w,
+                no_memcmp: n,
+            } => {
+                words += w;
+                no_memcmp |= n;
+            }
+        }
+    }
+    Some(Kind::Struct { words, no_memcmp })
+}
+
+/// Each leg of an aggregate — a struct's fields (a refstruct's heap
+/// object included), a tuple's elements (ADR 0043), or a fixed array's
+/// slots (ADR 0056) — with its byte offset, in layout order. `None` for any other type, or an
+/// uncompilable leg.
+pub(crate) fn legs(t: &Type, res: &Resolutions) -> Option<Vec<(i64, Type)>> {
+    let tys: Vec<Type> = match t {
+        Type::Struct(m, n) => res.structs[&(*m, n.clone())]
+            .fields
+            .iter()
+            .map(|(_, ft)| ft.clone())
+            .collect(),
+        Type::Tuple(elems) => elems.clone(),
+        Type::Fixed(inner, n) => vec![(**inner).clone(); *n],
+        _ => return None,
+    };
+    let mut off = 0;
+    tys.into_iter()
+        .map(|lt| {
+            let at = off;
+            off += 8 * kind_of(&lt, res, FUEL)?.words() as i64;
+            Some((at, lt))
+        })
+        .collect()
+}
+
+/// Byte offset of leg `index` in an aggregate (see `legs`).
+pub(crate) fn leg_offset(t: &Type, index: usize, res: &Resolutions) -> Option<i64> {
+    legs(t, res)?.get(index).map(|(off, _)| *off)
+}
+
 /// Byte offset of field `index` in `def` — the sum of the sizes before
 /// it (C-style declaration-order layout, ADR 0009).
 pub(crate) fn offset_of(def: &StructType, index: usize, res: &Resolutions) -> Option<i64> {
@@ -0,0 +1,874 @@
+//! Pattern dispatch for both match forms (ADR 0036/0044/0045), for
+//! let-else, a one-arm match (ADR 0057), and for the heads of `if const`
+//! and `while const` (ADR 0058): the arms compile to a
+//! decision tree that tests each scrutinee position at most once per
+//! path, rather than trying the arms one by one.
+//! Every position is an offset into the scrutinee — nested enums are
+//! inline. A leaf copies its arm's bindings out (so mutation inside
+//! the arm can't alias the scrutinee), runs any guard, and jumps to
+//! the arm's body, which is emitted once however many leaves reach
+//! it. A failed guard continues the tree with the rows below it.
+//! Equal subtrees are emitted once and shared, and a match whose tree
+//! still grows past `MAX_SUBTREES` tries its rows in order instead.
+//! Int, string, and error scrutinees (ADR 0046) are a single column;
+//! dense int and error-code cases become one jump table.
+
+use std::collections::HashMap;
+
+use super::{Binding, Lowerer};
+use crate::ast::{BinOp, Expr, MatchArm, Pattern, Stmt};
+use crate::diagnostic::Diagnostic;
+use crate::ir::layout::{FUEL, Kind, kind_of};
+use crate::ir::{Inst, Lbl, V, unsupported};
+use crate::span::Span;
+use crate::types::Type;
+
+/// One row of the decision matrix: the patterns left to test, one
+/// per column (`None` matches anything), and the arm it selects —
+/// `else` is the row past the last arm.
+#[derive(Clone)]
+struct Row<'p> {
+    pats: Vec<Option<&'p Pattern>>,
+    arm: usize,
+}
+
+/// A column: a position in the scrutinee and the type found there.
+#[derive(Clone)]
+struct Col {
+    off: i64,
+    ty: Type,
+}
+
+/// What a column's patterns test for: a variant tag, an int, bool,
+/// or error-code word, or string content.
+enum Test<'p> {
+    Word(i64),
+    Str(&'p Expr),
+}
+
+/// Tests agree by value: two `"a"` patterns are one case.
+impl PartialEq for Test<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Test::Word(a), Test::Word(b)) => a == b,
+            (Test::Str(Expr::Str(a, _)), Test::Str(Expr::Str(b, _))) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Everything the tree needs to finish a leaf, per arm.
+struct Plan<'p> {
+    /// The scrutinee, addressed by column offsets — or, when `word`,
+    /// the int or error-code value itself (ADR 0046).
+    u: V,
+    word: bool,
+    guards: Vec<Option<&'p Expr>>,
+    /// Binding slots and the copy-outs that fill them.
+    binds: Vec<Vec<(V, i64, Kind)>>,
+    bodies: Vec<Lbl>,
+    end: Lbl,
+    /// Subtrees emitted so far, by their rows and columns. A path that
+    /// reaches the same rows over the same columns jumps to the code
+    /// already emitted, so the tree is a DAG: wide matches whose cases
+    /// keep the same rows stay linear instead of doubling per column.
+    emitted: HashMap<SubKey, Lbl>,
+    /// Set once `emitted` outgrows `MAX_SUBTREES`; the tree is then
+    /// dropped for `rows_in_order`.
+    over: bool,
+}
+
+/// The most distinct subtrees one dispatch may emit. Rows that test
+/// many columns with wildcards between them can still need
+/// exponentially many; past this, arms are tried one by one.
+const MAX_SUBTREES: usize = 1024;
+
+/// A subtree's identity: each row's cells as pattern addresses (0 for
+/// a wildcard) with its arm, and each column's offset. A pattern sits
+/// at one position of one type, so equal keys emit equal code.
+type SubKey = (Vec<(Vec<usize>, usize)>, Vec<i64>);
+
+/// The arms' scopes and body labels, for the caller to emit bodies
+/// against; `bodies` has the `else` label last.
+struct Dispatch {
+    scopes: Vec<HashMap<String, Binding>>,
+    bodies: Vec<Lbl>,
+    end: Lbl,
+}
+
+impl Lowerer<'_> {
+    pub(super) fn match_stmt(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm],
+        else_body: Option<&[Stmt]>,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let d = self.dispatch(scrutinee, arms, else_body.is_some(), span)?;
+        for ((arm, scope), body) in arms.iter().zip(d.scopes).zip(&d.bodies) {
+            self.insts.push(Inst::Label(*body));
+            let result = self.body_in(scope, &arm.body);
+            result?;
+            self.insts.push(Inst::Jmp(d.end));
+        }
+        if let Some(else_body) = else_body {
+            self.insts.push(Inst::Label(d.bodies[arms.len()]));
+            self.block(else_body)?;
+        }
+        self.insts.push(Inst::Label(d.end));
+        Ok(())
+    }
+
+    /// The match expression (ADR 0044): each arm's value lands in the
+    /// result slot, shaped by the match's recorded type — a declared
+    /// `T?` slot wraps per arm, like any other value flowing into it.
+    pub(super) fn match_expr(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm<Expr>],
+        else_value: Option<&Expr>,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let rt = self
+            .ty(&span)
+            .cloned()
+            .ok_or_else(|| unsupported("this match", span))?;
+        let rk = kind_of(&rt, self.res, FUEL).ok_or_else(|| unsupported("this match", span))?;
+        let out = self.fresh(rt == Type::Float);
+        if rk != Kind::Word {
+            self.insts.push(Inst::Temp {
+                dst: out,
+                words: rk.words(),
+            });
+        }
+        let d = self.dispatch(scrutinee, arms, else_value.is_some(), span)?;
+        for ((arm, scope), body) in arms.iter().zip(d.scopes).zip(&d.bodies) {
+            self.insts.push(Inst::Label(*body));
+            self.scopes.push(scope);
+            let result = self.expr_into(&arm.body, &rt);
+            self.scopes.pop();
+            self.settle(out, result?, rk);
+            self.insts.push(Inst::Jmp(d.end));
+        }
+        if let Some(e) = else_value {
+            self.insts.push(Inst::Label(d.bodies[arms.len()]));
+            let v = self.expr_into(e, &rt)?;
+            self.settle(out, v, rk);
+        }
+        self.insts.push(Inst::Label(d.end));
+        Ok(out)
+    }
+
+    /// A let-else (ADR 0057): a one-arm dispatch whose bindings join
+    /// the enclosing scope. The `else` block leaves, so the bindings'
+    /// code path is the only one reaching the statement's end.
+    pub(super) fn let_else(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expr,
+        else_body: Option<&[Stmt]>,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let d = self.dispatch_heads(value, &[(pattern, None, span)], else_body.is_some(), span)?;
+        if let Some(else_body) = else_body {
+            self.insts.push(Inst::Label(d.bodies[1]));
+            self.block(else_body)?;
+        }
+        self.insts.push(Inst::Label(d.bodies[0]));
+        self.insts.push(Inst::Label(d.end));
+        let scope = d.scopes.into_iter().next().expect("one head, one scope");
+        self.scopes
+            .last_mut()
+            .expect("a scope is always open")
+            .extend(scope);
+        Ok(())
+    }
+
+    /// `if const` (ADR 0058): the head falls through into the then
+    /// branch with its bindings, or jumps to the `else`.
+    pub(super) fn if_const(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expr,
+        then_body: &[Stmt],
+        else_body: Option<&[Stmt]>,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let miss = self.fresh_label();
+        let scope = self.const_head(pattern, value, miss, span)?;
+        let result = self.body_in(scope, then_body);
+        result?;
+        match else_body {
+            None => self.insts.push(Inst::Label(miss)),
+            Some(else_body) => {
+                let end = self.fresh_label();
+                self.insts.push(Inst::Jmp(end));
+                self.insts.push(Inst::Label(miss));
+                self.block(else_body)?;
+                self.insts.push(Inst::Label(end));
+            }
+        }
+        Ok(())
+    }
+
+    /// `while const` (ADR 0058): the head runs at the top of every
+    /// pass; a miss leaves the loop. `continue` re-runs the head.
+    pub(super) fn while_const(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expr,
+        body: &[Stmt],
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let scope = self.const_head(pattern, value, end, span)?;
+        self.loops.push((top, end));
+        let result = self.body_in(scope, body);
+        self.loops.pop();
+        result?;
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(())
+    }
+
+    /// Evaluates a `const` head and jumps to `miss` unless it binds;
+    /// the fall-through path holds the returned bindings. A name
+    /// copies out the payload of a present `T?` (tag 1, or a non-null
+    /// handle) or of a `T!` holding a value (tag 0); a variant pattern
+    /// is a one-head dispatch.
+    fn const_head(
+        &mut self,
+        pattern: &Pattern,
+        value: &Expr,
+        miss: Lbl,
+        span: Span,
+    ) -> Result<HashMap<String, Binding>, Diagnostic> {
+        let Pattern::Bind(name, _) = pattern else {
+            let d = self.dispatch_heads(value, &[(pattern, None, span)], true, span)?;
+            self.insts.push(Inst::Label(d.bodies[1]));
+            self.insts.push(Inst::Jmp(miss));
+            self.insts.push(Inst::Label(d.bodies[0]));
+            self.insts.push(Inst::Label(d.end));
+            return Ok(d.scopes.into_iter().next().expect("one head, one scope"));
+        };
+        let vt = self
+            .ty(&value.span())
+            .cloned()
+            .ok_or_else(|| unsupported("this binding", span))?;
+        let u = self.expr(value)?;
+        let inner = match vt {
+            Type::ErrUnion(inner) => {
+                let tag = self.load_at(u, 0);
+                let ok = self.fresh(false);
+                self.insts.push(Inst::BinImm {
+                    op: BinOp::Eq,
+                    dst: ok,
+                    lhs: tag,
+                    imm: 0,
+                });
+                self.insts.push(Inst::BrZero(ok, miss));
+                *inner
+            }
+            Type::Optional(inner) if self.opt_inner_of(&vt).is_some() => {
+                let tag = self.load_at(u, 0);
+                self.insts.push(Inst::BrZero(tag, miss));
+                *inner
+            }
+            // A nullable handle: the word itself, 0 = null.
+            Type::Optional(inner) => {
+                self.insts.push(Inst::BrZero(u, miss));
+                let v = self.fresh(false);
+                self.insts.push(Inst::Copy(v, u));
+                return Ok(self.head_binding(name, v, &inner));
+            }
+            _ => return Err(unsupported("this binding", span)),
+        };
+        let k = kind_of(&inner, self.res, FUEL)
+            .ok_or_else(|| unsupported("values of this type", span))?;
+        let v = if k == Kind::Word {
+            let v = self.fresh(inner == Type::Float);
+            self.insts.push(Inst::LoadAt {
+                dst: v,
+                base: u,
+                off: 8,
+            });
+            v
+        } else {
+            let p = self.lea_at(u, 8);
+            self.snapshot(p, k.words())
+        };
+        Ok(self.head_binding(name, v, &inner))
+    }
+
+    fn head_binding(&self, name: &str, v: V, ty: &Type) -> HashMap<String, Binding> {
+        let mut scope = HashMap::new();
+        if name != "_" {
+            scope.insert(
+                name.to_string(),
+                Binding {
+                    v,
+                    opt_inner: self.opt_inner_of(ty),
+                    err_inner: self.err_inner_of(ty),
+                },
+            );
+        }
+        scope
+    }
+
+    /// `dispatch_heads` over a match's arms.
+    fn dispatch<B>(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm<B>],
+        has_else: bool,
+        span: Span,
+    ) -> Result<Dispatch, Diagnostic> {
+        let heads: Vec<_> = arms
+            .iter()
+            .map(|a| (&a.pattern, a.guard.as_ref(), a.span))
+            .collect();
+        self.dispatch_heads(scrutinee, &heads, has_else, span)
+    }
+
+    /// Evaluates the scrutinee, allocates every head's binding slots,
+    /// and emits the decision tree. Bodies are left to the caller.
+    /// A head is an arm's pattern, guard, and span.
+    fn dispatch_heads(
+        &mut self,
+        scrutinee: &Expr,
+        heads: &[(&Pattern, Option<&Expr>, Span)],
+        has_else: bool,
+        span: Span,
+    ) -> Result<Dispatch, Diagnostic> {
+        let Some(ty @ (Type::Enum(..) | Type::Int | Type::Str | Type::ErrCode)) =
+            self.ty(&scrutinee.span()).cloned()
+        else {
+            return Err(unsupported("this match", span));
+        };
+        // An error is its code word until errors carry payloads
+        // (ADR 0062); then it is read through like an enum.
+        let word = match ty {
+            Type::Int => true,
+            Type::ErrCode => self.err_kind(span)? == Kind::Word,
+            _ => false,
+        };
+        let u = self.expr(scrutinee)?;
+        let mut scopes = Vec::new();
+        let mut binds = Vec::new();
+        for &(pattern, _, arm_span) in heads {
+            let mut found = Vec::new();
+            self.pattern_slots(pattern, 0, &ty, &mut found)?;
+            let mut scope = HashMap::new();
+            let mut copies = Vec::new();
+            for (name, off, pt) in found {
+                let pk = kind_of(&pt, self.res, FUEL)
+                    .ok_or_else(|| unsupported("payloads of this type", arm_span))?;
+                let v = self.fresh(pt == Type::Float);
+                if pk != Kind::Word {
+                    self.insts.push(Inst::Temp {
+                        dst: v,
+                        words: pk.words(),
+                    });
+                }
+                copies.push((v, off, pk));
+                scope.insert(
+                    name,
+                    Binding {
+                        v,
+                        opt_inner: self.opt_inner_of(&pt),
+                        err_inner: self.err_inner_of(&pt),
+                    },
+                );
+            }
+            scopes.push(scope);
+            binds.push(copies);
+        }
+        let bodies: Vec<Lbl> = (0..=heads.len()).map(|_| self.fresh_label()).collect();
+        let end = self.fresh_label();
+        let mut plan = Plan {
+            u,
+            word,
+            guards: heads.iter().map(|&(_, guard, _)| guard).collect(),
+            binds,
+            bodies: bodies.clone(),
+            end,
+            emitted: HashMap::new(),
+            over: false,
+        };
+        let mut rows: Vec<Row> = heads
+            .iter()
+            .enumerate()
+            .map(|(arm, &(pattern, _, _))| Row {
+                pats: vec![Some(pattern)],
+                arm,
+            })
+            .collect();
+        if has_else {
+            rows.push(Row {
+                pats: vec![None],
+                arm: heads.len(),
+            });
+        }
+        let cols = vec![Col { off: 0, ty }];
+        let (insts, lambdas) = (self.insts.len(), self.lambdas.len());
+        self.tree(rows.clone(), cols.clone(), &mut plan, &scopes)?;
+        if plan.over {
+            self.insts.truncate(insts);
+            self.lambdas.truncate(lambdas);
+            self.rows_in_order(&rows, &cols, &plan, &scopes)?;
+        }
+        Ok(Dispatch {
+            scopes,
+            bodies,
+            end,
+        })
+    }
+
+    /// Every name a pattern binds, with its offset and type.
+    fn pattern_slots(
+        &self,
+        p: &Pattern,
+        off: i64,
+        ty: &Type,
+        out: &mut Vec<(String, i64, Type)>,
+    ) -> Result<(), Diagnostic> {
+        match p {
+            Pattern::Bind(name, _) if name != "_" => out.push((name.clone(), off, ty.clone())),
+            Pattern::Bind(..) | Pattern::Lit(_) => {}
+            Pattern::Variant { args, .. } | Pattern::Error { args, .. } => {
+                for (arg, col) in args.iter().zip(self.payload_cols(p, off, ty)?) {
+                    self.pattern_slots(arg, col.off, &col.ty, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The payload columns a variant or error pattern opens: its
+    /// payloads' types and offsets past the tag (or code) word.
+    fn payload_cols(&self, p: &Pattern, off: i64, ty: &Type) -> Result<Vec<Col>, Diagnostic> {
+        let payloads = match (p, ty) {
+            (Pattern::Variant { .. }, Type::Enum(m, n)) => {
+                let tag = self.variant_tag(p)?;
+                &self.res.enums[&(*m, n.clone())].variants[tag as usize].1
+            }
+            (Pattern::Error { name_span, .. }, _) => {
+                &self.res.error_payloads[self.res.error_lits[name_span] as usize - 2]
+            }
+            _ => return Err(unsupported("this pattern", p.span())),
+        };
+        let mut cols = Vec::new();
+        let mut at = off + 8;
+        for pt in payloads {
+            let pk = kind_of(pt, self.res, FUEL)
+                .ok_or_else(|| unsupported("payloads of this type", p.span()))?;
+            cols.push(Col {
+                off: at,
+                ty: pt.clone(),
+            });
+            at += 8 * pk.words() as i64;
+        }
+        Ok(cols)
+    }
+
+    fn variant_tag(&self, p: &Pattern) -> Result<u32, Diagnostic> {
+        let Pattern::Variant { name_span, .. } = p else {
+            unreachable!("only variant patterns have tags")
+        };
+        self.res
+            .variant_tags
+            .get(name_span)
+            .copied()
+            .ok_or_else(|| unsupported("this pattern", *name_span))
+    }
+
+    /// What a refutable pattern tests its column for.
+    fn test_of<'p>(&self, p: &'p Pattern) -> Result<Test<'p>, Diagnostic> {
+        Ok(match p {
+            Pattern::Variant { .. } => Test::Word(self.variant_tag(p)? as i64),
+            Pattern::Lit(Expr::Int(n, _)) => Test::Word(*n),
+            Pattern::Lit(Expr::Bool(b, _)) => Test::Word(*b as i64),
+            Pattern::Lit(e @ Expr::Str(..)) => Test::Str(e),
+            Pattern::Error { name_span, .. } => Test::Word(self.res.error_lits[name_span] as i64),
+            other => return Err(unsupported("this pattern", other.span())),
+        })
+    }
+
+    /// Emits the tree for `rows` over `cols`, or a jump to it when an
+    /// earlier path already did. Every subtree ends in a jump, so its
+    /// code can be entered from anywhere.
+    fn tree<'p>(
+        &mut self,
+        rows: Vec<Row<'p>>,
+        cols: Vec<Col>,
+        plan: &mut Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        if plan.over {
+            return Ok(());
+        }
+        let key: SubKey = (
+            rows.iter()
+                .map(|r| {
+                    let cells = r
+                        .pats
+                        .iter()
+                        .map(|p| p.map_or(0, |p| p as *const Pattern as usize));
+                    (cells.collect(), r.arm)
+                })
+                .collect(),
+            cols.iter().map(|c| c.off).collect(),
+        );
+        if let Some(&at) = plan.emitted.get(&key) {
+            self.insts.push(Inst::Jmp(at));
+            return Ok(());
+        }
+        if plan.emitted.len() >= MAX_SUBTREES {
+            plan.over = true;
+            return Ok(());
+        }
+        let at = self.fresh_label();
+        self.insts.push(Inst::Label(at));
+        plan.emitted.insert(key, at);
+        self.subtree(rows, cols, plan, scopes)
+    }
+
+    /// The first row decides: if it tests nothing it is a leaf,
+    /// otherwise the tree switches on the first column that row tests.
+    fn subtree<'p>(
+        &mut self,
+        mut rows: Vec<Row<'p>>,
+        mut cols: Vec<Col>,
+        plan: &mut Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        let Some(first) = rows.first() else {
+            // No row left: coverage makes this path dead.
+            self.insts.push(Inst::Jmp(plan.end));
+            return Ok(());
+        };
+        let Some(j) = first.pats.iter().position(Option::is_some) else {
+            return self.leaf(rows, cols, plan, scopes);
+        };
+        for row in &mut rows {
+            row.pats.swap(0, j);
+        }
+        cols.swap(0, j);
+        let col = cols[0].clone();
+        // Each distinct test in the column, in first-seen order, with
+        // a pattern that makes it.
+        let mut tests: Vec<(Test, &Pattern)> = Vec::new();
+        for p in rows.iter().filter_map(|r| r.pats[0]) {
+            let t = self.test_of(p)?;
+            if !tests.iter().any(|(seen, _)| *seen == t) {
+                tests.push((t, p));
+            }
+        }
+        let complete = match &col.ty {
+            Type::Enum(m, n) => self.res.enums[&(*m, n.clone())].variants.len() == tests.len(),
+            Type::Bool => tests.len() == 2,
+            _ => false,
+        };
+        let value = self.column_value(plan, &col, &tests[0].0);
+        // Dense int or error-code cases dispatch through one jump
+        // table (ADR 0046) instead of a compare per case.
+        if matches!(col.ty, Type::Int | Type::ErrCode)
+            && let Some((lo, len)) = dense(&tests)
+        {
+            let targets: Vec<Lbl> = tests.iter().map(|_| self.fresh_label()).collect();
+            let default = self.fresh_label();
+            let mut table = vec![default; len];
+            for ((test, _), target) in tests.iter().zip(&targets) {
+                if let Test::Word(n) = test {
+                    table[(*n - lo) as usize] = *target;
+                }
+            }
+            self.insts.push(Inst::Switch {
+                value,
+                lo,
+                table,
+                default,
+            });
+            for ((test, maker), target) in tests.iter().zip(&targets) {
+                self.insts.push(Inst::Label(*target));
+                self.case(&rows, &cols, test, maker, plan, scopes)?;
+            }
+            self.insts.push(Inst::Label(default));
+            return self.default_case(&rows, &cols, plan, scopes);
+        }
+        let last = tests.len() - 1;
+        for (i, (test, maker)) in tests.iter().enumerate() {
+            // A complete switch's last case needs no test.
+            let next = (i < last || !complete).then(|| self.fresh_label());
+            if let Some(next) = next {
+                let hit = self.test_hit(value, test)?;
+                self.insts.push(Inst::BrZero(hit, next));
+            }
+            self.case(&rows, &cols, test, maker, plan, scopes)?;
+            if let Some(next) = next {
+                self.insts.push(Inst::Label(next));
+            }
+        }
+        if !complete {
+            self.default_case(&rows, &cols, plan, scopes)?;
+        }
+        Ok(())
+    }
+
+    /// A column's value as `test` compares it: the scrutinee itself
+    /// when it is a word, else the word or string at the column.
+    fn column_value(&mut self, plan: &Plan, col: &Col, test: &Test) -> V {
+        if plan.word {
+            return plan.u;
+        }
+        match test {
+            Test::Word(_) => self.load_at(plan.u, col.off),
+            Test::Str(_) => self.ptr_at(plan.u, col.off),
+        }
+    }
+
+    /// 1 when `value` passes `test`, else 0.
+    fn test_hit(&mut self, value: V, test: &Test) -> Result<V, Diagnostic> {
+        Ok(match test {
+            // Immediates are i32; wider literals compare against a
+            // register.
+            Test::Word(imm) if i32::try_from(*imm).is_ok() => {
+                let hit = self.fresh(false);
+                self.insts.push(Inst::BinImm {
+                    op: BinOp::Eq,
+                    dst: hit,
+                    lhs: value,
+                    imm: *imm,
+                });
+                hit
+            }
+            Test::Word(n) => {
+                let wide = self.const_word(*n);
+                let hit = self.fresh(false);
+                self.insts.push(Inst::Bin {
+                    op: BinOp::Eq,
+                    float: false,
+                    dst: hit,
+                    lhs: value,
+                    rhs: wide,
+                });
+                hit
+            }
+            Test::Str(e) => {
+                let lit = self.expr(e)?;
+                self.str_content_eq(value, lit)
+            }
+        })
+    }
+
+    /// The subtree for one case of the first column: the rows that
+    /// agree with it, the column opened into the variant's payloads (a
+    /// literal opens nothing).
+    fn case<'p>(
+        &mut self,
+        rows: &[Row<'p>],
+        cols: &[Col],
+        test: &Test<'p>,
+        maker: &'p Pattern,
+        plan: &mut Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        let col = &cols[0];
+        let opened = match maker {
+            Pattern::Variant { .. } | Pattern::Error { .. } => {
+                self.payload_cols(maker, col.off, &col.ty)?
+            }
+            _ => Vec::new(),
+        };
+        let mut sub = Vec::new();
+        for row in rows {
+            let head: Vec<Option<&Pattern>> = match row.pats[0] {
+                None => vec![None; opened.len()],
+                Some(p) if self.test_of(p)? != *test => continue,
+                Some(Pattern::Variant { args, .. }) => args.iter().map(refutable).collect(),
+                // A bare error pattern names the code alone (ADR 0062).
+                Some(Pattern::Error { args, .. }) if args.is_empty() => vec![None; opened.len()],
+                Some(Pattern::Error { args, .. }) => args.iter().map(refutable).collect(),
+                Some(_) => Vec::new(),
+            };
+            sub.push(Row {
+                pats: head
+                    .into_iter()
+                    .chain(row.pats[1..].iter().copied())
+                    .collect(),
+                arm: row.arm,
+            });
+        }
+        let mut sub_cols = opened;
+        sub_cols.extend_from_slice(&cols[1..]);
+        self.tree(sub, sub_cols, plan, scopes)
+    }
+
+    /// The subtree for a value no case of the first column names: the
+    /// rows with nothing to test there.
+    fn default_case<'p>(
+        &mut self,
+        rows: &[Row<'p>],
+        cols: &[Col],
+        plan: &mut Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        let rest = rows
+            .iter()
+            .filter(|r| r.pats[0].is_none())
+            .map(|r| Row {
+                pats: r.pats[1..].to_vec(),
+                arm: r.arm,
+            })
+            .collect();
+        self.tree(rest, cols[1..].to_vec(), plan, scopes)
+    }
+
+    /// The first row tests nothing, so its arm is chosen: copy the
+    /// bindings out, then jump to the body once the guard holds. A
+    /// failed guard falls to the rows below.
+    fn leaf<'p>(
+        &mut self,
+        rows: Vec<Row<'p>>,
+        cols: Vec<Col>,
+        plan: &mut Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        let Some(fail) = self.enter_arm(rows[0].arm, plan, scopes)? else {
+            return Ok(());
+        };
+        self.insts.push(Inst::Label(fail));
+        self.tree(rows[1..].to_vec(), cols, plan, scopes)
+    }
+
+    /// Copies `arm`'s bindings out and jumps to its body once its guard
+    /// holds. Returns the label a failed guard jumps to, left for the
+    /// caller to place; an arm without a guard never fails.
+    fn enter_arm(
+        &mut self,
+        arm: usize,
+        plan: &Plan,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<Option<Lbl>, Diagnostic> {
+        for &(v, off, kind) in plan.binds.get(arm).into_iter().flatten() {
+            if kind == Kind::Word {
+                self.insts.push(Inst::LoadAt {
+                    dst: v,
+                    base: plan.u,
+                    off,
+                });
+            } else {
+                let src = self.ptr_at(plan.u, off);
+                self.insts.push(Inst::CopyW {
+                    dst: v,
+                    src,
+                    words: kind.words(),
+                });
+            }
+        }
+        let Some(guard) = plan.guards.get(arm).copied().flatten() else {
+            self.insts.push(Inst::Jmp(plan.bodies[arm]));
+            return Ok(None);
+        };
+        self.scopes.push(scopes[arm].clone());
+        let holds = self.expr(guard);
+        self.scopes.pop();
+        let fail = self.fresh_label();
+        self.insts.push(Inst::BrZero(holds?, fail));
+        self.insts.push(Inst::Jmp(plan.bodies[arm]));
+        Ok(Some(fail))
+    }
+
+    /// The fallback for a tree over its budget: each row in order
+    /// tests its own patterns and falls to the next on a miss. Code
+    /// grows with the patterns written, whatever their overlap.
+    fn rows_in_order<'p>(
+        &mut self,
+        rows: &[Row<'p>],
+        cols: &[Col],
+        plan: &Plan<'p>,
+        scopes: &[HashMap<String, Binding>],
+    ) -> Result<(), Diagnostic> {
+        for row in rows {
+            let next = self.fresh_label();
+            for (p, col) in row.pats.iter().zip(cols) {
+                if let Some(p) = p {
+                    self.pattern_test(p, col, plan, next)?;
+                }
+            }
+            if let Some(fail) = self.enter_arm(row.arm, plan, scopes)? {
+                self.insts.push(Inst::Label(fail));
+            }
+            self.insts.push(Inst::Label(next));
+        }
+        // No row left: coverage makes this path dead.
+        self.insts.push(Inst::Jmp(plan.end));
+        Ok(())
+    }
+
+    /// Jumps to `miss` unless the value at `col` matches `p`, payloads
+    /// included.
+    fn pattern_test(
+        &mut self,
+        p: &Pattern,
+        col: &Col,
+        plan: &Plan,
+        miss: Lbl,
+    ) -> Result<(), Diagnostic> {
+        let test = self.test_of(p)?;
+        let value = self.column_value(plan, col, &test);
+        let hit = self.test_hit(value, &test)?;
+        self.insts.push(Inst::BrZero(hit, miss));
+        if let Pattern::Variant { args, .. } | Pattern::Error { args, .. } = p {
+            for (arg, col) in args.iter().zip(self.payload_cols(p, col.off, &col.ty)?) {
+                if let Some(arg) = refutable(arg) {
+                    self.pattern_test(arg, &col, plan, miss)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One arm's value into the shared result slot.
+    fn settle(&mut self, out: V, v: V, kind: Kind) {
+        if kind == Kind::Word {
+            self.insts.push(Inst::Copy(out, v));
+        } else {
+            self.insts.push(Inst::CopyW {
+                dst: out,
+                src: v,
+                words: kind.words(),
+            });
+        }
+    }
+}
+
+/// The fewest cases worth a jump table, and how sparse it may be:
+/// at least one case per `JUMP_TABLE_SPREAD` slots.
+const JUMP_TABLE_MIN: usize = 4;
+const JUMP_TABLE_SPREAD: usize = 2;
+
+/// The table's base and length when the word tests are dense enough
+/// for a jump table (ADR 0046).
+fn dense(tests: &[(Test, &Pattern)]) -> Option<(i64, usize)> {
+    if tests.len() < JUMP_TABLE_MIN {
+        return None;
+    }
+    let words = tests.iter().filter_map(|(t, _)| match t {
+        Test::Word(n) => Some(*n as i128),
+        Test::Str(_) => None,
+    });
+    let lo = words.clone().min()?;
+    let len = usize::try_from(words.max()? - lo + 1).ok()?;
+    (len <= tests.len() * JUMP_TABLE_SPREAD).then_some((lo as i64, len))
+}
+
+/// A sub-pattern as a matrix cell: bindings test nothing.
+fn refutable(p: &Pattern) -> Option<&Pattern> {
+    match p {
+        Pattern::Bind(..) => None,
+        other => Some(other),
+    }
+}
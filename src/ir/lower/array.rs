@@ -0,0 +1,396 @@
+//! The array library (ADR 0055). Everything that moves elements runs
+//! in the runtime at the array's compile-time stride (ADR 0023), the
+//! `ys_push_n` contract: an inserted element arrives behind a pointer to
+//! a private copy, so growing the buffer cannot pull it out from under
+//! the call. What only reads or shrinks stays inline: `pop` and
+//! `removeAt` read through the bounds-checked `Index`, `clear` stores a
+//! zero length, and `indexOf`/`contains` walk the elements with
+//! `value_eq` — the comparator `==` uses.
+
+use super::{Lowerer, TAG_PRESENT};
+use crate::ast::{BinOp, Expr};
+use crate::codegen::{
+    RT_ALLOC, RT_ARR_INSERT, RT_ARR_REMOVE, RT_ARR_REVERSE, RT_ARR_SORT, RT_PUSH, RT_PUSH_N,
+    SORT_BY_FLOAT, SORT_BY_STR,
+};
+use crate::diagnostic::Diagnostic;
+use crate::ir::layout::{Elem, FUEL, Kind, kind_of};
+use crate::ir::{Inst, V, unsupported};
+use crate::span::Span;
+use crate::syntax;
+use crate::types::IntTy;
+use crate::types::Type;
+
+impl Lowerer<'_> {
+    /// The array library builtins; the checker has fixed the arity and
+    /// that the first argument is an array.
+    pub(super) fn array_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let elem = self.elem_ty(&args[0])?;
+        let el = self.elem_layout(&elem, span)?;
+        match name {
+            syntax::BUILTIN_POP => self.pop(&args[0], &elem, el, span),
+            syntax::BUILTIN_REMOVE_AT => {
+                let arr = self.expr(&args[0])?;
+                let idx = self.expr(&args[1])?;
+                let x = self.element(arr, idx, &elem, el, span);
+                // The element leaves the buffer before the gap closes.
+                let out = match el {
+                    Elem::Agg(words) => self.snapshot(x, words),
+                    _ => x,
+                };
+                let stride = self.const_word(el.stride());
+                self.call_rt(RT_ARR_REMOVE, vec![arr, idx, stride]);
+                Ok(out)
+            }
+            syntax::BUILTIN_INSERT => {
+                let arr = self.expr(&args[0])?;
+                let idx = self.expr(&args[1])?;
+                let val = self.expr_into(&args[2], &elem)?;
+                let src = self.elem_bytes(val, el);
+                let stride = self.const_word(el.stride());
+                let loc_lbl = self.loc_of(span);
+                let loc = self.lea_sym(loc_lbl);
+                Ok(self.call_rt(RT_ARR_INSERT, vec![arr, idx, src, stride, loc]))
+            }
+            syntax::BUILTIN_SLICE => {
+                let arr = self.expr(&args[0])?;
+                let lo = self.expr(&args[1])?;
+                let hi = self.expr(&args[2])?;
+                let loc_lbl = self.loc_of(span);
+                let loc = self.lea_sym(loc_lbl);
+                Ok(self.array_slice(arr, lo, hi, el, loc))
+            }
+            syntax::BUILTIN_CLEAR => {
+                let arr = self.expr(&args[0])?;
+                let zero = self.const_word(0);
+                self.insts.push(Inst::StoreAt {
+                    base: arr,
+                    off: 0,
+                    val: zero,
+                });
+                Ok(zero)
+            }
+            syntax::BUILTIN_REVERSE => {
+                let arr = self.expr(&args[0])?;
+                let stride = self.const_word(el.stride());
+                Ok(self.call_rt(RT_ARR_REVERSE, vec![arr, stride]))
+            }
+            syntax::BUILTIN_SORT => self.sort(args, &elem, el, span),
+            _ => self.search(name, args, &elem, el, span),
+        }
+    }
+
+    /// `pop(xs)`: the last element, copied out before the length drops,
+    /// or null on an empty array — shaped like a map miss (ADR 0042).
+    fn pop(&mut self, array: &Expr, elem: &Type, el: Elem, span: Span) -> Result<V, Diagnostic> {
+        let read = self
+            .ty(&span)
+            .cloned()
+            .ok_or_else(|| unsupported("this pop", span))?;
+        let rk = kind_of(&read, self.res, FUEL)
+            .ok_or_else(|| unsupported("arrays of this element type", span))?;
+        let arr = self.expr(array)?;
+        let n = self.fresh(false);
+        self.insts.push(Inst::Len(n, arr));
+        let end = self.fresh_label();
+        let out = if rk == Kind::Word {
+            self.const_word(0)
+        } else {
+            self.null_optional(rk.words())
+        };
+        self.insts.push(Inst::BrZero(n, end));
+        let last = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Sub,
+            dst: last,
+            lhs: n,
+            imm: 1,
+        });
+        let x = self.element(arr, last, elem, el, span);
+        self.insts.push(Inst::StoreAt {
+            base: arr,
+            off: 0,
+            val: last,
+        });
+        if rk == Kind::Word {
+            self.insts.push(Inst::Copy(out, x));
+        } else if read == *elem {
+            self.insts.push(Inst::CopyW {
+                dst: out,
+                src: x,
+                words: rk.words(),
+            });
+        } else {
+            let tag = self.const_word(TAG_PRESENT);
+            self.insts.push(Inst::StoreAt {
+                base: out,
+                off: 0,
+                val: tag,
+            });
+            match el {
+                Elem::Agg(words) => {
+                    let payload = self.lea_at(out, 8);
+                    self.insts.push(Inst::CopyW {
+                        dst: payload,
+                        src: x,
+                        words,
+                    });
+                }
+                _ => self.insts.push(Inst::StoreAt {
+                    base: out,
+                    off: 8,
+                    val: x,
+                }),
+            }
+        }
+        self.insts.push(Inst::Label(end));
+        Ok(out)
+    }
+
+    /// `sort(xs)` orders the elements as their own keys; `sort(xs, key)`
+    /// first calls `key` once per element, in order, into a keys array.
+    /// The runtime then reorders stably by the keys.
+    fn sort(&mut self, args: &[Expr], elem: &Type, el: Elem, span: Span) -> Result<V, Diagnostic> {
+        let arr = self.expr(&args[0])?;
+        let (keys, key_ty) = match args.get(1) {
+            // A sized element is its own key, widened to a word.
+            None if matches!(elem, Type::Sized(_)) => (
+                self.sort_keys(arr, None, elem, elem, el, span)?,
+                elem.clone(),
+            ),
+            None => (arr, elem.clone()),
+            Some(key) => {
+                let Some(Type::Fn(_, ret)) = self.ty(&key.span()).cloned() else {
+                    return Err(unsupported("this sort key", key.span()));
+                };
+                let f = self.expr(key)?;
+                (self.sort_keys(arr, Some(f), &ret, elem, el, span)?, *ret)
+            }
+        };
+        let order = match key_ty {
+            Type::Float => SORT_BY_FLOAT,
+            Type::Str => SORT_BY_STR,
+            _ => 0,
+        };
+        let order = self.const_word(order);
+        let stride = self.const_word(el.stride());
+        Ok(self.call_rt(RT_ARR_SORT, vec![arr, keys, order, stride]))
+    }
+
+    /// The keys array: `key(xs[i])` for each element present when the
+    /// sort began — a key function that shrinks the array hits the
+    /// bounds check, like the oracle's — or with no key function, each
+    /// sized element as a word. `ys_sort` compares words signed, so a
+    /// `u64` key has its top bit flipped to order as unsigned.
+    fn sort_keys(
+        &mut self,
+        arr: V,
+        f: Option<V>,
+        key_ty: &Type,
+        elem: &Type,
+        el: Elem,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let c24 = self.const_word(24);
+        let keys = self.call_rt(RT_ALLOC, vec![c24]);
+        let c8 = self.const_word(8);
+        let buf = self.call_rt(RT_ALLOC, vec![c8]);
+        self.insts.push(Inst::StoreHdr {
+            hdr: keys,
+            buf,
+            len: 0,
+        });
+        let n = self.fresh(false);
+        self.insts.push(Inst::Len(n, arr));
+        let i = self.const_word(0);
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Lt,
+            float: false,
+            dst: cond,
+            lhs: i,
+            rhs: n,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let x = self.element(arr, i, elem, el, span);
+        let (k, sret) = match f {
+            None => (x, None),
+            Some(f) => {
+                // Call-argument discipline: a multi-word element snapshots.
+                let arg = match el {
+                    Elem::Agg(words) => self.snapshot(x, words),
+                    _ => x,
+                };
+                let sret = (*key_ty == Type::Str).then(|| {
+                    let t = self.fresh(false);
+                    self.insts.push(Inst::Temp { dst: t, words: 2 });
+                    t
+                });
+                let k = self.fresh(*key_ty == Type::Float);
+                self.insts.push(Inst::CallInd {
+                    dst: k,
+                    callee: f,
+                    args: vec![arg],
+                    sret,
+                });
+                (k, sret)
+            }
+        };
+        let k = if *key_ty == Type::Sized(IntTy::U64) {
+            let top = self.const_word(i64::MIN);
+            let flipped = self.fresh(false);
+            self.insts.push(Inst::Bin {
+                op: BinOp::BitXor,
+                float: false,
+                dst: flipped,
+                lhs: k,
+                rhs: top,
+            });
+            flipped
+        } else {
+            k
+        };
+        match sret {
+            Some(desc) => {
+                let stride = self.const_word(16);
+                self.call_rt(RT_PUSH_N, vec![keys, desc, stride]);
+            }
+            None => {
+                self.call_rt(RT_PUSH, vec![keys, k]);
+            }
+        }
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(keys)
+    }
+
+    /// `indexOf(xs, v)` / `contains(xs, v)`: the first position whose
+    /// element `==` the value, walking the live length.
+    fn search(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        elem: &Type,
+        el: Elem,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let arr = self.expr(&args[0])?;
+        let val = self.expr_into(&args[1], elem)?;
+        let want = self.elem_bytes(val, el);
+        let i = self.const_word(0);
+        let found = self.const_word(0);
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let n = self.fresh(false);
+        self.insts.push(Inst::Len(n, arr));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Lt,
+            float: false,
+            dst: cond,
+            lhs: i,
+            rhs: n,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let x = self.element(arr, i, elem, el, span);
+        let at = match el {
+            Elem::Agg(_) => x,
+            _ => self.elem_bytes(x, el),
+        };
+        let same = self.value_eq(elem, at, 0, want, 0, span)?;
+        let next = self.fresh_label();
+        self.insts.push(Inst::BrZero(same, next));
+        let yes = self.const_word(1);
+        self.insts.push(Inst::Copy(found, yes));
+        self.insts.push(Inst::Jmp(end));
+        self.insts.push(Inst::Label(next));
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        if name == syntax::BUILTIN_CONTAINS {
+            return Ok(found);
+        }
+        let out = self.null_optional(2);
+        let miss = self.fresh_label();
+        self.insts.push(Inst::BrZero(found, miss));
+        let tag = self.const_word(TAG_PRESENT);
+        self.insts.push(Inst::StoreAt {
+            base: out,
+            off: 0,
+            val: tag,
+        });
+        self.insts.push(Inst::StoreAt {
+            base: out,
+            off: 8,
+            val: i,
+        });
+        self.insts.push(Inst::Label(miss));
+        Ok(out)
+    }
+
+    /// `xs[i]` behind the bounds check: a word (packed elements
+    /// widened) or an interior pointer for aggregates.
+    fn element(&mut self, arr: V, idx: V, elem: &Type, el: Elem, span: Span) -> V {
+        let loc = self.loc_of(span);
+        let dst = self.fresh(*elem == Type::Float);
+        self.insts.push(Inst::Index {
+            dst,
+            arr,
+            idx,
+            loc,
+            elem: el,
+        });
+        dst
+    }
+
+    /// A pointer to a private copy of an element's bytes — what the
+    /// runtime memcpys in, and what `value_eq` compares through. A
+    /// packed element's low bytes are its word's first bytes.
+    fn elem_bytes(&mut self, val: V, el: Elem) -> V {
+        if let Elem::Agg(words) = el {
+            return self.snapshot(val, words);
+        }
+        let slot = self.fresh(false);
+        self.insts.push(Inst::Temp {
+            dst: slot,
+            words: 1,
+        });
+        self.insts.push(Inst::StoreAt {
+            base: slot,
+            off: 0,
+            val,
+        });
+        slot
+    }
+
+    fn call_rt(&mut self, sym: &'static str, args: Vec<V>) -> V {
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym,
+            args,
+            varargs: false,
+        });
+        dst
+    }
+}
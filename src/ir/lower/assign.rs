@@ -0,0 +1,447 @@
+//! Compound assignment (ADR 0050). `place op= value` lowers as one
+//! read-modify-write: the value first (as for `=`), then the place's
+//! subexpressions — bases and indexes — exactly once, resolved to a
+//! `Place`; the old value is read from it and the result written back
+//! to it. `??=` reads first and runs its value only when the place
+//! holds null.
+
+use super::{Lowerer, reducible};
+use crate::ast::{BinOp, Expr};
+use crate::codegen::global_label;
+use crate::diagnostic::Diagnostic;
+use crate::ir::layout::{Elem, FUEL, Kind, kind_of, leg_offset};
+use crate::ir::{Inst, V, unsupported};
+use crate::span::Span;
+use crate::types::Type;
+
+/// An assignment target with its subexpressions evaluated.
+enum Place {
+    /// A frame local — its binding, read and written by name.
+    Local(String),
+    /// Storage `off` bytes into a base address; `slot` is the declared
+    /// type stored there.
+    At { base: Base, off: i64, slot: Type },
+    /// An array element, bounds-checked on each access.
+    Elem {
+        arr: V,
+        idx: V,
+        loc: String,
+        elem: Type,
+    },
+}
+
+/// Where a field chain's storage begins.
+enum Base {
+    /// A stable address: a frame slot, a module var, a heap object.
+    Ptr(V),
+    /// A value element of an array. Re-indexed on each access: a `??=`
+    /// value may push and move the buffer between the read and the
+    /// write.
+    Elem {
+        arr: V,
+        idx: V,
+        loc: String,
+        words: usize,
+    },
+    /// A slot of a fixed array `off` bytes into `outer` (ADR 0056),
+    /// bounds-checked on each access like `Elem`.
+    Slot {
+        outer: Box<Base>,
+        off: i64,
+        idx: V,
+        len: usize,
+        kind: Kind,
+        loc: String,
+    },
+}
+
+impl Lowerer<'_> {
+    pub(super) fn compound_assign(
+        &mut self,
+        target: &Expr,
+        op: BinOp,
+        value: &Expr,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        if op == BinOp::Coalesce {
+            return self.coalesce_assign(target, value, span);
+        }
+        let read_ty = self
+            .ty(&target.span())
+            .cloned()
+            .ok_or_else(|| unsupported("this assignment target", span))?;
+        let float = read_ty == Type::Float;
+        // A literal operand has no effects to order; it stays an
+        // immediate.
+        let literal = match value {
+            Expr::Int(n, _)
+                if !float && !matches!(read_ty, Type::Sized(_)) && reducible(op, *n) =>
+            {
+                Some(*n)
+            }
+            _ => None,
+        };
+        let r = match literal {
+            Some(_) => None,
+            None => {
+                let r = self.expr(value)?;
+                // Snapshot at evaluation: the place's index expressions
+                // may push and move the buffer this pointer aims into.
+                Some(match self.kind(value, span)? {
+                    Kind::Word => r,
+                    k => self.snapshot(r, k.words()),
+                })
+            }
+        };
+        let place = self.place(target)?;
+        let old = self.read_place(&place, target)?;
+        let new = match (literal, r) {
+            (Some(n), _) => self.scalar_imm(op, old, n),
+            (None, Some(r)) if read_ty == Type::Str => self.concat_strs(old, r),
+            (None, Some(r)) => match read_ty {
+                Type::Sized(t) => self.sized_op(op, t, old, r, span),
+                _ => self.scalar_op(op, float, old, r, span),
+            },
+            (None, None) => unreachable!("a non-literal value is evaluated"),
+        };
+        self.write_place(&place, new, &read_ty, span)
+    }
+
+    /// `a[i] = value` on a fixed array (ADR 0056): the value, then the
+    /// place — the slot's storage is found like a field chain's.
+    pub(super) fn slot_assign(
+        &mut self,
+        target: &Expr,
+        value: &Expr,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let elem = self
+            .ty(&target.span())
+            .cloned()
+            .ok_or_else(|| unsupported("this assignment target", span))?;
+        let val = self.expr_into(value, &elem)?;
+        // Snapshot at evaluation, as for compound assignment.
+        let val = match self.kind(value, span)? {
+            Kind::Word => val,
+            _ => {
+                let words = kind_of(&elem, self.res, FUEL)
+                    .ok_or_else(|| unsupported("fixed arrays of this element type", span))?
+                    .words();
+                self.snapshot(val, words)
+            }
+        };
+        let place = self.place(target)?;
+        self.write_place(&place, val, &elem, span)
+    }
+
+    /// `place ??= value`: the null test on the place as read, then the
+    /// value and the write only on the null path.
+    fn coalesce_assign(
+        &mut self,
+        target: &Expr,
+        value: &Expr,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let read_ty = self
+            .ty(&target.span())
+            .cloned()
+            .ok_or_else(|| unsupported("this assignment target", span))?;
+        let place = self.place(target)?;
+        let old = self.read_place(&place, target)?;
+        // A nullable handle is null as 0; a value optional by its tag.
+        let present = match self.opt_inner_of(&read_ty) {
+            Some(_) => self.load_at(old, 0),
+            None => old,
+        };
+        let isnull = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Eq,
+            dst: isnull,
+            lhs: present,
+            imm: 0,
+        });
+        let end = self.fresh_label();
+        self.insts.push(Inst::BrZero(isnull, end));
+        let v = self.expr(value)?;
+        let value_ty = self.ty(&value.span()).cloned();
+        let v = match value_ty {
+            // A bare `null` refills nothing; the place keeps its null.
+            None | Some(Type::Null) => None,
+            Some(ty) => Some((v, ty)),
+        };
+        if let Some((v, ty)) = v {
+            self.write_place(&place, v, &ty, span)?;
+        }
+        self.insts.push(Inst::Label(end));
+        Ok(())
+    }
+
+    /// Evaluates the target's bases and indexes, once.
+    fn place(&mut self, target: &Expr) -> Result<Place, Diagnostic> {
+        match target {
+            Expr::Ident(_, span) if self.res.global_refs.contains_key(span) => {
+                let key = &self.res.global_refs[span];
+                let slot = self.res.globals[key].clone();
+                let base = self.lea_sym(global_label(key.0, &key.1));
+                Ok(Place::At {
+                    base: Base::Ptr(base),
+                    off: 0,
+                    slot,
+                })
+            }
+            Expr::Ident(name, _) => Ok(Place::Local(name.clone())),
+            Expr::Index { base, index, span } if self.is_fixed(base) => {
+                let slot = self.fixed_of(base)?.0;
+                Ok(Place::At {
+                    base: self.fixed_slot(base, index, *span)?,
+                    off: 0,
+                    slot,
+                })
+            }
+            Expr::Index { base, index, span } => {
+                let loc = self.loc_of(*span);
+                let elem = self.elem_ty(base)?;
+                let arr = self.expr(base)?;
+                let idx = self.expr(index)?;
+                Ok(Place::Elem {
+                    arr,
+                    idx,
+                    loc,
+                    elem,
+                })
+            }
+            Expr::Field { base, span, .. } => {
+                let (b, off) = self.field_base(base)?;
+                let (slot, field_off) = self.field_slot(*span)?;
+                Ok(Place::At {
+                    base: b,
+                    off: off + field_off,
+                    slot,
+                })
+            }
+            other => Err(unsupported("this assignment target", other.span())),
+        }
+    }
+
+    /// The storage a field chain's base names: a handle's object, or
+    /// the inline storage of a value struct or tuple.
+    fn field_base(&mut self, e: &Expr) -> Result<(Base, i64), Diagnostic> {
+        // A handle — a refstruct, or a narrowed `T?` of one — is the
+        // object's address; so is a local or module var read, which
+        // yields its storage.
+        if self.kind(e, e.span())? == Kind::Word || matches!(e, Expr::Ident(..)) {
+            return Ok((Base::Ptr(self.expr(e)?), 0));
+        }
+        match e {
+            Expr::Index { base, index, span } if self.is_fixed(base) => {
+                Ok((self.fixed_slot(base, index, *span)?, 0))
+            }
+            Expr::Index { base, index, span } => {
+                let loc = self.loc_of(*span);
+                let words = self.kind(e, *span)?.words();
+                let arr = self.expr(base)?;
+                let idx = self.expr(index)?;
+                Ok((
+                    Base::Elem {
+                        arr,
+                        idx,
+                        loc,
+                        words,
+                    },
+                    0,
+                ))
+            }
+            Expr::Field {
+                base,
+                span,
+                optional: false,
+                ..
+            } => {
+                let (b, off) = self.field_base(base)?;
+                let (slot, field_off) = self.field_slot(*span)?;
+                // A narrowed union field holds its payload at +8.
+                let narrowed = match (&slot, self.ty(span)) {
+                    (Type::ErrUnion(_), Some(Type::ErrUnion(_))) => false,
+                    (Type::ErrUnion(_), _) => true,
+                    (slot, Some(t)) => {
+                        self.opt_inner_of(slot).is_some() && !matches!(t, Type::Optional(_))
+                    }
+                    _ => false,
+                };
+                Ok((b, off + field_off + if narrowed { 8 } else { 0 }))
+            }
+            other => Ok((Base::Ptr(self.expr(other)?), 0)),
+        }
+    }
+
+    /// The slot `base[index]` of a fixed array, its storage located
+    /// the way a field chain's is.
+    fn fixed_slot(&mut self, base: &Expr, index: &Expr, span: Span) -> Result<Base, Diagnostic> {
+        let (_, kind, len) = self.fixed_of(base)?;
+        let loc = self.loc_of(span);
+        let (outer, off) = self.field_base(base)?;
+        let idx = self.expr(index)?;
+        Ok(Base::Slot {
+            outer: Box::new(outer),
+            off,
+            idx,
+            len,
+            kind,
+            loc,
+        })
+    }
+
+    /// A field access's declared type and offset in its struct.
+    fn field_slot(&self, span: Span) -> Result<(Type, i64), Diagnostic> {
+        let slot = self
+            .res
+            .field_slots
+            .get(&span)
+            .ok_or_else(|| unsupported("this field target", span))?;
+        let off = leg_offset(&slot.base, slot.index, self.res)
+            .ok_or_else(|| unsupported("this struct layout", span))?;
+        Ok((slot.ty.clone(), off))
+    }
+
+    fn base_addr(&mut self, base: &Base) -> V {
+        match base {
+            Base::Ptr(p) => *p,
+            Base::Elem {
+                arr,
+                idx,
+                loc,
+                words,
+            } => {
+                let dst = self.fresh(false);
+                self.insts.push(Inst::Index {
+                    dst,
+                    arr: *arr,
+                    idx: *idx,
+                    loc: loc.clone(),
+                    elem: Elem::Agg(*words),
+                });
+                dst
+            }
+            Base::Slot {
+                outer,
+                off,
+                idx,
+                len,
+                kind,
+                loc,
+            } => {
+                let b = self.base_addr(outer);
+                let b = if *off == 0 { b } else { self.lea_at(b, *off) };
+                self.slot_at(b, *idx, *len, *kind, loc.clone())
+            }
+        }
+    }
+
+    /// The place's current value, as the checker typed `target`'s read.
+    fn read_place(&mut self, place: &Place, target: &Expr) -> Result<V, Diagnostic> {
+        match place {
+            Place::Local(_) => self.expr(target),
+            Place::At { base, off, slot } => {
+                let b = self.base_addr(base);
+                self.field_read(b, &slot.clone(), *off, target.span())
+            }
+            Place::Elem {
+                arr,
+                idx,
+                loc,
+                elem,
+            } => {
+                let el = self.elem_layout(elem, target.span())?;
+                let dst = self.fresh(*elem == Type::Float);
+                self.insts.push(Inst::Index {
+                    dst,
+                    arr: *arr,
+                    idx: *idx,
+                    loc: loc.clone(),
+                    elem: el,
+                });
+                Ok(dst)
+            }
+        }
+    }
+
+    /// Stores `v`, of type `ty`, into the place — wrapped for a union
+    /// slot the way `expr_into` wraps.
+    fn write_place(
+        &mut self,
+        place: &Place,
+        v: V,
+        ty: &Type,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        match place {
+            Place::Local(name) => {
+                let b = self
+                    .lookup(name)
+                    .ok_or_else(|| unsupported("this assignment target", span))?;
+                let slot = b.union_slot().unwrap_or_else(|| ty.clone());
+                let v = self.shape(v, Some(ty), &slot, span)?;
+                let kind = kind_of(&slot, self.res, FUEL)
+                    .ok_or_else(|| unsupported("this assignment target", span))?;
+                self.insts.push(if kind == Kind::Word {
+                    Inst::Copy(b.v, v)
+                } else {
+                    Inst::CopyW {
+                        dst: b.v,
+                        src: v,
+                        words: kind.words(),
+                    }
+                });
+            }
+            Place::At { base, off, slot } => {
+                let v = self.shape(v, Some(ty), slot, span)?;
+                let kind = kind_of(slot, self.res, FUEL)
+                    .ok_or_else(|| unsupported("fields of this type", span))?;
+                let b = self.base_addr(base);
+                if kind == Kind::Word {
+                    self.insts.push(Inst::StoreAt {
+                        base: b,
+                        off: *off,
+                        val: v,
+                    });
+                } else {
+                    let p = self.lea_at(b, *off);
+                    self.insts.push(Inst::CopyW {
+                        dst: p,
+                        src: v,
+                        words: kind.words(),
+                    });
+                }
+            }
+            Place::Elem {
+                arr,
+                idx,
+                loc,
+                elem,
+            } => {
+                let val = self.shape(v, Some(ty), elem, span)?;
+                let el = self.elem_layout(elem, span)?;
+                self.insts.push(Inst::IndexSet {
+                    arr: *arr,
+                    idx: *idx,
+                    val,
+                    loc: loc.clone(),
+                    elem: el,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl super::Binding {
+    /// The declared union type of a `T?`/`T!` local, whose storage is
+    /// the tagged union whatever its narrowing.
+    pub(super) fn union_slot(&self) -> Option<Type> {
+        match (&self.opt_inner, &self.err_inner) {
+            (Some(inner), _) => Some(Type::Optional(Box::new(inner.clone()))),
+            (_, Some(inner)) => Some(Type::ErrUnion(Box::new(inner.clone()))),
+            _ => None,
+        }
+    }
+}
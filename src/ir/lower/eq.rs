@@ -10,7 +10,7 @@ use super::Lowerer;
 use crate::ast::{BinOp, Expr};
 use crate::codegen::RT_MEMCMP;
 use crate::diagnostic::Diagnostic;
-use crate::ir::layout::{FUEL, Kind, kind_of, offset_of};
+use crate::ir::layout::{FUEL, Kind, kind_of, legs};
 use crate::ir::{Inst, V, unsupported};
 use crate::span::Span;
 use crate::types::Type;
@@ -173,7 +173,7 @@ impl Lowerer<'_> {
     /// layout allows it (padding-free words, canonical nulls);
     /// content, IEEE, and per-field legs where it doesn't (ADR 0026).
     /// Value structs cannot be recursive, so the walk is finite.
-    fn value_eq(
+    pub(super) fn value_eq(
         &mut self,
         t: &Type,
         a: V,
@@ -182,8 +182,12 @@ impl Lowerer<'_> {
         boff: i64,
         span: Span,
     ) -> Result<V, Diagnostic> {
-        let kind =
-            kind_of(t, self.res, FUEL).ok_or_else(|| unsupported("values of this type", span))?;
+        // Errors compare by code alone, payloads aside (ADR 0062).
+        let kind = if *t == Type::ErrCode {
+            Kind::Word
+        } else {
+            kind_of(t, self.res, FUEL).ok_or_else(|| unsupported("values of this type", span))?
+        };
         match kind {
             // Scalars by value, refstructs/arrays by handle identity.
             Kind::Word => {
@@ -245,17 +249,7 @@ impl Lowerer<'_> {
             Kind::Struct {
                 no_memcmp: true, ..
             } => {
-                let Type::Struct(m, n) = t else {
-                    unreachable!("struct kind from a struct type")
-                };
-                let res = self.res;
-                let def = &res.structs[&(*m, n.clone())];
-                let legs: Vec<(i64, Type)> = def
-                    .fields
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (_, ft))| Some((offset_of(def, i, res)?, ft.clone())))
-                    .collect::<Option<_>>()
+                let legs = legs(t, self.res)
                     .ok_or_else(|| unsupported("values of this type", span))?;
                 let v = self.const_word(1);
                 let end = self.fresh_label();
@@ -346,7 +340,7 @@ impl Lowerer<'_> {
     }
 
     /// `base + off` as a pointer; offset 0 is the pointer itself.
-    fn ptr_at(&mut self, base: V, off: i64) -> V {
+    pub(super) fn ptr_at(&mut self, base: V, off: i64) -> V {
         if off == 0 {
             base
         } else {
@@ -355,7 +349,7 @@ impl Lowerer<'_> {
     }
 
     /// Content equality of two string descriptors: length, then bytes.
-    fn str_content_eq(&mut self, a: V, b: V) -> V {
+    pub(super) fn str_content_eq(&mut self, a: V, b: V) -> V {
         let la = self.load_at(a, 8);
         let lb = self.load_at(b, 8);
         let result = self.fresh(false);
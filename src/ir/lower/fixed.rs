@@ -0,0 +1,232 @@
+//! Fixed arrays (ADR 0056). A `T[N]` is a value aggregate — N slots
+//! inline at the element's stride, laid out like a tuple of N equal
+//! legs — so it lives wherever a value struct lives and copies at the
+//! same points. Only indexing is new: `SlotAt` checks the index against
+//! the type's length and yields the slot's interior pointer; reads go
+//! through it like a field read, writes through `assign.rs`'s places.
+
+use super::{Binding, Lowerer};
+use crate::ast::{BinOp, Expr, Stmt};
+use crate::diagnostic::Diagnostic;
+use crate::ir::layout::{FUEL, Kind, kind_of};
+use crate::ir::{Inst, V, unsupported};
+use crate::span::Span;
+use crate::types::Type;
+use std::collections::HashMap;
+
+impl Lowerer<'_> {
+    pub(super) fn is_fixed(&self, e: &Expr) -> bool {
+        matches!(self.ty(&e.span()), Some(Type::Fixed(..)))
+    }
+
+    /// The element type, element kind, and length of a fixed array
+    /// expression.
+    pub(super) fn fixed_of(&self, e: &Expr) -> Result<(Type, Kind, usize), Diagnostic> {
+        let Some(Type::Fixed(elem, len)) = self.ty(&e.span()) else {
+            return Err(unsupported("this fixed array", e.span()));
+        };
+        let kind = kind_of(elem, self.res, FUEL)
+            .ok_or_else(|| unsupported("fixed arrays of this element type", e.span()))?;
+        Ok(((**elem).clone(), kind, *len))
+    }
+
+    /// Checks `idx` against the length and yields the slot's address
+    /// in the storage at `base`.
+    pub(super) fn slot_at(&mut self, base: V, idx: V, len: usize, kind: Kind, loc: String) -> V {
+        let dst = self.fresh(false);
+        self.insts.push(Inst::SlotAt {
+            dst,
+            base,
+            idx,
+            len,
+            stride: 8 * kind.words() as i64,
+            loc,
+        });
+        dst
+    }
+
+    /// Stores an evaluated element into the slot at byte `off`.
+    fn store_slot(&mut self, base: V, off: i64, val: V, kind: Kind) {
+        if kind == Kind::Word {
+            self.insts.push(Inst::StoreAt { base, off, val });
+        } else {
+            let p = self.lea_at(base, off);
+            self.insts.push(Inst::CopyW {
+                dst: p,
+                src: val,
+                words: kind.words(),
+            });
+        }
+    }
+
+    /// `[a, b, c]` at a `T[3]` slot: a fresh temp, each element stored
+    /// as it evaluates — the tuple literal's shape.
+    pub(super) fn fixed_lit(&mut self, lit: &Expr, elements: &[Expr]) -> Result<V, Diagnostic> {
+        let (elem, kind, len) = self.fixed_of(lit)?;
+        let base = self.fresh(false);
+        self.insts.push(Inst::Temp {
+            dst: base,
+            words: len * kind.words(),
+        });
+        let stride = 8 * kind.words() as i64;
+        for (slot, element) in elements.iter().enumerate() {
+            let val = self.expr_into(element, &elem)?;
+            self.store_slot(base, stride * slot as i64, val, kind);
+        }
+        Ok(base)
+    }
+
+    /// `[v; N]`: the value runs once, then a loop copies it into every
+    /// slot.
+    pub(super) fn fixed_repeat(&mut self, lit: &Expr, value: &Expr) -> Result<V, Diagnostic> {
+        let (elem, kind, len) = self.fixed_of(lit)?;
+        let val = self.expr_into(value, &elem)?;
+        let base = self.fresh(false);
+        self.insts.push(Inst::Temp {
+            dst: base,
+            words: len * kind.words(),
+        });
+        let i = self.const_word(0);
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Lt,
+            dst: cond,
+            lhs: i,
+            imm: len as i64,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let at = self.step_addr(base, i, kind);
+        self.store_slot(at, 0, val, kind);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(base)
+    }
+
+    /// `base + i*stride` for a loop that has already proven `i` in
+    /// bounds.
+    fn step_addr(&mut self, base: V, i: V, kind: Kind) -> V {
+        let off = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Mul,
+            dst: off,
+            lhs: i,
+            imm: 8 * kind.words() as i64,
+        });
+        let at = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Add,
+            float: false,
+            dst: at,
+            lhs: base,
+            rhs: off,
+        });
+        at
+    }
+
+    /// `a[i]`: the slot read like a field — a word loads, an aggregate
+    /// is its interior pointer; consumers copy.
+    pub(super) fn fixed_index(
+        &mut self,
+        base: &Expr,
+        index: &Expr,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let (elem, kind, len) = self.fixed_of(base)?;
+        let loc = self.loc_of(span);
+        let b = self.expr(base)?;
+        let idx = self.expr(index)?;
+        let p = self.slot_at(b, idx, len, kind, loc);
+        self.field_read(p, &elem, 0, span)
+    }
+
+    /// `for x in a` over a fixed array: the oracle iterates a copy, so
+    /// the loop walks a snapshot — writes to `a` in the body are not
+    /// seen — with the length a constant.
+    pub(super) fn fixed_for(
+        &mut self,
+        index: Option<&String>,
+        name: &str,
+        iterable: &Expr,
+        body: &[Stmt],
+    ) -> Result<(), Diagnostic> {
+        let (elem, kind, len) = self.fixed_of(iterable)?;
+        let src = self.expr(iterable)?;
+        let copy = self.snapshot(src, len * kind.words());
+        let i = self.const_word(0);
+        let x = self.fresh(elem == Type::Float);
+        if kind != Kind::Word {
+            self.insts.push(Inst::Temp {
+                dst: x,
+                words: kind.words(),
+            });
+        }
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Lt,
+            dst: cond,
+            lhs: i,
+            imm: len as i64,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let at = self.step_addr(copy, i, kind);
+        if kind == Kind::Word {
+            self.insts.push(Inst::LoadAt {
+                dst: x,
+                base: at,
+                off: 0,
+            });
+        } else {
+            self.insts.push(Inst::CopyW {
+                dst: x,
+                src: at,
+                words: kind.words(),
+            });
+        }
+        let cont = self.fresh_label();
+        let mut bindings = HashMap::new();
+        bindings.insert(
+            name.to_string(),
+            Binding {
+                v: x,
+                opt_inner: self.opt_inner_of(&elem),
+                err_inner: self.err_inner_of(&elem),
+            },
+        );
+        if let Some(ix) = index {
+            bindings.insert(
+                ix.clone(),
+                Binding {
+                    v: i,
+                    opt_inner: None,
+                    err_inner: None,
+                },
+            );
+        }
+        self.loops.push((cont, end));
+        let result = self.body_in(bindings, body);
+        self.loops.pop();
+        result?;
+        self.insts.push(Inst::Label(cont));
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(())
+    }
+}
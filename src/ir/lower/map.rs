@@ -0,0 +1,582 @@
+//! Maps (ADR 0042). A handle points at a 48-byte header — `{len, cap,
+//! entries*, icap, index*, stride}` — over an entry buffer in insertion
+//! order and an open-addressed index of entry positions. Each entry is
+//! `[hash, key…, value…]` at a compile-time stride. The runtime
+//! (codegen.rs) owns the table mechanics; the per-type halves live
+//! here: `value_hash` walks a key the way `value_eq` compares it, and
+//! every probe hit on the stored hash is confirmed by `value_eq`
+//! itself — one equality for `==` and for keys.
+
+use super::{Binding, Lowerer, TAG_PRESENT};
+use crate::ast::{BinOp, Expr, Stmt};
+use crate::codegen::{
+    RT_HASH_BYTES, RT_HASH_WORD, RT_MAP_ADD, RT_MAP_DEL, RT_MAP_NEW, RT_MAP_PROBE,
+};
+use crate::diagnostic::Diagnostic;
+use crate::ir::layout::{FUEL, Kind, kind_of, legs};
+use crate::ir::{Inst, V, unsupported};
+use crate::span::Span;
+use crate::types::Type;
+use std::collections::HashMap;
+
+/// The FNV-1a offset basis — every key hash starts here; the runtime
+/// folds words and byte runs in with the FNV prime.
+const HASH_SEED: i64 = 0xcbf2_9ce4_8422_2325_u64 as i64;
+
+/// One map type's entry layout: key and value kinds, and where they
+/// sit behind the hash word.
+#[derive(Clone, Copy)]
+struct Entry {
+    kk: Kind,
+    vk: Kind,
+}
+
+impl Entry {
+    const KEY: i64 = 8;
+
+    fn val(self) -> i64 {
+        8 + 8 * self.kk.words() as i64
+    }
+
+    fn stride(self) -> i64 {
+        self.val() + 8 * self.vk.words() as i64
+    }
+}
+
+impl Lowerer<'_> {
+    /// The key and value types behind a map-typed expression — outer
+    /// optionals unwrap first, as for `elem_ty`.
+    fn map_tys(&self, e: &Expr) -> Result<(Type, Type), Diagnostic> {
+        let mut t = self
+            .ty(&e.span())
+            .cloned()
+            .ok_or_else(|| unsupported("this map", e.span()))?;
+        while let Type::Optional(inner) = t {
+            t = *inner;
+        }
+        match t {
+            Type::Map(k, v) => Ok((*k, *v)),
+            _ => Err(unsupported("this map", e.span())),
+        }
+    }
+
+    fn entry(&self, k: &Type, v: &Type, span: Span) -> Result<Entry, Diagnostic> {
+        let kk =
+            kind_of(k, self.res, FUEL).ok_or_else(|| unsupported("maps of this key type", span))?;
+        let vk = kind_of(v, self.res, FUEL)
+            .ok_or_else(|| unsupported("maps of this value type", span))?;
+        Ok(Entry { kk, vk })
+    }
+
+    /// Does this expression have a map type? Index and `len`-style
+    /// sites dispatch on it.
+    pub(super) fn is_map(&self, e: &Expr) -> bool {
+        matches!(self.ty(&e.span()), Some(Type::Map(..)))
+    }
+
+    /// `{ k: v, … }`: a fresh table, then one upsert per entry in
+    /// source order — key, value, insert, like the oracle.
+    pub(super) fn map_lit(
+        &mut self,
+        lit: &Expr,
+        entries: &[(Expr, Expr)],
+    ) -> Result<V, Diagnostic> {
+        let (kt, vt) = self.map_tys(lit)?;
+        let ent = self.entry(&kt, &vt, lit.span())?;
+        let stride = self.const_word(ent.stride());
+        let m = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst: m,
+            sym: RT_MAP_NEW,
+            args: vec![stride],
+            varargs: false,
+        });
+        for (key, value) in entries {
+            let kp = self.key_slot(key, &kt, ent)?;
+            let val = self.map_value(value, &vt, ent)?;
+            self.upsert(m, kp, val, &kt, ent, key.span())?;
+        }
+        Ok(m)
+    }
+
+    /// `m[k]`: the value copied out, or null on a miss — a handle word
+    /// (0) for ref-shaped values, a tagged optional otherwise; an
+    /// already-optional value copies whole (its own tag says null).
+    pub(super) fn map_index(
+        &mut self,
+        base: &Expr,
+        index: &Expr,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let (kt, vt) = self.map_tys(base)?;
+        let ent = self.entry(&kt, &vt, span)?;
+        let read = self
+            .ty(&span)
+            .cloned()
+            .ok_or_else(|| unsupported("this map lookup", span))?;
+        let rk = kind_of(&read, self.res, FUEL)
+            .ok_or_else(|| unsupported("maps of this value type", span))?;
+        let m = self.expr(base)?;
+        let kp = self.key_slot(index, &kt, ent)?;
+        let (e, _) = self.find(m, kp, &kt, span)?;
+        let end = self.fresh_label();
+        if rk == Kind::Word {
+            let out = self.const_word(0);
+            self.insts.push(Inst::BrZero(e, end));
+            let v = self.load_at(e, ent.val());
+            self.insts.push(Inst::Copy(out, v));
+            self.insts.push(Inst::Label(end));
+            return Ok(out);
+        }
+        let out = self.null_optional(rk.words());
+        self.insts.push(Inst::BrZero(e, end));
+        let src = self.lea_at(e, ent.val());
+        if read == vt {
+            self.insts.push(Inst::CopyW {
+                dst: out,
+                src,
+                words: rk.words(),
+            });
+        } else {
+            let tag = self.const_word(TAG_PRESENT);
+            self.insts.push(Inst::StoreAt {
+                base: out,
+                off: 0,
+                val: tag,
+            });
+            let payload = self.lea_at(out, 8);
+            self.insts.push(Inst::CopyW {
+                dst: payload,
+                src,
+                words: ent.vk.words(),
+            });
+        }
+        self.insts.push(Inst::Label(end));
+        Ok(out)
+    }
+
+    /// `insert(m, k, v)` / `remove(m, k)` / `has(m, k)`: arguments in
+    /// order, then the table operation.
+    pub(super) fn map_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let (kt, vt) = self.map_tys(&args[0])?;
+        let ent = self.entry(&kt, &vt, span)?;
+        let m = self.expr(&args[0])?;
+        let kp = self.key_slot(&args[1], &kt, ent)?;
+        if let [_, _, value] = args {
+            let val = self.map_value(value, &vt, ent)?;
+            self.upsert(m, kp, val, &kt, ent, span)?;
+            return Ok(self.const_word(0));
+        }
+        let (e, _) = self.find(m, kp, &kt, span)?;
+        let found = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Ne,
+            dst: found,
+            lhs: e,
+            imm: 0,
+        });
+        if name == crate::syntax::BUILTIN_REMOVE {
+            let end = self.fresh_label();
+            self.insts.push(Inst::BrZero(e, end));
+            let d = self.fresh(false);
+            self.insts.push(Inst::CallRt {
+                dst: d,
+                sym: RT_MAP_DEL,
+                args: vec![m, e],
+                varargs: false,
+            });
+            self.insts.push(Inst::Label(end));
+        }
+        Ok(found)
+    }
+
+    /// `for [k, v] in m`: live positional iteration, the array loop's
+    /// contract — length re-read every step, key and value copied out
+    /// before the body runs.
+    pub(super) fn map_for(
+        &mut self,
+        index: Option<&String>,
+        name: &str,
+        iterable: &Expr,
+        body: &[Stmt],
+    ) -> Result<(), Diagnostic> {
+        let (kt, vt) = self.map_tys(iterable)?;
+        let ent = self.entry(&kt, &vt, iterable.span())?;
+        let m = self.expr(iterable)?;
+        let i = self.const_word(0);
+        let k = self.slot_for(&kt, ent.kk);
+        let x = self.slot_for(&vt, ent.vk);
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let n = self.fresh(false);
+        self.insts.push(Inst::Len(n, m));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Lt,
+            float: false,
+            dst: cond,
+            lhs: i,
+            rhs: n,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let entries = self.load_at(m, 16);
+        let off = self.fresh(false);
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Mul,
+            dst: off,
+            lhs: i,
+            imm: ent.stride(),
+        });
+        let e = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Add,
+            float: false,
+            dst: e,
+            lhs: entries,
+            rhs: off,
+        });
+        self.copy_out(k, e, Entry::KEY, ent.kk);
+        self.copy_out(x, e, ent.val(), ent.vk);
+        let cont = self.fresh_label();
+        let mut bindings = HashMap::new();
+        bindings.insert(
+            name.to_string(),
+            Binding {
+                v: x,
+                opt_inner: self.opt_inner_of(&vt),
+                err_inner: self.err_inner_of(&vt),
+            },
+        );
+        if let Some(key) = index {
+            bindings.insert(
+                key.clone(),
+                Binding {
+                    v: k,
+                    opt_inner: self.opt_inner_of(&kt),
+                    err_inner: None,
+                },
+            );
+        }
+        self.loops.push((cont, end));
+        let result = self.body_in(bindings, body);
+        self.loops.pop();
+        result?;
+        self.insts.push(Inst::Label(cont));
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(())
+    }
+
+    /// A loop binding's storage: a word vreg, or a private temp for
+    /// multi-word kinds.
+    fn slot_for(&mut self, t: &Type, kind: Kind) -> V {
+        let v = self.fresh(*t == Type::Float);
+        if kind != Kind::Word {
+            self.insts.push(Inst::Temp {
+                dst: v,
+                words: kind.words(),
+            });
+        }
+        v
+    }
+
+    /// Copies the entry field at `e + off` into a loop binding.
+    fn copy_out(&mut self, dst: V, e: V, off: i64, kind: Kind) {
+        if kind == Kind::Word {
+            self.insts.push(Inst::LoadAt { dst, base: e, off });
+        } else {
+            let src = self.lea_at(e, off);
+            self.insts.push(Inst::CopyW {
+                dst,
+                src,
+                words: kind.words(),
+            });
+        }
+    }
+
+    /// Evaluates a key into private storage — the copy point — and
+    /// returns a pointer to it: probes compare and hash through
+    /// memory, and a new entry copies the key in from there.
+    fn key_slot(&mut self, key: &Expr, kt: &Type, ent: Entry) -> Result<V, Diagnostic> {
+        let v = self.expr_into(key, kt)?;
+        if ent.kk == Kind::Word {
+            let t = self.fresh(false);
+            self.insts.push(Inst::Temp { dst: t, words: 1 });
+            self.insts.push(Inst::StoreAt {
+                base: t,
+                off: 0,
+                val: v,
+            });
+            return Ok(t);
+        }
+        Ok(self.snapshot(v, ent.kk.words()))
+    }
+
+    /// Evaluates a value for storing; multi-word values snapshot like
+    /// call arguments, so a value read out of this very table survives
+    /// the insert's reallocation.
+    fn map_value(&mut self, value: &Expr, vt: &Type, ent: Entry) -> Result<V, Diagnostic> {
+        let v = self.expr_into(value, vt)?;
+        if ent.vk == Kind::Word {
+            return Ok(v);
+        }
+        Ok(self.snapshot(v, ent.vk.words()))
+    }
+
+    /// Stores `val` under the key at `kp`: update in place when the key
+    /// is present, else append a new entry (hash word set by the
+    /// runtime) and copy the key in.
+    fn upsert(
+        &mut self,
+        m: V,
+        kp: V,
+        val: V,
+        kt: &Type,
+        ent: Entry,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let (e, h) = self.find(m, kp, kt, span)?;
+        let add = self.fresh_label();
+        let store = self.fresh_label();
+        self.insts.push(Inst::BrZero(e, add));
+        self.insts.push(Inst::Jmp(store));
+        self.insts.push(Inst::Label(add));
+        let fresh = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst: fresh,
+            sym: RT_MAP_ADD,
+            args: vec![m, h],
+            varargs: false,
+        });
+        self.insts.push(Inst::Copy(e, fresh));
+        self.store_into(e, Entry::KEY, kp, ent.kk, true);
+        self.insts.push(Inst::Label(store));
+        self.store_into(e, ent.val(), val, ent.vk, false);
+        Ok(())
+    }
+
+    /// Writes a value into the entry at `e + off`. `indirect` words
+    /// arrive behind a pointer (the key slot); other words ride the
+    /// vreg itself.
+    fn store_into(&mut self, e: V, off: i64, src: V, kind: Kind, indirect: bool) {
+        if kind == Kind::Word {
+            let val = if indirect { self.load_at(src, 0) } else { src };
+            self.insts.push(Inst::StoreAt { base: e, off, val });
+        } else {
+            let dst = self.lea_at(e, off);
+            self.insts.push(Inst::CopyW {
+                dst,
+                src,
+                words: kind.words(),
+            });
+        }
+    }
+
+    /// Finds the entry holding the key at `kp`: hash it, then probe —
+    /// the runtime yields each entry whose stored hash matches, and
+    /// `value_eq` confirms. Returns (entry pointer or 0, hash).
+    fn find(&mut self, m: V, kp: V, kt: &Type, span: Span) -> Result<(V, V), Diagnostic> {
+        let seed = self.const_word(HASH_SEED);
+        let h = self.value_hash(kt, kp, 0, seed, span)?;
+        let cursor = self.fresh(false);
+        self.insts.push(Inst::Temp {
+            dst: cursor,
+            words: 1,
+        });
+        self.insts.push(Inst::StoreAt {
+            base: cursor,
+            off: 0,
+            val: h,
+        });
+        let e = self.fresh(false);
+        let top = self.fresh_label();
+        let done = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let hit = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst: hit,
+            sym: RT_MAP_PROBE,
+            args: vec![m, h, cursor],
+            varargs: false,
+        });
+        self.insts.push(Inst::Copy(e, hit));
+        self.insts.push(Inst::BrZero(e, done));
+        let same = self.value_eq(kt, e, Entry::KEY, kp, 0, span)?;
+        self.insts.push(Inst::BrZero(same, top));
+        self.insts.push(Inst::Label(done));
+        Ok((e, h))
+    }
+
+    /// Folds the value at `base + off` into the hash `h`, mirroring
+    /// `value_eq` leg for leg so equal keys always hash alike: words by
+    /// bits (±0.0 folded together — IEEE calls them equal), string
+    /// content, one byte run where canonical layout allows memcmp, and
+    /// per-field, per-payload walks where it doesn't.
+    fn value_hash(
+        &mut self,
+        t: &Type,
+        base: V,
+        off: i64,
+        h: V,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        // Errors compare by code alone, payloads aside (ADR 0062).
+        let kind = if *t == Type::ErrCode {
+            Kind::Word
+        } else {
+            kind_of(t, self.res, FUEL).ok_or_else(|| unsupported("values of this type", span))?
+        };
+        match kind {
+            Kind::Word => {
+                let w = self.load_at(base, off);
+                if *t == Type::Float {
+                    // -0.0 is 0.0 with the sign bit: doubling drops it.
+                    let twice = self.fresh(false);
+                    self.insts.push(Inst::Bin {
+                        op: BinOp::Add,
+                        float: false,
+                        dst: twice,
+                        lhs: w,
+                        rhs: w,
+                    });
+                    let signed_zero = self.fresh(false);
+                    self.insts.push(Inst::BinImm {
+                        op: BinOp::Eq,
+                        dst: signed_zero,
+                        lhs: twice,
+                        imm: 0,
+                    });
+                    let past = self.fresh_label();
+                    self.insts.push(Inst::BrZero(signed_zero, past));
+                    self.insts.push(Inst::Const(w, 0));
+                    self.insts.push(Inst::Label(past));
+                }
+                Ok(self.hash_word(h, w))
+            }
+            Kind::Str => {
+                let p = self.ptr_at(base, off);
+                let data = self.load_at(p, 0);
+                let len = self.load_at(p, 8);
+                let h = self.hash_word(h, len);
+                Ok(self.hash_bytes(h, data, len))
+            }
+            Kind::Struct {
+                no_memcmp: false,
+                words,
+            }
+            | Kind::Opt {
+                no_memcmp: false,
+                words,
+            }
+            | Kind::Enum {
+                no_memcmp: false,
+                words,
+            } => {
+                let p = self.ptr_at(base, off);
+                let n = self.const_word(8 * words as i64);
+                Ok(self.hash_bytes(h, p, n))
+            }
+            Kind::Struct {
+                no_memcmp: true, ..
+            } => {
+                let legs =
+                    legs(t, self.res).ok_or_else(|| unsupported("values of this type", span))?;
+                let mut h = h;
+                for (foff, ft) in legs {
+                    h = self.value_hash(&ft, base, off + foff, h, span)?;
+                }
+                Ok(h)
+            }
+            // The tag, then the live variant's payloads — the same
+            // compare chain `value_eq` walks.
+            Kind::Enum {
+                no_memcmp: true, ..
+            } => {
+                let Type::Enum(m, n) = t else {
+                    unreachable!("enum kind from an enum type")
+                };
+                let def = self.res.enums[&(*m, n.clone())].clone();
+                let tag = self.load_at(base, off);
+                let out = self.hash_word(h, tag);
+                let end = self.fresh_label();
+                for (idx, (_, payloads)) in def.variants.iter().enumerate() {
+                    if payloads.is_empty() {
+                        continue;
+                    }
+                    let hit = self.fresh(false);
+                    self.insts.push(Inst::BinImm {
+                        op: BinOp::Eq,
+                        dst: hit,
+                        lhs: tag,
+                        imm: idx as i64,
+                    });
+                    let next = self.fresh_label();
+                    self.insts.push(Inst::BrZero(hit, next));
+                    let mut poff = 8i64;
+                    for pt in payloads {
+                        let pk = kind_of(pt, self.res, FUEL)
+                            .ok_or_else(|| unsupported("payloads of this type", span))?;
+                        let ph = self.value_hash(pt, base, off + poff, out, span)?;
+                        self.insts.push(Inst::Copy(out, ph));
+                        poff += 8 * pk.words() as i64;
+                    }
+                    self.insts.push(Inst::Jmp(end));
+                    self.insts.push(Inst::Label(next));
+                }
+                self.insts.push(Inst::Label(end));
+                Ok(out)
+            }
+            // The tag, then the payload when present.
+            Kind::Opt {
+                no_memcmp: true, ..
+            } => {
+                let Type::Optional(inner) = t else {
+                    unreachable!("opt kind from an optional type")
+                };
+                let inner = (**inner).clone();
+                let tag = self.load_at(base, off);
+                let out = self.hash_word(h, tag);
+                let end = self.fresh_label();
+                self.insts.push(Inst::BrZero(tag, end));
+                let ph = self.value_hash(&inner, base, off + 8, out, span)?;
+                self.insts.push(Inst::Copy(out, ph));
+                self.insts.push(Inst::Label(end));
+                Ok(out)
+            }
+        }
+    }
+
+    fn hash_word(&mut self, h: V, w: V) -> V {
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym: RT_HASH_WORD,
+            args: vec![h, w],
+            varargs: false,
+        });
+        dst
+    }
+
+    fn hash_bytes(&mut self, h: V, p: V, n: V) -> V {
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym: RT_HASH_BYTES,
+            args: vec![h, p, n],
+            varargs: false,
+        });
+        dst
+    }
+}
@@ -3,24 +3,32 @@
 //! Anything the backend can't represent yet returns a clean
 //! "not yet compilable" diagnostic — there is no fallback path.
 
-use super::layout::{FUEL, Kind, kind_of, offset_of, ref_shaped};
+use super::layout::{Elem, FUEL, Kind, elem_of, kind_of, leg_offset, legs, ref_shaped};
 use super::show::{DEPTH_BUDGET, Printers};
 use super::{FunctionIr, Inst, Lbl, V, unsupported};
-use crate::ast::{BinOp, Conv, Expr, Function, Stmt, UnOp};
-use crate::check::Resolutions;
+use crate::ast::{BinOp, Conv, Expr, Function, Item, Param, Stmt, UnOp};
+use crate::check::{ConstValue, Resolutions};
 use crate::codegen::{
-    FALSE_S, FMT_CSTR, FMT_INT, FMT_STR, NULL_S, RT_ARGS, RT_CLOSE, RT_FMT_F64, RT_MALLOC,
-    RT_MEMCPY, RT_OPEN, RT_PRINTF, RT_PUSH, RT_PUSH_N, RT_READ, RT_READLINE, RT_SB_INT, RT_WRITE,
-    SB_HDR, Strings, TRUE_S, label_of,
+    FALSE_S, FMT_CSTR, FMT_INT, FMT_STR, GLOBALS_INIT, NULL_S, RT_ALLOC, RT_ARENA_ENTER,
+    RT_ARENA_LEAVE, RT_ARGS, RT_CLOSE, RT_FMT_F64, RT_MEMCPY, RT_OPEN, RT_PRINTF, RT_PUSH,
+    RT_PUSH_N, RT_READ, RT_READLINE, RT_SB_INT, RT_SB_U64, RT_SLICE, RT_WRITE, SB_HDR, Strings,
+    TRAP_STEP0, TRUE_S, const_label, global_label, label_of,
 };
 use crate::diagnostic::Diagnostic;
+use crate::modules::ModuleGraph;
 use crate::source::SourceMap;
 use crate::span::Span;
 use crate::syntax;
-use crate::types::Type;
+use crate::types::{IntTy, Type};
 use std::collections::HashMap;
 
+mod arms;
+mod array;
+mod assign;
 mod eq;
+mod fixed;
+mod map;
+mod text;
 
 /// The `{tag, payload}` tag words (ADR 0021/0034): an optional's
 /// present state is 1, an error union's value state is 0 — codes
@@ -43,10 +51,23 @@ pub(super) struct Lowerer<'a> {
     /// `for`'s continue target is its increment step, not the loop top —
     /// jumping to the top would re-run the same element (ADR 0019).
     pub(super) loops: Vec<(Lbl, Lbl)>,
+    /// Labels of the enclosing labeled loops, each with its index into
+    /// `loops` — `break outer;` jumps straight to that entry (ADR 0059).
+    pub(super) loop_labels: Vec<(String, usize)>,
+    /// The open blocks' `defer` bodies, innermost block last: every
+    /// edge out of a block lowers its bodies again (ADR 0060).
+    pub(super) defers: Vec<DeferFrame>,
     /// The hidden destination pointer of a struct-returning function.
     pub(super) sret: Option<V>,
     pub(super) ret_words: usize,
     pub(super) ret_ty: Type,
+    /// The function being lowered — function literals inside it are
+    /// named and labeled after it (ADR 0039).
+    pub(super) module: usize,
+    pub(super) name: String,
+    /// Function literals lowered so far, nested ones included; they
+    /// emit right after the enclosing function.
+    pub(super) lambdas: Vec<FunctionIr>,
 }
 
 /// A lowered binding: its storage vreg plus the payload type when the
@@ -61,7 +82,20 @@ pub(super) struct Binding {
     err_inner: Option<Type>,
 }
 
-/// Lowers one checked function into owned virtual-register IR.
+/// One open block's `defer` bodies (ADR 0060).
+pub(super) struct DeferFrame {
+    /// `loops.len()` when the block opened: leaving loop `k` runs the
+    /// frames opened inside it, those above `k`.
+    loops: usize,
+    /// Each body with the scope depth it was registered at.
+    bodies: Vec<(usize, Vec<Stmt>)>,
+    /// An `arena` block's frame: every edge out frees its region, after
+    /// the bodies (ADR 0061).
+    arena: bool,
+}
+
+/// Lowers one checked function into owned virtual-register IR: the
+/// function itself first, then every function literal inside it.
 pub(super) fn lower(
     f: &Function,
     module: usize,
@@ -69,36 +103,11 @@ pub(super) fn lower(
     strings: &mut Strings,
     printers: &mut Printers,
     map: &SourceMap,
-) -> Result<FunctionIr, Diagnostic> {
+) -> Result<Vec<FunctionIr>, Diagnostic> {
     let sig = &res.sigs[&(module, f.name.clone())];
-    let ret_kind = match &sig.ret {
-        Type::Unit => Kind::Word,
-        t => kind_of(t, res, FUEL).ok_or_else(|| unsupported("this return type", f.span))?,
-    };
-    let sret = ret_kind != Kind::Word;
-
-    let mut lo = Lowerer {
-        res,
-        strings,
-        printers,
-        map,
-        insts: Vec::new(),
-        scopes: vec![HashMap::new()],
-        vregs: 0,
-        floats: Vec::new(),
-        labels: 0,
-        loops: Vec::new(),
-        sret: None,
-        ret_words: ret_kind.words(),
-        ret_ty: sig.ret.clone(),
-    };
-    // Hidden sret pointer first, then params — every param is one word
-    // (scalars/handles by value, structs/strings by pointer; the caller
-    // copied into a private temp at evaluation, so the pointer is safe).
-    if sret {
-        let v = lo.fresh(false);
-        lo.sret = Some(v);
-    }
+    let mut lo = Lowerer::open(
+        res, strings, printers, map, module, &f.name, &sig.ret, f.span,
+    )?;
     // The entry `main(args: string[])` (ADR 0031): the C runtime calls
     // main(argc, argv), so the IR takes those two words and binds the
     // ys parameter to the materialized argv array instead.
@@ -122,12 +131,113 @@ pub(super) fn lower(
             },
         );
     } else {
-        for (p, ty) in f.params.iter().zip(&sig.params) {
-            kind_of(ty, res, FUEL).ok_or_else(|| unsupported("parameters of this type", f.span))?;
-            let v = lo.fresh(*ty == Type::Float);
-            let opt_inner = lo.opt_inner_of(ty);
-            let err_inner = lo.err_inner_of(ty);
-            lo.scopes[0].insert(
+        lo.bind_params(&f.params, &sig.params, f.span)?;
+    }
+    // argc+argv, plus the hidden sret when main returns int! (ADR 0034).
+    let nparams = if entry_args {
+        lo.sret.is_some() as usize + 2
+    } else {
+        lo.vregs
+    };
+    lo.block(&f.body)?;
+    Ok(lo.close(nparams, None))
+}
+
+/// The module-var initializer (ADR 0048): a unit function that runs
+/// every var's initializer in initialization order — modules
+/// dependencies first, declaration order within — storing each value
+/// into the var's storage.
+pub(super) fn lower_globals(
+    graph: &ModuleGraph,
+    res: &Resolutions,
+    strings: &mut Strings,
+    printers: &mut Printers,
+    map: &SourceMap,
+) -> Result<Vec<FunctionIr>, Diagnostic> {
+    let mut lo = Lowerer::open(
+        res,
+        strings,
+        printers,
+        map,
+        0,
+        GLOBALS_INIT,
+        &Type::Unit,
+        Span::new(0, 0),
+    )?;
+    for &mi in &graph.init_order {
+        for item in &graph.modules[mi].ast {
+            if let Item::Global(g) = item {
+                let key = (mi, g.name.clone());
+                let v = lo.expr_into(&g.value, &res.globals[&key])?;
+                lo.store_global(&key, v, g.span)?;
+            }
+        }
+    }
+    Ok(lo.close(0, None))
+}
+
+impl<'a> Lowerer<'a> {
+    /// A fresh lowerer for one function body, its hidden sret pointer
+    /// (when the return is multi-word) already in vreg 0.
+    #[allow(clippy::too_many_arguments)]
+    fn open(
+        res: &'a Resolutions,
+        strings: &'a mut Strings,
+        printers: &'a mut Printers,
+        map: &'a SourceMap,
+        module: usize,
+        name: &str,
+        ret: &Type,
+        span: Span,
+    ) -> Result<Self, Diagnostic> {
+        let ret_kind = match ret {
+            Type::Unit => Kind::Word,
+            t => kind_of(t, res, FUEL).ok_or_else(|| unsupported("this return type", span))?,
+        };
+        let mut lo = Lowerer {
+            res,
+            strings,
+            printers,
+            map,
+            insts: Vec::new(),
+            scopes: vec![HashMap::new()],
+            vregs: 0,
+            floats: Vec::new(),
+            labels: 0,
+            loops: Vec::new(),
+            loop_labels: Vec::new(),
+            defers: Vec::new(),
+            sret: None,
+            ret_words: ret_kind.words(),
+            ret_ty: ret.clone(),
+            module,
+            name: name.to_string(),
+            lambdas: Vec::new(),
+        };
+        // Hidden sret pointer first, then params — every param is one
+        // word (scalars/handles by value, structs/strings by pointer; the
+        // caller copied into a private temp at evaluation, so the
+        // pointer is safe).
+        if ret_kind != Kind::Word {
+            let v = lo.fresh(false);
+            lo.sret = Some(v);
+        }
+        Ok(lo)
+    }
+
+    fn bind_params(
+        &mut self,
+        params: &[Param],
+        tys: &[Type],
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        for (p, ty) in params.iter().zip(tys) {
+            kind_of(ty, self.res, FUEL)
+                .ok_or_else(|| unsupported("parameters of this type", span))?;
+            let v = self.fresh(*ty == Type::Float);
+            let opt_inner = self.opt_inner_of(ty);
+            let err_inner = self.err_inner_of(ty);
+            self.scopes[0].insert(
                 p.name.clone(),
                 Binding {
                     v,
@@ -136,35 +246,27 @@ pub(super) fn lower(
                 },
             );
         }
+        Ok(())
+    }
+
+    /// Finishes the body: the function's IR, then its literals'.
+    fn close(mut self, nparams: usize, env: Option<V>) -> Vec<FunctionIr> {
+        // Fall-through for unit functions; value functions always return
+        // (checker-proven) so the extra ret is dead.
+        let zero = self.const_word(0);
+        self.insts.push(Inst::Ret(zero));
+        let mut out = vec![FunctionIr {
+            name: self.name,
+            module: self.module,
+            nparams,
+            env,
+            vregs: self.vregs,
+            floats: self.floats,
+            insts: self.insts,
+        }];
+        out.append(&mut self.lambdas);
+        out
     }
-    // argc+argv, plus the hidden sret when main returns int! (ADR 0034).
-    let nparams = if entry_args {
-        lo.sret.is_some() as usize + 2
-    } else {
-        lo.vregs
-    };
-    for stmt in &f.body {
-        lo.stmt(stmt)?;
-    }
-    // Fall-through for unit functions; value functions always return
-    // (checker-proven) so the extra ret is dead.
-    let zero = lo.const_word(0);
-    lo.insts.push(Inst::Ret(zero));
-
-    let Lowerer {
-        insts,
-        vregs,
-        floats,
-        ..
-    } = lo;
-    Ok(FunctionIr {
-        name: f.name.clone(),
-        module,
-        nparams,
-        vregs,
-        floats,
-        insts,
-    })
 }
 
 impl Lowerer<'_> {
@@ -179,6 +281,29 @@ impl Lowerer<'_> {
         self.labels - 1
     }
 
+    /// The (continue, break) targets of the loop a `break`/`continue`
+    /// leaves — the one its label names, else the innermost — after the
+    /// `defer` bodies of the blocks inside it (ADR 0060).
+    fn leave_loop(&mut self, label: Option<&str>) -> Result<(Lbl, Lbl), Diagnostic> {
+        let depth = match label {
+            Some(label) => self
+                .loop_labels
+                .iter()
+                .rev()
+                .find(|(l, _)| l == label)
+                .map(|&(_, depth)| depth)
+                .expect("checker: known labels only"),
+            None => self.loops.len() - 1,
+        };
+        let from = self
+            .defers
+            .iter()
+            .position(|frame| frame.loops > depth)
+            .unwrap_or(self.defers.len());
+        self.run_defers(from)?;
+        Ok(self.loops[depth])
+    }
+
     fn lookup(&self, name: &str) -> Option<Binding> {
         self.scopes
             .iter()
@@ -223,6 +348,19 @@ impl Lowerer<'_> {
         self.res.expr_types.get(span)
     }
 
+    /// The sized integer type the checker recorded for `e` (ADR 0052).
+    fn sized(&self, e: &Expr) -> Option<IntTy> {
+        match self.ty(&e.span()) {
+            Some(Type::Sized(t)) => Some(*t),
+            _ => None,
+        }
+    }
+
+    /// How `elem`-typed elements sit in an array buffer.
+    fn elem_layout(&self, elem: &Type, span: Span) -> Result<Elem, Diagnostic> {
+        elem_of(elem, self.res).ok_or_else(|| unsupported("arrays of this element type", span))
+    }
+
     fn is_float(&self, e: &Expr) -> bool {
         matches!(self.ty(&e.span()), Some(Type::Float))
     }
@@ -322,68 +460,111 @@ impl Lowerer<'_> {
     /// codes, and payload-typed values become tagged unions; everything
     /// else is unchanged.
     fn expr_into(&mut self, e: &Expr, target: &Type) -> Result<V, Diagnostic> {
-        match target {
-            Type::Optional(inner) if !ref_shaped(inner, self.res) => {
-                let total = kind_of(target, self.res, FUEL)
-                    .ok_or_else(|| unsupported("values of this type", e.span()))?
-                    .words();
-                match self.ty(&e.span()) {
-                    // The null literal itself — nothing to evaluate.
-                    None | Some(Type::Null) => Ok(self.null_optional(total)),
-                    // Already optional-shaped: pass the pointer through.
-                    Some(Type::Optional(_)) => self.expr(e),
-                    Some(_) => {
-                        let k = kind_of(inner, self.res, FUEL)
-                            .ok_or_else(|| unsupported("values of this type", e.span()))?;
-                        let v = self.expr(e)?;
-                        Ok(self.wrap_present(TAG_PRESENT, v, k, total))
-                    }
-                }
-            }
+        let from = self.ty(&e.span()).cloned();
+        // The null literal itself — nothing to evaluate.
+        if self.opt_inner_of(target).is_some() && matches!(from, None | Some(Type::Null)) {
+            let total = kind_of(target, self.res, FUEL)
+                .ok_or_else(|| unsupported("values of this type", e.span()))?
+                .words();
+            return Ok(self.null_optional(total));
+        }
+        let v = self.expr(e)?;
+        self.shape(v, from.as_ref(), target, e.span())
+    }
+
+    /// Wraps an evaluated value of type `from` for a slot of type
+    /// `target` — the wrapping half of `expr_into`.
+    fn shape(
+        &mut self,
+        v: V,
+        from: Option<&Type>,
+        target: &Type,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let (tag, inner) = match target {
+            Type::Optional(inner) if !ref_shaped(inner, self.res) => match from {
+                // Already optional-shaped: pass the pointer through.
+                Some(Type::Optional(_)) => return Ok(v),
+                _ => (TAG_PRESENT, inner),
+            },
             // `T` and codes flow into `T!` slots (ADR 0034): a value
             // wraps as {0, payload}, a code as {code, zeroed payload}.
-            Type::ErrUnion(inner) => {
-                let total = kind_of(target, self.res, FUEL)
-                    .ok_or_else(|| unsupported("values of this type", e.span()))?
-                    .words();
-                match self.ty(&e.span()) {
-                    Some(Type::ErrCode) => {
-                        let code = self.expr(e)?;
-                        Ok(self.wrap_err(code, total))
-                    }
-                    Some(Type::ErrUnion(_)) | None => self.expr(e),
-                    Some(_) => {
-                        let k = kind_of(inner, self.res, FUEL)
-                            .ok_or_else(|| unsupported("values of this type", e.span()))?;
-                        let v = self.expr(e)?;
-                        Ok(self.wrap_present(TAG_VALUE, v, k, total))
-                    }
+            Type::ErrUnion(inner) => match from {
+                Some(Type::ErrUnion(_)) | None => return Ok(v),
+                Some(Type::ErrCode) => {
+                    let total = kind_of(target, self.res, FUEL)
+                        .ok_or_else(|| unsupported("values of this type", span))?
+                        .words();
+                    return self.wrap_err(v, total, span);
                 }
-            }
-            _ => self.expr(e),
-        }
+                Some(_) => (TAG_VALUE, inner),
+            },
+            _ => return Ok(v),
+        };
+        let total = kind_of(target, self.res, FUEL)
+            .ok_or_else(|| unsupported("values of this type", span))?
+            .words();
+        let k = kind_of(inner, self.res, FUEL)
+            .ok_or_else(|| unsupported("values of this type", span))?;
+        Ok(self.wrap_present(tag, v, k, total))
     }
 
     /// The error state of a `T!` temp: the code in the tag word,
     /// payload words zeroed — canonical like the optional null
     /// (ADR 0034).
-    fn wrap_err(&mut self, code: V, words: usize) -> V {
+    fn wrap_err(&mut self, err: V, words: usize, span: Span) -> Result<V, Diagnostic> {
         let t = self.fresh(false);
         self.insts.push(Inst::Temp { dst: t, words });
+        self.store_err(t, err, words, span)?;
+        Ok(t)
+    }
+
+    /// Writes the error `err` into the `words`-word union at `dst`:
+    /// the code word, or the code and its payloads copied whole (ADR
+    /// 0062); the words after it are zeroed.
+    fn store_err(&mut self, dst: V, err: V, words: usize, span: Span) -> Result<(), Diagnostic> {
+        let ek = self.err_kind(span)?;
         let zero = self.const_word(0);
-        for i in 1..words {
+        for i in ek.words()..words {
             self.insts.push(Inst::StoreAt {
-                base: t,
+                base: dst,
                 off: 8 * i as i64,
                 val: zero,
             });
         }
-        self.insts.push(Inst::StoreAt {
-            base: t,
-            off: 0,
-            val: code,
-        });
-        t
+        if ek == Kind::Word {
+            self.insts.push(Inst::StoreAt {
+                base: dst,
+                off: 0,
+                val: err,
+            });
+        } else {
+            self.insts.push(Inst::CopyW {
+                dst,
+                src: err,
+                words: ek.words(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The kind of an `error` value: a word, or the code and room for
+    /// the widest payload list (ADR 0062).
+    fn err_kind(&self, span: Span) -> Result<Kind, Diagnostic> {
+        kind_of(&Type::ErrCode, self.res, FUEL)
+            .ok_or_else(|| unsupported("error payloads of this type", span))
+    }
+
+    /// Reads the error held at `base + off` by a union proven to be in
+    /// its error state: the tag word IS the code (ADR 0034); with
+    /// payloads the error is the union's leading words, read in place
+    /// (ADR 0062) — consumers copy.
+    fn error_read(&mut self, base: V, off: i64, span: Span) -> Result<V, Diagnostic> {
+        Ok(if self.err_kind(span)? == Kind::Word {
+            self.load_at(base, off)
+        } else {
+            self.ptr_at(base, off)
+        })
     }
 
     /// Reads the payload of a proven-present optional (the recorded type
@@ -410,13 +591,158 @@ impl Lowerer<'_> {
     }
 
     fn block(&mut self, body: &[Stmt]) -> Result<(), Diagnostic> {
-        self.scopes.push(HashMap::new());
-        let result = body.iter().try_for_each(|stmt| self.stmt(stmt));
-        self.scopes.pop();
+        self.body_in(HashMap::new(), body)
+    }
+
+    /// Lowers a block's statements with `scope` as its own, then its
+    /// `defer` bodies for the fall-through edge (ADR 0060).
+    pub(super) fn body_in(
+        &mut self,
+        scope: HashMap<String, Binding>,
+        body: &[Stmt],
+    ) -> Result<(), Diagnostic> {
+        self.frame_in(scope, body, false)
+    }
+
+    fn frame_in(
+        &mut self,
+        scope: HashMap<String, Binding>,
+        body: &[Stmt],
+        arena: bool,
+    ) -> Result<(), Diagnostic> {
+        let depth = self.scopes.len();
+        self.scopes.push(scope);
+        self.defers.push(DeferFrame {
+            loops: self.loops.len(),
+            bodies: Vec::new(),
+            arena,
+        });
+        let mut result = body.iter().try_for_each(|stmt| self.stmt(stmt));
+        if result.is_ok() {
+            result = self.run_defers(self.defers.len() - 1);
+        }
+        self.defers.pop();
+        // A defer opened a scope for the statements after it.
+        self.scopes.truncate(depth);
         result
     }
 
+    /// Lowers the `defer` bodies of the frames from `from` on, last
+    /// registered first, for an edge that leaves those blocks. Each
+    /// sees only the scopes open at its registration. An arena frame
+    /// frees its region once its own bodies ran (ADR 0061).
+    fn run_defers(&mut self, from: usize) -> Result<(), Diagnostic> {
+        let pending: Vec<_> = self.defers[from..]
+            .iter()
+            .rev()
+            .map(|frame| {
+                (
+                    frame.bodies.iter().rev().cloned().collect::<Vec<_>>(),
+                    frame.arena,
+                )
+            })
+            .collect();
+        for (bodies, arena) in pending {
+            for (depth, body) in bodies {
+                let later = self.scopes.split_off(depth);
+                let result = self.block(&body);
+                self.scopes.extend(later);
+                result?;
+            }
+            if arena {
+                self.call_rt_unit(RT_ARENA_LEAVE);
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls a runtime routine that takes and returns nothing.
+    fn call_rt_unit(&mut self, sym: &'static str) {
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym,
+            args: Vec::new(),
+            varargs: false,
+        });
+    }
+
+    /// Returns `v`, after every open `defer` body (ADR 0060). A word
+    /// result is copied out first — a body may assign the variable it
+    /// was read from; an sret result is already in the caller's memory.
+    fn emit_return(&mut self, v: V) -> Result<(), Diagnostic> {
+        let v = if self.sret.is_none() && self.defers.iter().any(|f| !f.bodies.is_empty()) {
+            let out = self.fresh(self.floats[v]);
+            self.insts.push(Inst::Copy(out, v));
+            out
+        } else {
+            v
+        };
+        self.run_defers(0)?;
+        self.insts.push(Inst::Ret(v));
+        Ok(())
+    }
+
+    /// Binds each named leg of the aggregate at `v` to a copy of its
+    /// own; `_` skips one. The destructuring bindings' shared tail
+    /// (ADR 0043/0057).
+    fn bind_legs<'n>(
+        &mut self,
+        v: V,
+        named: impl IntoIterator<Item = (&'n String, i64, Type)>,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        for (name, off, et) in named {
+            if name == "_" {
+                continue;
+            }
+            let kind = kind_of(&et, self.res, FUEL)
+                .ok_or_else(|| unsupported("bindings of this type", span))?;
+            let slot = if kind == Kind::Word {
+                let s = self.fresh(et == Type::Float);
+                self.insts.push(Inst::LoadAt {
+                    dst: s,
+                    base: v,
+                    off,
+                });
+                s
+            } else {
+                let p = self.lea_at(v, off);
+                self.snapshot(p, kind.words())
+            };
+            let opt_inner = self.opt_inner_of(&et);
+            let err_inner = self.err_inner_of(&et);
+            self.scopes
+                .last_mut()
+                .expect("a scope is always open")
+                .insert(
+                    name.clone(),
+                    Binding {
+                        v: slot,
+                        opt_inner,
+                        err_inner,
+                    },
+                );
+        }
+        Ok(())
+    }
+
     fn stmt(&mut self, stmt: &Stmt) -> Result<(), Diagnostic> {
+        // A labeled loop's `loops` entry lands at the current depth
+        // whatever kind of loop pushes it.
+        if let Stmt::While { label: Some(l), .. }
+        | Stmt::WhileConst { label: Some(l), .. }
+        | Stmt::For { label: Some(l), .. } = stmt
+        {
+            self.loop_labels.push((l.clone(), self.loops.len()));
+            let result = self.stmt_inner(stmt);
+            self.loop_labels.pop();
+            return result;
+        }
+        self.stmt_inner(stmt)
+    }
+
+    fn stmt_inner(&mut self, stmt: &Stmt) -> Result<(), Diagnostic> {
         match stmt {
             Stmt::Let {
                 name, value, ty, ..
@@ -458,17 +784,64 @@ impl Lowerer<'_> {
                         },
                     );
             }
+            // Each name copies its position out of the evaluated tuple
+            // into a slot of its own (ADR 0043).
+            Stmt::LetTuple { names, value, .. } => {
+                let tt = self
+                    .ty(&value.span())
+                    .cloned()
+                    .ok_or_else(|| unsupported("bindings of this type", stmt.span()))?;
+                let legs = legs(&tt, self.res)
+                    .ok_or_else(|| unsupported("bindings of this type", stmt.span()))?;
+                let v = self.expr(value)?;
+                let named = names
+                    .iter()
+                    .zip(legs)
+                    .map(|(name, (off, et))| (name, off, et));
+                self.bind_legs(v, named, stmt.span())?;
+            }
+            // The same copy-out, each field at the leg its slot records
+            // (ADR 0057).
+            Stmt::LetStruct { fields, value, .. } => {
+                let mut named = Vec::new();
+                for (_, name, span) in fields {
+                    let slot = self
+                        .res
+                        .field_slots
+                        .get(span)
+                        .ok_or_else(|| unsupported("this field access", *span))?;
+                    let off = leg_offset(&slot.base, slot.index, self.res)
+                        .ok_or_else(|| unsupported("this struct layout", *span))?;
+                    named.push((name, off, slot.ty.clone()));
+                }
+                let v = self.expr(value)?;
+                self.bind_legs(v, named, stmt.span())?;
+            }
+            Stmt::LetElse {
+                pattern,
+                value,
+                else_body,
+                span,
+                ..
+            } => self.let_else(pattern, value, else_body.as_deref(), *span)?,
+            Stmt::Assign {
+                target,
+                op: Some(op),
+                value,
+                span,
+                ..
+            } => self.compound_assign(target, *op, value, *span)?,
             Stmt::Assign { target, value, .. } => match target {
+                Expr::Ident(_, span) if self.res.global_refs.contains_key(span) => {
+                    let key = &self.res.global_refs[span];
+                    let v = self.expr_into(value, &self.res.globals[key])?;
+                    self.store_global(key, v, *span)?;
+                }
                 Expr::Ident(name, span) => {
                     let b = self
                         .lookup(name)
                         .ok_or_else(|| unsupported("this assignment target", *span))?;
-                    let union_slot = match (&b.opt_inner, &b.err_inner) {
-                        (Some(inner), _) => Some(Type::Optional(Box::new(inner.clone()))),
-                        (_, Some(inner)) => Some(Type::ErrUnion(Box::new(inner.clone()))),
-                        _ => None,
-                    };
-                    if let Some(target) = union_slot {
+                    if let Some(target) = b.union_slot() {
                         // Union-shaped slot: wrap the value in place.
                         let words = kind_of(&target, self.res, FUEL)
                             .ok_or_else(|| unsupported("this assignment target", *span))?
@@ -493,6 +866,11 @@ impl Lowerer<'_> {
                         }
                     }
                 }
+                // A fixed array's slot is a value hop (ADR 0056): the
+                // value, then the place, as for compound assignment.
+                Expr::Index { base, span, .. } if self.is_fixed(base) => {
+                    self.slot_assign(target, value, *span)?
+                }
                 // The oracle evaluates the value before the target.
                 Expr::Index { base, index, span } => {
                     let loc = self.loc_of(*span);
@@ -510,13 +888,13 @@ impl Lowerer<'_> {
                     };
                     let arr = self.expr(base)?;
                     let idx = self.expr(index)?;
-                    let agg = (ek != Kind::Word).then(|| ek.words());
+                    let el = self.elem_layout(&elem, *span)?;
                     self.insts.push(Inst::IndexSet {
                         arr,
                         idx,
                         val,
                         loc,
-                        agg,
+                        elem: el,
                     });
                 }
                 Expr::Field { base, span, .. } => {
@@ -527,8 +905,7 @@ impl Lowerer<'_> {
                         .ok_or_else(|| unsupported("this field target", *span))?;
                     let kind = kind_of(&slot.ty, self.res, FUEL)
                         .ok_or_else(|| unsupported("fields of this type", *span))?;
-                    let def = &self.res.structs[&slot.base];
-                    let off = offset_of(def, slot.index, self.res)
+                    let off = leg_offset(&slot.base, slot.index, self.res)
                         .ok_or_else(|| unsupported("this struct layout", *span))?;
                     let target = slot.ty.clone();
                     let val = self.expr_into(value, &target)?;
@@ -551,34 +928,53 @@ impl Lowerer<'_> {
                 }
                 other => return Err(unsupported("this assignment target", other.span())),
             },
-            Stmt::Return { value, .. } => match value {
-                Some(expr) => {
-                    let ret_ty = self.ret_ty.clone();
-                    let v = self.expr_into(expr, &ret_ty)?;
-                    if let Some(sret) = self.sret {
-                        self.insts.push(Inst::CopyW {
-                            dst: sret,
-                            src: v,
-                            words: self.ret_words,
-                        });
-                        self.insts.push(Inst::Ret(sret));
-                    } else {
-                        self.insts.push(Inst::Ret(v));
+            Stmt::Return { value, .. } => {
+                let v = match value {
+                    Some(expr) => {
+                        let ret_ty = self.ret_ty.clone();
+                        let v = self.expr_into(expr, &ret_ty)?;
+                        match self.sret {
+                            Some(sret) => {
+                                self.insts.push(Inst::CopyW {
+                                    dst: sret,
+                                    src: v,
+                                    words: self.ret_words,
+                                });
+                                sret
+                            }
+                            None => v,
+                        }
                     }
-                }
-                None => {
-                    let zero = self.const_word(0);
-                    self.insts.push(Inst::Ret(zero));
-                }
-            },
-            Stmt::Break { .. } => {
-                let (_, brk) = *self.loops.last().expect("checker: loops only");
+                    None => self.const_word(0),
+                };
+                self.emit_return(v)?;
+            }
+            Stmt::Break { label, .. } => {
+                let (_, brk) = self.leave_loop(label.as_deref())?;
                 self.insts.push(Inst::Jmp(brk));
             }
-            Stmt::Continue { .. } => {
-                let (cont, _) = *self.loops.last().expect("checker: loops only");
+            Stmt::Continue { label, .. } => {
+                let (cont, _) = self.leave_loop(label.as_deref())?;
                 self.insts.push(Inst::Jmp(cont));
             }
+            // Registered here, lowered on each edge out of the block
+            // (ADR 0060). The statements after it bind in a fresh scope,
+            // which the body does not see.
+            Stmt::Defer { body, .. } => {
+                let depth = self.scopes.len();
+                self.defers
+                    .last_mut()
+                    .expect("blocks open a defer frame")
+                    .bodies
+                    .push((depth, body.clone()));
+                self.scopes.push(HashMap::new());
+            }
+            // Allocations bind to a fresh region until every edge out
+            // frees it (ADR 0061).
+            Stmt::Arena { body, .. } => {
+                self.call_rt_unit(RT_ARENA_ENTER);
+                self.frame_in(HashMap::new(), body, true)?;
+            }
             Stmt::If {
                 cond,
                 then_body,
@@ -603,6 +999,21 @@ impl Lowerer<'_> {
                 }
                 self.insts.push(Inst::Label(end));
             }
+            // The binding forms, in `arms.rs` (ADR 0058).
+            Stmt::IfConst {
+                pattern,
+                value,
+                then_body,
+                else_body,
+                span,
+            } => self.if_const(pattern, value, then_body, else_body.as_deref(), *span)?,
+            Stmt::WhileConst {
+                pattern,
+                value,
+                body,
+                span,
+                ..
+            } => self.while_const(pattern, value, body, *span)?,
             Stmt::While { cond, body, .. } => {
                 let top = self.fresh_label();
                 let end = self.fresh_label();
@@ -623,6 +1034,18 @@ impl Lowerer<'_> {
                 body,
                 ..
             } => {
+                if let Expr::Range { .. } = iterable {
+                    return self.range_for(name, iterable, body);
+                }
+                if self.is_map(iterable) {
+                    return self.map_for(index.as_ref(), name, iterable, body);
+                }
+                if self.is_str(iterable) {
+                    return self.str_for(index.as_ref(), name, iterable, body);
+                }
+                if self.is_fixed(iterable) {
+                    return self.fixed_for(index.as_ref(), name, iterable, body);
+                }
                 // Live iteration, the oracle's contract: length re-read
                 // every step, element copied out before the body runs.
                 let elem = self.elem_ty(iterable)?;
@@ -655,12 +1078,13 @@ impl Lowerer<'_> {
                 // path is unreachable but keeps one Index shape.
                 let loc = self.loc_of(iterable.span());
                 if ek == Kind::Word {
+                    let el = self.elem_layout(&elem, iterable.span())?;
                     self.insts.push(Inst::Index {
                         dst: x,
                         arr,
                         idx: i,
                         loc,
-                        agg: None,
+                        elem: el,
                     });
                 } else {
                     // Interior pointer, then the per-step copy-out.
@@ -670,7 +1094,7 @@ impl Lowerer<'_> {
                         arr,
                         idx: i,
                         loc,
-                        agg: Some(ek.words()),
+                        elem: Elem::Agg(ek.words()),
                     });
                     self.insts.push(Inst::CopyW {
                         dst: x,
@@ -698,11 +1122,9 @@ impl Lowerer<'_> {
                         },
                     );
                 }
-                self.scopes.push(bindings);
                 self.loops.push((cont, end));
-                let result = body.iter().try_for_each(|stmt| self.stmt(stmt));
+                let result = self.body_in(bindings, body);
                 self.loops.pop();
-                self.scopes.pop();
                 result?;
                 self.insts.push(Inst::Label(cont));
                 self.insts.push(Inst::BinImm {
@@ -717,100 +1139,224 @@ impl Lowerer<'_> {
             Stmt::Expr(expr) => {
                 self.expr(expr)?;
             }
-            // Variant dispatch (ADR 0036): a tag-compare chain into arm
-            // blocks; payload bindings copy out of the scrutinee before
-            // the body runs, so mutation inside the arm can't alias it.
+            // Variant dispatch (ADR 0036), in `arms.rs`.
             Stmt::Match {
                 scrutinee,
                 arms,
                 else_body,
                 span,
-            } => {
-                let s_ty = self
-                    .ty(&scrutinee.span())
-                    .cloned()
-                    .ok_or_else(|| unsupported("this match", *span))?;
-                let Type::Enum(m, n) = &s_ty else {
-                    return Err(unsupported("this match", *span));
+            } => self.match_stmt(scrutinee, arms, else_body.as_deref(), *span)?,
+        }
+        Ok(())
+    }
+
+    /// `for i in a..b step s` (ADR 0051): a register counter, no array.
+    /// One entry test, then a bottom test per step on the distance
+    /// left, `end - i` (or `i - end` counting down): it is never
+    /// negative there, so as unsigned it is exact, and comparing it to
+    /// the step's magnitude stops the loop exactly where the oracle's
+    /// `checked_add` does. Unsigned compares are signed ones with the
+    /// sign bit flipped. A runtime step picks its direction with masks,
+    /// not branches.
+    fn range_for(&mut self, name: &str, range: &Expr, body: &[Stmt]) -> Result<(), Diagnostic> {
+        let Expr::Range {
+            start: Some(start),
+            end: Some(end),
+            inclusive,
+            step,
+            span,
+        } = range
+        else {
+            return Err(unsupported(
+                "a 'for' range without both bounds",
+                range.span(),
+            ));
+        };
+        // Private copies: the body may reassign the names they read.
+        let i = self.fresh(false);
+        let a = self.expr(start)?;
+        self.insts.push(Inst::Copy(i, a));
+        let e = self.fresh(false);
+        let b = self.expr(end)?;
+        self.insts.push(Inst::Copy(e, b));
+        let literal = match step.as_deref() {
+            None => Some(1),
+            Some(Expr::Int(n, _)) => Some(*n),
+            Some(Expr::Unary {
+                op: UnOp::Neg, rhs, ..
+            }) => match **rhs {
+                Expr::Int(n, _) => Some(n.wrapping_neg()),
+                _ => None,
+            },
+            Some(_) => None,
+        }
+        // `-0` slips past the checker's literal test; the trap catches it.
+        .filter(|&n| n != 0);
+        let (lt, gt) = if *inclusive {
+            (BinOp::Le, BinOp::Ge)
+        } else {
+            (BinOp::Lt, BinOp::Gt)
+        };
+        let more = if *inclusive { BinOp::Ge } else { BinOp::Gt };
+        let bias = self.const_word(i64::MIN);
+        let exit = self.fresh_label();
+        // (first test, step, |step| ^ bias, down mask)
+        let (first, st, mag, mask) = match literal {
+            Some(n) => {
+                let first = if n > 0 {
+                    self.scalar_op(lt, false, i, e, *span)
+                } else {
+                    self.scalar_op(gt, false, i, e, *span)
                 };
-                let key = (*m, n.clone());
-                let u = self.expr(scrutinee)?;
-                let tag = self.load_at(u, 0);
-                let end = self.fresh_label();
-                for arm in arms {
-                    let idx = *self
-                        .res
-                        .variant_tags
-                        .get(&arm.variant_span)
-                        .ok_or_else(|| unsupported("this match arm", arm.span))?;
-                    let hit = self.fresh(false);
-                    self.insts.push(Inst::BinImm {
-                        op: BinOp::Eq,
-                        dst: hit,
-                        lhs: tag,
-                        imm: idx as i64,
-                    });
-                    let next = self.fresh_label();
-                    self.insts.push(Inst::BrZero(hit, next));
-                    // Bindings copy out (the oracle clones payloads).
-                    let payloads = self.res.enums[&key].variants[idx as usize].1.clone();
-                    let mut bindings = HashMap::new();
-                    let mut off = 8i64;
-                    for (i, pt) in payloads.iter().enumerate() {
-                        let pk = kind_of(pt, self.res, FUEL)
-                            .ok_or_else(|| unsupported("payloads of this type", arm.span))?;
-                        if let Some((bname, _)) = arm.bindings.get(i)
-                            && bname != "_"
-                        {
-                            let v = self.fresh(*pt == Type::Float);
-                            if pk == Kind::Word {
-                                self.insts.push(Inst::LoadAt {
-                                    dst: v,
-                                    base: u,
-                                    off,
-                                });
-                            } else {
-                                let p = self.lea_at(u, off);
-                                self.insts.push(Inst::Temp {
-                                    dst: v,
-                                    words: pk.words(),
-                                });
-                                self.insts.push(Inst::CopyW {
-                                    dst: v,
-                                    src: p,
-                                    words: pk.words(),
-                                });
-                            }
-                            bindings.insert(
-                                bname.clone(),
-                                Binding {
-                                    v,
-                                    opt_inner: self.opt_inner_of(pt),
-                                    err_inner: self.err_inner_of(pt),
-                                },
-                            );
-                        }
-                        off += 8 * pk.words() as i64;
-                    }
-                    self.scopes.push(bindings);
-                    let result = arm.body.iter().try_for_each(|stmt| self.stmt(stmt));
-                    self.scopes.pop();
-                    result?;
-                    self.insts.push(Inst::Jmp(end));
-                    self.insts.push(Inst::Label(next));
-                }
-                if let Some(else_body) = else_body {
-                    self.block(else_body)?;
-                }
-                self.insts.push(Inst::Label(end));
+                let st = self.const_word(n);
+                let mag = self.const_word((n.unsigned_abs() ^ (1 << 63)) as i64);
+                (first, st, mag, None)
             }
-        }
+            None => {
+                let st = self.fresh(false);
+                let s = self.expr(step.as_deref().expect("a non-literal step"))?;
+                self.insts.push(Inst::Copy(st, s));
+                let zero = self.scalar_imm(BinOp::Eq, st, 0);
+                let ok = self.fresh_label();
+                self.insts.push(Inst::BrZero(zero, ok));
+                let loc_lbl = self.loc_of(*span);
+                let loc = self.lea_sym(loc_lbl);
+                let d = self.fresh(false);
+                self.insts.push(Inst::CallRt {
+                    dst: d,
+                    sym: TRAP_STEP0,
+                    args: vec![loc],
+                    varargs: false,
+                });
+                self.insts.push(Inst::Label(ok));
+                // up ? (i < e) : (i > e), as `down ^ ((up ^ down) & upbit)`.
+                let up = self.scalar_imm(BinOp::Gt, st, 0);
+                let fwd = self.scalar_op(lt, false, i, e, *span);
+                let back = self.scalar_op(gt, false, i, e, *span);
+                let diff = self.scalar_op(BinOp::BitXor, false, fwd, back, *span);
+                let pick = self.scalar_op(BinOp::BitAnd, false, diff, up, *span);
+                let first = self.scalar_op(BinOp::BitXor, false, back, pick, *span);
+                let mask = self.scalar_imm(BinOp::Shr, st, 63);
+                let mag = self.negate_if(st, mask, *span);
+                let mag = self.scalar_op(BinOp::BitXor, false, mag, bias, *span);
+                (first, st, mag, Some(mask))
+            }
+        };
+        self.insts.push(Inst::BrZero(first, exit));
+        let top = self.fresh_label();
+        let cont = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let binding = Binding {
+            v: i,
+            opt_inner: None,
+            err_inner: None,
+        };
+        self.loops.push((cont, exit));
+        let scope = HashMap::from([(name.to_string(), binding)]);
+        let result = self.body_in(scope, body);
+        self.loops.pop();
+        result?;
+        self.insts.push(Inst::Label(cont));
+        let left = match (literal, mask) {
+            (Some(n), _) if n < 0 => self.scalar_op(BinOp::Sub, false, i, e, *span),
+            (_, None) => self.scalar_op(BinOp::Sub, false, e, i, *span),
+            (_, Some(mask)) => {
+                let left = self.scalar_op(BinOp::Sub, false, e, i, *span);
+                self.negate_if(left, mask, *span)
+            }
+        };
+        let left = self.scalar_op(BinOp::BitXor, false, left, bias, *span);
+        let again = self.scalar_op(more, false, left, mag, *span);
+        self.insts.push(Inst::BrZero(again, exit));
+        self.insts.push(Inst::Bin {
+            op: BinOp::Add,
+            float: false,
+            dst: i,
+            lhs: i,
+            rhs: st,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(exit));
         Ok(())
     }
 
+    /// `mask` is 0 or -1: `v`, or `-v`, as `(v ^ mask) - mask`.
+    fn negate_if(&mut self, v: V, mask: V, span: Span) -> V {
+        let flipped = self.scalar_op(BinOp::BitXor, false, v, mask, span);
+        self.scalar_op(BinOp::Sub, false, flipped, mask, span)
+    }
+
+    /// `xs[a..b]` (ADR 0051): the runtime checks the bounds and copies
+    /// the elements into a fresh array.
+    fn slice(&mut self, base: &Expr, range: &Expr, span: Span) -> Result<V, Diagnostic> {
+        let Expr::Range {
+            start,
+            end,
+            inclusive,
+            ..
+        } = range
+        else {
+            return Err(unsupported("these slice bounds", range.span()));
+        };
+        // A string slices into a view (ADR 0053), an array into a copy.
+        let el = if self.is_str(base) {
+            None
+        } else {
+            let elem = self.elem_ty(base)?;
+            Some(self.elem_layout(&elem, span)?)
+        };
+        let arr = self.expr(base)?;
+        let lo = match start {
+            Some(start) => self.expr(start)?,
+            None => self.const_word(0),
+        };
+        let hi = match end {
+            Some(end) => {
+                let hi = self.expr(end)?;
+                if *inclusive {
+                    self.scalar_imm(BinOp::Add, hi, 1)
+                } else {
+                    hi
+                }
+            }
+            None if el.is_none() => self.load_at(arr, 8),
+            None => {
+                let n = self.fresh(false);
+                self.insts.push(Inst::Len(n, arr));
+                n
+            }
+        };
+        let loc_lbl = self.loc_of(span);
+        let loc = self.lea_sym(loc_lbl);
+        let Some(el) = el else {
+            return Ok(self.str_slice(arr, lo, hi, loc));
+        };
+        Ok(self.array_slice(arr, lo, hi, el, loc))
+    }
+
+    /// An array's elements `lo..hi` copied out, once the bounds are
+    /// words — `xs[a..b]` and `slice(xs, a, b)` (ADR 0055).
+    fn array_slice(&mut self, arr: V, lo: V, hi: V, el: Elem, loc: V) -> V {
+        let stride = self.const_word(el.stride());
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym: RT_SLICE,
+            args: vec![arr, lo, hi, stride, loc],
+            varargs: false,
+        });
+        dst
+    }
+
     fn expr(&mut self, expr: &Expr) -> Result<V, Diagnostic> {
         match expr {
-            Expr::Int(n, _) => Ok(self.const_word(*n)),
+            Expr::Int(n, _) => {
+                let n = match self.sized(expr) {
+                    Some(t) => t.wrap(*n),
+                    None => *n,
+                };
+                Ok(self.const_word(n))
+            }
             Expr::Bool(b, _) => Ok(self.const_word(*b as i64)),
             Expr::Float(f, _) => {
                 let v = self.fresh(true);
@@ -821,7 +1367,54 @@ impl Lowerer<'_> {
             // handle 0; value-optional slots wrap it in `expr_into`.
             Expr::Null(_) => Ok(self.const_word(0)),
             // The checker interned the code (ADR 0034): a constant word.
-            Expr::ErrorLit(_, span) => Ok(self.const_word(self.res.error_lits[span] as i64)),
+            // Once any error carries payloads (ADR 0062), a frame temp
+            // built like an enum's: the code, then the payloads, slack
+            // zeroed.
+            Expr::ErrorLit { args, span, .. } => {
+                let code = self.res.error_lits[span];
+                let total = self.err_kind(*span)?.words();
+                if total == 1 {
+                    return Ok(self.const_word(code as i64));
+                }
+                let t = self.fresh(false);
+                self.insts.push(Inst::Temp {
+                    dst: t,
+                    words: total,
+                });
+                let zero = self.const_word(0);
+                for i in 1..total {
+                    self.insts.push(Inst::StoreAt {
+                        base: t,
+                        off: 8 * i as i64,
+                        val: zero,
+                    });
+                }
+                let codev = self.const_word(code as i64);
+                self.insts.push(Inst::StoreAt {
+                    base: t,
+                    off: 0,
+                    val: codev,
+                });
+                let payloads = self.res.error_payloads[code as usize - 2].clone();
+                let mut off = 8i64;
+                for (arg, pt) in args.iter().zip(&payloads) {
+                    let pk = kind_of(pt, self.res, FUEL)
+                        .ok_or_else(|| unsupported("payloads of this type", *span))?;
+                    let val = self.expr_into(arg, pt)?;
+                    if pk == Kind::Word {
+                        self.insts.push(Inst::StoreAt { base: t, off, val });
+                    } else {
+                        let p = self.lea_at(t, off);
+                        self.insts.push(Inst::CopyW {
+                            dst: p,
+                            src: val,
+                            words: pk.words(),
+                        });
+                    }
+                    off += 8 * pk.words() as i64;
+                }
+                Ok(t)
+            }
             // The equality intercept consumes the marker; it never
             // reaches plain evaluation (ADR 0034).
             Expr::ErrorKind(_) => unreachable!("checker restricts bare 'error' to tests"),
@@ -844,20 +1437,16 @@ impl Lowerer<'_> {
                 let cont = self.fresh_label();
                 self.insts.push(Inst::BrZero(is_err, cont));
                 let sret = self.sret.expect("checker: try requires a T! return");
-                let zero = self.const_word(0);
-                for i in 1..self.ret_words {
-                    self.insts.push(Inst::StoreAt {
-                        base: sret,
-                        off: 8 * i as i64,
-                        val: zero,
-                    });
-                }
-                self.insts.push(Inst::StoreAt {
-                    base: sret,
-                    off: 0,
-                    val: tag,
-                });
-                self.insts.push(Inst::Ret(sret));
+                // The tag is the whole error unless errors carry
+                // payloads (ADR 0062); then the union's leading words
+                // are the error.
+                let err = if self.err_kind(*span)? == Kind::Word {
+                    tag
+                } else {
+                    u
+                };
+                self.store_err(sret, err, self.ret_words, *span)?;
+                self.emit_return(sret)?;
                 self.insts.push(Inst::Label(cont));
                 let inner = match self.ty(&operand.span()) {
                     Some(Type::ErrUnion(i)) => (**i).clone(),
@@ -871,6 +1460,48 @@ impl Lowerer<'_> {
                 let sym = self.strings.intern(text);
                 Ok(self.lea_sym(sym))
             }
+            // A module const (ADR 0047): a string is its interned
+            // descriptor; any other value is a word read from rodata.
+            Expr::Ident(_, span) if self.res.const_refs.contains_key(span) => {
+                let key = &self.res.const_refs[span];
+                if let ConstValue::Str(bytes) = &self.res.consts[key] {
+                    let sym = self.strings.intern(bytes);
+                    return Ok(self.lea_sym(sym));
+                }
+                let base = self.lea_sym(const_label(key.0, &key.1));
+                let dst = self.fresh(matches!(self.res.consts[key], ConstValue::Float(_)));
+                self.insts.push(Inst::LoadAt { dst, base, off: 0 });
+                Ok(dst)
+            }
+            // A module var (ADR 0048): a word is loaded from its storage;
+            // anything wider reads as the storage itself — consumers
+            // copy, as from a local's slot.
+            Expr::Ident(_, span) if self.res.global_refs.contains_key(span) => {
+                let key = &self.res.global_refs[span];
+                let ty = &self.res.globals[key];
+                let kind = kind_of(ty, self.res, FUEL)
+                    .ok_or_else(|| unsupported("module vars of this type", *span))?;
+                let base = self.lea_sym(global_label(key.0, &key.1));
+                if kind != Kind::Word {
+                    return Ok(base);
+                }
+                let dst = self.fresh(*ty == Type::Float);
+                self.insts.push(Inst::LoadAt { dst, base, off: 0 });
+                Ok(dst)
+            }
+            // A function value is a pointer to its static function
+            // object (ADR 0038).
+            Expr::Ident(_, span) | Expr::FnRef { span, .. }
+                if self.res.fn_values.contains_key(span) =>
+            {
+                let (m, name) = &self.res.fn_values[span];
+                let sym = self.strings.intern_fn(&label_of(*m, name));
+                Ok(self.lea_sym(sym))
+            }
+            Expr::FnRef { span, .. } => Err(unsupported("this function value", *span)),
+            Expr::Lambda {
+                params, body, span, ..
+            } => self.lambda(params, body, *span),
             Expr::Ident(name, span) => {
                 let b = self
                     .lookup(name)
@@ -883,12 +1514,11 @@ impl Lowerer<'_> {
                     return self.payload_read(b.v, &inner.clone(), *span);
                 }
                 // T! narrowing (ADR 0034): proven-value reads unwrap the
-                // payload; proven-error reads load the tag — it IS the
-                // code.
+                // payload; proven-error reads the error.
                 if let Some(inner) = &b.err_inner {
                     return match self.ty(span) {
                         Some(Type::ErrUnion(_)) | None => Ok(b.v),
-                        Some(Type::ErrCode) => Ok(self.load_at(b.v, 0)),
+                        Some(Type::ErrCode) => self.error_read(b.v, 0, *span),
                         Some(_) => self.payload_read(b.v, &inner.clone(), *span),
                     };
                 }
@@ -897,13 +1527,24 @@ impl Lowerer<'_> {
             // float(i) is one convert; int(f) is the checked form —
             // NaN and out-of-range report and exit 1 (ADR 0028);
             // string(x) renders through the shared builder (ADR 0029).
+            // Sized integers (ADR 0052) are canonical words already:
+            // widening to int is free, narrowing re-extends the low bits.
             Expr::Convert { to, arg, span, .. } => match to {
                 Conv::Float => {
                     let v = self.expr(arg)?;
                     let dst = self.fresh(true);
-                    self.insts.push(Inst::IntToFloat(dst, v));
+                    self.insts.push(if self.sized(arg) == Some(IntTy::U64) {
+                        Inst::UintToFloat(dst, v)
+                    } else {
+                        Inst::IntToFloat(dst, v)
+                    });
                     Ok(dst)
                 }
+                Conv::Int if self.sized(arg).is_some() => self.expr(arg),
+                Conv::Sized(t) => {
+                    let v = self.expr(arg)?;
+                    Ok(self.narrow(v, *t))
+                }
                 Conv::Int => {
                     let v = self.expr(arg)?;
                     let loc = self.loc_of(*span);
@@ -921,8 +1562,12 @@ impl Lowerer<'_> {
                     UnOp::Neg if float => Inst::NegF(v, r),
                     UnOp::Neg => Inst::Neg(v, r),
                     UnOp::Not => Inst::Not(v, r),
+                    UnOp::BitNot => Inst::BitNot(v, r),
                 });
-                Ok(v)
+                Ok(match self.sized(rhs) {
+                    Some(t) => self.narrow(v, t),
+                    None => v,
+                })
             }
             Expr::Binary {
                 op: op @ (BinOp::And | BinOp::Or),
@@ -958,18 +1603,24 @@ impl Lowerer<'_> {
             Expr::Call {
                 callee, args, span, ..
             } => self.call(callee, args, *span),
+            Expr::MapLit { entries, .. } => self.map_lit(expr, entries),
+            // Fixed arrays (ADR 0056), in `fixed.rs`.
+            Expr::ArrayLit { elements, .. } if self.is_fixed(expr) => {
+                self.fixed_lit(expr, elements)
+            }
+            Expr::ArrayRepeat { value, .. } => self.fixed_repeat(expr, value),
             Expr::ArrayLit { elements, span } => {
                 let elem = self.elem_ty(expr)?;
-                let ek = kind_of(&elem, self.res, FUEL)
-                    .ok_or_else(|| unsupported("arrays of this element type", *span))?;
-                let stride = 8 * ek.words() as i64;
+                let el = self.elem_layout(&elem, *span)?;
+                let stride = el.stride();
                 // Header {len, cap, data*} plus buffer, per ADR 0014;
-                // elements sit at a compile-time stride (ADR 0023).
+                // elements sit at a compile-time stride (ADR 0023),
+                // sized integers packed (ADR 0052).
                 let c24 = self.const_word(24);
                 let hdr = self.fresh(false);
                 self.insts.push(Inst::CallRt {
                     dst: hdr,
-                    sym: RT_MALLOC,
+                    sym: RT_ALLOC,
                     args: vec![c24],
                     varargs: false,
                 });
@@ -977,7 +1628,7 @@ impl Lowerer<'_> {
                 let buf = self.fresh(false);
                 self.insts.push(Inst::CallRt {
                     dst: buf,
-                    sym: RT_MALLOC,
+                    sym: RT_ALLOC,
                     args: vec![size],
                     varargs: false,
                 });
@@ -990,31 +1641,47 @@ impl Lowerer<'_> {
                 // point; optional elements wrap here (the fits rule).
                 for (slot, element) in elements.iter().enumerate() {
                     let val = self.expr_into(element, &elem)?;
-                    if ek == Kind::Word {
-                        self.insts.push(Inst::BufSet { buf, slot, val });
-                    } else {
+                    if let Elem::Agg(words) = el {
                         let p = self.lea_at(buf, stride * slot as i64);
                         self.insts.push(Inst::CopyW {
                             dst: p,
                             src: val,
-                            words: ek.words(),
+                            words,
+                        });
+                    } else {
+                        self.insts.push(Inst::BufSet {
+                            buf,
+                            slot,
+                            val,
+                            elem: el,
                         });
                     }
                 }
                 Ok(hdr)
             }
+            Expr::Index { base, index, span } if matches!(**index, Expr::Range { .. }) => {
+                self.slice(base, index, *span)
+            }
+            Expr::Range { span, .. } => {
+                Err(unsupported("a range outside 'for' and slicing", *span))
+            }
+            Expr::Index { base, index, span } if self.is_map(base) => {
+                self.map_index(base, index, *span)
+            }
+            Expr::Index { base, index, span } if self.is_str(base) => {
+                self.str_index(base, index, *span)
+            }
+            Expr::Index { base, index, span } if self.is_fixed(base) => {
+                self.fixed_index(base, index, *span)
+            }
             Expr::Index { base, index, span } => {
                 let loc = self.loc_of(*span);
                 // The recorded type IS the element type — index
                 // expressions are never narrowable places. Aggregates
                 // (any width) read as interior pointers; consumers copy.
-                let agg = match self.ty(span) {
-                    None => None,
-                    Some(t) => {
-                        let k = kind_of(t, self.res, FUEL)
-                            .ok_or_else(|| unsupported("arrays of this element type", *span))?;
-                        (k != Kind::Word).then(|| k.words())
-                    }
+                let el = match self.ty(span) {
+                    None => Elem::Word,
+                    Some(t) => self.elem_layout(&t.clone(), *span)?,
                 };
                 let arr = self.expr(base)?;
                 let idx = self.expr(index)?;
@@ -1024,7 +1691,7 @@ impl Lowerer<'_> {
                     arr,
                     idx,
                     loc,
-                    agg,
+                    elem: el,
                 });
                 Ok(dst)
             }
@@ -1039,92 +1706,28 @@ impl Lowerer<'_> {
                     .field_slots
                     .get(span)
                     .ok_or_else(|| unsupported("this field access", *span))?;
-                let kind = kind_of(&slot.ty, self.res, FUEL)
-                    .ok_or_else(|| unsupported("fields of this type", *span))?;
-                let def = &self.res.structs[&slot.base];
-                let off = offset_of(def, slot.index, self.res)
+                let off = leg_offset(&slot.base, slot.index, self.res)
                     .ok_or_else(|| unsupported("this struct layout", *span))?;
                 let slot_ty = slot.ty.clone();
-                let float = matches!(slot_ty, Type::Float);
                 let b = self.expr(base)?;
                 if *optional {
                     return self.optional_field(b, base, &slot_ty, off, *span);
                 }
-                // Narrowing proved an optional field present when the
-                // recorded type is the payload type (ADR 0021): read
-                // through the tag.
-                if let Some(inner) = self.opt_inner_of(&slot_ty)
-                    && !matches!(self.ty(span), Some(Type::Optional(_)))
-                {
-                    let k = kind_of(&inner, self.res, FUEL)
-                        .ok_or_else(|| unsupported("fields of this type", *span))?;
-                    let r = self.fresh(inner == Type::Float);
-                    self.insts.push(if k == Kind::Word {
-                        Inst::LoadAt {
-                            dst: r,
-                            base: b,
-                            off: off + 8,
-                        }
-                    } else {
-                        Inst::LeaAt {
-                            dst: r,
-                            base: b,
-                            off: off + 8,
-                        }
-                    });
-                    return Ok(r);
-                }
-                // The `T!` mirror (ADR 0037): a proven-value field reads
-                // its payload, a proven-error field reads the tag — it
-                // IS the code (same contract as locals, ADR 0034).
-                if let Some(inner) = self.err_inner_of(&slot_ty) {
-                    match self.ty(span) {
-                        Some(Type::ErrUnion(_)) | None => {}
-                        Some(Type::ErrCode) => return Ok(self.load_at(b, off)),
-                        Some(_) => {
-                            let k = kind_of(&inner, self.res, FUEL)
-                                .ok_or_else(|| unsupported("fields of this type", *span))?;
-                            let r = self.fresh(inner == Type::Float);
-                            self.insts.push(if k == Kind::Word {
-                                Inst::LoadAt {
-                                    dst: r,
-                                    base: b,
-                                    off: off + 8,
-                                }
-                            } else {
-                                Inst::LeaAt {
-                                    dst: r,
-                                    base: b,
-                                    off: off + 8,
-                                }
-                            });
-                            return Ok(r);
-                        }
-                    }
-                }
-                if kind == Kind::Word {
-                    // No null check: the checker's narrowing is sound, so
-                    // a plain `.` base is proven non-null (ADR 0007).
-                    let r = self.fresh(float);
-                    self.insts.push(Inst::LoadAt {
-                        dst: r,
-                        base: b,
-                        off,
-                    });
-                    Ok(r)
-                } else {
-                    // A struct/str-typed field's value is its storage
-                    // inside the base — an interior pointer; consumers
-                    // copy.
-                    let r = self.fresh(false);
-                    self.insts.push(Inst::LeaAt {
-                        dst: r,
-                        base: b,
-                        off,
-                    });
-                    Ok(r)
-                }
+                self.field_read(b, &slot_ty, off, *span)
             }
+            // `x.m(…)` on a variable (ADR 0040): the checker resolved
+            // a method, and the receiver is that variable.
+            Expr::EnumLit {
+                name, args, span, ..
+            } if self.res.call_targets.contains_key(span) => {
+                self.method_call(&Expr::enum_lit_receiver(name, *span), args, *span)
+            }
+            Expr::MethodCall {
+                receiver,
+                args,
+                span,
+                ..
+            } => self.method_call(receiver, args, *span),
             // Construction (ADR 0036): a frame temp — tag word, then
             // payloads at their packed offsets, slack zeroed so
             // equality can memcmp (canonical, like the optional null).
@@ -1182,6 +1785,46 @@ impl Lowerer<'_> {
                 }
                 Ok(t)
             }
+            // Built like a value struct literal (ADR 0043): a frame
+            // temp, each element stored at its position's offset.
+            Expr::Match {
+                scrutinee,
+                arms,
+                else_value,
+                span,
+            } => self.match_expr(scrutinee, arms, else_value.as_deref(), *span),
+            Expr::TupleLit { elements, span } => {
+                let tt = self
+                    .ty(span)
+                    .cloned()
+                    .ok_or_else(|| unsupported("this tuple literal", *span))?;
+                let total = kind_of(&tt, self.res, FUEL)
+                    .ok_or_else(|| unsupported("tuples with elements of this type", *span))?
+                    .words();
+                let legs =
+                    legs(&tt, self.res).ok_or_else(|| unsupported("this tuple literal", *span))?;
+                let base = self.fresh(false);
+                self.insts.push(Inst::Temp {
+                    dst: base,
+                    words: total.max(1),
+                });
+                for (element, (off, et)) in elements.iter().zip(legs) {
+                    let kind = kind_of(&et, self.res, FUEL)
+                        .ok_or_else(|| unsupported("tuples with elements of this type", *span))?;
+                    let val = self.expr_into(element, &et)?;
+                    if kind == Kind::Word {
+                        self.insts.push(Inst::StoreAt { base, off, val });
+                    } else {
+                        let p = self.lea_at(base, off);
+                        self.insts.push(Inst::CopyW {
+                            dst: p,
+                            src: val,
+                            words: kind.words(),
+                        });
+                    }
+                }
+                Ok(base)
+            }
             Expr::StructLit { fields, span, .. } => {
                 let Some(Type::Struct(dm, dn)) = self.ty(span) else {
                     return Err(unsupported("this struct literal", *span));
@@ -1211,7 +1854,7 @@ impl Lowerer<'_> {
                     let hdr = self.fresh(false);
                     self.insts.push(Inst::CallRt {
                         dst: hdr,
-                        sym: RT_MALLOC,
+                        sym: RT_ALLOC,
                         args: vec![size],
                         varargs: false,
                     });
@@ -1311,12 +1954,100 @@ impl Lowerer<'_> {
         self.scalar_binary(op, lhs, rhs, span)
     }
 
+    /// Reads the field stored `off` bytes into `b`, declared `slot_ty`,
+    /// as the checker typed the access at `span` — the plain `.` half of
+    /// `Expr::Field`, shared with compound assignment (ADR 0050).
+    fn field_read(&mut self, b: V, slot_ty: &Type, off: i64, span: Span) -> Result<V, Diagnostic> {
+        let kind = kind_of(slot_ty, self.res, FUEL)
+            .ok_or_else(|| unsupported("fields of this type", span))?;
+        let float = matches!(slot_ty, Type::Float);
+        // Narrowing proved an optional field present when the
+        // recorded type is the payload type (ADR 0021): read
+        // through the tag.
+        if let Some(inner) = self.opt_inner_of(slot_ty)
+            && !matches!(self.ty(&span), Some(Type::Optional(_)))
+        {
+            let k = kind_of(&inner, self.res, FUEL)
+                .ok_or_else(|| unsupported("fields of this type", span))?;
+            let r = self.fresh(inner == Type::Float);
+            self.insts.push(if k == Kind::Word {
+                Inst::LoadAt {
+                    dst: r,
+                    base: b,
+                    off: off + 8,
+                }
+            } else {
+                Inst::LeaAt {
+                    dst: r,
+                    base: b,
+                    off: off + 8,
+                }
+            });
+            return Ok(r);
+        }
+        // The `T!` mirror (ADR 0037): a proven-value field reads
+        // its payload, a proven-error field reads the error (same
+        // contract as locals, ADR 0034).
+        if let Some(inner) = self.err_inner_of(slot_ty) {
+            match self.ty(&span) {
+                Some(Type::ErrUnion(_)) | None => {}
+                Some(Type::ErrCode) => return self.error_read(b, off, span),
+                Some(_) => {
+                    let k = kind_of(&inner, self.res, FUEL)
+                        .ok_or_else(|| unsupported("fields of this type", span))?;
+                    let r = self.fresh(inner == Type::Float);
+                    self.insts.push(if k == Kind::Word {
+                        Inst::LoadAt {
+                            dst: r,
+                            base: b,
+                            off: off + 8,
+                        }
+                    } else {
+                        Inst::LeaAt {
+                            dst: r,
+                            base: b,
+                            off: off + 8,
+                        }
+                    });
+                    return Ok(r);
+                }
+            }
+        }
+        if kind == Kind::Word {
+            // No null check: the checker's narrowing is sound, so
+            // a plain `.` base is proven non-null (ADR 0007).
+            let r = self.fresh(float);
+            self.insts.push(Inst::LoadAt {
+                dst: r,
+                base: b,
+                off,
+            });
+            Ok(r)
+        } else {
+            // A struct/str-typed field's value is its storage
+            // inside the base — an interior pointer; consumers
+            // copy.
+            let r = self.fresh(false);
+            self.insts.push(Inst::LeaAt {
+                dst: r,
+                base: b,
+                off,
+            });
+            Ok(r)
+        }
+    }
+
     /// `a + b` on strings — the one explicitly allocating string
     /// operation (ADR 0013): new buffer, both byte runs copied, fresh
     /// descriptor in a statement temp.
     fn concat(&mut self, lhs: &Expr, rhs: &Expr) -> Result<V, Diagnostic> {
         let l = self.expr(lhs)?;
         let r = self.expr(rhs)?;
+        Ok(self.concat_strs(l, r))
+    }
+
+    /// The concatenation of two evaluated string descriptors.
+    fn concat_strs(&mut self, l: V, r: V) -> V {
         let la = self.load_at(l, 8);
         let lb = self.load_at(r, 8);
         let total = self.fresh(false);
@@ -1330,7 +2061,7 @@ impl Lowerer<'_> {
         let buf = self.fresh(false);
         self.insts.push(Inst::CallRt {
             dst: buf,
-            sym: RT_MALLOC,
+            sym: RT_ALLOC,
             args: vec![total],
             varargs: false,
         });
@@ -1370,7 +2101,7 @@ impl Lowerer<'_> {
             off: 8,
             val: total,
         });
-        Ok(out)
+        out
     }
 
     /// Scalar arithmetic and comparisons — IEEE via SSE for floats,
@@ -1383,76 +2114,24 @@ impl Lowerer<'_> {
         rhs: &Expr,
         span: Span,
     ) -> Result<V, Diagnostic> {
+        if let Some(t) = self.sized(lhs) {
+            let l = self.expr(lhs)?;
+            let r = self.expr(rhs)?;
+            return Ok(self.sized_op(op, t, l, r, span));
+        }
         let float = self.is_float(lhs);
-        let arith = !matches!(
-            op,
-            BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge
-        );
-        // Constant right operands strength-reduce: power-of-two div/rem
-        // to shift sequences, other divisors to magic multiplies,
-        // everything else to an immediate form (i32 range).
         if !float {
-            if let Expr::Int(n, _) = rhs {
-                let imm_ok = i32::try_from(*n).is_ok();
-                // k > 31 would put the 2^k - 1 bias outside leaq's 32-bit
-                // displacement; those divisors take the magic path.
-                let pow2 = *n >= 2 && (*n & (*n - 1)) == 0 && n.trailing_zeros() <= 31;
-                match op {
-                    BinOp::Div | BinOp::Rem if pow2 => {
-                        let l = self.expr(lhs)?;
-                        let v = self.fresh(false);
-                        let k = n.trailing_zeros();
-                        self.insts.push(if matches!(op, BinOp::Div) {
-                            Inst::DivPow2 { dst: v, src: l, k }
-                        } else {
-                            Inst::RemPow2 { dst: v, src: l, k }
-                        });
-                        return Ok(v);
-                    }
-                    BinOp::Div | BinOp::Rem if *n >= 2 => {
-                        let l = self.expr(lhs)?;
-                        let v = self.fresh(false);
-                        self.insts.push(if matches!(op, BinOp::Div) {
-                            Inst::DivMagic {
-                                dst: v,
-                                src: l,
-                                d: *n,
-                            }
-                        } else {
-                            Inst::RemMagic {
-                                dst: v,
-                                src: l,
-                                d: *n,
-                            }
-                        });
-                        return Ok(v);
-                    }
-                    BinOp::Add
-                    | BinOp::Sub
-                    | BinOp::Mul
-                    | BinOp::Eq
-                    | BinOp::Ne
-                    | BinOp::Lt
-                    | BinOp::Le
-                    | BinOp::Gt
-                    | BinOp::Ge
-                        if imm_ok =>
-                    {
-                        let l = self.expr(lhs)?;
-                        let v = self.fresh(false);
-                        self.insts.push(Inst::BinImm {
-                            op,
-                            dst: v,
-                            lhs: l,
-                            imm: *n,
-                        });
-                        return Ok(v);
-                    }
-                    _ => {}
-                }
+            if let Expr::Int(n, _) = rhs
+                && reducible(op, *n)
+            {
+                let l = self.expr(lhs)?;
+                return Ok(self.scalar_imm(op, l, *n));
             }
             if let Expr::Int(n, _) = lhs
-                && matches!(op, BinOp::Add | BinOp::Mul)
+                && matches!(
+                    op,
+                    BinOp::Add | BinOp::Mul | BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor
+                )
                 && i32::try_from(*n).is_ok()
             {
                 let r = self.expr(rhs)?;
@@ -1468,6 +2147,46 @@ impl Lowerer<'_> {
         }
         let l = self.expr(lhs)?;
         let r = self.expr(rhs)?;
+        Ok(self.scalar_op(op, float, l, r, span))
+    }
+
+    /// `l op n` on ints for a constant `n` that `reducible` accepts.
+    fn scalar_imm(&mut self, op: BinOp, l: V, n: i64) -> V {
+        // k > 31 would put the 2^k - 1 bias outside leaq's 32-bit
+        // displacement; those divisors take the magic path.
+        let pow2 = (n & (n - 1)) == 0 && n.trailing_zeros() <= 31;
+        let k = n.trailing_zeros();
+        let v = self.fresh(false);
+        self.insts.push(match op {
+            BinOp::Div if pow2 => Inst::DivPow2 { dst: v, src: l, k },
+            BinOp::Rem if pow2 => Inst::RemPow2 { dst: v, src: l, k },
+            BinOp::Div => Inst::DivMagic {
+                dst: v,
+                src: l,
+                d: n,
+            },
+            BinOp::Rem => Inst::RemMagic {
+                dst: v,
+                src: l,
+                d: n,
+            },
+            _ => Inst::BinImm {
+                op,
+                dst: v,
+                lhs: l,
+                imm: n,
+            },
+        });
+        v
+    }
+
+    /// `l op r` on evaluated scalars — the general form behind
+    /// `scalar_binary` and compound assignment (ADR 0050).
+    fn scalar_op(&mut self, op: BinOp, float: bool, l: V, r: V, span: Span) -> V {
+        let arith = !matches!(
+            op,
+            BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge
+        );
         // Runtime divisors go through the checked form: divisor zero and
         // MIN/-1 report and exit instead of trapping (ADR 0022).
         if !float && matches!(op, BinOp::Div | BinOp::Rem) {
@@ -1478,9 +2197,24 @@ impl Lowerer<'_> {
                 lhs: l,
                 rhs: r,
                 rem: matches!(op, BinOp::Rem),
+                unsigned: false,
                 loc,
             });
-            return Ok(v);
+            return v;
+        }
+        // Runtime counts likewise: outside 0..=63 reports and exits
+        // (ADR 0049).
+        if matches!(op, BinOp::Shl | BinOp::Shr | BinOp::UShr) {
+            let loc = self.loc_of(span);
+            let v = self.fresh(false);
+            self.insts.push(Inst::ShiftChecked {
+                op,
+                dst: v,
+                lhs: l,
+                rhs: r,
+                loc,
+            });
+            return v;
         }
         let v = self.fresh(float && arith);
         self.insts.push(Inst::Bin {
@@ -1490,7 +2224,67 @@ impl Lowerer<'_> {
             lhs: l,
             rhs: r,
         });
-        Ok(v)
+        v
+    }
+
+    /// `l op r` on a sized integer type (ADR 0052): the word operation
+    /// on canonical operands, narrowed again wherever the result can
+    /// leave the width. u64 compares and divides unsigned; `>>` is
+    /// logical on unsigned types, and `>>>` on a signed one shifts its
+    /// own-width bit pattern.
+    fn sized_op(&mut self, op: BinOp, t: IntTy, l: V, r: V, span: Span) -> V {
+        let u64 = t == IntTy::U64;
+        match op {
+            BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge if u64 => {
+                let v = self.fresh(false);
+                self.insts.push(Inst::CmpU {
+                    op,
+                    dst: v,
+                    lhs: l,
+                    rhs: r,
+                });
+                v
+            }
+            BinOp::Div | BinOp::Rem if u64 => {
+                let loc = self.loc_of(span);
+                let v = self.fresh(false);
+                self.insts.push(Inst::DivChecked {
+                    dst: v,
+                    lhs: l,
+                    rhs: r,
+                    rem: op == BinOp::Rem,
+                    unsigned: true,
+                    loc,
+                });
+                v
+            }
+            BinOp::Shr | BinOp::UShr if !t.signed() => {
+                self.scalar_op(BinOp::UShr, false, l, r, span)
+            }
+            BinOp::UShr => {
+                let bits = self.narrow(l, t.unsigned());
+                let v = self.scalar_op(BinOp::UShr, false, bits, r, span);
+                self.narrow(v, t)
+            }
+            // Only these leave the width: compares yield bools, and
+            // bitwise operations and `>>` keep canonical operands so.
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Shl => {
+                let v = self.scalar_op(op, false, l, r, span);
+                self.narrow(v, t)
+            }
+            _ => self.scalar_op(op, false, l, r, span),
+        }
+    }
+
+    /// `v` truncated to `t`'s width and re-extended (ADR 0052); a u64
+    /// fills the word already.
+    fn narrow(&mut self, v: V, t: IntTy) -> V {
+        if t.bits() == 64 {
+            return v;
+        }
+        let dst = self.fresh(false);
+        self.insts.push(Inst::Narrow { dst, src: v, ty: t });
+        dst
     }
 
     fn load_at(&mut self, base: V, off: i64) -> V {
@@ -1499,6 +2293,28 @@ impl Lowerer<'_> {
         dst
     }
 
+    /// Writes `v`, already shaped for the var's declared type, into a
+    /// module var's storage (ADR 0048).
+    fn store_global(&mut self, key: &(usize, String), v: V, span: Span) -> Result<(), Diagnostic> {
+        let kind = kind_of(&self.res.globals[key], self.res, FUEL)
+            .ok_or_else(|| unsupported("module vars of this type", span))?;
+        let base = self.lea_sym(global_label(key.0, &key.1));
+        if kind == Kind::Word {
+            self.insts.push(Inst::StoreAt {
+                base,
+                off: 0,
+                val: v,
+            });
+        } else {
+            self.insts.push(Inst::CopyW {
+                dst: base,
+                src: v,
+                words: kind.words(),
+            });
+        }
+        Ok(())
+    }
+
     fn lea_at(&mut self, base: V, off: i64) -> V {
         let dst = self.fresh(false);
         self.insts.push(Inst::LeaAt { dst, base, off });
@@ -1678,6 +2494,13 @@ impl Lowerer<'_> {
     }
 
     fn call(&mut self, callee: &Expr, args: &[Expr], span: Span) -> Result<V, Diagnostic> {
+        // A callee typed as a function value calls indirectly (ADR
+        // 0038): the pointer evaluates first, like the oracle.
+        if let Some(Type::Fn(params, ret)) = self.ty(&callee.span()).cloned() {
+            let f = self.expr(callee)?;
+            let args: Vec<&Expr> = args.iter().collect();
+            return self.call_with(&params, &ret, &args, span, Some(f), String::new());
+        }
         let Expr::Ident(name, _) = callee else {
             return Err(unsupported("this callee", span));
         };
@@ -1689,7 +2512,50 @@ impl Lowerer<'_> {
         };
         let res = self.res;
         let sig = &res.sigs[&key];
-        let ret_kind = match &sig.ret {
+        let args: Vec<&Expr> = args.iter().collect();
+        self.call_with(
+            &sig.params,
+            &sig.ret,
+            &args,
+            span,
+            None,
+            label_of(key.0, &key.1),
+        )
+    }
+
+    /// A method call (ADR 0040) is a direct call with the receiver as
+    /// the first argument — a value-struct `self` snapshots like any
+    /// multi-word argument, a refstruct passes its handle.
+    fn method_call(&mut self, receiver: &Expr, args: &[Expr], span: Span) -> Result<V, Diagnostic> {
+        let Some(key) = self.res.call_targets.get(&span).cloned() else {
+            return Err(unsupported("this method call", span));
+        };
+        let res = self.res;
+        let sig = &res.sigs[&key];
+        let args: Vec<&Expr> = std::iter::once(receiver).chain(args).collect();
+        self.call_with(
+            &sig.params,
+            &sig.ret,
+            &args,
+            span,
+            None,
+            label_of(key.0, &key.1),
+        )
+    }
+
+    /// The shared call sequence: arguments into the parameter slots,
+    /// the sret temp, then a direct call to `label` — or, with
+    /// `callee`, an indirect call through the function value.
+    fn call_with(
+        &mut self,
+        params: &[Type],
+        ret: &Type,
+        args: &[&Expr],
+        span: Span,
+        callee: Option<V>,
+        label: String,
+    ) -> Result<V, Diagnostic> {
+        let ret_kind = match ret {
             Type::Unit => Kind::Word,
             t => kind_of(t, self.res, FUEL)
                 .ok_or_else(|| unsupported("calls returning this type", span))?,
@@ -1699,9 +2565,8 @@ impl Lowerer<'_> {
         // TIME: the oracle copies as it evaluates, so a later argument
         // mutating the storage through an alias must not be visible.
         // The parameter type is the slot shape — optionals wrap here.
-        let params = sig.params.clone();
         let mut arg_vregs = Vec::new();
-        for (arg, pty) in args.iter().zip(&params) {
+        for (arg, pty) in args.iter().zip(params) {
             let kind = kind_of(pty, self.res, FUEL)
                 .ok_or_else(|| unsupported("calls with arguments of this type", span))?;
             let v = self.expr_into(arg, pty)?;
@@ -1719,16 +2584,132 @@ impl Lowerer<'_> {
             });
             t
         });
-        let dst = self.fresh(sig.ret == Type::Float);
-        self.insts.push(Inst::Call {
-            dst,
-            label: label_of(key.0, &key.1),
-            args: arg_vregs,
-            sret: sret_temp,
+        let dst = self.fresh(*ret == Type::Float);
+        self.insts.push(match callee {
+            Some(callee) => Inst::CallInd {
+                dst,
+                callee,
+                args: arg_vregs,
+                sret: sret_temp,
+            },
+            None => Inst::Call {
+                dst,
+                label,
+                args: arg_vregs,
+                sret: sret_temp,
+            },
         });
         Ok(dst)
     }
 
+    /// A function literal (ADR 0039): a heap object `{code, captures…}`
+    /// whose address is the function value — an indirect call moves it
+    /// into `%rax` and calls through its first word, exactly as for a
+    /// named function's static descriptor. Captures copy in at their
+    /// declared slot shape; the body lowers now, as its own function
+    /// whose captures bind by reading the object it receives.
+    fn lambda(&mut self, params: &[Param], body: &[Stmt], span: Span) -> Result<V, Diagnostic> {
+        let Some(Type::Fn(param_tys, ret)) = self.ty(&span).cloned() else {
+            return Err(unsupported("this function literal", span));
+        };
+        let res = self.res;
+        let captures = &res.captures[&span];
+        let mut kinds = Vec::with_capacity(captures.len());
+        for (_, ty) in captures {
+            kinds.push(
+                kind_of(ty, res, FUEL).ok_or_else(|| unsupported("captures of this type", span))?,
+            );
+        }
+        let name = format!("{}.fun{}", self.name, span.start);
+
+        let words: usize = kinds.iter().map(|k| k.words()).sum();
+        let size = self.const_word(8 * (1 + words) as i64);
+        let obj = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst: obj,
+            sym: RT_ALLOC,
+            args: vec![size],
+            varargs: false,
+        });
+        let code = self.lea_sym(label_of(self.module, &name));
+        self.insts.push(Inst::StoreAt {
+            base: obj,
+            off: 0,
+            val: code,
+        });
+        let mut off = 8;
+        for ((cname, _), kind) in captures.iter().zip(&kinds) {
+            let b = self
+                .lookup(cname)
+                .ok_or_else(|| unsupported("this capture", span))?;
+            if *kind == Kind::Word {
+                self.insts.push(Inst::StoreAt {
+                    base: obj,
+                    off,
+                    val: b.v,
+                });
+            } else {
+                let dst = self.lea_at(obj, off);
+                self.insts.push(Inst::CopyW {
+                    dst,
+                    src: b.v,
+                    words: kind.words(),
+                });
+            }
+            off += 8 * kind.words() as i64;
+        }
+
+        let mut lo = Lowerer::open(
+            res,
+            self.strings,
+            self.printers,
+            self.map,
+            self.module,
+            &name,
+            &ret,
+            span,
+        )?;
+        lo.bind_params(params, &param_tys, span)?;
+        let nparams = lo.vregs;
+        // Captures are immutable in the body, so multi-word ones bind
+        // as pointers straight into the object. A parameter never shares
+        // a capture's name: the checker only captures what the literal's
+        // own scopes don't bind.
+        let env = lo.fresh(false);
+        let mut off = 8;
+        for ((cname, ty), kind) in captures.iter().zip(&kinds) {
+            let v = lo.fresh(*ty == Type::Float);
+            lo.insts.push(if *kind == Kind::Word {
+                Inst::LoadAt {
+                    dst: v,
+                    base: env,
+                    off,
+                }
+            } else {
+                Inst::LeaAt {
+                    dst: v,
+                    base: env,
+                    off,
+                }
+            });
+            let opt_inner = lo.opt_inner_of(ty);
+            let err_inner = lo.err_inner_of(ty);
+            lo.scopes[0].insert(
+                cname.clone(),
+                Binding {
+                    v,
+                    opt_inner,
+                    err_inner,
+                },
+            );
+            off += 8 * kind.words() as i64;
+        }
+        lo.block(body)?;
+        let irs = lo.close(nparams, Some(env));
+        self.lambdas.extend(irs);
+        Ok(obj)
+    }
+
     fn builtin(&mut self, name: &str, args: &[Expr], span: Span) -> Result<V, Diagnostic> {
         match (name, args) {
             // The world interface (ADR 0031): open/write/close return
@@ -1806,6 +2787,48 @@ impl Lowerer<'_> {
                 });
                 Ok(dst)
             }
+            (syntax::BUILTIN_INSERT, [target, ..]) if !self.is_map(target) => {
+                self.array_builtin(name, args, span)
+            }
+            (syntax::BUILTIN_INSERT | syntax::BUILTIN_REMOVE | syntax::BUILTIN_HAS, _) => {
+                self.map_builtin(name, args, span)
+            }
+            (
+                syntax::BUILTIN_POP
+                | syntax::BUILTIN_REMOVE_AT
+                | syntax::BUILTIN_SLICE
+                | syntax::BUILTIN_CLEAR
+                | syntax::BUILTIN_SORT
+                | syntax::BUILTIN_REVERSE
+                | syntax::BUILTIN_INDEX_OF
+                | syntax::BUILTIN_CONTAINS,
+                _,
+            ) => self.array_builtin(name, args, span),
+            ("len", [s]) if self.is_str(s) => {
+                let desc = self.expr(s)?;
+                Ok(self.load_at(desc, 8))
+            }
+            (syntax::BUILTIN_CHARS, [s]) => self.chars(s),
+            (
+                syntax::BUILTIN_FIND
+                | syntax::BUILTIN_STARTS_WITH
+                | syntax::BUILTIN_ENDS_WITH
+                | syntax::BUILTIN_SPLIT
+                | syntax::BUILTIN_TRIM
+                | syntax::BUILTIN_REPLACE
+                | syntax::BUILTIN_JOIN
+                | syntax::BUILTIN_TO_UPPER
+                | syntax::BUILTIN_TO_LOWER
+                | syntax::BUILTIN_PARSE_INT
+                | syntax::BUILTIN_PARSE_FLOAT,
+                _,
+            ) => self.string_library(name, args, span),
+            // The type's length; the operand still runs.
+            ("len", [array]) if self.is_fixed(array) => {
+                let (_, _, len) = self.fixed_of(array)?;
+                self.expr(array)?;
+                Ok(self.const_word(len as i64))
+            }
             ("len", [array]) => {
                 let arr = self.expr(array)?;
                 let dst = self.fresh(false);
@@ -1818,6 +2841,29 @@ impl Lowerer<'_> {
                     .ok_or_else(|| unsupported("arrays of this element type", value.span()))?;
                 let arr = self.expr(array)?;
                 let val = self.expr_into(value, &elem)?;
+                // A packed element (ADR 0052) pushes its low bytes from
+                // a word of storage.
+                if let Some(Elem::Packed(t)) = elem_of(&elem, self.res) {
+                    let slot = self.fresh(false);
+                    self.insts.push(Inst::Temp {
+                        dst: slot,
+                        words: 1,
+                    });
+                    self.insts.push(Inst::StoreAt {
+                        base: slot,
+                        off: 0,
+                        val,
+                    });
+                    let stride = self.const_word(Elem::Packed(t).stride());
+                    let dst = self.fresh(false);
+                    self.insts.push(Inst::CallRt {
+                        dst,
+                        sym: RT_PUSH_N,
+                        args: vec![arr, slot, stride],
+                        varargs: false,
+                    });
+                    return Ok(dst);
+                }
                 if ek == Kind::Word {
                     let dst = self.fresh(false);
                     self.insts.push(Inst::CallRt {
@@ -1847,10 +2893,17 @@ impl Lowerer<'_> {
                     .cloned()
                     .ok_or_else(|| unsupported("printing this value", span))?;
                 match &ty {
+                    // Sized integers below u64 are canonical signed
+                    // words (ADR 0052); u64 prints through its show
+                    // routine.
                     Type::Int => {
                         let v = self.expr(value)?;
                         Ok(self.print_int(v))
                     }
+                    Type::Sized(t) if *t != IntTy::U64 => {
+                        let v = self.expr(value)?;
+                        Ok(self.print_int(v))
+                    }
                     Type::Bool => {
                         let v = self.expr(value)?;
                         Ok(self.print_bool(v))
@@ -2005,12 +3058,16 @@ impl Lowerer<'_> {
                 self.insts.push(Inst::Label(end));
                 return Ok(r);
             }
-            Type::Int => {
+            Type::Int | Type::Sized(_) => {
                 self.sb_reset();
                 let dst = self.fresh(false);
                 self.insts.push(Inst::CallRt {
                     dst,
-                    sym: RT_SB_INT,
+                    sym: if ty == Type::Sized(IntTy::U64) {
+                        RT_SB_U64
+                    } else {
+                        RT_SB_INT
+                    },
                     args: vec![v],
                     varargs: false,
                 });
@@ -2051,7 +3108,7 @@ impl Lowerer<'_> {
         let buf = self.fresh(false);
         self.insts.push(Inst::CallRt {
             dst: buf,
-            sym: RT_MALLOC,
+            sym: RT_ALLOC,
             args: vec![len],
             varargs: false,
         });
@@ -2144,3 +3201,28 @@ impl Lowerer<'_> {
         dst
     }
 }
+
+/// Whether `l op n` on ints has a strength-reduced form for the
+/// constant `n`: power-of-two div/rem to shift sequences, other
+/// divisors to magic multiplies, everything else to an immediate form
+/// (i32 range).
+fn reducible(op: BinOp, n: i64) -> bool {
+    match op {
+        BinOp::Div | BinOp::Rem => n >= 2,
+        // An in-range constant count needs no check.
+        BinOp::Shl | BinOp::Shr | BinOp::UShr => (0..64).contains(&n),
+        BinOp::Add
+        | BinOp::Sub
+        | BinOp::Mul
+        | BinOp::BitAnd
+        | BinOp::BitOr
+        | BinOp::BitXor
+        | BinOp::Eq
+        | BinOp::Ne
+        | BinOp::Lt
+        | BinOp::Le
+        | BinOp::Gt
+        | BinOp::Ge => i32::try_from(n).is_ok(),
+        _ => false,
+    }
+}
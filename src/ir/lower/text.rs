@@ -0,0 +1,203 @@
+//! Strings as bytes (ADR 0053). A string is a `{ptr, len}` descriptor
+//! over immutable bytes (ADR 0013), so reading inside one never copies:
+//! `s[i]` loads a byte behind a bounds check, `s[a..b]` is a new
+//! descriptor into the same bytes, and `for b in s` walks the pointer
+//! and length it read once. Only `chars(s)` allocates — the decoded
+//! `u32[]` is a new array.
+//!
+//! The string library (ADR 0054) is one runtime call per builtin. A
+//! result that is a string or an optional lands in a caller-provided
+//! temp, the way `s[a..b]` fills its descriptor; `bool` and `string[]`
+//! come back in the return register.
+
+use super::{Binding, Lowerer};
+use crate::ast::{BinOp, Expr, Stmt};
+use crate::codegen::{
+    RT_CHARS, RT_PARSE_FLOAT, RT_PARSE_INT, RT_STR_CASE, RT_STR_ENDS, RT_STR_FIND, RT_STR_JOIN,
+    RT_STR_REPLACE, RT_STR_SLICE, RT_STR_SPLIT, RT_STR_STARTS, RT_STR_TRIM,
+};
+use crate::diagnostic::Diagnostic;
+use crate::ir::{Inst, V};
+use crate::span::Span;
+use crate::syntax;
+use crate::types::{IntTy, Type};
+use std::collections::HashMap;
+
+impl Lowerer<'_> {
+    pub(super) fn is_str(&self, e: &Expr) -> bool {
+        matches!(self.ty(&e.span()), Some(Type::Str))
+    }
+
+    /// `s[i]`: the byte, zero-extended to its canonical `u8` word.
+    pub(super) fn str_index(
+        &mut self,
+        base: &Expr,
+        index: &Expr,
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let loc = self.loc_of(span);
+        let s = self.expr(base)?;
+        let idx = self.expr(index)?;
+        let dst = self.fresh(false);
+        self.insts.push(Inst::ByteAt { dst, s, idx, loc });
+        Ok(dst)
+    }
+
+    /// `s[lo..hi]` once the bounds are words: the runtime checks them
+    /// against the length and fills a fresh descriptor.
+    pub(super) fn str_slice(&mut self, s: V, lo: V, hi: V, loc: V) -> V {
+        let out = self.fresh(false);
+        self.insts.push(Inst::Temp { dst: out, words: 2 });
+        let d = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst: d,
+            sym: RT_STR_SLICE,
+            args: vec![out, s, lo, hi, loc],
+            varargs: false,
+        });
+        out
+    }
+
+    /// `for b in s`, `for [i, b] in s`: pointer and length are read
+    /// once — the bytes cannot change under the loop, and reassigning
+    /// the variable in the body does not move it.
+    pub(super) fn str_for(
+        &mut self,
+        index: Option<&String>,
+        name: &str,
+        iterable: &Expr,
+        body: &[Stmt],
+    ) -> Result<(), Diagnostic> {
+        let s = self.expr(iterable)?;
+        let p = self.load_at(s, 0);
+        let n = self.load_at(s, 8);
+        let i = self.const_word(0);
+        let top = self.fresh_label();
+        let end = self.fresh_label();
+        self.insts.push(Inst::Label(top));
+        let cond = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Lt,
+            float: false,
+            dst: cond,
+            lhs: i,
+            rhs: n,
+        });
+        self.insts.push(Inst::BrZero(cond, end));
+        let at = self.fresh(false);
+        self.insts.push(Inst::Bin {
+            op: BinOp::Add,
+            float: false,
+            dst: at,
+            lhs: p,
+            rhs: i,
+        });
+        let x = self.fresh(false);
+        self.insts.push(Inst::LoadPacked {
+            dst: x,
+            base: at,
+            off: 0,
+            ty: IntTy::U8,
+        });
+        let cont = self.fresh_label();
+        let mut bindings = HashMap::new();
+        bindings.insert(
+            name.to_string(),
+            Binding {
+                v: x,
+                opt_inner: None,
+                err_inner: None,
+            },
+        );
+        if let Some(ix) = index {
+            bindings.insert(
+                ix.clone(),
+                Binding {
+                    v: i,
+                    opt_inner: None,
+                    err_inner: None,
+                },
+            );
+        }
+        self.loops.push((cont, end));
+        let result = self.body_in(bindings, body);
+        self.loops.pop();
+        result?;
+        self.insts.push(Inst::Label(cont));
+        self.insts.push(Inst::BinImm {
+            op: BinOp::Add,
+            dst: i,
+            lhs: i,
+            imm: 1,
+        });
+        self.insts.push(Inst::Jmp(top));
+        self.insts.push(Inst::Label(end));
+        Ok(())
+    }
+
+    /// `chars(s)`: the code points as a packed `u32[]`.
+    pub(super) fn chars(&mut self, s: &Expr) -> Result<V, Diagnostic> {
+        let desc = self.expr(s)?;
+        let dst = self.fresh(false);
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym: RT_CHARS,
+            args: vec![desc],
+            varargs: false,
+        });
+        Ok(dst)
+    }
+
+    /// The string library builtins; the checker has fixed the arity.
+    pub(super) fn string_library(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        span: Span,
+    ) -> Result<V, Diagnostic> {
+        let mut vals = Vec::with_capacity(args.len() + 2);
+        for a in args {
+            vals.push(self.expr(a)?);
+        }
+        let (sym, into_temp, trapping) = match name {
+            syntax::BUILTIN_FIND => (RT_STR_FIND, true, false),
+            syntax::BUILTIN_STARTS_WITH => (RT_STR_STARTS, false, false),
+            syntax::BUILTIN_ENDS_WITH => (RT_STR_ENDS, false, false),
+            syntax::BUILTIN_SPLIT => (RT_STR_SPLIT, false, true),
+            syntax::BUILTIN_TRIM => (RT_STR_TRIM, true, false),
+            syntax::BUILTIN_REPLACE => (RT_STR_REPLACE, true, true),
+            syntax::BUILTIN_JOIN => (RT_STR_JOIN, true, false),
+            syntax::BUILTIN_TO_UPPER | syntax::BUILTIN_TO_LOWER => {
+                let upper = name == syntax::BUILTIN_TO_UPPER;
+                vals.push(self.const_word(upper as i64));
+                (RT_STR_CASE, true, false)
+            }
+            syntax::BUILTIN_PARSE_INT => (RT_PARSE_INT, true, false),
+            _ => (RT_PARSE_FLOAT, true, false),
+        };
+        if trapping {
+            let loc_lbl = self.loc_of(span);
+            vals.push(self.lea_sym(loc_lbl));
+        }
+        let dst = self.fresh(false);
+        if into_temp {
+            let out = self.fresh(false);
+            self.insts.push(Inst::Temp { dst: out, words: 2 });
+            vals.insert(0, out);
+            self.insts.push(Inst::CallRt {
+                dst,
+                sym,
+                args: vals,
+                varargs: false,
+            });
+            return Ok(out);
+        }
+        self.insts.push(Inst::CallRt {
+            dst,
+            sym,
+            args: vals,
+            varargs: false,
+        });
+        Ok(dst)
+    }
+}
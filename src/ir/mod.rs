@@ -21,7 +21,7 @@
 //! cleanly; CLI tests pin the error-path parity.
 
 mod emit;
-mod layout;
+pub(crate) mod layout;
 mod lower;
 mod regalloc;
 pub(crate) mod show;
@@ -30,8 +30,11 @@ use crate::ast::{BinOp, Function};
 use crate::check::Resolutions;
 use crate::codegen::Strings;
 use crate::diagnostic::Diagnostic;
+use crate::modules::ModuleGraph;
 use crate::source::SourceMap;
 use crate::span::Span;
+use crate::types::{IntTy, Type};
+use layout::Elem;
 use std::fmt;
 
 /// A virtual register.
@@ -43,6 +46,9 @@ pub(crate) struct FunctionIr {
     name: String,
     module: usize,
     nparams: usize,
+    /// A function literal's environment pointer (ADR 0039): arrives in
+    /// `%rax` — the closure object an indirect call goes through.
+    env: Option<V>,
     vregs: usize,
     floats: Vec<bool>,
     insts: Vec<Inst>,
@@ -55,7 +61,7 @@ pub(crate) fn lower_function(
     strings: &mut Strings,
     printers: &mut show::Printers,
     map: &SourceMap,
-) -> Result<FunctionIr, Diagnostic> {
+) -> Result<Vec<FunctionIr>, Diagnostic> {
     lower::lower(f, module, res, strings, printers, map)
 }
 
@@ -67,27 +73,31 @@ pub(crate) fn function(
     printers: &mut show::Printers,
     map: &SourceMap,
 ) -> Result<String, Diagnostic> {
-    Ok(emit::emit(lower_function(
-        f, module, res, strings, printers, map,
-    )?))
+    let irs = lower_function(f, module, res, strings, printers, map)?;
+    Ok(irs.into_iter().map(emit::emit).collect())
 }
 
-/// Like `function`, but emitted under `name` instead of the source
-/// name — the `main(): int!` implementation moves aside so the C entry
-/// can be a tag-testing wrapper (ADR 0034 decision 8). Lowering still
-/// sees the source name, so the entry-args materialization applies.
-pub(crate) fn function_as(
-    f: &Function,
-    module: usize,
+/// The module-var initializer (ADR 0048), with any function literals
+/// its initializers contain.
+pub(crate) fn lower_globals(
+    graph: &ModuleGraph,
+    res: &Resolutions,
+    strings: &mut Strings,
+    printers: &mut show::Printers,
+    map: &SourceMap,
+) -> Result<Vec<FunctionIr>, Diagnostic> {
+    lower::lower_globals(graph, res, strings, printers, map)
+}
+
+pub(crate) fn globals_init(
+    graph: &ModuleGraph,
     res: &Resolutions,
     strings: &mut Strings,
     printers: &mut show::Printers,
     map: &SourceMap,
-    name: &str,
 ) -> Result<String, Diagnostic> {
-    let mut ir = lower_function(f, module, res, strings, printers, map)?;
-    ir.name = name.to_string();
-    Ok(emit::emit(ir))
+    let irs = lower_globals(graph, res, strings, printers, map)?;
+    Ok(irs.into_iter().map(emit::emit).collect())
 }
 
 /// Emits one already-lowered function (the generated show routines).
@@ -95,6 +105,13 @@ pub(crate) fn emit_function(ir: FunctionIr) -> String {
     emit::emit(ir)
 }
 
+/// How many 8-byte words a value of `ty` occupies — for storage laid
+/// out outside any frame (module vars, ADR 0048). `None` when the type
+/// is not compilable; its uses report that.
+pub(crate) fn storage_words(ty: &Type, res: &Resolutions) -> Option<usize> {
+    layout::kind_of(ty, res, layout::FUEL).map(|k| k.words())
+}
+
 fn unsupported(what: &str, span: Span) -> Diagnostic {
     Diagnostic::error(format!("not yet compilable: {what}"), span)
 }
@@ -150,18 +167,48 @@ pub(crate) enum Inst {
     },
     /// Integer division with a runtime divisor: divisor-zero and
     /// MIN/-1 branch to the trap stubs before idiv (ADR 0022).
+    /// `unsigned` (u64, ADR 0052) divides with divq — only zero traps.
     DivChecked {
         dst: V,
         lhs: V,
         rhs: V,
         rem: bool,
+        unsigned: bool,
+        loc: String,
+    },
+    /// An unsigned comparison (u64, ADR 0052): `Bin`'s compare with the
+    /// below/above condition codes.
+    CmpU {
+        op: BinOp,
+        dst: V,
+        lhs: V,
+        rhs: V,
+    },
+    /// Truncates a word to a sized integer's width and re-extends it to
+    /// the canonical form (ADR 0052): sign for signed, zero for unsigned.
+    Narrow {
+        dst: V,
+        src: V,
+        ty: IntTy,
+    },
+    /// A shift by a runtime count (ADR 0049): a count outside 0..=63
+    /// branches to the trap stub before the shift.
+    ShiftChecked {
+        op: BinOp,
+        dst: V,
+        lhs: V,
+        rhs: V,
         loc: String,
     },
     Neg(V, V),
     NegF(V, V),
     Not(V, V),
+    /// `~`: the bitwise complement (ADR 0049); `Not` flips a bool.
+    BitNot(V, V),
     /// int → float: cvtsi2sd, nearest-even, total (ADR 0028).
     IntToFloat(V, V),
+    /// u64 → float (ADR 0052): the word read as unsigned, nearest-even.
+    UintToFloat(V, V),
     /// float → int, truncating toward zero; NaN and out-of-range
     /// report and exit 1 via the conversion trap stub (ADR 0028).
     FloatToInt {
@@ -177,6 +224,15 @@ pub(crate) enum Inst {
         /// hidden first argument.
         sret: Option<V>,
     },
+    /// A call through a function value (ADR 0038): `callee` points at a
+    /// function object whose first word is the code address. Arguments
+    /// and sret travel exactly as for `Call`.
+    CallInd {
+        dst: V,
+        callee: V,
+        args: Vec<V>,
+        sret: Option<V>,
+    },
     /// A runtime/libc call: a clobber point like Call. `varargs` zeroes
     /// %al (the SysV vector-register count) for printf.
     CallRt {
@@ -202,6 +258,14 @@ pub(crate) enum Inst {
         base: V,
         off: i64,
     },
+    /// A packed sized integer's load (ADR 0052), extended to its
+    /// canonical word.
+    LoadPacked {
+        dst: V,
+        base: V,
+        off: i64,
+        ty: IntTy,
+    },
     StoreAt {
         base: V,
         off: i64,
@@ -223,39 +287,69 @@ pub(crate) enum Inst {
         buf: V,
         len: usize,
     },
-    /// Literal element store at a constant slot: val into 8*i(buf).
+    /// Literal element store at a constant slot: val into
+    /// stride*slot(buf), a word or packed (ADR 0052).
     BufSet {
         buf: V,
         slot: usize,
         val: V,
+        elem: Elem,
     },
     Len(V, V),
     /// Bounds-checked element read (ADR 0008's runtime check; violation
-    /// reports and exits 1 via the OOB trap stub, ADR 0022). `agg` is
-    /// the pointer-shaped discipline (ADR 0023): `None` loads a word
-    /// element's value; `Some(words)` produces an interior pointer to
-    /// `data + idx*stride` — consumers copy. The kind decides, not the
-    /// width: a one-word value struct is still pointer-shaped.
+    /// reports and exits 1 via the OOB trap stub, ADR 0022). `elem` is
+    /// the pointer-shaped discipline (ADR 0023): `Word` and `Packed`
+    /// load the element's value; `Agg(words)` produces an interior
+    /// pointer to `data + idx*stride` — consumers copy. The kind
+    /// decides, not the width: a one-word value struct is still
+    /// pointer-shaped.
     Index {
         dst: V,
         arr: V,
         idx: V,
         loc: String,
-        agg: Option<usize>,
+        elem: Elem,
+    },
+    /// A string's byte (ADR 0053): `idx` bounds-checked against the
+    /// descriptor's length like `Index`, then zero-extended.
+    ByteAt {
+        dst: V,
+        s: V,
+        idx: V,
+        loc: String,
     },
-    /// Bounds-checked element write: `None` stores `val`'s value;
-    /// `Some(words)` copies that many words from the `val` pointer.
+    /// A fixed array's element address (ADR 0056): `idx` bounds-checked
+    /// against the constant `len` like `Index`, then `base + idx*stride`
+    /// — an interior pointer into the array's inline storage.
+    SlotAt {
+        dst: V,
+        base: V,
+        idx: V,
+        len: usize,
+        stride: i64,
+        loc: String,
+    },
+    /// Bounds-checked element write: `Word` and `Packed` store `val`'s
+    /// value; `Agg(words)` copies that many words from the `val` pointer.
     IndexSet {
         arr: V,
         idx: V,
         val: V,
         loc: String,
-        agg: Option<usize>,
+        elem: Elem,
     },
     Ret(V),
     Jmp(Lbl),
     /// Falls through when `cond` is nonzero, jumps when zero.
     BrZero(V, Lbl),
+    /// Jump table (ADR 0046): jumps to `table[value - lo]`, or to
+    /// `default` when `value` falls outside the table.
+    Switch {
+        value: V,
+        lo: i64,
+        table: Vec<Lbl>,
+        default: Lbl,
+    },
     Label(Lbl),
 }
 
@@ -275,6 +369,21 @@ fn op_name(op: BinOp) -> &'static str {
         BinOp::Gt => "gt",
         BinOp::Ge => "ge",
         BinOp::Coalesce => "coalesce",
+        BinOp::BitAnd => "bitand",
+        BinOp::BitOr => "bitor",
+        BinOp::BitXor => "bitxor",
+        BinOp::Shl => "shl",
+        BinOp::Shr => "shr",
+        BinOp::UShr => "ushr",
+    }
+}
+
+/// `.u8` for a packed element, `.3w` for an aggregate, nothing for a word.
+fn elem_suffix(elem: Elem) -> String {
+    match elem {
+        Elem::Word => String::new(),
+        Elem::Packed(t) => format!(".{}", t.name()),
+        Elem::Agg(w) => format!(".{w}w"),
     }
 }
 
@@ -323,18 +432,39 @@ impl fmt::Display for Inst {
                 lhs,
                 rhs,
                 rem,
+                unsigned,
                 loc,
             } => {
                 let name = if *rem { "rem" } else { "div" };
-                write!(f, "v{dst} = {name}.checked v{lhs}, v{rhs} @ {loc}")
+                let u = if *unsigned { "u" } else { "" };
+                write!(f, "v{dst} = {u}{name}.checked v{lhs}, v{rhs} @ {loc}")
+            }
+            Inst::CmpU { op, dst, lhs, rhs } => {
+                write!(f, "v{dst} = {}.unsigned v{lhs}, v{rhs}", op_name(*op))
+            }
+            Inst::Narrow { dst, src, ty } => {
+                write!(f, "v{dst} = narrow.{} v{src}", ty.name())
             }
+            Inst::ShiftChecked {
+                op,
+                dst,
+                lhs,
+                rhs,
+                loc,
+            } => write!(
+                f,
+                "v{dst} = {}.checked v{lhs}, v{rhs} @ {loc}",
+                op_name(*op)
+            ),
             Inst::Neg(dst, src) => write!(f, "v{dst} = neg.word v{src}"),
             Inst::IntToFloat(dst, src) => write!(f, "v{dst} = int_to_float v{src}"),
+            Inst::UintToFloat(dst, src) => write!(f, "v{dst} = uint_to_float v{src}"),
             Inst::FloatToInt { dst, src, loc } => {
                 write!(f, "v{dst} = float_to_int v{src} @ {loc}")
             }
             Inst::NegF(dst, src) => write!(f, "v{dst} = neg.float v{src}"),
             Inst::Not(dst, src) => write!(f, "v{dst} = not v{src}"),
+            Inst::BitNot(dst, src) => write!(f, "v{dst} = bitnot v{src}"),
             Inst::Call {
                 dst,
                 label,
@@ -347,6 +477,18 @@ impl fmt::Display for Inst {
                 }
                 Ok(())
             }
+            Inst::CallInd {
+                dst,
+                callee,
+                args,
+                sret,
+            } => {
+                write!(f, "v{dst} = call_ind v{callee}({})", vreg_list(args))?;
+                if let Some(sret) = sret {
+                    write!(f, ", sret v{sret}")?;
+                }
+                Ok(())
+            }
             Inst::CallRt {
                 dst,
                 sym,
@@ -366,6 +508,9 @@ impl fmt::Display for Inst {
             Inst::LoadAt { dst, base, off } => {
                 write!(f, "v{dst} = load v{base}{off:+}")
             }
+            Inst::LoadPacked { dst, base, off, ty } => {
+                write!(f, "v{dst} = load.{} v{base}{off:+}", ty.name())
+            }
             Inst::StoreAt { base, off, val } => {
                 write!(f, "store v{val} -> v{base}{off:+}")
             }
@@ -376,33 +521,69 @@ impl fmt::Display for Inst {
             Inst::StoreHdr { hdr, buf, len } => {
                 write!(f, "store_header v{hdr}, buffer v{buf}, len {len}")
             }
-            Inst::BufSet { buf, slot, val } => {
-                write!(f, "store_buffer v{val} -> v{buf}[{slot}]")
-            }
+            Inst::BufSet {
+                buf,
+                slot,
+                val,
+                elem,
+            } => write!(
+                f,
+                "store_buffer{} v{val} -> v{buf}[{slot}]",
+                elem_suffix(*elem)
+            ),
             Inst::Len(dst, arr) => write!(f, "v{dst} = len v{arr}"),
             Inst::Index {
                 dst,
                 arr,
                 idx,
                 loc,
-                agg,
-            } => match agg {
-                None => write!(f, "v{dst} = index v{arr}, v{idx} @ {loc}"),
-                Some(w) => write!(f, "v{dst} = index.{w}w v{arr}, v{idx} @ {loc}"),
-            },
+                elem,
+            } => write!(
+                f,
+                "v{dst} = index{} v{arr}, v{idx} @ {loc}",
+                elem_suffix(*elem)
+            ),
+            Inst::ByteAt { dst, s, idx, loc } => {
+                write!(f, "v{dst} = byte_at v{s}, v{idx} @ {loc}")
+            }
+            Inst::SlotAt {
+                dst,
+                base,
+                idx,
+                len,
+                stride,
+                loc,
+            } => write!(
+                f,
+                "v{dst} = slot_at v{base}, v{idx} < {len}, stride {stride} @ {loc}"
+            ),
             Inst::IndexSet {
                 arr,
                 idx,
                 val,
                 loc,
-                agg,
-            } => match agg {
-                None => write!(f, "index_set v{arr}, v{idx}, v{val} @ {loc}"),
-                Some(w) => write!(f, "index_set.{w}w v{arr}, v{idx}, v{val} @ {loc}"),
-            },
+                elem,
+            } => write!(
+                f,
+                "index_set{} v{arr}, v{idx}, v{val} @ {loc}",
+                elem_suffix(*elem)
+            ),
             Inst::Ret(value) => write!(f, "ret v{value}"),
             Inst::Jmp(label) => write!(f, "jump L{label}"),
             Inst::BrZero(value, label) => write!(f, "br_zero v{value}, L{label}"),
+            Inst::Switch {
+                value,
+                lo,
+                table,
+                default,
+            } => {
+                let table: Vec<String> = table.iter().map(|l| format!("L{l}")).collect();
+                write!(
+                    f,
+                    "switch v{value}, lo {lo}, [{}], default L{default}",
+                    table.join(", ")
+                )
+            }
             Inst::Label(label) => write!(f, "L{label}:"),
         }
     }
@@ -410,9 +591,13 @@ impl fmt::Display for Inst {
 
 impl fmt::Display for FunctionIr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let env = match self.env {
+            Some(v) => format!(", env v{v}"),
+            None => String::new(),
+        };
         writeln!(
             f,
-            "fn {} [module {}] (params {}, vregs {}) {{",
+            "fn {} [module {}] (params {}{env}, vregs {}) {{",
             self.name, self.module, self.nparams, self.vregs
         )?;
         let float_vregs: Vec<String> = self
@@ -442,6 +627,7 @@ mod tests {
             name: "probe".to_string(),
             module: 2,
             nparams: 1,
+            env: None,
             vregs: 5,
             floats: vec![false, false, false, false, false],
             insts: vec![
@@ -464,7 +650,19 @@ mod tests {
                     args: vec![3],
                     sret: Some(2),
                 },
+                Inst::CallInd {
+                    dst: 4,
+                    callee: 3,
+                    args: vec![1],
+                    sret: None,
+                },
                 Inst::BrZero(4, 0),
+                Inst::Switch {
+                    value: 4,
+                    lo: -1,
+                    table: vec![0, 1, 0],
+                    default: 1,
+                },
                 Inst::Label(0),
                 Inst::Ret(4),
             ],
@@ -476,7 +674,9 @@ mod tests {
              \x20\x20v2 = add.word v0, v1\n\
              \x20\x20v3 = load v2+8\n\
              \x20\x20v4 = call helper_1(v3), sret v2\n\
+             \x20\x20v4 = call_ind v3(v1)\n\
              \x20\x20br_zero v4, L0\n\
+             \x20\x20switch v4, lo -1, [L0, L1, L0], default L1\n\
              \x20\x20L0:\n\
              \x20\x20ret v4\n\
              }"
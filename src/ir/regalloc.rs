@@ -40,17 +40,25 @@ pub(super) fn uses_defs(inst: &Inst) -> (Vec<V>, Option<V>) {
     match inst {
         Inst::Const(d, _) => (vec![], Some(*d)),
         Inst::Copy(d, s) => (vec![*s], Some(*d)),
-        Inst::Bin { dst, lhs, rhs, .. } => (vec![*lhs, *rhs], Some(*dst)),
+        Inst::Bin { dst, lhs, rhs, .. } | Inst::CmpU { dst, lhs, rhs, .. } => {
+            (vec![*lhs, *rhs], Some(*dst))
+        }
         Inst::BinImm { dst, lhs, .. } => (vec![*lhs], Some(*dst)),
         Inst::DivPow2 { dst, src, .. }
         | Inst::RemPow2 { dst, src, .. }
         | Inst::DivMagic { dst, src, .. }
         | Inst::RemMagic { dst, src, .. } => (vec![*src], Some(*dst)),
         // The trap stubs never return, so this is no call-clobber point.
-        Inst::DivChecked { dst, lhs, rhs, .. } => (vec![*lhs, *rhs], Some(*dst)),
-        Inst::Neg(d, s) | Inst::NegF(d, s) | Inst::Not(d, s) | Inst::IntToFloat(d, s) => {
-            (vec![*s], Some(*d))
+        Inst::DivChecked { dst, lhs, rhs, .. } | Inst::ShiftChecked { dst, lhs, rhs, .. } => {
+            (vec![*lhs, *rhs], Some(*dst))
         }
+        Inst::Neg(d, s)
+        | Inst::NegF(d, s)
+        | Inst::Not(d, s)
+        | Inst::BitNot(d, s)
+        | Inst::IntToFloat(d, s)
+        | Inst::UintToFloat(d, s)
+        | Inst::Narrow { dst: d, src: s, .. } => (vec![*s], Some(*d)),
         // Like DivChecked: the trap never returns, so no call-clobber.
         Inst::FloatToInt { dst, src, .. } => (vec![*src], Some(*dst)),
         Inst::Call {
@@ -62,10 +70,23 @@ pub(super) fn uses_defs(inst: &Inst) -> (Vec<V>, Option<V>) {
             }
             (uses, Some(*dst))
         }
+        Inst::CallInd {
+            dst,
+            callee,
+            args,
+            sret,
+        } => {
+            let mut uses = args.clone();
+            uses.extend(sret);
+            uses.push(*callee);
+            (uses, Some(*dst))
+        }
         Inst::CallRt { dst, args, .. } => (args.clone(), Some(*dst)),
         Inst::Temp { dst, .. } => (vec![], Some(*dst)),
         Inst::CopyW { dst, src, .. } => (vec![*dst, *src], None),
-        Inst::LoadAt { dst, base, .. } => (vec![*base], Some(*dst)),
+        Inst::LoadAt { dst, base, .. } | Inst::LoadPacked { dst, base, .. } => {
+            (vec![*base], Some(*dst))
+        }
         Inst::StoreAt { base, val, .. } => (vec![*base, *val], None),
         Inst::LeaAt { dst, base, .. } => (vec![*base], Some(*dst)),
         Inst::LeaSym { dst, .. } => (vec![], Some(*dst)),
@@ -74,8 +95,10 @@ pub(super) fn uses_defs(inst: &Inst) -> (Vec<V>, Option<V>) {
         Inst::Len(d, arr) => (vec![*arr], Some(*d)),
         Inst::Index { dst, arr, idx, .. } => (vec![*arr, *idx], Some(*dst)),
         Inst::IndexSet { arr, idx, val, .. } => (vec![*arr, *idx, *val], None),
+        Inst::ByteAt { dst, s, idx, .. } => (vec![*s, *idx], Some(*dst)),
+        Inst::SlotAt { dst, base, idx, .. } => (vec![*base, *idx], Some(*dst)),
         Inst::Ret(v) => (vec![*v], None),
-        Inst::BrZero(v, _) => (vec![*v], None),
+        Inst::BrZero(v, _) | Inst::Switch { value: v, .. } => (vec![*v], None),
         Inst::Jmp(_) | Inst::Label(_) => (vec![], None),
     }
 }
@@ -99,6 +122,11 @@ pub(super) fn intervals(insts: &[Inst], vregs: usize) -> Vec<Interval> {
         .map(|(i, inst)| match inst {
             Inst::Jmp(l) => vec![label_pos[l]],
             Inst::BrZero(_, l) => vec![i + 1, label_pos[l]],
+            Inst::Switch { table, default, .. } => table
+                .iter()
+                .chain([default])
+                .map(|l| label_pos[l])
+                .collect(),
             Inst::Ret(_) => vec![],
             _ if i + 1 < insts.len() => vec![i + 1],
             _ => vec![],
@@ -157,18 +185,25 @@ pub(super) fn intervals(insts: &[Inst], vregs: usize) -> Vec<Interval> {
         }
     }
     for (i, inst) in insts.iter().enumerate() {
-        let is_call = matches!(inst, Inst::Call { .. } | Inst::CallRt { .. })
-            || matches!(
-                inst,
-                Inst::Bin {
-                    op: BinOp::Rem,
-                    float: true,
-                    ..
-                }
-            );
+        let is_call = matches!(
+            inst,
+            Inst::Call { .. } | Inst::CallInd { .. } | Inst::CallRt { .. }
+        ) || matches!(
+            inst,
+            Inst::Bin {
+                op: BinOp::Rem,
+                float: true,
+                ..
+            }
+        );
         if is_call {
+            // A vreg live into the call crosses it even when the call is
+            // its first touch — a parameter passed at instruction 0 and
+            // read again afterwards. Only the call's own result doesn't.
+            let def = ud[i].1;
             for iv in ivs.iter_mut() {
-                if iv.start < i && iv.end > i {
+                let live_in = iv.start < i || (iv.start == i && def != Some(iv.vreg));
+                if live_in && iv.end > i {
                     iv.crosses_call = true;
                 }
             }
@@ -4,12 +4,12 @@
 //! byte: name-sorted fields, raw strings, and the depth budget where a
 //! refstruct hop costs a level. `print` and `string()` both consume.
 
-use super::layout::{FUEL, Kind, kind_of, offset_of, ref_shaped};
+use super::layout::{Elem, FUEL, Kind, elem_of, kind_of, legs, offset_of, ref_shaped};
 use super::{FunctionIr, Inst, Lbl, V};
 use crate::ast::BinOp;
 use crate::check::Resolutions;
-use crate::codegen::{RT_FMT_F64, RT_SB_APPEND, RT_SB_INT, Strings, label_of};
-use crate::types::Type;
+use crate::codegen::{RT_FMT_F64, RT_SB_APPEND, RT_SB_INT, RT_SB_U64, Strings, label_of};
+use crate::types::{IntTy, Type};
 use std::collections::HashMap;
 
 /// The oracle's display budget (render.rs `display`): parity needs the
@@ -202,11 +202,16 @@ fn routine(
     // display_depth's entry check: depth 0 renders anything as "...".
     b.eq_ret_ellipsis(D, 0);
     match t {
-        Type::Int => {
+        // Canonical words (ADR 0052): only u64 needs unsigned digits.
+        Type::Int | Type::Sized(_) => {
             let dst = b.fresh();
             b.insts.push(Inst::CallRt {
                 dst,
-                sym: RT_SB_INT,
+                sym: if *t == Type::Sized(IntTy::U64) {
+                    RT_SB_U64
+                } else {
+                    RT_SB_INT
+                },
                 args: vec![X],
                 varargs: false,
             });
@@ -247,18 +252,29 @@ fn routine(
         // Opaque handle (ADR 0031): constant text — an address could
         // never match the oracle.
         Type::File => b.piece("file"),
+        // Function values (ADR 0038): same rule.
+        Type::Fn(..) => b.piece("fun"),
         // A declared error code (ADR 0034): `error.Name` selected by
-        // code.
-        Type::ErrCode => err_chain(&mut b, res, X),
+        // code, its payloads after it when errors carry them (ADR
+        // 0062) — then the value is a pointer to the code word.
+        Type::ErrCode => match kind_of(t, res, FUEL).expect("printable error") {
+            Kind::Word => err_chain(&mut b, printers, res, X, None),
+            _ => {
+                let code = b.load(X, 0);
+                err_chain(&mut b, printers, res, code, Some(X));
+            }
+        },
         // `T!` (ADR 0034): the tag decides — 0 renders the payload at
         // the same depth (stored unwrapped in the oracle, like
-        // optionals), ≥2 renders the code's name.
+        // optionals), ≥2 renders the error; its payloads share the
+        // words after the tag.
         Type::ErrUnion(inner) => {
             let tag = b.load(X, 0);
             let is_value = b.label();
             let end = b.label();
             b.insts.push(Inst::BrZero(tag, is_value));
-            err_chain(&mut b, res, tag);
+            let wide = kind_of(&Type::ErrCode, res, FUEL) != Some(Kind::Word);
+            err_chain(&mut b, printers, res, tag, wide.then_some(X));
             b.insts.push(Inst::Jmp(end));
             b.insts.push(Inst::Label(is_value));
             let k = kind_of(inner, res, FUEL).expect("printable payload");
@@ -291,7 +307,7 @@ fn routine(
             b.insts.push(Inst::Len(n, X));
             let data = b.load(X, 16);
             let dm = b.sub(D, 1);
-            let ek = kind_of(inner, res, FUEL).expect("printable element");
+            let el = elem_of(inner, res).expect("printable element");
             let child = printers.request(inner, res);
             let i = b.konst(0);
             let top = b.label();
@@ -325,7 +341,7 @@ fn routine(
                 op: BinOp::Mul,
                 dst: off,
                 lhs: i,
-                imm: 8 * ek.words() as i64,
+                imm: el.stride(),
             });
             let addr = b.fresh();
             b.insts.push(Inst::Bin {
@@ -335,10 +351,19 @@ fn routine(
                 lhs: data,
                 rhs: off,
             });
-            let v = if ek == Kind::Word {
-                b.load(addr, 0)
-            } else {
-                addr
+            let v = match el {
+                Elem::Word => b.load(addr, 0),
+                Elem::Packed(ty) => {
+                    let dst = b.fresh();
+                    b.insts.push(Inst::LoadPacked {
+                        dst,
+                        base: addr,
+                        off: 0,
+                        ty,
+                    });
+                    dst
+                }
+                Elem::Agg(_) => addr,
             };
             b.show(&child, v, dm);
             b.insts.push(Inst::BinImm {
@@ -351,6 +376,139 @@ fn routine(
             b.insts.push(Inst::Label(end));
             b.piece("]");
         }
+        // `[1, 2]` (ADR 0056): the array arm's loop over inline slots —
+        // no handle, so no null absorb, and the length is the type's.
+        Type::Fixed(inner, len) => {
+            b.piece("[");
+            let n = b.konst(*len as i64);
+            let dm = b.sub(D, 1);
+            let ek = kind_of(inner, res, FUEL).expect("printable element");
+            let child = printers.request(inner, res);
+            let i = b.konst(0);
+            let top = b.label();
+            let end = b.label();
+            b.insts.push(Inst::Label(top));
+            let c = b.fresh();
+            b.insts.push(Inst::Bin {
+                op: BinOp::Lt,
+                float: false,
+                dst: c,
+                lhs: i,
+                rhs: n,
+            });
+            b.insts.push(Inst::BrZero(c, end));
+            let first = b.fresh();
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Eq,
+                dst: first,
+                lhs: i,
+                imm: 0,
+            });
+            let comma = b.label();
+            let elem = b.label();
+            b.insts.push(Inst::BrZero(first, comma));
+            b.insts.push(Inst::Jmp(elem));
+            b.insts.push(Inst::Label(comma));
+            b.piece(", ");
+            b.insts.push(Inst::Label(elem));
+            let off = b.fresh();
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Mul,
+                dst: off,
+                lhs: i,
+                imm: 8 * ek.words() as i64,
+            });
+            let addr = b.fresh();
+            b.insts.push(Inst::Bin {
+                op: BinOp::Add,
+                float: false,
+                dst: addr,
+                lhs: X,
+                rhs: off,
+            });
+            let v = b.child(addr, 0, ek);
+            b.show(&child, v, dm);
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Add,
+                dst: i,
+                lhs: i,
+                imm: 1,
+            });
+            b.insts.push(Inst::Jmp(top));
+            b.insts.push(Inst::Label(end));
+            b.piece("]");
+        }
+        // `{k: v, …}` (ADR 0042): entries in insertion order, keys and
+        // values one level deeper — the oracle's `display_entries`.
+        Type::Map(kt, vt) => {
+            b.null_handle_ret();
+            b.piece("{");
+            let n = b.fresh();
+            b.insts.push(Inst::Len(n, X));
+            let entries = b.load(X, 16);
+            let dm = b.sub(D, 1);
+            let kk = kind_of(kt, res, FUEL).expect("printable key");
+            let vk = kind_of(vt, res, FUEL).expect("printable value");
+            let stride = 8 * (1 + kk.words() + vk.words()) as i64;
+            let kchild = printers.request(kt, res);
+            let vchild = printers.request(vt, res);
+            let i = b.konst(0);
+            let top = b.label();
+            let end = b.label();
+            b.insts.push(Inst::Label(top));
+            let c = b.fresh();
+            b.insts.push(Inst::Bin {
+                op: BinOp::Lt,
+                float: false,
+                dst: c,
+                lhs: i,
+                rhs: n,
+            });
+            b.insts.push(Inst::BrZero(c, end));
+            let first = b.fresh();
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Eq,
+                dst: first,
+                lhs: i,
+                imm: 0,
+            });
+            let comma = b.label();
+            let entry = b.label();
+            b.insts.push(Inst::BrZero(first, comma));
+            b.insts.push(Inst::Jmp(entry));
+            b.insts.push(Inst::Label(comma));
+            b.piece(", ");
+            b.insts.push(Inst::Label(entry));
+            let off = b.fresh();
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Mul,
+                dst: off,
+                lhs: i,
+                imm: stride,
+            });
+            let addr = b.fresh();
+            b.insts.push(Inst::Bin {
+                op: BinOp::Add,
+                float: false,
+                dst: addr,
+                lhs: entries,
+                rhs: off,
+            });
+            let k = b.child(addr, 8, kk);
+            b.show(&kchild, k, dm);
+            b.piece(": ");
+            let v = b.child(addr, 8 * (1 + kk.words()) as i64, vk);
+            b.show(&vchild, v, dm);
+            b.insts.push(Inst::BinImm {
+                op: BinOp::Add,
+                dst: i,
+                lhs: i,
+                imm: 1,
+            });
+            b.insts.push(Inst::Jmp(top));
+            b.insts.push(Inst::Label(end));
+            b.piece("}");
+        }
         // `Circle(1.5)` / `Ready` (ADR 0036): a tag chain into the
         // live variant's name and payloads, children one level deeper
         // — the oracle's `display_variant`, byte for byte.
@@ -390,6 +548,23 @@ fn routine(
             }
             b.insts.push(Inst::Label(end));
         }
+        // `(1, a)` (ADR 0043): elements in order, one level deeper —
+        // the oracle's `display_tuple`.
+        Type::Tuple(_) => {
+            let dm = b.sub(D, 1);
+            b.piece("(");
+            let legs = legs(t, res).expect("printable layout");
+            for (k, (off, et)) in legs.iter().enumerate() {
+                if k > 0 {
+                    b.piece(", ");
+                }
+                let ek = kind_of(et, res, FUEL).expect("printable element");
+                let v = b.child(X, *off, ek);
+                let child = printers.request(et, res);
+                b.show(&child, v, dm);
+            }
+            b.piece(")");
+        }
         Type::Struct(m, sname) => {
             let def = &res.structs[&(*m, sname.clone())];
             // A handle hop costs a level (render.rs): null absorb, the
@@ -435,11 +610,15 @@ fn routine(
 /// Appends `error.Name` for the code in `v` — a linear compare chain
 /// over the declared errors, codes from 2 in declaration order
 /// (ADR 0034). Shared by the bare `error` routine and the `T!` error
-/// branch.
+/// branch. `payloads` points at the code word of an error that may
+/// carry payloads (ADR 0062); such a code renders like a variant,
+/// `Name(12, "eof")`, its payloads one level down. A `string` payload
+/// is quoted, and a `string?` one when it holds a string.
 // ponytail: linear chain; a code-indexed .rodata table if programs
 // ever declare enough errors to feel it.
-fn err_chain(b: &mut B, res: &Resolutions, v: V) {
+fn err_chain(b: &mut B, printers: &mut Printers, res: &Resolutions, v: V, payloads: Option<V>) {
     let end = b.label();
+    let dm = payloads.map(|_| b.sub(D, 1));
     for (i, ename) in res.error_names.iter().enumerate() {
         let c = b.fresh();
         b.insts.push(Inst::BinImm {
@@ -450,7 +629,54 @@ fn err_chain(b: &mut B, res: &Resolutions, v: V) {
         });
         let next = b.label();
         b.insts.push(Inst::BrZero(c, next));
-        b.piece(&format!("error.{ename}"));
+        let pts = &res.error_payloads[i];
+        if let (Some(base), Some(dm)) = (payloads, dm)
+            && !pts.is_empty()
+        {
+            b.piece(&format!("{ename}("));
+            let mut off = 8i64;
+            for (k, pt) in pts.iter().enumerate() {
+                if k > 0 {
+                    b.piece(", ");
+                }
+                let pk = kind_of(pt, res, FUEL).expect("printable payload");
+                let pv = b.child(base, off, pk);
+                match pt {
+                    Type::Str => {
+                        let child = printers.request(pt, res);
+                        b.piece("\"");
+                        b.show(&child, pv, dm);
+                        b.piece("\"");
+                    }
+                    // A value-shaped optional: the tag picks quoted or
+                    // `null`, as the interpreter's does.
+                    Type::Optional(inner) if **inner == Type::Str => {
+                        let tag = b.load(pv, 0);
+                        let is_null = b.label();
+                        let done = b.label();
+                        b.insts.push(Inst::BrZero(tag, is_null));
+                        let sk = kind_of(inner, res, FUEL).expect("printable payload");
+                        let sv = b.child(pv, 8, sk);
+                        let child = printers.request(inner, res);
+                        b.piece("\"");
+                        b.show(&child, sv, dm);
+                        b.piece("\"");
+                        b.insts.push(Inst::Jmp(done));
+                        b.insts.push(Inst::Label(is_null));
+                        b.piece("null");
+                        b.insts.push(Inst::Label(done));
+                    }
+                    _ => {
+                        let child = printers.request(pt, res);
+                        b.show(&child, pv, dm);
+                    }
+                }
+                off += 8 * pk.words() as i64;
+            }
+            b.piece(")");
+        } else {
+            b.piece(&format!("error.{ename}"));
+        }
         b.insts.push(Inst::Jmp(end));
         b.insts.push(Inst::Label(next));
     }
@@ -463,6 +689,7 @@ fn finish(b: B, name: String) -> FunctionIr {
         name,
         module: 0,
         nparams: 2,
+        env: None,
         vregs,
         floats: vec![false; vregs],
         insts,
@@ -8,6 +8,7 @@ use crate::diagnostic::Diagnostic;
 use crate::span::Span;
 use crate::syntax;
 use crate::token::{Token, TokenKind};
+use crate::types::IntTy;
 
 /// Tokenizes a standalone source string (base offset 0) — the single-file
 /// convenience used throughout the test suites; production code lexes files
@@ -26,10 +27,12 @@ pub fn lex_at(source: &str, base: usize) -> (Vec<Token>, Vec<Diagnostic>) {
         base,
         diagnostics: Vec::new(),
         templates: Vec::new(),
+        after_dot: false,
     };
     let mut tokens = Vec::new();
     loop {
         let token = lexer.next_token();
+        lexer.after_dot = matches!(token.kind, TokenKind::Dot | TokenKind::QuestionDot);
         let is_eof = matches!(token.kind, TokenKind::Eof);
         tokens.push(token);
         if is_eof {
@@ -48,6 +51,9 @@ struct Lexer<'a> {
     /// entry counts the interpolation's own nested `{`…`}` pairs so a
     /// struct literal's `}` doesn't end the `${`.
     templates: Vec<usize>,
+    /// The previous token was `.`/`?.`: a number there is a tuple
+    /// index (ADR 0043), so `t.0.1` never lexes `0.1` as a float.
+    after_dot: bool,
 }
 
 impl Lexer<'_> {
@@ -124,19 +130,41 @@ impl Lexer<'_> {
             syntax::COLON => self.single(TokenKind::Colon),
             syntax::SEMICOLON => self.single(TokenKind::Semicolon),
             syntax::COMMA => self.single(TokenKind::Comma),
-            syntax::DOT => self.single(TokenKind::Dot),
-            syntax::EQUALS => self.maybe_eq(TokenKind::Equals, TokenKind::EqEq),
-            syntax::PLUS => self.single(TokenKind::Plus),
-            syntax::MINUS => self.single(TokenKind::Minus),
-            syntax::STAR => self.single(TokenKind::Asterisk),
-            syntax::PERCENT => self.single(TokenKind::Percent),
+            syntax::DOT => self.scan_dot(),
+            syntax::EQUALS => self.scan_equals(),
+            syntax::PLUS => self.maybe_double(
+                syntax::PLUS,
+                TokenKind::Plus,
+                TokenKind::PlusPlus,
+                TokenKind::PlusEq,
+            ),
+            syntax::MINUS => self.maybe_double(
+                syntax::MINUS,
+                TokenKind::Minus,
+                TokenKind::MinusMinus,
+                TokenKind::MinusEq,
+            ),
+            syntax::STAR => self.maybe_eq(TokenKind::Asterisk, TokenKind::AsteriskEq),
+            syntax::PERCENT => self.maybe_eq(TokenKind::Percent, TokenKind::PercentEq),
             syntax::BANG => self.maybe_eq(TokenKind::Bang, TokenKind::BangEq),
-            syntax::LESS => self.maybe_eq(TokenKind::Less, TokenKind::LessEq),
-            syntax::GREATER => self.maybe_eq(TokenKind::Greater, TokenKind::GreaterEq),
+            syntax::LESS => self.scan_less(),
+            syntax::GREATER => self.scan_greater(),
             syntax::QUOTE => self.scan_string(),
             syntax::BACKTICK => self.scan_template(true),
-            syntax::AMPERSAND => self.double(syntax::AMPERSAND, TokenKind::AmpAmp),
-            syntax::PIPE => self.double(syntax::PIPE, TokenKind::PipePipe),
+            syntax::AMPERSAND => self.maybe_double(
+                syntax::AMPERSAND,
+                TokenKind::Amp,
+                TokenKind::AmpAmp,
+                TokenKind::AmpEq,
+            ),
+            syntax::PIPE => self.maybe_double(
+                syntax::PIPE,
+                TokenKind::Pipe,
+                TokenKind::PipePipe,
+                TokenKind::PipeEq,
+            ),
+            syntax::CARET => self.maybe_eq(TokenKind::Caret, TokenKind::CaretEq),
+            syntax::TILDE => self.single(TokenKind::Tilde),
             syntax::QUESTION => self.scan_question(),
             syntax::SLASH => self.scan_slash_or_comment(),
             c if c.is_ascii_digit() => self.scan_number(),
@@ -159,7 +187,8 @@ impl Lexer<'_> {
     }
 
     /// Consumes one char; a following `=` upgrades `single` to `double`
-    /// (`=`→`==`, `!`→`!=`, `<`→`<=`, `>`→`>=`).
+    /// (`=`→`==`, `!`→`!=`, `<`→`<=`, `>`→`>=`, and the compound
+    /// assignments `*=`, `%=`, `^=` of ADR 0050, and `..`→`..=`).
     fn maybe_eq(&mut self, single: TokenKind, double: TokenKind) -> Option<TokenKind> {
         self.bump();
         if self.peek() == Some(syntax::EQUALS) {
@@ -170,13 +199,71 @@ impl Lexer<'_> {
         }
     }
 
-    /// `?` and its two-char forms: `??` (coalescing) and `?.` (chaining).
+    /// `=` and its two-char forms: `==` and `=>` (match-expression arms,
+    /// ADR 0044).
+    fn scan_equals(&mut self) -> Option<TokenKind> {
+        if self.source[self.pos + 1..].starts_with(syntax::GREATER) {
+            self.bump();
+            return self.single(TokenKind::FatArrow);
+        }
+        self.maybe_eq(TokenKind::Equals, TokenKind::EqEq)
+    }
+
+    /// `<`, `<=`, and the left shift `<<` (ADR 0049) with its
+    /// assigning form `<<=` (ADR 0050).
+    fn scan_less(&mut self) -> Option<TokenKind> {
+        if self.source[self.pos + 1..].starts_with(syntax::LESS) {
+            self.bump();
+            return self.maybe_eq(TokenKind::LessLess, TokenKind::LessLessEq);
+        }
+        self.maybe_eq(TokenKind::Less, TokenKind::LessEq)
+    }
+
+    /// `>`, `>=`, and the right shifts `>>` and `>>>` (ADR 0049), each
+    /// with its assigning form `>>=`/`>>>=` (ADR 0050). The lexer takes
+    /// the longest run; a type-argument list that closes onto one splits
+    /// it back into `>`s (`close_type_args`).
+    fn scan_greater(&mut self) -> Option<TokenKind> {
+        let more = self.source[self.pos + 1..]
+            .chars()
+            .take(2)
+            .take_while(|&c| c == syntax::GREATER)
+            .count();
+        match more {
+            0 => self.maybe_eq(TokenKind::Greater, TokenKind::GreaterEq),
+            1 => {
+                self.bump();
+                self.maybe_eq(TokenKind::GreaterGreater, TokenKind::GreaterGreaterEq)
+            }
+            _ => {
+                self.bump();
+                self.bump();
+                self.maybe_eq(
+                    TokenKind::GreaterGreaterGreater,
+                    TokenKind::GreaterGreaterGreaterEq,
+                )
+            }
+        }
+    }
+
+    /// `.`, and the range operators `..` and `..=` (ADR 0051). A number
+    /// never swallows the first dot of `0..n`: `scan_number` only takes
+    /// a dot followed by a digit.
+    fn scan_dot(&mut self) -> Option<TokenKind> {
+        self.bump();
+        if self.peek() != Some(syntax::DOT) {
+            return Some(TokenKind::Dot);
+        }
+        self.maybe_eq(TokenKind::DotDot, TokenKind::DotDotEq)
+    }
+
+    /// `?` and its two-char forms: `??` (coalescing) and `?.` (chaining),
+    /// plus `??=` (ADR 0050).
     fn scan_question(&mut self) -> Option<TokenKind> {
         self.bump();
         match self.peek() {
             Some(syntax::QUESTION) => {
-                self.bump();
-                Some(TokenKind::QuestionQuestion)
+                self.maybe_eq(TokenKind::QuestionQuestion, TokenKind::QuestionQuestionEq)
             }
             Some(syntax::DOT) => {
                 self.bump();
@@ -336,28 +423,32 @@ impl Lexer<'_> {
         }
     }
 
-    /// `first` is the current char and must be doubled (e.g. `&&`, `||`).
-    fn double(&mut self, first: char, both: TokenKind) -> Option<TokenKind> {
-        let start = self.pos;
+    /// `first` is the current char; doubled it is `both` (`&&`, `||`,
+    /// `++`, `--`), followed by `=` it is `assign` (`&=`, `+=`, ADR
+    /// 0050), and alone it is `single`.
+    fn maybe_double(
+        &mut self,
+        first: char,
+        single: TokenKind,
+        both: TokenKind,
+        assign: TokenKind,
+    ) -> Option<TokenKind> {
         self.bump();
-        if self.peek() == Some(first) {
-            self.bump();
-            Some(both)
-        } else {
-            let found = match self.peek() {
-                Some(c) => format!("'{c}'"),
-                None => "end of input".to_string(),
-            };
-            self.error(
-                format!("expected '{first}{first}', found {found}"),
-                self.abs_span(start),
-            );
-            None
+        match self.peek() {
+            Some(c) if c == first => {
+                self.bump();
+                Some(both)
+            }
+            Some(syntax::EQUALS) => {
+                self.bump();
+                Some(assign)
+            }
+            _ => Some(single),
         }
     }
 
-    /// A `/` is either a `//` line comment (to end of line, on any platform) or
-    /// the division operator.
+    /// A `/` is either a `//` line comment (to end of line, on any platform),
+    /// the division operator, or `/=` (ADR 0050).
     fn scan_slash_or_comment(&mut self) -> Option<TokenKind> {
         self.bump(); // first '/'
         if self.peek() == Some(syntax::SLASH) {
@@ -368,6 +459,9 @@ impl Lexer<'_> {
                 self.bump();
             }
             None
+        } else if self.peek() == Some(syntax::EQUALS) {
+            self.bump();
+            Some(TokenKind::SlashEq)
         } else {
             Some(TokenKind::Slash)
         }
@@ -379,7 +473,7 @@ impl Lexer<'_> {
             self.bump();
         }
         let mut is_float = false;
-        if self.peek() == Some(syntax::DOT) {
+        if self.peek() == Some(syntax::DOT) && !self.after_dot {
             let after_dot = self.source[self.pos + 1..].chars().next();
             if matches!(after_dot, Some(c) if c.is_ascii_digit()) {
                 is_float = true;
@@ -445,10 +539,23 @@ impl Lexer<'_> {
             syntax::KW_BOOL => TokenKind::BoolType,
             syntax::KW_STRING => TokenKind::StringType,
             syntax::KW_FILE => TokenKind::FileType,
+            syntax::KW_I64 => TokenKind::IntType,
+            syntax::KW_I8 => TokenKind::SizedType(IntTy::I8),
+            syntax::KW_I16 => TokenKind::SizedType(IntTy::I16),
+            syntax::KW_I32 => TokenKind::SizedType(IntTy::I32),
+            syntax::KW_U8 => TokenKind::SizedType(IntTy::U8),
+            syntax::KW_U16 => TokenKind::SizedType(IntTy::U16),
+            syntax::KW_U32 => TokenKind::SizedType(IntTy::U32),
+            syntax::KW_U64 => TokenKind::SizedType(IntTy::U64),
+            syntax::KW_BYTE => TokenKind::SizedType(IntTy::U8),
             syntax::KW_ERROR => TokenKind::ErrorKw,
             syntax::KW_TRY => TokenKind::Try,
             syntax::KW_ENUM => TokenKind::Enum,
             syntax::KW_MATCH => TokenKind::Match,
+            syntax::KW_IMPL => TokenKind::Impl,
+            syntax::KW_INTERFACE => TokenKind::Interface,
+            syntax::KW_DEFER => TokenKind::Defer,
+            syntax::KW_ARENA => TokenKind::Arena,
             other => TokenKind::Identifier(other.to_string()),
         }
     }
@@ -545,19 +652,75 @@ mod tests {
     }
 
     #[test]
-    fn lone_ampersand_reports_what_was_found() {
-        let (_, diags) = lex("a &x");
-        assert_eq!(diags.len(), 1);
-        assert!(
-            diags[0].message.contains("expected '&&', found 'x'"),
-            "{diags:?}"
+    fn bitwise_operators_take_the_longest_run() {
+        assert_eq!(
+            kinds("& | ^ ~ << >> >>> <<< >>>> > >= &&& |||"),
+            vec![
+                TokenKind::Amp,
+                TokenKind::Pipe,
+                TokenKind::Caret,
+                TokenKind::Tilde,
+                TokenKind::LessLess,
+                TokenKind::GreaterGreater,
+                TokenKind::GreaterGreaterGreater,
+                TokenKind::LessLess,
+                TokenKind::Less,
+                TokenKind::GreaterGreaterGreater,
+                TokenKind::Greater,
+                TokenKind::Greater,
+                TokenKind::GreaterEq,
+                TokenKind::AmpAmp,
+                TokenKind::Amp,
+                TokenKind::PipePipe,
+                TokenKind::Pipe,
+                TokenKind::Eof
+            ]
         );
-        let (_, diags) = lex("a &");
-        assert!(
-            diags[0]
-                .message
-                .contains("expected '&&', found end of input"),
-            "{diags:?}"
+    }
+
+    #[test]
+    fn compound_assignments_lex_as_one_token() {
+        assert_eq!(
+            kinds("+= -= *= /= %= &= |= ^= <<= >>= >>>= ??= ++ -- == >= // c"),
+            vec![
+                TokenKind::PlusEq,
+                TokenKind::MinusEq,
+                TokenKind::AsteriskEq,
+                TokenKind::SlashEq,
+                TokenKind::PercentEq,
+                TokenKind::AmpEq,
+                TokenKind::PipeEq,
+                TokenKind::CaretEq,
+                TokenKind::LessLessEq,
+                TokenKind::GreaterGreaterEq,
+                TokenKind::GreaterGreaterGreaterEq,
+                TokenKind::QuestionQuestionEq,
+                TokenKind::PlusPlus,
+                TokenKind::MinusMinus,
+                TokenKind::EqEq,
+                TokenKind::GreaterEq,
+                TokenKind::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn ranges_do_not_lex_as_floats() {
+        assert_eq!(
+            kinds("0..n 1..=2 1.5 x.0"),
+            vec![
+                TokenKind::IntLiteral(0),
+                TokenKind::DotDot,
+                TokenKind::Identifier("n".to_string()),
+                TokenKind::IntLiteral(1),
+                TokenKind::DotDotEq,
+                TokenKind::IntLiteral(2),
+                TokenKind::FloatLiteral(1.5),
+                TokenKind::Identifier("x".to_string()),
+                TokenKind::Dot,
+                TokenKind::IntLiteral(0),
+                TokenKind::Eof
+            ]
         );
     }
 
@@ -640,6 +803,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sized_integer_keywords() {
+        assert_eq!(
+            kinds("i8 u8 i16 u16 i32 u32 i64 u64 u128"),
+            vec![
+                TokenKind::SizedType(IntTy::I8),
+                TokenKind::SizedType(IntTy::U8),
+                TokenKind::SizedType(IntTy::I16),
+                TokenKind::SizedType(IntTy::U16),
+                TokenKind::SizedType(IntTy::I32),
+                TokenKind::SizedType(IntTy::U32),
+                TokenKind::IntType,
+                TokenKind::SizedType(IntTy::U64),
+                TokenKind::Identifier("u128".to_string()),
+                TokenKind::Eof
+            ]
+        );
+        // A string's element is spelled `byte` (ADR 0053).
+        assert_eq!(
+            kinds("byte"),
+            vec![TokenKind::SizedType(IntTy::U8), TokenKind::Eof]
+        );
+    }
+
     #[test]
     fn string_literal_with_escapes() {
         assert_eq!(
@@ -111,7 +111,7 @@ fn run() {
         Mode::Interpret { args } => match interpreter::interpret(&graph, &resolutions, &args) {
             // `main(): int!` escaping with an error (ADR 0034): the
             // trap-shaped exit — stderr message, code 1, no result line.
-            Ok((value @ interpreter::Value::Err(_), heap)) => {
+            Ok((value @ interpreter::Value::Err(..), heap)) => {
                 let name = String::from_utf8_lossy(&value.display(&heap)).into_owned();
                 let _ = writeln!(std::io::stderr(), "error: {name}");
                 std::process::exit(1);
@@ -31,8 +31,11 @@ pub struct Module {
 
 /// The program's import graph. Modules are numbered in discovery order —
 /// the entry file is always index 0 — which keeps output deterministic.
+/// `init_order` lists them dependencies first, the entry last: the
+/// order module vars initialize in (ADR 0048).
 pub struct ModuleGraph {
     pub modules: Vec<Module>,
+    pub init_order: Vec<usize>,
 }
 
 /// Loads a program starting at `entry`: parse it, discover its imports, load
@@ -157,11 +160,21 @@ pub fn load_program(
         .map(|m| m.expect("every discovered module is loaded"))
         .collect();
 
-    if let Some(cycle) = detect_cycle(&modules) {
-        diags.push(cycle);
-    }
+    let init_order = match order_modules(&modules) {
+        Ok(order) => order,
+        Err(cycle) => {
+            diags.push(cycle);
+            (0..modules.len()).collect()
+        }
+    };
     diags.sort_by_key(|d| (d.span.start, d.span.end));
-    Ok((ModuleGraph { modules }, diags))
+    Ok((
+        ModuleGraph {
+            modules,
+            init_order,
+        },
+        diags,
+    ))
 }
 
 /// Resolves an import path relative to the importing file, lexically
@@ -192,9 +205,12 @@ fn resolve_path(importer: &str, import: &str) -> String {
     path
 }
 
-/// Rejects import cycles: depth-first search over the import edges; a back
-/// edge to a module still on the stack is a cycle, reported with its path.
-fn detect_cycle(modules: &[Module]) -> Option<Diagnostic> {
+/// Orders modules dependencies first and rejects import cycles: depth-first
+/// search over the import edges, in import order. A module finishes after
+/// everything it imports — the post-order is the initialization order. A
+/// back edge to a module still on the stack is a cycle, reported with its
+/// path.
+fn order_modules(modules: &[Module]) -> Result<Vec<usize>, Diagnostic> {
     const UNSEEN: u8 = 0;
     const ON_STACK: u8 = 1;
     const DONE: u8 = 2;
@@ -204,6 +220,7 @@ fn detect_cycle(modules: &[Module]) -> Option<Diagnostic> {
         modules: &[Module],
         color: &mut [u8],
         stack: &mut Vec<usize>,
+        order: &mut Vec<usize>,
     ) -> Option<(usize, Span)> {
         color[v] = ON_STACK;
         stack.push(v);
@@ -211,7 +228,7 @@ fn detect_cycle(modules: &[Module]) -> Option<Diagnostic> {
             match color[binding.target] {
                 ON_STACK => return Some((binding.target, binding.span)),
                 UNSEEN => {
-                    if let Some(found) = dfs(binding.target, modules, color, stack) {
+                    if let Some(found) = dfs(binding.target, modules, color, stack, order) {
                         return Some(found);
                     }
                 }
@@ -220,21 +237,23 @@ fn detect_cycle(modules: &[Module]) -> Option<Diagnostic> {
         }
         color[v] = DONE;
         stack.pop();
+        order.push(v);
         None
     }
 
     let mut color = vec![UNSEEN; modules.len()];
     let mut stack = Vec::new();
+    let mut order = Vec::with_capacity(modules.len());
     for v in 0..modules.len() {
         if color[v] == UNSEEN {
-            if let Some((back_to, span)) = dfs(v, modules, &mut color, &mut stack) {
+            if let Some((back_to, span)) = dfs(v, modules, &mut color, &mut stack, &mut order) {
                 let from = stack.iter().position(|&m| m == back_to).unwrap_or(0);
                 let mut names: Vec<&str> = stack[from..]
                     .iter()
                     .map(|&m| modules[m].path.as_str())
                     .collect();
                 names.push(&modules[back_to].path);
-                return Some(Diagnostic::error(
+                return Err(Diagnostic::error(
                     format!("import cycle: {}", names.join(" → ")),
                     span,
                 ));
@@ -242,7 +261,7 @@ fn detect_cycle(modules: &[Module]) -> Option<Diagnostic> {
             stack.clear();
         }
     }
-    None
+    Ok(order)
 }
 
 #[cfg(test)]
@@ -317,6 +336,8 @@ mod tests {
             graph.modules[1].imports[0].target,
             graph.modules[2].imports[0].target
         );
+        // Dependencies initialize first, the shared module once.
+        assert_eq!(graph.init_order, [3, 1, 2, 0]);
     }
 
     #[test]
@@ -88,8 +88,9 @@ pub(crate) fn condition_facts(cond: &Expr) -> (HashMap<String, Fact>, HashMap<St
 
 /// Does this statement list never fall through — every path ends in
 /// `return`, `break`, or `continue`? Loops never count: a contained
-/// `break` targets the loop itself, and `while true` analysis stays out,
-/// consistent with definite return (ADR 0020).
+/// `break` may end the loop itself (or, labeled, an outer one — ADR
+/// 0059), and `while true` analysis stays out, consistent with definite
+/// return (ADR 0020).
 pub(crate) fn diverges(stmts: &[Stmt]) -> bool {
     stmts.iter().any(|stmt| match stmt {
         Stmt::Return { .. } | Stmt::Break { .. } | Stmt::Continue { .. } => true,
@@ -97,14 +98,18 @@ pub(crate) fn diverges(stmts: &[Stmt]) -> bool {
             then_body,
             else_body: Some(else_body),
             ..
-        } => diverges(then_body) && diverges(else_body),
-        // A match diverges only with an `else` — this analysis is pure
-        // syntax and cannot prove exhaustiveness (ADR 0036).
-        Stmt::Match {
-            arms,
+        }
+        | Stmt::IfConst {
+            then_body,
             else_body: Some(else_body),
             ..
-        } => arms.iter().all(|a| diverges(&a.body)) && diverges(else_body),
+        } => diverges(then_body) && diverges(else_body),
+        // The checker rejects a match that leaves a variant uncovered
+        // (ADR 0044), so without `else` the arms alone are every path.
+        Stmt::Match {
+            arms, else_body, ..
+        } => arms.iter().all(|a| diverges(&a.body)) && else_body.as_deref().is_none_or(diverges),
+        Stmt::Arena { body, .. } => diverges(body),
         _ => false,
     })
 }
@@ -113,24 +118,51 @@ pub(crate) fn diverges(stmts: &[Stmt]) -> bool {
 /// refstruct they can reach, which kills field-path narrowing facts.
 pub(crate) fn contains_call(e: &Expr) -> bool {
     match e {
-        Expr::Call { .. } => true,
+        Expr::Call { .. } | Expr::MethodCall { .. } => true,
         Expr::Unary { rhs, .. } => contains_call(rhs),
         Expr::Convert { arg, .. } => contains_call(arg),
         Expr::Binary { lhs, rhs, .. } => contains_call(lhs) || contains_call(rhs),
         Expr::Field { base, .. } => contains_call(base),
         Expr::StructLit { fields, .. } => fields.iter().any(|(_, v)| contains_call(v)),
-        // Construction only evaluates its payloads — but they may call.
-        Expr::EnumLit { args, .. } => args.iter().any(contains_call),
-        Expr::ArrayLit { elements, .. } => elements.iter().any(contains_call),
+        // `x.m(…)` on a variable is a method call (ADR 0040), and syntax
+        // can't tell it from enum construction — assume the call.
+        Expr::EnumLit { .. } => true,
+        Expr::ArrayLit { elements, .. } | Expr::TupleLit { elements, .. } => {
+            elements.iter().any(contains_call)
+        }
+        Expr::ArrayRepeat { value, .. } => contains_call(value),
+        Expr::MapLit { entries, .. } => entries
+            .iter()
+            .any(|(k, v)| contains_call(k) || contains_call(v)),
         Expr::Index { base, index, .. } => contains_call(base) || contains_call(index),
+        Expr::Range {
+            start, end, step, ..
+        } => [start, end, step]
+            .into_iter()
+            .any(|b| b.as_deref().is_some_and(contains_call)),
         Expr::Try { expr, .. } => contains_call(expr),
+        Expr::ErrorLit { args, .. } => args.iter().any(contains_call),
+        Expr::Match {
+            scrutinee,
+            arms,
+            else_value,
+            ..
+        } => {
+            contains_call(scrutinee)
+                || arms
+                    .iter()
+                    .any(|a| contains_call(&a.body) || a.guard.as_ref().is_some_and(contains_call))
+                || else_value.as_deref().is_some_and(contains_call)
+        }
         Expr::Int(..)
         | Expr::Float(..)
         | Expr::Bool(..)
         | Expr::Str(..)
         | Expr::Ident(..)
+        | Expr::FnRef { .. }
+        // Creating a closure runs nothing (ADR 0039).
+        | Expr::Lambda { .. }
         | Expr::Null(_)
-        | Expr::ErrorLit(..)
         | Expr::ErrorKind(_) => false,
     }
 }
@@ -153,10 +185,23 @@ pub(crate) fn body_effects(
                     *kills_fields = true;
                 }
             }
-            Stmt::Let { value, .. } | Stmt::Expr(value) => {
+            Stmt::Let { value, .. }
+            | Stmt::LetTuple { value, .. }
+            | Stmt::LetStruct { value, .. }
+            | Stmt::Expr(value) => {
+                if contains_call(value) {
+                    *kills_fields = true;
+                }
+            }
+            Stmt::LetElse {
+                value, else_body, ..
+            } => {
                 if contains_call(value) {
                     *kills_fields = true;
                 }
+                if let Some(else_body) = else_body {
+                    body_effects(else_body, assigned, kills_fields);
+                }
             }
             Stmt::Return { value, .. } => {
                 if value.as_ref().is_some_and(contains_call) {
@@ -165,6 +210,10 @@ pub(crate) fn body_effects(
             }
             // No expressions, no writes — inert for narrowing (ADR 0019).
             Stmt::Break { .. } | Stmt::Continue { .. } => {}
+            // Runs at the end of every pass the defer was reached in
+            // (ADR 0060).
+            Stmt::Defer { body, .. } => body_effects(body, assigned, kills_fields),
+            Stmt::Arena { body, .. } => body_effects(body, assigned, kills_fields),
             Stmt::If {
                 cond,
                 then_body,
@@ -179,7 +228,24 @@ pub(crate) fn body_effects(
                     body_effects(else_body, assigned, kills_fields);
                 }
             }
-            Stmt::While { cond, body, .. } => {
+            Stmt::IfConst {
+                value,
+                then_body,
+                else_body,
+                ..
+            } => {
+                if contains_call(value) {
+                    *kills_fields = true;
+                }
+                body_effects(then_body, assigned, kills_fields);
+                if let Some(else_body) = else_body {
+                    body_effects(else_body, assigned, kills_fields);
+                }
+            }
+            Stmt::While { cond, body, .. }
+            | Stmt::WhileConst {
+                value: cond, body, ..
+            } => {
                 if contains_call(cond) {
                     *kills_fields = true;
                 }
@@ -197,7 +263,8 @@ pub(crate) fn body_effects(
                 else_body,
                 ..
             } => {
-                if contains_call(scrutinee) {
+                if contains_call(scrutinee) || arms.iter().flat_map(|a| &a.guard).any(contains_call)
+                {
                     *kills_fields = true;
                 }
                 for arm in arms {
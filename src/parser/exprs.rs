@@ -20,8 +20,9 @@ impl Parser {
         let mut lhs = self.parse_prefix();
         loop {
             // `ident <` may open a type-argument list (ADR 0035): commit
-            // only when a well-formed list closes onto `(` or `{`,
-            // otherwise fall through to comparison. Binds like postfix.
+            // only when a well-formed list closes onto `(` or `{` (or
+            // ends the operand, ADR 0038), otherwise fall through to
+            // comparison. Binds like postfix.
             if self.check(&TokenKind::Less)
                 && matches!(lhs, Expr::Ident(..))
                 && POSTFIX_BP >= min_bp
@@ -75,6 +76,7 @@ impl Parser {
         let op = match tok.kind {
             TokenKind::Minus => UnOp::Neg,
             TokenKind::Bang => UnOp::Not,
+            TokenKind::Tilde => UnOp::BitNot,
             _ => return self.parse_atom(),
         };
         self.bump();
@@ -162,14 +164,24 @@ impl Parser {
             TokenKind::True => Expr::Bool(true, tok.span),
             TokenKind::False => Expr::Bool(false, tok.span),
             TokenKind::Null => Expr::Null(tok.span),
-            // `error.Name` — an error-code literal; bare `error` is the
-            // state marker for `==`/`!=` tests (ADR 0034) — the checker
+            // `error.Name` — an error-code literal, with its payloads
+            // when a list follows (ADR 0062); bare `error` is the state
+            // marker for `==`/`!=` tests (ADR 0034) — the checker
             // rejects it anywhere else.
             TokenKind::ErrorKw => {
                 if self.eat(&TokenKind::Dot) {
                     let name_span = self.peek().span;
                     let name = self.expect_identifier();
-                    Expr::ErrorLit(name, tok.span.to(name_span))
+                    let mut span = tok.span.to(name_span);
+                    let mut args = Vec::new();
+                    if self.eat(&TokenKind::LeftParen) {
+                        let (list, end) = self.arg_list();
+                        if self.claim_op(end) {
+                            args = list;
+                            span = span.to(end);
+                        }
+                    }
+                    Expr::ErrorLit { name, args, span }
                 } else {
                     Expr::ErrorKind(tok.span)
                 }
@@ -193,9 +205,12 @@ impl Parser {
                 }
             }
             // `int(x)` / `float(x)` / `string(x)` — conversion calls
-            // (ADR 0028/0029); the type keywords cannot be shadowed, so
-            // the form is unambiguous.
-            kind @ (TokenKind::IntType | TokenKind::FloatType | TokenKind::StringType) => {
+            // (ADR 0028/0029), and `u8(x)` & co. (ADR 0052); the type
+            // keywords cannot be shadowed, so the form is unambiguous.
+            kind @ (TokenKind::IntType
+            | TokenKind::FloatType
+            | TokenKind::StringType
+            | TokenKind::SizedType(_)) => {
                 self.expect(TokenKind::LeftParen);
                 let arg = self.parse_expr(0);
                 let end = self.expect(TokenKind::RightParen);
@@ -203,6 +218,7 @@ impl Parser {
                     to: match kind {
                         TokenKind::IntType => Conv::Int,
                         TokenKind::FloatType => Conv::Float,
+                        TokenKind::SizedType(t) => Conv::Sized(t),
                         _ => Conv::Str,
                     },
                     implicit: false,
@@ -211,13 +227,40 @@ impl Parser {
                 }
             }
             TokenKind::TemplateHead(first) => self.parse_template(first, tok.span),
+            TokenKind::Fun => self.parse_lambda(tok.span),
+            // In expression position `match` yields a value (ADR 0044);
+            // at statement start it stays the block form.
+            TokenKind::Match => self.parse_match_expr(tok.span),
             TokenKind::LeftParen => {
                 // Parentheses re-enable struct literals inside a condition.
                 let prev = self.struct_literals_allowed;
                 self.struct_literals_allowed = true;
                 let inner = self.parse_expr(0);
+                // A comma makes it a tuple literal (ADR 0043).
+                if self.eat(&TokenKind::Comma) {
+                    let mut elements = vec![inner];
+                    while !self.check(&TokenKind::RightParen) && !self.at_eof() {
+                        elements.push(self.parse_expr(0));
+                        if !self.eat(&TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    self.struct_literals_allowed = prev;
+                    let end = self.expect(TokenKind::RightParen);
+                    if elements.len() < 2 {
+                        self.error(
+                            "a tuple has at least two elements — '(x)' is a grouping".to_string(),
+                            tok.span.to(end),
+                        );
+                    }
+                    return Expr::TupleLit {
+                        elements,
+                        span: tok.span.to(end),
+                    };
+                }
                 self.struct_literals_allowed = prev;
                 self.expect(TokenKind::RightParen);
+                self.grouped = Some(inner.span());
                 inner
             }
             TokenKind::LeftBracket => {
@@ -228,6 +271,18 @@ impl Parser {
                 let mut elements = Vec::new();
                 while !self.check(&TokenKind::RightBracket) && !self.at_eof() {
                     elements.push(self.parse_expr(0));
+                    // `[v; N]` repeats one value into a fixed array
+                    // (ADR 0056).
+                    if elements.len() == 1 && self.eat(&TokenKind::Semicolon) {
+                        let count = self.fixed_len();
+                        self.struct_literals_allowed = prev;
+                        let end = self.expect(TokenKind::RightBracket);
+                        return Expr::ArrayRepeat {
+                            value: Box::new(elements.remove(0)),
+                            count,
+                            span: tok.span.to(end),
+                        };
+                    }
                     if !self.eat(&TokenKind::Comma) {
                         break;
                     }
@@ -239,6 +294,29 @@ impl Parser {
                     span: tok.span.to(end),
                 }
             }
+            // `{ k: v, … }` — a map literal (ADR 0042). Blocks never
+            // start an expression, so the brace is unambiguous here;
+            // the entries re-enable struct literals like array brackets.
+            TokenKind::LeftBrace => {
+                let prev = self.struct_literals_allowed;
+                self.struct_literals_allowed = true;
+                let mut entries = Vec::new();
+                while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
+                    let key = self.parse_expr(0);
+                    self.expect(TokenKind::Colon);
+                    let value = self.parse_expr(0);
+                    entries.push((key, value));
+                    if !self.eat(&TokenKind::Comma) {
+                        break;
+                    }
+                }
+                self.struct_literals_allowed = prev;
+                let end = self.expect(TokenKind::RightBrace);
+                Expr::MapLit {
+                    entries,
+                    span: tok.span.to(end),
+                }
+            }
             other => {
                 self.error(
                     format!("expected an expression, found {}", describe(&other)),
@@ -249,6 +327,74 @@ impl Parser {
         }
     }
 
+    /// `fun (x: int): int { … }` — a function literal (ADR 0039); the
+    /// `fun` keyword is already consumed. The body is a full block, so
+    /// struct literals are back on even inside a condition.
+    fn parse_lambda(&mut self, start: Span) -> Expr {
+        if !self.enter_nested() {
+            return Expr::Int(0, start); // recovery placeholder
+        }
+        let (params, return_type) = self.parse_signature();
+        let prev = self.struct_literals_allowed;
+        self.struct_literals_allowed = true;
+        let (body, end, _) = self.parse_block();
+        self.struct_literals_allowed = prev;
+        self.depth -= 1;
+        Expr::Lambda {
+            params,
+            return_type,
+            body,
+            span: start.to(end),
+        }
+    }
+
+    /// `match s { V(a) => e, …, else => e }` — comma-separated arms,
+    /// a trailing comma allowed; `else` closes the list.
+    fn parse_match_expr(&mut self, start: Span) -> Expr {
+        if !self.enter_nested() {
+            return Expr::Int(0, start); // recovery placeholder
+        }
+        let scrutinee = self.parse_condition();
+        self.expect(TokenKind::LeftBrace);
+        let prev = self.struct_literals_allowed;
+        self.struct_literals_allowed = true;
+        let mut arms = Vec::new();
+        let mut else_value = None;
+        while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
+            if self.eat(&TokenKind::Else) {
+                self.expect(TokenKind::FatArrow);
+                else_value = Some(Box::new(self.parse_expr(0)));
+                self.eat(&TokenKind::Comma);
+                break;
+            }
+            let start = self.peek().span;
+            let Some((pattern, guard)) = self.parse_arm_head() else {
+                break; // malformed arm head: the '}' check reports it
+            };
+            self.expect(TokenKind::FatArrow);
+            let body = self.parse_expr(0);
+            let span = start.to(body.span());
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+                span,
+            });
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.struct_literals_allowed = prev;
+        let end = self.expect(TokenKind::RightBrace);
+        self.depth -= 1;
+        Expr::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            else_value,
+            span: start.to(end),
+        }
+    }
+
     /// Speculative type-argument suffix on an identifier (ADR 0035).
     /// `Ok` is the committed call or struct literal; `Err` hands the
     /// identifier back untouched — position and diagnostics rolled back
@@ -256,6 +402,7 @@ impl Parser {
     fn try_generic_suffix(&mut self, lhs: Expr) -> Result<Expr, Expr> {
         let start_pos = self.pos;
         let start_diags = self.diagnostics.len();
+        let start_splits = self.splits.len();
         self.bump(); // '<'
         let mut type_args = Vec::new();
         loop {
@@ -266,15 +413,21 @@ impl Parser {
         }
         // Commit needs a clean list (no diagnostics — speculation is
         // silent), a plain `>`, and `(`, `{`, or `.` right after — the
-        // dot is qualified enum construction (ADR 0036).
+        // dot is qualified enum construction (ADR 0036) — or a token
+        // that ends an operand, where no comparison could continue:
+        // `identity<int>` named as a value (ADR 0038).
         let commit = self.diagnostics.len() == start_diags
             && self.eat(&TokenKind::Greater)
             && (self.check(&TokenKind::LeftParen)
                 || self.check(&TokenKind::Dot)
-                || (self.struct_literals_allowed && self.check(&TokenKind::LeftBrace)));
+                || (self.struct_literals_allowed && self.check(&TokenKind::LeftBrace))
+                || self.at_operand_end());
         if !commit {
             self.pos = start_pos;
             self.diagnostics.truncate(start_diags);
+            for (at, tok) in self.splits.drain(start_splits..).rev() {
+                self.tokens[at] = tok;
+            }
             return Err(lhs);
         }
         if self.eat(&TokenKind::LeftParen) {
@@ -283,6 +436,14 @@ impl Parser {
         let Expr::Ident(name, span) = lhs else {
             unreachable!("caller guarantees an identifier lhs")
         };
+        if self.at_operand_end() {
+            let end = self.tokens[self.pos - 1].span;
+            return Ok(Expr::FnRef {
+                name,
+                type_args,
+                span: span.to(end),
+            });
+        }
         if self.eat(&TokenKind::Dot) {
             // `Result<int, string>.Ok(3)` — the applied name can only
             // be a type, so the shape is unambiguous from here on.
@@ -293,6 +454,19 @@ impl Parser {
         Ok(self.parse_struct_literal(name, span, type_args))
     }
 
+    /// A token no binary operator or postfix can follow an operand
+    /// with — `)`, `,`, `;`, `]`, `}`.
+    fn at_operand_end(&self) -> bool {
+        matches!(
+            self.peek().kind,
+            TokenKind::RightParen
+                | TokenKind::Comma
+                | TokenKind::Semicolon
+                | TokenKind::RightBracket
+                | TokenKind::RightBrace
+        )
+    }
+
     /// Construction arguments after `Enum.Variant(` was consumed
     /// (ADR 0036).
     fn enum_lit_tail(
@@ -302,17 +476,7 @@ impl Parser {
         type_args: Vec<TypeAnn>,
         variant: String,
     ) -> Expr {
-        let prev = self.struct_literals_allowed;
-        self.struct_literals_allowed = true;
-        let mut args = Vec::new();
-        while !self.check(&TokenKind::RightParen) && !self.at_eof() {
-            args.push(self.parse_expr(0));
-            if !self.eat(&TokenKind::Comma) {
-                break;
-            }
-        }
-        self.struct_literals_allowed = prev;
-        let end = self.expect(TokenKind::RightParen);
+        let (args, end) = self.arg_list();
         if !self.claim_op(end) {
             return Expr::Ident(name, start);
         }
@@ -325,11 +489,10 @@ impl Parser {
         }
     }
 
-    /// Call arguments after the `(` was consumed — shared by plain
-    /// postfix calls and committed generic suffixes.
-    fn parse_call_tail(&mut self, lhs: Expr, type_args: Vec<TypeAnn>) -> Expr {
-        // Call parentheses re-enable struct literals inside a
-        // condition, same as grouping parentheses.
+    /// Arguments through the closing `)`, after the `(` was consumed,
+    /// with the `)` span. Call parentheses re-enable struct literals
+    /// inside a condition, same as grouping parentheses.
+    fn arg_list(&mut self) -> (Vec<Expr>, Span) {
         let prev = self.struct_literals_allowed;
         self.struct_literals_allowed = true;
         let mut args = Vec::new();
@@ -340,26 +503,42 @@ impl Parser {
             }
         }
         self.struct_literals_allowed = prev;
-        let end = self.expect(TokenKind::RightParen);
+        (args, self.expect(TokenKind::RightParen))
+    }
+
+    /// Call arguments after the `(` was consumed — shared by plain
+    /// postfix calls and committed generic suffixes.
+    fn parse_call_tail(&mut self, lhs: Expr, type_args: Vec<TypeAnn>) -> Expr {
+        let (args, end) = self.arg_list();
         if !self.claim_op(end) {
             return lhs;
         }
         let span = lhs.span().to(end);
-        // `ident.ident(…)` is qualified enum construction (ADR 0036) —
-        // there are no methods, so no call shape is stolen; the checker
-        // decides whether the base names an enum.
-        if let Expr::Field {
-            base,
-            name: variant,
-            optional: false,
-            ..
-        } = &lhs
-            && let Expr::Ident(enum_name, _) = base.as_ref()
+        // `ident.ident(…)` is qualified enum construction (ADR 0036)
+        // or a method call on a variable — the checker decides whether
+        // the base names an enum. Any other `e.ident(…)` is a method
+        // call (ADR 0040). A parenthesized `(p.f)(…)` calls the field's
+        // value (ADR 0038).
+        if self.grouped != Some(lhs.span())
+            && let Expr::Field {
+                base,
+                name: method,
+                optional: false,
+                ..
+            } = lhs
         {
-            return Expr::EnumLit {
-                name: enum_name.clone(),
-                type_args: Vec::new(),
-                variant: variant.clone(),
+            if let Expr::Ident(enum_name, _) = *base {
+                return Expr::EnumLit {
+                    name: enum_name,
+                    type_args: Vec::new(),
+                    variant: method,
+                    args,
+                    span,
+                };
+            }
+            return Expr::MethodCall {
+                receiver: base,
+                method,
                 args,
                 span,
             };
@@ -372,6 +551,43 @@ impl Parser {
         }
     }
 
+    pub(super) fn at_range(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::DotDot | TokenKind::DotDotEq)
+    }
+
+    /// A range from its `..`/`..=` on (ADR 0051): the end, then an
+    /// optional contextual `step`. Only slice bounds (`open`) may omit
+    /// an end; the checker rejects a slice's step.
+    pub(super) fn parse_range(&mut self, start: Option<Expr>, open: bool) -> Expr {
+        let op = self.advance();
+        let inclusive = op.kind == TokenKind::DotDotEq;
+        let end = if open && !inclusive && self.check(&TokenKind::RightBracket) {
+            None
+        } else {
+            Some(self.parse_expr(0))
+        };
+        let step = match &self.peek().kind {
+            TokenKind::Identifier(w) if w == syntax::RANGE_STEP => {
+                self.bump();
+                Some(Box::new(self.parse_expr(0)))
+            }
+            _ => None,
+        };
+        let first = start.as_ref().map_or(op.span, Expr::span);
+        let last = match (&step, &end) {
+            (Some(s), _) => s.span(),
+            (None, Some(e)) => e.span(),
+            (None, None) => op.span,
+        };
+        Expr::Range {
+            start: start.map(Box::new),
+            end: end.map(Box::new),
+            inclusive,
+            step,
+            span: first.to(last),
+        }
+    }
+
     pub(super) fn parse_postfix(&mut self, lhs: Expr) -> Expr {
         match self.peek().kind {
             TokenKind::LeftParen => {
@@ -384,7 +600,17 @@ impl Parser {
                 // condition, same as call parentheses.
                 let prev = self.struct_literals_allowed;
                 self.struct_literals_allowed = true;
-                let index = self.parse_expr(0);
+                // `xs[a..b]`, `xs[a..]`, `xs[..b]` — slice bounds.
+                let index = if self.at_range() {
+                    self.parse_range(None, true)
+                } else {
+                    let index = self.parse_expr(0);
+                    if self.at_range() {
+                        self.parse_range(Some(index), true)
+                    } else {
+                        index
+                    }
+                };
                 self.struct_literals_allowed = prev;
                 let end = self.expect(TokenKind::RightBracket);
                 if !self.claim_op(end) {
@@ -403,6 +629,8 @@ impl Parser {
                 let field = self.advance();
                 let (name, name_span) = match field.kind {
                     TokenKind::Identifier(n) => (n, field.span),
+                    // `pair.0` — a tuple element (ADR 0043).
+                    TokenKind::IntLiteral(n) => (n.to_string(), field.span),
                     other => {
                         self.error(
                             format!("expected a field name, found {}", describe(&other)),
@@ -1,14 +1,15 @@
 //! Top-level declarations and type annotations: `fun`, `struct`,
-//! `refstruct`, `import`, and the `T?`/`T[]` annotation grammar —
+//! `refstruct`, `impl`, `interface`, `import`, and the `T?`/`T[]`/`T[N]`/`fun(T): U` annotation grammar —
 //! plus item-boundary error recovery (`synchronize`).
 
 use super::*;
 
 impl Parser {
     /// A base type with postfix suffixes: `?` (optional), `!` (error
-    /// union, ADR 0034), and `[]` (array), composable left to right —
-    /// `int?[]` is an array of optional ints, `int[]?` an optional
-    /// array, `int[]!` an array-or-error. Doubling the optional is
+    /// union, ADR 0034), `[]` (array), and `[N]` (fixed array, ADR
+    /// 0056), composable left to right — `int?[]` is an array of
+    /// optional ints, `int[]?` an optional array, `int[]!` an
+    /// array-or-error, `float[4][]` an array of fixed arrays. Doubling the optional is
     /// rejected with a dedicated message (the lexer reads `??` greedily
     /// as one token), and optionals and error unions do not mix
     /// (`T?!`/`T!?` — the ADR 0034 reserved seat).
@@ -73,8 +74,14 @@ impl Parser {
                 }
                 TokenKind::LeftBracket => {
                     self.bump();
-                    self.expect(TokenKind::RightBracket);
-                    ty = TypeAnn::Array(Box::new(ty));
+                    if self.check(&TokenKind::RightBracket) {
+                        self.bump();
+                        ty = TypeAnn::Array(Box::new(ty));
+                    } else {
+                        let n = self.fixed_len();
+                        self.expect(TokenKind::RightBracket);
+                        ty = TypeAnn::Fixed(Box::new(ty), n);
+                    }
                 }
                 _ => return ty,
             }
@@ -104,6 +111,10 @@ impl Parser {
                 self.bump();
                 TypeAnn::File
             }
+            TokenKind::SizedType(t) => {
+                self.bump();
+                TypeAnn::Sized(t)
+            }
             TokenKind::ErrorKw => {
                 self.bump();
                 TypeAnn::ErrCode
@@ -116,6 +127,39 @@ impl Parser {
                     TypeAnn::Named(n)
                 }
             }
+            TokenKind::Fun => self.parse_fn_type(),
+            // `(T)` groups — the only way to put a suffix on a function
+            // type: `(fun(int): int)[]` (ADR 0038). A comma makes it a
+            // tuple type (ADR 0043).
+            TokenKind::LeftParen => {
+                if !self.enter_nested() {
+                    return TypeAnn::Int; // recovery placeholder
+                }
+                self.bump();
+                let inner = self.parse_type();
+                let ty = if self.eat(&TokenKind::Comma) {
+                    let mut elems = vec![inner];
+                    while !self.check(&TokenKind::RightParen) && !self.at_eof() {
+                        elems.push(self.parse_type());
+                        if !self.eat(&TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    if elems.len() < 2 {
+                        self.error(
+                            "a tuple type has at least two elements — '(T)' is a grouping"
+                                .to_string(),
+                            tok.span,
+                        );
+                    }
+                    TypeAnn::Tuple(elems)
+                } else {
+                    inner
+                };
+                self.expect(TokenKind::RightParen);
+                self.depth -= 1;
+                ty
+            }
             other => {
                 self.error(
                     format!("expected a type, found {}", describe(&other)),
@@ -146,20 +190,47 @@ impl Parser {
         TypeAnn::Applied(name, args)
     }
 
-    /// Consumes the `>` closing a type-argument list. A `>=` token is
-    /// split in place — `var b: Box<int>= x` — by rewriting it to the
-    /// remaining `=` (ADR 0035); ys has no shift operator, so `>>`
-    /// already lexes as two tokens.
+    /// `fun(int, string): int` — a function type (ADR 0038). The return
+    /// type is greedy, like a declaration's: `fun(): int[]` returns an
+    /// array; group to suffix the function type itself.
+    fn parse_fn_type(&mut self) -> TypeAnn {
+        if !self.enter_nested() {
+            return TypeAnn::Int; // recovery placeholder
+        }
+        self.bump(); // 'fun'
+        self.expect(TokenKind::LeftParen);
+        let mut params = Vec::new();
+        while !self.check(&TokenKind::RightParen) && !self.at_eof() {
+            params.push(self.parse_type());
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightParen);
+        let ret = self
+            .eat(&TokenKind::Colon)
+            .then(|| Box::new(self.parse_type()));
+        self.depth -= 1;
+        TypeAnn::Fn(params, ret)
+    }
+
+    /// Consumes the `>` closing a type-argument list. A longer token
+    /// starting with `>` is split in place by rewriting it to the rest:
+    /// `>=` to `=` (`var b: Box<int>= x`, ADR 0035), the shifts
+    /// `>>`/`>>>` to `>`/`>>` (`Box<Box<int>>`, ADR 0049), and their
+    /// assigning forms `>>=`/`>>>=` to `>=`/`>>=` (ADR 0050).
     pub(super) fn close_type_args(&mut self) {
         let tok = self.peek().clone();
-        match tok.kind {
-            TokenKind::Greater => self.bump(),
-            TokenKind::GreaterEq => {
-                self.tokens[self.pos] = Token {
-                    kind: TokenKind::Equals,
-                    span: Span::new(tok.span.start + 1, tok.span.end),
-                };
+        let rest = match tok.kind {
+            TokenKind::Greater => {
+                self.bump();
+                return;
             }
+            TokenKind::GreaterEq => TokenKind::Equals,
+            TokenKind::GreaterGreater => TokenKind::Greater,
+            TokenKind::GreaterGreaterGreater => TokenKind::GreaterGreater,
+            TokenKind::GreaterGreaterEq => TokenKind::GreaterEq,
+            TokenKind::GreaterGreaterGreaterEq => TokenKind::GreaterGreaterEq,
             other => {
                 self.error(
                     format!(
@@ -168,37 +239,75 @@ impl Parser {
                     ),
                     tok.span,
                 );
+                return;
             }
-        }
+        };
+        self.splits.push((self.pos, tok.clone()));
+        self.tokens[self.pos] = Token {
+            kind: rest,
+            span: Span::new(tok.span.start + 1, tok.span.end),
+        };
     }
 
-    /// `<T, U>` after a function or struct name — generic type
+    /// `<T, U>` after a struct, enum, or impl name — generic type
     /// parameters (ADR 0035). Absent means an ordinary declaration.
+    /// Bounds constrain instantiation, so only functions take them.
     fn parse_type_params(&mut self) -> Vec<(String, Span)> {
-        if !self.eat(&TokenKind::Less) {
-            return Vec::new();
+        let (params, bounds) = self.parse_bounded_type_params();
+        if let Some(b) = bounds.first() {
+            self.error(
+                "type parameter bounds are only allowed on functions and methods".to_string(),
+                b.span,
+            );
         }
+        params
+    }
+
+    /// `<T: Ordered + Show, U>` after a function name — type parameters
+    /// with optional interface bounds (ADR 0041), one `Bound` per
+    /// interface named.
+    fn parse_bounded_type_params(&mut self) -> (Vec<(String, Span)>, Vec<Bound>) {
         let mut params = Vec::new();
+        let mut bounds = Vec::new();
+        if !self.eat(&TokenKind::Less) {
+            return (params, bounds);
+        }
         loop {
             let span = self.peek().span;
             let name = self.expect_identifier();
+            if self.eat(&TokenKind::Colon) {
+                loop {
+                    let span = self.peek().span;
+                    let interface = self.expect_identifier();
+                    bounds.push(Bound {
+                        param: name.clone(),
+                        interface,
+                        span,
+                    });
+                    if !self.eat(&TokenKind::Plus) {
+                        break;
+                    }
+                }
+            }
             params.push((name, span));
             if !self.eat(&TokenKind::Comma) {
                 break;
             }
         }
         self.expect(TokenKind::Greater);
-        params
+        (params, bounds)
     }
 
-    pub(super) fn parse_function(&mut self, exported: bool) -> Function {
-        self.fn_ops = 0;
-        self.fn_ops_reported = false;
-        let start = self.expect(TokenKind::Fun);
-        let name = self.expect_identifier();
-        let type_params = self.parse_type_params();
+    /// `(a: int, b: T): R` — the annotated parameter list and optional
+    /// return type shared by declarations and function literals.
+    pub(super) fn parse_signature(&mut self) -> (Vec<Param>, Option<TypeAnn>) {
         self.expect(TokenKind::LeftParen);
-        let mut params = Vec::new();
+        self.parse_params_tail(Vec::new())
+    }
+
+    /// The parameters after `(` (and after a method's `self`), the
+    /// closing `)`, and the optional return type.
+    fn parse_params_tail(&mut self, mut params: Vec<Param>) -> (Vec<Param>, Option<TypeAnn>) {
         while !self.check(&TokenKind::RightParen) && !self.at_eof() {
             let param_name = self.expect_identifier();
             self.expect(TokenKind::Colon);
@@ -217,12 +326,23 @@ impl Parser {
         } else {
             None
         };
+        (params, return_type)
+    }
+
+    pub(super) fn parse_function(&mut self, exported: bool) -> Function {
+        self.fn_ops = 0;
+        self.fn_ops_reported = false;
+        let start = self.expect(TokenKind::Fun);
+        let name = self.expect_identifier();
+        let (type_params, bounds) = self.parse_bounded_type_params();
+        let (params, return_type) = self.parse_signature();
         // An unclosed body is recovered by the caller's item-level synchronize.
         let (body, end, _) = self.parse_block();
         Function {
             exported,
             name,
             type_params,
+            bounds,
             params,
             return_type,
             body,
@@ -230,6 +350,168 @@ impl Parser {
         }
     }
 
+    /// Parses `impl Name<T, U> { fun m(self, …) { … } … }` (ADR 0040).
+    /// Methods come out as functions named `Name.m` with `self`
+    /// annotated as the impl's type (`Name<T, U>` for a generic impl,
+    /// whose parameters lead each method's own).
+    pub(super) fn parse_impl(&mut self) -> ImplDecl {
+        let start = self.expect(TokenKind::Impl);
+        let name = self.expect_identifier();
+        let type_params = self.parse_type_params();
+        let self_ty = if type_params.is_empty() {
+            TypeAnn::Named(name.clone())
+        } else {
+            let args = type_params
+                .iter()
+                .map(|(n, _)| TypeAnn::Named(n.clone()))
+                .collect();
+            TypeAnn::Applied(name.clone(), args)
+        };
+        self.expect(TokenKind::LeftBrace);
+        let mut methods = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
+            if !self.check(&TokenKind::Fun) {
+                let tok = self.peek().clone();
+                self.error(
+                    format!(
+                        "expected 'fun' in an impl block, found {}",
+                        describe(&tok.kind)
+                    ),
+                    tok.span,
+                );
+                self.synchronize();
+                continue;
+            }
+            methods.push(self.parse_method(&name, &type_params, &self_ty));
+        }
+        let end = self.expect(TokenKind::RightBrace);
+        ImplDecl {
+            name,
+            type_params,
+            methods,
+            span: start.to(end),
+        }
+    }
+
+    /// One method: a function whose first parameter must be a bare
+    /// `self` — its type is the impl's, never written.
+    fn parse_method(
+        &mut self,
+        owner: &str,
+        owner_params: &[(String, Span)],
+        self_ty: &TypeAnn,
+    ) -> Function {
+        self.fn_ops = 0;
+        self.fn_ops_reported = false;
+        let start = self.expect(TokenKind::Fun);
+        let name = self.expect_identifier();
+        let mut type_params = owner_params.to_vec();
+        let (own, bounds) = self.parse_bounded_type_params();
+        type_params.extend(own);
+        let (params, return_type) = self.parse_receiver_signature(self_ty);
+        let (body, end, _) = self.parse_block();
+        Function {
+            exported: false,
+            name: format!("{owner}.{name}"),
+            type_params,
+            bounds,
+            params,
+            return_type,
+            body,
+            span: start.to(end),
+        }
+    }
+
+    /// `(self, a: int): R` — a method's signature, shared by impl
+    /// methods and interface requirements. `self` is bare and gets
+    /// `self_ty`; anything else first is an error.
+    fn parse_receiver_signature(&mut self, self_ty: &TypeAnn) -> (Vec<Param>, Option<TypeAnn>) {
+        self.expect(TokenKind::LeftParen);
+        let tok = self.peek().clone();
+        if tok.kind == TokenKind::Identifier(syntax::SELF_PARAM.to_string()) {
+            self.bump();
+            if self.check(&TokenKind::Colon) {
+                self.error(
+                    "'self' takes the receiver's type — drop the annotation".to_string(),
+                    self.peek().span,
+                );
+                self.bump();
+                self.parse_type();
+            }
+            if !self.check(&TokenKind::RightParen) {
+                self.expect(TokenKind::Comma);
+            }
+        } else {
+            self.error(
+                format!(
+                    "a method's first parameter must be 'self', found {}",
+                    describe(&tok.kind)
+                ),
+                tok.span,
+            );
+        }
+        let receiver = Param {
+            name: syntax::SELF_PARAM.to_string(),
+            ty: self_ty.clone(),
+        };
+        self.parse_params_tail(vec![receiver])
+    }
+
+    /// Parses `interface Name { fun m(self, o: Self): R; … }` (ADR
+    /// 0041): body-less method headers whose `self` is typed `Self`,
+    /// each ended by `;` or by the `fun` or `}` that follows it.
+    pub(super) fn parse_interface(&mut self, exported: bool) -> InterfaceDecl {
+        let start = self.expect(TokenKind::Interface);
+        let name = self.expect_identifier();
+        self.expect(TokenKind::LeftBrace);
+        let self_ty = TypeAnn::Named(syntax::SELF_TYPE.to_string());
+        let mut methods = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
+            let tok = self.peek().clone();
+            if tok.kind != TokenKind::Fun {
+                self.error(
+                    format!(
+                        "expected 'fun' in an interface, found {}",
+                        describe(&tok.kind)
+                    ),
+                    tok.span,
+                );
+                self.synchronize();
+                continue;
+            }
+            self.bump();
+            let method = self.expect_identifier();
+            if self.check(&TokenKind::Less) {
+                self.error(
+                    "interface methods cannot be generic".to_string(),
+                    self.peek().span,
+                );
+                self.parse_type_params();
+            }
+            let (params, return_type) = self.parse_receiver_signature(&self_ty);
+            // The `;` may be left off where the next header or the
+            // closing `}` already ends this one.
+            let end = if matches!(self.peek().kind, TokenKind::Fun | TokenKind::RightBrace) {
+                self.tokens[self.pos - 1].span
+            } else {
+                self.expect(TokenKind::Semicolon)
+            };
+            methods.push(MethodSig {
+                name: method,
+                params,
+                return_type,
+                span: tok.span.to(end),
+            });
+        }
+        let end = self.expect(TokenKind::RightBrace);
+        InterfaceDecl {
+            exported,
+            name,
+            methods,
+            span: start.to(end),
+        }
+    }
+
     /// Parses `enum Name<T, U> { Variant(T), Ready }` (ADR 0036) —
     /// comma-separated variants, each with optional positional payload
     /// types. The caller dispatched on the `enum` keyword.
@@ -240,23 +522,7 @@ impl Parser {
         self.expect(TokenKind::LeftBrace);
         let mut variants = Vec::new();
         while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
-            let vspan = self.peek().span;
-            let vname = self.expect_identifier();
-            let mut payloads = Vec::new();
-            if self.eat(&TokenKind::LeftParen) {
-                while !self.check(&TokenKind::RightParen) && !self.at_eof() {
-                    payloads.push(self.parse_type());
-                    if !self.eat(&TokenKind::Comma) {
-                        break;
-                    }
-                }
-                self.expect(TokenKind::RightParen);
-            }
-            variants.push(Variant {
-                name: vname,
-                payloads,
-                span: vspan,
-            });
+            variants.push(self.parse_variant());
             if !self.eat(&TokenKind::Comma) {
                 break;
             }
@@ -271,15 +537,36 @@ impl Parser {
         }
     }
 
-    /// Parses `error Name[, Name]*;` — module-scoped error codes
-    /// (ADR 0034). The caller dispatched on the `error` keyword.
+    /// One `Name` or `Name(T, U)` — an enum variant, or an error code
+    /// with its payload types (ADR 0062).
+    fn parse_variant(&mut self) -> Variant {
+        let span = self.peek().span;
+        let name = self.expect_identifier();
+        let mut payloads = Vec::new();
+        if self.eat(&TokenKind::LeftParen) {
+            while !self.check(&TokenKind::RightParen) && !self.at_eof() {
+                payloads.push(self.parse_type());
+                if !self.eat(&TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(TokenKind::RightParen);
+        }
+        Variant {
+            name,
+            payloads,
+            span,
+        }
+    }
+
+    /// Parses `error Name[(T, …)][, Name[(T, …)]]*;` — module-scoped
+    /// error codes (ADR 0034), each with optional positional payload
+    /// types (ADR 0062). The caller dispatched on the `error` keyword.
     pub(super) fn parse_error_decl(&mut self, exported: bool) -> ErrorDecl {
         let start = self.expect(TokenKind::ErrorKw);
-        let mut names = Vec::new();
+        let mut codes = Vec::new();
         loop {
-            let span = self.peek().span;
-            let name = self.expect_identifier();
-            names.push((name, span));
+            codes.push(self.parse_variant());
             if !self.eat(&TokenKind::Comma) {
                 break;
             }
@@ -287,7 +574,45 @@ impl Parser {
         let end = self.expect(TokenKind::Semicolon);
         ErrorDecl {
             exported,
-            names,
+            codes,
+            span: start.to(end),
+        }
+    }
+
+    /// Parses `const NAME: T = expr;` at module level (ADR 0047). The
+    /// caller dispatched on the `const` keyword.
+    pub(super) fn parse_const_decl(&mut self, exported: bool) -> ConstDecl {
+        let start = self.expect(TokenKind::Const);
+        let name = self.expect_identifier();
+        self.expect(TokenKind::Colon);
+        let ty = self.parse_type();
+        self.expect(TokenKind::Equals);
+        let value = self.parse_expr(0);
+        let end = self.expect(TokenKind::Semicolon);
+        ConstDecl {
+            exported,
+            name,
+            ty,
+            value,
+            span: start.to(end),
+        }
+    }
+
+    /// Parses `var NAME: T = expr;` at module level (ADR 0048) — the
+    /// const shape, run-time initialized.
+    pub(super) fn parse_global_decl(&mut self, exported: bool) -> GlobalDecl {
+        let start = self.expect(TokenKind::Var);
+        let name = self.expect_identifier();
+        self.expect(TokenKind::Colon);
+        let ty = self.parse_type();
+        self.expect(TokenKind::Equals);
+        let value = self.parse_expr(0);
+        let end = self.expect(TokenKind::Semicolon);
+        GlobalDecl {
+            exported,
+            name,
+            ty,
+            value,
             span: start.to(end),
         }
     }
@@ -340,9 +665,13 @@ impl Parser {
                 | TokenKind::Struct
                 | TokenKind::RefStruct
                 | TokenKind::Enum
+                | TokenKind::Impl
+                | TokenKind::Interface
                 | TokenKind::Import
                 | TokenKind::Export
-                | TokenKind::ErrorKw => return,
+                | TokenKind::ErrorKw
+                | TokenKind::Const
+                | TokenKind::Var => return,
                 _ => {
                     self.bump();
                 }
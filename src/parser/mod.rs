@@ -1,14 +1,16 @@
 use crate::ast::{
-    Ast, BinOp, Conv, EnumDecl, ErrorDecl, Expr, Field, Function, ImportDecl, Item, MatchArm,
-    Param, Stmt, Struct, TypeAnn, UnOp, Variant,
+    Ast, BinOp, Bound, ConstDecl, Conv, EnumDecl, ErrorDecl, Expr, Field, Function, GlobalDecl,
+    ImplDecl, ImportDecl, InterfaceDecl, Item, MatchArm, MethodSig, Param, Pattern, Stmt, Struct,
+    TypeAnn, UnOp, Variant,
 };
 use crate::diagnostic::Diagnostic;
 use crate::span::Span;
+use crate::syntax;
 use crate::token::{Token, TokenKind};
 
 pub struct Parser {
-    /// Owned so the type-argument closer can split a `>=` token into
-    /// `>` `=` in place (`var b: Box<int>= x`, ADR 0035).
+    /// Owned so the type-argument closer can split a `>=` or `>>` token
+    /// in place (`var b: Box<int>= x`, ADR 0035; `Box<Box<int>>`, ADR 0049).
     pub tokens: Vec<Token>,
     pub pos: usize,
     pub diagnostics: Vec<Diagnostic>,
@@ -16,12 +18,19 @@ pub struct Parser {
     /// read as identifier-then-block, not a struct literal. Parentheses
     /// re-enable it.
     pub struct_literals_allowed: bool,
+    /// The span of the last parenthesized expression — `(p.f)(x)` calls
+    /// the field's function value instead of reading as qualified enum
+    /// construction (ADR 0038).
+    grouped: Option<Span>,
     /// Current expression/statement nesting depth (see `MAX_NESTING`).
     pub depth: u32,
     /// Chain-built expression nodes in the current function (see `MAX_FN_OPS`).
     fn_ops: u32,
     /// True once the current function's operator budget was reported.
     fn_ops_reported: bool,
+    /// Tokens the type-argument closer rewrote, with their originals —
+    /// a rolled-back type-argument speculation restores them.
+    splits: Vec<(usize, Token)>,
 }
 
 /// Recursion ceiling for nested expressions and statements — a stack-safety
@@ -40,8 +49,14 @@ const MAX_NESTING: u32 = 128;
 /// pipeline worker stack fits (main.rs).
 const MAX_FN_OPS: u32 = 32_768;
 
+/// The most elements a fixed array may hold (ADR 0056). Its value
+/// lives in frame slots, and every copy point takes one, so this keeps
+/// frames well inside the default stack.
+const MAX_FIXED_LEN: i64 = 4096;
+
 mod exprs;
 mod items;
+mod patterns;
 mod stmts;
 #[cfg(test)]
 mod tests;
@@ -57,9 +72,11 @@ impl Parser {
             pos: 0,
             diagnostics: Vec::new(),
             struct_literals_allowed: true,
+            grouped: None,
             depth: 0,
             fn_ops: 0,
             fn_ops_reported: false,
+            splits: Vec::new(),
         }
     }
 
@@ -99,11 +116,38 @@ impl Parser {
         }
     }
 
+    /// The length of a fixed array type or a repeat literal (ADR 0056):
+    /// an integer literal from 1 to `MAX_FIXED_LEN`. Recovers as 1.
+    fn fixed_len(&mut self) -> usize {
+        let tok = self.peek().clone();
+        let TokenKind::IntLiteral(n) = tok.kind else {
+            self.error(
+                format!("expected an array length, found {}", describe(&tok.kind)),
+                tok.span,
+            );
+            return 1;
+        };
+        self.bump();
+        if !(1..=MAX_FIXED_LEN).contains(&n) {
+            self.error(
+                format!("a fixed array holds 1 to {MAX_FIXED_LEN} elements, found {n}"),
+                tok.span,
+            );
+            return 1;
+        }
+        n as usize
+    }
+
     fn peek(&self) -> &Token {
         // Safe: the token stream always ends with an Eof sentinel.
         &self.tokens[self.pos]
     }
 
+    /// The token after `peek` — the Eof sentinel at the end.
+    fn peek_next(&self) -> &Token {
+        &self.tokens[(self.pos + 1).min(self.tokens.len() - 1)]
+    }
+
     fn at_eof(&self) -> bool {
         matches!(self.peek().kind, TokenKind::Eof)
     }
@@ -206,8 +250,12 @@ pub(super) enum Precedence {
     Coalesce,   // ??
     Or,         // ||
     And,        // &&
+    BitOr,      // |
+    BitXor,     // ^
+    BitAnd,     // &
     Equality,   // == !=
     Comparison, // < <= > >=
+    Shift,      // << >> >>>
     Sum,        // + -
     Product,    // * / %
 }
@@ -220,12 +268,18 @@ impl Precedence {
             TokenKind::QuestionQuestion => (Precedence::Coalesce, BinOp::Coalesce),
             TokenKind::PipePipe => (Precedence::Or, BinOp::Or),
             TokenKind::AmpAmp => (Precedence::And, BinOp::And),
+            TokenKind::Pipe => (Precedence::BitOr, BinOp::BitOr),
+            TokenKind::Caret => (Precedence::BitXor, BinOp::BitXor),
+            TokenKind::Amp => (Precedence::BitAnd, BinOp::BitAnd),
             TokenKind::EqEq => (Precedence::Equality, BinOp::Eq),
             TokenKind::BangEq => (Precedence::Equality, BinOp::Ne),
             TokenKind::Less => (Precedence::Comparison, BinOp::Lt),
             TokenKind::LessEq => (Precedence::Comparison, BinOp::Le),
             TokenKind::Greater => (Precedence::Comparison, BinOp::Gt),
             TokenKind::GreaterEq => (Precedence::Comparison, BinOp::Ge),
+            TokenKind::LessLess => (Precedence::Shift, BinOp::Shl),
+            TokenKind::GreaterGreater => (Precedence::Shift, BinOp::Shr),
+            TokenKind::GreaterGreaterGreater => (Precedence::Shift, BinOp::UShr),
             TokenKind::Plus => (Precedence::Sum, BinOp::Add),
             TokenKind::Minus => (Precedence::Sum, BinOp::Sub),
             TokenKind::Asterisk => (Precedence::Product, BinOp::Mul),
@@ -247,10 +301,10 @@ impl Precedence {
 }
 
 /// Unary prefix operators bind tighter than every binary operator.
-pub(super) const PREFIX_BP: u8 = 16;
+pub(super) const PREFIX_BP: u8 = 24;
 
 /// Postfix (call `(`, field `.`) binds tighter than everything, including prefix.
-pub(super) const POSTFIX_BP: u8 = 18;
+pub(super) const POSTFIX_BP: u8 = 26;
 
 // Adding a Precedence level shifts the derived binding powers — this guard
 // keeps prefix/postfix above every infix level at compile time.
@@ -283,10 +337,15 @@ pub(super) fn describe(kind: &TokenKind) -> &'static str {
         BoolType => "'bool'",
         StringType => "'string'",
         FileType => "'file'",
+        SizedType(_) => "a sized integer type",
         ErrorKw => "'error'",
         Try => "'try'",
         Enum => "'enum'",
         Match => "'match'",
+        Impl => "'impl'",
+        Interface => "'interface'",
+        Defer => "'defer'",
+        Arena => "'arena'",
         Identifier(_) => "an identifier",
         IntLiteral(_) => "an integer",
         FloatLiteral(_) => "a float",
@@ -304,6 +363,7 @@ pub(super) fn describe(kind: &TokenKind) -> &'static str {
         RightBrace => "'}'",
         Equals => "'='",
         EqEq => "'=='",
+        FatArrow => "'=>'",
         Plus => "'+'",
         Minus => "'-'",
         Asterisk => "'*'",
@@ -320,8 +380,31 @@ pub(super) fn describe(kind: &TokenKind) -> &'static str {
         Question => "'?'",
         QuestionQuestion => "'??'",
         QuestionDot => "'?.'",
+        DotDot => "'..'",
+        DotDotEq => "'..='",
         AmpAmp => "'&&'",
         PipePipe => "'||'",
+        Amp => "'&'",
+        Pipe => "'|'",
+        Caret => "'^'",
+        Tilde => "'~'",
+        LessLess => "'<<'",
+        GreaterGreater => "'>>'",
+        GreaterGreaterGreater => "'>>>'",
+        PlusEq => "'+='",
+        MinusEq => "'-='",
+        AsteriskEq => "'*='",
+        SlashEq => "'/='",
+        PercentEq => "'%='",
+        AmpEq => "'&='",
+        PipeEq => "'|='",
+        CaretEq => "'^='",
+        LessLessEq => "'<<='",
+        GreaterGreaterEq => "'>>='",
+        GreaterGreaterGreaterEq => "'>>>='",
+        QuestionQuestionEq => "'??='",
+        PlusPlus => "'++'",
+        MinusMinus => "'--'",
         Eof => "end of input",
     }
 }
@@ -339,8 +422,12 @@ pub fn parse(tokens: &[Token]) -> (Ast, Vec<Diagnostic>) {
                 items.push(Item::Struct(parser.parse_struct(false)))
             }
             TokenKind::Enum => items.push(Item::Enum(parser.parse_enum(false))),
+            TokenKind::Impl => items.push(Item::Impl(parser.parse_impl())),
+            TokenKind::Interface => items.push(Item::Interface(parser.parse_interface(false))),
             TokenKind::Import => items.push(Item::Import(parser.parse_import())),
             TokenKind::ErrorKw => items.push(Item::Error(parser.parse_error_decl(false))),
+            TokenKind::Const => items.push(Item::Const(parser.parse_const_decl(false))),
+            TokenKind::Var => items.push(Item::Global(parser.parse_global_decl(false))),
             TokenKind::Export => {
                 parser.bump();
                 match parser.peek().kind {
@@ -349,12 +436,17 @@ pub fn parse(tokens: &[Token]) -> (Ast, Vec<Diagnostic>) {
                         items.push(Item::Struct(parser.parse_struct(true)))
                     }
                     TokenKind::Enum => items.push(Item::Enum(parser.parse_enum(true))),
+                    TokenKind::Interface => {
+                        items.push(Item::Interface(parser.parse_interface(true)))
+                    }
                     TokenKind::ErrorKw => items.push(Item::Error(parser.parse_error_decl(true))),
+                    TokenKind::Const => items.push(Item::Const(parser.parse_const_decl(true))),
+                    TokenKind::Var => items.push(Item::Global(parser.parse_global_decl(true))),
                     _ => {
                         let tok = parser.peek().clone();
                         parser.error(
                             format!(
-                                "expected 'fun', 'struct', 'enum', or 'error' after 'export', found {}",
+                                "expected 'fun', 'struct', 'enum', 'interface', 'error', 'const', or 'var' after 'export', found {}",
                                 describe(&tok.kind)
                             ),
                             tok.span,
@@ -367,7 +459,7 @@ pub fn parse(tokens: &[Token]) -> (Ast, Vec<Diagnostic>) {
                 let tok = parser.peek().clone();
                 parser.error(
                     format!(
-                        "expected 'fun', 'struct', 'enum', or 'error', found {}",
+                        "expected 'fun', 'struct', 'enum', 'impl', 'interface', 'error', 'const', or 'var', found {}",
                         describe(&tok.kind)
                     ),
                     tok.span,
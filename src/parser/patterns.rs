@@ -0,0 +1,202 @@
+//! Match-arm heads and patterns (ADR 0045): variants with nested
+//! payload patterns, literals, error codes (ADR 0046) with their
+//! payload patterns (ADR 0062), bindings, and the optional `if` guard.
+//! Shared by the statement and expression forms of `match`, by the
+//! destructuring bindings (ADR 0057), and by `if const` and
+//! `while const` (ADR 0058).
+
+use super::*;
+
+impl Parser {
+    /// An arm head: a variant or literal pattern, then an optional `if` guard read
+    /// as a condition, so `{` opens the statement arm's block. `None`
+    /// after a malformed head.
+    pub(super) fn parse_arm_head(&mut self) -> Option<(Pattern, Option<Expr>)> {
+        let pattern = self.parse_pattern(true)?;
+        let guard = self.eat(&TokenKind::If).then(|| self.parse_condition());
+        Some((pattern, guard))
+    }
+
+    /// One pattern. A name followed by `(` is a variant with payload
+    /// patterns; a bare name names the variant at the arm head and
+    /// binds everywhere else.
+    fn parse_pattern(&mut self, head: bool) -> Option<Pattern> {
+        let tok = self.peek().clone();
+        let lit = match tok.kind {
+            TokenKind::IntLiteral(n) => Expr::Int(n, tok.span),
+            TokenKind::StringLiteral(s) => Expr::Str(s, tok.span),
+            TokenKind::True => Expr::Bool(true, tok.span),
+            TokenKind::False => Expr::Bool(false, tok.span),
+            TokenKind::Minus => {
+                self.bump();
+                let next = self.peek().clone();
+                let TokenKind::IntLiteral(n) = next.kind else {
+                    self.error(
+                        format!(
+                            "expected an integer after '-' in a pattern, found {}",
+                            describe(&next.kind)
+                        ),
+                        next.span,
+                    );
+                    return None;
+                };
+                self.bump();
+                return Some(Pattern::Lit(Expr::Int(-n, tok.span.to(next.span))));
+            }
+            // `error.Name` — an error code (ADR 0046), and its payload
+            // patterns when a list follows (ADR 0062).
+            TokenKind::ErrorKw => {
+                self.bump();
+                self.expect(TokenKind::Dot);
+                let code_span = self.peek().span;
+                let name = self.expect_identifier();
+                let (args, end) = self.payload_patterns(code_span)?;
+                return Some(Pattern::Error {
+                    name,
+                    name_span: tok.span.to(code_span),
+                    args,
+                    span: tok.span.to(end),
+                });
+            }
+            TokenKind::Identifier(name) => {
+                self.bump();
+                if !head && !self.check(&TokenKind::LeftParen) {
+                    return Some(Pattern::Bind(name, tok.span));
+                }
+                return self.parse_variant_pattern(name, tok.span);
+            }
+            _ => {
+                self.error(
+                    format!("expected a pattern, found {}", describe(&tok.kind)),
+                    tok.span,
+                );
+                return None;
+            }
+        };
+        self.bump();
+        Some(Pattern::Lit(lit))
+    }
+
+    /// `{ x, y: py } = value;` after `const`/`var` (ADR 0057): a field
+    /// binds under its own name, or under the name after `:`.
+    pub(super) fn parse_let_struct(&mut self, mutable: bool, start: Span) -> (Stmt, bool) {
+        self.bump(); // '{'
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.at_eof() {
+            let span = self.peek().span;
+            let field = self.expect_identifier();
+            let name = if self.eat(&TokenKind::Colon) {
+                self.expect_identifier()
+            } else {
+                field.clone()
+            };
+            fields.push((field, name, span));
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace);
+        if fields.is_empty() {
+            self.error(
+                "a destructuring binds at least one field".to_string(),
+                start,
+            );
+        }
+        self.expect(TokenKind::Equals);
+        let value = self.parse_expr(0);
+        let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
+        (
+            Stmt::LetStruct {
+                mutable,
+                fields,
+                value,
+                span: start.to(end),
+            },
+            clean,
+        )
+    }
+
+    /// `Name(p, …) = value else { … };` after `const`/`var` (ADR 0057);
+    /// the `else` block is optional here and required by the checker
+    /// unless the pattern matches every value.
+    pub(super) fn parse_let_else(&mut self, mutable: bool, start: Span) -> (Stmt, bool) {
+        let tok = self.advance();
+        let TokenKind::Identifier(name) = tok.kind else {
+            unreachable!("the caller saw an identifier")
+        };
+        let Some(pattern) = self.parse_variant_pattern(name, tok.span) else {
+            return (Stmt::Expr(Expr::Int(0, tok.span)), false); // recovery placeholder
+        };
+        self.expect(TokenKind::Equals);
+        let value = self.parse_expr(0);
+        let else_body = self.eat(&TokenKind::Else).then(|| self.parse_block().0);
+        let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
+        (
+            Stmt::LetElse {
+                mutable,
+                pattern,
+                value,
+                else_body,
+                span: start.to(end),
+            },
+            clean,
+        )
+    }
+
+    /// The head of `if const` / `while const` (ADR 0058), after
+    /// `const`: a name that unwraps or a variant pattern, then `=` and
+    /// the value read as a condition. A literal is reported but still
+    /// read, so the statement parses on. `None` after a malformed head.
+    pub(super) fn parse_const_head(&mut self) -> Option<(Pattern, Expr)> {
+        let tok = self.peek().clone();
+        if !matches!(tok.kind, TokenKind::Identifier(_)) {
+            self.error(
+                format!(
+                    "expected a name or a variant pattern, found {}",
+                    describe(&tok.kind)
+                ),
+                tok.span,
+            );
+        }
+        let pattern = self.parse_pattern(false)?;
+        self.expect(TokenKind::Equals);
+        Some((pattern, self.parse_condition()))
+    }
+
+    /// `Name(p, …)` after its name; the list is optional at an arm
+    /// head (`Dot`).
+    fn parse_variant_pattern(&mut self, name: String, name_span: Span) -> Option<Pattern> {
+        let (args, end) = self.payload_patterns(name_span)?;
+        Some(Pattern::Variant {
+            name,
+            name_span,
+            args,
+            span: name_span.to(end),
+        })
+    }
+
+    /// The optional `(p, …)` after a variant or error name, with the
+    /// span it ends at (`name_span` when absent). Nesting charges the
+    /// parser's depth budget.
+    fn payload_patterns(&mut self, name_span: Span) -> Option<(Vec<Pattern>, Span)> {
+        let mut args = Vec::new();
+        if !self.eat(&TokenKind::LeftParen) {
+            return Some((args, name_span));
+        }
+        if !self.enter_nested() {
+            return None;
+        }
+        while !self.check(&TokenKind::RightParen) && !self.at_eof() {
+            let Some(arg) = self.parse_pattern(false) else {
+                self.depth -= 1;
+                return None;
+            };
+            args.push(arg);
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.depth -= 1;
+        Some((args, self.expect(TokenKind::RightParen)))
+    }
+}
@@ -52,6 +52,8 @@ impl Parser {
                 | TokenKind::Return
                 | TokenKind::Break
                 | TokenKind::Continue
+                | TokenKind::Defer
+                | TokenKind::Arena
                 | TokenKind::If
                 | TokenKind::While
                 | TokenKind::For => return,
@@ -73,7 +75,15 @@ impl Parser {
     pub(super) fn parse_if_inner(&mut self) -> (Stmt, bool) {
         let start = self.peek().span;
         self.bump(); // 'if'
-        let cond = self.parse_condition();
+        // `if const p = value` binds instead of testing (ADR 0058).
+        let (pattern, cond) = if self.eat(&TokenKind::Const) {
+            let Some((pattern, value)) = self.parse_const_head() else {
+                return (Stmt::Expr(Expr::Int(0, start)), false); // recovery placeholder
+            };
+            (Some(pattern), value)
+        } else {
+            (None, self.parse_condition())
+        };
         let (then_body, then_end, mut clean) = self.parse_block();
         let mut span = start.to(then_end);
         let mut else_body = None;
@@ -84,11 +94,8 @@ impl Parser {
                 // to extend — keep the chain's span as-is then.)
                 let (nested, nested_clean) = self.parse_if();
                 clean = nested_clean;
-                if let Stmt::If {
-                    span: nested_span, ..
-                } = &nested
-                {
-                    span = start.to(*nested_span);
+                if let Stmt::If { .. } | Stmt::IfConst { .. } = &nested {
+                    span = start.to(nested.span());
                 }
                 else_body = Some(vec![nested]);
             } else {
@@ -98,15 +105,49 @@ impl Parser {
                 else_body = Some(body);
             }
         }
-        (
-            Stmt::If {
+        let stmt = match pattern {
+            Some(pattern) => Stmt::IfConst {
+                pattern,
+                value: cond,
+                then_body,
+                else_body,
+                span,
+            },
+            None => Stmt::If {
                 cond,
                 then_body,
                 else_body,
                 span,
             },
-            clean,
-        )
+        };
+        (stmt, clean)
+    }
+
+    /// `outer: while … { … }` or `outer: for …` (ADR 0059): the label
+    /// and its colon, then the loop, which takes the label.
+    fn parse_labeled_loop(&mut self, name: String, start: Span) -> (Stmt, bool) {
+        self.bump(); // the label
+        self.bump(); // ':'
+        if !self.check(&TokenKind::While) && !self.check(&TokenKind::For) {
+            let tok = self.peek().clone();
+            self.error(
+                format!(
+                    "expected 'while' or 'for' after the label '{name}', found {}",
+                    describe(&tok.kind)
+                ),
+                tok.span,
+            );
+            return (Stmt::Expr(Expr::Int(0, start)), false); // recovery placeholder
+        }
+        let (mut stmt, clean) = self.parse_stmt_inner();
+        if let Stmt::While { label, span, .. }
+        | Stmt::WhileConst { label, span, .. }
+        | Stmt::For { label, span, .. } = &mut stmt
+        {
+            *label = Some(name);
+            *span = start.to(*span);
+        }
+        (stmt, clean)
     }
 
     /// Parses one statement. The flag reports whether it terminated cleanly
@@ -124,18 +165,37 @@ impl Parser {
         let tok = self.peek().clone();
         match tok.kind {
             TokenKind::If => self.parse_if(),
+            TokenKind::Identifier(label) if matches!(self.peek_next().kind, TokenKind::Colon) => {
+                self.parse_labeled_loop(label, tok.span)
+            }
             TokenKind::While => {
                 self.bump();
-                let cond = self.parse_condition();
+                let (pattern, cond) = if self.eat(&TokenKind::Const) {
+                    let Some((pattern, value)) = self.parse_const_head() else {
+                        return (Stmt::Expr(Expr::Int(0, tok.span)), false); // recovery placeholder
+                    };
+                    (Some(pattern), value)
+                } else {
+                    (None, self.parse_condition())
+                };
                 let (body, end, clean) = self.parse_block();
-                (
-                    Stmt::While {
+                let span = tok.span.to(end);
+                let stmt = match pattern {
+                    Some(pattern) => Stmt::WhileConst {
+                        label: None,
+                        pattern,
+                        value: cond,
+                        body,
+                        span,
+                    },
+                    None => Stmt::While {
+                        label: None,
                         cond,
                         body,
-                        span: tok.span.to(end),
+                        span,
                     },
-                    clean,
-                )
+                };
+                (stmt, clean)
             }
             TokenKind::For => {
                 self.bump();
@@ -176,10 +236,19 @@ impl Parser {
                 // Struct literals are off in the iterable, same as
                 // conditions: `for x in xs { … }` must read `xs` then a
                 // block, not a struct literal `xs { … }`.
-                let iterable = self.parse_condition();
+                let prev = self.struct_literals_allowed;
+                self.struct_literals_allowed = false;
+                let iterable = self.parse_expr(0);
+                let iterable = if self.at_range() {
+                    self.parse_range(Some(iterable), false)
+                } else {
+                    iterable
+                };
+                self.struct_literals_allowed = prev;
                 let (body, end, clean) = self.parse_block();
                 (
                     Stmt::For {
+                        label: None,
                         index,
                         name,
                         iterable,
@@ -204,31 +273,18 @@ impl Parser {
                         else_body = Some(body);
                         break;
                     }
-                    let variant_span = self.peek().span;
-                    let Some(variant) = self.header_identifier() else {
+                    let start = self.peek().span;
+                    let Some((pattern, guard)) = self.parse_arm_head() else {
                         // Malformed arm head: skip the arm region.
                         self.synchronize_stmt();
                         continue;
                     };
-                    let mut bindings = Vec::new();
-                    if self.eat(&TokenKind::LeftParen) {
-                        while !self.check(&TokenKind::RightParen) && !self.at_eof() {
-                            let span = self.peek().span;
-                            let name = self.expect_identifier();
-                            bindings.push((name, span));
-                            if !self.eat(&TokenKind::Comma) {
-                                break;
-                            }
-                        }
-                        self.expect(TokenKind::RightParen);
-                    }
                     let (body, arm_end, _) = self.parse_block();
                     arms.push(MatchArm {
-                        variant,
-                        variant_span,
-                        bindings,
+                        pattern,
+                        guard,
                         body,
-                        span: variant_span.to(arm_end),
+                        span: start.to(arm_end),
                     });
                 }
                 let (end, clean) = self.expect_or_flag(TokenKind::RightBrace);
@@ -245,6 +301,40 @@ impl Parser {
             TokenKind::Var | TokenKind::Const => {
                 let mutable = matches!(tok.kind, TokenKind::Var);
                 self.bump();
+                // `const (a, b) = pair;` — tuple destructuring (ADR 0043).
+                // `const { x, y } = p;` — struct destructuring (ADR 0057).
+                if self.check(&TokenKind::LeftBrace) {
+                    return self.parse_let_struct(mutable, tok.span);
+                }
+                // `const Circle(r) = shape else { … };` — let-else
+                // (ADR 0057): a name and `(` open a variant pattern.
+                if matches!(self.peek().kind, TokenKind::Identifier(_))
+                    && matches!(self.peek_next().kind, TokenKind::LeftParen)
+                {
+                    return self.parse_let_else(mutable, tok.span);
+                }
+                if self.eat(&TokenKind::LeftParen) {
+                    let mut names = Vec::new();
+                    loop {
+                        names.push(self.expect_identifier());
+                        if !self.eat(&TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(TokenKind::RightParen);
+                    self.expect(TokenKind::Equals);
+                    let value = self.parse_expr(0);
+                    let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
+                    return (
+                        Stmt::LetTuple {
+                            mutable,
+                            names,
+                            value,
+                            span: tok.span.to(end),
+                        },
+                        clean,
+                    );
+                }
                 let name = self.expect_identifier();
                 let ty = if self.eat(&TokenKind::Colon) {
                     Some(self.parse_type())
@@ -265,16 +355,55 @@ impl Parser {
                     clean,
                 )
             }
+            // `defer close(f);` or `defer { … }` (ADR 0060).
+            TokenKind::Defer => {
+                self.bump();
+                if self.check(&TokenKind::LeftBrace) {
+                    let (body, end, clean) = self.parse_block();
+                    let span = tok.span.to(end);
+                    return (Stmt::Defer { body, span }, clean);
+                }
+                let (stmt, clean) = self.parse_stmt();
+                let span = tok.span.to(stmt.span());
+                (
+                    Stmt::Defer {
+                        body: vec![stmt],
+                        span,
+                    },
+                    clean,
+                )
+            }
+            // `arena { … }` (ADR 0061).
+            TokenKind::Arena => {
+                self.bump();
+                let (body, end, clean) = self.parse_block();
+                (
+                    Stmt::Arena {
+                        body,
+                        span: tok.span.to(end),
+                    },
+                    clean,
+                )
+            }
             TokenKind::Break | TokenKind::Continue => {
                 let is_break = matches!(tok.kind, TokenKind::Break);
                 self.bump();
+                // `break outer;` names the loop it leaves (ADR 0059).
+                let label = match &self.peek().kind {
+                    TokenKind::Identifier(name) => {
+                        let name = name.clone();
+                        self.bump();
+                        Some(name)
+                    }
+                    _ => None,
+                };
                 let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
                 let span = tok.span.to(end);
                 (
                     if is_break {
-                        Stmt::Break { span }
+                        Stmt::Break { label, span }
                     } else {
-                        Stmt::Continue { span }
+                        Stmt::Continue { label, span }
                     },
                     clean,
                 )
@@ -297,30 +426,65 @@ impl Parser {
             }
             _ => {
                 let expr = self.parse_expr(0);
-                // `place = expr;` is an assignment; anything else is an
-                // expression statement.
-                if self.check(&TokenKind::Equals) {
-                    // Only places (a variable or plain field chain) can be
-                    // assigned to; `?.` links are excluded by place_path.
-                    if !expr.is_place() {
-                        self.error("invalid assignment target".to_string(), expr.span());
-                    }
-                    let start = expr.span();
-                    self.bump(); // '='
-                    let value = self.parse_expr(0);
-                    let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
-                    return (
-                        Stmt::Assign {
-                            target: expr,
-                            value,
-                            span: start.to(end),
-                        },
-                        clean,
-                    );
+                // `place = expr;` (or `place op= expr;`, `place++;`) is an
+                // assignment; anything else is an expression statement.
+                let next = self.peek().clone();
+                let op = match next.kind {
+                    TokenKind::Equals => None,
+                    TokenKind::PlusPlus => Some(BinOp::Add),
+                    TokenKind::MinusMinus => Some(BinOp::Sub),
+                    ref kind => match compound_op(kind) {
+                        Some(op) => Some(op),
+                        None => {
+                            let (_, clean) = self.expect_or_flag(TokenKind::Semicolon);
+                            return (Stmt::Expr(expr), clean);
+                        }
+                    },
+                };
+                // Only places (a variable or plain field chain) can be
+                // assigned to; `?.` links are excluded by place_path.
+                if !expr.is_place() {
+                    self.error("invalid assignment target".to_string(), expr.span());
                 }
-                let (_, clean) = self.expect_or_flag(TokenKind::Semicolon);
-                (Stmt::Expr(expr), clean)
+                let start = expr.span();
+                self.bump(); // the assignment operator
+                let value = match next.kind {
+                    // The step is a literal at the operator's span — the
+                    // checker keys expression types by span.
+                    TokenKind::PlusPlus | TokenKind::MinusMinus => Expr::Int(1, next.span),
+                    _ => self.parse_expr(0),
+                };
+                let (end, clean) = self.expect_or_flag(TokenKind::Semicolon);
+                (
+                    Stmt::Assign {
+                        target: expr,
+                        op,
+                        value,
+                        step: matches!(next.kind, TokenKind::PlusPlus | TokenKind::MinusMinus),
+                        span: start.to(end),
+                    },
+                    clean,
+                )
             }
         }
     }
 }
+
+/// The operator a compound assignment token applies (ADR 0050).
+fn compound_op(kind: &TokenKind) -> Option<BinOp> {
+    Some(match kind {
+        TokenKind::PlusEq => BinOp::Add,
+        TokenKind::MinusEq => BinOp::Sub,
+        TokenKind::AsteriskEq => BinOp::Mul,
+        TokenKind::SlashEq => BinOp::Div,
+        TokenKind::PercentEq => BinOp::Rem,
+        TokenKind::AmpEq => BinOp::BitAnd,
+        TokenKind::PipeEq => BinOp::BitOr,
+        TokenKind::CaretEq => BinOp::BitXor,
+        TokenKind::LessLessEq => BinOp::Shl,
+        TokenKind::GreaterGreaterEq => BinOp::Shr,
+        TokenKind::GreaterGreaterGreaterEq => BinOp::UShr,
+        TokenKind::QuestionQuestionEq => BinOp::Coalesce,
+        _ => return None,
+    })
+}
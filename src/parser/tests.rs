@@ -412,13 +412,13 @@ fn parses_error_declarations() {
         panic!("expected error decl")
     };
     assert!(!e.exported);
-    assert_eq!(e.names.len(), 1);
-    assert_eq!(e.names[0].0, "NotFound");
+    assert_eq!(e.codes.len(), 1);
+    assert_eq!(e.codes[0].name, "NotFound");
     let Item::Error(e) = &ast[1] else {
         panic!("expected error decl")
     };
     assert!(e.exported);
-    let names: Vec<&str> = e.names.iter().map(|(n, _)| n.as_str()).collect();
+    let names: Vec<&str> = e.codes.iter().map(|c| c.name.as_str()).collect();
     assert_eq!(names, ["Timeout", "Busy"]);
 }
 
@@ -880,6 +880,748 @@ fn enum_declarations_and_match_parse() {
         panic!("expected a match")
     };
     assert_eq!(arms.len(), 1);
-    assert_eq!(arms[0].bindings[0].0, "r");
+    assert_eq!(arms[0].pattern.sexpr(), "(Circle r)");
     assert!(else_body.is_some());
 }
+
+// --- Function values (ADR 0038) ---
+
+#[test]
+fn function_types_parse_with_greedy_returns_and_grouping() {
+    let (tokens, _) = lex(
+        "fun f(a: fun(int, string): int, b: fun(), c: (fun(int): int)[], \
+         d: fun(): int[], e: (fun(): int)?) {}",
+    );
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let shown: Vec<String> = f.params.iter().map(|p| p.ty.show()).collect();
+    assert_eq!(
+        shown,
+        [
+            "(fun(int, string): int)",
+            "(fun())",
+            "(fun(int): int)[]",
+            "(fun(): int[])",
+            "(fun(): int)?",
+        ]
+    );
+}
+
+#[test]
+fn generic_instances_parse_as_values_before_operand_ends() {
+    assert_eq!(expr("f(id<int>, x)").sexpr(), "(call f id<int> x)");
+    assert_eq!(expr("[id<int>]").sexpr(), "[id<int>]");
+    // Anything else after `>` still reads as comparison.
+    assert_eq!(expr("a < b > c").sexpr(), "(> (< a b) c)");
+}
+
+#[test]
+fn grouped_field_callee_is_a_call_not_enum_construction() {
+    assert_eq!(expr("(p.run)(3)").sexpr(), "(call (. p run) 3)");
+    assert_eq!(expr("p.run(3)").sexpr(), "(enum p.run 3)");
+    assert_eq!(expr("ops[0](3)").sexpr(), "(call (idx ops 0) 3)");
+    assert_eq!(expr("make()(3)").sexpr(), "(call (call make ) 3)");
+}
+
+// --- Function literals (ADR 0039) ---
+
+#[test]
+fn function_literals_parse_as_atoms() {
+    assert_eq!(
+        expr("fun (x: int): int { return x + k; }").sexpr(),
+        "(fun (x: int): int {1 stmts})"
+    );
+    assert_eq!(
+        expr("apply(fun () {}, 2)").sexpr(),
+        "(call apply (fun () {0 stmts}) 2)"
+    );
+    // A literal is a callee like any other primary.
+    assert_eq!(
+        expr("fun (): int { return 1; }()").sexpr(),
+        "(call (fun (): int {1 stmts}) )"
+    );
+}
+
+// --- Methods (ADR 0040) ---
+
+#[test]
+fn method_calls_parse_on_any_non_ident_receiver() {
+    assert_eq!(expr("p.q.sum()").sexpr(), "(method (. p q) sum )");
+    assert_eq!(
+        expr("make().scaled(2).area()").sexpr(),
+        "(method (method (call make ) scaled 2) area )"
+    );
+    // On a bare name the checker decides: variable or enum.
+    assert_eq!(expr("p.sum(1)").sexpr(), "(enum p.sum 1)");
+}
+
+#[test]
+fn impl_blocks_qualify_methods_and_inherit_type_params() {
+    let (tokens, _) = lex("impl Pair<T, U> {\n\
+             fun first(self): T { return self.a; }\n\
+             fun with<V>(self, v: V): Pair<T, V> { return (Pair<T, V> { a: self.a, b: v }); }\n\
+         }");
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Impl(i) = &items[0] else {
+        panic!("expected an impl")
+    };
+    assert_eq!(i.methods.len(), 2);
+    let names: Vec<&str> = i.methods.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["Pair.first", "Pair.with"]);
+    assert_eq!(i.methods[0].params[0].ty.show(), "Pair<T, U>");
+    let params: Vec<&str> = i.methods[1]
+        .type_params
+        .iter()
+        .map(|(n, _)| n.as_str())
+        .collect();
+    assert_eq!(params, ["T", "U", "V"]);
+}
+
+#[test]
+fn methods_must_take_a_bare_self_first() {
+    for (src, want) in [
+        (
+            "impl P { fun f(x: int) {} }",
+            "a method's first parameter must be 'self'",
+        ),
+        (
+            "impl P { fun f() {} }",
+            "a method's first parameter must be 'self'",
+        ),
+        ("impl P { fun f(self: P) {} }", "drop the annotation"),
+        ("impl P { struct Q {} }", "expected 'fun' in an impl block"),
+    ] {
+        let (tokens, _) = lex(src);
+        let (_, diags) = parse(&tokens);
+        assert!(
+            diags.iter().any(|d| d.message.contains(want)),
+            "{src}: {diags:?}"
+        );
+    }
+}
+
+// --- Interfaces (ADR 0041) ---
+
+#[test]
+fn interfaces_and_bounds_parse() {
+    let (tokens, _) = lex(
+        "export interface Ordered { fun less(self, other: Self): bool; }\n\
+         fun max<T: Ordered + Show, U>(a: T, b: U): T { return a; }",
+    );
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Interface(i) = &items[0] else {
+        panic!("expected an interface")
+    };
+    assert!(i.exported);
+    assert_eq!(i.methods[0].name, "less");
+    let shown: Vec<String> = i.methods[0].params.iter().map(|p| p.ty.show()).collect();
+    assert_eq!(shown, ["Self", "Self"]);
+    let Item::Function(f) = &items[1] else {
+        panic!("expected a function")
+    };
+    assert_eq!(f.type_params.len(), 2);
+    let bounds: Vec<(&str, &str)> = f
+        .bounds
+        .iter()
+        .map(|b| (b.param.as_str(), b.interface.as_str()))
+        .collect();
+    assert_eq!(bounds, [("T", "Ordered"), ("T", "Show")]);
+}
+
+#[test]
+fn interface_method_semicolons_are_optional_before_fun_and_brace() {
+    for src in [
+        "interface Ordered { fun less(self, other: Self): bool }",
+        "interface Shape { fun area(self): int fun name(self): string; }",
+    ] {
+        let (tokens, _) = lex(src);
+        let (items, diags) = parse(&tokens);
+        assert!(diags.is_empty(), "{src}: {diags:?}");
+        let Item::Interface(i) = &items[0] else {
+            panic!("expected an interface")
+        };
+        assert_eq!(i.methods.len(), src.matches("fun ").count(), "{src}");
+    }
+}
+
+#[test]
+fn bounds_and_interface_shapes_are_checked_by_the_parser() {
+    for (src, want) in [
+        (
+            "struct S<T: Ordered> { v: T }",
+            "type parameter bounds are only allowed on functions and methods",
+        ),
+        (
+            "interface I { fun f<T>(self); }",
+            "interface methods cannot be generic",
+        ),
+        (
+            "interface I { fun f(x: int); }",
+            "a method's first parameter must be 'self'",
+        ),
+        ("interface I { fun f(self) {} }", "expected ';'"),
+    ] {
+        let (tokens, _) = lex(src);
+        let (_, diags) = parse(&tokens);
+        assert!(
+            diags.iter().any(|d| d.message.contains(want)),
+            "{src}: {diags:?}"
+        );
+    }
+}
+
+// --- Maps (ADR 0042) ---
+
+#[test]
+fn map_literals_parse_key_colon_value_entries() {
+    assert_eq!(expr("{}").sexpr(), "(map)");
+    assert_eq!(
+        expr("{ \"a\": 1 + 2, k: [1], }").sexpr(),
+        "(map \"a\"=(+ 1 2) k=[1])"
+    );
+    // Struct literals re-enable inside the braces, as in parentheses.
+    assert_eq!(
+        expr("{ P { x: 1 }: true }").sexpr(),
+        "(map (struct P x=1)=true)"
+    );
+}
+
+// --- Tuples (ADR 0043) ---
+
+#[test]
+fn tuple_literals_and_element_access_parse() {
+    assert_eq!(expr("(1, a + b)").sexpr(), "(tuple 1 (+ a b))");
+    assert_eq!(expr("(a)").sexpr(), "a");
+    // `t.0.1` lexes as two element hops, never the float `0.1`.
+    assert_eq!(expr("t.0.1").sexpr(), expr("(t.0).1").sexpr());
+    match stmt("const (q, _) = f(x);") {
+        Stmt::LetTuple {
+            mutable,
+            names,
+            value,
+            ..
+        } => {
+            assert!(!mutable);
+            assert_eq!(names, ["q", "_"]);
+            assert_eq!(value.sexpr(), "(call f x)");
+        }
+        other => panic!("expected LetTuple, got {other:?}"),
+    }
+}
+
+#[test]
+fn one_element_tuples_are_rejected() {
+    for src in ["fun f(): int { return (1,); }", "fun f(x: (int,)) { }"] {
+        let (tokens, _) = lex(src);
+        let (_, pd) = parse(&tokens);
+        assert!(
+            pd.iter()
+                .any(|e| e.message.contains("at least two elements")),
+            "{src}: {pd:?}"
+        );
+    }
+}
+
+#[test]
+fn match_expressions_parse_as_atoms() {
+    assert_eq!(
+        expr("match s { A(x, _) => x + 1, B => 0, else => -1 } * 2").sexpr(),
+        "(* (match s (A x _ => (+ x 1)) (B => 0) (else => (- 1))) 2)"
+    );
+    // A trailing comma is fine; struct literals are allowed in arms.
+    assert_eq!(
+        expr("match s { A => P { x: 1 }, }").sexpr(),
+        "(match s (A => (struct P x=1)))"
+    );
+    for src in [
+        "fun f(s: S): int { return match s { A { 1 } }; }",
+        "fun f(s: S): int { return match s { A => 1 B => 2 }; }",
+        "fun f(s: S): int { return match s { else => 1, A => 2 }; }",
+    ] {
+        let (tokens, _) = lex(src);
+        let (_, pd) = parse(&tokens);
+        assert!(!pd.is_empty(), "{src}");
+    }
+}
+
+#[test]
+fn nested_literal_and_guarded_patterns_parse() {
+    assert_eq!(
+        expr("match o { Some(Circle(r)) if r > 1.0 => r, Some(Dot()) => 0.0, else => -1.0 }")
+            .sexpr(),
+        "(match o (Some (Circle r) if (> r 1) => r) (Some (Dot) => 0) (else => (- 1)))"
+    );
+    assert_eq!(
+        expr("match t { Num(-1) => 1, Word(\"no\") => 2, Flag(true) => 3, Pair(x, _) => x }")
+            .sexpr(),
+        "(match t (Num -1 => 1) (Word \"no\" => 2) (Flag true => 3) (Pair x _ => x))"
+    );
+    // Statement arms read the guard as a condition, so `{` opens the
+    // arm's block.
+    let (tokens, _) = lex("fun f(s: S) { match s { A(n) if n > 0 { } else { } } }");
+    let (items, pd) = parse(&tokens);
+    assert!(pd.is_empty(), "{pd:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let Stmt::Match { arms, .. } = &f.body[0] else {
+        panic!("expected a match")
+    };
+    assert_eq!(arms[0].head_sexpr(), "A n if (> n 0)");
+    for (src, want) in [
+        ("A(-x)", "expected an integer after '-' in a pattern"),
+        ("A(1.5)", "expected a pattern, found a float"),
+    ] {
+        let (tokens, _) = lex(&format!("fun f(s: S) {{ match s {{ {src} {{ }} }} }}"));
+        let (_, pd) = parse(&tokens);
+        assert!(pd.iter().any(|e| e.message.contains(want)), "{src}: {pd:?}");
+    }
+}
+
+#[test]
+fn literal_arm_heads_parse() {
+    assert_eq!(
+        expr("match n { 0 => 1, -1 => 2, else => 3 }").sexpr(),
+        "(match n (0 => 1) (-1 => 2) (else => 3))"
+    );
+    assert_eq!(
+        expr("match e { error.NotFound => 1, else => 2 }").sexpr(),
+        "(match e (error.NotFound => 1) (else => 2))"
+    );
+    let (tokens, _) = lex("fun f(s: string) { match s { \"a\" { } else { } } }");
+    let (items, pd) = parse(&tokens);
+    assert!(pd.is_empty(), "{pd:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let Stmt::Match { arms, .. } = &f.body[0] else {
+        panic!("expected a match")
+    };
+    assert_eq!(arms[0].head_sexpr(), "\"a\"");
+}
+
+// --- Module consts (ADR 0047) ---
+
+#[test]
+fn module_consts_parse_at_top_level_and_after_export() {
+    let (tokens, _) = lex("const A: int = 1 + 2;\nexport const B: string = \"b\";");
+    let (items, pd) = parse(&tokens);
+    assert!(pd.is_empty(), "{pd:?}");
+    let [Item::Const(a), Item::Const(b)] = &items[..] else {
+        panic!("expected two consts, got {items:?}")
+    };
+    assert_eq!((a.name.as_str(), a.exported), ("A", false));
+    assert_eq!(a.value.sexpr(), "(+ 1 2)");
+    assert_eq!((b.name.as_str(), b.exported), ("B", true));
+}
+
+// --- Module vars (ADR 0048) ---
+
+#[test]
+fn module_vars_parse_like_consts() {
+    let (tokens, _) = lex("var hits: int = 0;\nexport var names: string[] = [];");
+    let (items, pd) = parse(&tokens);
+    assert!(pd.is_empty(), "{pd:?}");
+    let [Item::Global(a), Item::Global(b)] = &items[..] else {
+        panic!("expected two vars, got {items:?}")
+    };
+    assert_eq!((a.name.as_str(), a.exported), ("hits", false));
+    assert_eq!((b.name.as_str(), b.exported), ("names", true));
+}
+
+// --- Bitwise operators (ADR 0049) ---
+
+#[test]
+fn bitwise_operators_bind_like_c() {
+    assert_eq!(expr("a | b ^ c & d").sexpr(), "(| a (^ b (& c d)))");
+    assert_eq!(expr("a & b == c").sexpr(), "(& a (== b c))");
+    assert_eq!(expr("a < b << c").sexpr(), "(< a (<< b c))");
+    assert_eq!(expr("a << 1 + b").sexpr(), "(<< a (+ 1 b))");
+    assert_eq!(expr("a >> b >>> c").sexpr(), "(>>> (>> a b) c)");
+    assert_eq!(expr("a || b | c && d").sexpr(), "(|| a (&& (| b c) d))");
+    assert_eq!(expr("~a & -b").sexpr(), "(& (~ a) (- b))");
+}
+
+#[test]
+fn shift_tokens_split_when_closing_type_arguments() {
+    let (tokens, _) = lex("fun f() { var b: Box<Box<int>> = x; var c: Box<Box<Box<int>>>= y; }");
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let shows: Vec<String> = f
+        .body
+        .iter()
+        .map(|s| match s {
+            Stmt::Let { ty: Some(ty), .. } => ty.show(),
+            other => panic!("expected an annotated let, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(shows, ["Box<Box<int>>", "Box<Box<Box<int>>>"]);
+    assert_eq!(
+        expr("id<Box<int>>(x) >> 2").sexpr(),
+        "(>> (call id<Box<int>> x) 2)"
+    );
+    // A speculation that splits `>>` and then rolls back restores it.
+    assert_eq!(expr("a < b<c >> 2").sexpr(), "(< (< a b) (>> c 2))");
+}
+
+// --- Compound assignment (ADR 0050) ---
+
+#[test]
+fn compound_assignments_carry_their_operator() {
+    for (src, op, value) in [
+        ("x += 2;", BinOp::Add, "2"),
+        ("xs[i].n -= a * b;", BinOp::Sub, "(* a b)"),
+        ("x *= 2;", BinOp::Mul, "2"),
+        ("x /= 2;", BinOp::Div, "2"),
+        ("x %= 2;", BinOp::Rem, "2"),
+        ("x &= 2;", BinOp::BitAnd, "2"),
+        ("x |= 2;", BinOp::BitOr, "2"),
+        ("x ^= 2;", BinOp::BitXor, "2"),
+        ("x <<= 2;", BinOp::Shl, "2"),
+        ("x >>= 2;", BinOp::Shr, "2"),
+        ("x >>>= 2;", BinOp::UShr, "2"),
+        ("o.v ??= f(1);", BinOp::Coalesce, "(call f 1)"),
+        ("x++;", BinOp::Add, "1"),
+        ("p.n--;", BinOp::Sub, "1"),
+    ] {
+        match stmt(src) {
+            Stmt::Assign {
+                op: Some(got),
+                value: v,
+                ..
+            } => {
+                assert_eq!(got, op, "{src}");
+                assert_eq!(v.sexpr(), value, "{src}");
+            }
+            other => panic!("{src}: expected a compound Assign, got {other:?}"),
+        }
+    }
+    assert!(matches!(stmt("x = 1;"), Stmt::Assign { op: None, .. }));
+}
+
+#[test]
+fn compound_assignment_needs_a_place() {
+    let (tokens, _) = lex("fun f() { a + b += 1; g()++; }");
+    let (_, diags) = parse(&tokens);
+    assert_eq!(diags.len(), 2, "{diags:?}");
+    assert!(
+        diags
+            .iter()
+            .all(|d| d.message == "invalid assignment target")
+    );
+    // Closing type arguments splits the assigning shift too.
+    let (tokens, _) = lex("fun f() { var b: Box<Box<int>>= x; }");
+    let (_, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+// --- Ranges and slices (ADR 0051) ---
+
+#[test]
+fn for_ranges_carry_bounds_and_step() {
+    for (src, range) in [
+        ("for i in 0..n { }", "(.. 0 n)"),
+        (
+            "for i in a + 1..=len(xs) - 1 { }",
+            "(..= (+ a 1) (- (call len xs) 1))",
+        ),
+        ("for i in 10..0 step -2 { }", "(.. 10 0 step (- 2))"),
+        ("for i in 0..n step k * 2 { }", "(.. 0 n step (* k 2))"),
+    ] {
+        match stmt(src) {
+            Stmt::For { iterable, .. } => assert_eq!(iterable.sexpr(), range, "{src}"),
+            other => panic!("{src}: expected For, got {other:?}"),
+        }
+    }
+    // `step` is only a word after a range's end.
+    let (tokens, _) = lex("fun f() { var step: int = 2; for i in 0..9 step step { } }");
+    let (_, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+#[test]
+fn slice_bounds_may_be_open() {
+    assert_eq!(expr("xs[1..3]").sexpr(), "(idx xs (.. 1 3))");
+    assert_eq!(expr("xs[i..]").sexpr(), "(idx xs (.. i _))");
+    assert_eq!(expr("xs[..=j]").sexpr(), "(idx xs (..= _ j))");
+    assert_eq!(expr("xs[..]").sexpr(), "(idx xs (.. _ _))");
+    assert_eq!(expr("xs[1..][0]").sexpr(), "(idx (idx xs (.. 1 _)) 0)");
+    // A range is no operator: outside `for` and brackets it is an error,
+    // and a `for` range needs its end.
+    for src in [
+        "fun f() { const r: int = 0..2; }",
+        "fun f() { for i in 0.. { } }",
+    ] {
+        let (tokens, _) = lex(src);
+        let (_, diags) = parse(&tokens);
+        assert!(!diags.is_empty(), "{src}");
+    }
+}
+
+// --- Sized integers (ADR 0052) ---
+
+#[test]
+fn sized_integer_types_annotate_and_convert() {
+    assert_eq!(expr("u8(x) + i32(-1)").sexpr(), "(+ (u8 x) (i32 (- 1)))");
+    assert_eq!(expr("u64(int(f))").sexpr(), "(u64 (int f))");
+    let (tokens, _) = lex("fun f(xs: u8[], n: i16?): u64 { var w: i64 = 0; }");
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let params: Vec<String> = f.params.iter().map(|p| p.ty.show()).collect();
+    assert_eq!(params, ["u8[]", "i16?"]);
+    let Stmt::Let { ty: Some(ty), .. } = &f.body[0] else {
+        panic!("expected an annotated let")
+    };
+    // `i64` is another spelling of `int`.
+    assert_eq!(ty.show(), "int");
+}
+
+// --- Fixed arrays (ADR 0056) ---
+
+#[test]
+fn fixed_array_types_and_repeat_literals_parse() {
+    assert_eq!(expr("[0; 16]").sexpr(), "[0; 16]");
+    assert_eq!(expr("[f(x); 2][1]").sexpr(), "(idx [(call f x); 2] 1)");
+    let (tokens, _) = lex("fun f(m: int[3][2], r: string?[4], g: u8[]) { }");
+    let (items, diags) = parse(&tokens);
+    assert!(diags.is_empty(), "{diags:?}");
+    let Item::Function(f) = &items[0] else {
+        panic!("expected a function")
+    };
+    let params: Vec<String> = f.params.iter().map(|p| p.ty.show()).collect();
+    assert_eq!(params, ["int[3][2]", "string?[4]", "u8[]"]);
+    for (src, message) in [
+        (
+            "fun f(a: int[0]) { }",
+            "a fixed array holds 1 to 4096 elements, found 0",
+        ),
+        (
+            "fun f(a: int[n]) { }",
+            "expected an array length, found an identifier",
+        ),
+        (
+            "fun f() { print([0; 4097]); }",
+            "a fixed array holds 1 to 4096 elements, found 4097",
+        ),
+    ] {
+        let (tokens, _) = lex(src);
+        let (_, diags) = parse(&tokens);
+        assert!(
+            diags.iter().any(|e| e.message == message),
+            "{src}: {diags:?}"
+        );
+    }
+}
+
+// --- Destructuring (ADR 0057) ---
+
+#[test]
+fn struct_destructuring_and_let_else_parse() {
+    match stmt("var { x, y: py } = p;") {
+        Stmt::LetStruct {
+            mutable,
+            fields,
+            value,
+            ..
+        } => {
+            assert!(mutable);
+            let pairs: Vec<(&str, &str)> = fields
+                .iter()
+                .map(|(f, n, _)| (f.as_str(), n.as_str()))
+                .collect();
+            assert_eq!(pairs, [("x", "x"), ("y", "py")]);
+            assert_eq!(value.sexpr(), "p");
+        }
+        other => panic!("expected LetStruct, got {other:?}"),
+    }
+    match stmt("const Circle(r) = f(s) else { return 0; };") {
+        Stmt::LetElse {
+            pattern,
+            value,
+            else_body,
+            ..
+        } => {
+            assert!(matches!(pattern, Pattern::Variant { name, args, .. }
+                if name == "Circle" && args.len() == 1));
+            assert_eq!(value.sexpr(), "(call f s)");
+            assert_eq!(else_body.map(|b| b.len()), Some(1));
+        }
+        other => panic!("expected LetElse, got {other:?}"),
+    }
+    assert!(matches!(
+        stmt("const Only(k) = o;"),
+        Stmt::LetElse {
+            else_body: None,
+            ..
+        }
+    ));
+    let (tokens, _) = lex("fun f() { const {} = p; }");
+    let (_, diags) = parse(&tokens);
+    assert!(
+        diags
+            .iter()
+            .any(|e| e.message == "a destructuring binds at least one field"),
+        "{diags:?}"
+    );
+}
+
+// --- Binding conditions (ADR 0058) ---
+
+#[test]
+fn if_const_and_while_const_parse() {
+    match stmt("if const v = xs[i] { f(v); } else if const Circle(r) = s { } else { }") {
+        Stmt::IfConst {
+            pattern,
+            value,
+            else_body: Some(else_body),
+            ..
+        } => {
+            assert!(matches!(pattern, Pattern::Bind(name, _) if name == "v"));
+            assert_eq!(value.sexpr(), "(idx xs i)");
+            assert!(matches!(
+                &else_body[..],
+                [Stmt::IfConst {
+                    pattern: Pattern::Variant { .. },
+                    else_body: Some(_),
+                    ..
+                }]
+            ));
+        }
+        other => panic!("expected IfConst, got {other:?}"),
+    }
+    match stmt("while const line = readLine(f) { print(line); }") {
+        Stmt::WhileConst { pattern, value, .. } => {
+            assert!(matches!(pattern, Pattern::Bind(name, _) if name == "line"));
+            assert_eq!(value.sexpr(), "(call readLine f)");
+        }
+        other => panic!("expected WhileConst, got {other:?}"),
+    }
+    let (tokens, _) = lex("fun f() { if const 3 = n { } print(1); }");
+    let (_, diags) = parse(&tokens);
+    let messages: Vec<&str> = diags.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        ["expected a name or a variant pattern, found an integer"]
+    );
+}
+
+// --- Loop labels (ADR 0059) ---
+
+#[test]
+fn labels_attach_to_loops_and_loop_controls() {
+    let s = stmt("outer: while a { inner: for x in xs { break outer; continue; } }");
+    let Stmt::While {
+        label: Some(outer),
+        body,
+        ..
+    } = s
+    else {
+        panic!("expected labeled While, got {s:?}");
+    };
+    assert_eq!(outer, "outer");
+    let Stmt::For {
+        label: Some(inner),
+        body,
+        ..
+    } = &body[0]
+    else {
+        panic!("expected labeled For");
+    };
+    assert_eq!(inner, "inner");
+    assert!(matches!(&body[0], Stmt::Break { label: Some(l), .. } if l == "outer"));
+    assert!(matches!(&body[1], Stmt::Continue { label: None, .. }));
+    assert!(matches!(
+        stmt("rows: while const r = next() { continue rows; }"),
+        Stmt::WhileConst { label: Some(l), .. } if l == "rows"
+    ));
+
+    let (tokens, _) = lex("fun f() { here: print(1); }");
+    let (_, diags) = parse(&tokens);
+    let messages: Vec<&str> = diags.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        ["expected 'while' or 'for' after the label 'here', found an identifier"]
+    );
+}
+
+// --- Defer (ADR 0060) ---
+
+#[test]
+fn defer_takes_a_statement_or_a_block() {
+    match stmt("defer close(f);") {
+        Stmt::Defer { body, .. } => {
+            assert!(matches!(&body[..], [Stmt::Expr(e)] if e.sexpr() == "(call close f)"));
+        }
+        other => panic!("expected Defer, got {other:?}"),
+    }
+    match stmt("defer { n = n + 1; print(n); }") {
+        Stmt::Defer { body, .. } => {
+            assert!(matches!(&body[..], [Stmt::Assign { .. }, Stmt::Expr(_)]));
+        }
+        other => panic!("expected Defer, got {other:?}"),
+    }
+    // Recovery stops before `defer`, like the other statement keywords.
+    let (tokens, _) = lex("fun f() { const = 1 defer close(f); }");
+    let (ast, pd) = parse(&tokens);
+    assert!(!pd.is_empty());
+    let Item::Function(f) = &ast[0] else {
+        panic!("expected function")
+    };
+    assert!(
+        f.body.iter().any(|s| matches!(s, Stmt::Defer { .. })),
+        "defer must survive recovery: {:?}",
+        f.body
+    );
+}
+
+// --- Arena blocks (ADR 0061) ---
+
+#[test]
+fn arena_takes_a_block() {
+    match stmt("arena { const xs: int[] = [1]; print(len(xs)); }") {
+        Stmt::Arena { body, .. } => {
+            assert!(matches!(&body[..], [Stmt::Let { .. }, Stmt::Expr(_)]));
+        }
+        other => panic!("expected Arena, got {other:?}"),
+    }
+    let (tokens, _) = lex("fun f() { arena print(1); }");
+    let (_, pd) = parse(&tokens);
+    assert!(!pd.is_empty(), "arena needs a block");
+}
+
+// --- Error payloads (ADR 0062) ---
+
+#[test]
+fn error_codes_declare_payloads() {
+    let (tokens, _) = lex("error NotFound, ParseFailed(int, string);");
+    let (ast, pd) = parse(&tokens);
+    assert!(pd.is_empty(), "parse errors: {pd:?}");
+    let Item::Error(e) = &ast[0] else {
+        panic!("expected error decl")
+    };
+    assert!(e.codes[0].payloads.is_empty());
+    assert_eq!(e.codes[1].payloads, [TypeAnn::Int, TypeAnn::Str]);
+}
+
+#[test]
+fn error_literals_and_patterns_take_payloads() {
+    assert_eq!(
+        expr("error.ParseFailed(line, \"eof\")").sexpr(),
+        "(error.ParseFailed line \"eof\")"
+    );
+    assert_eq!(
+        expr("match e { error.ParseFailed(n, _) => n, error.NotFound => 0, else => 1 }").sexpr(),
+        "(match e (error.ParseFailed n _ => n) (error.NotFound => 0) (else => 1))"
+    );
+}
@@ -33,6 +33,9 @@ pub const LESS: char = '<';
 pub const GREATER: char = '>';
 pub const AMPERSAND: char = '&';
 pub const PIPE: char = '|';
+/// Bitwise xor and complement (ADR 0049).
+pub const CARET: char = '^';
+pub const TILDE: char = '~';
 pub const QUESTION: char = '?';
 pub const UNDERSCORE: char = '_';
 
@@ -60,11 +63,30 @@ pub const KW_FLOAT: &str = "float";
 pub const KW_BOOL: &str = "bool";
 pub const KW_STRING: &str = "string";
 pub const KW_FILE: &str = "file";
+/// Sized integers (ADR 0052); `i64` is another spelling of `int`.
+pub const KW_I8: &str = "i8";
+pub const KW_I16: &str = "i16";
+pub const KW_I32: &str = "i32";
+pub const KW_I64: &str = "i64";
+pub const KW_U8: &str = "u8";
+pub const KW_U16: &str = "u16";
+pub const KW_U32: &str = "u32";
+pub const KW_U64: &str = "u64";
+/// A string's element (ADR 0053): another spelling of `u8`.
+pub const KW_BYTE: &str = "byte";
 pub const KW_ERROR: &str = "error";
 pub const KW_TRY: &str = "try";
 /// Payload enums and their consumption (ADR 0036).
 pub const KW_ENUM: &str = "enum";
 pub const KW_MATCH: &str = "match";
+/// Method blocks (ADR 0040).
+pub const KW_IMPL: &str = "impl";
+/// Method-set declarations for generic bounds (ADR 0041).
+pub const KW_INTERFACE: &str = "interface";
+/// Scope-exit cleanup (ADR 0060).
+pub const KW_DEFER: &str = "defer";
+/// Region blocks (ADR 0061).
+pub const KW_ARENA: &str = "arena";
 pub const KW_TRUE: &str = "true";
 pub const KW_FALSE: &str = "false";
 pub const KW_NULL: &str = "null";
@@ -81,6 +103,17 @@ pub const KW_FROM: &str = "from";
 /// The program entry point's function name — the driver, checker, and
 /// backend all resolve the entry through this one spelling.
 pub const ENTRY_FN: &str = "main";
+/// A method's receiver parameter (ADR 0040) — an ordinary identifier
+/// everywhere else; only a method's first parameter must spell it.
+pub const SELF_PARAM: &str = "self";
+/// Inside an interface, the type that satisfies it (ADR 0041).
+pub const SELF_TYPE: &str = "Self";
+/// The built-in hash map type, `map<K, V>` (ADR 0042) — not a keyword:
+/// a user type of the same name shadows it, like the builtins below.
+pub const TYPE_MAP: &str = "map";
+/// A range's stride, `for i in 0..n step 2` (ADR 0051) — contextual:
+/// only a word after a `for` range's end, an identifier everywhere else.
+pub const RANGE_STEP: &str = "step";
 
 // --- Builtin function names ---
 // Not keywords: a user definition of the same name shadows the builtin,
@@ -94,6 +127,35 @@ pub const BUILTIN_READ: &str = "read";
 pub const BUILTIN_READLINE: &str = "readLine";
 pub const BUILTIN_WRITE: &str = "write";
 pub const BUILTIN_CLOSE: &str = "close";
+/// Map operations (ADR 0042); `len` counts map entries too. `insert`
+/// also shifts an element into an array (ADR 0055).
+pub const BUILTIN_INSERT: &str = "insert";
+pub const BUILTIN_REMOVE: &str = "remove";
+pub const BUILTIN_HAS: &str = "has";
+/// The array library (ADR 0055), next to `push` and `insert`.
+pub const BUILTIN_POP: &str = "pop";
+pub const BUILTIN_REMOVE_AT: &str = "removeAt";
+pub const BUILTIN_SLICE: &str = "slice";
+pub const BUILTIN_CLEAR: &str = "clear";
+pub const BUILTIN_SORT: &str = "sort";
+pub const BUILTIN_REVERSE: &str = "reverse";
+pub const BUILTIN_INDEX_OF: &str = "indexOf";
+pub const BUILTIN_CONTAINS: &str = "contains";
+/// A string's UTF-8 code points as a `u32[]` (ADR 0053).
+pub const BUILTIN_CHARS: &str = "chars";
+/// The string library (ADR 0054): searching, splitting, and parsing
+/// over bytes.
+pub const BUILTIN_FIND: &str = "find";
+pub const BUILTIN_STARTS_WITH: &str = "startsWith";
+pub const BUILTIN_ENDS_WITH: &str = "endsWith";
+pub const BUILTIN_SPLIT: &str = "split";
+pub const BUILTIN_TRIM: &str = "trim";
+pub const BUILTIN_REPLACE: &str = "replace";
+pub const BUILTIN_JOIN: &str = "join";
+pub const BUILTIN_TO_UPPER: &str = "toUpper";
+pub const BUILTIN_TO_LOWER: &str = "toLower";
+pub const BUILTIN_PARSE_INT: &str = "parseInt";
+pub const BUILTIN_PARSE_FLOAT: &str = "parseFloat";
 
 /// True for a source line break (`\n` or `\r`). CRLF is handled by the caller
 /// consuming the trailing `\n`.
@@ -1,4 +1,5 @@
 use crate::span::Span;
+use crate::types::IntTy;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
@@ -31,12 +32,22 @@ pub enum TokenKind {
     Enum,
     /// `match` — variant dispatch (ADR 0036).
     Match,
+    /// `impl` — method blocks (ADR 0040).
+    Impl,
+    /// `interface` — method-set declarations (ADR 0041).
+    Interface,
+    /// `defer` — cleanup run at the block's exit (ADR 0060).
+    Defer,
+    /// `arena` — a block whose allocations free at its exit (ADR 0061).
+    Arena,
     // Type keywords
     IntType,
     FloatType,
     BoolType,
     StringType,
     FileType,
+    /// `i8` … `u64` (ADR 0052); `i64` lexes as `IntType`.
+    SizedType(IntTy),
     // Literals & identifiers
     Identifier(String),
     IntLiteral(i64),
@@ -60,6 +71,8 @@ pub enum TokenKind {
     // Operators
     Equals,
     EqEq,
+    /// `=>` — a match-expression arm (ADR 0044).
+    FatArrow,
     Plus,
     Minus,
     Asterisk,
@@ -73,6 +86,16 @@ pub enum TokenKind {
     GreaterEq,
     AmpAmp,
     PipePipe,
+    /// `&`, `|`, `^`, `~` — bitwise operators on `int` (ADR 0049).
+    Amp,
+    Pipe,
+    Caret,
+    Tilde,
+    /// `<<`, `>>` (arithmetic), and `>>>` (logical) shifts (ADR 0049).
+    /// A type-argument list closing onto `>>` splits it.
+    LessLess,
+    GreaterGreater,
+    GreaterGreaterGreater,
     LeftBracket,
     RightBracket,
     /// `?` — only valid as a postfix type modifier (`int?`).
@@ -81,6 +104,26 @@ pub enum TokenKind {
     QuestionQuestion,
     /// `?.` — optional chaining.
     QuestionDot,
+    /// `..` and `..=` — range bounds (ADR 0051).
+    DotDot,
+    DotDotEq,
+    /// Compound assignment `place op= value` (ADR 0050) — statement
+    /// syntax, one token per operator that has an assigning form.
+    PlusEq,
+    MinusEq,
+    AsteriskEq,
+    SlashEq,
+    PercentEq,
+    AmpEq,
+    PipeEq,
+    CaretEq,
+    LessLessEq,
+    GreaterGreaterEq,
+    GreaterGreaterGreaterEq,
+    QuestionQuestionEq,
+    /// `place++;` and `place--;` — statement sugar for `+= 1` / `-= 1`.
+    PlusPlus,
+    MinusMinus,
     // End of input
     Eof,
 }
@@ -3,9 +3,14 @@
 //! predicates, and the resolved signature shapes. The checker produces
 //! these; the interpreter and future codegen consume them.
 
+use crate::syntax;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Int,
+    /// A sized integer (ADR 0052): one word in registers and struct
+    /// fields, packed at its own width in array buffers.
+    Sized(IntTy),
     Float,
     Bool,
     Str,
@@ -23,12 +28,25 @@ pub enum Type {
     /// `T[]` — growable array, reference semantics (aliased, identity
     /// equality), like refstruct.
     Array(Box<Type>),
+    /// `T[4]` — a fixed-size array (ADR 0056): value semantics, laid
+    /// out like a value struct of N elements, compared element-wise.
+    Fixed(Box<Type>, usize),
+    /// `(int, string)` — an anonymous value tuple (ADR 0043): laid out
+    /// and compared like a value struct whose fields are `.0`, `.1`, ….
+    Tuple(Vec<Type>),
+    /// `map<K, V>` — a hash map in insertion order (ADR 0042),
+    /// reference semantics like arrays (aliased, identity equality).
+    Map(Box<Type>, Box<Type>),
     /// `error` — a declared error code (ADR 0034): one word, identity
     /// equality, module-scoped names.
     ErrCode,
     /// `T!` — T or a declared error code (ADR 0034): tag word first
     /// (0 = value, 1 = reserved, ≥2 = the code), payload after.
     ErrUnion(Box<Type>),
+    /// `fun(int, string): int` — a function value (ADR 0038): one
+    /// word, identity equality, ref-shaped so `(fun(…))?` is a free
+    /// word. A unit return is `Unit`.
+    Fn(Vec<Type>, Box<Type>),
     /// The type of the `null` literal; fits only into `T?` slots.
     Null,
     Unit,
@@ -48,17 +66,25 @@ pub enum Type {
 pub(crate) fn poisoned(t: &Type) -> bool {
     match t {
         Type::Error => true,
-        Type::Optional(inner) | Type::Array(inner) | Type::ErrUnion(inner) => poisoned(inner),
+        Type::Optional(inner)
+        | Type::Array(inner)
+        | Type::Fixed(inner, _)
+        | Type::ErrUnion(inner) => poisoned(inner),
+        Type::Fn(params, ret) => params.iter().any(poisoned) || poisoned(ret),
+        Type::Map(k, v) => poisoned(k) || poisoned(v),
+        Type::Tuple(elems) => elems.iter().any(poisoned),
         _ => false,
     }
 }
 
 /// An element type that imposes no constraint yet — `[]`, or nested empties
-/// like `[[]]`.
+/// like `[[]]`; `{}` leaves both the key and the value open.
 pub(crate) fn unconstrained(t: &Type) -> bool {
     match t {
         Type::Unknown => true,
-        Type::Array(inner) => unconstrained(inner),
+        Type::Array(inner) | Type::Fixed(inner, _) => unconstrained(inner),
+        Type::Map(k, v) => unconstrained(k) || unconstrained(v),
+        Type::Tuple(elems) => elems.iter().any(unconstrained),
         _ => false,
     }
 }
@@ -70,9 +96,13 @@ pub(crate) fn unconstrained(t: &Type) -> bool {
 pub(crate) fn canon_name(t: &Type) -> String {
     match t {
         Type::Struct(m, n) | Type::Enum(m, n) => format!("{n}#{m}"),
-        Type::Optional(inner) => format!("{}?", canon_name(inner)),
-        Type::Array(inner) => format!("{}[]", canon_name(inner)),
-        Type::ErrUnion(inner) => format!("{}!", canon_name(inner)),
+        Type::Optional(inner) => format!("{}?", grouped(inner, canon_name)),
+        Type::Array(inner) => format!("{}[]", grouped(inner, canon_name)),
+        Type::Fixed(inner, n) => format!("{}[{n}]", grouped(inner, canon_name)),
+        Type::ErrUnion(inner) => format!("{}!", grouped(inner, canon_name)),
+        Type::Fn(params, ret) => fn_name(params, ret, canon_name),
+        Type::Map(k, v) => format!("map<{}, {}>", canon_name(k), canon_name(v)),
+        Type::Tuple(elems) => tuple_name(elems, canon_name),
         other => other.name(),
     }
 }
@@ -106,6 +136,7 @@ impl Type {
     pub fn name(&self) -> String {
         match self {
             Type::Int => "int".to_string(),
+            Type::Sized(t) => t.name().to_string(),
             Type::Float => "float".to_string(),
             Type::Bool => "bool".to_string(),
             Type::Str => "string".to_string(),
@@ -114,11 +145,16 @@ impl Type {
             // module qualifiers (ADR 0035) — `pretty` is the identity
             // for source names.
             Type::Struct(_, n) | Type::Enum(_, n) => pretty(n),
-            Type::Optional(inner) => format!("{}?", inner.name()),
+            Type::Optional(inner) => format!("{}?", grouped(inner, Type::name)),
             Type::Array(inner) if unconstrained(inner) => "[]".to_string(),
-            Type::Array(inner) => format!("{}[]", inner.name()),
+            Type::Array(inner) => format!("{}[]", grouped(inner, Type::name)),
+            Type::Fixed(inner, n) => format!("{}[{n}]", grouped(inner, Type::name)),
+            Type::Map(k, v) if unconstrained(k) && unconstrained(v) => "{}".to_string(),
+            Type::Map(k, v) => format!("map<{}, {}>", k.name(), v.name()),
+            Type::Tuple(elems) => tuple_name(elems, Type::name),
             Type::ErrCode => "error".to_string(),
-            Type::ErrUnion(inner) => format!("{}!", inner.name()),
+            Type::ErrUnion(inner) => format!("{}!", grouped(inner, Type::name)),
+            Type::Fn(params, ret) => fn_name(params, ret, Type::name),
             Type::Null => "null".to_string(),
             Type::Unit => "unit".to_string(),
             Type::Error => "<error>".to_string(),
@@ -127,6 +163,31 @@ impl Type {
     }
 }
 
+/// `fun(int, string): int` — the shared rendering of function types;
+/// a unit return drops the `: ret` tail, as in declarations.
+fn fn_name(params: &[Type], ret: &Type, one: fn(&Type) -> String) -> String {
+    let parts: Vec<String> = params.iter().map(one).collect();
+    match ret {
+        Type::Unit => format!("fun({})", parts.join(", ")),
+        ret => format!("fun({}): {}", parts.join(", "), one(ret)),
+    }
+}
+
+/// `(int, string)` — the shared rendering of tuple types.
+fn tuple_name(elems: &[Type], one: fn(&Type) -> String) -> String {
+    let parts: Vec<String> = elems.iter().map(one).collect();
+    format!("({})", parts.join(", "))
+}
+
+/// A suffix operand: function types parenthesize — `(fun(): int)?`
+/// — since the return type would otherwise swallow the suffix.
+fn grouped(t: &Type, one: fn(&Type) -> String) -> String {
+    match t {
+        Type::Fn(..) => format!("({})", one(t)),
+        _ => one(t),
+    }
+}
+
 /// Can a value of type `value` be stored where `target` is expected?
 /// Exact match, or `T`/`null` into `T?`. Never an implicit conversion —
 /// optionality is spelled in the target's type.
@@ -147,6 +208,27 @@ pub(crate) fn fits(value: &Type, target: &Type) -> bool {
         // null into the int[] — except unconstrained elements on either
         // side: `[]` fits any array slot and accepts any element type.
         (Type::Array(v), Type::Array(t)) => unconstrained(v) || unconstrained(t) || v == t,
+        // Maps are invariant in both positions for the same reason;
+        // `{}` leaves both open and fits any map slot.
+        (Type::Map(vk, vv), Type::Map(tk, tv)) => {
+            let slot = |v: &Type, t: &Type| unconstrained(v) || unconstrained(t) || v == t;
+            slot(vk, tk) && slot(vv, tv)
+        }
+        // Fixed arrays follow the tuple rule with one element type
+        // (ADR 0056); the length is part of the type.
+        (Type::Fixed(v, vn), Type::Fixed(t, tn)) => {
+            vn == tn && (unconstrained(v) || unconstrained(t) || v == t)
+        }
+        // Tuples are values, but `T` and `T?` differ in layout, so the
+        // elements must match exactly; literals check element-wise
+        // against their declared slot instead (ADR 0043).
+        (Type::Tuple(vs), Type::Tuple(ts)) => {
+            vs.len() == ts.len()
+                && vs
+                    .iter()
+                    .zip(ts)
+                    .all(|(v, t)| unconstrained(v) || unconstrained(t) || v == t)
+        }
         _ => false,
     }
 }
@@ -184,5 +266,83 @@ pub(crate) fn eq_comparable(lt: &Type, rt: &Type) -> bool {
 }
 
 pub(crate) fn is_numeric(t: &Type) -> bool {
-    matches!(t, Type::Int | Type::Float)
+    matches!(t, Type::Int | Type::Float | Type::Sized(_))
+}
+
+/// The bit-operation domain (ADR 0049): `int` and the sized integers.
+pub(crate) fn is_integer(t: &Type) -> bool {
+    matches!(t, Type::Int | Type::Sized(_))
+}
+
+/// The sized integer types (ADR 0052). `int` is the 64-bit signed one,
+/// so `i64` spells `int` and has no entry here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl IntTy {
+    pub fn bits(self) -> u32 {
+        match self {
+            IntTy::I8 | IntTy::U8 => 8,
+            IntTy::I16 | IntTy::U16 => 16,
+            IntTy::I32 | IntTy::U32 => 32,
+            IntTy::U64 => 64,
+        }
+    }
+
+    pub fn signed(self) -> bool {
+        matches!(self, IntTy::I8 | IntTy::I16 | IntTy::I32)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntTy::I8 => syntax::KW_I8,
+            IntTy::I16 => syntax::KW_I16,
+            IntTy::I32 => syntax::KW_I32,
+            IntTy::U8 => syntax::KW_U8,
+            IntTy::U16 => syntax::KW_U16,
+            IntTy::U32 => syntax::KW_U32,
+            IntTy::U64 => syntax::KW_U64,
+        }
+    }
+
+    /// The unsigned type of the same width — `>>>` shifts a signed
+    /// value's bit pattern through it.
+    pub fn unsigned(self) -> IntTy {
+        match self {
+            IntTy::I8 => IntTy::U8,
+            IntTy::I16 => IntTy::U16,
+            IntTy::I32 => IntTy::U32,
+            other => other,
+        }
+    }
+
+    /// `n` truncated to the type's width, in the canonical word form
+    /// both engines hold: sign-extended for signed types, zero-extended
+    /// for unsigned ones (u64 keeps its bit pattern).
+    pub fn wrap(self, n: i64) -> i64 {
+        let shift = 64 - self.bits();
+        if self.signed() {
+            (n << shift) >> shift
+        } else {
+            ((n as u64) << shift >> shift) as i64
+        }
+    }
+
+    /// Whether the integer `n` is one of the type's values — the
+    /// literal rule. A u64 literal is at most `i64::MAX`: lexing caps
+    /// every literal there.
+    pub fn holds(self, n: i64) -> bool {
+        match self {
+            IntTy::U64 => n >= 0,
+            t => t.wrap(n) == n,
+        }
+    }
 }
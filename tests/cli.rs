@@ -67,6 +67,62 @@ fn main_error_union_exits_one_with_the_trap_shape() {
     );
 }
 
+#[test]
+fn main_error_payloads_render_on_the_trap_exit() {
+    // ADR 0062: the escaping error renders with its payloads, as
+    // `print` would, in BOTH engines — a `string?` payload quoted like
+    // a `string` one.
+    let dir = tempdir();
+    for (name, program, want) in [
+        (
+            "p",
+            "error NotFound, ParseFailed(int, string);\n\
+             fun parse(line: int): int! {\n\
+                 if line > 10 { return error.ParseFailed(line, \"eof\"); }\n\
+                 return error.NotFound;\n\
+             }\n\
+             fun main(): int! {\n\
+                 const n: int = try parse(12);\n\
+                 return n;\n\
+             }",
+            "error: ParseFailed(12, \"eof\")",
+        ),
+        (
+            "q",
+            "error E(string?);\n\
+             fun main(): int! {\n\
+                 const s: string? = \"x\";\n\
+                 return error.E(s);\n\
+             }",
+            "error: E(\"x\")",
+        ),
+    ] {
+        let src = dir.join(format!("{name}.ys"));
+        std::fs::write(&src, program).unwrap();
+        let out = compiler(&[src.to_str().unwrap()]);
+        assert_eq!(out.status.code(), Some(1));
+        assert!(
+            String::from_utf8_lossy(&out.stderr).contains(want),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        let bin = dir.join(name);
+        let out = compiler(&["build", src.to_str().unwrap(), "-o", bin.to_str().unwrap()]);
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        let run = std::process::Command::new(&bin).output().unwrap();
+        assert_eq!(run.status.code(), Some(1));
+        assert!(
+            String::from_utf8_lossy(&run.stderr).contains(want),
+            "{}",
+            String::from_utf8_lossy(&run.stderr)
+        );
+    }
+}
+
 #[test]
 fn runs_a_program_from_its_entry_file() {
     // examples/main.ys imports fib from examples/math.ys — discovery loads it.
@@ -350,7 +406,7 @@ fn compiled_runtime_errors_report_and_exit_1() {
     let dir = tempdir();
     let scratch = dir.join("rt_io.txt");
     let p = scratch.to_str().unwrap();
-    let cases: [(&str, String, &str); 7] = [
+    let cases: [(&str, String, &str); 16] = [
         (
             "rt_f2i",
             "fun main(): int { return int(0.0 / 0.0); }".to_string(),
@@ -394,6 +450,56 @@ fn compiled_runtime_errors_report_and_exit_1() {
                 .to_string(),
             "division overflow",
         ),
+        (
+            "rt_shift",
+            "fun main(): int { var c: int = 64; return 1 << c; }".to_string(),
+            "shift count 64 out of range",
+        ),
+        (
+            "rt_div0_compound",
+            // The statement is the location (ADR 0050).
+            "fun main(): int {\n    var x: int = 7;\n    var z: int = 0;\n    x /= z;\n    return x;\n}"
+                .to_string(),
+            "division by zero",
+        ),
+        (
+            "rt_step0",
+            "fun main(): int {\n    var s: int = 0;\n    for i in 0..3 step s { print(i); }\n    return 0;\n}"
+                .to_string(),
+            "range step must not be zero",
+        ),
+        (
+            "rt_slice",
+            "fun main(): int { const xs: int[] = [1, 2]; return len(xs[1..3]); }".to_string(),
+            "slice 1..3 out of bounds (length 2)",
+        ),
+        (
+            "rt_udiv0",
+            "fun main(): int { const z: u64 = u64(0); return int(u64(7) / z); }".to_string(),
+            "division by zero",
+        ),
+        (
+            "rt_str_slice",
+            "fun main(): int { const s: string = \"abc\"; return len(s[2..1]); }".to_string(),
+            "slice 2..1 out of bounds (length 3)",
+        ),
+        (
+            "rt_split0",
+            "fun main(): int { return len(split(\"a,b\", \"\")); }".to_string(),
+            "'split' separator must not be empty",
+        ),
+        (
+            "rt_insert",
+            "fun main(): int { var xs: int[] = [1]; insert(xs, 2, 0); return 0; }".to_string(),
+            "index 2 out of bounds (length 1)",
+        ),
+        (
+            "rt_fixed",
+            // The length is the type's (ADR 0056); same message, same trap.
+            "fun main(): int { var a: int[3] = [0; 3]; var i: int = 3; a[i] = 1; return 0; }"
+                .to_string(),
+            "index 3 out of bounds (length 3)",
+        ),
     ];
     for (name, program, message) in cases {
         let src = dir.join(format!("{name}.ys"));
@@ -626,3 +732,51 @@ fn double_dash_allows_dashed_source_names() {
         .expect("failed to run built binary");
     assert_eq!(run.status.code(), Some(5));
 }
+
+/// Module vars (ADR 0048): imported modules initialize first — in both
+/// engines — and the initializers' prints come before `main`'s.
+#[test]
+fn module_vars_initialize_dependencies_first_in_both_engines() {
+    let dir = tempdir().join("module_vars");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("main.ys"),
+        "import { count, tick } from \"./counter\";\n\
+         var first: int = tick(\"main\");\n\
+         fun main(): int {\n\
+             print(first);\n\
+             return tick(\"run\") * 10 + count;\n\
+         }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("counter.ys"),
+        "export var count: int = start();\n\
+         fun start(): int { print(\"counter\"); return 1; }\n\
+         export fun tick(who: string): int { print(who); count = count + 1; return count; }",
+    )
+    .unwrap();
+    let src = dir.join("main.ys");
+    let out = compiler(&[src.to_str().unwrap()]);
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "counter\nmain\n2\nrun\n=> Int(33)\n",
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let bin = dir.join("module_vars");
+    let out = compiler(&["build", src.to_str().unwrap(), "-o", bin.to_str().unwrap()]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let run = std::process::Command::new(&bin)
+        .output()
+        .expect("failed to run built binary");
+    assert_eq!(
+        String::from_utf8_lossy(&run.stdout),
+        "counter\nmain\n2\nrun\n"
+    );
+    assert_eq!(run.status.code(), Some(33));
+}
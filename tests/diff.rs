@@ -2786,3 +2786,1625 @@ fn err_union_behind_optional_chain() {
          }",
     );
 }
+
+#[test]
+fn function_values_dispatch_through_tables_and_fields() {
+    // ADR 0038: table dispatch, a returned function, a grouped field
+    // callee, and a generic instance all lower to one `call_ind`.
+    diff(
+        "fn_values",
+        "struct Op { name: string, run: fun(int): int }\n\
+         fun double(x: int): int { return x * 2; }\n\
+         fun inc(x: int): int { return x + 1; }\n\
+         fun identity<T>(x: T): T { return x; }\n\
+         fun pick(n: int): fun(int): int {\n\
+             if n == 0 { return double; }\n\
+             return inc;\n\
+         }\n\
+         fun main(): int {\n\
+             const ops: (fun(int): int)[] = [double, inc, identity<int>];\n\
+             var sum: int = 0;\n\
+             for op in ops { sum = sum + op(10); }\n\
+             const o: Op = Op { name: \"dbl\", run: double };\n\
+             print(o);\n\
+             print((o.run)(4));\n\
+             print(pick(1)(41));\n\
+             print(double == ops[0]);\n\
+             print(ops[1] == identity<int>);\n\
+             return sum;\n\
+         }",
+    );
+}
+
+#[test]
+fn function_values_carry_multiword_and_float_signatures() {
+    // The indirect call reuses the direct path's sret and float
+    // argument setup; a nullable function value is a free word.
+    diff(
+        "fn_values_abi",
+        "struct P { x: int, y: int }\n\
+         fun mk(a: int, b: int): P { return P { x: a, y: b }; }\n\
+         fun half(f: float): float { return f / 2.0; }\n\
+         fun shout(s: string): string { return s + \"!\"; }\n\
+         fun map<T, U>(xs: T[], f: fun(T): U): U[] {\n\
+             var out: U[] = [];\n\
+             for x in xs { push(out, f(x)); }\n\
+             return out;\n\
+         }\n\
+         fun main(): int {\n\
+             const m: fun(int, int): P = mk;\n\
+             const p: P = m(3, 4);\n\
+             print(p);\n\
+             const h: fun(float): float = half;\n\
+             print(h(5.0));\n\
+             print(map([\"a\", \"b\"], shout));\n\
+             var maybe: (fun(int, int): P)? = null;\n\
+             print(maybe == null);\n\
+             maybe = mk;\n\
+             if maybe != null { return maybe(1, 2).y + p.x; }\n\
+             return -1;\n\
+         }",
+    );
+}
+
+#[test]
+fn closures_capture_values_by_copy_and_handles_by_alias() {
+    // ADR 0039: captures copy in at creation — a later write to the
+    // captured local is invisible — while refstruct and array captures
+    // share their object; the object pointer rides in %rax.
+    diff(
+        "closures",
+        "refstruct Counter { n: int }\n\
+         fun adder(k: int): fun(int): int {\n\
+             return fun (x: int): int { return x + k; };\n\
+         }\n\
+         fun twice(f: fun(int): int, x: int): int { return f(f(x)); }\n\
+         fun main(): int {\n\
+             var base: int = 10;\n\
+             const add: fun(int): int = adder(base);\n\
+             base = 99;\n\
+             print(add(1));\n\
+             print(twice(adder(3), 1));\n\
+             const c: Counter = Counter { n: 0 };\n\
+             const bump: fun(): int = fun (): int { c.n = c.n + 1; return c.n; };\n\
+             bump();\n\
+             print(bump() + c.n);\n\
+             const xs: int[] = [1, 2];\n\
+             const size: fun(): int = fun (): int { return len(xs); };\n\
+             push(xs, 3);\n\
+             print(size());\n\
+             var o: int? = 5;\n\
+             const seen: fun(): int = fun (): int { return o ?? 0; };\n\
+             o = null;\n\
+             print(seen());\n\
+             print(add == add);\n\
+             print(add == adder(10));\n\
+             return base;\n\
+         }",
+    );
+}
+
+#[test]
+fn closures_capture_multiword_values_and_nest() {
+    // Struct and string captures copy into the object; a nested
+    // literal threads its capture through the outer one; sret and
+    // float signatures reuse the ordinary call setup.
+    diff(
+        "closures_multiword",
+        "struct P { x: int, y: int }\n\
+         fun constant<T>(v: T): fun(): T {\n\
+             return fun (): T { return v; };\n\
+         }\n\
+         fun main(): int {\n\
+             var p: P = P { x: 1, y: 2 };\n\
+             const shift: fun(int): P = fun (d: int): P { return P { x: p.x + d, y: p.y }; };\n\
+             p.x = 100;\n\
+             print(shift(5));\n\
+             print(p);\n\
+             const scale: fun(float): float = fun (f: float): float { return f * 2.5; };\n\
+             print(scale(2.0));\n\
+             const msg: string = \"hi\";\n\
+             const greet: fun(int): fun(): string = fun (n: int): fun(): string {\n\
+                 return fun (): string { return msg + string(n); };\n\
+             };\n\
+             print(greet(7)());\n\
+             print(constant<string>(\"s\")() + string(constant<int>(4)()));\n\
+             return 0;\n\
+         }",
+    );
+}
+
+#[test]
+fn methods_pass_self_by_copy_or_handle_and_chain() {
+    // ADR 0040: a method is a direct call with the receiver first — a
+    // value-struct `self` snapshots like any multi-word argument, a
+    // refstruct passes its handle; a local shadows an enum name.
+    diff(
+        "methods",
+        "struct P { x: int, y: int }\n\
+         refstruct C { n: int }\n\
+         enum Shape { Circle(int), Rect(int, int) }\n\
+         impl P {\n\
+             fun sum(self): int { return self.x + self.y; }\n\
+             fun moved(self, dx: int): P { var q: P = self; q.x = q.x + dx; return q; }\n\
+         }\n\
+         impl C { fun bump(self): int { self.n = self.n + 1; return self.n; } }\n\
+         impl Shape {\n\
+             fun area(self): int {\n\
+                 match self {\n\
+                     Circle(r) { return 3 * r * r; }\n\
+                     Rect(w, h) { return w * h; }\n\
+                 }\n\
+                 return 0;\n\
+             }\n\
+         }\n\
+         fun main(): int {\n\
+             const p: P = P { x: 1, y: 2 };\n\
+             print(p.moved(10).moved(100).sum());\n\
+             print(p.sum());\n\
+             const c: C = C { n: 0 };\n\
+             c.bump();\n\
+             print(c.bump() + c.n);\n\
+             const shapes: Shape[] = [Shape.Rect(2, 3), Shape.Circle(2)];\n\
+             print(shapes[1].area());\n\
+             const Shape: Shape = shapes[0];\n\
+             print(Shape.area());\n\
+             return p.sum();\n\
+         }",
+    );
+}
+
+#[test]
+fn generic_methods_monomorphize_per_receiver() {
+    // Methods on `Pair<T, U>` instantiate like generic functions
+    // (ADR 0035), once per distinct receiver and method arguments.
+    diff(
+        "generic_methods",
+        "struct Pair<T, U> { a: T, b: U }\n\
+         impl Pair<T, U> {\n\
+             fun first(self): T { return self.a; }\n\
+             fun swap(self): Pair<U, T> { return (Pair<U, T> { a: self.b, b: self.a }); }\n\
+             fun with<V>(self, v: V): Pair<T, V> { return (Pair<T, V> { a: self.a, b: v }); }\n\
+         }\n\
+         fun main(): int {\n\
+             const p: Pair<int, string> = Pair<int, string> { a: 7, b: \"x\" };\n\
+             print(p.swap().first());\n\
+             print(p.with(2.5));\n\
+             print(p.swap().swap().first() + p.with(true).first());\n\
+             return p.first();\n\
+         }",
+    );
+}
+
+#[test]
+fn bounded_generics_monomorphize_per_satisfying_type() {
+    // ADR 0041: a bound is checked at the call site; the instance then
+    // calls the argument type's method directly — no vtables, one
+    // direct `call` per instance.
+    diff(
+        "interfaces",
+        "interface Ordered { fun less(self, other: Self): bool; }\n\
+         interface Show { fun show(self): string; }\n\
+         struct Money { cents: int }\n\
+         refstruct Job { prio: int }\n\
+         struct Box<T> { v: T }\n\
+         impl Money {\n\
+             fun less(self, o: Money): bool { return self.cents < o.cents; }\n\
+             fun show(self): string { return `$${self.cents}`; }\n\
+         }\n\
+         impl Job { fun less(self, o: Job): bool { return self.prio < o.prio; } }\n\
+         impl Box<T> { fun show(self): string { return `box(${self.v})`; } }\n\
+         fun max<T: Ordered>(a: T, b: T): T { if a.less(b) { return b; } return a; }\n\
+         fun best<T: Ordered + Show>(xs: T[]): string {\n\
+             var top: T = xs[0];\n\
+             for x in xs { top = max(top, x); }\n\
+             return top.show();\n\
+         }\n\
+         fun all<T: Show>(xs: T[]): string {\n\
+             var out: string = \"\";\n\
+             for x in xs { out = `${out}${x.show()};`; }\n\
+             return out;\n\
+         }\n\
+         fun main(): int {\n\
+             print(best([Money { cents: 5 }, Money { cents: 90 }, Money { cents: 7 }]));\n\
+             const j: Job = max(Job { prio: 3 }, Job { prio: 1 });\n\
+             j.prio = 10;\n\
+             print(j.prio);\n\
+             print(all([(Box<int> { v: 1 }), (Box<int> { v: 2 })]));\n\
+             print(all([(Box<string> { v: \"s\" })]));\n\
+             return max(Money { cents: 4 }, Money { cents: 2 }).cents;\n\
+         }",
+    );
+}
+
+#[test]
+fn maps_agree_on_lookup_order_and_rendering() {
+    // ADR 0042: insertion order is the only order either engine shows,
+    // so printing and iteration are byte-identical; growth past the
+    // index's load factor rehashes without reordering.
+    diff(
+        "maps",
+        "struct P { x: int, y: float }\n\
+         enum Shape { Circle(float), Rect(int, int), Dot }\n\
+         fun count(m: map<string, int>, w: string) { insert(m, w, (m[w] ?? 0) + 1); }\n\
+         fun main(): int {\n\
+             const words: map<string, int> = {};\n\
+             for w in [\"b\", \"a\", \"b\", \"c\", \"a\", \"b\"] { count(words, w); }\n\
+             print(words);\n\
+             print(words[\"b\"]);\n\
+             print(words[\"z\"]);\n\
+             print(remove(words, \"a\"));\n\
+             print(has(words, \"a\"));\n\
+             const n: map<int, int> = {};\n\
+             var i: int = 0;\n\
+             while i < 500 { insert(n, i * 31, i); i = i + 1; }\n\
+             i = 0;\n\
+             while i < 500 { if i % 2 == 0 { remove(n, i * 31); } i = i + 1; }\n\
+             var sum: int = 0;\n\
+             for [k, v] in n { sum = sum + v; }\n\
+             print(sum);\n\
+             const pts: map<P, string> = { P { x: 1, y: 0.0 }: \"origin\" };\n\
+             print(pts[P { x: 1, y: -0.0 }]);\n\
+             const shapes: map<Shape, int> = { Shape.Rect(1, 2): 1, Shape.Dot(): 2 };\n\
+             print(shapes[Shape.Rect(2, 1)]);\n\
+             print(shapes);\n\
+             const opt: map<int?, string?> = { null: \"none\", 3: null };\n\
+             print(opt);\n\
+             print(opt[3]);\n\
+             const nested: map<string, map<string, int[]>> = { \"x\": { \"y\": [1, 2] } };\n\
+             print(nested);\n\
+             return len(n);\n\
+         }",
+    );
+}
+
+#[test]
+fn map_iteration_is_live_and_positional() {
+    // Like the array loop (ADR 0019): the length re-reads every step,
+    // so removing behind the cursor shifts the next entry under it.
+    diff(
+        "maps",
+        "fun main(): int {\n\
+             const m: map<int, string> = { 1: \"a\", 2: \"b\", 3: \"c\", 4: \"d\" };\n\
+             for [k, v] in m {\n\
+                 print(`${k}=${v}`);\n\
+                 if k == 2 { remove(m, 1); }\n\
+                 if k == 4 { insert(m, 5, \"e\"); }\n\
+             }\n\
+             print(m);\n\
+             return len(m);\n\
+         }",
+    );
+}
+
+#[test]
+fn tuples_agree_on_layout_equality_and_rendering() {
+    // ADR 0043: a tuple lays out like a value struct, so element
+    // access, structural `==`, map keys, and printing all ride the
+    // struct paths — float legs compare by value, not by bits.
+    diff(
+        "tuples",
+        "struct P { pos: (float, float), tag: string }\n\
+         enum E { Pair((int, string)), Dot }\n\
+         fun divmod(a: int, b: int): (int, int) { return (a / b, a % b); }\n\
+         fun first<T, U>(p: (T, U)): T { return p.0; }\n\
+         fun maybe(b: bool): (int, string?)? { if b { return (1, null); } return null; }\n\
+         fun main(): int {\n\
+             const (q, r) = divmod(17, 5);\n\
+             print(q * 10 + r);\n\
+             var t: (int, string?) = (1, \"x\");\n\
+             print(t == (1, \"x\"));\n\
+             t.1 = null;\n\
+             print(t);\n\
+             print(maybe(true));\n\
+             print(maybe(false));\n\
+             var p: P = P { pos: (1.5, -0.0), tag: \"p\" };\n\
+             print(p.pos == (1.5, 0.0));\n\
+             p.pos.1 = 2.5;\n\
+             print(p);\n\
+             const nested: ((int, int), string) = ((1, 2), \"n\");\n\
+             print(nested.0.1);\n\
+             print(nested);\n\
+             const xs: (int, string)[] = [(2, \"b\"), (1, \"a\")];\n\
+             for x in xs { print(x.1); }\n\
+             print(xs);\n\
+             print(E.Pair((3, \"c\")));\n\
+             const m: map<(int, string), float> = { (1, \"k\"): 0.5 };\n\
+             print(m[(1, \"k\")]);\n\
+             print(m);\n\
+             const (_, s) = (first((7, \"z\")), \"skip\");\n\
+             print(s);\n\
+             return first((q, s));\n\
+         }",
+    );
+}
+
+// --- Match expressions (ADR 0044) ---
+
+#[test]
+fn match_expressions_agree_on_every_result_kind() {
+    // Word, float, string, optional, and multi-word tuple results all
+    // go through the shared result slot; an `else`-less exhaustive
+    // match satisfies definite return.
+    diff(
+        "match_expr",
+        "enum Shape { Circle(float), Rect(int, int), Dot }\n\
+         fun area(s: Shape): float {\n\
+             return match s { Circle(r) => 3.0 * r * r, Rect(w, h) => float(w * h), Dot => 0.0 };\n\
+         }\n\
+         fun sides(s: Shape): int {\n\
+             match s {\n\
+                 Circle(_) { return 0; }\n\
+                 Rect(_, _) { return 4; }\n\
+                 Dot { return 1; }\n\
+             }\n\
+         }\n\
+         fun main(): int {\n\
+             const shapes: Shape[] = [Shape.Circle(1.5), Shape.Rect(2, 3), Shape.Dot()];\n\
+             var total: int = 0;\n\
+             for s in shapes {\n\
+                 print(area(s));\n\
+                 const name: string = match s { Circle(_) => \"circle\", else => \"other\" };\n\
+                 const tag: string? = match s { Dot => null, else => name };\n\
+                 const dims: (int, int) = match s { Rect(w, h) => (w, h), else => (0, 0) };\n\
+                 print(tag);\n\
+                 print(dims);\n\
+                 total = total + match s { Rect(w, _) => w, else => 1 } * sides(s);\n\
+             }\n\
+             return total;\n\
+         }",
+    );
+}
+
+// --- Nested, literal, and guarded patterns (ADR 0045) ---
+
+#[test]
+fn pattern_decision_trees_agree() {
+    // Nested enums read at their inline offsets, string literals
+    // compare by content, and a failed guard resumes the tree at the
+    // rows below it; both engines must pick the same arm.
+    diff(
+        "patterns",
+        "enum Shape { Circle(float), Square(float), Dot }\n\
+         enum Opt<T> { Some(T), None }\n\
+         enum Tok { Num(int), Word(string), Flag(bool), Pair(Shape, int) }\n\
+         fun big(o: Opt<Shape>): string {\n\
+             return match o {\n\
+                 Some(Circle(r)) if r > 1.0 => \"big circle\",\n\
+                 Some(Circle(_)) => \"circle\",\n\
+                 Some(Square(w)) if w > 2.0 => \"big square\",\n\
+                 Some(Dot()) => \"dot\",\n\
+                 Some(_) => \"other shape\",\n\
+                 None => \"nothing\",\n\
+             };\n\
+         }\n\
+         fun tok(t: Tok): int {\n\
+             match t {\n\
+                 Num(0) { return 100; }\n\
+                 Num(-1) { return 101; }\n\
+                 Num(n) if n > 10 { return n * 2; }\n\
+                 Num(n) { return n; }\n\
+                 Word(\"yes\") { return 1; }\n\
+                 Word(\"no\") { return 0; }\n\
+                 Word(w) { print(w); return -5; }\n\
+                 Flag(true) { return 7; }\n\
+                 Flag(false) { return 8; }\n\
+                 Pair(Dot(), k) { return k; }\n\
+                 Pair(Circle(r), 3) { return int(r); }\n\
+                 Pair(_, k) { return -k; }\n\
+             }\n\
+         }\n\
+         fun main(): int {\n\
+             const os: Opt<Shape>[] = [Opt<Shape>.Some(Shape.Circle(2.0)),\n\
+                 Opt<Shape>.Some(Shape.Circle(0.5)), Opt<Shape>.Some(Shape.Square(3.0)),\n\
+                 Opt<Shape>.Some(Shape.Square(1.0)), Opt<Shape>.Some(Shape.Dot()),\n\
+                 Opt<Shape>.None()];\n\
+             for o in os { print(big(o)); }\n\
+             const ts: Tok[] = [Tok.Num(0), Tok.Num(-1), Tok.Num(20), Tok.Num(5),\n\
+                 Tok.Word(\"yes\"), Tok.Word(\"no\"), Tok.Word(\"maybe\"), Tok.Flag(true),\n\
+                 Tok.Flag(false), Tok.Pair(Shape.Dot(), 9), Tok.Pair(Shape.Circle(4.5), 3),\n\
+                 Tok.Pair(Shape.Circle(4.5), 4), Tok.Pair(Shape.Square(1.0), 6)];\n\
+             var sum: int = 0;\n\
+             for t in ts { sum = sum + tok(t); }\n\
+             print(sum);\n\
+             return sum % 100;\n\
+         }",
+    );
+}
+
+#[test]
+fn wide_pattern_matrix_compiles_small() {
+    // 61 arms over 24 bool payloads, mostly wildcards: a tree that
+    // copies rows into every case doubles per column and once ran the
+    // build out of memory. Shared subtrees and the ordered-rows
+    // fallback keep it small; both engines must still pick alike.
+    let mut x: u64 = 1;
+    let mut next = || {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (x >> 33) % 10
+    };
+    let bools = vec!["bool"; 24].join(", ");
+    let mut src =
+        format!("enum E {{ V({bools}), W }}\nfun pick(e: E): int {{\nreturn match e {{\n");
+    for arm in 0..61 {
+        let pats: Vec<&str> = (0..24)
+            .map(|_| match next() {
+                0..=5 => "_",
+                6 | 7 => "true",
+                _ => "false",
+            })
+            .collect();
+        src += &format!("V({}) => {arm},\n", pats.join(", "));
+    }
+    src += "else => -1,\n};\n}\nfun main(): int {\nvar total: int = 0;\n";
+    for _ in 0..8 {
+        let args: Vec<&str> = (0..24)
+            .map(|_| if next() < 5 { "true" } else { "false" })
+            .collect();
+        src += &format!("total = total * 61 + pick(E.V({}));\n", args.join(", "));
+    }
+    src += "print(total);\nreturn 0;\n}";
+    diff("wide_match", &src);
+}
+
+// --- Int, string, and error-code match (ADR 0046) ---
+
+#[test]
+fn switch_matches_agree() {
+    // Dense int arms go through a jump table: holes, values on both
+    // sides of the table, and a negative base must all reach the same
+    // arm the interpreter picks. Sparse ints and strings stay a
+    // compare chain; error codes are interned densely.
+    diff(
+        "switch_match",
+        "error NotFound;\n\
+         error Timeout;\n\
+         error Denied;\n\
+         error Busy;\n\
+         fun dense(n: int): int {\n\
+             match n {\n\
+                 -2 { return 20; }\n\
+                 -1 { return 21; }\n\
+                 0 { return 22; }\n\
+                 2 { return 24; }\n\
+                 3 { return 25; }\n\
+                 else { return 0; }\n\
+             }\n\
+         }\n\
+         fun sparse(n: int): string {\n\
+             return match n {\n\
+                 1 => \"one\",\n\
+                 1000 => \"thousand\",\n\
+                 -9223372036854775807 => \"min\",\n\
+                 else => \"other\",\n\
+             };\n\
+         }\n\
+         fun word(s: string): int {\n\
+             return match s {\n\
+                 \"\" => 0,\n\
+                 \"a\" => 1,\n\
+                 \"ab\" => 2,\n\
+                 else => 9,\n\
+             };\n\
+         }\n\
+         fun status(e: error): int {\n\
+             return match e {\n\
+                 error.NotFound => 404,\n\
+                 error.Timeout => 408,\n\
+                 error.Denied => 403,\n\
+                 error.Busy => 503,\n\
+                 else => 500,\n\
+             };\n\
+         }\n\
+         fun fetch(n: int): int! {\n\
+             if n == 0 { return error.Timeout; }\n\
+             if n == 1 { return error.Busy; }\n\
+             return n;\n\
+         }\n\
+         fun main(): int {\n\
+             var sum: int = 0;\n\
+             for n in [-5, -2, -1, 0, 1, 2, 3, 4, 100] { sum = sum * 3 + dense(n); }\n\
+             print(sum);\n\
+             for n in [1, 1000, -9223372036854775807, 7] { print(sparse(n)); }\n\
+             print(word(\"\") + word(\"a\") * 10 + word(\"ab\") * 100 + word(\"b\") * 1000);\n\
+             print(status(error.NotFound) + status(error.Busy));\n\
+             for n in [0, 1, 2] {\n\
+                 const r: int! = fetch(n);\n\
+                 if r == error {\n\
+                     match r {\n\
+                         error.Timeout { print(\"timeout\"); }\n\
+                         else { print(r); }\n\
+                     }\n\
+                 }\n\
+             }\n\
+             return sum % 100;\n\
+         }",
+    );
+}
+
+// --- Module consts (ADR 0047) ---
+
+#[test]
+fn module_consts_agree() {
+    diff(
+        "module_consts",
+        "const LIMIT: int = KB * 4;\n\
+         const KB: int = square(32);\n\
+         const HALF: float = 1.0 / 2.0;\n\
+         const BIG: bool = LIMIT > 4000;\n\
+         const NAME: string = \"ys\" + \"-\" + \"lang\";\n\
+         const MIN: int = -9223372036854775807 - 1;\n\
+         fun square(n: int): int { return n * n; }\n\
+         fun scaled(n: int): float { return float(n) * HALF; }\n\
+         fun main(): int {\n\
+             print(LIMIT);\n\
+             print(scaled(LIMIT));\n\
+             if BIG { print(NAME); }\n\
+             print(MIN);\n\
+             return LIMIT % 200;\n\
+         }",
+    );
+}
+
+// --- Module vars (ADR 0048) ---
+
+#[test]
+fn module_vars_agree() {
+    diff(
+        "module_vars",
+        "refstruct Node { v: int, next: Node? }\n\
+         struct Span2 { lo: int, hi: int }\n\
+         var head: Node? = null;\n\
+         var window: Span2 = Span2 { lo: 0, hi: 10 };\n\
+         var seen: map<string, int> = {};\n\
+         var scale: float = 1.5;\n\
+         var last: int? = null;\n\
+         var status: int! = 0;\n\
+         var pair: (int, string) = (1, \"one\");\n\
+         var op: fun(int): int = double;\n\
+         fun double(n: int): int { return n * 2; }\n\
+         fun add(v: int) {\n\
+             head = Node { v: v, next: head };\n\
+             window.hi = window.hi + v;\n\
+             last = v;\n\
+         }\n\
+         fun main(): int {\n\
+             for v in [3, 1, 4] { add(v); }\n\
+             var sum: int = 0;\n\
+             var cur: Node? = head;\n\
+             while cur != null { sum = sum * 10 + cur.v; cur = cur.next; }\n\
+             print(sum);\n\
+             print(window);\n\
+             insert(seen, \"a\", op(sum));\n\
+             print(seen);\n\
+             scale = scale * 2.0;\n\
+             print(scale);\n\
+             print(last ?? -1);\n\
+             last = null;\n\
+             print(last ?? -1);\n\
+             status = 5;\n\
+             print(status);\n\
+             pair.1 = \"uno\";\n\
+             print(pair);\n\
+             op = fun (n: int): int { return n + window.lo; };\n\
+             return op(sum) % 256;\n\
+         }",
+    );
+}
+
+#[test]
+fn a_recursive_main_initializes_module_vars_once() {
+    // The C entry runs the initializer; `main` calling itself does not.
+    diff(
+        "recursive_main",
+        "var count: int = 5;\n\
+         fun main(): int {\n\
+             count = count + 1;\n\
+             print(count);\n\
+             if count < 8 { return main(); }\n\
+             return count;\n\
+         }",
+    );
+    diff(
+        "recursive_main_errs",
+        "error Deep;\n\
+         var seen: int[] = [];\n\
+         fun main(): int! {\n\
+             push(seen, len(seen));\n\
+             if len(seen) < 3 { return main(); }\n\
+             print(seen);\n\
+             if len(seen) > 3 { return error.Deep; }\n\
+             return len(seen);\n\
+         }",
+    );
+}
+
+// --- Bitwise operators (ADR 0049) ---
+
+#[test]
+fn bitwise_operators_agree() {
+    diff(
+        "bitwise",
+        "fun popcount(x: int): int {\n\
+             var n: int = 0;\n\
+             var v: int = x;\n\
+             while v != 0 { v = v & (v - 1); n = n + 1; }\n\
+             return n;\n\
+         }\n\
+         fun mix(x: int): int {\n\
+             var h: int = x ^ (x >>> 33);\n\
+             h = h * -49064778989728563;\n\
+             return h ^ (h >>> 33);\n\
+         }\n\
+         fun main(): int {\n\
+             var a: int = 1234567;\n\
+             var k: int = 0;\n\
+             while k < 64 {\n\
+                 print((a << k) ^ (a >> k) ^ (a >>> k) ^ (-a >> k) ^ (-a >>> k));\n\
+                 k = k + 9;\n\
+             }\n\
+             print(popcount(-1));\n\
+             print(popcount(a));\n\
+             print(mix(a));\n\
+             print((~a & 255) == 0);\n\
+             print(a & 255 | 1 << 40 | -a >>> 62);\n\
+             print(a >> 3 << 3 ^ 7 & ~0);\n\
+             return popcount(a & 4095);\n\
+         }",
+    );
+}
+
+// --- Compound assignment (ADR 0050) ---
+
+#[test]
+fn compound_assignment_agrees() {
+    diff(
+        "compound_assign",
+        "struct In { n: int, f: float, s: string }\n\
+         struct Out { inner: In, c: int?, opt: In? }\n\
+         refstruct Node { v: int, next: Node? }\n\
+         var total: int = 3;\n\
+         var grid: Out[] = [];\n\
+         fun grow(): int {\n\
+             push(grid, Out { inner: In { n: 0, f: 0.0, s: \"\" }, c: null, opt: null });\n\
+             return len(grid);\n\
+         }\n\
+         fun at(i: int): int {\n\
+             total++;\n\
+             return i;\n\
+         }\n\
+         fun main(): int {\n\
+             var xs: int[] = [1, 2, 3];\n\
+             xs[at(2)] *= 7;\n\
+             xs[at(0)] -= xs[2];\n\
+             print(xs[0] * 1000 + xs[2]);\n\
+             var o: Out = Out { inner: In { n: 1, f: 1.5, s: \"a\" }, c: null, opt: null };\n\
+             o.inner.n <<= 4;\n\
+             o.inner.f /= 4.0;\n\
+             o.inner.s += \"bc\";\n\
+             o.c ??= 5;\n\
+             o.c ??= 6;\n\
+             o.opt ??= In { n: 9, f: 0.25, s: \"x\" };\n\
+             if o.opt != null { o.opt.n %= 4; }\n\
+             print(o);\n\
+             var c: int? = 8;\n\
+             if c != null { c >>>= 1; c ^= 1; print(c); }\n\
+             var t: (int, string) = (1, \"t\");\n\
+             t.0 |= 6;\n\
+             t.1 += \"u\";\n\
+             print(t);\n\
+             const n: Node = Node { v: 5, next: Node { v: 2, next: null } };\n\
+             if n.next != null { n.next.v &= 3; }\n\
+             print(n.next?.v ?? -1);\n\
+             grow();\n\
+             grid[at(0)].c ??= grow() * 10;\n\
+             grid[at(1)].inner.s += \"s\";\n\
+             grid[at(1)].inner.n--;\n\
+             print(grid);\n\
+             var w: string? = null;\n\
+             w ??= \"w\";\n\
+             print(w);\n\
+             return total;\n\
+         }",
+    );
+}
+
+// --- Ranges and slices (ADR 0051) ---
+
+#[test]
+fn range_loops_and_slices_agree() {
+    diff(
+        "ranges",
+        "struct P { x: int, y: float }\n\
+         fun walk(a: int, b: int, s: int, incl: bool): int {\n\
+             var h: int = 0;\n\
+             if incl {\n\
+                 for i in a..=b step s { h = h * 31 + i; }\n\
+             } else {\n\
+                 for i in a..b step s { h = h * 31 + i; }\n\
+             }\n\
+             return h;\n\
+         }\n\
+         fun main(): int {\n\
+             const mx: int = 9223372036854775807;\n\
+             const mn: int = -9223372036854775807 - 1;\n\
+             var steps: int[] = [1, 2, 3, -1, -2, -3, mx, mn, 1 << 62];\n\
+             var ends: int[] = [mn, -7, -1, 0, 1, 7, mx];\n\
+             for s in steps {\n\
+                 for a in ends {\n\
+                     for b in ends {\n\
+                         if s > 0 && b - a > 0 && b - a < 64 || s < 0 && a - b > 0 && a - b < 64 {\n\
+                             print(walk(a, b, s, false) ^ walk(a, b, s, true));\n\
+                         }\n\
+                     }\n\
+                 }\n\
+             }\n\
+             print(walk(mn, mx, mx, true));\n\
+             print(walk(mx, mn, mn, true));\n\
+             print(walk(mx - 4, mx, 3, true));\n\
+             print(walk(mn + 4, mn, -3, true));\n\
+             var n: int = 0;\n\
+             for i in mx - 3..=mx { n += 1; }\n\
+             for i in mn + 3..=mn step -1 { n += 1; }\n\
+             for i in 0..100 {\n\
+                 if i % 3 == 0 { continue; }\n\
+                 if i > 20 { break; }\n\
+                 n += i;\n\
+             }\n\
+             print(n);\n\
+             var ps: P[] = [];\n\
+             for i in 0..6 { push(ps, P { x: i, y: float(i) / 2.0 }); }\n\
+             var mid: P[] = ps[2..=4];\n\
+             push(mid, P { x: 9, y: 9.5 });\n\
+             mid[0].x = 40;\n\
+             print(ps[2].x);\n\
+             print(mid);\n\
+             print(len(ps[..]) + len(ps[6..]) + len(ps[..0]));\n\
+             const words: string[] = [\"a\", \"b\", \"c\", \"d\"];\n\
+             var joined: string = \"\";\n\
+             for w in words[1..] { joined += w; }\n\
+             print(joined);\n\
+             return len(ps[1..3]);\n\
+         }",
+    );
+}
+
+// --- Sized integers (ADR 0052) ---
+
+#[test]
+fn sized_integers_agree() {
+    diff(
+        "sized",
+        "struct Px { r: u8, g: u8 }\n\
+         fun sum(xs: u8[]): u32 {\n\
+             var total: u32 = 0;\n\
+             for x in xs { total += u32(x); }\n\
+             return total;\n\
+         }\n\
+         fun main(): int {\n\
+             const a: u8 = 200;\n\
+             const b: u8 = 100;\n\
+             print(a + b);\n\
+             print(b - a);\n\
+             print(~a);\n\
+             const c: i8 = -128;\n\
+             print(c / -1);\n\
+             print(c % -1);\n\
+             print(-c);\n\
+             const e: u64 = u64(-1);\n\
+             print(e);\n\
+             print(e / 3);\n\
+             print(e % 10);\n\
+             print(e > 5);\n\
+             print(e >> 60);\n\
+             print(float(e));\n\
+             print(int(e));\n\
+             const f: i16 = -2;\n\
+             print(f >>> 1);\n\
+             print(f >> 1);\n\
+             print(u16(f));\n\
+             var xs: u8[] = [1, 2, 250, 255];\n\
+             push(xs, 7);\n\
+             xs[0] = 9;\n\
+             print(xs);\n\
+             print(sum(xs));\n\
+             print(xs[1..3]);\n\
+             var ys: i16[] = [-1, 300, -300];\n\
+             ys[1] += 1;\n\
+             print(ys);\n\
+             var zs: u64[] = [1, 2];\n\
+             push(zs, e);\n\
+             print(zs);\n\
+             print(Px { r: 255, g: 1 });\n\
+             print(string(e) + \" \" + string(a) + `${c}`);\n\
+             const o: u32? = 4000000000;\n\
+             print(o);\n\
+             const m: u32 = 4000000000;\n\
+             print(m + m);\n\
+             print(i32(m));\n\
+             var sh: u8 = 1;\n\
+             sh <<= 7;\n\
+             print(sh);\n\
+             sh <<= 1;\n\
+             print(sh);\n\
+             return int(a) + int(c);\n\
+         }",
+    );
+}
+
+#[test]
+fn sized_integer_map_keys_agree() {
+    diff(
+        "sized_keys",
+        "fun main(): int {\n\
+             var m: map<u8, int> = {};\n\
+             const a: u8 = 200;\n\
+             insert(m, a, 1);\n\
+             insert(m, a + 56, 2);\n\
+             insert(m, 200, 3);\n\
+             print(m);\n\
+             print(m[0] ?? -1);\n\
+             var n: map<i8, string> = {};\n\
+             insert(n, -128, \"min\");\n\
+             insert(n, 127, \"max\");\n\
+             for [k, v] in n { print(`${k}=${v}`); }\n\
+             var w: map<u64, int> = {};\n\
+             insert(w, u64(-1), 9);\n\
+             print(w[u64(-1)] ?? 0);\n\
+             print(has(w, 1));\n\
+             return len(m) + len(n);\n\
+         }",
+    );
+}
+
+// --- Strings as bytes (ADR 0053) ---
+
+#[test]
+fn string_bytes_and_views_agree() {
+    diff(
+        "strings",
+        "fun count(s: string, want: byte): int {\n\
+             var n: int = 0;\n\
+             for b in s { if b == want { n += 1; } }\n\
+             return n;\n\
+         }\n\
+         fun main(): int {\n\
+             const s: string = \"h\u{e9}llo, w\u{f6}rld \u{20ac}\u{1f600}\";\n\
+             print(len(s));\n\
+             print(s[0]);\n\
+             print(s[1]);\n\
+             print(s[7..]);\n\
+             print(s[..5]);\n\
+             print(s[1..=2]);\n\
+             print(count(s, 108));\n\
+             print(chars(s));\n\
+             var all: string = \"\";\n\
+             for i in 0..len(s) {\n\
+                 for j in i..=len(s) { all += `${chars(s[i..j])}`; }\n\
+             }\n\
+             print(all);\n\
+             var t: string = s;\n\
+             for [i, b] in t {\n\
+                 if i == 0 { t = \"zz\"; }\n\
+                 if i > 2 { break; }\n\
+                 print(b);\n\
+             }\n\
+             print(t);\n\
+             const v: string = s[7..];\n\
+             print(v[0..5] + \"!\");\n\
+             print(s[len(s)..] == \"\");\n\
+             return len(s[3..8]);\n\
+         }",
+    );
+}
+
+// --- String library (ADR 0054) ---
+
+#[test]
+fn string_library_agrees() {
+    diff(
+        "string_library",
+        "fun show(x: int?): string {\n\
+             if x == null { return \"null\"; }\n\
+             return string(x);\n\
+         }\n\
+         fun showf(x: float?): string {\n\
+             if x == null { return \"null\"; }\n\
+             return string(x);\n\
+         }\n\
+         fun main(): int {\n\
+             const s: string = \"  Hello, W\u{f6}rld!  \";\n\
+             print(show(find(s, \"l\")));\n\
+             print(show(find(s, \"z\")));\n\
+             print(show(find(\"\", \"\")));\n\
+             print(startsWith(s, \"  He\"));\n\
+             print(endsWith(s, \"d!\"));\n\
+             print(split(\"a,b,,c,\", \",\"));\n\
+             print(split(\"a--b---c\", \"--\"));\n\
+             print(len(split(\"\", \",\")));\n\
+             print(`[${trim(s)}]`);\n\
+             print(`[${trim(\" \\t\\n \")}]`);\n\
+             print(replace(s, \"l\", \"LL\"));\n\
+             print(replace(\"aaaa\", \"aa\", \"b\"));\n\
+             print(join(split(\"x y z\", \" \"), \" + \"));\n\
+             print(`[${join([], \", \")}]`);\n\
+             print(toUpper(s));\n\
+             print(toLower(s));\n\
+             for t in [\"0\", \"-0\", \"+7\", \"-9223372036854775808\", \"9223372036854775807\",\n\
+                       \"9223372036854775808\", \"\", \"-\", \" 1\", \"12a\"] {\n\
+                 print(`${t} -> ${show(parseInt(t))}`);\n\
+             }\n\
+             for t in [\"1.\", \".5\", \"+.5e-3\", \"1e400\", \"2.5E+2\", \".\", \"1e\",\n\
+                       \"inf\", \"nan\", \"0x10\", \"00012.50\"] {\n\
+                 print(`${t} -> ${showf(parseFloat(t))}`);\n\
+             }\n\
+             const e: string = \"h\u{e9}\u{e9}\";\n\
+             const odd: string = e[0..2] + \",\" + e[2..5];\n\
+             for p in split(odd, \",\") { print(chars(p)); }\n\
+             print(show(find(odd, e[2..3])));\n\
+             print(chars(toUpper(odd)));\n\
+             print(chars(replace(odd, e[2..3], \"?\")));\n\
+             print(startsWith(e[2..5], e[2..3]));\n\
+             print(endsWith(odd, e[4..5]));\n\
+             print(chars(join(split(odd, e[2..3]), \"|\")));\n\
+             print(show(parseInt(e[1..2])));\n\
+             return len(trim(\"\\t\" + e[1..2] + \" \"));\n\
+         }",
+    );
+}
+
+// --- Array library (ADR 0055) ---
+
+#[test]
+fn array_library_agrees() {
+    diff(
+        "array_library",
+        "struct R { id: int, score: float, tag: string }\n\
+         fun main(): int {\n\
+             var xs: int[] = [];\n\
+             var seed: int = 7;\n\
+             for i in 0..100 {\n\
+                 seed = (seed * 1103515245 + 12345) % 2147483648;\n\
+                 push(xs, seed % 40 - 20);\n\
+             }\n\
+             var rs: R[] = [];\n\
+             for [i, x] in xs {\n\
+                 push(rs, R { id: i, score: float(x % 7) / 2.0, tag: `t${x % 5}` });\n\
+             }\n\
+             sort(xs);\n\
+             print(xs);\n\
+             var seen: int[] = [];\n\
+             sort(rs, fun (r: R): float { push(seen, r.id); return r.score; });\n\
+             print(len(seen));\n\
+             print(slice(rs, 0, 6));\n\
+             sort(rs, fun (r: R): string { return r.tag; });\n\
+             print(slice(rs, 90, 100));\n\
+             print(indexOf(rs, rs[42]));\n\
+             const nan: float = 0.0 / 0.0;\n\
+             var fs: float[] = [1.0, nan, -0.0, 0.0, -nan, -2.0, 3.5];\n\
+             sort(fs);\n\
+             print(fs);\n\
+             print(indexOf(fs, nan));\n\
+             print(contains(fs, 0.0));\n\
+             var ss: string[] = [\"zeta\", \"Zeta\", \"\u{e9}clair\", \"eclair\", \"\", \"a\", \"ab\", \"aa\"];\n\
+             sort(ss);\n\
+             print(ss);\n\
+             print(removeAt(ss, 0));\n\
+             insert(ss, len(ss), \"end\");\n\
+             insert(ss, 0, \"start\");\n\
+             reverse(ss);\n\
+             print(ss);\n\
+             var os: string?[] = [\"x\", null];\n\
+             insert(os, 1, null);\n\
+             print(os);\n\
+             print(pop(os));\n\
+             print(pop(os));\n\
+             print(pop(os));\n\
+             print(pop(os));\n\
+             var hs: i16[] = [300, -5, 7];\n\
+             reverse(hs);\n\
+             print(removeAt(hs, 1));\n\
+             insert(hs, 1, -32768);\n\
+             print(hs);\n\
+             print(indexOf(hs, -32768));\n\
+             var nested: int[][] = [[1], [2, 2], []];\n\
+             sort(nested, fun (a: int[]): int { return len(a); });\n\
+             print(nested);\n\
+             print(pop(nested));\n\
+             clear(nested);\n\
+             sort(nested, fun (a: int[]): int { return len(a); });\n\
+             reverse(nested);\n\
+             print(pop(nested));\n\
+             return len(xs);\n\
+         }",
+    );
+}
+
+#[test]
+fn sized_integer_sorts_agree() {
+    // Packed elements sort at their own stride; `u64` orders unsigned,
+    // whether it is the element or a key function's result.
+    diff(
+        "sized_sorts",
+        r#"fun main(): int {
+            var bs: u8[] = [200, 3, 255, 0, 17, 3];
+            sort(bs);
+            print(bs);
+            var hs: i16[] = [300, -5, 7, -32768, 32767];
+            sort(hs);
+            print(hs);
+            var ws: u32[] = [4000000000, 1, 2147483648];
+            sort(ws);
+            print(ws);
+            var qs: u64[] = [u64(-1), 5, u64(-2), 0];
+            sort(qs);
+            print(qs);
+            var xs: int[] = [1, 2, 3, 4];
+            sort(xs, fun (x: int): u64 { return u64(2 - x); });
+            print(xs);
+            sort(bs, fun (b: u8): i8 { return i8(b); });
+            print(bs);
+            var empty: i32[] = [];
+            sort(empty);
+            print(empty);
+            return int(bs[0]);
+        }"#,
+    );
+}
+
+// --- Fixed arrays (ADR 0056) ---
+
+#[test]
+fn fixed_arrays_agree() {
+    diff(
+        "fixed_arrays",
+        "struct P { x: int, y: float }\n\
+         struct Grid { cells: int[4], tag: string, marks: string?[2] }\n\
+         enum Shape { Quad(float[4]), Dot }\n\
+         var table: int[3] = [10, 20, 30];\n\
+         fun area(s: Shape): float {\n\
+             match s {\n\
+                 Quad(q) { return q[0] + q[1] + q[2] + q[3]; }\n\
+                 Dot { return 0.0; }\n\
+             }\n\
+         }\n\
+         fun rot(a: int[3]): int[3] {\n\
+             var b: int[3] = a;\n\
+             b[0] += 1;\n\
+             return [b[1], b[2], b[0]];\n\
+         }\n\
+         fun main(): int {\n\
+             table[1] += 5;\n\
+             const k: int[3] = rot(table);\n\
+             print(k);\n\
+             print(table);\n\
+             var m: int[3][2] = [[1, 2, 3], [4, 5, 6]];\n\
+             m[1][2] = 60;\n\
+             m[0][1] *= 10;\n\
+             for [i, row] in m {\n\
+                 m[1][0] = 400;\n\
+                 print(i * 1000 + row[0]);\n\
+             }\n\
+             print(m);\n\
+             var ps: P[2] = [P { x: 1, y: 2.5 }, P { x: 3, y: -0.0 }];\n\
+             ps[1].x = 7;\n\
+             print(ps);\n\
+             print(ps == [P { x: 1, y: 2.5 }, P { x: 7, y: 0.0 }]);\n\
+             var g: Grid = Grid { cells: [0; 4], tag: \"g\", marks: [null; 2] };\n\
+             g.cells[3] += 2;\n\
+             g.marks[1] ??= \"m\";\n\
+             g.marks[1] ??= \"no\";\n\
+             var gs: Grid[] = [g, g];\n\
+             gs[1].cells[0] = 11;\n\
+             print(gs);\n\
+             var rows: int[2][] = [];\n\
+             push(rows, [3, 4]);\n\
+             rows[0][1] = 44;\n\
+             print(rows);\n\
+             var maybe: int[2]? = null;\n\
+             print(maybe);\n\
+             maybe = [7, 8];\n\
+             if maybe != null { print(maybe[1]); }\n\
+             print(area(Shape.Quad([1.0, 2.0, 3.0, 4.5])));\n\
+             const t: (int[2], string) = ([5, 6], \"t\");\n\
+             print(t);\n\
+             const f: fun(): int = fun (): int { return k[2]; };\n\
+             var bytes: u8[4] = [250; 4];\n\
+             bytes[0] += 10;\n\
+             print(bytes);\n\
+             var hits: int[5] = [0; 5];\n\
+             for i in 0..12 { hits[i % 5] += 1; }\n\
+             print(hits);\n\
+             var seen: map<string[2], int> = {};\n\
+             insert(seen, [\"a\", \"b\"], 1);\n\
+             insert(seen, [\"a\", \"b\"], 2);\n\
+             insert(seen, [\"b\", \"a\"], 3);\n\
+             print(seen);\n\
+             return f() + len(hits);\n\
+         }",
+    );
+}
+
+// --- Destructuring (ADR 0057) ---
+
+#[test]
+fn destructuring_agrees() {
+    diff(
+        "destructuring",
+        "struct P { x: int, y: float }\n\
+         struct Box { p: P, tag: string, cells: int[2] }\n\
+         refstruct Node { val: int, next: Node? }\n\
+         enum Shape { Circle(float), Rect(P, P), Dot }\n\
+         fun span(s: Shape): float {\n\
+             const Rect(a, b) = s else { return -1.0; };\n\
+             const { x: ax, y: ay } = a;\n\
+             const { x: bx, y: by } = b;\n\
+             return float(bx - ax) * (by - ay);\n\
+         }\n\
+         fun main(): int {\n\
+             const b: Box = Box { p: P { x: 2, y: 0.5 }, tag: \"b\", cells: [4, 5] };\n\
+             var { p, cells: c, tag: _ } = b;\n\
+             p.x = 9;\n\
+             c[0] = 40;\n\
+             print(p);\n\
+             print(c);\n\
+             print(b);\n\
+             const n: Node = Node { val: 1, next: Node { val: 2, next: null } };\n\
+             const { next } = n;\n\
+             if next != null { next.val = 20; }\n\
+             print(n.next);\n\
+             print(span(Shape.Rect(P { x: 1, y: 1.0 }, P { x: 4, y: 3.0 })));\n\
+             print(span(Shape.Dot()));\n\
+             const shapes: Shape[] = [Shape.Circle(1.5), Shape.Dot(), Shape.Circle(2.0)];\n\
+             var r2: float = 0.0;\n\
+             for s in shapes {\n\
+                 var Circle(r) = s else { continue; };\n\
+                 r *= r;\n\
+                 r2 += r;\n\
+             }\n\
+             print(r2);\n\
+             return int(r2);\n\
+         }",
+    );
+}
+
+// --- Binding conditions (ADR 0058) ---
+
+#[test]
+fn binding_conditions_agree() {
+    diff_io(
+        "binding_conditions",
+        "error Missing;\n\
+         struct P { x: int, tag: string }\n\
+         enum Shape { Circle(float), Rect(int, int) }\n\
+         fun find(xs: string[], v: string): P! {\n\
+             for [i, x] in xs { if x == v { return P { x: i, tag: x }; } }\n\
+             return error.Missing;\n\
+         }\n\
+         fun main(): int {\n\
+             var words: string[] = [];\n\
+             while const line = readLine() {\n\
+                 if len(line) == 0 { continue; }\n\
+                 push(words, line);\n\
+             }\n\
+             print(words);\n\
+             if const p = find(words, \"beta\") { print(p); }\n\
+             if const p = find(words, \"zeta\") { print(p); } else { print(\"none\"); }\n\
+             const opts: P?[] = [null, P { x: 5, tag: \"five\" }];\n\
+             for i in 0..2 {\n\
+                 if const p = opts[i] { print(p.tag); } else { print(i); }\n\
+             }\n\
+             var fs: float?[] = [1.5, 2.25, null, 9.0];\n\
+             var k: int = 0;\n\
+             var total: float = 0.0;\n\
+             while const f = fs[k] {\n\
+                 k += 1;\n\
+                 total += f;\n\
+             }\n\
+             print(total);\n\
+             const shapes: Shape[] = [Shape.Rect(2, 3), Shape.Circle(0.5)];\n\
+             for s in shapes {\n\
+                 if const Rect(w, h) = s { print(w * h); } else if const Circle(r) = s { print(r); }\n\
+             }\n\
+             return len(words);\n\
+         }",
+        &[],
+        b"alpha\n\nbeta\ngamma",
+    );
+}
+
+// --- Loop labels (ADR 0059) ---
+
+#[test]
+fn labeled_loops_agree() {
+    diff(
+        "labeled_loops",
+        "fun find(grid: int[][], want: int): int {\n\
+             var at: int = -1;\n\
+             rows: for [r, row] in grid {\n\
+                 for [c, v] in row {\n\
+                     if v == want { at = r * 10 + c; break rows; }\n\
+                 }\n\
+             }\n\
+             return at;\n\
+         }\n\
+         fun main(): int {\n\
+             print(find([[1, 2], [3, 4, 5], [6]], 5));\n\
+             print(find([[1]], 9));\n\
+             var count: int = 0;\n\
+             outer: for i in 0..5 step 2 {\n\
+                 var k: int = 0;\n\
+                 while k < 5 {\n\
+                     k += 1;\n\
+                     if k > i { continue outer; }\n\
+                     count += 1;\n\
+                 }\n\
+             }\n\
+             print(count);\n\
+             const m: map<string, int> = { \"ab\": 1, \"cd\": 2 };\n\
+             var total: int = 0;\n\
+             keys: for [k, v] in m {\n\
+                 chars: for ch in k {\n\
+                     const cells: int[3] = [v, v * 10, v * 100];\n\
+                     for x in cells {\n\
+                         total += x;\n\
+                         if x >= 10 { continue keys; }\n\
+                         if ch == u8(98) { break chars; }\n\
+                     }\n\
+                 }\n\
+             }\n\
+             print(total);\n\
+             var xs: int?[] = [1, 2, null, 4];\n\
+             var i: int = 0;\n\
+             var n: int = 0;\n\
+             scan: while const x = xs[i] {\n\
+                 i += 1;\n\
+                 while true {\n\
+                     n += x;\n\
+                     if x == 2 { break scan; }\n\
+                     continue scan;\n\
+                 }\n\
+             }\n\
+             print(n);\n\
+             return count + total;\n\
+         }",
+    );
+}
+
+// --- Defer (ADR 0060) ---
+
+#[test]
+fn defers_agree() {
+    let dir = tempdir("ys-diff-defer");
+    let path = dir.join("lines.txt");
+    let p = path.to_str().unwrap();
+    diff(
+        "defers",
+        &format!(
+            r#"error Bad;
+            struct P {{ x: int, y: float }}
+            fun check(n: int): int! {{
+                if n < 0 {{ return error.Bad; }}
+                return n;
+            }}
+            fun tried(n: int): int! {{
+                defer print("cleanup");
+                const v: int = try check(n);
+                return v + 1;
+            }}
+            fun kept(): P {{
+                var p: P = P {{ x: 1, y: 0.5 }};
+                defer p.x = 9;
+                return p;
+            }}
+            fun word(): float {{
+                var f: float = 1.5;
+                defer f = 0.0;
+                return f;
+            }}
+            fun lines(): int {{
+                const r: file? = open("{p}", "r");
+                if r == null {{ return -1; }}
+                defer close(r);
+                var n: int = 0;
+                while const line = readLine(r) {{
+                    defer n += 1;
+                    if line == "stop" {{ return n; }}
+                }}
+                return n;
+            }}
+            fun main(): int {{
+                const w: file? = open("{p}", "w");
+                if w != null {{
+                    defer close(w);
+                    write(w, "a\nb\nstop\nc\n");
+                }}
+                print(lines());
+                print(tried(2));
+                print(tried(-2));
+                print(kept());
+                print(word());
+                var total: int = 0;
+                outer: for i in 0..4 {{
+                    defer print(`end ${{i}}`);
+                    for [j, s] in ["x", "y", "z"] {{
+                        defer total += j;
+                        if i == 1 {{ continue outer; }}
+                        if i == 3 && j == 1 {{ break outer; }}
+                    }}
+                }}
+                const a: int = 1;
+                defer print(a);
+                const a: int = 2;
+                defer {{
+                    defer print("nested");
+                    print(a);
+                }}
+                return total;
+            }}"#
+        ),
+    );
+}
+
+// --- Arena blocks (ADR 0061) ---
+
+#[test]
+fn arenas_agree() {
+    diff(
+        "arenas",
+        r#"refstruct Node { v: int, next: Node? }
+        struct Tally { n: int, last: string }
+        fun depth(n: int): int {
+            arena {
+                var xs: int[] = [];
+                for i in 0..n { push(xs, i * i); }
+                arena {
+                    var m: map<string, int> = {};
+                    for x in xs { insert(m, `k${x}`, x); }
+                    if n > 5 { return len(m) + 100; }
+                }
+                return len(xs);
+            }
+        }
+        fun main(): int {
+            var outer: map<int, string> = {};
+            var kept: int[] = [];
+            var t: Tally = Tally { n: 0, last: "" };
+            for round in 0..300 {
+                arena {
+                    var head: Node? = null;
+                    for i in 0..40 { head = Node { v: i + round, next: head }; }
+                    var s: string = "";
+                    for i in 0..8 { s = s + `${i}`; }
+                    var parts: string[] = split(`${round},${round + 1}`, ",");
+                    insert(outer, round, "fixed");
+                    push(kept, round);
+                    if const h = head { t.n = t.n + h.v + len(s) + len(parts); }
+                    if round == 299 { print(s); print(parts); }
+                }
+            }
+            print(t.n);
+            print(len(outer));
+            print(outer[299] ?? "none");
+            print(len(kept));
+            print(depth(3));
+            print(depth(9));
+            outer2: for i in 0..4 {
+                arena {
+                    defer print(`leave ${i}`);
+                    var xs: int[] = [i];
+                    if i == 1 { continue outer2; }
+                    if i == 3 { break outer2; }
+                    print(xs);
+                }
+            }
+            return t.n % 7;
+        }"#,
+    );
+}
+
+#[test]
+fn sorting_an_outer_array_inside_an_arena_keeps_its_buffer() {
+    // The sorted elements go back into the array's own buffer, not one
+    // from the arena; the next arena's allocations would overwrite it.
+    diff(
+        "arena_sort",
+        r#"struct P { k: int, name: string }
+        fun main(): int {
+            var xs: int[] = [];
+            for i in 0..2000 { push(xs, 2000 - i); }
+            var ps: P[] = [];
+            for i in 0..50 { push(ps, P { k: (i * 7) % 50, name: `p${i}` }); }
+            var ws: string[] = ["pear", "fig", "apple"];
+            arena {
+                sort(xs);
+                sort(ps, fun (p: P): int { return p.k; });
+                sort(ws);
+            }
+            arena {
+                var junk: int[] = [];
+                for i in 0..5000 { push(junk, -7); }
+                var names: string[] = [];
+                for i in 0..50 { push(names, `junk${i}`); }
+            }
+            var sum: int = 0;
+            for x in xs { sum = sum + x; }
+            print(sum);
+            print(xs[0]);
+            print(xs[1999]);
+            print(ps[0]);
+            print(ps[49]);
+            print(ws);
+            return xs[0];
+        }"#,
+    );
+}
+
+#[test]
+fn computed_containers_inside_an_arena_agree() {
+    // Storing into a call's result, a `??`, or a `match` is judged by
+    // what the container reaches: outer ones take only values built
+    // before the arena, inner ones take anything (the escaping forms
+    // are checker errors, so both engines never see them).
+    diff(
+        "arena_containers",
+        r#"refstruct N { v: int }
+        refstruct H { xs: N[] }
+        impl H { fun items(self): N[] { return self.xs; } }
+        fun id(x: N[]): N[] { return x; }
+        fun main(): int {
+            var out: N[] = [];
+            var o: N[]? = null;
+            var m: map<int, N> = {};
+            var mo: map<int, N>? = null;
+            const h: H = H { xs: [] };
+            const kept: N = N { v: 5 };
+            for k in 0..3 {
+                arena {
+                    push(id(out), kept);
+                    push(o ?? out, kept);
+                    push(match k { 0 => out, else => out }, kept);
+                    push(h.items(), kept);
+                    insert(mo ?? m, k, kept);
+                    var inner: N[] = [];
+                    push(id(inner), N { v: k });
+                    push(match k { 0 => inner, else => inner }, N { v: k + 1 });
+                    print(len(inner));
+                }
+            }
+            var sum: int = 0;
+            for n in out { sum = sum + n.v; }
+            print(sum);
+            print(len(h.xs));
+            print(len(m));
+            return sum;
+        }"#,
+    );
+}
+
+#[test]
+fn outer_handles_stored_into_inner_places_inside_an_arena_agree() {
+    // An inner container or field given an outer handle aliases outer
+    // memory for the whole arena, loop back edges included: writes
+    // through it may store only what outlives the arena (the escaping
+    // forms are checker errors).
+    diff(
+        "arena_aliases",
+        r#"refstruct Node { v: int, next: Node? }
+        refstruct Box { n: Node? }
+        refstruct H { b: Box? }
+        fun main(): int {
+            const b: Box = Box { n: null };
+            const kept: Node = Node { v: 3, next: null };
+            arena {
+                const xs: Box[] = [];
+                push(xs, b);
+                for y in xs { y.n = kept; }
+                const h: H = H { b: null };
+                h.b = b;
+                if const bb = h.b { bb.n = kept; }
+                var x: Box = Box { n: null };
+                var i: int = 0;
+                while i < 2 { x.n = kept; x = b; i = i + 1; }
+                for y in [Box { n: null }] { y.n = Node { v: 9, next: null }; print(y.n); }
+            }
+            arena {
+                var junk: Node[] = [];
+                for i in 0..200 { push(junk, Node { v: 1000 + i, next: null }); }
+            }
+            print(b.n);
+            if const n = b.n { return n.v; }
+            return 0;
+        }"#,
+    );
+}
+
+// --- Error payloads (ADR 0062) ---
+
+#[test]
+fn error_payloads_agree() {
+    diff(
+        "error_payloads",
+        r#"enum Why { Eof, Char(string) }
+        error NotFound, ParseFailed(int, Why), Range(int, int, int, string);
+        struct Big { a: int, b: int, c: int, d: int, e: int }
+        fun parse(n: int): int! {
+            if n < 0 { return error.ParseFailed(-n, Why.Char(`${n}`)); }
+            if n == 0 { return error.NotFound; }
+            if n > 100 { return error.Range(0, 100, n, "high"); }
+            return n * 2;
+        }
+        fun twice(n: int): int! {
+            const v: int = try parse(n);
+            return v + 1;
+        }
+        fun big(n: int): Big! {
+            if n > 0 { return Big { a: n, b: n, c: n, d: n, e: n }; }
+            return error.ParseFailed(n, Why.Eof());
+        }
+        fun describe(e: error): string {
+            return match e {
+                error.NotFound => "missing",
+                error.ParseFailed(line, Char(c)) if line > 5 => `late ${c}`,
+                error.ParseFailed(line, Char(_)) => `char at ${line}`,
+                error.ParseFailed(_, Eof()) => "eof",
+                error.Range(lo, hi, got, _) => `${got} not in ${lo}..${hi}`,
+                else => "other",
+            };
+        }
+        fun main(): int {
+            var score: int = 0;
+            for n in [3, 0, -2, -9, 500] {
+                const r: int! = twice(n);
+                print(r);
+                if r == error {
+                    const e: error = r;
+                    print(describe(e));
+                    if e == error.ParseFailed { score = score + 10; }
+                } else {
+                    score = score + r;
+                }
+            }
+            print(big(2));
+            print(big(-1));
+            var es: error[] = [error.NotFound, error.Range(1, 2, 3, "x")];
+            push(es, error.ParseFailed(4, Why.Eof()));
+            print(es);
+            print(es[1] == error.Range(9, 9, 9, "other"));
+            var seen: map<error, int> = {};
+            for e in es { insert(seen, e, len(seen)); }
+            print(len(seen));
+            const o: error? = error.Range(5, 6, 7, "o");
+            print(o);
+            print(`${es[2]}!`);
+            return score;
+        }"#,
+    );
+}
+
+#[test]
+fn optional_string_payloads_quote_by_their_declared_type() {
+    // A `string?` payload is quoted when it holds a string and prints
+    // `null` bare otherwise, in both engines.
+    diff(
+        "error_payloads_optional",
+        r#"error E(string?, int), F(int?, string);
+        fun pick(n: int): string? {
+            if n > 0 { return `v${n}`; }
+            return null;
+        }
+        fun main(): int {
+            print(error.E("hi", 1));
+            print(error.E(null, 2));
+            print(error.E(pick(3), 3));
+            print(error.F(null, "s"));
+            print(error.F(4, "t"));
+            var es: error[] = [error.E(pick(0), 5), error.E(pick(6), 6)];
+            print(es);
+            print(`${es[1]}!`);
+            return 0;
+        }"#,
+    );
+}
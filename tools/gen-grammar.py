@@ -24,9 +24,9 @@ GRAMMAR = ROOT / "editors" / "vscode" / "syntaxes" / "ys.tmLanguage.json"
 # How each keyword spelling maps to a TextMate scope. Every KW_ in syntax.rs
 # must appear in exactly one bucket (checked below).
 CATEGORIES = {
-    "storage.type.ys": ["fun", "struct", "refstruct", "enum", "var", "const"],
-    "keyword.control.ys": ["return", "break", "continue", "if", "else", "while", "for", "in", "import", "export", "from", "try", "match"],
-    "support.type.primitive.ys": ["int", "float", "bool", "string", "file", "error"],
+    "storage.type.ys": ["fun", "struct", "refstruct", "enum", "impl", "interface", "var", "const"],
+    "keyword.control.ys": ["return", "break", "continue", "if", "else", "while", "for", "in", "import", "export", "from", "try", "match", "defer", "arena"],
+    "support.type.primitive.ys": ["int", "float", "bool", "string", "file", "error", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "byte"],
     "constant.language.ys": ["true", "false", "null"],
 }
 
@@ -142,7 +142,7 @@ def build_grammar():
             },
             "operators": {
                 "name": "keyword.operator.ys",
-                "match": r"\?\?|\?\.|==|!=|<=|>=|&&|\|\||[-+*/%=<>!?]",
+                "match": r"\.\.=|\.\.|\?\?=|>>>=|<<=|>>=|\+\+|--|[-+*/%&|^]=|\?\?|\?\.|==|=>|!=|<=|>=|&&|\|\||>>>|<<|>>|[-+*/%=<>!?&|^~]",
             },
         },
     }